@@ -0,0 +1,107 @@
+//! # Benchmarks for face triangulation
+//!
+//! Run with `cargo bench -p fj-core --bench triangulate`.
+//!
+//! Measures how triangulation scales with the number of boundary points and
+//! holes, using faces that resemble complex profiles, like text engravings.
+
+use std::time::{Duration, Instant};
+
+use fj_core::{
+    algorithms::{
+        approx::{Approx, Tolerance},
+        triangulate::Triangulate,
+    },
+    objects::{Cycle, Face},
+    operations::{
+        build::{BuildCycle, BuildFace},
+        update::{UpdateFace, UpdateRegion},
+    },
+    Core,
+};
+use fj_math::{Point, Scalar};
+
+fn main() -> anyhow::Result<()> {
+    println!("boundary points | holes | triangles | time per triangulation");
+
+    for (num_boundary_points, num_holes_per_row) in [
+        (100, 2),
+        (1_000, 5),
+        (1_000, 10),
+        (10_000, 10),
+        (10_000, 20),
+    ] {
+        let mut core = Core::new();
+        let face = face(num_boundary_points, num_holes_per_row, &mut core);
+
+        let tolerance = Tolerance::from_scalar(1.)?;
+        let num_iterations = 5;
+
+        let mut num_triangles = 0;
+        let mut total = Duration::ZERO;
+
+        for _ in 0..num_iterations {
+            let start = Instant::now();
+            let mesh = face.approx(tolerance, &mut core).triangulate(&mut core);
+            total += start.elapsed();
+
+            num_triangles = mesh.triangles().count();
+        }
+
+        println!(
+            "{num_boundary_points:>15} | {:>5} | {num_triangles:>9} | {:?}",
+            num_holes_per_row * num_holes_per_row,
+            total / num_iterations,
+        );
+    }
+
+    Ok(())
+}
+
+/// Build a face with a star-shaped exterior and a grid of square holes
+fn face(
+    num_boundary_points: u32,
+    num_holes_per_row: u32,
+    core: &mut Core,
+) -> Face {
+    let radius = Scalar::from(f64::from(num_holes_per_row) * 4.);
+
+    let exterior = (0..num_boundary_points)
+        .map(|i| {
+            let angle =
+                Scalar::TAU / f64::from(num_boundary_points) * f64::from(i);
+            let radius = if i % 2 == 0 { radius } else { radius * 0.95 };
+
+            let (sin, cos) = angle.sin_cos();
+            Point::from([cos * radius, sin * radius])
+        })
+        .collect::<Vec<_>>();
+
+    let size = radius.into_f64() / 2.;
+    let step = size / f64::from(num_holes_per_row);
+
+    let mut holes = Vec::new();
+    for i in 0..num_holes_per_row {
+        for j in 0..num_holes_per_row {
+            let [x, y] = [i, j].map(|k| -size / 2. + step * f64::from(k));
+            let d = step / 2.;
+
+            holes.push([[x, y], [x, y + d], [x + d, y + d], [x + d, y]]);
+        }
+    }
+
+    let surface = core.layers.objects.surfaces.xy_plane();
+    Face::unbound(surface, core).update_region(
+        |region, core| {
+            let interiors = holes
+                .into_iter()
+                .map(|hole| Cycle::polygon(hole, core))
+                .collect::<Vec<_>>();
+
+            region
+                .update_exterior(|_, core| Cycle::polygon(exterior, core), core)
+                .add_interiors(interiors, core)
+        },
+        core,
+    )
+}
@@ -2,7 +2,7 @@
 
 use std::collections::BTreeMap;
 
-use fj_math::Point;
+use fj_math::{NurbsSurface, Point, Scalar};
 
 use crate::{
     geometry::{CurveBoundary, GlobalPath, SurfaceGeometry, SurfacePath},
@@ -31,8 +31,9 @@ impl Approx
         core: &mut Core,
     ) -> Self::Approximation {
         let (curve, surface_path, surface, boundary) = self;
+        let max_distance = core.layers.validation.config.identical_max_distance;
 
-        match cache.get(curve, boundary) {
+        match cache.get(curve, boundary, max_distance) {
             Some(approx) => approx,
             None => {
                 let approx = approx_curve(
@@ -43,7 +44,7 @@ impl Approx
                     core,
                 );
 
-                cache.insert(curve.clone(), boundary, approx)
+                cache.insert(curve.clone(), boundary, approx, max_distance)
             }
         }
     }
@@ -56,19 +57,69 @@ fn approx_curve(
     tolerance: impl Into<Tolerance>,
     core: &mut Core,
 ) -> CurveApprox {
-    // There are different cases of varying complexity. Circles are the hard
-    // part here, as they need to be approximated, while lines don't need to be.
+    // There are different cases of varying complexity. Circles and ellipses
+    // are the hard part here, as they need to be approximated, while lines
+    // don't need to be.
     //
     // This will probably all be unified eventually, as `SurfacePath` and
     // `GlobalPath` grow APIs that are better suited to implementing this code
     // in a more abstract way.
-    let points = match (path, surface.u) {
-        (SurfacePath::Circle(_), GlobalPath::Circle(_)) => {
+    let points = match (path, surface) {
+        (_, SurfaceGeometry::Nurbs(nurbs)) => {
+            approx_path_on_nurbs(path, nurbs, boundary, tolerance.into())
+        }
+        (
+            SurfacePath::Circle(_),
+            SurfaceGeometry::Swept {
+                u:
+                    GlobalPath::Circle(_)
+                    | GlobalPath::CubicBezier(_)
+                    | GlobalPath::Ellipse(_),
+                ..
+            },
+        ) => {
             todo!(
                 "Approximating a circle on a curved surface not supported yet."
             )
         }
-        (SurfacePath::Circle(_), GlobalPath::Line(_)) => {
+        (
+            SurfacePath::Ellipse(_),
+            SurfaceGeometry::Swept {
+                u:
+                    GlobalPath::Circle(_)
+                    | GlobalPath::CubicBezier(_)
+                    | GlobalPath::Ellipse(_),
+                ..
+            },
+        ) => {
+            todo!(
+                "Approximating an ellipse on a curved surface not supported yet."
+            )
+        }
+        (
+            SurfacePath::CubicBezier(_),
+            SurfaceGeometry::Swept {
+                u:
+                    GlobalPath::Circle(_)
+                    | GlobalPath::CubicBezier(_)
+                    | GlobalPath::Ellipse(_),
+                ..
+            },
+        ) => {
+            todo!(
+                "Approximating a Bézier curve on a curved surface not \
+                supported yet."
+            )
+        }
+        (
+            SurfacePath::Circle(_)
+            | SurfacePath::CubicBezier(_)
+            | SurfacePath::Ellipse(_),
+            SurfaceGeometry::Swept {
+                u: GlobalPath::Line(_),
+                ..
+            },
+        ) => {
             (path, boundary)
                 .approx_with_cache(tolerance, &mut (), core)
                 .into_iter()
@@ -95,17 +146,14 @@ fn approx_curve(
                 })
                 .collect()
         }
-        (SurfacePath::Line(line), _) => {
+        (SurfacePath::Line(line), SurfaceGeometry::Swept { u, .. }) => {
             let range_u =
                 CurveBoundary::from(boundary.inner.map(|point_curve| {
                     [path.point_from_path_coords(point_curve).u]
                 }));
 
-            let approx_u = (surface.u, range_u).approx_with_cache(
-                tolerance,
-                &mut (),
-                core,
-            );
+            let approx_u =
+                (*u, range_u).approx_with_cache(tolerance, &mut (), core);
 
             let mut points = Vec::new();
             for (u, _) in approx_u {
@@ -113,7 +161,7 @@ fn approx_curve(
                 let point_surface = path.point_from_path_coords([t]);
                 let point_global =
                     surface.point_from_surface_coords(point_surface);
-                points.push((u, point_global));
+                points.push((Point::from([t]), point_global));
             }
 
             points
@@ -129,6 +177,57 @@ fn approx_curve(
     CurveApprox { points }
 }
 
+/// Approximate a path on a NURBS surface
+///
+/// The curvature of the surface isn't known upfront, so the path is
+/// subdivided into ever more segments, until the middle of each segment is
+/// within the tolerance of the actual curve on the surface.
+fn approx_path_on_nurbs(
+    path: &SurfacePath,
+    surface: &NurbsSurface,
+    boundary: CurveBoundary<Point<1>>,
+    tolerance: Tolerance,
+) -> Vec<(Point<1>, Point<3>)> {
+    // Limit the number of segments, in case the tolerance can't be reached
+    // for some reason.
+    const MAX_SEGMENTS: usize = 256;
+
+    let [a, b] = boundary.inner;
+    let point_at = |i: usize, n: usize| {
+        let point_curve = a + (b - a) * (i as f64 / n as f64);
+        let point_surface = path.point_from_path_coords(point_curve);
+        (
+            point_curve,
+            surface.point_from_surface_coords(point_surface),
+        )
+    };
+
+    let mut num_segments = 1;
+    let points = loop {
+        let points = (0..=num_segments)
+            .map(|i| point_at(i, num_segments))
+            .collect::<Vec<_>>();
+
+        let is_within_tolerance =
+            points.windows(2).enumerate().all(|(i, w)| {
+                let [(_, start), (_, end)] = [w[0], w[1]];
+                let (_, middle) = point_at(2 * i + 1, 2 * num_segments);
+
+                let chord_middle = start + (end - start) / 2.;
+                (middle - chord_middle).magnitude() <= tolerance.inner()
+            });
+
+        if is_within_tolerance || num_segments >= MAX_SEGMENTS {
+            break points;
+        }
+
+        num_segments *= 2;
+    };
+
+    // The boundary points are not part of the approximation.
+    points[1..points.len() - 1].to_vec()
+}
+
 /// Approximation of [`Curve`], within a specific boundary
 #[derive(Clone)]
 pub struct CurveApprox {
@@ -137,17 +236,58 @@ pub struct CurveApprox {
 }
 
 impl CurveApprox {
-    fn reverse(mut self) -> Self {
+    /// Access the points, with their positions on the curve's surface
+    ///
+    /// The provided path must be the one that the curve was approximated with.
+    /// It converts the curve coordinates of each point into surface
+    /// coordinates.
+    pub fn points_on_path(
+        &self,
+        path: SurfacePath,
+    ) -> impl Iterator<Item = CurveApproxPoint> + '_ {
+        self.points.iter().map(move |point| CurveApproxPoint {
+            curve: point.local_form,
+            surface: path.point_from_path_coords(point.local_form),
+            global: point.global_form,
+        })
+    }
+
+    pub(super) fn reverse(mut self) -> Self {
         self.points.reverse();
         self
     }
 }
 
+/// A point from a curve approximation, in all of its coordinate systems
+///
+/// This allows callers to refine or re-sample an approximation along the
+/// curve, without having to approximate the curve themselves.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct CurveApproxPoint {
+    /// The position of the point on the curve
+    pub curve: Point<1>,
+
+    /// The position of the point on the surface
+    pub surface: Point<2>,
+
+    /// The position of the point in global coordinates
+    pub global: Point<3>,
+}
+
 /// Cache for curve approximations
+///
+/// The half-edges that share a curve might define their boundaries on it
+/// slightly differently, due to floating-point error. Boundaries that are
+/// within [`identical_max_distance`] of a cached one are snapped to it, so all
+/// of those half-edges end up with the same approximation.
+///
+/// [`identical_max_distance`]: crate::validation::ValidationConfig::identical_max_distance
 #[derive(Default)]
 pub struct CurveApproxCache {
-    inner:
-        BTreeMap<(HandleWrapper<Curve>, CurveBoundary<Point<1>>), CurveApprox>,
+    inner: BTreeMap<
+        HandleWrapper<Curve>,
+        Vec<(CurveBoundary<Point<1>>, CurveApprox)>,
+    >,
 }
 
 impl CurveApproxCache {
@@ -155,14 +295,17 @@ impl CurveApproxCache {
         &self,
         handle: &Handle<Curve>,
         boundary: CurveBoundary<Point<1>>,
+        max_distance: Scalar,
     ) -> Option<CurveApprox> {
         let handle = HandleWrapper::from(handle.clone());
 
-        if let Some(approx) = self.inner.get(&(handle.clone(), boundary)) {
-            return Some(approx.clone());
-        }
-        if let Some(approx) = self.inner.get(&(handle, boundary.reverse())) {
-            return Some(approx.clone().reverse());
+        for (cached, approx) in self.inner.get(&handle)? {
+            if is_same_boundary(*cached, boundary, max_distance) {
+                return Some(approx.clone());
+            }
+            if is_same_boundary(*cached, boundary.reverse(), max_distance) {
+                return Some(approx.clone().reverse());
+            }
         }
 
         None
@@ -173,22 +316,42 @@ impl CurveApproxCache {
         handle: Handle<Curve>,
         boundary: CurveBoundary<Point<1>>,
         approx: CurveApprox,
+        max_distance: Scalar,
     ) -> CurveApprox {
+        if let Some(cached) = self.get(&handle, boundary, max_distance) {
+            return cached;
+        }
+
         let handle = HandleWrapper::from(handle);
         self.inner
-            .insert((handle, boundary), approx.clone())
-            .unwrap_or(approx)
+            .entry(handle)
+            .or_default()
+            .push((boundary, approx.clone()));
+
+        approx
     }
 }
 
+fn is_same_boundary(
+    a: CurveBoundary<Point<1>>,
+    b: CurveBoundary<Point<1>>,
+    max_distance: Scalar,
+) -> bool {
+    a.inner
+        .into_iter()
+        .zip(b.inner)
+        .all(|(a, b)| (a.t - b.t).abs() <= max_distance)
+}
+
 #[cfg(test)]
 mod tests {
     use std::f64::consts::TAU;
 
+    use fj_math::{NurbsSurface, Scalar};
     use pretty_assertions::assert_eq;
 
     use crate::{
-        algorithms::approx::{Approx, ApproxPoint},
+        algorithms::approx::{curve::CurveApproxCache, Approx, ApproxPoint},
         geometry::{CurveBoundary, GlobalPath, SurfaceGeometry, SurfacePath},
         objects::Curve,
         operations::insert::Insert,
@@ -203,7 +366,7 @@ mod tests {
         let (surface_path, boundary) =
             SurfacePath::line_from_points([[1., 1.], [2., 1.]]);
         let boundary = CurveBoundary::from(boundary);
-        let surface = core.layers.geometry.xz_plane();
+        let surface = core.layers.geometry.xz_plane().clone();
 
         let tolerance = 1.;
         let approx = (&curve, surface_path, &surface, boundary)
@@ -220,7 +383,7 @@ mod tests {
         let (surface_path, boundary) =
             SurfacePath::line_from_points([[1., 1.], [2., 1.]]);
         let boundary = CurveBoundary::from(boundary);
-        let surface = SurfaceGeometry {
+        let surface = SurfaceGeometry::Swept {
             u: GlobalPath::circle_from_radius(1.),
             v: [0., 0., 1.].into(),
         };
@@ -243,7 +406,7 @@ mod tests {
             ([TAU], [TAU, 1.]),
         ]);
         let boundary = CurveBoundary::from([[0.], [TAU]]);
-        let surface = SurfaceGeometry {
+        let surface = SurfaceGeometry::Swept {
             u: global_path,
             v: [0., 0., 1.].into(),
         };
@@ -274,7 +437,7 @@ mod tests {
         let surface_path =
             SurfacePath::circle_from_center_and_radius([0., 0.], 1.);
         let boundary = CurveBoundary::from([[0.], [TAU]]);
-        let surface = core.layers.geometry.xz_plane();
+        let surface = core.layers.geometry.xz_plane().clone();
 
         let tolerance = 1.;
         let approx = (&curve, surface_path, &surface, boundary)
@@ -293,4 +456,77 @@ mod tests {
             .collect::<Vec<_>>();
         assert_eq!(approx.points, expected_approx);
     }
+
+    #[test]
+    fn approx_with_nearly_identical_boundaries() {
+        let mut core = Core::new();
+
+        // On a curved NURBS surface, the points of the approximation depend
+        // on the exact boundary.
+        let curve = Curve::new().insert(&mut core);
+        let (surface_path, _) =
+            SurfacePath::line_from_points([[0., 0.5], [1., 0.5]]);
+        let surface = SurfaceGeometry::Nurbs(NurbsSurface::clamped(
+            [0., 0.5, 1.].map(|u| {
+                [0., 0.5, 1.].map(|v| {
+                    let z = if u == 0.5 && v == 0.5 { 1. } else { 0. };
+                    [u, v, z]
+                })
+            }),
+            [2, 2],
+        ));
+
+        let tolerance = 0.01;
+        let mut cache = CurveApproxCache::default();
+
+        let boundary = CurveBoundary::from([[0.], [1.]]);
+        let approx = (&curve, surface_path, &surface, boundary)
+            .approx_with_cache(tolerance, &mut cache, &mut core);
+
+        // Boundaries that differ only by floating-point error result in the
+        // same approximation, regardless of direction.
+        let jitter = core.layers.validation.config.identical_max_distance / 5.;
+        let jittered = CurveBoundary::from([[jitter], [Scalar::ONE - jitter]]);
+        let jittered_approx = (&curve, surface_path, &surface, jittered)
+            .approx_with_cache(tolerance, &mut cache, &mut core);
+        assert_eq!(jittered_approx.points, approx.points);
+
+        let reversed_approx =
+            (&curve, surface_path, &surface, jittered.reverse())
+                .approx_with_cache(tolerance, &mut cache, &mut core);
+        let mut expected = approx.points.clone();
+        expected.reverse();
+        assert_eq!(reversed_approx.points, expected);
+
+        // A different boundary still results in a different approximation.
+        let different = CurveBoundary::from([[0.], [0.5]]);
+        let different_approx = (&curve, surface_path, &surface, different)
+            .approx_with_cache(tolerance, &mut cache, &mut core);
+        assert_ne!(different_approx.points, approx.points);
+    }
+
+    #[test]
+    fn points_on_path() {
+        let mut core = Core::new();
+
+        let curve = Curve::new().insert(&mut core);
+        let surface_path =
+            SurfacePath::circle_from_center_and_radius([1., 2.], 1.);
+        let boundary = CurveBoundary::from([[0.], [TAU]]);
+        let surface = core.layers.geometry.xz_plane().clone();
+
+        let approx =
+            (&curve, surface_path, &surface, boundary).approx(0.1, &mut core);
+        let points = approx.points_on_path(surface_path).collect::<Vec<_>>();
+
+        assert_eq!(points.len(), approx.points.len());
+        for (point, approx_point) in points.into_iter().zip(&approx.points) {
+            assert_eq!(point.curve, approx_point.local_form);
+            assert_eq!(point.global, approx_point.global_form);
+            assert_eq!(
+                surface.point_from_surface_coords(point.surface),
+                point.global,
+            );
+        }
+    }
 }
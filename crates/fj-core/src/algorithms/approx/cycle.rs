@@ -28,9 +28,9 @@ impl Approx for (&Cycle, &SurfaceGeometry) {
 
         let half_edges = cycle
             .half_edges()
-            .iter()
-            .map(|edge| {
-                (edge.deref(), surface)
+            .pairs()
+            .map(|(edge, next)| {
+                (edge.deref(), next.start_vertex(), surface)
                     .approx_with_cache(tolerance, cache, core)
             })
             .collect();
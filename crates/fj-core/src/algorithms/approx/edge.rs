@@ -5,14 +5,29 @@
 //! approximations are usually used to build cycle approximations, and this way,
 //! the caller doesn't have to deal with duplicate vertices.
 
-use crate::{geometry::SurfaceGeometry, objects::HalfEdge, Core};
+use std::collections::BTreeMap;
+
+use fj_math::Point;
+
+use crate::{
+    geometry::{CurveBoundary, SurfaceGeometry},
+    objects::{Curve, HalfEdge, Vertex},
+    storage::{Handle, HandleWrapper},
+    Core,
+};
 
 use super::{
-    curve::CurveApproxCache, vertex::VertexApproxCache, Approx, ApproxPoint,
-    Tolerance,
+    curve::{CurveApprox, CurveApproxCache, CurveApproxPoint},
+    vertex::VertexApproxCache,
+    Approx, ApproxPoint, Tolerance,
 };
 
-impl Approx for (&HalfEdge, &SurfaceGeometry) {
+/// Approximate a half-edge
+///
+/// The [`Vertex`] is where the half-edge ends, which is the start vertex of the
+/// next half-edge in its cycle. Together with its curve and its start vertex,
+/// it identifies the edge that the half-edge shares with its sibling.
+impl Approx for (&HalfEdge, &Handle<Vertex>, &SurfaceGeometry) {
     type Approximation = HalfEdgeApprox;
     type Cache = HalfEdgeApproxCache;
 
@@ -22,7 +37,7 @@ impl Approx for (&HalfEdge, &SurfaceGeometry) {
         cache: &mut Self::Cache,
         core: &mut Core,
     ) -> Self::Approximation {
-        let (edge, surface) = self;
+        let (edge, end_vertex, surface) = self;
         let tolerance = tolerance.into();
 
         let start_position_surface = edge.start_position();
@@ -39,23 +54,55 @@ impl Approx for (&HalfEdge, &SurfaceGeometry) {
         };
 
         let first = ApproxPoint::new(start_position_surface, start_position);
+        let first_curve = edge.boundary().inner[0];
 
         let rest = {
-            let approx = (edge.curve(), edge.path(), surface, edge.boundary())
-                .approx_with_cache(tolerance, &mut cache.curve, core);
+            // Whichever of the sibling half-edges is approximated first
+            // defines the approximation of their shared edge. Otherwise, the
+            // faces they bound could end up with slightly different points
+            // along that edge, leaving cracks in the mesh.
+            let vertices = CurveBoundary::from([
+                edge.start_vertex().clone(),
+                end_vertex.clone(),
+            ]);
+            let approx = match cache.edge.get(
+                edge.curve(),
+                &vertices,
+                edge.boundary(),
+            ) {
+                Some(approx) => approx,
+                None => {
+                    let approx =
+                        (edge.curve(), edge.path(), surface, edge.boundary())
+                            .approx_with_cache(
+                                tolerance,
+                                &mut cache.curve,
+                                core,
+                            );
 
-            approx.points.into_iter().map(|point| {
-                let point_surface =
-                    edge.path().point_from_path_coords(point.local_form);
+                    cache.edge.insert(
+                        edge.curve().clone(),
+                        vertices,
+                        edge.boundary(),
+                        approx,
+                    )
+                }
+            };
 
-                ApproxPoint::new(point_surface, point.global_form)
-            })
+            approx.points_on_path(edge.path()).collect::<Vec<_>>()
         };
 
         let mut points = vec![first];
-        points.extend(rest);
+        let mut curve_coords = vec![first_curve];
+        for point in rest {
+            points.push(ApproxPoint::new(point.surface, point.global));
+            curve_coords.push(point.curve);
+        }
 
-        HalfEdgeApprox { points }
+        HalfEdgeApprox {
+            points,
+            curve_coords,
+        }
     }
 }
 
@@ -64,11 +111,257 @@ impl Approx for (&HalfEdge, &SurfaceGeometry) {
 pub struct HalfEdgeApprox {
     /// The points that approximate the half-edge
     pub points: Vec<ApproxPoint<2>>,
+
+    curve_coords: Vec<Point<1>>,
+}
+
+impl HalfEdgeApprox {
+    /// Access the points, with their positions on the half-edge's curve
+    ///
+    /// Returns the same points as [`HalfEdgeApprox::points`], in the same
+    /// order.
+    pub fn curve_points(&self) -> impl Iterator<Item = CurveApproxPoint> + '_ {
+        self.points
+            .iter()
+            .zip(&self.curve_coords)
+            .map(|(point, curve)| CurveApproxPoint {
+                curve: *curve,
+                surface: point.local_form,
+                global: point.global_form,
+            })
+    }
 }
 
 /// Cache for half-edge approximations
 #[derive(Default)]
 pub struct HalfEdgeApproxCache {
     start_position: VertexApproxCache,
+    edge: EdgeApproxCache,
     curve: CurveApproxCache,
 }
+
+/// Cache for the approximations of edges that sibling half-edges share
+///
+/// An edge is identified by its curve and its bounding vertices. The same
+/// curve could be bounded by the same vertices more than once (a circle that
+/// is split into two arcs, for example), so the curve boundary is used to tell
+/// those edges apart.
+#[derive(Default)]
+struct EdgeApproxCache {
+    inner: BTreeMap<
+        (HandleWrapper<Curve>, CurveBoundary<Vertex>),
+        Vec<CachedEdge>,
+    >,
+}
+
+struct CachedEdge {
+    vertices: CurveBoundary<Vertex>,
+    boundary: CurveBoundary<Point<1>>,
+    approx: CurveApprox,
+}
+
+impl EdgeApproxCache {
+    fn get(
+        &self,
+        curve: &Handle<Curve>,
+        vertices: &CurveBoundary<Vertex>,
+        boundary: CurveBoundary<Point<1>>,
+    ) -> Option<CurveApprox> {
+        let key = (
+            HandleWrapper::from(curve.clone()),
+            vertices.clone().normalize(),
+        );
+
+        for cached in self.inner.get(&key)? {
+            // The boundaries of sibling half-edges might not be exactly the
+            // same, but they cover the same part of the curve.
+            if cached.boundary.intersection(boundary).is_empty() {
+                continue;
+            }
+
+            // If the edge starts and ends at the same vertex, only the
+            // direction of the boundary tells us which way it goes.
+            let same_direction = if vertices.inner[0] == vertices.inner[1] {
+                cached.boundary.is_normalized() == boundary.is_normalized()
+            } else {
+                &cached.vertices == vertices
+            };
+
+            return Some(if same_direction {
+                cached.approx.clone()
+            } else {
+                cached.approx.clone().reverse()
+            });
+        }
+
+        None
+    }
+
+    fn insert(
+        &mut self,
+        curve: Handle<Curve>,
+        vertices: CurveBoundary<Vertex>,
+        boundary: CurveBoundary<Point<1>>,
+        approx: CurveApprox,
+    ) -> CurveApprox {
+        let key = (HandleWrapper::from(curve), vertices.clone().normalize());
+        self.inner.entry(key).or_default().push(CachedEdge {
+            vertices,
+            boundary,
+            approx: approx.clone(),
+        });
+
+        approx
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use fj_math::NurbsSurface;
+
+    use crate::{
+        algorithms::approx::{Approx, ApproxPoint},
+        geometry::{SurfaceGeometry, SurfacePath},
+        objects::{Curve, HalfEdge, Region, Sketch, Vertex},
+        operations::{
+            build::{BuildRegion, BuildSketch},
+            insert::Insert,
+            sweep::SweepSketch,
+            update::UpdateSketch,
+        },
+        queries::SiblingOfHalfEdge,
+        storage::HandleWrapper,
+        Core,
+    };
+
+    use super::{HalfEdgeApprox, HalfEdgeApproxCache};
+
+    #[test]
+    fn siblings_share_approximation_within_shell() {
+        let mut core = Core::new();
+
+        let surface = core.layers.objects.surfaces.xy_plane();
+        let cylinder = Sketch::empty()
+            .add_regions([Region::circle([0., 0.], 1., &mut core)], &mut core)
+            .sweep_sketch(surface, [0., 0., 1.], &mut core);
+        let shell = cylinder.shells().only();
+
+        let tolerance = 0.01;
+        let mut cache = HalfEdgeApproxCache::default();
+        let mut approxs = BTreeMap::new();
+
+        // Approximating the shell a second time, when all edges are already
+        // cached, must not change anything.
+        for _ in 0..2 {
+            for face in shell.faces() {
+                for cycle in face.region().all_cycles() {
+                    for (half_edge, next) in cycle.half_edges().pairs() {
+                        let points = (
+                            &**half_edge,
+                            next.start_vertex(),
+                            face.surface().geometry(),
+                        )
+                            .approx_with_cache(tolerance, &mut cache, &mut core)
+                            .points
+                            .into_iter()
+                            .map(|point| point.global_form)
+                            .collect::<Vec<_>>();
+
+                        let key = HandleWrapper::from(half_edge.clone());
+                        if let Some(previous) =
+                            approxs.insert(key, points.clone())
+                        {
+                            assert_eq!(previous, points);
+                        }
+                    }
+                }
+            }
+        }
+
+        for (half_edge, points) in &approxs {
+            let sibling = shell
+                .get_sibling_of(half_edge)
+                .expect("Expected every half-edge to have a sibling");
+            let sibling_points = &approxs[&HandleWrapper::from(sibling)];
+
+            // The approximation of a half-edge leaves out its end, which is
+            // the start of its sibling.
+            let mut a = points.clone();
+            a.push(sibling_points[0]);
+            let mut b = sibling_points.clone();
+            b.push(points[0]);
+            b.reverse();
+
+            assert_eq!(a, b);
+        }
+    }
+
+    #[test]
+    fn siblings_share_approximation_despite_boundary_mismatch() {
+        let mut core = Core::new();
+
+        // On a curved NURBS surface, the points of the approximation depend
+        // on the exact boundary.
+        let surface = SurfaceGeometry::Nurbs(NurbsSurface::clamped(
+            [0., 0.5, 1.].map(|u| {
+                [0., 0.5, 1.].map(|v| {
+                    let z = if u == 0.5 && v == 0.5 { 1. } else { 0. };
+                    [u, v, z]
+                })
+            }),
+            [2, 2],
+        ));
+        let (path, _) = SurfacePath::line_from_points([[0., 0.5], [1., 0.5]]);
+
+        let curve = Curve::new().insert(&mut core);
+        let [a, b] =
+            [Vertex::new(), Vertex::new()].map(|v| v.insert(&mut core));
+
+        // The boundary of the sibling is off by more than the curve
+        // approximation cache would snap.
+        let half_edge =
+            HalfEdge::new(path, [[0.], [1.]], curve.clone(), a.clone());
+        let sibling =
+            HalfEdge::new(path, [[1. - 1e-6], [1e-6]], curve, b.clone());
+
+        let tolerance = 0.01;
+        let mut cache = HalfEdgeApproxCache::default();
+
+        let approx = (&half_edge, &b, &surface)
+            .approx_with_cache(tolerance, &mut cache, &mut core);
+        let sibling_approx = (&sibling, &a, &surface)
+            .approx_with_cache(tolerance, &mut cache, &mut core);
+
+        // The start of each approximation is the start vertex, which differs
+        // between the siblings.
+        let global = |points: &[ApproxPoint<2>]| {
+            points[1..]
+                .iter()
+                .map(|point| point.global_form)
+                .collect::<Vec<_>>()
+        };
+        let mut expected = global(&approx.points);
+        expected.reverse();
+        assert!(!expected.is_empty());
+        assert_eq!(global(&sibling_approx.points), expected);
+
+        // Both siblings share the curve, so the points they share are at the
+        // same position on it.
+        let curve = |approx: &HalfEdgeApprox| {
+            approx
+                .curve_points()
+                .skip(1)
+                .map(|point| point.curve)
+                .collect::<Vec<_>>()
+        };
+        let mut expected = curve(&approx);
+        expected.reverse();
+        assert_eq!(curve(&sibling_approx), expected);
+        assert_eq!(
+            approx.curve_points().next().map(|p| p.curve),
+            Some([0.].into())
+        );
+    }
+}
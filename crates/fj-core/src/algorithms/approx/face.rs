@@ -5,10 +5,15 @@
 use std::{collections::BTreeSet, ops::Deref};
 
 use fj_interop::Color;
+use fj_math::{Aabb, NurbsSurface, Point};
 
 use crate::{
+    geometry::SurfaceGeometry,
     objects::{Face, Handedness, ObjectSet},
-    operations::presentation::GetColor,
+    operations::{
+        boolean::{contains, distance_to_segment},
+        presentation::{GetColor, GetName},
+    },
     validation::ValidationConfig,
     Core,
 };
@@ -77,34 +82,41 @@ impl Approx for &Face {
     ) -> Self::Approximation {
         let tolerance = tolerance.into();
 
-        // Curved faces whose curvature is not fully defined by their edges
-        // are not supported yet. For that reason, we can fully ignore `face`'s
-        // `surface` field and just pass the edges to `Self::for_edges`.
+        // For faces on swept surfaces, the curvature is fully defined by their
+        // edges. For example, the curvature of a cylinder is fully defined by
+        // the edges (circles) that border it, and the circle approximations
+        // are sufficient to triangulate the surface.
         //
-        // An example of a curved face that is supported, is the cylinder. Its
-        // curvature is fully defined be the edges (circles) that border it. The
-        // circle approximations are sufficient to triangulate the surface.
-        //
-        // An example of a curved face that is currently not supported, and thus
-        // doesn't need to be handled here, is a sphere. A spherical face would
-        // would need to provide its own approximation, as the edges that bound
-        // it have nothing to do with its curvature.
+        // This is not the case for NURBS surfaces, which can curve in any
+        // direction within the boundary of the face. Those need additional
+        // points within the face, which are computed below.
 
         let exterior =
-            (self.region().exterior().deref(), &self.surface().geometry())
+            (self.region().exterior().deref(), self.surface().geometry())
                 .approx_with_cache(tolerance, cache, core);
 
         let mut interiors = BTreeSet::new();
         for cycle in self.region().interiors() {
-            let cycle = (cycle.deref(), &self.surface().geometry())
+            let cycle = (cycle.deref(), self.surface().geometry())
                 .approx_with_cache(tolerance, cache, core);
             interiors.insert(cycle);
         }
 
+        let interior_points = match self.surface().geometry() {
+            SurfaceGeometry::Swept { .. } => BTreeSet::new(),
+            SurfaceGeometry::Nurbs(nurbs) => approx_nurbs_interior(
+                nurbs,
+                [&exterior].into_iter().chain(&interiors),
+                tolerance,
+            ),
+        };
+
         FaceApprox {
             exterior,
             interiors,
+            interior_points,
             color: self.region().get_color(core),
+            name: self.region().get_name(core),
             coord_handedness: self.coord_handedness(),
         }
     }
@@ -119,9 +131,18 @@ pub struct FaceApprox {
     /// Approximations of the interior cycles
     pub interiors: BTreeSet<CycleApprox>,
 
+    /// Points within the face, that are not part of any cycle
+    ///
+    /// These are required for faces whose curvature is not fully defined by
+    /// their boundary.
+    pub interior_points: BTreeSet<ApproxPoint<2>>,
+
     /// The color of the approximated face
     pub color: Option<Color>,
 
+    /// The name of the approximated face
+    pub name: Option<String>,
+
     /// The handedness of the approximated face's front-side coordinate system
     pub coord_handedness: Handedness,
 }
@@ -137,6 +158,108 @@ impl FaceApprox {
             points.extend(cycle_approx.points());
         }
 
+        points.extend(self.interior_points.iter().copied());
+
         points
     }
 }
+
+/// Compute the points within a face on a NURBS surface
+///
+/// The points are laid out on a grid, that covers the boundary of the face in
+/// surface coordinates. The grid gets refined, until the center of each cell
+/// is within the tolerance of the plane spanned by its corners.
+fn approx_nurbs_interior<'r>(
+    surface: &NurbsSurface,
+    cycles: impl IntoIterator<Item = &'r CycleApprox>,
+    tolerance: Tolerance,
+) -> BTreeSet<ApproxPoint<2>> {
+    // Limit the size of the grid, in case the tolerance can't be reached for
+    // some reason.
+    const MAX_CELLS: usize = 64;
+
+    let cycles = cycles
+        .into_iter()
+        .map(|cycle| {
+            cycle
+                .points()
+                .into_iter()
+                .map(|point| point.local_form)
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    if cycles.iter().all(Vec::is_empty) {
+        return BTreeSet::new();
+    }
+    let bounds = Aabb::<2>::from_points(cycles.iter().flatten().copied());
+
+    let point_at = |[i, j]: [usize; 2], n: usize| {
+        let [u, v] = [0, 1].map(|k| {
+            let [min, max] =
+                [bounds.min, bounds.max].map(|p| p.coords.components[k]);
+            let t = [i, j][k] as f64 / n as f64;
+            min + (max - min) * t
+        });
+        Point::from([u, v])
+    };
+
+    let mut num_cells = 1;
+    loop {
+        let is_within_tolerance = (0..num_cells)
+            .flat_map(|i| (0..num_cells).map(move |j| [i, j]))
+            .all(|[i, j]| {
+                let corners = [[i, j], [i + 1, j], [i + 1, j + 1], [i, j + 1]]
+                    .map(|index| {
+                        surface.point_from_surface_coords(point_at(
+                            index.map(|c| c * 2),
+                            num_cells * 2,
+                        ))
+                    });
+                let center = surface.point_from_surface_coords(point_at(
+                    [i * 2 + 1, j * 2 + 1],
+                    num_cells * 2,
+                ));
+
+                // The cell center is compared to the middle of each
+                // diagonal, to detect curvature in any direction.
+                [[0, 2], [1, 3]].into_iter().all(|[a, b]| {
+                    let [a, b] = [corners[a], corners[b]];
+                    let middle = a + (b - a) / 2.;
+                    (center - middle).magnitude() <= tolerance.inner()
+                })
+            });
+
+        if is_within_tolerance || num_cells >= MAX_CELLS {
+            break;
+        }
+
+        num_cells *= 2;
+    }
+
+    // Points that are too close to the boundary would result in degenerate
+    // triangles.
+    let cell_size = (bounds.max - bounds.min) / num_cells as f64;
+    let min_distance = cell_size.u.min(cell_size.v) / 2.;
+
+    (1..num_cells)
+        .flat_map(|i| (1..num_cells).map(move |j| [i, j]))
+        .map(|index| point_at(index, num_cells))
+        .filter(|&point| {
+            let num_containing =
+                cycles.iter().filter(|cycle| contains(cycle, point)).count();
+            num_containing % 2 == 1
+        })
+        .filter(|&point| {
+            cycles.iter().all(|cycle| {
+                cycle.windows(2).all(|segment| {
+                    distance_to_segment(point, [segment[0], segment[1]])
+                        > min_distance
+                })
+            })
+        })
+        .map(|point| {
+            ApproxPoint::new(point, surface.point_from_surface_coords(point))
+        })
+        .collect()
+}
@@ -5,6 +5,7 @@ pub mod cycle;
 pub mod edge;
 pub mod face;
 pub mod path;
+pub mod placed;
 pub mod shell;
 pub mod sketch;
 pub mod solid;
@@ -21,7 +22,9 @@ use fj_math::Point;
 
 use crate::Core;
 
-pub use self::tolerance::{InvalidTolerance, Tolerance};
+pub use self::tolerance::{
+    InvalidAngularDeflection, InvalidTolerance, Tolerance,
+};
 
 /// Approximate an object
 pub trait Approx: Sized {
@@ -57,6 +60,10 @@ pub trait Approx: Sized {
 }
 
 /// A point from an approximation, with local and global forms
+///
+/// For points of a curve approximation, the local form is the position on the
+/// curve. For points of other approximations, it is the position on the
+/// surface. Where both are needed, see [`curve::CurveApproxPoint`].
 #[derive(Clone, Copy, Debug)]
 pub struct ApproxPoint<const D: usize> {
     /// The local form of the point
@@ -27,10 +27,25 @@
 //! As a result, path approximation is guaranteed to generate points that can
 //! fit together in a valid mesh, no matter which ranges of a path are being
 //! approximated, and how many times.
+//!
+//! ## Seams
+//!
+//! Closed paths, like circles and ellipses, have a seam where their coordinates
+//! wrap around, meaning that multiple path coordinates refer to the same global
+//! point. Path approximation takes this into account in two ways:
+//!
+//! - Boundaries that are within [`identical_max_distance`] of a point from the
+//!   set are treated as being exactly on that point. Otherwise, a boundary at the
+//!   seam could result in an additional point that (almost) coincides with it.
+//! - Global points are always computed from the path coordinate within the
+//!   first revolution, so points that are equivalent across the seam are
+//!   exactly identical.
+//!
+//! [`identical_max_distance`]: crate::validation::ValidationConfig::identical_max_distance
 
 use std::iter;
 
-use fj_math::{Circle, Point, Scalar, Sign};
+use fj_math::{Circle, CubicBezier, Ellipse, Point, Scalar, Sign};
 
 use crate::{
     geometry::{CurveBoundary, GlobalPath, SurfacePath},
@@ -47,13 +62,23 @@ impl Approx for (&SurfacePath, CurveBoundary<Point<1>>) {
         self,
         tolerance: impl Into<Tolerance>,
         (): &mut Self::Cache,
-        _core: &mut Core,
+        core: &mut Core,
     ) -> Self::Approximation {
         let (path, range) = self;
+        let max_distance = core.layers.validation.config.identical_max_distance;
 
         match path {
             SurfacePath::Circle(circle) => {
-                approx_circle(circle, range, tolerance.into())
+                approx_circle(circle, range, tolerance.into(), max_distance)
+            }
+            SurfacePath::CubicBezier(bezier) => approx_cubic_bezier(
+                bezier,
+                range,
+                tolerance.into(),
+                max_distance,
+            ),
+            SurfacePath::Ellipse(ellipse) => {
+                approx_ellipse(ellipse, range, tolerance.into(), max_distance)
             }
             SurfacePath::Line(_) => vec![],
         }
@@ -68,13 +93,23 @@ impl Approx for (GlobalPath, CurveBoundary<Point<1>>) {
         self,
         tolerance: impl Into<Tolerance>,
         (): &mut Self::Cache,
-        _core: &mut Core,
+        core: &mut Core,
     ) -> Self::Approximation {
         let (path, range) = self;
+        let max_distance = core.layers.validation.config.identical_max_distance;
 
         match path {
             GlobalPath::Circle(circle) => {
-                approx_circle(&circle, range, tolerance.into())
+                approx_circle(&circle, range, tolerance.into(), max_distance)
+            }
+            GlobalPath::CubicBezier(bezier) => approx_cubic_bezier(
+                &bezier,
+                range,
+                tolerance.into(),
+                max_distance,
+            ),
+            GlobalPath::Ellipse(ellipse) => {
+                approx_ellipse(&ellipse, range, tolerance.into(), max_distance)
             }
             GlobalPath::Line(_) => vec![],
         }
@@ -89,14 +124,63 @@ fn approx_circle<const D: usize>(
     circle: &Circle<D>,
     boundary: impl Into<CurveBoundary<Point<1>>>,
     tolerance: Tolerance,
+    max_distance: Scalar,
 ) -> Vec<(Point<1>, Point<D>)> {
     let boundary = boundary.into();
 
     let params = PathApproxParams::for_circle(circle, tolerance);
     let mut points = Vec::new();
 
-    for point_curve in params.points(boundary) {
-        let point_global = circle.point_from_circle_coords(point_curve);
+    for point_curve in params.points(boundary, max_distance) {
+        let point_global =
+            circle.point_from_circle_coords(params.wrap(point_curve));
+        points.push((point_curve, point_global));
+    }
+
+    points
+}
+
+/// Approximate an ellipse
+///
+/// `tolerance` specifies how much the approximation is allowed to deviate
+/// from the ellipse.
+fn approx_ellipse<const D: usize>(
+    ellipse: &Ellipse<D>,
+    boundary: impl Into<CurveBoundary<Point<1>>>,
+    tolerance: Tolerance,
+    max_distance: Scalar,
+) -> Vec<(Point<1>, Point<D>)> {
+    let boundary = boundary.into();
+
+    let params = PathApproxParams::for_ellipse(ellipse, tolerance);
+    let mut points = Vec::new();
+
+    for point_curve in params.points(boundary, max_distance) {
+        let point_global =
+            ellipse.point_from_ellipse_coords(params.wrap(point_curve));
+        points.push((point_curve, point_global));
+    }
+
+    points
+}
+
+/// Approximate a cubic Bézier curve
+///
+/// `tolerance` specifies how much the approximation is allowed to deviate
+/// from the curve.
+fn approx_cubic_bezier<const D: usize>(
+    bezier: &CubicBezier<D>,
+    boundary: impl Into<CurveBoundary<Point<1>>>,
+    tolerance: Tolerance,
+    max_distance: Scalar,
+) -> Vec<(Point<1>, Point<D>)> {
+    let boundary = boundary.into();
+
+    let params = PathApproxParams::for_cubic_bezier(bezier, tolerance);
+    let mut points = Vec::new();
+
+    for point_curve in params.points(boundary, max_distance) {
+        let point_global = bezier.point_from_bezier_coords(point_curve);
         points.push((point_curve, point_global));
     }
 
@@ -105,6 +189,7 @@ fn approx_circle<const D: usize>(
 
 struct PathApproxParams {
     increment: Scalar,
+    num_vertices: Scalar,
 }
 
 impl PathApproxParams {
@@ -112,31 +197,108 @@ impl PathApproxParams {
         circle: &Circle<D>,
         tolerance: impl Into<Tolerance>,
     ) -> Self {
-        let radius = circle.a().magnitude();
+        Self::for_radius(circle.a().magnitude(), tolerance)
+    }
 
-        let num_vertices_to_approx_full_circle = Scalar::max(
-            Scalar::PI
-                / (Scalar::ONE - (tolerance.into().inner() / radius)).acos(),
-            3.,
+    pub fn for_ellipse<const D: usize>(
+        ellipse: &Ellipse<D>,
+        tolerance: impl Into<Tolerance>,
+    ) -> Self {
+        // An ellipse is a circle that has been scaled along its axes. The
+        // deviation of the approximation from the ellipse is the deviation
+        // from the circle, scaled by at most the longer semi-axis. Using the
+        // parameters for a circle of that radius respects the tolerance.
+        Self::for_radius(ellipse.max_radius(), tolerance)
+    }
+
+    pub fn for_cubic_bezier<const D: usize>(
+        bezier: &CubicBezier<D>,
+        tolerance: impl Into<Tolerance>,
+    ) -> Self {
+        // Wang's formula bounds how far a uniform subdivision of a polynomial
+        // curve deviates from it, based on the second differences of its
+        // control points. For a cubic curve, `n` segments deviate by at most
+        // `3/4 * max_second_difference / n^2`.
+        //
+        // This adapts the number of segments to how much each curve bends,
+        // while keeping the set of points deterministic, as required.
+        let num_segments = Scalar::from(
+            (0.75 * bezier.max_second_difference().into_f64()
+                / tolerance.into().inner().into_f64())
+            .sqrt(),
         )
-        .ceil();
+        .ceil()
+        .max(Scalar::ONE);
+
+        Self {
+            increment: Scalar::ONE / num_segments,
+            num_vertices: num_segments,
+        }
+    }
+
+    fn for_radius(radius: Scalar, tolerance: impl Into<Tolerance>) -> Self {
+        let tolerance = tolerance.into();
+
+        let num_vertices_for_deviation =
+            Scalar::PI / (Scalar::ONE - (tolerance.inner() / radius)).acos();
+
+        // Adjacent segments of the approximation are at an angle of one
+        // increment to each other.
+        let num_vertices_for_angle = tolerance
+            .angular_deflection()
+            .map(|angle| Scalar::TAU / angle)
+            .unwrap_or(Scalar::ZERO);
+
+        let num_vertices_to_approx_full_circle = num_vertices_for_deviation
+            .max(num_vertices_for_angle)
+            .max(Scalar::from(3.))
+            .ceil();
 
         let increment = Scalar::TAU / num_vertices_to_approx_full_circle;
 
-        Self { increment }
+        Self {
+            increment,
+            num_vertices: num_vertices_to_approx_full_circle,
+        }
     }
 
     pub fn increment(&self) -> Scalar {
         self.increment
     }
 
+    /// Map a point from the approximation into the first revolution
+    ///
+    /// Returns the point that is equivalent to the provided one, as far as
+    /// the global form of the path is concerned, but is computed from the same
+    /// index within the set of points for every revolution.
+    pub fn wrap(&self, point: Point<1>) -> Point<1> {
+        let i = (point.t / self.increment()).round();
+        let i =
+            ((i % self.num_vertices) + self.num_vertices) % self.num_vertices;
+
+        Point::from([self.increment() * i])
+    }
+
     pub fn points(
         &self,
         boundary: impl Into<CurveBoundary<Point<1>>>,
+        max_distance: Scalar,
     ) -> impl Iterator<Item = Point<1>> + '_ {
         let boundary = boundary.into();
 
-        let [a, b] = boundary.inner.map(|point| point.t / self.increment());
+        let [a, b] = boundary.inner.map(|point| {
+            let i = point.t / self.increment();
+
+            // A boundary at the seam of the path might have ended up slightly
+            // off due to floating-point error. Snap it to the point it's
+            // supposed to be on, or we'll generate a point right next to it.
+            let i_rounded = i.round();
+            if (i - i_rounded).abs() * self.increment() <= max_distance {
+                i_rounded
+            } else {
+                i
+            }
+        });
         let direction = (b - a).sign();
         let [min, max] = if a < b { [a, b] } else { [b, a] };
 
@@ -173,9 +335,12 @@ impl PathApproxParams {
 mod tests {
     use std::f64::consts::TAU;
 
-    use fj_math::{Circle, Point, Scalar};
+    use fj_math::{Circle, CubicBezier, Point, Scalar};
 
-    use crate::algorithms::approx::{path::CurveBoundary, Tolerance};
+    use crate::{
+        algorithms::approx::{path::CurveBoundary, Tolerance},
+        validation::ValidationConfig,
+    };
 
     use super::PathApproxParams;
 
@@ -198,6 +363,42 @@ mod tests {
         }
     }
 
+    #[test]
+    fn increment_for_circle_with_angular_deflection() -> anyhow::Result<()> {
+        let circle = Circle::from_center_and_radius([0., 0.], 1.);
+
+        // The angular deflection requires more vertices than the deviation.
+        let tolerance =
+            Tolerance::from_scalar(0.5)?.with_angular_deflection(TAU / 8.)?;
+        let params = PathApproxParams::for_circle(&circle, tolerance);
+        assert_eq!(params.increment(), Scalar::TAU / 8.);
+
+        // The deviation requires more vertices than the angular deflection.
+        let tolerance =
+            Tolerance::from_scalar(0.01)?.with_angular_deflection(TAU / 8.)?;
+        let params = PathApproxParams::for_circle(&circle, tolerance);
+        assert_eq!(params.increment(), Scalar::TAU / 23.);
+
+        Ok(())
+    }
+
+    #[test]
+    fn increment_for_cubic_bezier() {
+        // A curve with evenly spaced control points along a line is a line.
+        let line = CubicBezier::new([[0., 0.], [1., 0.], [2., 0.], [3., 0.]]);
+        let params = PathApproxParams::for_cubic_bezier(&line, 0.01);
+        assert_eq!(params.increment(), Scalar::ONE);
+
+        // The more the curve bends, the more segments are needed.
+        let curve = CubicBezier::new([[0., 0.], [0., 1.], [1., 1.], [1., 0.]]);
+        let params = PathApproxParams::for_cubic_bezier(&curve, 0.01);
+        assert_eq!(params.increment(), Scalar::ONE / 11.);
+
+        let curve = CubicBezier::new([[0., 0.], [0., 4.], [1., 4.], [1., 0.]]);
+        let params = PathApproxParams::for_cubic_bezier(&curve, 0.01);
+        assert_eq!(params.increment(), Scalar::ONE / 18.);
+    }
+
     #[test]
     fn points_for_circle() {
         // At the chosen values for radius and tolerance (see below), the
@@ -237,7 +438,8 @@ mod tests {
             let circle = Circle::from_center_and_radius([0., 0.], radius);
             let params = PathApproxParams::for_circle(&circle, tolerance);
 
-            let points = params.points(boundary).collect::<Vec<_>>();
+            let points =
+                params.points(boundary, max_distance()).collect::<Vec<_>>();
 
             let expected_points = expected_coords
                 .into_iter()
@@ -246,4 +448,52 @@ mod tests {
             assert_eq!(points, expected_points);
         }
     }
+
+    #[test]
+    fn points_for_circle_at_seam() {
+        let circle = Circle::from_center_and_radius([0., 0.], 1.);
+
+        // For some numbers of vertices, the boundaries of a full circle end up
+        // slightly off the points they're supposed to be on, due to floating-
+        // point error. Make sure none of them result in additional points.
+        for num_vertices in 3..=200 {
+            let num_vertices = f64::from(num_vertices);
+
+            let tolerance = Scalar::ONE - (Scalar::PI / num_vertices).cos();
+            let params = PathApproxParams::for_circle(&circle, tolerance);
+            let num_vertices = (Scalar::TAU / params.increment()).round();
+
+            for revolution in [-1., 0., 1., 2.] {
+                let start = Scalar::TAU * revolution;
+                let end = start + Scalar::TAU;
+
+                for boundary in [[[start], [end]], [[end], [start]]] {
+                    let points = params
+                        .points(boundary, max_distance())
+                        .collect::<Vec<_>>();
+                    assert_eq!(
+                        Scalar::from(points.len() as f64),
+                        num_vertices - 1.,
+                    );
+
+                    // Equivalent points from different revolutions must map
+                    // to the exact same point.
+                    let mut wrapped = points
+                        .into_iter()
+                        .map(|point| params.wrap(point))
+                        .collect::<Vec<_>>();
+                    wrapped.sort();
+                    wrapped.dedup();
+                    assert_eq!(
+                        Scalar::from(wrapped.len() as f64),
+                        num_vertices - 1.,
+                    );
+                }
+            }
+        }
+    }
+
+    fn max_distance() -> Scalar {
+        ValidationConfig::default().identical_max_distance
+    }
 }
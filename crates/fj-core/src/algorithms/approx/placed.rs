@@ -0,0 +1,117 @@
+//! Approximation of placed objects
+//!
+//! See [`Placed`].
+
+use std::collections::BTreeSet;
+
+use fj_math::{Transform, Vector};
+
+use crate::{
+    objects::{Shell, Solid},
+    operations::place::Placed,
+    Core,
+};
+
+use super::{
+    cycle::CycleApprox, edge::HalfEdgeApproxCache, face::FaceApprox, Approx,
+    Tolerance,
+};
+
+impl Approx for &Placed<Solid> {
+    type Approximation = BTreeSet<FaceApprox>;
+    type Cache = HalfEdgeApproxCache;
+
+    fn approx_with_cache(
+        self,
+        tolerance: impl Into<Tolerance>,
+        cache: &mut Self::Cache,
+        core: &mut Core,
+    ) -> Self::Approximation {
+        approx_placed(self, tolerance.into(), cache, core)
+    }
+}
+
+impl Approx for &Placed<Shell> {
+    type Approximation = BTreeSet<FaceApprox>;
+    type Cache = HalfEdgeApproxCache;
+
+    fn approx_with_cache(
+        self,
+        tolerance: impl Into<Tolerance>,
+        cache: &mut Self::Cache,
+        core: &mut Core,
+    ) -> Self::Approximation {
+        approx_placed(self, tolerance.into(), cache, core)
+    }
+}
+
+fn approx_placed<'r, T>(
+    placed: &'r Placed<T>,
+    tolerance: Tolerance,
+    cache: &mut HalfEdgeApproxCache,
+    core: &mut Core,
+) -> BTreeSet<FaceApprox>
+where
+    &'r T: Approx<
+        Approximation = BTreeSet<FaceApprox>,
+        Cache = HalfEdgeApproxCache,
+    >,
+{
+    let transform = placed.transform();
+
+    // The tolerance applies to the placed object. If the transform scales the
+    // object, the approximation of the original object needs to be finer (or
+    // may be coarser) by the same factor.
+    let scale = [Vector::unit_x(), Vector::unit_y(), Vector::unit_z()]
+        .map(|axis| transform.transform_vector(&axis).magnitude())
+        .into_iter()
+        .max()
+        .expect("Array is not empty");
+    let mut scaled_tolerance =
+        Tolerance::from_scalar(tolerance.inner() / scale)
+            .expect("Transform must not collapse object");
+
+    // Angles are not affected by scaling.
+    if let Some(angle) = tolerance.angular_deflection() {
+        scaled_tolerance = scaled_tolerance
+            .with_angular_deflection(angle)
+            .expect("Angular deflection has already been validated");
+    }
+    let tolerance = scaled_tolerance;
+
+    (&**placed.object())
+        .approx_with_cache(tolerance, cache, core)
+        .into_iter()
+        .map(|approx| place_face_approx(approx, transform))
+        .collect()
+}
+
+fn place_face_approx(approx: FaceApprox, transform: &Transform) -> FaceApprox {
+    let place_cycle = |mut cycle: CycleApprox| {
+        for half_edge in &mut cycle.half_edges {
+            for point in &mut half_edge.points {
+                point.global_form =
+                    transform.transform_point(&point.global_form);
+            }
+        }
+
+        cycle
+    };
+
+    FaceApprox {
+        exterior: place_cycle(approx.exterior),
+        interiors: approx.interiors.into_iter().map(place_cycle).collect(),
+        interior_points: approx
+            .interior_points
+            .into_iter()
+            .map(|mut point| {
+                point.global_form =
+                    transform.transform_point(&point.global_form);
+                point
+            })
+            .collect(),
+        color: approx.color,
+        name: approx.name,
+        coord_handedness: approx.coord_handedness,
+    }
+}
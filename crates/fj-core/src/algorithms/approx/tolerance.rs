@@ -9,6 +9,13 @@ use fj_math::Scalar;
 /// A tolerance value is used during approximation. It defines the maximum
 /// allowed deviation of the approximation from the actual shape.
 ///
+/// Optionally, a tolerance can also limit the angular deflection of the
+/// approximation, which is the angle between adjacent segments that
+/// approximate a curve. This can be useful, if the approximation is used for
+/// rendering or machining, where the number of segments that make up large arcs
+/// matters more than how far they deviate from the arc. See
+/// [`Tolerance::with_angular_deflection`].
+///
 /// The `Tolerance` type enforces that the tolerance value is always larger than
 /// zero, which is an attribute that the approximation code relies on.
 ///
@@ -22,7 +29,10 @@ use fj_math::Scalar;
 /// documentation doesn't provide any actual reasoning for this requirement, I'm
 /// feeling free to just ignore it.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
-pub struct Tolerance(Scalar);
+pub struct Tolerance {
+    chordal: Scalar,
+    angular: Option<Scalar>,
+}
 
 impl Tolerance {
     /// Construct a `Tolerance` from a [`Scalar`]
@@ -37,12 +47,44 @@ impl Tolerance {
             return Err(InvalidTolerance(scalar));
         }
 
-        Ok(Self(scalar))
+        Ok(Self {
+            chordal: scalar,
+            angular: None,
+        })
     }
 
-    /// Return the [`Scalar`] that defines the tolerance
+    /// Limit the angular deflection of the approximation
+    ///
+    /// The angle is given in radians. Returns an error, if it is not larger
+    /// than zero, or larger than pi.
+    ///
+    /// The angular deflection is respected in addition to the maximum
+    /// deviation. Currently, it only affects the approximation of circles and
+    /// ellipses.
+    pub fn with_angular_deflection(
+        self,
+        angle: impl Into<Scalar>,
+    ) -> Result<Self, InvalidAngularDeflection> {
+        let angle = angle.into();
+
+        if angle <= Scalar::ZERO || angle > Scalar::PI {
+            return Err(InvalidAngularDeflection(angle));
+        }
+
+        Ok(Self {
+            angular: Some(angle),
+            ..self
+        })
+    }
+
+    /// Return the [`Scalar`] that defines the maximum deviation
     pub fn inner(&self) -> Scalar {
-        self.0
+        self.chordal
+    }
+
+    /// Return the maximum angular deflection, if one has been defined
+    pub fn angular_deflection(&self) -> Option<Scalar> {
+        self.angular
     }
 }
 
@@ -60,3 +102,30 @@ where
 #[derive(Debug, thiserror::Error)]
 #[error("Invalid tolerance ({0}); must be above zero")]
 pub struct InvalidTolerance(Scalar);
+
+/// Error defining the angular deflection of a tolerance
+#[derive(Debug, thiserror::Error)]
+#[error("Invalid angular deflection ({0}); must be above zero and at most pi")]
+pub struct InvalidAngularDeflection(Scalar);
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Scalar;
+
+    use super::Tolerance;
+
+    #[test]
+    fn angular_deflection() -> anyhow::Result<()> {
+        let tolerance = Tolerance::from_scalar(1.)?;
+        assert_eq!(tolerance.angular_deflection(), None);
+
+        let tolerance = tolerance.with_angular_deflection(0.5)?;
+        assert_eq!(tolerance.inner(), Scalar::ONE);
+        assert_eq!(tolerance.angular_deflection(), Some(Scalar::from(0.5)));
+
+        assert!(tolerance.with_angular_deflection(0.).is_err());
+        assert!(tolerance.with_angular_deflection(4.).is_err());
+
+        Ok(())
+    }
+}
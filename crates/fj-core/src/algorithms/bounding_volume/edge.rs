@@ -17,6 +17,16 @@ impl super::BoundingVolume<2> for HalfEdge {
                     max: circle.center() + center_to_min_max,
                 })
             }
+            SurfacePath::CubicBezier(bezier) => {
+                // The AABB of the whole curve, which is bounded by its
+                // control points.
+                Some(bezier.aabb())
+            }
+            SurfacePath::Ellipse(ellipse) => {
+                // Same as for the circle, this is the AABB of the whole
+                // ellipse.
+                Some(ellipse.aabb())
+            }
             SurfacePath::Line(_) => {
                 let points = self.boundary().inner.map(|point_curve| {
                     self.path().point_from_path_coords(point_curve)
@@ -1,29 +1,72 @@
 use fj_math::Aabb;
 
-use crate::{geometry::GlobalPath, objects::Face};
+use crate::{
+    geometry::{GlobalPath, SurfaceGeometry},
+    objects::Face,
+};
 
 impl super::BoundingVolume<3> for Face {
     fn aabb(&self) -> Option<Aabb<3>> {
         self.region().exterior().aabb().map(|aabb2| {
             let surface = self.surface().geometry();
 
-            match surface.u {
+            let (u, v) = match surface {
+                SurfaceGeometry::Swept { u, v } => (u, v),
+                SurfaceGeometry::Nurbs(nurbs) => {
+                    // This is the AABB of the whole surface, which might be
+                    // much larger than the face.
+                    return nurbs.aabb();
+                }
+            };
+
+            match u {
                 GlobalPath::Circle(circle) => {
                     // This is not the most precise way to calculate the AABB,
                     // doing it for the whole circle, but it should do.
 
                     let aabb_bottom = circle.aabb();
                     let aabb_top = Aabb {
-                        min: aabb_bottom.min + surface.v,
-                        max: aabb_bottom.max + surface.v,
+                        min: aabb_bottom.min + *v,
+                        max: aabb_bottom.max + *v,
+                    };
+
+                    aabb_bottom.merged(&aabb_top)
+                }
+                GlobalPath::CubicBezier(bezier) => {
+                    let aabb_bottom = bezier.aabb();
+                    let aabb_top = Aabb {
+                        min: aabb_bottom.min + *v,
+                        max: aabb_bottom.max + *v,
                     };
 
                     aabb_bottom.merged(&aabb_top)
                 }
-                GlobalPath::Line(_) => Aabb {
-                    min: surface.point_from_surface_coords(aabb2.min),
-                    max: surface.point_from_surface_coords(aabb2.max),
-                },
+                GlobalPath::Ellipse(ellipse) => {
+                    let aabb_bottom = ellipse.aabb();
+                    let aabb_top = Aabb {
+                        min: aabb_bottom.min + *v,
+                        max: aabb_bottom.max + *v,
+                    };
+
+                    aabb_bottom.merged(&aabb_top)
+                }
+                GlobalPath::Line(_) => {
+                    // The surface axes aren't necessarily aligned with the
+                    // global ones, so all corners need to be considered.
+                    let [min, max] = [aabb2.min, aabb2.max];
+                    let corners = [
+                        [min.u, min.v],
+                        [max.u, min.v],
+                        [max.u, max.v],
+                        [min.u, max.v],
+                    ];
+
+                    Aabb::<3>::from_points(
+                        corners.map(|point| {
+                            surface.point_from_surface_coords(point)
+                        }),
+                    )
+                }
             }
         })
     }
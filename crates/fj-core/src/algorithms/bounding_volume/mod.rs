@@ -3,6 +3,7 @@
 mod cycle;
 mod edge;
 mod face;
+mod placed;
 mod shell;
 mod solid;
 
@@ -0,0 +1,13 @@
+use fj_math::Aabb;
+
+use crate::operations::place::Placed;
+
+impl<T> super::BoundingVolume<3> for Placed<T>
+where
+    T: super::BoundingVolume<3>,
+{
+    fn aabb(&self) -> Option<Aabb<3>> {
+        let aabb = self.object().aabb()?;
+        Some(self.transform().transform_aabb(&aabb))
+    }
+}
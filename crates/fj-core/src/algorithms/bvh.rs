@@ -0,0 +1,553 @@
+//! Spatial index over the faces of a shape
+//!
+//! See [`FaceBvh`].
+
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BinaryHeap},
+};
+
+use fj_math::{Aabb, Point, Ray, Scalar, Triangle, Vector};
+
+use crate::{
+    algorithms::{
+        approx::Tolerance,
+        intersect::ray_shape::{
+            hit_face, ray_hits_aabb, triangulate_face, RayHit,
+        },
+        triangulate::{delaunay::TriangulationPoint, index::aabb_overlap},
+    },
+    objects::{Face, Solid},
+    storage::Handle,
+    validation::ValidationConfig,
+    Core,
+};
+
+/// A bounding volume hierarchy (BVH) over the faces of a shape
+///
+/// Answers spatial queries about the faces, like ray casts, nearest-face
+/// queries, and interference tests, without having to look at every face.
+/// Faces are approximated with the provided tolerance when they are added to
+/// the index, and all queries operate on those approximations.
+///
+/// When the shape changes, [`FaceBvh::update`] brings the index up to date.
+/// Only faces that are new to the index need to be approximated.
+pub struct FaceBvh {
+    tolerance: Tolerance,
+    config: ValidationConfig,
+    faces: Vec<IndexedFace>,
+
+    /// The nodes of the hierarchy, with the root at index `0`
+    nodes: Vec<Node>,
+}
+
+impl FaceBvh {
+    /// Build an index over the provided faces
+    pub fn new<'r>(
+        faces: impl IntoIterator<Item = &'r Handle<Face>>,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Self {
+        let mut bvh = Self {
+            tolerance: tolerance.into(),
+            config: core.layers.validation.config,
+            faces: Vec::new(),
+            nodes: Vec::new(),
+        };
+        bvh.update(faces, core);
+        bvh
+    }
+
+    /// Build an index over the faces of a solid
+    pub fn from_solid(
+        solid: &Solid,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Self {
+        let faces = solid.shells().iter().flat_map(|shell| shell.faces());
+        Self::new(faces, tolerance, core)
+    }
+
+    /// Update the index to cover the provided faces
+    ///
+    /// Faces that are already indexed keep their approximation, while new
+    /// faces are approximated. Faces that are not provided are removed from
+    /// the index. The hierarchy is then rebuilt from the bounding boxes of the
+    /// faces, which is cheap compared to approximating them.
+    pub fn update<'r>(
+        &mut self,
+        faces: impl IntoIterator<Item = &'r Handle<Face>>,
+        core: &mut Core,
+    ) {
+        let mut indexed = self
+            .faces
+            .drain(..)
+            .map(|face| (face.face.id(), face))
+            .collect::<BTreeMap<_, _>>();
+
+        let mut faces_by_id = BTreeMap::new();
+        for face in faces {
+            if faces_by_id.contains_key(&face.id()) {
+                continue;
+            }
+
+            let indexed_face =
+                indexed.remove(&face.id()).unwrap_or_else(|| {
+                    IndexedFace::new(face.clone(), self.tolerance, core)
+                });
+            faces_by_id.insert(face.id(), indexed_face);
+        }
+        self.faces = faces_by_id.into_values().collect();
+
+        self.nodes.clear();
+        let mut leaves = self
+            .faces
+            .iter()
+            .enumerate()
+            .filter_map(|(i, face)| face.aabb.map(|_| i))
+            .collect::<Vec<_>>();
+        if !leaves.is_empty() {
+            self.build_node(&mut leaves);
+        }
+    }
+
+    /// Access the bounding box of all indexed faces
+    ///
+    /// Returns `None`, if the index is empty.
+    pub fn aabb(&self) -> Option<Aabb<3>> {
+        self.nodes.first().map(|node| node.aabb)
+    }
+
+    /// Compute the points where the ray hits the indexed faces
+    ///
+    /// Returns all hits, ordered by their distance from the origin of the ray.
+    /// See [`CastRay`] for more information.
+    ///
+    /// [`CastRay`]: crate::algorithms::intersect::ray_shape::CastRay
+    pub fn cast_ray(&self, ray: &Ray<3>) -> Vec<RayHit> {
+        let mut hits = Vec::new();
+
+        self.visit(
+            |aabb| ray_hits_aabb(ray, aabb),
+            |face| {
+                hits.extend(hit_face(
+                    &face.face,
+                    &face.triangles,
+                    ray,
+                    &self.config,
+                ));
+            },
+        );
+
+        hits.sort();
+        hits
+    }
+
+    /// Find the face that is nearest to the provided point
+    ///
+    /// Returns `None`, if the index is empty. The distance is measured to the
+    /// approximation of the face, so it can be off by the tolerance.
+    pub fn nearest_face(
+        &self,
+        point: impl Into<Point<3>>,
+    ) -> Option<NearestFace> {
+        let point = point.into();
+
+        let mut nearest: Option<NearestFace> = None;
+        let mut queue = BinaryHeap::new();
+        if let Some(root) = self.nodes.first() {
+            queue.push(Reverse((distance_to_aabb(&point, &root.aabb), 0)));
+        }
+
+        // Visit the nodes in the order of their distance. Once the next one is
+        // farther away than the nearest face found so far, we're done.
+        while let Some(Reverse((distance, index))) = queue.pop() {
+            if let Some(nearest) = &nearest {
+                if distance >= nearest.distance {
+                    break;
+                }
+            }
+
+            match self.nodes[index].kind {
+                NodeKind::Leaf { face } => {
+                    let face = &self.faces[face];
+                    let distance = face
+                        .triangles()
+                        .map(|triangle| triangle.distance_to_point(point))
+                        .min();
+
+                    if let Some(distance) = distance {
+                        let is_nearer = nearest
+                            .as_ref()
+                            .map(|nearest| distance < nearest.distance)
+                            .unwrap_or(true);
+                        if is_nearer {
+                            nearest = Some(NearestFace {
+                                face: face.face.clone(),
+                                distance,
+                            });
+                        }
+                    }
+                }
+                NodeKind::Branch { children } => {
+                    for child in children {
+                        let distance =
+                            distance_to_aabb(&point, &self.nodes[child].aabb);
+                        queue.push(Reverse((distance, child)));
+                    }
+                }
+            }
+        }
+
+        nearest
+    }
+
+    /// Find the faces that come near the faces of another index
+    ///
+    /// Returns pairs of faces, the first from this index and the second from
+    /// the other one, whose approximations are within the larger of both
+    /// tolerances of each other. This includes faces that touch, as well as
+    /// faces that intersect.
+    pub fn interfering_faces(&self, other: &Self) -> Vec<[Handle<Face>; 2]> {
+        let margin = self.tolerance.inner().max(other.tolerance.inner());
+        let offset = Vector::from([margin; 3]);
+        let grown = |aabb: &Aabb<3>| Aabb {
+            min: aabb.min - offset,
+            max: aabb.max + offset,
+        };
+
+        let mut pairs = Vec::new();
+
+        let mut stack = Vec::new();
+        if !self.nodes.is_empty() && !other.nodes.is_empty() {
+            stack.push((0, 0));
+        }
+
+        while let Some((a, b)) = stack.pop() {
+            let [node_a, node_b] = [&self.nodes[a], &other.nodes[b]];
+            if !aabb_overlap(&grown(&node_a.aabb), &node_b.aabb) {
+                continue;
+            }
+
+            match (node_a.kind, node_b.kind) {
+                (
+                    NodeKind::Leaf { face: face_a },
+                    NodeKind::Leaf { face: face_b },
+                ) => {
+                    let [face_a, face_b] =
+                        [&self.faces[face_a], &other.faces[face_b]];
+
+                    let is_near = face_a.triangles().any(|a| {
+                        let aabb = grown(&Aabb::<3>::from_points(a.points()));
+                        face_b.triangles().any(|b| {
+                            aabb_overlap(
+                                &aabb,
+                                &Aabb::<3>::from_points(b.points()),
+                            ) && a.distance_to_triangle(&b) <= margin
+                        })
+                    });
+
+                    if is_near {
+                        pairs.push([face_a.face.clone(), face_b.face.clone()]);
+                    }
+                }
+                (NodeKind::Branch { children }, NodeKind::Leaf { .. }) => {
+                    stack.extend(children.map(|child| (child, b)));
+                }
+                (NodeKind::Leaf { .. }, NodeKind::Branch { children }) => {
+                    stack.extend(children.map(|child| (a, child)));
+                }
+                (
+                    NodeKind::Branch {
+                        children: children_a,
+                    },
+                    NodeKind::Branch {
+                        children: children_b,
+                    },
+                ) => {
+                    for child_a in children_a {
+                        stack.extend(
+                            children_b.map(|child_b| (child_a, child_b)),
+                        );
+                    }
+                }
+            }
+        }
+
+        pairs
+    }
+
+    /// Build a node over the provided faces, returning its index
+    ///
+    /// Nodes are split along the longest axis of their bounding box, at the
+    /// median of the centers of their faces.
+    fn build_node(&mut self, faces: &mut [usize]) -> usize {
+        let aabb = faces
+            .iter()
+            .filter_map(|&face| self.faces[face].aabb)
+            .reduce(|a, b| a.merged(&b))
+            .expect("Expected non-empty list of faces with bounding boxes");
+
+        let index = self.nodes.len();
+        if let [face] = faces {
+            self.nodes.push(Node {
+                aabb,
+                kind: NodeKind::Leaf { face: *face },
+            });
+            return index;
+        }
+
+        // The children are filled in, once they have been built.
+        self.nodes.push(Node {
+            aabb,
+            kind: NodeKind::Branch { children: [0; 2] },
+        });
+
+        let size = aabb.size();
+        let axis = (0..3)
+            .max_by_key(|&i| size.components[i])
+            .expect("Iterating over non-empty range");
+        faces.sort_by_key(|&face| {
+            let aabb = self.faces[face]
+                .aabb
+                .expect("Only faces with bounding boxes are in the hierarchy");
+            aabb.center().coords.components[axis]
+        });
+
+        let (a, b) = faces.split_at_mut(faces.len() / 2);
+        let children = [self.build_node(a), self.build_node(b)];
+        self.nodes[index].kind = NodeKind::Branch { children };
+
+        index
+    }
+
+    /// Visit all faces in nodes that match the provided condition
+    fn visit(
+        &self,
+        mut condition: impl FnMut(&Aabb<3>) -> bool,
+        mut visitor: impl FnMut(&IndexedFace),
+    ) {
+        let mut stack = Vec::new();
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if !condition(&node.aabb) {
+                continue;
+            }
+
+            match node.kind {
+                NodeKind::Leaf { face } => visitor(&self.faces[face]),
+                NodeKind::Branch { children } => stack.extend(children),
+            }
+        }
+    }
+}
+
+/// The face that is nearest to a point
+///
+/// See [`FaceBvh::nearest_face`].
+#[derive(Clone, Debug)]
+pub struct NearestFace {
+    /// The face
+    pub face: Handle<Face>,
+
+    /// The distance between the face and the point
+    pub distance: Scalar,
+}
+
+struct IndexedFace {
+    face: Handle<Face>,
+
+    /// The triangles of the face's approximation
+    triangles: Vec<[TriangulationPoint; 3]>,
+
+    /// The bounding box of the approximation
+    ///
+    /// This is `None`, if the approximation doesn't have any triangles. Those
+    /// faces are left out of the hierarchy.
+    aabb: Option<Aabb<3>>,
+}
+
+impl IndexedFace {
+    fn new(face: Handle<Face>, tolerance: Tolerance, core: &mut Core) -> Self {
+        let triangles = triangulate_face(&face, tolerance, core);
+        let aabb = triangles
+            .iter()
+            .map(|triangle| {
+                Aabb::<3>::from_points(triangle.map(|point| point.point_global))
+            })
+            .reduce(|a, b| a.merged(&b));
+
+        Self {
+            face,
+            triangles,
+            aabb,
+        }
+    }
+
+    /// Iterate over the non-degenerate triangles of the approximation
+    fn triangles(&self) -> impl Iterator<Item = Triangle<3>> + '_ {
+        self.triangles.iter().filter_map(|triangle| {
+            Triangle::from_points(triangle.map(|point| point.point_global)).ok()
+        })
+    }
+}
+
+struct Node {
+    aabb: Aabb<3>,
+    kind: NodeKind,
+}
+
+#[derive(Clone, Copy)]
+enum NodeKind {
+    Leaf { face: usize },
+    Branch { children: [usize; 2] },
+}
+
+fn distance_to_aabb(point: &Point<3>, aabb: &Aabb<3>) -> Scalar {
+    let offset = Vector::from([0, 1, 2].map(|i| {
+        let coord = point.coords.components[i];
+        let [min, max] = [aabb.min, aabb.max].map(|p| p.coords.components[i]);
+
+        if coord < min {
+            min - coord
+        } else if coord > max {
+            coord - max
+        } else {
+            Scalar::ZERO
+        }
+    }));
+
+    offset.magnitude()
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Ray, Scalar};
+    use fj_test_utils::assert_scalars_eq;
+
+    use crate::{
+        algorithms::{
+            bounding_volume::BoundingVolume, intersect::ray_shape::CastRay,
+        },
+        objects::{Shell, Solid},
+        operations::{
+            build::{BuildShell, BuildSolid},
+            insert::Insert,
+            update::UpdateSolid,
+        },
+        Core,
+    };
+
+    use super::FaceBvh;
+
+    #[test]
+    fn cast_ray() {
+        let mut core = Core::new();
+
+        // A row of cubes, which the ray passes through.
+        let shells = (0..4)
+            .map(|i| {
+                let x = f64::from(i) * 2.;
+                Shell::cuboid([x, 0., 0.], [x + 1., 1., 1.], &mut core)
+                    .insert(&mut core)
+            })
+            .collect::<Vec<_>>();
+        let solid = Solid::empty().add_shells(shells, &mut core);
+
+        let bvh = FaceBvh::from_solid(&solid, 0.01, &mut core);
+
+        let ray =
+            Ray::from_origin_and_direction([-1., 0.5, 0.25], [1., 0., 0.]);
+        let hits = bvh.cast_ray(&ray);
+        assert_eq!(hits.len(), 8);
+        assert_eq!(hits, solid.cast_ray(&ray, 0.01, &mut core));
+
+        let ray =
+            Ray::from_origin_and_direction([-1., 0.5, 0.25], [-1., 0., 0.]);
+        assert!(bvh.cast_ray(&ray).is_empty());
+    }
+
+    #[test]
+    fn nearest_face() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let solid = Solid::sphere(1., &mut core)?.add_shells(
+            [Shell::cuboid([2., 0., 0.], [3., 1., 1.], &mut core)
+                .insert(&mut core)],
+            &mut core,
+        );
+        let bvh = FaceBvh::from_solid(&solid, 0.01, &mut core);
+
+        // Right above the top face of the cube.
+        let nearest = bvh
+            .nearest_face([2.5, 0.5, 3.])
+            .expect("Expected non-empty index");
+        assert_eq!(nearest.distance, Scalar::TWO);
+        let aabb = nearest.face.aabb().expect("Face has bounding box");
+        assert_eq!(aabb.min.z, Scalar::ONE);
+
+        // Near the sphere, which is approximated by a polyhedron.
+        let nearest = bvh
+            .nearest_face([-2., 0., 0.])
+            .expect("Expected non-empty index");
+        assert_scalars_eq!(nearest.distance, 1., 0.01);
+
+        Ok(())
+    }
+
+    #[test]
+    fn interfering_faces() {
+        let mut core = Core::new();
+
+        let cube = |min: [f64; 3], max: [f64; 3], core: &mut Core| {
+            let shell = Shell::cuboid(min, max, core).insert(core);
+            let solid = Solid::empty().add_shells([shell], core);
+            FaceBvh::from_solid(&solid, 0.01, core)
+        };
+
+        let a = cube([0., 0., 0.], [1., 1., 1.], &mut core);
+        let disjoint = cube([2., 0., 0.], [3., 1., 1.], &mut core);
+        let overlapping = cube([0.5, 0.25, 0.25], [1.5, 0.75, 0.75], &mut core);
+
+        assert!(a.interfering_faces(&disjoint).is_empty());
+
+        // The smaller cube sticks out of the right face of the other one. Its
+        // top, bottom, front, and back face intersect that face.
+        let pairs = a.interfering_faces(&overlapping);
+        assert_eq!(pairs.len(), 4);
+        for [face, _] in pairs {
+            let aabb = face.aabb().expect("Face has bounding box");
+            assert_eq!(aabb.min.x, Scalar::ONE);
+        }
+    }
+
+    #[test]
+    fn update() {
+        let mut core = Core::new();
+
+        let [a, b] = [[0., 0., 0.], [2., 0., 0.]].map(|[x, y, z]| {
+            Shell::cuboid([x, y, z], [x + 1., y + 1., z + 1.], &mut core)
+                .insert(&mut core)
+        });
+        let ray =
+            Ray::from_origin_and_direction([-1., 0.5, 0.25], [1., 0., 0.]);
+
+        let mut bvh = FaceBvh::new(a.faces(), 0.01, &mut core);
+        assert_eq!(bvh.cast_ray(&ray).len(), 2);
+
+        bvh.update(a.faces().iter().chain(b.faces()), &mut core);
+        assert_eq!(bvh.cast_ray(&ray).len(), 4);
+        let fresh =
+            FaceBvh::new(a.faces().iter().chain(b.faces()), 0.01, &mut core);
+        assert_eq!(bvh.cast_ray(&ray), fresh.cast_ray(&ray));
+
+        bvh.update(b.faces(), &mut core);
+        let hits = bvh.cast_ray(&ray);
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].distance, Scalar::from(3.));
+    }
+}
@@ -0,0 +1,624 @@
+//! Intersection between two curves in 2D
+//!
+//! Lines and circles are intersected with each other exactly. All other
+//! combinations are handled numerically.
+//!
+//! Curves are considered in their full extent: Lines are infinite, circles and
+//! ellipses are closed. Bézier curves are considered between their first and
+//! last control points. The curve coordinates of any intersection point on a
+//! circle or ellipse are normalized into the range `0` (inclusive) to `2 * PI`
+//! (exclusive).
+
+use fj_math::{Circle, Line, Point, Scalar, Vector};
+
+use crate::{geometry::SurfacePath, validation::ValidationConfig};
+
+/// The intersection between two curves
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum CurveCurveIntersection {
+    /// The curves intersect at one or more points
+    ///
+    /// The points are ordered by their curve coordinates on the first curve.
+    Points(Vec<CurveCurveIntersectionPoint>),
+
+    /// The curves are coincident
+    ///
+    /// The curves are the same line or circle, but they don't necessarily
+    /// share the same curve coordinates.
+    Coincident,
+}
+
+/// A point where two curves intersect
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct CurveCurveIntersectionPoint {
+    /// The intersection point, in curve coordinates on each curve
+    pub points_on_curves: [Point<1>; 2],
+
+    /// The intersection point, in surface coordinates
+    pub point_surface: Point<2>,
+}
+
+impl CurveCurveIntersection {
+    /// Compute the intersection between two curves
+    ///
+    /// Lines are considered parallel, if the angle between them is
+    /// within the `identical_max_angle` of the provided configuration. They
+    /// are considered to touch or be coincident, if they are within its
+    /// `identical_max_distance` of each other.
+    ///
+    /// Returns `None`, if the curves don't intersect.
+    ///
+    /// # Implementation Note
+    ///
+    /// Intersections that are computed numerically are found by approximating
+    /// the curves. Points where the curves only touch each other, without
+    /// crossing, might be missed there. Coincident curves are not detected, if
+    /// they are handled numerically.
+    pub fn compute(
+        paths: [&SurfacePath; 2],
+        config: &ValidationConfig,
+    ) -> Option<Self> {
+        let intersection = match paths {
+            [SurfacePath::Line(a), SurfacePath::Line(b)] => {
+                line_line(a, b, config)
+            }
+            [SurfacePath::Line(line), SurfacePath::Circle(circle)] => {
+                Self::Points(line_circle(line, circle, config))
+            }
+            [SurfacePath::Circle(circle), SurfacePath::Line(line)] => {
+                Self::Points(
+                    line_circle(line, circle, config)
+                        .into_iter()
+                        .map(CurveCurveIntersectionPoint::swap)
+                        .collect(),
+                )
+            }
+            [SurfacePath::Circle(a), SurfacePath::Circle(b)] => {
+                circle_circle(a, b, config)
+            }
+            _ => Self::Points(numeric(paths, config)),
+        };
+
+        match intersection {
+            Self::Points(mut points) => {
+                if points.is_empty() {
+                    return None;
+                }
+
+                points.sort();
+                Some(Self::Points(points))
+            }
+            Self::Coincident => Some(Self::Coincident),
+        }
+    }
+}
+
+impl CurveCurveIntersectionPoint {
+    fn from_curve_coords(
+        paths: [&SurfacePath; 2],
+        points_on_curves: [Point<1>; 2],
+    ) -> Self {
+        Self {
+            points_on_curves,
+            point_surface: paths[0].point_from_path_coords(points_on_curves[0]),
+        }
+    }
+
+    fn swap(self) -> Self {
+        let [a, b] = self.points_on_curves;
+
+        Self {
+            points_on_curves: [b, a],
+            point_surface: self.point_surface,
+        }
+    }
+}
+
+fn line_line(
+    a: &Line<2>,
+    b: &Line<2>,
+    config: &ValidationConfig,
+) -> CurveCurveIntersection {
+    let denominator = a.direction().cross2d(&b.direction());
+    let sin_of_angle = denominator.abs()
+        / (a.direction().magnitude() * b.direction().magnitude());
+
+    let origin_to_origin = b.origin() - a.origin();
+
+    if sin_of_angle <= config.identical_max_angle.sin_cos().0 {
+        let distance =
+            a.direction().normalize().cross2d(&origin_to_origin).abs();
+        if distance <= config.identical_max_distance {
+            return CurveCurveIntersection::Coincident;
+        }
+
+        return CurveCurveIntersection::Points(Vec::new());
+    }
+
+    let s = origin_to_origin.cross2d(&b.direction()) / denominator;
+    let t = origin_to_origin.cross2d(&a.direction()) / denominator;
+
+    CurveCurveIntersection::Points(vec![CurveCurveIntersectionPoint {
+        points_on_curves: [[s], [t]].map(Point::from),
+        point_surface: a.point_from_line_coords([s]),
+    }])
+}
+
+fn line_circle(
+    line: &Line<2>,
+    circle: &Circle<2>,
+    config: &ValidationConfig,
+) -> Vec<CurveCurveIntersectionPoint> {
+    let direction = line.direction().normalize();
+    let normal = Vector::from([-direction.v, direction.u]);
+
+    // The point on the line that is closest to the center of the circle.
+    let distance = normal.dot(&(circle.center() - line.origin()));
+    let closest = circle.center() - normal * distance;
+
+    let radius = circle.radius();
+    let offsets =
+        if (distance.abs() - radius).abs() <= config.identical_max_distance {
+            vec![Scalar::ZERO]
+        } else if distance.abs() < radius {
+            let offset = (radius * radius - distance * distance).sqrt();
+            vec![-offset, offset]
+        } else {
+            Vec::new()
+        };
+
+    offsets
+        .into_iter()
+        .map(|offset| {
+            let point_surface = closest + direction * offset;
+
+            CurveCurveIntersectionPoint {
+                points_on_curves: [
+                    line.point_to_line_coords(point_surface),
+                    conic_coords(
+                        circle.center(),
+                        [circle.a(), circle.b()],
+                        point_surface,
+                    ),
+                ],
+                point_surface,
+            }
+        })
+        .collect()
+}
+
+fn circle_circle(
+    a: &Circle<2>,
+    b: &Circle<2>,
+    config: &ValidationConfig,
+) -> CurveCurveIntersection {
+    let [radius_a, radius_b] = [a.radius(), b.radius()];
+
+    let center_to_center = b.center() - a.center();
+    let distance = center_to_center.magnitude();
+
+    if distance <= config.identical_max_distance {
+        if (radius_a - radius_b).abs() <= config.identical_max_distance {
+            return CurveCurveIntersection::Coincident;
+        }
+
+        return CurveCurveIntersection::Points(Vec::new());
+    }
+
+    let touches = (distance - (radius_a + radius_b)).abs()
+        <= config.identical_max_distance
+        || (distance - (radius_a - radius_b).abs()).abs()
+            <= config.identical_max_distance;
+    let is_apart = distance > radius_a + radius_b
+        || distance < (radius_a - radius_b).abs();
+
+    // The intersection points are on a line that is perpendicular to the line
+    // between the centers. This is where it crosses that line.
+    let along = (radius_a * radius_a - radius_b * radius_b
+        + distance * distance)
+        / (distance * 2.);
+    let direction = center_to_center / distance;
+    let normal = Vector::from([-direction.v, direction.u]);
+
+    let offsets = if touches {
+        vec![Scalar::ZERO]
+    } else if is_apart {
+        Vec::new()
+    } else {
+        let offset = (radius_a * radius_a - along * along)
+            .max(Scalar::ZERO)
+            .sqrt();
+        vec![-offset, offset]
+    };
+
+    let points = offsets
+        .into_iter()
+        .map(|offset| {
+            let point_surface =
+                a.center() + direction * along + normal * offset;
+
+            CurveCurveIntersectionPoint {
+                points_on_curves: [a, b].map(|circle| {
+                    conic_coords(
+                        circle.center(),
+                        [circle.a(), circle.b()],
+                        point_surface,
+                    )
+                }),
+                point_surface,
+            }
+        })
+        .collect();
+
+    CurveCurveIntersection::Points(points)
+}
+
+/// The number of segments that each curve is approximated with
+const NUM_SEGMENTS: usize = 64;
+
+/// The maximum number of iterations, when refining an intersection point
+const MAX_ITERATIONS: usize = 32;
+
+/// Compute the intersection between two curves numerically
+///
+/// Both curves are approximated by polylines. Wherever those cross, the
+/// intersection point is refined using Newton's method.
+fn numeric(
+    paths: [&SurfacePath; 2],
+    config: &ValidationConfig,
+) -> Vec<CurveCurveIntersectionPoint> {
+    let ranges = match paths.map(natural_range) {
+        [Some(a), Some(b)] => [a, b],
+        [None, Some(range)] => [line_range(paths[0], paths[1], range), range],
+        [Some(range), None] => [range, line_range(paths[1], paths[0], range)],
+        [None, None] => {
+            unreachable!("Intersection of lines is computed exactly")
+        }
+    };
+    let polylines = [0, 1].map(|i| {
+        // A line is straight, so a single segment is enough.
+        let num_segments = if let SurfacePath::Line(_) = paths[i] {
+            1
+        } else {
+            NUM_SEGMENTS
+        };
+
+        let [min, max] = ranges[i];
+        (0..=num_segments)
+            .map(|j| {
+                let t = min + (max - min) * (j as f64 / num_segments as f64);
+                (t, paths[i].point_from_path_coords([t]))
+            })
+            .collect::<Vec<_>>()
+    });
+
+    let mut points: Vec<CurveCurveIntersectionPoint> = Vec::new();
+
+    for a in polylines[0].windows(2) {
+        for b in polylines[1].windows(2) {
+            let Some([s, t]) =
+                segment_segment([a[0].1, a[1].1], [b[0].1, b[1].1])
+            else {
+                continue;
+            };
+
+            let estimate = [
+                a[0].0 + (a[1].0 - a[0].0) * s,
+                b[0].0 + (b[1].0 - b[0].0) * t,
+            ];
+            let Some(coords) = refine(paths, estimate, config) else {
+                continue;
+            };
+            let Some(coords) = normalize(paths, coords) else {
+                continue;
+            };
+
+            let point = CurveCurveIntersectionPoint::from_curve_coords(
+                paths,
+                coords.map(|t| Point::from([t])),
+            );
+
+            // Crossings at the vertices of the polylines are found more than
+            // once.
+            let is_known = points.iter().any(|known| {
+                (known.point_surface - point.point_surface).magnitude()
+                    < config.distinct_min_distance
+            });
+            if !is_known {
+                points.push(point);
+            }
+        }
+    }
+
+    points
+}
+
+/// The range of curve coordinates that covers the full extent of the curve
+///
+/// Returns `None` for lines, which are infinite.
+fn natural_range(path: &SurfacePath) -> Option<[Scalar; 2]> {
+    match path {
+        SurfacePath::Circle(_) | SurfacePath::Ellipse(_) => {
+            Some([Scalar::ZERO, Scalar::TAU])
+        }
+        SurfacePath::CubicBezier(_) => Some([Scalar::ZERO, Scalar::ONE]),
+        SurfacePath::Line(_) => None,
+    }
+}
+
+/// The range of a line, that covers where it could cross the other curve
+fn line_range(
+    line: &SurfacePath,
+    other: &SurfacePath,
+    range: [Scalar; 2],
+) -> [Scalar; 2] {
+    let SurfacePath::Line(line) = line else {
+        unreachable!("Only lines have no natural range");
+    };
+
+    // The other curve is contained in the convex hull of these points, or
+    // close enough, so that the margin below covers it.
+    let [min, max] = range;
+    let coords = (0..=NUM_SEGMENTS).map(|i| {
+        let t = min + (max - min) * (i as f64 / NUM_SEGMENTS as f64);
+        line.point_to_line_coords(other.point_from_path_coords([t]))
+            .t
+    });
+
+    let (min, max) =
+        coords.fold((Scalar::MAX, -Scalar::MAX), |(min, max), t| {
+            (if t < min { t } else { min }, if t > max { t } else { max })
+        });
+    let margin = (max - min) / 2. + Scalar::ONE;
+
+    [min - margin, max + margin]
+}
+
+/// Compute where two segments cross
+///
+/// Returns the position of the crossing along each segment, from `0` at its
+/// first point to `1` at its second.
+fn segment_segment(
+    [a, b]: [Point<2>; 2],
+    [c, d]: [Point<2>; 2],
+) -> Option<[Scalar; 2]> {
+    let [r, s] = [b - a, d - c];
+
+    let denominator = r.cross2d(&s);
+    if denominator == Scalar::ZERO {
+        return None;
+    }
+
+    let t = (c - a).cross2d(&s) / denominator;
+    let u = (c - a).cross2d(&r) / denominator;
+
+    let range = Scalar::ZERO..=Scalar::ONE;
+    (range.contains(&t) && range.contains(&u)).then_some([t, u])
+}
+
+/// Refine an intersection point, using Newton's method
+fn refine(
+    paths: [&SurfacePath; 2],
+    estimate: [Scalar; 2],
+    config: &ValidationConfig,
+) -> Option<[Scalar; 2]> {
+    let mut coords = estimate;
+    let mut best: Option<([Scalar; 2], Scalar)> = None;
+
+    for _ in 0..MAX_ITERATIONS {
+        let [a, b] =
+            [0, 1].map(|i| paths[i].point_from_path_coords([coords[i]]));
+        let offset = a - b;
+        let distance = offset.magnitude();
+
+        if let Some((_, best_distance)) = best {
+            if distance >= best_distance {
+                break;
+            }
+        }
+        best = Some((coords, distance));
+        if distance <= config.identical_max_distance {
+            break;
+        }
+
+        let [da, db] = [0, 1].map(|i| derivative(paths[i], coords[i]));
+
+        // Solve `da * s - db * t = offset` for the step.
+        let determinant = db.cross2d(&da);
+        if determinant == Scalar::ZERO {
+            break;
+        }
+        let s = db.cross2d(&offset) / determinant;
+        let t = da.cross2d(&offset) / determinant;
+
+        coords = [coords[0] - s, coords[1] - t];
+    }
+
+    let (coords, distance) = best?;
+    (distance < config.distinct_min_distance).then_some(coords)
+}
+
+/// Normalize the coordinates of an intersection point
+///
+/// Returns `None`, if the point is not within the extent of the curves.
+fn normalize(
+    paths: [&SurfacePath; 2],
+    coords: [Scalar; 2],
+) -> Option<[Scalar; 2]> {
+    let [a, b] = [0, 1].map(|i| {
+        let t = coords[i];
+
+        match paths[i] {
+            SurfacePath::Circle(_) | SurfacePath::Ellipse(_) => Some(
+                Scalar::from(t.into_f64().rem_euclid(Scalar::TAU.into_f64())),
+            ),
+            SurfacePath::CubicBezier(_) => {
+                (Scalar::ZERO..=Scalar::ONE).contains(&t).then_some(t)
+            }
+            SurfacePath::Line(_) => Some(t),
+        }
+    });
+
+    Some([a?, b?])
+}
+
+/// Compute the derivative of a path with respect to its path coordinate
+pub(crate) fn derivative(path: &SurfacePath, t: Scalar) -> Vector<2> {
+    match path {
+        SurfacePath::Circle(circle) => {
+            let (sin, cos) = t.sin_cos();
+            circle.b() * cos - circle.a() * sin
+        }
+        SurfacePath::CubicBezier(bezier) => bezier.derivative([t]),
+        SurfacePath::Ellipse(ellipse) => {
+            let (sin, cos) = t.sin_cos();
+            ellipse.b() * cos - ellipse.a() * sin
+        }
+        SurfacePath::Line(line) => line.direction(),
+    }
+}
+
+/// Compute the curve coordinates of a point on a circle or ellipse
+pub(crate) fn conic_coords(
+    center: Point<2>,
+    [a, b]: [Vector<2>; 2],
+    point: Point<2>,
+) -> Point<1> {
+    let vector = point - center;
+
+    let cos = vector.dot(&a) / a.dot(&a);
+    let sin = vector.dot(&b) / b.dot(&b);
+    let angle = sin.atan2(cos).into_f64().rem_euclid(Scalar::TAU.into_f64());
+
+    Point::from([angle])
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar};
+
+    use crate::{geometry::SurfacePath, validation::ValidationConfig};
+
+    use super::CurveCurveIntersection;
+
+    #[test]
+    fn line_line() {
+        let config = ValidationConfig::default();
+
+        let (a, _) = SurfacePath::line_from_points([[0., 0.], [1., 0.]]);
+        let (b, _) = SurfacePath::line_from_points([[1., -1.], [1., 1.]]);
+        assert_points([&a, &b], &[[1., 0.]], &config);
+
+        let (parallel, _) = SurfacePath::line_from_points([[0., 1.], [1., 1.]]);
+        assert_eq!(
+            CurveCurveIntersection::compute([&a, &parallel], &config),
+            None,
+        );
+
+        let (coincident, _) =
+            SurfacePath::line_from_points([[2., 0.], [3., 0.]]);
+        assert_eq!(
+            CurveCurveIntersection::compute([&a, &coincident], &config),
+            Some(CurveCurveIntersection::Coincident),
+        );
+    }
+
+    #[test]
+    fn line_circle() {
+        let config = ValidationConfig::default();
+
+        let circle = SurfacePath::circle_from_center_and_radius([0., 0.], 1.);
+
+        let (secant, _) = SurfacePath::line_from_points([[-2., 0.], [2., 0.]]);
+        assert_points([&secant, &circle], &[[-1., 0.], [1., 0.]], &config);
+        assert_points([&circle, &secant], &[[1., 0.], [-1., 0.]], &config);
+
+        let (tangent, _) = SurfacePath::line_from_points([[-2., 1.], [2., 1.]]);
+        assert_points([&tangent, &circle], &[[0., 1.]], &config);
+
+        let (outside, _) = SurfacePath::line_from_points([[-2., 2.], [2., 2.]]);
+        assert_eq!(
+            CurveCurveIntersection::compute([&outside, &circle], &config),
+            None,
+        );
+    }
+
+    #[test]
+    fn circle_circle() {
+        let config = ValidationConfig::default();
+
+        let a = SurfacePath::circle_from_center_and_radius([0., 0.], 1.);
+
+        let b = SurfacePath::circle_from_center_and_radius([1., 0.], 1.);
+        let y = Scalar::from(0.75).into_f64().sqrt();
+        assert_points([&a, &b], &[[0.5, y], [0.5, -y]], &config);
+
+        let touching = SurfacePath::circle_from_center_and_radius([2., 0.], 1.);
+        assert_points([&a, &touching], &[[1., 0.]], &config);
+
+        let inside = SurfacePath::circle_from_center_and_radius([0., 0.], 0.5);
+        assert_eq!(
+            CurveCurveIntersection::compute([&a, &inside], &config),
+            None
+        );
+
+        assert_eq!(
+            CurveCurveIntersection::compute([&a, &a], &config),
+            Some(CurveCurveIntersection::Coincident),
+        );
+    }
+
+    #[test]
+    fn numeric() {
+        let config = ValidationConfig::default();
+
+        let ellipse =
+            SurfacePath::ellipse_from_center_and_radii([0., 0.], [2., 1.]);
+        let (line, _) = SurfacePath::line_from_points([[1., -2.], [1., 2.]]);
+        let y = Scalar::from(0.75).into_f64().sqrt();
+        assert_points([&line, &ellipse], &[[1., -y], [1., y]], &config);
+
+        let bezier = SurfacePath::cubic_bezier([
+            [0., 0.],
+            [1., 2.],
+            [2., -2.],
+            [3., 0.],
+        ]);
+        let (line, _) = SurfacePath::line_from_points([[0., 0.], [3., 0.]]);
+        assert_points(
+            [&bezier, &line],
+            &[[0., 0.], [1.5, 0.], [3., 0.]],
+            &config,
+        );
+    }
+
+    fn assert_points(
+        paths: [&SurfacePath; 2],
+        expected: &[[f64; 2]],
+        config: &ValidationConfig,
+    ) {
+        let Some(CurveCurveIntersection::Points(points)) =
+            CurveCurveIntersection::compute(paths, config)
+        else {
+            panic!("Expected curves to intersect at points");
+        };
+
+        assert_eq!(points.len(), expected.len());
+        for (point, expected) in points.iter().zip(expected) {
+            let expected = Point::from(*expected);
+            assert!(
+                (point.point_surface - expected).magnitude()
+                    < Scalar::from(1e-9)
+            );
+
+            // The point must be on both curves, at the returned coordinates.
+            for (path, point_curve) in paths.iter().zip(point.points_on_curves)
+            {
+                let point_surface = path.point_from_path_coords(point_curve);
+                assert!(
+                    (point_surface - expected).magnitude() < Scalar::from(1e-9)
+                );
+            }
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use fj_math::{Point, Segment};
 
-use crate::{geometry::SurfacePath, objects::HalfEdge};
+use crate::{
+    geometry::SurfacePath, objects::HalfEdge, validation::ValidationConfig,
+};
 
 use super::LineSegmentIntersection;
 
@@ -27,7 +29,13 @@ impl CurveEdgeIntersection {
     ///
     /// Currently, only intersections between lines and line segments can be
     /// computed. Panics, if a different type of curve or [`HalfEdge`] is passed.
-    pub fn compute(path: &SurfacePath, edge: &HalfEdge) -> Option<Self> {
+    ///
+    /// See [`LineSegmentIntersection::compute`] for how `config` is used.
+    pub fn compute(
+        path: &SurfacePath,
+        edge: &HalfEdge,
+        config: &ValidationConfig,
+    ) -> Option<Self> {
         let path_as_line = match path {
             SurfacePath::Line(line) => line,
             _ => todo!("Curve-edge intersection only supports lines"),
@@ -49,8 +57,11 @@ impl CurveEdgeIntersection {
             Segment::from_points(edge_vertices)
         };
 
-        let intersection =
-            LineSegmentIntersection::compute(path_as_line, &edge_as_segment)?;
+        let intersection = LineSegmentIntersection::compute(
+            path_as_line,
+            &edge_as_segment,
+            config,
+        )?;
 
         let intersection = match intersection {
             LineSegmentIntersection::Point { point_on_line } => Self::Point {
@@ -86,7 +97,11 @@ mod tests {
         let edge =
             HalfEdge::line_segment([[1., -1.], [1., 1.]], None, &mut core);
 
-        let intersection = CurveEdgeIntersection::compute(&path, &edge);
+        let intersection = CurveEdgeIntersection::compute(
+            &path,
+            &edge,
+            &core.layers.validation.config,
+        );
 
         assert_eq!(
             intersection,
@@ -104,7 +119,11 @@ mod tests {
         let edge =
             HalfEdge::line_segment([[-1., -1.], [-1., 1.]], None, &mut core);
 
-        let intersection = CurveEdgeIntersection::compute(&path, &edge);
+        let intersection = CurveEdgeIntersection::compute(
+            &path,
+            &edge,
+            &core.layers.validation.config,
+        );
 
         assert_eq!(
             intersection,
@@ -122,7 +141,11 @@ mod tests {
         let edge =
             HalfEdge::line_segment([[-1., -1.], [1., -1.]], None, &mut core);
 
-        let intersection = CurveEdgeIntersection::compute(&path, &edge);
+        let intersection = CurveEdgeIntersection::compute(
+            &path,
+            &edge,
+            &core.layers.validation.config,
+        );
 
         assert!(intersection.is_none());
     }
@@ -135,7 +158,11 @@ mod tests {
         let edge =
             HalfEdge::line_segment([[-1., 0.], [1., 0.]], None, &mut core);
 
-        let intersection = CurveEdgeIntersection::compute(&path, &edge);
+        let intersection = CurveEdgeIntersection::compute(
+            &path,
+            &edge,
+            &core.layers.validation.config,
+        );
 
         assert_eq!(
             intersection,
@@ -3,7 +3,9 @@ use std::vec;
 use fj_interop::ext::SliceExt;
 use fj_math::Point;
 
-use crate::{geometry::SurfacePath, objects::Face};
+use crate::{
+    geometry::SurfacePath, objects::Face, validation::ValidationConfig,
+};
 
 use super::CurveEdgeIntersection;
 
@@ -28,7 +30,13 @@ impl CurveFaceIntersection {
     }
 
     /// Compute the intersection
-    pub fn compute(path: &SurfacePath, face: &Face) -> Self {
+    ///
+    /// See [`CurveEdgeIntersection::compute`] for how `config` is used.
+    pub fn compute(
+        path: &SurfacePath,
+        face: &Face,
+        config: &ValidationConfig,
+    ) -> Self {
         let edges = face
             .region()
             .all_cycles()
@@ -37,7 +45,8 @@ impl CurveFaceIntersection {
         let mut intersections = Vec::new();
 
         for edge in edges {
-            let intersection = CurveEdgeIntersection::compute(path, edge);
+            let intersection =
+                CurveEdgeIntersection::compute(path, edge, config);
 
             if let Some(intersection) = intersection {
                 match intersection {
@@ -204,7 +213,14 @@ mod tests {
 
         let expected =
             CurveFaceIntersection::from_intervals([[[1.], [2.]], [[4.], [5.]]]);
-        assert_eq!(CurveFaceIntersection::compute(&path, &face), expected);
+        assert_eq!(
+            CurveFaceIntersection::compute(
+                &path,
+                &face,
+                &core.layers.validation.config
+            ),
+            expected
+        );
     }
 
     #[test]
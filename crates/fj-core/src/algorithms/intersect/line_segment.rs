@@ -1,4 +1,6 @@
-use fj_math::{Aabb, Line, Point, Scalar, Segment, Vector};
+use fj_math::{Aabb, Line, Point, Segment, Vector};
+
+use crate::validation::ValidationConfig;
 
 /// An intersection between a [`Line`] and a [`Segment`]
 #[derive(Debug, Eq, PartialEq)]
@@ -18,25 +20,35 @@ pub enum LineSegmentIntersection {
 
 impl LineSegmentIntersection {
     /// Determine the intersection between a [`Line`] and a [`Segment`]
-    pub fn compute(line: &Line<2>, segment: &Segment<2>) -> Option<Self> {
+    ///
+    /// The line and segment are considered parallel, if the angle between them
+    /// is within the `identical_max_angle` of the provided configuration. They
+    /// are considered coincident, if they are also within its
+    /// `identical_max_distance` of each other.
+    pub fn compute(
+        line: &Line<2>,
+        segment: &Segment<2>,
+        config: &ValidationConfig,
+    ) -> Option<Self> {
         // Algorithm adapted from Real-Time Collision Detection by Christer
         // Ericson. See section 5.1.9.1, 2D Segment Intersection.
 
         let [a, b] = segment.points();
 
-        // Find vector that is orthogonal to `segment`.
+        // Find unit vector that is orthogonal to `segment`.
         let n = {
             let ab = b - a;
-            Vector::from([ab.v, ab.u])
+            Vector::from([-ab.v, ab.u]).normalize()
         };
 
         let n_dot_origin = n.dot(&(b - line.origin()));
         let n_dot_direction = n.dot(&line.direction());
 
-        if n_dot_direction == Scalar::ZERO {
+        let sin_of_angle = n_dot_direction.abs() / line.direction().magnitude();
+        if sin_of_angle <= config.identical_max_angle.sin_cos().0 {
             // `line` and `segment` are parallel
 
-            if n_dot_origin == Scalar::ZERO {
+            if n_dot_origin.abs() <= config.identical_max_distance {
                 // `line` and `segment` are not just parallel, but coincident!
                 return Some(Self::Coincident {
                     points_on_line: segment
@@ -68,6 +80,8 @@ impl LineSegmentIntersection {
 mod tests {
     use fj_math::{Line, Point, Scalar, Segment, Vector};
 
+    use crate::validation::ValidationConfig;
+
     use super::LineSegmentIntersection;
 
     #[test]
@@ -79,6 +93,7 @@ mod tests {
             LineSegmentIntersection::compute(
                 &line,
                 &Segment::from_points([[1., -1.], [1., 1.]]),
+                &ValidationConfig::default(),
             ),
             Some(LineSegmentIntersection::Point {
                 point_on_line: Point::from([Scalar::ONE])
@@ -95,6 +110,7 @@ mod tests {
             LineSegmentIntersection::compute(
                 &line,
                 &Segment::from_points([[1., 0.], [2., 0.]]),
+                &ValidationConfig::default(),
             ),
             Some(LineSegmentIntersection::Coincident {
                 points_on_line: [Point::from([1.]), Point::from([2.])],
@@ -111,6 +127,7 @@ mod tests {
             LineSegmentIntersection::compute(
                 &line,
                 &Segment::from_points([[1., 1.], [1., 2.]]),
+                &ValidationConfig::default(),
             ),
             None,
         );
@@ -125,6 +142,7 @@ mod tests {
             LineSegmentIntersection::compute(
                 &line,
                 &Segment::from_points([[1., -2.], [1., -1.]]),
+                &ValidationConfig::default(),
             ),
             None,
         );
@@ -139,8 +157,37 @@ mod tests {
             LineSegmentIntersection::compute(
                 &line,
                 &Segment::from_points([[-1., 1.], [1., 1.]]),
+                &ValidationConfig::default(),
             ),
             None,
         );
     }
+
+    #[test]
+    fn compute_coincident_within_tolerance() {
+        let line =
+            Line::from_origin_and_direction(Point::origin(), Vector::unit_u());
+        let segment = Segment::from_points([[1., -1e-6], [2., 1e-6]]);
+
+        assert!(matches!(
+            LineSegmentIntersection::compute(
+                &line,
+                &segment,
+                &ValidationConfig::default(),
+            ),
+            Some(LineSegmentIntersection::Point { .. }),
+        ));
+        assert!(matches!(
+            LineSegmentIntersection::compute(
+                &line,
+                &segment,
+                &ValidationConfig {
+                    identical_max_distance: Scalar::from(1e-5),
+                    identical_max_angle: Scalar::from(1e-5),
+                    ..ValidationConfig::default()
+                },
+            ),
+            Some(LineSegmentIntersection::Coincident { .. }),
+        ));
+    }
 }
@@ -1,8 +1,13 @@
 //! Intersection algorithms
 
+pub mod curve_curve;
 pub mod face_point;
 pub mod ray_edge;
 pub mod ray_segment;
+pub mod ray_shape;
+pub mod solid_point;
+pub mod solid_solid;
+pub mod surface_surface;
 
 mod curve_edge;
 mod curve_face;
@@ -22,6 +22,12 @@ impl Intersect for (&HorizontalRayToTheRight<2>, &Handle<HalfEdge>) {
             SurfacePath::Circle(_) => {
                 todo!("Casting rays against circles is not supported yet")
             }
+            SurfacePath::CubicBezier(_) => {
+                todo!("Casting rays against Bézier curves is not supported yet")
+            }
+            SurfacePath::Ellipse(_) => {
+                todo!("Casting rays against ellipses is not supported yet")
+            }
         };
 
         let points = edge
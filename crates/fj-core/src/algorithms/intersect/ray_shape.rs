@@ -0,0 +1,366 @@
+//! Intersection between a ray and the faces of a shape
+//!
+//! See [`CastRay`].
+
+use fj_math::{Aabb, Point, Ray, Scalar, Triangle, Vector};
+
+use crate::{
+    algorithms::{
+        approx::{Approx, Tolerance},
+        bounding_volume::BoundingVolume,
+        triangulate::delaunay::{self, TriangulationPoint},
+    },
+    geometry::SurfaceGeometry,
+    objects::{Face, Shell, Solid},
+    storage::Handle,
+    validation::ValidationConfig,
+    Core,
+};
+
+/// Cast a ray against the faces of a shape
+pub trait CastRay {
+    /// Compute the points where the ray hits the faces of the shape
+    ///
+    /// Returns all hits, ordered by their distance from the origin of the ray.
+    /// Where the ray hits an edge or a vertex, it hits all faces that share
+    /// that edge or vertex, and there is a hit for each of them.
+    ///
+    /// # Implementation Note
+    ///
+    /// Faces are approximated with the provided tolerance, to find the hits.
+    /// Each hit is then refined, to lie on the surface of its face exactly.
+    /// Where the ray only grazes a face, the approximation might not register
+    /// that, and the hit could be missed.
+    fn cast_ray(
+        &self,
+        ray: &Ray<3>,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Vec<RayHit>;
+}
+
+impl CastRay for Shell {
+    fn cast_ray(
+        &self,
+        ray: &Ray<3>,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Vec<RayHit> {
+        cast_ray(self.faces(), ray, tolerance.into(), core)
+    }
+}
+
+impl CastRay for Solid {
+    fn cast_ray(
+        &self,
+        ray: &Ray<3>,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Vec<RayHit> {
+        let faces = self.shells().iter().flat_map(|shell| shell.faces());
+        cast_ray(faces, ray, tolerance.into(), core)
+    }
+}
+
+/// A point where a ray hits a face
+///
+/// See [`CastRay`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct RayHit {
+    /// The distance of the hit from the origin of the ray
+    pub distance: Scalar,
+
+    /// The face that was hit
+    pub face: Handle<Face>,
+
+    /// The point where the face was hit, in surface coordinates
+    pub point_surface: Point<2>,
+
+    /// The point where the face was hit, in global coordinates
+    pub point_global: Point<3>,
+}
+
+/// The maximum number of iterations, when refining a hit
+const MAX_ITERATIONS: usize = 16;
+
+fn cast_ray<'r>(
+    faces: impl IntoIterator<Item = &'r Handle<Face>>,
+    ray: &Ray<3>,
+    tolerance: Tolerance,
+    core: &mut Core,
+) -> Vec<RayHit> {
+    let config = core.layers.validation.config;
+
+    let mut hits = Vec::new();
+
+    for face in faces {
+        if let Some(aabb) = face.aabb() {
+            // The approximation of the face can deviate from it by the
+            // tolerance.
+            let margin = Vector::from([tolerance.inner(); 3]);
+            let aabb = Aabb {
+                min: aabb.min - margin,
+                max: aabb.max + margin,
+            };
+
+            if !ray_hits_aabb(ray, &aabb) {
+                continue;
+            }
+        }
+
+        let triangles = triangulate_face(face, tolerance, core);
+        hits.extend(hit_face(face, &triangles, ray, &config));
+    }
+
+    hits.sort();
+    hits
+}
+
+/// Triangulate the approximation of a face
+pub(crate) fn triangulate_face(
+    face: &Face,
+    tolerance: Tolerance,
+    core: &mut Core,
+) -> Vec<[TriangulationPoint; 3]> {
+    let approx = face.approx(tolerance, core);
+    delaunay::triangulate(
+        [approx.exterior].into_iter().chain(approx.interiors),
+        approx.interior_points,
+        approx.coord_handedness,
+        None,
+    )
+}
+
+/// Compute the points where the ray hits a face
+///
+/// Expects the triangles of the face's approximation, as returned by
+/// [`triangulate_face`].
+pub(crate) fn hit_face(
+    face: &Handle<Face>,
+    triangles: &[[TriangulationPoint; 3]],
+    ray: &Ray<3>,
+    config: &ValidationConfig,
+) -> Vec<RayHit> {
+    let mut face_hits: Vec<RayHit> = Vec::new();
+
+    for triangle in triangles {
+        let Some((point_surface, distance)) = hit_triangle(ray, *triangle)
+        else {
+            continue;
+        };
+
+        let surface = face.surface().geometry();
+        let (point_surface, distance) = refine(
+            surface,
+            ray,
+            point_surface,
+            distance,
+            config.identical_max_distance,
+        );
+        if distance < Scalar::ZERO {
+            continue;
+        }
+        let point_global = surface.point_from_surface_coords(point_surface);
+
+        // Where the ray hits an edge between triangles, it hits all of them.
+        let is_known = face_hits.iter().any(|hit| {
+            hit.point_global.distance_to(&point_global)
+                < config.distinct_min_distance
+        });
+        if is_known {
+            continue;
+        }
+
+        face_hits.push(RayHit {
+            distance,
+            face: face.clone(),
+            point_surface,
+            point_global,
+        });
+    }
+
+    face_hits
+}
+
+/// Determine whether the ray hits the bounding box
+pub(crate) fn ray_hits_aabb(ray: &Ray<3>, aabb: &Aabb<3>) -> bool {
+    let mut min = Scalar::ZERO;
+    let mut max = Scalar::MAX;
+
+    for i in 0..3 {
+        let origin = ray.origin().coords.components[i];
+        let direction = ray.direction().components[i];
+        let [lower, upper] =
+            [aabb.min, aabb.max].map(|p| p.coords.components[i]);
+
+        if direction == Scalar::ZERO {
+            if origin < lower || origin > upper {
+                return false;
+            }
+            continue;
+        }
+
+        let [a, b] = [lower, upper].map(|bound| (bound - origin) / direction);
+        let (near, far) = if a < b { (a, b) } else { (b, a) };
+
+        min = min.max(near);
+        max = if far < max { far } else { max };
+
+        if min > max {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Compute where the ray hits a triangle of the face's approximation
+///
+/// Returns the hit in surface coordinates, as well as its distance from the
+/// origin of the ray.
+fn hit_triangle(
+    ray: &Ray<3>,
+    triangle: [TriangulationPoint; 3],
+) -> Option<(Point<2>, Scalar)> {
+    let points = triangle.map(|point| point.point_global);
+    let distance = Triangle::from_points(points).ok()?.cast_local_ray(
+        ray.origin(),
+        ray.direction(),
+        f64::INFINITY,
+        true,
+    )?;
+
+    // Interpolate the surface coordinates of the hit, using its barycentric
+    // coordinates within the triangle.
+    let [a, b, c] = points;
+    let point = ray.point_at(distance);
+    let normal = (b - a).cross(&(c - a));
+    let area = normal.dot(&normal);
+    let weight_b = (point - a).cross(&(c - a)).dot(&normal) / area;
+    let weight_c = (b - a).cross(&(point - a)).dot(&normal) / area;
+    let weight_a = Scalar::ONE - weight_b - weight_c;
+
+    let [a, b, c] = triangle.map(|point| point.point_surface.coords);
+    let point_surface = Point {
+        coords: a * weight_a + b * weight_b + c * weight_c,
+    };
+
+    Some((point_surface, distance))
+}
+
+/// Refine a hit, to lie exactly on the surface
+///
+/// Uses Newton's method to find the point where the ray intersects the surface.
+/// Returns the original hit, if that doesn't converge.
+fn refine(
+    surface: &SurfaceGeometry,
+    ray: &Ray<3>,
+    point_surface: Point<2>,
+    distance: Scalar,
+    max_error: Scalar,
+) -> (Point<2>, Scalar) {
+    let mut point = point_surface;
+    let mut t = distance;
+
+    for _ in 0..MAX_ITERATIONS {
+        let error = surface.point_from_surface_coords(point) - ray.point_at(t);
+        if error.magnitude() <= max_error {
+            return (point, t);
+        }
+
+        // Solve `du * s + dv * r - direction * q = error` for the step, using
+        // Cramer's rule.
+        let [du, dv] = surface.derivatives(point);
+        let columns = [du, dv, -ray.direction()];
+
+        let determinant = columns[0].dot(&columns[1].cross(&columns[2]));
+        if determinant == Scalar::ZERO {
+            break;
+        }
+        let [s, r, q] = [0, 1, 2].map(|i| {
+            let mut columns = columns;
+            columns[i] = error;
+            columns[0].dot(&columns[1].cross(&columns[2])) / determinant
+        });
+
+        point = Point {
+            coords: point.coords - Vector::from([s, r]),
+        };
+        t -= q;
+    }
+
+    let error = surface.point_from_surface_coords(point) - ray.point_at(t);
+    if error.magnitude() <= max_error {
+        (point, t)
+    } else {
+        (point_surface, distance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Ray, Scalar};
+    use fj_test_utils::{assert_points_eq, assert_scalars_eq};
+
+    use crate::{
+        objects::{Shell, Solid},
+        operations::build::{BuildShell, BuildSolid},
+        Core,
+    };
+
+    use super::CastRay;
+
+    #[test]
+    fn cuboid() {
+        let mut core = Core::new();
+
+        let cuboid = Shell::cuboid([0., 0., 0.], [1., 1., 1.], &mut core);
+        let ray =
+            Ray::from_origin_and_direction([0.5, 0.25, -1.], [0., 0., 1.]);
+
+        let hits = cuboid.cast_ray(&ray, 0.01, &mut core);
+        assert_eq!(hits.len(), 2);
+
+        let [a, b] = [&hits[0], &hits[1]];
+        assert_eq!(a.distance, Scalar::ONE);
+        assert_eq!(b.distance, Scalar::TWO);
+        assert_eq!(a.point_global, Point::from([0.5, 0.25, 0.]));
+        assert_eq!(b.point_global, Point::from([0.5, 0.25, 1.]));
+        assert_ne!(a.face.id(), b.face.id());
+
+        for hit in hits {
+            let point = hit
+                .face
+                .surface()
+                .geometry()
+                .point_from_surface_coords(hit.point_surface);
+            assert_eq!(point, hit.point_global);
+        }
+
+        let ray =
+            Ray::from_origin_and_direction([0.5, 0.25, -1.], [0., 0., -1.]);
+        assert!(cuboid.cast_ray(&ray, 0.01, &mut core).is_empty());
+    }
+
+    #[test]
+    fn sphere() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let sphere = Solid::sphere(1., &mut core)?;
+        let ray = Ray::from_origin_and_direction([-2., 0.3, 0.4], [1., 0., 0.]);
+
+        let hits = sphere.cast_ray(&ray, 0.01, &mut core);
+        assert_eq!(hits.len(), 2);
+
+        // The hits are refined to lie on the sphere exactly, not on its
+        // approximation.
+        let x = Scalar::from(1. - 0.3 * 0.3 - 0.4 * 0.4).into_f64().sqrt();
+        for (hit, x) in hits.iter().zip([-x, x]) {
+            let expected = Point::from([x, 0.3, 0.4]);
+            assert_points_eq!(hit.point_global, expected, 1e-9);
+            assert_scalars_eq!(hit.distance, x + 2., 1e-9);
+        }
+
+        Ok(())
+    }
+}
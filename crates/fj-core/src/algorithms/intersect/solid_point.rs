@@ -0,0 +1,315 @@
+//! Intersection between a solid and a point
+//!
+//! See [`ContainsPoint`].
+
+use std::f64::consts::PI;
+
+use fj_math::{Point, Ray, Scalar, Vector};
+
+use crate::{
+    algorithms::approx::Tolerance,
+    objects::{Shell, Solid},
+    Core,
+};
+
+use super::ray_shape::{CastRay, RayHit};
+
+/// Determine whether a shape contains a point
+pub trait ContainsPoint {
+    /// Determine whether the shape contains the point
+    ///
+    /// Points on the boundary of the shape, within the provided tolerance, are
+    /// considered to be contained.
+    ///
+    /// Returns `None`, if this can't be determined, because every ray that
+    /// was cast was ambiguous (see implementation note below).
+    ///
+    /// # Implementation Note
+    ///
+    /// This casts a ray from the point, and counts how often it crosses the
+    /// boundary of the shape. Where that is ambiguous, because the ray hits an
+    /// edge or a vertex, or only grazes a face, another ray is cast in a
+    /// different direction, up to [`MAX_RAYS`] rays in total.
+    ///
+    /// A ray grazes a face, if it stays within the tolerance of the face's
+    /// tangent plane over the distance it travels through the shape.
+    fn contains_point(
+        &self,
+        point: impl Into<Point<3>>,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Option<bool>;
+}
+
+impl ContainsPoint for Shell {
+    fn contains_point(
+        &self,
+        point: impl Into<Point<3>>,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Option<bool> {
+        contains_point(
+            |ray, tolerance, core| self.cast_ray(ray, tolerance, core),
+            point.into(),
+            tolerance.into(),
+            core,
+        )
+    }
+}
+
+impl ContainsPoint for Solid {
+    fn contains_point(
+        &self,
+        point: impl Into<Point<3>>,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Option<bool> {
+        contains_point(
+            |ray, tolerance, core| self.cast_ray(ray, tolerance, core),
+            point.into(),
+            tolerance.into(),
+            core,
+        )
+    }
+}
+
+/// The directions, in which rays are cast
+///
+/// These are chosen to not be aligned with anything that models are likely to
+/// be aligned with.
+pub(crate) const DIRECTIONS: [[f64; 3]; 4] = [
+    [1., 0.61, 0.37],
+    [-0.43, 1., 0.71],
+    [0.29, -0.53, 1.],
+    [-0.83, -0.47, -0.19],
+];
+
+/// The maximum number of rays that are cast for a single query
+pub const MAX_RAYS: usize = 32;
+
+/// The directions, in which rays are cast, in order
+///
+/// Starts with [`DIRECTIONS`]. In case all of those are ambiguous, the rest
+/// are spread evenly over the unit sphere.
+pub(crate) fn ray_directions() -> impl Iterator<Item = Vector<3>> {
+    let num_spread = MAX_RAYS - DIRECTIONS.len();
+    let golden_angle = PI * (3. - 5f64.sqrt());
+
+    let spread = (0..num_spread).map(move |i| {
+        let i = i as f64 + 0.5;
+
+        let z = 1. - 2. * i / num_spread as f64;
+        let radius = (1. - z * z).sqrt();
+        let (sin, cos) = (golden_angle * i).sin_cos();
+
+        Vector::from([radius * cos, radius * sin, z])
+    });
+
+    DIRECTIONS
+        .into_iter()
+        .map(|direction| Vector::from(direction).normalize())
+        .chain(spread)
+}
+
+fn contains_point(
+    cast_ray: impl Fn(&Ray<3>, Tolerance, &mut Core) -> Vec<RayHit>,
+    point: Point<3>,
+    tolerance: Tolerance,
+    core: &mut Core,
+) -> Option<bool> {
+    for direction in ray_directions() {
+        // A ray that starts right on a face, might or might not hit it. Start
+        // a bit before the point, so the face is hit either way.
+        let ray = Ray::from_origin_and_direction(
+            point - direction * tolerance.inner(),
+            direction,
+        );
+        let hits = cast_ray(&ray, tolerance, core);
+
+        if hits
+            .iter()
+            .any(|hit| hit.distance <= tolerance.inner() * 2.)
+        {
+            // The point is on the boundary.
+            return Some(true);
+        }
+
+        let hits_edge_or_vertex = hits.windows(2).any(|hits| {
+            hits[0].point_global.distance_to(&hits[1].point_global)
+                <= tolerance.inner()
+        });
+        let length = hits
+            .iter()
+            .map(|hit| hit.distance)
+            .fold(Scalar::ZERO, Scalar::max);
+        let grazes_face = hits.iter().any(|hit| {
+            let normal =
+                hit.face.surface().geometry().normal(hit.point_surface);
+            normal.dot(&ray.direction()).abs() * length < tolerance.inner()
+        });
+
+        if !hits_edge_or_vertex && !grazes_face {
+            return Some(hits.len() % 2 == 1);
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use fj_math::{Point, Scalar, Vector};
+
+    use crate::{
+        objects::{Shell, Solid},
+        operations::{
+            build::{BuildShell, BuildSolid},
+            insert::Insert,
+            update::UpdateSolid,
+        },
+        Core,
+    };
+
+    use super::{contains_point, ContainsPoint, RayHit, DIRECTIONS, MAX_RAYS};
+
+    #[test]
+    fn cuboid() {
+        let mut core = Core::new();
+
+        let cuboid = Shell::cuboid([0., 0., 0.], [1., 1., 1.], &mut core);
+        let tolerance = 0.01;
+
+        assert_eq!(
+            cuboid.contains_point([0.5, 0.5, 0.5], tolerance, &mut core),
+            Some(true)
+        );
+        assert_eq!(
+            cuboid.contains_point([1.5, 0.5, 0.5], tolerance, &mut core),
+            Some(false)
+        );
+        assert_eq!(
+            cuboid.contains_point([-0.5, 2., 3.], tolerance, &mut core),
+            Some(false)
+        );
+
+        // Points on the boundary are contained.
+        assert_eq!(
+            cuboid.contains_point([1., 0.5, 0.5], tolerance, &mut core),
+            Some(true)
+        );
+        assert_eq!(
+            cuboid.contains_point([1., 1., 1.], tolerance, &mut core),
+            Some(true)
+        );
+
+        // The first ray from this point hits an edge.
+        let direction = Vector::from(DIRECTIONS[0]).normalize();
+        let point = Point::from([1., 1., 0.5]) - direction * 0.5;
+        assert_eq!(
+            cuboid.contains_point(point, tolerance, &mut core),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn ambiguous() {
+        let mut core = Core::new();
+
+        let cuboid = Shell::cuboid([0., 0., 0.], [1., 1., 1.], &mut core);
+        let face = cuboid.faces().first().clone();
+        let tolerance = 0.01;
+
+        // Pretend that every ray hits an edge, except those that are cast
+        // after the first `num_ambiguous` ones.
+        let num_rays = Cell::new(0);
+        let cast_ray = |num_ambiguous: usize| {
+            num_rays.set(0);
+
+            let face = face.clone();
+            let num_rays = &num_rays;
+            move |_: &_, _, _: &mut _| {
+                num_rays.set(num_rays.get() + 1);
+
+                let hit = RayHit {
+                    distance: Scalar::ONE,
+                    face: face.clone(),
+                    point_surface: Point::from([0.5, 0.5]),
+                    point_global: Point::from([0.5, 0.5, 0.]),
+                };
+                if num_rays.get() > num_ambiguous {
+                    vec![hit]
+                } else {
+                    vec![hit.clone(), hit]
+                }
+            }
+        };
+
+        let point = Point::from([0.5, 0.5, 0.5]);
+        let contained = contains_point(
+            cast_ray(DIRECTIONS.len()),
+            point,
+            tolerance.into(),
+            &mut core,
+        );
+        assert_eq!(contained, Some(true));
+        assert_eq!(num_rays.get(), DIRECTIONS.len() + 1);
+
+        let contained = contains_point(
+            cast_ray(MAX_RAYS),
+            point,
+            tolerance.into(),
+            &mut core,
+        );
+        assert_eq!(contained, None);
+        assert_eq!(num_rays.get(), MAX_RAYS);
+    }
+
+    #[test]
+    fn solid_with_cavity() {
+        let mut core = Core::new();
+
+        let [outer, inner] = [
+            Shell::cuboid([0., 0., 0.], [3., 3., 3.], &mut core),
+            Shell::cuboid([1., 1., 1.], [2., 2., 2.], &mut core),
+        ];
+        let solid = Solid::empty().add_shells(
+            [outer, inner].map(|shell| shell.insert(&mut core)),
+            &mut core,
+        );
+        let tolerance = 0.01;
+
+        assert_eq!(
+            solid.contains_point([0.5, 0.5, 0.5], tolerance, &mut core),
+            Some(true)
+        );
+        assert_eq!(
+            solid.contains_point([1.5, 1.5, 1.5], tolerance, &mut core),
+            Some(false)
+        );
+        assert_eq!(
+            solid.contains_point([4., 1.5, 1.5], tolerance, &mut core),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn sphere() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let sphere = Solid::sphere(1., &mut core)?;
+        let tolerance = 0.01;
+
+        assert_eq!(
+            sphere.contains_point([0.5, 0.3, -0.2], tolerance, &mut core),
+            Some(true)
+        );
+        assert_eq!(
+            sphere.contains_point([0.8, 0.8, 0.], tolerance, &mut core),
+            Some(false)
+        );
+
+        Ok(())
+    }
+}
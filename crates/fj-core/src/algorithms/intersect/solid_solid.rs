@@ -0,0 +1,287 @@
+//! Interference between two solids
+//!
+//! See [`Interference`].
+
+use fj_math::{Aabb, Point, Scalar};
+
+use crate::{
+    algorithms::{
+        approx::Tolerance,
+        triangulate::{index::TriangleIndex, Triangulate},
+    },
+    objects::Solid,
+    Core,
+};
+
+/// Determine how two solids interfere with each other
+pub trait Interference {
+    /// Determine whether the solids overlap, touch, or are disjoint
+    ///
+    /// Solids touch, if their boundaries are within the provided tolerance of
+    /// each other, without their interiors overlapping.
+    ///
+    /// # Implementation Note
+    ///
+    /// Both solids are approximated with the provided tolerance. Overlap is
+    /// detected by looking for points that are inside of both approximations,
+    /// and not within the tolerance of either boundary. Those points are
+    /// sampled from the approximations themselves, and from a grid within the
+    /// region where the bounding boxes of the solids overlap.
+    ///
+    /// An overlap that is thinner than the spacing of that grid, and doesn't
+    /// contain any points of the approximations, might be missed. In that
+    /// case, the solids are reported as touching.
+    fn interference(
+        &self,
+        other: &Self,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> SolidSolidInterference;
+}
+
+impl Interference for Solid {
+    fn interference(
+        &self,
+        other: &Self,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> SolidSolidInterference {
+        let tolerance = tolerance.into();
+
+        // The approximations are within the tolerance of the solids, so they
+        // might be within twice the tolerance of each other, where the solids
+        // touch.
+        let threshold = tolerance.inner() * 2.;
+
+        let [a, b] = [self, other].map(|solid| {
+            let mesh = (solid, tolerance).triangulate(core);
+            let triangles = mesh.triangles().map(|triangle| triangle.inner);
+            TriangleIndex::new(triangles, threshold)
+        });
+
+        let (Some(aabb_a), Some(aabb_b)) = (a.aabb(), b.aabb()) else {
+            return SolidSolidInterference::Disjoint;
+        };
+        let Some(aabb) = aabb_intersection(&aabb_a, &aabb_b) else {
+            return SolidSolidInterference::Disjoint;
+        };
+
+        let vertices =
+            a.triangles().chain(b.triangles()).flat_map(|triangle| {
+                let [p0, p1, p2] = triangle.points();
+                let centroid = Point {
+                    coords: (p0.coords + p1.coords + p2.coords) / 3.,
+                };
+
+                [p0, p1, p2, centroid]
+            });
+        let samples = vertices.chain(grid(&aabb));
+
+        let overlapping = samples
+            .filter(|point| {
+                aabb.contains(*point)
+                    && [&a, &b].into_iter().all(|index| {
+                        index.contains(point) == Some(true)
+                            && !index.is_near(point)
+                    })
+            })
+            .collect::<Vec<_>>();
+
+        if !overlapping.is_empty() {
+            return SolidSolidInterference::Overlapping {
+                aabb: Aabb::<3>::from_points(overlapping),
+            };
+        }
+
+        let touching =
+            a.triangles().any(|triangle| b.is_near_triangle(triangle));
+        if touching {
+            return SolidSolidInterference::Touching;
+        }
+
+        SolidSolidInterference::Disjoint
+    }
+}
+
+/// The interference between two solids
+///
+/// See [`Interference`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum SolidSolidInterference {
+    /// The solids are apart from each other
+    Disjoint,
+
+    /// The boundaries of the solids touch, but their interiors don't overlap
+    Touching,
+
+    /// The interiors of the solids overlap
+    Overlapping {
+        /// The approximate bounding box of the overlapping volume
+        ///
+        /// This is the bounding box of the points that were found within both
+        /// solids, so it might be smaller than the actual overlap.
+        aabb: Aabb<3>,
+    },
+}
+
+/// The number of grid points along each axis, when sampling for overlap
+const GRID_RESOLUTION: usize = 16;
+
+fn aabb_intersection(a: &Aabb<3>, b: &Aabb<3>) -> Option<Aabb<3>> {
+    let mut min = a.min;
+    let mut max = a.max;
+
+    for i in 0..3 {
+        let [min_a, max_a] = [a.min, a.max].map(|p| p.coords.components[i]);
+        let [min_b, max_b] = [b.min, b.max].map(|p| p.coords.components[i]);
+
+        min.coords.components[i] = min_a.max(min_b);
+        max.coords.components[i] = if max_a < max_b { max_a } else { max_b };
+
+        if min.coords.components[i] > max.coords.components[i] {
+            return None;
+        }
+    }
+
+    Some(Aabb { min, max })
+}
+
+/// Sample the bounding box with a grid of points at the centers of its cells
+fn grid(aabb: &Aabb<3>) -> impl Iterator<Item = Point<3>> + '_ {
+    let size = aabb.size();
+    let coord = move |axis: usize, i: usize| {
+        aabb.min.coords.components[axis]
+            + size.components[axis]
+                * Scalar::from((i as f64 + 0.5) / GRID_RESOLUTION as f64)
+    };
+
+    (0..GRID_RESOLUTION).flat_map(move |i| {
+        (0..GRID_RESOLUTION).flat_map(move |j| {
+            (0..GRID_RESOLUTION).map(move |k| {
+                Point::from([coord(0, i), coord(1, j), coord(2, k)])
+            })
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Aabb;
+    use fj_test_utils::assert_points_eq;
+
+    use crate::{
+        objects::{Shell, Solid},
+        operations::{
+            build::{BuildShell, BuildSolid},
+            insert::Insert,
+            transform::TransformObject,
+            update::UpdateSolid,
+        },
+        Core,
+    };
+
+    use super::{Interference, SolidSolidInterference};
+
+    #[test]
+    fn disjoint() {
+        let mut core = Core::new();
+
+        let a = cuboid([0., 0., 0.], [1., 1., 1.], &mut core);
+        let b = cuboid([2., 0., 0.], [3., 1., 1.], &mut core);
+
+        assert_eq!(
+            a.interference(&b, 0.01, &mut core),
+            SolidSolidInterference::Disjoint
+        );
+    }
+
+    #[test]
+    fn touching() {
+        let mut core = Core::new();
+
+        let a = cuboid([0., 0., 0.], [1., 1., 1.], &mut core);
+
+        // Touching along a face, part of a face, and an edge.
+        for [min, max] in [
+            [[1., 0., 0.], [2., 1., 1.]],
+            [[0.5, 0.5, 1.], [1.5, 1.5, 2.]],
+            [[1., 1., 0.], [2., 2., 1.]],
+        ] {
+            let b = cuboid(min, max, &mut core);
+            assert_eq!(
+                a.interference(&b, 0.01, &mut core),
+                SolidSolidInterference::Touching
+            );
+        }
+    }
+
+    #[test]
+    fn overlapping() {
+        let mut core = Core::new();
+
+        let a = cuboid([0., 0., 0.], [2., 2., 2.], &mut core);
+        let b = cuboid([1., 1., 1.], [3., 3., 3.], &mut core);
+
+        let SolidSolidInterference::Overlapping { aabb } =
+            a.interference(&b, 0.01, &mut core)
+        else {
+            panic!("Expected solids to overlap");
+        };
+        assert_aabb_approx(aabb, [1., 1., 1.], [2., 2., 2.]);
+
+        // Crossing each other, without any vertex of one inside the other.
+        let b = cuboid([-1., 0.5, 0.], [3., 1.5, 2.], &mut core);
+        assert!(matches!(
+            a.interference(&b, 0.01, &mut core),
+            SolidSolidInterference::Overlapping { .. }
+        ));
+
+        // One contained within the other.
+        let b = cuboid([0.5, 0.5, 0.5], [1.5, 1.5, 1.5], &mut core);
+        assert!(matches!(
+            b.interference(&a, 0.01, &mut core),
+            SolidSolidInterference::Overlapping { .. }
+        ));
+    }
+
+    #[test]
+    fn cylinder_in_cavity() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let [outer, inner] = [
+            Shell::cuboid([-2., -2., -1.], [2., 2., 3.], &mut core),
+            Shell::cuboid([-1.5, -1.5, -0.5], [1.5, 1.5, 2.5], &mut core),
+        ];
+        let hollow = Solid::empty().add_shells(
+            [outer, inner].map(|shell| shell.insert(&mut core)),
+            &mut core,
+        );
+        let cylinder = Solid::cylinder(1., 2., &mut core)?;
+
+        assert_eq!(
+            hollow.interference(&cylinder, 0.01, &mut core),
+            SolidSolidInterference::Disjoint
+        );
+
+        let cylinder = cylinder.translate([1., 0., 0.], &mut core);
+        assert!(matches!(
+            hollow.interference(&cylinder, 0.01, &mut core),
+            SolidSolidInterference::Overlapping { .. }
+        ));
+
+        Ok(())
+    }
+
+    fn cuboid(min: [f64; 3], max: [f64; 3], core: &mut Core) -> Solid {
+        let shell = Shell::cuboid(min, max, core).insert(core);
+        Solid::empty().add_shells([shell], core)
+    }
+
+    fn assert_aabb_approx(aabb: Aabb<3>, min: [f64; 3], max: [f64; 3]) {
+        // The bounding box is computed from sample points, so it can be
+        // smaller than the actual overlap.
+        for (actual, expected) in [(aabb.min, min), (aabb.max, max)] {
+            assert_points_eq!(actual, expected, 0.2);
+        }
+    }
+}
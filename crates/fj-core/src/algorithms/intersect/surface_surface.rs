@@ -0,0 +1,950 @@
+//! Intersection between two surfaces
+//!
+//! Planes that intersect planes or cylinders are handled exactly. All other
+//! cases are handled by tracing the intersection curves across both surfaces,
+//! which results in polylines.
+
+use fj_math::{Aabb, Circle, Ellipse, Line, Plane, Point, Scalar, Vector};
+
+use crate::{
+    algorithms::approx::Tolerance,
+    geometry::{GlobalPath, SurfaceGeometry, SurfacePath},
+    validation::ValidationConfig,
+};
+
+/// The intersection between two surfaces
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct SurfaceSurfaceIntersection {
+    /// The curves along which the surfaces intersect
+    pub curves: Vec<IntersectionCurve>,
+}
+
+impl SurfaceSurfaceIntersection {
+    /// Compute the intersection between two surfaces
+    ///
+    /// Curves that can't be represented exactly are only searched for within
+    /// the provided `bounds`, which are given in the coordinates of the
+    /// respective surface. They are then approximated within the provided
+    /// tolerance. Curves that are represented exactly are not limited by the
+    /// bounds.
+    ///
+    /// Returns `None`, if no intersection curves are found. Coincident planes
+    /// are not considered to intersect.
+    ///
+    /// # Implementation Note
+    ///
+    /// Curves that are traced are found by sampling the surfaces within the
+    /// bounds. Curves that are small compared to the bounds might be missed,
+    /// as might be curves where the surfaces only touch each other.
+    pub fn compute(
+        surfaces: [&SurfaceGeometry; 2],
+        bounds: [Aabb<2>; 2],
+        tolerance: impl Into<Tolerance>,
+        config: &ValidationConfig,
+    ) -> Option<Self> {
+        let tolerance = tolerance.into();
+
+        let exact = match surfaces.map(Kind::of) {
+            [Kind::Plane(a), Kind::Plane(b)] => {
+                Some(plane_plane(&a, &b, config))
+            }
+            [Kind::Plane(plane), Kind::Cylinder(cylinder)] => {
+                plane_cylinder(&plane, &cylinder, config)
+            }
+            [Kind::Cylinder(cylinder), Kind::Plane(plane)] => {
+                plane_cylinder(&plane, &cylinder, config).map(|paths| {
+                    paths
+                        .into_iter()
+                        .map(|[path_plane, path_cylinder]| {
+                            [path_cylinder, path_plane]
+                        })
+                        .collect()
+                })
+            }
+            _ => None,
+        };
+
+        let curves = match exact {
+            Some(paths) => {
+                paths.into_iter().map(IntersectionCurve::Paths).collect()
+            }
+            None => march(surfaces, bounds, tolerance, config),
+        };
+
+        if curves.is_empty() {
+            return None;
+        }
+
+        Some(Self { curves })
+    }
+}
+
+/// A curve along which two surfaces intersect
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum IntersectionCurve {
+    /// The curve is represented exactly, by a path on each surface
+    ///
+    /// Both paths share a parametrization: The same path coordinate refers to
+    /// the same point in 3D.
+    Paths([SurfacePath; 2]),
+
+    /// The curve is approximated by a polyline, traced across both surfaces
+    ///
+    /// Each point is given in the coordinates of both surfaces. If the curve
+    /// is closed, its last point is equal to its first one.
+    Polyline(Vec<[Point<2>; 2]>),
+}
+
+/// The kinds of surfaces that have special handling
+enum Kind {
+    Plane(Plane),
+    Cylinder(Cylinder),
+    Other,
+}
+
+impl Kind {
+    fn of(surface: &SurfaceGeometry) -> Self {
+        match surface {
+            SurfaceGeometry::Swept {
+                u: GlobalPath::Line(line),
+                v,
+            } => Self::Plane(Plane::from_parametric(
+                line.origin(),
+                line.direction(),
+                *v,
+            )),
+            SurfaceGeometry::Swept {
+                u: GlobalPath::Circle(circle),
+                v,
+            } => Self::Cylinder(Cylinder {
+                circle: *circle,
+                axis: *v,
+            }),
+            _ => Self::Other,
+        }
+    }
+}
+
+/// A circle, swept along an axis
+///
+/// The axis is not necessarily perpendicular to the circle.
+struct Cylinder {
+    circle: Circle<3>,
+    axis: Vector<3>,
+}
+
+fn plane_plane(
+    a: &Plane,
+    b: &Plane,
+    config: &ValidationConfig,
+) -> Vec<[SurfacePath; 2]> {
+    let (distance_a, normal_a) = a.constant_normal_form();
+    let (distance_b, normal_b) = b.constant_normal_form();
+
+    let direction = normal_a.cross(&normal_b);
+    if direction.magnitude() <= config.identical_max_angle.sin_cos().0 {
+        // The planes are parallel, or even coincident.
+        return Vec::new();
+    }
+
+    let origin = (normal_b * distance_a - normal_a * distance_b)
+        .cross(&direction)
+        / direction.dot(&direction);
+    let line = Line::from_origin_and_direction(
+        Point { coords: origin },
+        direction.normalize(),
+    );
+
+    vec![[a, b].map(|plane| SurfacePath::Line(plane.project_line(&line)))]
+}
+
+/// Compute the intersection between a plane and a cylinder
+///
+/// Returns `None`, if the intersection can't be represented exactly.
+fn plane_cylinder(
+    plane: &Plane,
+    cylinder: &Cylinder,
+    config: &ValidationConfig,
+) -> Option<Vec<[SurfacePath; 2]>> {
+    let sin_max_angle = config.identical_max_angle.sin_cos().0;
+
+    let Cylinder { circle, axis } = cylinder;
+    let circle_normal = circle.a().cross(&circle.b()).normalize();
+    if circle_normal.cross(&axis.normalize()).magnitude() > sin_max_angle {
+        // The cylinder is oblique. The intersection is not a line, nor a
+        // circle.
+        return None;
+    }
+
+    let (distance, normal) = plane.constant_normal_form();
+
+    if normal.dot(&axis.normalize()).abs() <= sin_max_angle {
+        // The plane is parallel to the axis. The intersection consists of
+        // lines along the axis, at the points of the circle that lie in the
+        // plane.
+        let [a, b] = [circle.a(), circle.b()].map(|v| normal.dot(&v));
+        let amplitude = Vector::from([a, b]).magnitude();
+        let offset = distance - normal.dot(&circle.center().coords);
+
+        let angles = if (offset.abs() - amplitude).abs()
+            <= config.identical_max_distance
+        {
+            // The plane touches the cylinder along a single line.
+            let angle = if offset.is_positive() {
+                Scalar::ZERO
+            } else {
+                Scalar::PI
+            };
+            vec![angle]
+        } else if offset.abs() < amplitude {
+            let angle = (offset / amplitude).acos();
+            vec![-angle, angle]
+        } else {
+            Vec::new()
+        };
+
+        let paths = angles
+            .into_iter()
+            .map(|angle| {
+                let angle = Scalar::from(
+                    (b.atan2(a) + angle)
+                        .into_f64()
+                        .rem_euclid(Scalar::TAU.into_f64()),
+                );
+                let line = Line::from_origin_and_direction(
+                    circle.point_from_circle_coords([angle]),
+                    *axis,
+                );
+
+                [
+                    SurfacePath::Line(plane.project_line(&line)),
+                    SurfacePath::Line(Line::from_origin_and_direction(
+                        Point::from([angle, Scalar::ZERO]),
+                        Vector::unit_v(),
+                    )),
+                ]
+            })
+            .collect();
+
+        return Some(paths);
+    }
+
+    if normal.cross(axis).magnitude() / axis.magnitude() <= sin_max_angle {
+        // The plane is perpendicular to the axis. The intersection is a copy
+        // of the circle.
+        let v =
+            (distance - normal.dot(&circle.center().coords)) / normal.dot(axis);
+
+        let center = plane.project_point(circle.center() + *axis * v);
+        let [a, b] =
+            [circle.a(), circle.b()].map(|vector| plane.project_vector(vector));
+
+        // Unless the coordinate system of the plane is skewed, the circle is
+        // still a circle in surface coordinates, or at least an ellipse.
+        if a.dot(&b).abs() / (a.magnitude() * b.magnitude()) > sin_max_angle {
+            return None;
+        }
+        let path_plane = if (a.magnitude() - b.magnitude()).abs()
+            <= config.identical_max_distance
+        {
+            // Rotate `a` to get a `b` of exactly the same length, that is
+            // exactly perpendicular to it, as `Circle::new` requires.
+            let b = if a.cross2d(&b).is_positive() {
+                Vector::from([-a.v, a.u])
+            } else {
+                Vector::from([a.v, -a.u])
+            };
+            SurfacePath::Circle(Circle::new(center, a, b))
+        } else {
+            // Remove whatever is left of `b` along `a`. `Ellipse::new` only
+            // allows for a very small absolute error, which large ellipses
+            // might still exceed.
+            let b = b - a * (a.dot(&b) / a.dot(&a));
+            if a.dot(&b).abs() >= Scalar::from(f64::EPSILON) {
+                return None;
+            }
+            SurfacePath::Ellipse(Ellipse::new(center, a, b))
+        };
+        let path_cylinder = SurfacePath::Line(Line::from_origin_and_direction(
+            Point::from([Scalar::ZERO, v]),
+            Vector::unit_u(),
+        ));
+
+        return Some(vec![[path_plane, path_cylinder]]);
+    }
+
+    None
+}
+
+/// The number of cells along each axis of the bounds, when sampling
+const NUM_SAMPLES: usize = 16;
+
+/// The maximum number of points of a traced curve
+const MAX_POINTS: usize = 10_000;
+
+/// The maximum number of iterations, when moving a point onto a curve
+const MAX_ITERATIONS: usize = 16;
+
+/// A point in the coordinates of both surfaces
+type Coords = [Point<2>; 2];
+
+/// Trace the curves along which the surfaces intersect
+fn march(
+    surfaces: [&SurfaceGeometry; 2],
+    bounds: [Aabb<2>; 2],
+    tolerance: Tolerance,
+    config: &ValidationConfig,
+) -> Vec<IntersectionCurve> {
+    let samples = [0, 1].map(|i| {
+        let Aabb { min, max } = bounds[i];
+
+        (0..=NUM_SAMPLES)
+            .flat_map(|j| (0..=NUM_SAMPLES).map(move |k| [j, k]))
+            .map(|indices| {
+                let [u, v] = [0, 1].map(|axis| {
+                    let [min, max] =
+                        [min, max].map(|p| p.coords.components[axis]);
+                    let t = indices[axis] as f64 / NUM_SAMPLES as f64;
+                    min + (max - min) * t
+                });
+                let point = Point::from([u, v]);
+
+                (point, surfaces[i].point_from_surface_coords(point))
+            })
+            .collect::<Vec<_>>()
+    });
+
+    let max_step = {
+        let aabb = Aabb::<3>::from_points(
+            samples.iter().flatten().map(|&(_, point)| point),
+        );
+        aabb.size().magnitude() / 8.
+    };
+
+    let tracer = Tracer {
+        surfaces,
+        bounds,
+        tolerance: tolerance.inner(),
+        max_step,
+        config,
+    };
+
+    let mut curves: Vec<(Vec<Coords>, Vec<Point<3>>)> = Vec::new();
+
+    for &(a, point_a) in &samples[0] {
+        // Use the closest sample on the other surface, to find a nearby point
+        // on an intersection curve.
+        let Some(&(b, _)) = samples[1]
+            .iter()
+            .min_by_key(|(_, point_b)| (*point_b - point_a).magnitude())
+        else {
+            continue;
+        };
+
+        let Some(seed) = tracer.correct([a, b]) else {
+            continue;
+        };
+        if !tracer.is_within_bounds(seed) {
+            continue;
+        }
+
+        let seed_global = tracer.global(seed);
+        let is_on_known_curve = curves.iter().any(|(_, points)| {
+            distance_to_polyline(seed_global, points) <= tracer.tolerance * 2.
+        });
+        if is_on_known_curve {
+            continue;
+        }
+
+        let points = tracer.trace(seed);
+        let points_global =
+            points.iter().map(|&point| tracer.global(point)).collect();
+        curves.push((points, points_global));
+    }
+
+    curves
+        .into_iter()
+        .filter(|(points, _)| points.len() >= 2)
+        .map(|(points, _)| IntersectionCurve::Polyline(points))
+        .collect()
+}
+
+struct Tracer<'r> {
+    surfaces: [&'r SurfaceGeometry; 2],
+    bounds: [Aabb<2>; 2],
+    tolerance: Scalar,
+    max_step: Scalar,
+    config: &'r ValidationConfig,
+}
+
+impl Tracer<'_> {
+    /// Trace the curve that passes through the provided point
+    fn trace(&self, seed: Coords) -> Vec<Coords> {
+        let (forward, is_closed) = self.trace_in_direction(seed, Scalar::ONE);
+        if is_closed {
+            return forward;
+        }
+
+        let (backward, _) = self.trace_in_direction(seed, -Scalar::ONE);
+        backward
+            .into_iter()
+            .rev()
+            .chain(forward.into_iter().skip(1))
+            .collect()
+    }
+
+    /// Trace the curve from the provided point, until it ends or closes
+    ///
+    /// Returns the points of the curve, and whether it is closed.
+    fn trace_in_direction(
+        &self,
+        seed: Coords,
+        direction: Scalar,
+    ) -> (Vec<Coords>, bool) {
+        let start = self.global(seed);
+        let mut points = vec![seed];
+        let mut step = self.max_step;
+
+        while points.len() < MAX_POINTS {
+            let current = points[points.len() - 1];
+            let Some(tangent) = self.tangent(current) else {
+                break;
+            };
+            let tangent = tangent * direction;
+
+            if points.len() > 2 {
+                let to_start = start - self.global(current);
+                if to_start.magnitude() <= step
+                    && to_start.dot(&tangent) > Scalar::ZERO
+                {
+                    points.push(seed);
+                    return (points, true);
+                }
+            }
+
+            let next = loop {
+                if step < self.tolerance / 1000. {
+                    return (points, false);
+                }
+                if let Some(next) = self.step(current, direction, step) {
+                    break next;
+                }
+                step /= 2.;
+            };
+
+            if !self.is_within_bounds(next) {
+                points.extend(self.find_boundary(current, tangent, step));
+                break;
+            }
+
+            points.push(next);
+
+            step *= 1.5;
+            if step > self.max_step {
+                step = self.max_step;
+            }
+        }
+
+        (points, false)
+    }
+
+    /// Take a step along the curve
+    ///
+    /// Returns `None`, if the step was too large.
+    fn step(
+        &self,
+        current: Coords,
+        direction: Scalar,
+        step: Scalar,
+    ) -> Option<Coords> {
+        let tangent = self.tangent(current)? * direction;
+        let next = self.correct(self.move_along(current, tangent, step)?)?;
+
+        // A sharp turn means, that we might have jumped to a different part of
+        // the curve, or a different curve altogether.
+        let next_tangent = self.tangent(next)? * direction;
+        if next_tangent.dot(&tangent) < Scalar::from(0.8) {
+            return None;
+        }
+
+        let [current_global, next_global] =
+            [current, next].map(|point| self.global(point));
+        if (next_global - current_global).dot(&tangent) <= Scalar::ZERO {
+            return None;
+        }
+
+        let middle = self.correct(
+            [0, 1].map(|i| current[i] + (next[i] - current[i]) / 2.),
+        )?;
+        let middle_of_chord =
+            current_global + (next_global - current_global) / 2.;
+        if (self.global(middle) - middle_of_chord).magnitude() > self.tolerance
+        {
+            return None;
+        }
+
+        Some(next)
+    }
+
+    /// Find the point where the curve leaves the bounds
+    ///
+    /// Expects the curve to leave the bounds within the provided step.
+    fn find_boundary(
+        &self,
+        current: Coords,
+        tangent: Vector<3>,
+        step: Scalar,
+    ) -> Option<Coords> {
+        let mut inside = None;
+        let [mut min, mut max] = [Scalar::ZERO, step];
+
+        for _ in 0..32 {
+            let middle = (min + max) / 2.;
+
+            let point = self
+                .move_along(current, tangent, middle)
+                .and_then(|point| self.correct(point));
+            match point {
+                Some(point) if self.is_within_bounds(point) => {
+                    inside = Some(point);
+                    min = middle;
+                }
+                _ => {
+                    max = middle;
+                }
+            }
+        }
+
+        inside
+    }
+
+    /// Move a point along a vector, in the coordinates of both surfaces
+    ///
+    /// The result is only an estimate, which might not be on the curve.
+    fn move_along(
+        &self,
+        point: Coords,
+        direction: Vector<3>,
+        distance: Scalar,
+    ) -> Option<Coords> {
+        let [a, b] = [0, 1].map(|i| {
+            let [du, dv] = self.surfaces[i].derivatives(point[i]);
+
+            // Find the vector in surface coordinates, that comes closest to
+            // the direction.
+            let [uu, uv, vv] = [du.dot(&du), du.dot(&dv), dv.dot(&dv)];
+            let [u, v] = [du.dot(&direction), dv.dot(&direction)];
+            let determinant = uu * vv - uv * uv;
+            if determinant == Scalar::ZERO {
+                return None;
+            }
+
+            let vector = Vector::from([
+                (u * vv - v * uv) / determinant,
+                (uu * v - uv * u) / determinant,
+            ]);
+            Some(point[i] + vector * distance)
+        });
+
+        Some([a?, b?])
+    }
+
+    /// Move a point onto the curve, using Newton's method
+    ///
+    /// Returns `None`, if the point doesn't converge towards a curve.
+    fn correct(&self, point: Coords) -> Option<Coords> {
+        let mut point = point;
+        let mut best: Option<(Coords, Scalar)> = None;
+
+        for _ in 0..MAX_ITERATIONS {
+            let [a, b] = [0, 1]
+                .map(|i| self.surfaces[i].point_from_surface_coords(point[i]));
+            let offset = a - b;
+            let distance = offset.magnitude();
+
+            if let Some((_, best_distance)) = best {
+                if distance >= best_distance {
+                    break;
+                }
+            }
+            best = Some((point, distance));
+            if distance <= self.config.identical_max_distance {
+                break;
+            }
+
+            // Of all the changes to the coordinates that would close the
+            // distance (assuming the surfaces are flat), take the smallest.
+            let [[au, av], [bu, bv]] =
+                [0, 1].map(|i| self.surfaces[i].derivatives(point[i]));
+            let columns = [0, 1, 2].map(|j| {
+                au * au.components[j]
+                    + av * av.components[j]
+                    + bu * bu.components[j]
+                    + bv * bv.components[j]
+            });
+            let Some(y) = solve(columns, offset) else {
+                break;
+            };
+
+            point = [
+                point[0] - Vector::from([au.dot(&y), av.dot(&y)]),
+                point[1] + Vector::from([bu.dot(&y), bv.dot(&y)]),
+            ];
+        }
+
+        let (point, distance) = best?;
+        (distance <= self.tolerance / 100.).then_some(point)
+    }
+
+    /// Compute the tangent of the curve at the provided point
+    ///
+    /// Returns `None`, if the surfaces touch each other at the point.
+    fn tangent(&self, point: Coords) -> Option<Vector<3>> {
+        let [a, b] = [0, 1].map(|i| {
+            let [du, dv] = self.surfaces[i].derivatives(point[i]);
+            du.cross(&dv)
+        });
+
+        let tangent = a.cross(&b);
+        if tangent.magnitude()
+            <= a.magnitude()
+                * b.magnitude()
+                * self.config.identical_max_angle.sin_cos().0
+        {
+            return None;
+        }
+
+        Some(tangent.normalize())
+    }
+
+    fn global(&self, point: Coords) -> Point<3> {
+        self.surfaces[0].point_from_surface_coords(point[0])
+    }
+
+    fn is_within_bounds(&self, point: Coords) -> bool {
+        self.bounds
+            .iter()
+            .zip(point)
+            .all(|(bounds, point)| bounds.contains(point))
+    }
+}
+
+/// Solve a system of 3 linear equations, given the columns of its matrix
+fn solve(columns: [Vector<3>; 3], b: Vector<3>) -> Option<Vector<3>> {
+    let [c0, c1, c2] = columns;
+
+    let determinant = c0.dot(&c1.cross(&c2));
+    if determinant == Scalar::ZERO {
+        return None;
+    }
+
+    let solution = Vector::from([
+        b.dot(&c1.cross(&c2)),
+        c0.dot(&b.cross(&c2)),
+        c0.dot(&c1.cross(&b)),
+    ]);
+    Some(solution / determinant)
+}
+
+fn distance_to_polyline(point: Point<3>, polyline: &[Point<3>]) -> Scalar {
+    polyline
+        .windows(2)
+        .map(|segment| {
+            let [a, b] = [segment[0], segment[1]];
+            let ab = b - a;
+
+            let length_squared = ab.dot(&ab);
+            let t = if length_squared == Scalar::ZERO {
+                Scalar::ZERO
+            } else {
+                ((point - a).dot(&ab) / length_squared)
+                    .max(Scalar::ZERO)
+                    .into_f64()
+                    .min(1.)
+                    .into()
+            };
+
+            (point - (a + ab * t)).magnitude()
+        })
+        .chain(polyline.iter().map(|&p| (point - p).magnitude()))
+        .fold(
+            Scalar::MAX,
+            |min, distance| {
+                if distance < min {
+                    distance
+                } else {
+                    min
+                }
+            },
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::TAU;
+
+    use fj_math::{Aabb, Line, NurbsSurface, Point, Scalar, Vector};
+    use fj_test_utils::{assert_points_eq, assert_scalars_eq};
+
+    use crate::{
+        geometry::{GlobalPath, SurfaceGeometry, SurfacePath},
+        validation::ValidationConfig,
+    };
+
+    use super::{IntersectionCurve, SurfaceSurfaceIntersection};
+
+    #[test]
+    fn plane_plane() {
+        let xy = plane([0., 0., 0.], [1., 0., 0.], [0., 1., 0.]);
+        let xz = plane([0., 0., 0.], [1., 0., 0.], [0., 0., 1.]);
+
+        let points = points_on_paths([&xy, &xz]);
+        assert!(!points.is_empty());
+        for point in points {
+            assert_scalars_eq!(point.y, 0., 1e-12);
+            assert_scalars_eq!(point.z, 0., 1e-12);
+        }
+
+        let parallel = plane([0., 0., 1.], [1., 0., 0.], [0., 1., 0.]);
+        assert_eq!(intersect([&xy, &parallel]), None);
+        assert_eq!(intersect([&xy, &xy]), None);
+    }
+
+    #[test]
+    fn plane_cylinder() {
+        let cylinder = SurfaceGeometry::Swept {
+            u: GlobalPath::circle_from_radius(1.),
+            v: Vector::unit_z(),
+        };
+
+        // Parallel to the axis.
+        for (x, num_lines) in [(0.5, 2), (-1., 1), (2., 0)] {
+            let plane = plane([x, 0., 0.], [0., 1., 0.], [0., 0., 1.]);
+
+            let num_curves = intersect([&plane, &cylinder])
+                .map(|intersection| intersection.curves.len())
+                .unwrap_or_default();
+            assert_eq!(num_curves, num_lines);
+
+            for surfaces in [[&plane, &cylinder], [&cylinder, &plane]] {
+                for point in points_on_paths(surfaces) {
+                    assert_on_cylinder(point);
+                    assert_scalars_eq!(point.x, x, 1e-12);
+                }
+            }
+        }
+
+        // Perpendicular to the axis.
+        let plane = plane([0., 0., 0.5], [2., 0., 0.], [0., 1., 0.]);
+        for surfaces in [[&plane, &cylinder], [&cylinder, &plane]] {
+            let points = points_on_paths(surfaces);
+            assert!(!points.is_empty());
+
+            for point in points {
+                assert_on_cylinder(point);
+                assert_scalars_eq!(point.z, 0.5, 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn plane_cylinder_within_tolerance() {
+        let cylinder = SurfaceGeometry::Swept {
+            u: GlobalPath::circle_from_radius(1.),
+            v: Vector::unit_z(),
+        };
+
+        // The plane's coordinate system is scaled slightly differently along
+        // its axes, but not enough to make the intersection an ellipse.
+        let jitter = ValidationConfig::default().identical_max_distance / 5.;
+        let scaled = plane(
+            [0., 0., 0.5],
+            [1. + jitter.into_f64(), 0., 0.],
+            [0., 1., 0.],
+        );
+
+        let intersection = intersect([&scaled, &cylinder]).unwrap();
+        let [IntersectionCurve::Paths([SurfacePath::Circle(_), _])] =
+            &intersection.curves[..]
+        else {
+            panic!("Expected a circle: {intersection:?}");
+        };
+
+        // Stretched enough, it is an ellipse.
+        let stretched = plane([0., 0., 0.5], [1.5, 0., 0.], [0., 1., 0.]);
+        let intersection = intersect([&stretched, &cylinder]).unwrap();
+        let [IntersectionCurve::Paths([SurfacePath::Ellipse(_), _])] =
+            &intersection.curves[..]
+        else {
+            panic!("Expected an ellipse: {intersection:?}");
+        };
+    }
+
+    #[test]
+    fn plane_cylinder_oblique() {
+        let tolerance = 0.001;
+
+        let cylinder = SurfaceGeometry::Swept {
+            u: GlobalPath::circle_from_radius(1.),
+            v: Vector::unit_z(),
+        };
+        let plane = plane([0., 0., 0.], [1., 0., 0.5], [0., 1., 0.]);
+
+        let intersection = SurfaceSurfaceIntersection::compute(
+            [&plane, &cylinder],
+            [
+                Aabb {
+                    min: Point::from([-2., -2.]),
+                    max: Point::from([2., 2.]),
+                },
+                Aabb {
+                    min: Point::from([0., -2.]),
+                    max: Point::from([TAU, 2.]),
+                },
+            ],
+            tolerance,
+            &ValidationConfig::default(),
+        )
+        .unwrap();
+
+        // The intersection is an ellipse, which crosses the seam of the
+        // cylinder.
+        let [IntersectionCurve::Polyline(points)] = &intersection.curves[..]
+        else {
+            panic!("Expected a single polyline: {intersection:?}");
+        };
+        let [first, last] = [points[0], points[points.len() - 1]]
+            .map(|[_, point_cylinder]| point_cylinder.u);
+        assert!((first - last).abs() > Scalar::from(TAU - 1e-9));
+
+        let points = points_on_polyline([&plane, &cylinder], points);
+        for point in &points {
+            assert_on_cylinder(*point);
+            assert_scalars_eq!(point.z, point.x * 0.5, 1e-9);
+        }
+        for segment in points.windows(2) {
+            let middle = segment[0] + (segment[1] - segment[0]) / 2.;
+            let distance = Scalar::ONE - middle.coords.xy().magnitude();
+            assert!(distance <= Scalar::from(tolerance));
+        }
+    }
+
+    #[test]
+    fn plane_nurbs() {
+        let tolerance = 0.001;
+
+        // A bump, with its top at a height of `0.25`.
+        let bump = SurfaceGeometry::Nurbs(NurbsSurface::clamped(
+            [0., 0.5, 1.].map(|u| {
+                [0., 0.5, 1.].map(|v| {
+                    let z = if u == 0.5 && v == 0.5 { 1. } else { 0. };
+                    [u, v, z]
+                })
+            }),
+            [2, 2],
+        ));
+        let plane = plane([0., 0., 0.125], [1., 0., 0.], [0., 1., 0.]);
+
+        let intersection = SurfaceSurfaceIntersection::compute(
+            [&bump, &plane],
+            [
+                Aabb {
+                    min: Point::from([0., 0.]),
+                    max: Point::from([1., 1.]),
+                },
+                Aabb {
+                    min: Point::from([-1., -1.]),
+                    max: Point::from([2., 2.]),
+                },
+            ],
+            tolerance,
+            &ValidationConfig::default(),
+        )
+        .unwrap();
+
+        // The intersection is a closed curve around the top of the bump.
+        let [IntersectionCurve::Polyline(points)] = &intersection.curves[..]
+        else {
+            panic!("Expected a single polyline: {intersection:?}");
+        };
+        assert_eq!(points.first(), points.last());
+        assert!(points.len() > 8);
+
+        for point in points_on_polyline([&bump, &plane], points) {
+            assert_scalars_eq!(point.z, 0.125, 1e-9);
+        }
+    }
+
+    fn plane(origin: [f64; 3], u: [f64; 3], v: [f64; 3]) -> SurfaceGeometry {
+        SurfaceGeometry::Swept {
+            u: GlobalPath::Line(Line::from_origin_and_direction(
+                Point::from(origin),
+                Vector::from(u),
+            )),
+            v: Vector::from(v),
+        }
+    }
+
+    fn intersect(
+        surfaces: [&SurfaceGeometry; 2],
+    ) -> Option<SurfaceSurfaceIntersection> {
+        let bounds = Aabb {
+            min: Point::from([-1., -1.]),
+            max: Point::from([1., 1.]),
+        };
+
+        SurfaceSurfaceIntersection::compute(
+            surfaces,
+            [bounds, bounds],
+            0.001,
+            &ValidationConfig::default(),
+        )
+    }
+
+    /// Sample the exact curves, making sure that both paths agree
+    fn points_on_paths(surfaces: [&SurfaceGeometry; 2]) -> Vec<Point<3>> {
+        let Some(intersection) = intersect(surfaces) else {
+            return Vec::new();
+        };
+
+        let mut points = Vec::new();
+        for curve in intersection.curves {
+            let IntersectionCurve::Paths(paths) = curve else {
+                panic!("Expected exact intersection: {curve:?}");
+            };
+
+            for t in [-1., 0., 0.5, 2.] {
+                let [a, b] = [0, 1].map(|i| {
+                    let point = paths[i].point_from_path_coords([t]);
+                    surfaces[i].point_from_surface_coords(point)
+                });
+                assert_points_eq!(a, b, 1e-12);
+
+                points.push(a);
+            }
+        }
+
+        points
+    }
+
+    /// Convert traced points, making sure that both surfaces agree
+    fn points_on_polyline(
+        surfaces: [&SurfaceGeometry; 2],
+        points: &[[Point<2>; 2]],
+    ) -> Vec<Point<3>> {
+        points
+            .iter()
+            .map(|point| {
+                let [a, b] = [0, 1]
+                    .map(|i| surfaces[i].point_from_surface_coords(point[i]));
+                assert_points_eq!(a, b, 1e-9);
+                a
+            })
+            .collect()
+    }
+
+    fn assert_on_cylinder(point: Point<3>) {
+        let radius = point.coords.xy().magnitude();
+        assert_scalars_eq!(radius, 1., 1e-9, "{point:?}");
+    }
+}
@@ -0,0 +1,388 @@
+//! Measure the size of objects
+//!
+//! See [`Measure`].
+
+use fj_math::{CubicBezier, Point, Scalar, Vector};
+
+use crate::{
+    geometry::{CurveBoundary, GlobalPath, SurfaceGeometry, SurfacePath},
+    objects::{Cycle, Face, Region},
+    Core,
+};
+
+use super::{
+    approx::{edge::HalfEdgeApproxCache, Approx, Tolerance},
+    triangulate::Triangulate,
+};
+
+/// Measure the size of an object
+///
+/// Where the size of an object can be computed analytically, the result is
+/// exact. Otherwise, it is computed from an approximation of the object, using
+/// the provided tolerance.
+pub trait Measure {
+    /// Compute the area of the object
+    fn area(&self, tolerance: impl Into<Tolerance>, core: &mut Core) -> Scalar;
+
+    /// Compute the length of the boundary of the object
+    ///
+    /// This includes the boundaries of any holes.
+    fn perimeter(
+        &self,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Scalar;
+}
+
+/// Measure the size of a [`Region`], in surface coordinates
+///
+/// The area of a region is always exact.
+impl Measure for Region {
+    fn area(
+        &self,
+        _tolerance: impl Into<Tolerance>,
+        _core: &mut Core,
+    ) -> Scalar {
+        let holes = self
+            .interiors()
+            .iter()
+            .map(|interior| signed_area(interior).abs())
+            .fold(Scalar::ZERO, |sum, area| sum + area);
+
+        signed_area(self.exterior()).abs() - holes
+    }
+
+    fn perimeter(
+        &self,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Scalar {
+        let tolerance = tolerance.into();
+
+        self.all_cycles()
+            .flat_map(|cycle| cycle.half_edges())
+            .map(|half_edge| {
+                path_length(
+                    &half_edge.path(),
+                    half_edge.boundary(),
+                    tolerance,
+                    core,
+                )
+            })
+            .fold(Scalar::ZERO, |sum, length| sum + length)
+    }
+}
+
+/// Measure the size of a [`Face`], in global coordinates
+///
+/// The area is exact for faces on a plane. The perimeter is exact for faces on
+/// a plane that are bounded by straight edges only.
+impl Measure for Face {
+    fn area(&self, tolerance: impl Into<Tolerance>, core: &mut Core) -> Scalar {
+        if let Some([u, v]) = plane_axes(self.surface().geometry()) {
+            // A plane maps areas in surface coordinates to global ones by a
+            // constant factor.
+            let scale = u.cross(&v).magnitude();
+            return self.region().area(tolerance, core) * scale;
+        }
+
+        self.approx(tolerance, core)
+            .triangulate(core)
+            .triangles()
+            .map(|triangle| {
+                let [a, b, c] = triangle.inner.points();
+                (b - a).cross(&(c - a)).magnitude() / 2.
+            })
+            .fold(Scalar::ZERO, |sum, area| sum + area)
+    }
+
+    fn perimeter(
+        &self,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Scalar {
+        let tolerance = tolerance.into();
+        let surface = self.surface().geometry();
+
+        let mut cache = HalfEdgeApproxCache::default();
+        let mut perimeter = Scalar::ZERO;
+
+        for cycle in self.region().all_cycles() {
+            for (half_edge, next) in cycle.half_edges().pairs() {
+                // The approximation of a half-edge includes its start, but not
+                // its end.
+                let mut points = (&**half_edge, next.start_vertex(), surface)
+                    .approx_with_cache(tolerance, &mut cache, core)
+                    .points
+                    .into_iter()
+                    .map(|point| point.global_form)
+                    .collect::<Vec<_>>();
+                points.push(
+                    surface.point_from_surface_coords(
+                        half_edge.path().point_from_path_coords(
+                            half_edge.boundary().inner[1],
+                        ),
+                    ),
+                );
+
+                perimeter += polyline_length(&points);
+            }
+        }
+
+        perimeter
+    }
+}
+
+/// Return the axes of the surface, if it is a plane
+fn plane_axes(surface: &SurfaceGeometry) -> Option<[Vector<3>; 2]> {
+    match surface {
+        SurfaceGeometry::Swept {
+            u: GlobalPath::Line(u),
+            v,
+        } => Some([u.direction(), *v]),
+        _ => None,
+    }
+}
+
+/// Compute the signed area enclosed by a cycle
+///
+/// The area is positive, if the cycle is wound counter-clockwise.
+fn signed_area(cycle: &Cycle) -> Scalar {
+    // By Green's theorem, the area enclosed by a closed path `p` is the
+    // integral of `p × p' / 2` along it. This is summed up for each half-edge.
+    cycle
+        .half_edges()
+        .iter()
+        .map(|half_edge| path_area(&half_edge.path(), half_edge.boundary()))
+        .fold(Scalar::ZERO, |sum, area| sum + area)
+}
+
+/// Compute the integral of `p × p' / 2` along a path, within a boundary
+fn path_area(path: &SurfacePath, boundary: CurveBoundary<Point<1>>) -> Scalar {
+    let [t0, t1] = boundary.inner.map(|point| point.t);
+
+    match path {
+        SurfacePath::Circle(circle) => {
+            conic_area(circle.center(), circle.a(), circle.b(), [t0, t1])
+        }
+        SurfacePath::CubicBezier(bezier) => cubic_bezier_area(bezier, [t0, t1]),
+        SurfacePath::Ellipse(ellipse) => {
+            conic_area(ellipse.center(), ellipse.a(), ellipse.b(), [t0, t1])
+        }
+        SurfacePath::Line(_) => {
+            let [a, b] = boundary
+                .inner
+                .map(|point| path.point_from_path_coords(point).coords);
+            a.cross2d(&b) / 2.
+        }
+    }
+}
+
+/// Compute the area integral of a circle or ellipse
+///
+/// The conic is `p(t) = center + a·cos(t) + b·sin(t)`.
+fn conic_area(
+    center: Point<2>,
+    a: Vector<2>,
+    b: Vector<2>,
+    [t0, t1]: [Scalar; 2],
+) -> Scalar {
+    let c = center.coords;
+    let (sin0, cos0) = t0.sin_cos();
+    let (sin1, cos1) = t1.sin_cos();
+
+    (c.cross2d(&a) * (cos1 - cos0)
+        + c.cross2d(&b) * (sin1 - sin0)
+        + a.cross2d(&b) * (t1 - t0))
+        / 2.
+}
+
+/// Compute the area integral of a cubic Bézier curve
+fn cubic_bezier_area(bezier: &CubicBezier<2>, [t0, t1]: [Scalar; 2]) -> Scalar {
+    // The integrand is a polynomial of degree 5, for which three-point
+    // Gauss-Legendre quadrature is exact.
+    let x = (3. / 5_f64).sqrt();
+    let nodes = [(-x, 5. / 9.), (0., 8. / 9.), (x, 5. / 9.)];
+
+    let mid = (t0 + t1) / 2.;
+    let half = (t1 - t0) / 2.;
+
+    nodes
+        .into_iter()
+        .map(|(node, weight)| {
+            let t = mid + half * node;
+            let p = bezier.point_from_bezier_coords([t]).coords;
+            let d = bezier.derivative([t]);
+            p.cross2d(&d) * weight
+        })
+        .fold(Scalar::ZERO, |sum, value| sum + value)
+        * half
+        / 2.
+}
+
+/// Compute the length of a path, within a boundary
+fn path_length(
+    path: &SurfacePath,
+    boundary: CurveBoundary<Point<1>>,
+    tolerance: Tolerance,
+    core: &mut Core,
+) -> Scalar {
+    let [t0, t1] = boundary.inner.map(|point| point.t);
+
+    match path {
+        SurfacePath::Circle(circle) => circle.radius() * (t1 - t0).abs(),
+        SurfacePath::Line(line) => {
+            line.direction().magnitude() * (t1 - t0).abs()
+        }
+        SurfacePath::CubicBezier(_) | SurfacePath::Ellipse(_) => {
+            let [start, end] = boundary
+                .inner
+                .map(|point| path.point_from_path_coords(point));
+
+            let mut points = vec![start];
+            points.extend(
+                (path, boundary)
+                    .approx(tolerance, core)
+                    .into_iter()
+                    .map(|(_, point)| point),
+            );
+            points.push(end);
+
+            polyline_length(&points)
+        }
+    }
+}
+
+fn polyline_length<const D: usize>(points: &[Point<D>]) -> Scalar {
+    points
+        .windows(2)
+        .map(|segment| (segment[1] - segment[0]).magnitude())
+        .fold(Scalar::ZERO, |sum, length| sum + length)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use fj_math::Scalar;
+    use fj_test_utils::assert_scalars_eq;
+
+    use crate::{
+        algorithms::approx::Tolerance,
+        geometry::GlobalPath,
+        objects::{Cycle, Face, Region, Surface},
+        operations::{
+            build::{BuildCycle, BuildRegion, BuildSurface},
+            insert::Insert,
+            reverse::Reverse,
+            update::UpdateRegion,
+        },
+        Core,
+    };
+
+    use super::Measure;
+
+    #[test]
+    fn region() -> anyhow::Result<()> {
+        let mut core = Core::new();
+        let tolerance = Tolerance::from_scalar(0.001)?;
+
+        let square = Region::polygon(
+            [[0., 0.], [2., 0.], [2., 2.], [0., 2.]],
+            &mut core,
+        );
+        assert_eq!(square.area(tolerance, &mut core), Scalar::from(4.));
+        assert_eq!(square.perimeter(tolerance, &mut core), Scalar::from(8.));
+
+        // A hole, wound the other way, is subtracted.
+        let hole = Cycle::circle([1., 1.], 0.5, &mut core)
+            .reverse(&mut core)
+            .insert(&mut core);
+        let square_with_hole = square.add_interiors([hole], &mut core);
+        assert_scalars_eq!(
+            square_with_hole.area(tolerance, &mut core),
+            4. - PI / 4.,
+            1e-12,
+        );
+        assert_scalars_eq!(
+            square_with_hole.perimeter(tolerance, &mut core),
+            8. + PI,
+            1e-12,
+        );
+
+        let ellipse = Region::ellipse([1., 2.], [3., 1.], &mut core);
+        assert_scalars_eq!(ellipse.area(tolerance, &mut core), 3. * PI, 1e-12);
+
+        let lens = Cycle::cubic_bezier(
+            [
+                [[0.5, -1.], [1.5, -1.], [2., 0.]],
+                [[1.5, 1.], [0.5, 1.], [0., 0.]],
+            ],
+            &mut core,
+        )
+        .insert(&mut core);
+        let lens = Region::new(lens, []);
+        assert_scalars_eq!(lens.area(tolerance, &mut core), 2.1, 1e-12);
+
+        // The perimeter of an ellipse can't be computed exactly.
+        assert_scalars_eq!(
+            ellipse.perimeter(tolerance, &mut core),
+            ramanujan(3., 1.),
+            0.01,
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn face() -> anyhow::Result<()> {
+        let mut core = Core::new();
+        let tolerance = Tolerance::from_scalar(0.001)?;
+
+        let circle = Region::circle([0., 0.], 1., &mut core).insert(&mut core);
+
+        let surface = core.layers.objects.surfaces.xy_plane();
+        let face = Face::new(surface, circle.clone());
+        assert_scalars_eq!(face.area(tolerance, &mut core), PI, 1e-12);
+        assert_scalars_eq!(face.perimeter(tolerance, &mut core), 2. * PI, 0.01);
+
+        // On a plane that is stretched along one of its axes, the circle
+        // becomes an ellipse.
+        let surface = Surface::surface_from_uv(
+            GlobalPath::x_axis(),
+            [0., 2., 0.],
+            &mut core,
+        );
+        let face = Face::new(surface, circle);
+        assert_scalars_eq!(face.area(tolerance, &mut core), 2. * PI, 1e-12);
+        assert_scalars_eq!(
+            face.perimeter(tolerance, &mut core),
+            ramanujan(2., 1.),
+            0.01,
+        );
+
+        // A quarter of the side of a cylinder.
+        let surface = Surface::surface_from_uv(
+            GlobalPath::circle_from_radius(1.),
+            [0., 0., 1.],
+            &mut core,
+        );
+        let region = Region::polygon(
+            [[0., 0.], [PI / 2., 0.], [PI / 2., 1.], [0., 1.]],
+            &mut core,
+        )
+        .insert(&mut core);
+        let face = Face::new(surface, region);
+        assert_scalars_eq!(face.area(tolerance, &mut core), PI / 2., 0.01);
+        assert_scalars_eq!(face.perimeter(tolerance, &mut core), PI + 2., 0.01);
+
+        Ok(())
+    }
+
+    /// Ramanujan's approximation of the perimeter of an ellipse
+    ///
+    /// This is much more accurate than needed here.
+    fn ramanujan(a: f64, b: f64) -> f64 {
+        PI * (3. * (a + b) - ((3. * a + b) * (a + 3. * b)).sqrt())
+    }
+}
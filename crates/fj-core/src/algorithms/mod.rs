@@ -12,5 +12,9 @@
 
 pub mod approx;
 pub mod bounding_volume;
+pub mod bvh;
 pub mod intersect;
+pub mod measure;
+pub mod slice;
+pub mod symmetry;
 pub mod triangulate;
@@ -0,0 +1,397 @@
+//! Slicing of shapes into layers
+//!
+//! This is meant as a foundation for exporting shapes layer by layer, for
+//! 3D printing, for example. See [`Slice`].
+
+use std::collections::BTreeMap;
+
+use fj_interop::Mesh;
+use fj_math::{Plane, Point, Scalar, Triangle};
+
+use crate::{
+    objects::Solid,
+    operations::{
+        bitmap::simplify,
+        boolean::{contains, signed_area},
+    },
+    Core,
+};
+
+use super::{approx::Tolerance, triangulate::Triangulate};
+
+/// Slice a shape into layers of polygons
+pub trait Slice {
+    /// Slice the shape into layers of the provided height
+    ///
+    /// The layers are stacked along the z-axis, starting at the bottom of the
+    /// shape. Each layer is sliced at its middle, which results in the
+    /// polygons that describe the layer, in xy-coordinates.
+    ///
+    /// Shapes that are not triangle meshes are approximated with the provided
+    /// tolerance first. The polygons are simplified, removing as many vertices
+    /// as possible without them moving by more than the tolerance.
+    fn slice(
+        &self,
+        layer_height: impl Into<Scalar>,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Vec<SliceLayer>;
+}
+
+impl Slice for Mesh<Point<3>> {
+    fn slice(
+        &self,
+        layer_height: impl Into<Scalar>,
+        tolerance: impl Into<Tolerance>,
+        _core: &mut Core,
+    ) -> Vec<SliceLayer> {
+        let layer_height = layer_height.into();
+        let tolerance = tolerance.into();
+
+        assert!(layer_height > Scalar::ZERO, "Layer height must be positive");
+
+        let triangles = self
+            .triangles()
+            .map(|triangle| {
+                let [a, b, c] = triangle.inner.points().map(|point| point.z);
+                let min = a.min(b).min(c);
+                let max = a.max(b).max(c);
+
+                (triangle.inner, [min, max])
+            })
+            .collect::<Vec<_>>();
+
+        let Some((bottom, top)) = triangles
+            .iter()
+            .map(|(_, range)| (range[0], range[1]))
+            .reduce(|(min_a, max_a), (min_b, max_b)| {
+                (min_a.min(min_b), max_a.max(max_b))
+            })
+        else {
+            return Vec::new();
+        };
+
+        let num_layers = ((top - bottom) / layer_height).ceil().into_f64();
+
+        (0..num_layers as usize)
+            .map(|i| {
+                let z = bottom + layer_height * (i as f64 + 0.5);
+                let plane = Plane::from_parametric(
+                    [0., 0., z.into_f64()],
+                    [1., 0., 0.],
+                    [0., 1., 0.],
+                );
+
+                let triangles = triangles
+                    .iter()
+                    .filter(|(_, [min, max])| *min <= z && z <= *max)
+                    .map(|(triangle, _)| *triangle);
+                let polygons = section(triangles, &plane, tolerance);
+
+                SliceLayer { z, polygons }
+            })
+            .collect()
+    }
+}
+
+impl Slice for Solid {
+    fn slice(
+        &self,
+        layer_height: impl Into<Scalar>,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Vec<SliceLayer> {
+        let tolerance = tolerance.into();
+
+        (self, tolerance)
+            .triangulate(core)
+            .slice(layer_height, tolerance, core)
+    }
+}
+
+/// A layer of a sliced shape
+///
+/// See [`Slice`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct SliceLayer {
+    /// The height at which the shape was sliced
+    pub z: Scalar,
+
+    /// The polygons that describe the shape at this height
+    pub polygons: Vec<SlicePolygon>,
+}
+
+/// A polygon, that describes an area of a sliced shape
+///
+/// The first point of each cycle is not repeated at its end.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct SlicePolygon {
+    /// The outer boundary of the polygon, counter-clockwise
+    pub exterior: Vec<Point<2>>,
+
+    /// The holes in the polygon, each clockwise
+    pub interiors: Vec<Vec<Point<2>>>,
+}
+
+/// Intersect the triangles of a closed mesh with a plane
+///
+/// Returns the polygons within the plane, that the mesh encloses, in plane
+/// coordinates.
+///
+/// Triangles that lie within the plane are considered to be on the positive
+/// side of it (the side its normal points to).
+pub(crate) fn section(
+    triangles: impl IntoIterator<Item = Triangle<3>>,
+    plane: &Plane,
+    tolerance: Tolerance,
+) -> Vec<SlicePolygon> {
+    let segments = triangles
+        .into_iter()
+        .filter_map(|triangle| section_triangle(plane, triangle));
+
+    let contours = contours(segments)
+        .into_iter()
+        .map(|contour| simplify(&contour, tolerance.inner()))
+        .filter(|contour| {
+            contour.len() >= 3 && signed_area(contour) != Scalar::ZERO
+        })
+        .collect::<Vec<_>>();
+
+    // Contours never cross each other, so how many of them contain a contour
+    // tells us whether it is an exterior or an interior. We can't rely on the
+    // orientation of the contours for that, as it depends on the orientation
+    // of the shells they come from.
+    let depths = contours
+        .iter()
+        .enumerate()
+        .map(|(i, contour)| {
+            contours
+                .iter()
+                .enumerate()
+                .filter(|&(j, other)| i != j && contains(other, contour[0]))
+                .count()
+        })
+        .collect::<Vec<_>>();
+
+    let cycles = contours
+        .iter()
+        .zip(&depths)
+        .map(|(contour, depth)| {
+            let mut points = contour.clone();
+
+            // Exteriors must be counter-clockwise, interiors clockwise.
+            let is_exterior = depth % 2 == 0;
+            if is_exterior != (signed_area(&points) > Scalar::ZERO) {
+                points.reverse();
+            }
+
+            points
+        })
+        .collect::<Vec<_>>();
+
+    let mut polygons = BTreeMap::new();
+    for (i, cycle) in cycles.iter().enumerate() {
+        if depths[i] % 2 == 0 {
+            polygons.insert(
+                i,
+                SlicePolygon {
+                    exterior: cycle.clone(),
+                    interiors: Vec::new(),
+                },
+            );
+        }
+    }
+    for (i, cycle) in cycles.iter().enumerate() {
+        if depths[i] % 2 == 0 {
+            continue;
+        }
+
+        // The polygon this interior belongs to is the one contour that is one
+        // level further out and contains it.
+        let parent = (0..contours.len()).find(|&j| {
+            depths[j] + 1 == depths[i] && contains(&contours[j], contours[i][0])
+        });
+        if let Some(polygon) = parent.and_then(|j| polygons.get_mut(&j)) {
+            polygon.interiors.push(cycle.clone());
+        }
+    }
+
+    polygons.into_values().collect()
+}
+
+/// Compute the segment along which a triangle crosses the plane
+///
+/// The segment is given in plane coordinates. It is oriented according to the
+/// triangle's normal, so the segments of all triangles of a shell can be
+/// connected head to tail.
+fn section_triangle(
+    plane: &Plane,
+    triangle: Triangle<3>,
+) -> Option<[Point<2>; 2]> {
+    let normal = plane.normal();
+    let points = triangle.points();
+    let distances = points.map(|point| (point - plane.origin()).dot(&normal));
+
+    // Points on the plane count as being above it. That way, no edge lies
+    // within the plane, and an edge crosses the plane at most once.
+    let crossings = [[0, 1], [1, 2], [2, 0]]
+        .into_iter()
+        .filter_map(|[i, j]| {
+            let [above_i, above_j] =
+                [i, j].map(|k| distances[k] >= Scalar::ZERO);
+            if above_i == above_j {
+                return None;
+            }
+
+            // Always compute the crossing from the point above the plane. The
+            // neighboring triangle traverses the edge in the other direction,
+            // and needs to come up with the exact same crossing.
+            let [a, b] = if above_i { [i, j] } else { [j, i] };
+            let t = distances[a] / (distances[a] - distances[b]);
+            let crossing = points[a] + (points[b] - points[a]) * t;
+
+            Some(plane.project_point(crossing))
+        })
+        .collect::<Vec<_>>();
+
+    let [a, b] = crossings[..] else {
+        return None;
+    };
+    if a == b {
+        return None;
+    }
+
+    let direction = plane.project_vector(normal.cross(&triangle.normal()));
+    if (b - a).dot(&direction) > Scalar::ZERO {
+        Some([a, b])
+    } else {
+        Some([b, a])
+    }
+}
+
+/// Connect the segments into closed contours
+///
+/// Segments that can't be connected into a closed contour are dropped. This
+/// only happens, if the approximation of the shape is not watertight.
+fn contours(
+    segments: impl IntoIterator<Item = [Point<2>; 2]>,
+) -> Vec<Vec<Point<2>>> {
+    let mut next = BTreeMap::<_, Vec<_>>::new();
+    for [a, b] in segments {
+        next.entry(a).or_default().push(b);
+    }
+
+    let mut contours = Vec::new();
+
+    while let Some(&start) = next.keys().next() {
+        let mut contour = Vec::new();
+        let mut current = start;
+
+        let is_closed = loop {
+            let Some(ends) = next.get_mut(&current) else {
+                break false;
+            };
+            let Some(end) = ends.pop() else {
+                unreachable!("Only points with remaining segments are stored");
+            };
+            if ends.is_empty() {
+                next.remove(&current);
+            }
+
+            contour.push(current);
+            current = end;
+
+            if current == start {
+                break true;
+            }
+        };
+
+        if is_closed {
+            contours.push(contour);
+        }
+    }
+
+    contours
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use fj_math::Scalar;
+
+    use crate::{
+        objects::{Shell, Solid},
+        operations::{
+            boolean::signed_area,
+            build::{BuildShell, BuildSolid},
+            insert::Insert,
+            update::UpdateSolid,
+        },
+        Core,
+    };
+
+    use super::Slice;
+
+    #[test]
+    fn slice_hollow_solid() {
+        let mut core = Core::new();
+
+        let [outer, inner] = [
+            Shell::cuboid([-2., -2., 0.], [2., 2., 2.], &mut core),
+            Shell::cuboid([-1., -1., 0.5], [1., 1., 1.5], &mut core),
+        ];
+        let solid = Solid::empty().add_shells(
+            [outer, inner].map(|shell| shell.insert(&mut core)),
+            &mut core,
+        );
+
+        let layers = solid.slice(0.25, 0.01, &mut core);
+
+        let heights = layers.iter().map(|layer| layer.z).collect::<Vec<_>>();
+        let expected = (0..8)
+            .map(|i| Scalar::from(0.125 + 0.25 * i as f64))
+            .collect::<Vec<_>>();
+        assert_eq!(heights, expected);
+
+        for layer in &layers {
+            let [polygon] = &layer.polygons[..] else {
+                panic!("Expected one polygon per layer");
+            };
+            assert_eq!(polygon.exterior.len(), 4);
+            assert_eq!(signed_area(&polygon.exterior), Scalar::from(16.));
+
+            // Only the layers in the middle cut through the cavity.
+            let is_within_cavity =
+                layer.z > Scalar::from(0.5) && layer.z < Scalar::from(1.5);
+            let expected_interiors = if is_within_cavity { 1 } else { 0 };
+            assert_eq!(polygon.interiors.len(), expected_interiors);
+
+            for interior in &polygon.interiors {
+                assert_eq!(signed_area(interior), Scalar::from(-4.));
+            }
+        }
+    }
+
+    #[test]
+    fn slice_cylinder() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let solid = Solid::cylinder(1., 2., &mut core)?;
+        let layers = solid.slice(0.5, 0.001, &mut core);
+
+        assert_eq!(layers.len(), 4);
+        for layer in &layers {
+            let [polygon] = &layer.polygons[..] else {
+                panic!("Expected one polygon per layer");
+            };
+
+            // The approximation cuts off a bit of the circle.
+            let area = signed_area(&polygon.exterior);
+            assert!(area < Scalar::from(PI));
+            assert!(area > Scalar::from(PI - 0.01));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,420 @@
+//! Detection of the symmetries of a shape
+//!
+//! See [`DetectSymmetry`].
+
+use fj_interop::Mesh;
+use fj_math::{Point, Scalar, Transform, Triangle, Vector};
+
+use crate::{
+    geometry::{GlobalPath, SurfaceGeometry},
+    objects::{Face, Shell, Solid},
+    storage::Handle,
+    Core,
+};
+
+use super::{
+    approx::Tolerance,
+    triangulate::{index::TriangleIndex, Triangulate},
+};
+
+/// Detect the symmetries of a shape
+pub trait DetectSymmetry {
+    /// Detect the symmetry planes and axes of the shape
+    ///
+    /// A shape is symmetric with respect to a plane, if reflecting it across
+    /// that plane results in the same shape. It is symmetric with respect to
+    /// an axis, if rotating it around that axis does. All symmetry planes and
+    /// axes of a shape go through its centroid.
+    ///
+    /// Shapes are bounded, so they can't have translational symmetry. Only
+    /// reflections and rotations are considered.
+    ///
+    /// # Implementation Note
+    ///
+    /// The shape is approximated with the provided tolerance, and symmetries
+    /// are detected by checking whether the transformed approximation matches
+    /// the original one. Only candidate planes and axes derived from the
+    /// coordinate axes and the geometry of the shape's faces are checked, so
+    /// symmetries that are not aligned with any of those are not detected.
+    ///
+    /// If a shape has continuous rotational symmetry, it is symmetric with
+    /// respect to infinitely many planes that contain the axis. Only those
+    /// among the candidates are returned.
+    fn symmetries(
+        &self,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Symmetries;
+}
+
+impl DetectSymmetry for Shell {
+    fn symmetries(
+        &self,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Symmetries {
+        let tolerance = tolerance.into();
+        let mesh = (self, tolerance).triangulate(core);
+        let max_angle = core.layers.validation.config.identical_max_angle;
+
+        symmetries(&mesh, self.faces(), tolerance, max_angle)
+    }
+}
+
+impl DetectSymmetry for Solid {
+    fn symmetries(
+        &self,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Symmetries {
+        let tolerance = tolerance.into();
+        let mesh = (self, tolerance).triangulate(core);
+        let max_angle = core.layers.validation.config.identical_max_angle;
+
+        let faces = self.shells().iter().flat_map(|shell| shell.faces());
+        symmetries(&mesh, faces, tolerance, max_angle)
+    }
+}
+
+/// The symmetries of a shape
+///
+/// See [`DetectSymmetry`].
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Symmetries {
+    /// The planes that the shape is symmetric with respect to
+    pub planes: Vec<SymmetryPlane>,
+
+    /// The axes that the shape is symmetric with respect to
+    pub axes: Vec<SymmetryAxis>,
+}
+
+/// A plane that a shape is symmetric with respect to
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct SymmetryPlane {
+    /// A point on the plane
+    pub origin: Point<3>,
+
+    /// The normal of the plane, normalized
+    pub normal: Vector<3>,
+}
+
+/// An axis that a shape is symmetric with respect to
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct SymmetryAxis {
+    /// A point on the axis
+    pub origin: Point<3>,
+
+    /// The direction of the axis, normalized
+    pub direction: Vector<3>,
+
+    /// The order of the rotational symmetry
+    pub order: SymmetryOrder,
+}
+
+/// The order of a rotational symmetry
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum SymmetryOrder {
+    /// The shape is symmetric with respect to rotations by `2 * PI / n`
+    ///
+    /// `n` is the largest number for which that is the case, up to
+    /// [`SymmetryOrder::MAX_FINITE`].
+    Finite(u32),
+
+    /// The shape is symmetric with respect to rotations by any angle
+    Continuous,
+}
+
+impl SymmetryOrder {
+    /// The largest finite order that is detected
+    pub const MAX_FINITE: u32 = 12;
+}
+
+/// The angle that is used to check for continuous rotational symmetry
+///
+/// This is an irrational fraction of a full turn, so a shape that is symmetric
+/// with respect to a rotation by this angle has no finite order.
+const CONTINUOUS_TEST_ANGLE: f64 = 1.;
+
+fn symmetries<'r>(
+    mesh: &Mesh<Point<3>>,
+    faces: impl IntoIterator<Item = &'r Handle<Face>>,
+    tolerance: Tolerance,
+    max_angle: Scalar,
+) -> Symmetries {
+    let triangles = mesh
+        .triangles()
+        .map(|triangle| triangle.inner)
+        .collect::<Vec<_>>();
+    let Some(centroid) = centroid(&triangles) else {
+        return Symmetries::default();
+    };
+
+    // The vertices of the approximation are on the shape, and the centroids of
+    // its triangles are within the tolerance of it. Transformed by a symmetry,
+    // either will be within twice the tolerance of the approximation.
+    let threshold = tolerance.inner() * 2.;
+    let index = TriangleIndex::new(triangles.iter().copied(), threshold);
+    let samples = mesh
+        .vertices()
+        .chain(triangles.iter().map(|triangle| {
+            let [a, b, c] = triangle.points();
+            Point {
+                coords: (a.coords + b.coords + c.coords) / 3.,
+            }
+        }))
+        .collect::<Vec<_>>();
+
+    let is_symmetric = |transform: &Transform| {
+        samples
+            .iter()
+            .all(|point| index.is_near(&transform.transform_point(point)))
+    };
+
+    // Directions that are within the maximum angle of each other are
+    // considered to be the same.
+    let candidates = candidates(faces, max_angle.sin_cos().0);
+    let mut symmetries = Symmetries::default();
+
+    for &normal in &candidates {
+        if is_symmetric(&Transform::reflection(centroid, normal)) {
+            symmetries.planes.push(SymmetryPlane {
+                origin: centroid,
+                normal,
+            });
+        }
+    }
+
+    for &direction in &candidates {
+        let rotation = |angle: Scalar| {
+            Transform::translation(centroid.coords)
+                * Transform::rotation(direction * angle)
+                * Transform::translation(-centroid.coords)
+        };
+
+        let order = if is_symmetric(&rotation(CONTINUOUS_TEST_ANGLE.into())) {
+            Some(SymmetryOrder::Continuous)
+        } else {
+            (2..=SymmetryOrder::MAX_FINITE)
+                .rev()
+                .find(|&n| is_symmetric(&rotation(Scalar::TAU / n as f64)))
+                .map(SymmetryOrder::Finite)
+        };
+
+        if let Some(order) = order {
+            symmetries.axes.push(SymmetryAxis {
+                origin: centroid,
+                direction,
+                order,
+            });
+        }
+    }
+
+    symmetries
+}
+
+/// Compute the centroid of the surface of a triangle mesh
+fn centroid(triangles: &[Triangle<3>]) -> Option<Point<3>> {
+    let mut weighted_sum = Vector::from([0., 0., 0.]);
+    let mut total_area = Scalar::ZERO;
+
+    for triangle in triangles {
+        let [a, b, c] = triangle.points();
+        let area = (b - a).cross(&(c - a)).magnitude() / 2.;
+
+        weighted_sum =
+            weighted_sum + (a.coords + b.coords + c.coords) / 3. * area;
+        total_area += area;
+    }
+
+    if total_area == Scalar::ZERO {
+        return None;
+    }
+
+    Some(Point {
+        coords: weighted_sum / total_area,
+    })
+}
+
+/// Compute the candidate directions for symmetry planes and axes
+///
+/// These are used as the normals of candidate planes, as well as the
+/// directions of candidate axes.
+fn candidates<'r>(
+    faces: impl IntoIterator<Item = &'r Handle<Face>>,
+    max_sine: Scalar,
+) -> Vec<Vector<3>> {
+    let mut directions = Vec::new();
+
+    for direction in [Vector::unit_x(), Vector::unit_y(), Vector::unit_z()] {
+        push_direction(&mut directions, direction, max_sine);
+    }
+
+    for face in faces {
+        let SurfaceGeometry::Swept { u, v } = face.surface().geometry() else {
+            continue;
+        };
+
+        match u {
+            GlobalPath::Line(line) => {
+                push_direction(
+                    &mut directions,
+                    line.direction().cross(v),
+                    max_sine,
+                );
+            }
+            GlobalPath::Circle(circle) => {
+                push_direction(
+                    &mut directions,
+                    circle.a().cross(&circle.b()),
+                    max_sine,
+                );
+                push_direction(&mut directions, *v, max_sine);
+            }
+            GlobalPath::CubicBezier(_) | GlobalPath::Ellipse(_) => {
+                push_direction(&mut directions, *v, max_sine);
+            }
+        }
+    }
+
+    // Symmetry planes and axes can be between the features of a shape, like
+    // the diagonal planes of a square prism.
+    let num_directions = directions.len();
+    for i in 0..num_directions {
+        for j in i + 1..num_directions {
+            let [a, b] = [directions[i], directions[j]];
+
+            push_direction(&mut directions, a + b, max_sine);
+            push_direction(&mut directions, a - b, max_sine);
+            push_direction(&mut directions, a.cross(&b), max_sine);
+        }
+    }
+
+    directions
+}
+
+/// Add a direction to the candidates, unless it's already among them
+///
+/// Directions are normalized, and their sign is chosen to make their first
+/// non-zero component positive. Opposite directions describe the same plane or
+/// axis. Directions are considered to be the same, if the sine of the angle
+/// between them is no larger than `max_sine`.
+fn push_direction(
+    directions: &mut Vec<Vector<3>>,
+    direction: Vector<3>,
+    max_sine: Scalar,
+) {
+    if direction.magnitude() == Scalar::ZERO {
+        return;
+    }
+    let mut direction = direction.normalize();
+
+    let first_non_zero = direction
+        .components
+        .iter()
+        .find(|component| component.abs() > max_sine);
+    if let Some(component) = first_non_zero {
+        if *component < Scalar::ZERO {
+            direction = -direction;
+        }
+    }
+
+    let is_known = directions
+        .iter()
+        .any(|known| known.cross(&direction).magnitude() <= max_sine);
+    if !is_known {
+        directions.push(direction);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar, Vector};
+    use fj_test_utils::assert_points_eq;
+
+    use crate::{
+        objects::{Shell, Solid},
+        operations::{
+            build::{BuildShell, BuildSolid},
+            insert::Insert,
+            update::UpdateSolid,
+        },
+        Core,
+    };
+
+    use super::{DetectSymmetry, SymmetryOrder};
+
+    #[test]
+    fn cuboid() {
+        let mut core = Core::new();
+
+        let cuboid = Shell::cuboid([0., 0., 0.], [2., 2., 1.], &mut core);
+        let symmetries = cuboid.symmetries(0.01, &mut core);
+
+        let center = Point::from([1., 1., 0.5]);
+        for plane in &symmetries.planes {
+            assert_points_eq!(plane.origin, center, 1e-9);
+        }
+
+        // The cross-section is a square, so the diagonal planes are symmetry
+        // planes too.
+        let normals = symmetries
+            .planes
+            .iter()
+            .map(|plane| plane.normal)
+            .collect::<Vec<_>>();
+        let diagonal = Vector::from([1., 1., 0.]).normalize();
+        assert_eq!(normals.len(), 5);
+        assert!(normals.contains(&Vector::unit_x()));
+        assert!(normals.contains(&Vector::unit_y()));
+        assert!(normals.contains(&Vector::unit_z()));
+        assert!(normals.iter().any(|normal| {
+            (*normal - diagonal).magnitude() < Scalar::from(1e-9)
+        }));
+
+        let axes = symmetries
+            .axes
+            .iter()
+            .map(|axis| (axis.direction, axis.order))
+            .collect::<Vec<_>>();
+        assert!(axes.contains(&(Vector::unit_x(), SymmetryOrder::Finite(2))));
+        assert!(axes.contains(&(Vector::unit_y(), SymmetryOrder::Finite(2))));
+        assert!(axes.contains(&(Vector::unit_z(), SymmetryOrder::Finite(4))));
+    }
+
+    #[test]
+    fn cylinder() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let cylinder = Solid::cylinder(1., 2., &mut core)?;
+        let symmetries = cylinder.symmetries(0.01, &mut core);
+
+        let axis = symmetries
+            .axes
+            .iter()
+            .find(|axis| axis.direction == Vector::unit_z())
+            .expect("Expected cylinder to be symmetric around its axis");
+        assert_eq!(axis.order, SymmetryOrder::Continuous);
+        assert!(
+            axis.origin.distance_to(&Point::from([0., 0., 1.]))
+                < Scalar::from(0.01)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn asymmetric() {
+        let mut core = Core::new();
+
+        let shells = [
+            Shell::cuboid([0., 0., 0.], [3., 1., 1.], &mut core),
+            Shell::cuboid([0., 1., 0.], [1., 2., 2.], &mut core),
+        ];
+        let solid = Solid::empty()
+            .add_shells(shells.map(|shell| shell.insert(&mut core)), &mut core);
+
+        let symmetries = solid.symmetries(0.01, &mut core);
+        assert!(symmetries.planes.is_empty());
+        assert!(symmetries.axes.is_empty());
+    }
+}
@@ -1,51 +1,164 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 
 use fj_math::{Point, Scalar, Triangle, Winding};
-use spade::HasPosition;
+use spade::{
+    handles::{
+        DirectedEdgeHandle, FixedFaceHandle, FixedVertexHandle, InnerTag,
+    },
+    ConstrainedDelaunayTriangulation, HasPosition, Triangulation,
+};
 
-use crate::{algorithms::approx::cycle::CycleApprox, objects::Handedness};
+use crate::{
+    algorithms::approx::{cycle::CycleApprox, ApproxPoint},
+    objects::Handedness,
+};
 
-/// Create a Delaunay triangulation of all points
+/// Create a constrained Delaunay triangulation of the area bounded by cycles
+///
+/// The edges of the cycles become constraints of the triangulation, and only
+/// triangles that are within the area bounded by them are returned.
+///
+/// Points are bulk-loaded into the triangulation, and the triangles within the
+/// area are determined by a single walk over the triangulation, starting
+/// outside. This keeps the triangulation efficient, even for regions with
+/// thousands of points and many holes.
+///
+/// `interior_points` are inserted into the triangulation without becoming part
+/// of any constraint. They must be located within the area.
+///
+/// If `max_edge_length` is provided, the triangulation is refined until no
+/// triangle has an edge that is longer, as measured in global coordinates.
 pub fn triangulate(
     cycles: impl IntoIterator<Item = CycleApprox>,
+    interior_points: impl IntoIterator<Item = ApproxPoint<2>>,
     coord_handedness: Handedness,
+    max_edge_length: Option<Scalar>,
 ) -> Vec<[TriangulationPoint; 3]> {
-    use spade::Triangulation as _;
+    let cycles = cycles
+        .into_iter()
+        .map(|cycle| {
+            let points =
+                cycle.points().into_iter().map(|point| TriangulationPoint {
+                    point_surface: point.local_form,
+                    point_global: point.global_form,
+                });
 
-    let mut triangulation = spade::ConstrainedDelaunayTriangulation::<_>::new();
+            match max_edge_length {
+                Some(max_edge_length) => {
+                    subdivide_cycle(points, max_edge_length)
+                }
+                None => points.collect(),
+            }
+        })
+        .collect::<Vec<_>>();
 
-    let mut points = BTreeMap::new();
+    // Only insert each point once. Points that are shared between cycles are
+    // connected to all the respective constraints below.
+    let mut points = cycles.iter().flatten().copied().collect::<Vec<_>>();
+    points.extend(interior_points.into_iter().map(|point| {
+        TriangulationPoint {
+            point_surface: point.local_form,
+            point_global: point.global_form,
+        }
+    }));
+    points.sort();
+    points.dedup();
 
-    for cycle_approx in cycles {
-        let mut handle_prev = None;
+    let mut triangulation: ConstrainedDelaunayTriangulation<_> =
+        Triangulation::bulk_load(points)
+            .expect("Inserted invalid point into triangulation");
 
-        for point in cycle_approx.points() {
-            let handle = match points.get(&point) {
-                Some(handle) => *handle,
-                None => {
-                    let handle = triangulation
-                        .insert(TriangulationPoint {
-                            point_surface: point.local_form,
-                            point_global: point.global_form,
-                        })
-                        .expect("Inserted invalid point into triangulation");
+    let handles = triangulation
+        .fixed_vertices()
+        .map(|handle| {
+            (triangulation.vertex(handle).data().point_surface, handle)
+        })
+        .collect::<BTreeMap<_, _>>();
 
-                    points.insert(point, handle);
+    // Edges that are shared by multiple cycles are only added once as a
+    // constraint. We need to keep track of how many times each edge occurs,
+    // to know what crossing it means.
+    let mut constraints = BTreeMap::new();
 
-                    handle
-                }
-            };
+    for cycle in cycles {
+        let mut handle_prev = None;
+
+        for point in cycle {
+            let handle = handles[&point.point_surface];
 
             if let Some(handle_prev) = handle_prev {
-                triangulation.add_constraint(handle_prev, handle);
+                if handle_prev != handle {
+                    triangulation.add_constraint(handle_prev, handle);
+
+                    for edge in
+                        constraint_edges(&triangulation, handle_prev, handle)
+                    {
+                        *constraints.entry(edge).or_insert(0) += 1;
+                    }
+                }
             }
 
             handle_prev = Some(handle);
         }
     }
 
+    let mut is_inside = classify_faces(&triangulation, &constraints);
+
+    if let Some(max_edge_length) = max_edge_length {
+        loop {
+            let mut too_long = BTreeSet::new();
+
+            for (&face, &inside) in &is_inside {
+                if !inside {
+                    continue;
+                }
+
+                for edge in triangulation.face(face).adjacent_edges() {
+                    // The cycles have already been subdivided. Splitting
+                    // their edges again here would result in points that
+                    // the adjacent faces don't have.
+                    if edge.is_constraint_edge() {
+                        continue;
+                    }
+
+                    let [a, b] =
+                        [edge.from(), edge.to()].map(|vertex| *vertex.data());
+
+                    if (b.point_global - a.point_global).magnitude()
+                        > max_edge_length
+                    {
+                        too_long.insert(ordered_pair(
+                            edge.from().fix(),
+                            edge.to().fix(),
+                        ));
+                    }
+                }
+            }
+
+            if too_long.is_empty() {
+                break;
+            }
+
+            // We only look at triangles within the area, so all midpoints are
+            // within the area too.
+            for [a, b] in too_long {
+                let [a, b] =
+                    [a, b].map(|vertex| *triangulation.vertex(vertex).data());
+                triangulation
+                    .insert(TriangulationPoint::midpoint(a, b))
+                    .expect("Inserted invalid point into triangulation");
+            }
+
+            is_inside = classify_faces(&triangulation, &constraints);
+        }
+    }
+
     let mut triangles = Vec::new();
     for triangle in triangulation.inner_faces() {
+        if !is_inside.get(&triangle.fix()).copied().unwrap_or(false) {
+            continue;
+        }
+
         let [v0, v1, v2] = triangle.vertices().map(|vertex| *vertex.data());
         let triangle_winding = Triangle::<2>::from_points([
             v0.point_surface,
@@ -72,12 +185,184 @@ pub fn triangulate(
     triangles
 }
 
+/// Determine which faces of the triangulation are within the area
+///
+/// Walks the triangulation, starting outside of it. Crossing the boundary of
+/// the area toggles whether we're inside of it.
+fn classify_faces(
+    triangulation: &ConstrainedDelaunayTriangulation<TriangulationPoint>,
+    constraints: &BTreeMap<[FixedVertexHandle; 2], usize>,
+) -> BTreeMap<FixedFaceHandle<InnerTag>, bool> {
+    let mut is_inside = BTreeMap::new();
+    let mut queue = VecDeque::new();
+
+    // Multiple cycles might share the same edge, in which case we cross the
+    // boundary multiple times at once.
+    let toggles = |edge: DirectedEdgeHandle<_, _, _, _>| {
+        if !triangulation.is_constraint_edge(edge.as_undirected().fix()) {
+            return false;
+        }
+
+        let num_crossings = constraints
+            .get(&ordered_pair(edge.from().fix(), edge.to().fix()))
+            .copied()
+            .expect("Expected all constraint edges to be recorded");
+
+        num_crossings % 2 == 1
+    };
+
+    // Everything outside of the triangulation is also outside of the area.
+    // Start walking the triangulation from there.
+    for edge in triangulation.convex_hull() {
+        if let Some(face) = edge.rev().face().as_inner() {
+            is_inside.entry(face.fix()).or_insert_with(|| {
+                queue.push_back(face.fix());
+                toggles(edge)
+            });
+        }
+    }
+
+    while let Some(face) = queue.pop_front() {
+        let inside = is_inside[&face];
+
+        for edge in triangulation.face(face).adjacent_edges() {
+            let Some(neighbor) = edge.rev().face().as_inner() else {
+                continue;
+            };
+
+            is_inside.entry(neighbor.fix()).or_insert_with(|| {
+                queue.push_back(neighbor.fix());
+                inside != toggles(edge)
+            });
+        }
+    }
+
+    is_inside
+}
+
+/// Subdivide the segments of a cycle that are longer than the maximum length
+///
+/// The new points are computed from the endpoints of each segment in a
+/// canonical order, so faces that share an edge end up with exactly the same
+/// points on it.
+fn subdivide_cycle(
+    points: impl IntoIterator<Item = TriangulationPoint>,
+    max_edge_length: Scalar,
+) -> Vec<TriangulationPoint> {
+    let mut subdivided: Vec<TriangulationPoint> = Vec::new();
+
+    for point in points {
+        if let Some(&prev) = subdivided.last() {
+            let length = (point.point_global - prev.point_global).magnitude();
+            let num_segments = (length / max_edge_length).ceil();
+
+            let [a, b] = if prev.point_global <= point.point_global {
+                [prev, point]
+            } else {
+                [point, prev]
+            };
+
+            let mut intermediate = Vec::new();
+            let mut i = Scalar::ONE;
+            while i < num_segments {
+                intermediate.push(TriangulationPoint::lerp(
+                    a,
+                    b,
+                    i / num_segments,
+                ));
+                i += Scalar::ONE;
+            }
+
+            if a != prev {
+                intermediate.reverse();
+            }
+
+            subdivided.extend(intermediate);
+        }
+
+        subdivided.push(point);
+    }
+
+    subdivided
+}
+
+/// Find the constraint edges that make up the constraint between two vertices
+///
+/// Spade splits a constraint, if it goes through another vertex. The resulting
+/// edges don't connect the original endpoints, so we need to walk along the
+/// constraint to find them.
+fn constraint_edges(
+    triangulation: &ConstrainedDelaunayTriangulation<TriangulationPoint>,
+    from: FixedVertexHandle,
+    to: FixedVertexHandle,
+) -> Vec<[FixedVertexHandle; 2]> {
+    let target = triangulation.vertex(to).data().point_surface;
+
+    let mut edges = Vec::new();
+    let mut current = from;
+
+    while current != to {
+        let position = triangulation.vertex(current).data().point_surface;
+        let direction = (target - position).normalize();
+
+        // All edges of the constraint lie on the line towards the target.
+        let next = triangulation
+            .vertex(current)
+            .out_edges()
+            .filter(|edge| edge.is_constraint_edge())
+            .map(|edge| edge.to())
+            .max_by_key(|vertex| {
+                (vertex.data().point_surface - position)
+                    .normalize()
+                    .dot(&direction)
+            })
+            .expect("Constraint must consist of constraint edges");
+
+        assert!(
+            next.data().point_surface.distance_to(&target)
+                < position.distance_to(&target),
+            "Walking along constraint must approach its end"
+        );
+
+        edges.push(ordered_pair(current, next.fix()));
+        current = next.fix();
+    }
+
+    edges
+}
+
+fn ordered_pair(
+    a: FixedVertexHandle,
+    b: FixedVertexHandle,
+) -> [FixedVertexHandle; 2] {
+    if a < b {
+        [a, b]
+    } else {
+        [b, a]
+    }
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub struct TriangulationPoint {
     pub point_surface: Point<2>,
     pub point_global: Point<3>,
 }
 
+impl TriangulationPoint {
+    fn lerp(a: Self, b: Self, t: Scalar) -> Self {
+        Self {
+            point_surface: a.point_surface
+                + (b.point_surface - a.point_surface) * t,
+            point_global: a.point_global
+                + (b.point_global - a.point_global) * t,
+        }
+    }
+
+    fn midpoint(a: Self, b: Self) -> Self {
+        Self::lerp(a, b, Scalar::from(0.5))
+    }
+}
+
 // Enables the use of `LocalPoint` in the triangulation.
 impl HasPosition for TriangulationPoint {
     type Scalar = Scalar;
@@ -0,0 +1,124 @@
+//! Spatial queries on the triangles of a mesh
+//!
+//! See [`TriangleIndex`].
+
+use fj_math::{Aabb, Point, Scalar, Triangle, Vector};
+
+use crate::algorithms::intersect::solid_point::ray_directions;
+
+/// Answers spatial queries about the triangles of a mesh
+///
+/// Each triangle is stored with its bounding box, grown by a margin. Queries
+/// that look for triangles within that margin of something can skip all others
+/// cheaply.
+pub(crate) struct TriangleIndex {
+    triangles: Vec<(Triangle<3>, Aabb<3>)>,
+    margin: Scalar,
+}
+
+impl TriangleIndex {
+    pub fn new(
+        triangles: impl IntoIterator<Item = Triangle<3>>,
+        margin: Scalar,
+    ) -> Self {
+        let offset = Vector::from([margin; 3]);
+        let triangles = triangles
+            .into_iter()
+            .map(|triangle| {
+                let aabb = Aabb::<3>::from_points(triangle.points());
+                let aabb = Aabb {
+                    min: aabb.min - offset,
+                    max: aabb.max + offset,
+                };
+
+                (triangle, aabb)
+            })
+            .collect();
+
+        Self { triangles, margin }
+    }
+
+    pub fn triangles(&self) -> impl Iterator<Item = &Triangle<3>> {
+        self.triangles.iter().map(|(triangle, _)| triangle)
+    }
+
+    /// Compute the bounding box of all triangles, grown by the margin
+    pub fn aabb(&self) -> Option<Aabb<3>> {
+        self.triangles
+            .iter()
+            .map(|(_, aabb)| *aabb)
+            .reduce(|a, b| a.merged(&b))
+    }
+
+    /// Determine whether the point is within the margin of any triangle
+    pub fn is_near(&self, point: &Point<3>) -> bool {
+        self.triangles.iter().any(|(triangle, aabb)| {
+            aabb.contains(*point)
+                && triangle.distance_to_point(*point) <= self.margin
+        })
+    }
+
+    /// Determine whether the triangle is within the margin of any triangle
+    pub fn is_near_triangle(&self, triangle: &Triangle<3>) -> bool {
+        let aabb = Aabb::<3>::from_points(triangle.points());
+
+        self.triangles.iter().any(|(other, other_aabb)| {
+            aabb_overlap(&aabb, other_aabb)
+                && triangle.distance_to_triangle(other) <= self.margin
+        })
+    }
+
+    /// Determine whether the point is enclosed by the triangles
+    ///
+    /// This expects the triangles to form closed surfaces. Casts a ray from the
+    /// point, and counts how often it crosses a triangle. Where that is
+    /// ambiguous, because the ray hits an edge or a vertex, or only grazes a
+    /// triangle, another ray is cast in a different direction.
+    ///
+    /// Returns `None`, if every ray that was cast was ambiguous.
+    pub fn contains(&self, point: &Point<3>) -> Option<bool> {
+        for direction in ray_directions() {
+            let mut distances = Vec::new();
+            let mut sines = Vec::new();
+
+            for (triangle, _) in &self.triangles {
+                let Some(distance) = triangle.cast_local_ray(
+                    *point,
+                    direction,
+                    f64::INFINITY,
+                    true,
+                ) else {
+                    continue;
+                };
+
+                distances.push(distance);
+                sines.push(triangle.normal().dot(&direction).abs());
+            }
+
+            distances.sort();
+
+            let hits_edge_or_vertex = distances
+                .windows(2)
+                .any(|distances| distances[1] - distances[0] <= self.margin);
+
+            // See `ContainsPoint` on when a ray grazes a face.
+            let length = distances.last().copied().unwrap_or(Scalar::ZERO);
+            let grazes_triangle =
+                sines.iter().any(|&sine| sine * length < self.margin);
+
+            if !hits_edge_or_vertex && !grazes_triangle {
+                return Some(distances.len() % 2 == 1);
+            }
+        }
+
+        None
+    }
+}
+
+/// Determine whether two bounding boxes overlap or touch
+pub(crate) fn aabb_overlap(a: &Aabb<3>, b: &Aabb<3>) -> bool {
+    (0..3).all(|i| {
+        a.min.coords.components[i] <= b.max.coords.components[i]
+            && b.min.coords.components[i] <= a.max.coords.components[i]
+    })
+}
@@ -0,0 +1,62 @@
+//! Maximum edge length for triangulation
+//!
+//! See [`MaxEdgeLength`].
+
+use fj_math::Scalar;
+
+/// The maximum length of the edges of triangles in a triangulation
+///
+/// By default, triangulation creates as few triangles as possible, which
+/// results in large triangles on large flat faces. Some downstream uses (like
+/// finite element analysis, or vertex-based lighting) need smaller triangles.
+/// Passing a maximum edge length to the triangulation subdivides triangles,
+/// until none of their edges is longer than that.
+///
+/// This is independent of the [`Tolerance`], which controls how closely curved
+/// shapes are approximated. Subdividing triangles does not make the
+/// approximation of curved surfaces more accurate.
+///
+/// The `MaxEdgeLength` type enforces that its value is always larger than
+/// zero. Like [`Tolerance`], its [`From`]/[`Into`] implementations are
+/// fallible.
+///
+/// [`Tolerance`]: crate::algorithms::approx::Tolerance
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct MaxEdgeLength(Scalar);
+
+impl MaxEdgeLength {
+    /// Construct a `MaxEdgeLength` from a [`Scalar`]
+    ///
+    /// Returns an error, if the passed scalar is not larger than zero.
+    pub fn from_scalar(
+        scalar: impl Into<Scalar>,
+    ) -> Result<Self, InvalidMaxEdgeLength> {
+        let scalar = scalar.into();
+
+        if scalar <= Scalar::ZERO {
+            return Err(InvalidMaxEdgeLength(scalar));
+        }
+
+        Ok(Self(scalar))
+    }
+
+    /// Return the [`Scalar`] that defines the maximum edge length
+    pub fn inner(&self) -> Scalar {
+        self.0
+    }
+}
+
+impl<S> From<S> for MaxEdgeLength
+where
+    S: Into<Scalar>,
+{
+    fn from(scalar: S) -> Self {
+        Self::from_scalar(scalar)
+            .expect("Tried to create `MaxEdgeLength` from invalid value")
+    }
+}
+
+/// Error converting scalar to maximum edge length
+#[derive(Debug, thiserror::Error)]
+#[error("Invalid maximum edge length ({0}); must be above zero")]
+pub struct InvalidMaxEdgeLength(Scalar);
@@ -1,17 +1,21 @@
 //! Shape triangulation
 
-mod delaunay;
+pub(crate) mod delaunay;
+pub(crate) mod index;
+mod max_edge_length;
+
+#[cfg(test)]
 mod polygon;
 
 use fj_interop::Mesh;
-use fj_math::Point;
+use fj_math::{Point, Scalar, Triangle};
 
 use crate::Core;
 
-use self::polygon::Polygon;
-
 use super::approx::{face::FaceApprox, Approx, Tolerance};
 
+pub use self::max_edge_length::{InvalidMaxEdgeLength, MaxEdgeLength};
+
 /// Triangulate a shape
 pub trait Triangulate: Sized {
     /// Triangulate the shape
@@ -21,6 +25,24 @@ pub trait Triangulate: Sized {
         mesh
     }
 
+    /// Triangulate the shape, reporting progress along the way
+    ///
+    /// Calls `progress` with the mesh triangulated so far, every time a face
+    /// has been added to it. This allows callers to display large shapes
+    /// progressively, instead of waiting for the whole triangulation.
+    ///
+    /// Shapes that don't consist of multiple faces report progress only once,
+    /// after they have been triangulated completely.
+    fn triangulate_with_progress(
+        self,
+        core: &mut Core,
+        mut progress: impl FnMut(&Mesh<Point<3>>),
+    ) -> Mesh<Point<3>> {
+        let mesh = self.triangulate(core);
+        progress(&mesh);
+        mesh
+    }
+
     /// Triangulate a partial shape into the provided mesh
     ///
     /// This is a low-level method, intended for implementation of
@@ -33,6 +55,24 @@ where
     T: Approx,
     T::Approximation: IntoIterator<Item = FaceApprox>,
 {
+    fn triangulate_with_progress(
+        self,
+        core: &mut Core,
+        mut progress: impl FnMut(&Mesh<Point<3>>),
+    ) -> Mesh<Point<3>> {
+        let (approx, tolerance) = self;
+
+        let approx = approx.approx(tolerance, core);
+
+        let mut mesh = Mesh::new();
+        for approx in approx {
+            approx.triangulate_into_mesh(&mut mesh, core);
+            progress(&mesh);
+        }
+
+        mesh
+    }
+
     fn triangulate_into_mesh(self, mesh: &mut Mesh<Point<3>>, core: &mut Core) {
         let (approx, tolerance) = self;
 
@@ -44,56 +84,114 @@ where
     }
 }
 
+impl<T> Triangulate for (T, Tolerance, MaxEdgeLength)
+where
+    T: Approx,
+    T::Approximation: IntoIterator<Item = FaceApprox>,
+{
+    fn triangulate_with_progress(
+        self,
+        core: &mut Core,
+        mut progress: impl FnMut(&Mesh<Point<3>>),
+    ) -> Mesh<Point<3>> {
+        let (approx, tolerance, max_edge_length) = self;
+
+        let approx = approx.approx(tolerance, core);
+
+        let mut mesh = Mesh::new();
+        for approx in approx {
+            (approx, max_edge_length).triangulate_into_mesh(&mut mesh, core);
+            progress(&mesh);
+        }
+
+        mesh
+    }
+
+    fn triangulate_into_mesh(self, mesh: &mut Mesh<Point<3>>, core: &mut Core) {
+        let (approx, tolerance, max_edge_length) = self;
+
+        let approx = approx.approx(tolerance, core);
+
+        for approx in approx {
+            (approx, max_edge_length).triangulate_into_mesh(mesh, core);
+        }
+    }
+}
+
 impl Triangulate for FaceApprox {
     fn triangulate_into_mesh(
         self,
         mesh: &mut Mesh<Point<3>>,
         _core: &mut Core,
     ) {
-        let face_as_polygon = Polygon::new()
-            .with_exterior(
-                self.exterior
-                    .points()
-                    .into_iter()
-                    .map(|point| point.local_form),
-            )
-            .with_interiors(self.interiors.iter().map(|interior| {
-                interior.points().into_iter().map(|point| point.local_form)
-            }));
-
-        let cycles = [self.exterior].into_iter().chain(self.interiors);
-        let mut triangles =
-            delaunay::triangulate(cycles, self.coord_handedness);
-        triangles.retain(|triangle| {
-            face_as_polygon
-                .contains_triangle(triangle.map(|point| point.point_surface))
-        });
-
-        let color = self.color.unwrap_or_default();
-
-        for triangle in triangles {
-            let points = triangle.map(|point| point.point_global);
-            mesh.push_triangle(points, color);
-        }
+        triangulate_face(self, None, mesh);
+    }
+}
+
+impl Triangulate for (FaceApprox, MaxEdgeLength) {
+    fn triangulate_into_mesh(
+        self,
+        mesh: &mut Mesh<Point<3>>,
+        _core: &mut Core,
+    ) {
+        let (approx, max_edge_length) = self;
+        triangulate_face(approx, Some(max_edge_length.inner()), mesh);
+    }
+}
+
+fn triangulate_face(
+    approx: FaceApprox,
+    max_edge_length: Option<Scalar>,
+    mesh: &mut Mesh<Point<3>>,
+) {
+    let cycles = [approx.exterior].into_iter().chain(approx.interiors);
+    let triangles = delaunay::triangulate(
+        cycles,
+        approx.interior_points,
+        approx.coord_handedness,
+        max_edge_length,
+    );
+
+    let color = approx.color.unwrap_or_default();
+    let face = mesh.add_face();
+    if let Some(name) = approx.name {
+        mesh.set_face_name(face, name);
+    }
+
+    for triangle in triangles {
+        let points = triangle.map(|point| point.point_global);
+
+        // Where an edge of the face collapses into a single point, like at
+        // the poles of a sphere, the triangles next to it collapse too.
+        let Ok(triangle) = Triangle::from_points(points) else {
+            continue;
+        };
+
+        mesh.push_triangle_of_face(triangle, color, Some(face));
     }
 }
 
 #[cfg(test)]
 mod tests {
     use fj_interop::Mesh;
-    use fj_math::{Point, Scalar};
+    use fj_math::{NurbsSurface, Point, Scalar};
+    use fj_test_utils::{assert_scalars_eq, assert_watertight};
 
     use crate::{
         algorithms::approx::{Approx, Tolerance},
-        objects::{Cycle, Face},
+        objects::{Cycle, Face, Region, Sketch, Surface},
         operations::{
-            build::{BuildCycle, BuildFace},
-            update::{UpdateFace, UpdateRegion},
+            build::{
+                BuildCycle, BuildFace, BuildRegion, BuildSketch, BuildSurface,
+            },
+            presentation::SetName,
+            sweep::SweepSketch,
+            update::{UpdateFace, UpdateRegion, UpdateSketch},
         },
         Core,
     };
 
-    use super::Triangulate;
+    use super::{polygon::Polygon, MaxEdgeLength, Triangulate};
 
     #[test]
     fn simple() -> anyhow::Result<()> {
@@ -189,6 +287,51 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn hole_sharing_part_of_exterior_edge() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let a = [0., 0.];
+        let b = [4., 0.];
+        let c = [4., 4.];
+        let d = [0., 4.];
+
+        // The hole is a notch in the bottom edge of the square. The bottom edge
+        // of the exterior goes through the hole's vertices and gets split there,
+        // which makes part of it an edge shared by both cycles.
+        let e = [1., 0.];
+        let f = [1., 2.];
+        let g = [3., 2.];
+        let h = [3., 0.];
+
+        let surface = core.layers.objects.surfaces.xy_plane();
+
+        let face = Face::unbound(surface, &mut core).update_region(
+            |region, core| {
+                region
+                    .update_exterior(
+                        |_, core| Cycle::polygon([a, b, c, d], core),
+                        core,
+                    )
+                    .add_interiors([Cycle::polygon([e, f, g, h], core)], core)
+            },
+            &mut core,
+        );
+
+        let triangles = triangulate(face, &mut core)?;
+
+        let area = triangles
+            .triangles()
+            .map(|triangle| {
+                let [a, b, c] = triangle.inner.points();
+                (b - a).cross(&(c - a)).magnitude() / 2.
+            })
+            .fold(Scalar::ZERO, |sum, area| sum + area);
+        assert_scalars_eq!(area, Scalar::from(4. * 4. - 2. * 2.), 1e-12);
+
+        Ok(())
+    }
+
     #[test]
     fn sharp_concave_shape() -> anyhow::Result<()> {
         let mut core = Core::new();
@@ -237,6 +380,299 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn many_holes() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        // A square with a grid of square holes, similar to what text
+        // engravings and other complex profiles look like.
+        let num_holes_per_row = 10;
+        let size = f64::from(num_holes_per_row * 2 + 1);
+
+        let surface = core.layers.objects.surfaces.xy_plane();
+
+        let mut holes = Vec::new();
+        for i in 0..num_holes_per_row {
+            for j in 0..num_holes_per_row {
+                let [x, y] = [i, j].map(|k| f64::from(k * 2 + 1));
+                holes.push([
+                    [x, y],
+                    [x, y + 1.],
+                    [x + 1., y + 1.],
+                    [x + 1., y],
+                ]);
+            }
+        }
+
+        let face = Face::unbound(surface.clone(), &mut core).update_region(
+            |region, core| {
+                let interiors = holes
+                    .iter()
+                    .map(|&hole| Cycle::polygon(hole, core))
+                    .collect::<Vec<_>>();
+
+                region
+                    .update_exterior(
+                        |_, core| {
+                            Cycle::polygon(
+                                [
+                                    [0., 0.],
+                                    [size, 0.],
+                                    [size, size],
+                                    [0., size],
+                                ],
+                                core,
+                            )
+                        },
+                        core,
+                    )
+                    .add_interiors(interiors, core)
+            },
+            &mut core,
+        );
+
+        let polygon = Polygon::new()
+            .with_exterior([[0., 0.], [size, 0.], [size, size], [0., size]])
+            .with_interiors(holes.iter().copied());
+
+        let triangles = triangulate(face, &mut core)?;
+
+        let mut area = Scalar::ZERO;
+        for triangle in triangles.triangles() {
+            let [a, b, c] = triangle.inner.points();
+            assert!(polygon
+                .contains_triangle([a, b, c].map(|p| Point::from([p.x, p.y]))));
+
+            area += (b - a).cross(&(c - a)).magnitude() / 2.;
+        }
+
+        let num_holes = f64::from(num_holes_per_row * num_holes_per_row);
+        assert_eq!(area, Scalar::from(size * size - num_holes));
+
+        Ok(())
+    }
+
+    #[test]
+    fn nurbs_surface() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        // A dome over the unit square. Its height is `4u(1-u) * 4v(1-v) / 4`.
+        let height = |[u, v]: [Scalar; 2]| {
+            Scalar::from(4.) * u * (Scalar::ONE - u) * v * (Scalar::ONE - v)
+        };
+        let surface = Surface::surface_from_nurbs(
+            NurbsSurface::clamped(
+                [0., 0.5, 1.].map(|u| {
+                    [0., 0.5, 1.].map(|v| {
+                        let z = if u == 0.5 && v == 0.5 { 1. } else { 0. };
+                        [u, v, z]
+                    })
+                }),
+                [2, 2],
+            ),
+            &mut core,
+        );
+
+        let face = Face::unbound(surface, &mut core).update_region(
+            |region, core| {
+                region.update_exterior(
+                    |_, core| {
+                        Cycle::polygon(
+                            [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+                            core,
+                        )
+                    },
+                    core,
+                )
+            },
+            &mut core,
+        );
+
+        let tolerance = Tolerance::from_scalar(0.01)?;
+        let mesh = face.approx(tolerance, &mut core).triangulate(&mut core);
+
+        assert!(mesh.triangles().count() > 2);
+        for triangle in mesh.triangles() {
+            let points = triangle.inner.points();
+
+            for point in points {
+                let expected = height([point.x, point.y]);
+                assert_scalars_eq!(point.z, expected, 1e-9);
+            }
+
+            let [a, b, c] = points;
+            let center = a + ((b - a) + (c - a)) / 3.;
+            let expected = height([center.x, center.y]);
+            assert_scalars_eq!(center.z, expected, tolerance.inner() * 2.);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn cylinder_seam() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let surface = core.layers.objects.surfaces.xy_plane();
+        let radius = Scalar::ONE;
+
+        // Approximating the circle with certain numbers of vertices is prone
+        // to floating-point error at the seam, where the parameter wraps
+        // around. Cover a range of tolerances that includes such cases.
+        for num_vertices in (3..=130).step_by(7) {
+            let num_vertices = f64::from(num_vertices);
+            let tolerance = Tolerance::from_scalar(
+                radius * (Scalar::ONE - (Scalar::PI / num_vertices).cos()),
+            )?;
+
+            let cylinder = Sketch::empty()
+                .add_regions(
+                    [Region::circle([0., 0.], radius, &mut core)],
+                    &mut core,
+                )
+                .sweep_sketch(surface.clone(), [0., 0., 1.], &mut core);
+            let mesh = (&cylinder, tolerance).triangulate(&mut core);
+
+            // If the seam is handled correctly, the mesh is closed.
+            assert_watertight!(mesh, "{num_vertices} vertices");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn max_edge_length() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let surface = core.layers.objects.surfaces.xy_plane();
+        let cuboid = Sketch::empty()
+            .add_regions(
+                [Region::polygon(
+                    [[0., 0.], [10., 0.], [10., 10.], [0., 10.]],
+                    &mut core,
+                )],
+                &mut core,
+            )
+            .sweep_sketch(surface, [0., 0., 10.], &mut core);
+
+        let tolerance = Tolerance::from_scalar(1.)?;
+        let max_edge_length = MaxEdgeLength::from_scalar(1.5)?;
+
+        let mesh = (&cuboid, tolerance).triangulate(&mut core);
+        let subdivided =
+            (&cuboid, tolerance, max_edge_length).triangulate(&mut core);
+
+        assert!(subdivided.triangles().count() > mesh.triangles().count() * 10);
+
+        let mut area = Scalar::ZERO;
+        for triangle in subdivided.triangles() {
+            let [a, b, c] = triangle.inner.points();
+
+            for [a, b] in [[a, b], [b, c], [c, a]] {
+                assert!((b - a).magnitude() <= max_edge_length.inner());
+            }
+
+            area += (b - a).cross(&(c - a)).magnitude() / 2.;
+        }
+
+        // Faces that share an edge must have been subdivided the same way
+        // along it, or the mesh would have cracks.
+        assert_watertight!(subdivided);
+        assert_scalars_eq!(area, 600., 1e-9);
+
+        Ok(())
+    }
+
+    #[test]
+    fn face_indices() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let surface = core.layers.objects.surfaces.xy_plane();
+        let cuboid = Sketch::empty()
+            .add_regions(
+                [Region::polygon(
+                    [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+                    &mut core,
+                )],
+                &mut core,
+            )
+            .sweep_sketch(surface, [0., 0., 1.], &mut core);
+
+        let mesh =
+            (&cuboid, Tolerance::from_scalar(1.)?).triangulate(&mut core);
+
+        assert_eq!(mesh.num_faces(), 6);
+        for face in 0..mesh.num_faces() {
+            let num_triangles = mesh
+                .triangles()
+                .filter(|triangle| triangle.face == Some(face))
+                .count();
+            assert_eq!(num_triangles, 2);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn face_names() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let surface = core.layers.objects.surfaces.xy_plane();
+        let cuboid = Sketch::empty()
+            .add_regions(
+                [Region::polygon(
+                    [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+                    &mut core,
+                )],
+                &mut core,
+            )
+            .sweep_sketch(surface, [0., 0., 1.], &mut core);
+
+        let faces = cuboid.shells().only().faces();
+        for face in [faces.first(), faces.nth(5).expect("Expected top face")] {
+            face.region().set_name("ends", &mut core);
+        }
+
+        let mesh =
+            (&cuboid, Tolerance::from_scalar(1.)?).triangulate(&mut core);
+
+        let num_named_triangles = mesh
+            .triangles()
+            .filter_map(|triangle| triangle.face)
+            .filter(|&face| mesh.face_name(face) == Some("ends"))
+            .count();
+        assert_eq!(num_named_triangles, 4);
+
+        Ok(())
+    }
+
+    #[test]
+    fn progress() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let surface = core.layers.objects.surfaces.xy_plane();
+        let cuboid = Sketch::empty()
+            .add_regions(
+                [Region::polygon(
+                    [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+                    &mut core,
+                )],
+                &mut core,
+            )
+            .sweep_sketch(surface, [0., 0., 1.], &mut core);
+
+        let mut num_faces = Vec::new();
+        let mesh = (&cuboid, Tolerance::from_scalar(1.)?)
+            .triangulate_with_progress(&mut core, |mesh| {
+                num_faces.push(mesh.num_faces());
+            });
+
+        assert_eq!(num_faces, [1, 2, 3, 4, 5, 6]);
+        assert_eq!(mesh.triangles().count(), 12);
+
+        Ok(())
+    }
+
     fn triangulate(
         face: Face,
         core: &mut Core,
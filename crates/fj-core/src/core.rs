@@ -2,6 +2,8 @@
 //!
 //! See [`Core`].
 
+use std::fmt;
+
 use crate::{layers::Layers, validation::ValidationConfig};
 
 /// An instance of the Fornjot core
@@ -24,4 +26,27 @@ impl Core {
         let layers = Layers::with_validation_config(config);
         Self { layers }
     }
+
+    /// Run an operation, recording it in the trace layer
+    ///
+    /// Just runs the operation, unless tracing is enabled. If it is, the
+    /// operation is recorded along with its inputs and its output, using their
+    /// `Debug` representations. See [`Trace`].
+    ///
+    /// [`Trace`]: crate::trace::Trace
+    pub fn trace_operation<R>(
+        &mut self,
+        name: &'static str,
+        inputs: &[&dyn fmt::Debug],
+        operation: impl FnOnce(&mut Self) -> R,
+    ) -> R
+    where
+        R: fmt::Debug,
+    {
+        self.layers.trace.start_operation(name, inputs);
+        let result = operation(self);
+        self.layers.trace.end_operation(&result);
+
+        result
+    }
 }
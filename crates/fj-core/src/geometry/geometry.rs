@@ -31,21 +31,21 @@ impl Geometry {
 
         self_.define_surface_inner(
             self_.xy_plane.clone(),
-            SurfaceGeometry {
+            SurfaceGeometry::Swept {
                 u: GlobalPath::x_axis(),
                 v: Vector::unit_y(),
             },
         );
         self_.define_surface_inner(
             self_.xz_plane.clone(),
-            SurfaceGeometry {
+            SurfaceGeometry::Swept {
                 u: GlobalPath::x_axis(),
                 v: Vector::unit_z(),
             },
         );
         self_.define_surface_inner(
             self_.yz_plane.clone(),
-            SurfaceGeometry {
+            SurfaceGeometry::Swept {
                 u: GlobalPath::y_axis(),
                 v: Vector::unit_z(),
             },
@@ -67,25 +67,24 @@ impl Geometry {
     /// ## Panics
     ///
     /// Panics, if the geometry of surface is not defined.
-    pub fn of_surface(&self, surface: &Handle<Surface>) -> SurfaceGeometry {
+    pub fn of_surface(&self, surface: &Handle<Surface>) -> &SurfaceGeometry {
         self.surface
             .get(&surface.clone().into())
-            .copied()
             .expect("Expected geometry of surface to be defined")
     }
 
     /// Access the geometry of the xy-plane
-    pub fn xy_plane(&self) -> SurfaceGeometry {
+    pub fn xy_plane(&self) -> &SurfaceGeometry {
         self.of_surface(&self.xy_plane)
     }
 
     /// Access the geometry of the xz-plane
-    pub fn xz_plane(&self) -> SurfaceGeometry {
+    pub fn xz_plane(&self) -> &SurfaceGeometry {
         self.of_surface(&self.xz_plane)
     }
 
     /// Access the geometry of the yz-plane
-    pub fn yz_plane(&self) -> SurfaceGeometry {
+    pub fn yz_plane(&self) -> &SurfaceGeometry {
         self.of_surface(&self.yz_plane)
     }
 }
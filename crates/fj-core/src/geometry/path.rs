@@ -2,7 +2,9 @@
 //!
 //! See [`SurfacePath`] and [`GlobalPath`].
 
-use fj_math::{Circle, Line, Point, Scalar, Transform, Vector};
+use fj_math::{
+    Circle, CubicBezier, Ellipse, Line, Point, Scalar, Transform, Vector,
+};
 
 /// A path through surface (2D) space
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
@@ -10,6 +12,12 @@ pub enum SurfacePath {
     /// A circle
     Circle(Circle<2>),
 
+    /// A cubic Bézier curve
+    CubicBezier(CubicBezier<2>),
+
+    /// An ellipse
+    Ellipse(Ellipse<2>),
+
     /// A line
     Line(Line<2>),
 }
@@ -23,6 +31,26 @@ impl SurfacePath {
         Self::Circle(Circle::from_center_and_radius(center, radius))
     }
 
+    /// Build a cubic Bézier curve from the given control points
+    pub fn cubic_bezier(control_points: [impl Into<Point<2>>; 4]) -> Self {
+        Self::CubicBezier(CubicBezier::new(control_points))
+    }
+
+    /// Build an axis-aligned ellipse from the given radii
+    ///
+    /// The radii are the lengths of the ellipse's semi-axes along u and v.
+    pub fn ellipse_from_center_and_radii(
+        center: impl Into<Point<2>>,
+        radii: [impl Into<Scalar>; 2],
+    ) -> Self {
+        let [u, v] = radii.map(Into::into);
+        Self::Ellipse(Ellipse::new(
+            center,
+            [u, Scalar::ZERO],
+            [Scalar::ZERO, v],
+        ))
+    }
+
     /// Build a line that represents the u-axis of the surface its on
     pub fn u_axis() -> Self {
         let a = Point::origin();
@@ -65,6 +93,8 @@ impl SurfacePath {
     ) -> Point<2> {
         match self {
             Self::Circle(circle) => circle.point_from_circle_coords(point),
+            Self::CubicBezier(bezier) => bezier.point_from_bezier_coords(point),
+            Self::Ellipse(ellipse) => ellipse.point_from_ellipse_coords(point),
             Self::Line(line) => line.point_from_line_coords(point),
         }
     }
@@ -74,6 +104,8 @@ impl SurfacePath {
     pub fn reverse(self) -> Self {
         match self {
             Self::Circle(circle) => Self::Circle(circle.reverse()),
+            Self::CubicBezier(bezier) => Self::CubicBezier(bezier.reverse()),
+            Self::Ellipse(ellipse) => Self::Ellipse(ellipse.reverse()),
             Self::Line(line) => Self::Line(line.reverse()),
         }
     }
@@ -85,6 +117,12 @@ pub enum GlobalPath {
     /// A circle
     Circle(Circle<3>),
 
+    /// A cubic Bézier curve
+    CubicBezier(CubicBezier<3>),
+
+    /// An ellipse
+    Ellipse(Ellipse<3>),
+
     /// A line
     Line(Line<3>),
 }
@@ -135,6 +173,8 @@ impl GlobalPath {
     pub fn origin(&self) -> Point<3> {
         match self {
             Self::Circle(circle) => circle.center() + circle.a(),
+            Self::CubicBezier(bezier) => bezier.start(),
+            Self::Ellipse(ellipse) => ellipse.center() + ellipse.a(),
             Self::Line(line) => line.origin(),
         }
     }
@@ -146,6 +186,8 @@ impl GlobalPath {
     ) -> Point<3> {
         match self {
             Self::Circle(circle) => circle.point_from_circle_coords(point),
+            Self::CubicBezier(bezier) => bezier.point_from_bezier_coords(point),
+            Self::Ellipse(ellipse) => ellipse.point_from_ellipse_coords(point),
             Self::Line(line) => line.point_from_line_coords(point),
         }
     }
@@ -157,6 +199,12 @@ impl GlobalPath {
     ) -> Vector<3> {
         match self {
             Self::Circle(circle) => circle.vector_from_circle_coords(vector),
+            Self::CubicBezier(bezier) => {
+                bezier.vector_from_bezier_coords(vector)
+            }
+            Self::Ellipse(ellipse) => {
+                ellipse.vector_from_ellipse_coords(vector)
+            }
             Self::Line(line) => line.vector_from_line_coords(vector),
         }
     }
@@ -168,6 +216,12 @@ impl GlobalPath {
             Self::Circle(curve) => {
                 Self::Circle(transform.transform_circle(&curve))
             }
+            Self::CubicBezier(curve) => {
+                Self::CubicBezier(transform.transform_cubic_bezier(&curve))
+            }
+            Self::Ellipse(curve) => {
+                Self::Ellipse(transform.transform_ellipse(&curve))
+            }
             Self::Line(curve) => Self::Line(transform.transform_line(&curve)),
         }
     }
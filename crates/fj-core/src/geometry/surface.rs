@@ -1,17 +1,27 @@
 //! The geometry that defines a surface
 
-use fj_math::{Line, Plane, Point, Transform, Vector};
+use fj_math::{Line, NurbsSurface, Plane, Point, Transform, Vector};
 
 use super::GlobalPath;
 
 /// The geometry that defines a surface
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
-pub struct SurfaceGeometry {
-    /// The u-axis of the surface
-    pub u: GlobalPath,
-
-    /// The v-axis of the surface
-    pub v: Vector<3>,
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum SurfaceGeometry {
+    /// A surface that is defined by sweeping a path along a straight line
+    ///
+    /// Planes and cylinders are surfaces of this kind.
+    Swept {
+        /// The u-axis of the surface
+        u: GlobalPath,
+
+        /// The v-axis of the surface
+        v: Vector<3>,
+    },
+
+    /// A NURBS surface
+    ///
+    /// The surface coordinates are the parameters of the NURBS surface.
+    Nurbs(NurbsSurface),
 }
 
 impl SurfaceGeometry {
@@ -21,54 +31,122 @@ impl SurfaceGeometry {
         point: impl Into<Point<2>>,
     ) -> Point<3> {
         let point = point.into();
-        self.u.point_from_path_coords([point.u])
-            + self.path_to_line().vector_from_line_coords([point.v])
+
+        match self {
+            Self::Swept { u, v } => {
+                u.point_from_path_coords([point.u])
+                    + path_to_line(u, v).vector_from_line_coords([point.v])
+            }
+            Self::Nurbs(surface) => surface.point_from_surface_coords(point),
+        }
     }
 
     /// Convert a vector in surface coordinates to model coordinates
+    ///
+    /// For a NURBS surface, the vector is converted at the minimum of the
+    /// surface's domain. Unless the surface is flat, this is only an
+    /// approximation of the vector anywhere else on the surface.
     pub fn vector_from_surface_coords(
         &self,
         vector: impl Into<Vector<2>>,
     ) -> Vector<3> {
         let vector = vector.into();
-        self.u.vector_from_path_coords([vector.u])
-            + self.path_to_line().vector_from_line_coords([vector.v])
+
+        match self {
+            Self::Swept { u, v } => {
+                u.vector_from_path_coords([vector.u])
+                    + path_to_line(u, v).vector_from_line_coords([vector.v])
+            }
+            Self::Nurbs(surface) => {
+                let [[min_u, _], [min_v, _]] = surface.domain();
+                let origin = Point::from([min_u, min_v]);
+
+                surface.point_from_surface_coords(origin + vector)
+                    - surface.point_from_surface_coords(origin)
+            }
+        }
+    }
+
+    /// Compute the partial derivatives of the surface, along `u` and `v`
+    pub fn derivatives(&self, point: impl Into<Point<2>>) -> [Vector<3>; 2] {
+        let point = point.into();
+
+        match self {
+            Self::Swept { u, v } => {
+                let tangent = match u {
+                    GlobalPath::Circle(circle) => {
+                        let (sin, cos) = point.u.sin_cos();
+                        circle.b() * cos - circle.a() * sin
+                    }
+                    GlobalPath::CubicBezier(bezier) => {
+                        bezier.derivative([point.u])
+                    }
+                    GlobalPath::Ellipse(ellipse) => {
+                        let (sin, cos) = point.u.sin_cos();
+                        ellipse.b() * cos - ellipse.a() * sin
+                    }
+                    GlobalPath::Line(line) => line.direction(),
+                };
+
+                [tangent, *v]
+            }
+            Self::Nurbs(surface) => surface.derivatives(point),
+        }
     }
 
-    fn path_to_line(&self) -> Line<3> {
-        Line::from_origin_and_direction(self.u.origin(), self.v)
+    /// Compute the normal of the surface at the provided point
+    ///
+    /// The normal has unit length, and points into the direction of the cross
+    /// product of the surface's u- and v-axis at that point.
+    pub fn normal(&self, point: impl Into<Point<2>>) -> Vector<3> {
+        let [du, dv] = self.derivatives(point);
+        du.cross(&dv).normalize()
     }
 
     /// Project the global point into the surface
     pub fn project_global_point(&self, point: impl Into<Point<3>>) -> Point<2> {
-        let GlobalPath::Line(line) = self.u else {
+        let Self::Swept {
+            u: GlobalPath::Line(line),
+            v,
+        } = self
+        else {
             todo!("Projecting point into non-plane surface is not supported")
         };
 
-        let plane =
-            Plane::from_parametric(line.origin(), line.direction(), self.v);
+        let plane = Plane::from_parametric(line.origin(), line.direction(), *v);
         plane.project_point(point)
     }
 
     /// Transform the surface geometry
     #[must_use]
     pub fn transform(self, transform: &Transform) -> Self {
-        let u = self.u.transform(transform);
-        let v = transform.transform_vector(&self.v);
-        Self { u, v }
+        match self {
+            Self::Swept { u, v } => {
+                let u = u.transform(transform);
+                let v = transform.transform_vector(&v);
+                Self::Swept { u, v }
+            }
+            Self::Nurbs(surface) => {
+                Self::Nurbs(transform.transform_nurbs_surface(&surface))
+            }
+        }
     }
 }
 
+fn path_to_line(u: &GlobalPath, v: &Vector<3>) -> Line<3> {
+    Line::from_origin_and_direction(u.origin(), *v)
+}
+
 #[cfg(test)]
 mod tests {
-    use fj_math::{Line, Point, Vector};
+    use fj_math::{Line, NurbsSurface, Point, Vector};
     use pretty_assertions::assert_eq;
 
     use crate::geometry::{GlobalPath, SurfaceGeometry};
 
     #[test]
     fn point_from_surface_coords() {
-        let surface = SurfaceGeometry {
+        let surface = SurfaceGeometry::Swept {
             u: GlobalPath::Line(Line::from_origin_and_direction(
                 Point::from([1., 1., 1.]),
                 Vector::from([0., 2., 0.]),
@@ -84,7 +162,7 @@ mod tests {
 
     #[test]
     fn vector_from_surface_coords() {
-        let surface = SurfaceGeometry {
+        let surface = SurfaceGeometry::Swept {
             u: GlobalPath::Line(Line::from_origin_and_direction(
                 Point::from([1., 0., 0.]),
                 Vector::from([0., 2., 0.]),
@@ -97,4 +175,22 @@ mod tests {
             Vector::from([0., 4., 8.]),
         );
     }
+
+    #[test]
+    fn normal() {
+        let swept = SurfaceGeometry::Swept {
+            u: GlobalPath::Line(Line::from_origin_and_direction(
+                Point::from([1., 0., 0.]),
+                Vector::from([0., 2., 0.]),
+            )),
+            v: Vector::from([0., 0., 2.]),
+        };
+        let nurbs = SurfaceGeometry::Nurbs(NurbsSurface::clamped(
+            [[[0., 0., 0.], [0., 1., 0.]], [[1., 0., 0.], [1., 1., 0.]]],
+            [1, 1],
+        ));
+
+        assert_eq!(swept.normal([2., 4.]), Vector::from([1., 0., 0.]));
+        assert_eq!(nurbs.normal([0.5, 0.5]), Vector::from([0., 0., 1.]));
+    }
 }
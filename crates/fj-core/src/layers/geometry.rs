@@ -41,6 +41,6 @@ impl Command<Geometry> for DefineSurface {
 
 impl Event<Geometry> for DefineSurface {
     fn evolve(&self, state: &mut Geometry) {
-        state.define_surface_inner(self.surface.clone(), self.geometry);
+        state.define_surface_inner(self.surface.clone(), self.geometry.clone());
     }
 }
@@ -2,6 +2,7 @@ use crate::{
     geometry::Geometry,
     objects::Objects,
     presentation::Presentation,
+    trace::Trace,
     validation::{Validation, ValidationConfig},
 };
 
@@ -42,6 +43,11 @@ pub struct Layers {
     ///
     /// Stores data concerning the presentation of objects.
     pub presentation: Layer<Presentation>,
+
+    /// The trace layer
+    ///
+    /// Records the operations that are used to construct objects, if enabled.
+    pub trace: Layer<Trace>,
 }
 
 impl Layers {
@@ -55,6 +61,7 @@ impl Layers {
             geometry: Layer::new(geometry),
             validation: Layer::default(),
             presentation: Layer::default(),
+            trace: Layer::default(),
         }
     }
 
@@ -5,6 +5,7 @@
 pub mod geometry;
 pub mod objects;
 pub mod presentation;
+pub mod trace;
 pub mod validation;
 
 mod layer;
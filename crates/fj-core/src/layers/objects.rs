@@ -2,6 +2,7 @@
 
 use crate::{
     objects::{AboutToBeStored, AnyObject, Objects},
+    trace::Trace,
     validation::Validation,
 };
 
@@ -10,16 +11,20 @@ use super::{Command, Event, Layer};
 impl Layer<Objects> {
     /// Insert an object into the stores
     ///
-    /// Passes any events produced to the validation layer.
+    /// Passes any events produced to the validation and trace layers.
     pub fn insert(
         &mut self,
         object: AnyObject<AboutToBeStored>,
         validation: &mut Layer<Validation>,
+        trace: &mut Layer<Trace>,
     ) {
         let mut events = Vec::new();
         self.process(InsertObject { object }, &mut events);
 
         for event in events {
+            if trace.enabled {
+                trace.process(event.clone(), &mut Vec::new());
+            }
             validation.process(event, &mut Vec::new());
         }
     }
@@ -28,7 +33,7 @@ impl Layer<Objects> {
 /// Insert an object into the stores
 ///
 /// This struct serves as both event and command for `Layer<Objects>`, as well
-/// as a command for `Layer<Validation>`.
+/// as a command for `Layer<Validation>` and `Layer<Trace>`.
 #[derive(Clone, Debug)]
 pub struct InsertObject {
     /// The object to insert
@@ -17,6 +17,12 @@ impl Layer<Presentation> {
         self.process(SetColor { region, color }, &mut events);
     }
 
+    /// Set the name of a region
+    pub fn set_name(&mut self, region: Handle<Region>, name: String) {
+        let mut events = Vec::new();
+        self.process(SetName { region, name }, &mut events);
+    }
+
     /// Mark an object as being derived from another
     pub fn derive_object(
         &mut self,
@@ -56,6 +62,34 @@ impl Event<Presentation> for SetColor {
     }
 }
 
+/// Set the name of a region
+pub struct SetName {
+    /// The region to set the name for
+    region: Handle<Region>,
+
+    /// The name to set
+    name: String,
+}
+
+impl Command<Presentation> for SetName {
+    type Result = ();
+    type Event = Self;
+
+    fn decide(
+        self,
+        _: &Presentation,
+        events: &mut Vec<Self::Event>,
+    ) -> Self::Result {
+        events.push(self);
+    }
+}
+
+impl Event<Presentation> for SetName {
+    fn evolve(&self, state: &mut Presentation) {
+        state.name.insert(self.region.clone(), self.name.clone());
+    }
+}
+
 /// Handle an object being derived from another
 pub struct DeriveObject {
     /// The original object
@@ -67,7 +101,7 @@ pub struct DeriveObject {
 
 impl Command<Presentation> for DeriveObject {
     type Result = ();
-    type Event = SetColor;
+    type Event = PresentationEvent;
 
     fn decide(
         self,
@@ -77,12 +111,20 @@ impl Command<Presentation> for DeriveObject {
         if let (AnyObject::Region(original), AnyObject::Region(derived)) =
             (self.original, self.derived)
         {
+            let derived: Handle<Region> = derived.into();
+
             if let Some(color) = state.color.get(&original.0).cloned() {
-                events.push(SetColor {
-                    region: derived.into(),
+                events.push(PresentationEvent::SetColor {
+                    region: derived.clone(),
                     color,
                 });
             }
+            if let Some(name) = state.name.get(&original.0).cloned() {
+                events.push(PresentationEvent::SetName {
+                    region: derived,
+                    name,
+                });
+            }
         }
     }
 }
@@ -98,4 +140,26 @@ pub enum PresentationEvent {
         /// The color being set
         color: Color,
     },
+
+    /// The name of a region is being set
+    SetName {
+        /// The region the name is being set for
+        region: Handle<Region>,
+
+        /// The name being set
+        name: String,
+    },
+}
+
+impl Event<Presentation> for PresentationEvent {
+    fn evolve(&self, state: &mut Presentation) {
+        match self {
+            Self::SetColor { region, color } => {
+                state.color.insert(region.clone(), *color);
+            }
+            Self::SetName { region, name } => {
+                state.name.insert(region.clone(), name.clone());
+            }
+        }
+    }
 }
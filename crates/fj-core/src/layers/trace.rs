@@ -0,0 +1,195 @@
+//! Layer infrastructure for [`Trace`]
+
+use std::{fmt, time::Instant};
+
+use crate::{
+    objects::{AnyObject, Stored},
+    trace::{Trace, TracedOperation},
+};
+
+use super::{objects::InsertObject, Command, Event, Layer};
+
+impl Layer<Trace> {
+    /// Enable or disable tracing
+    pub fn set_enabled(&mut self, enabled: bool) {
+        let mut events = Vec::new();
+        self.process(SetEnabled { enabled }, &mut events);
+    }
+
+    /// Record the start of an operation
+    ///
+    /// Does nothing, if tracing is disabled.
+    pub fn start_operation(
+        &mut self,
+        name: &'static str,
+        inputs: &[&dyn fmt::Debug],
+    ) {
+        let mut events = Vec::new();
+        self.process(StartOperation { name, inputs }, &mut events);
+    }
+
+    /// Record the end of the innermost operation in progress
+    ///
+    /// Does nothing, if tracing is disabled.
+    pub fn end_operation(&mut self, output: &dyn fmt::Debug) {
+        let mut events = Vec::new();
+        self.process(EndOperation { output }, &mut events);
+    }
+}
+
+/// Enable or disable tracing
+pub struct SetEnabled {
+    enabled: bool,
+}
+
+impl Command<Trace> for SetEnabled {
+    type Result = ();
+    type Event = Self;
+
+    fn decide(self, _: &Trace, events: &mut Vec<Self::Event>) {
+        events.push(self);
+    }
+}
+
+impl Event<Trace> for SetEnabled {
+    fn evolve(&self, state: &mut Trace) {
+        state.enabled = self.enabled;
+    }
+}
+
+/// Record the start of an operation
+pub struct StartOperation<'r> {
+    name: &'static str,
+    inputs: &'r [&'r dyn fmt::Debug],
+}
+
+impl Command<Trace> for StartOperation<'_> {
+    type Result = ();
+    type Event = OperationStarted;
+
+    fn decide(self, state: &Trace, events: &mut Vec<Self::Event>) {
+        if state.enabled {
+            events.push(OperationStarted {
+                name: self.name,
+                inputs: self
+                    .inputs
+                    .iter()
+                    .map(|input| format!("{input:?}"))
+                    .collect(),
+                started: now(),
+            });
+        }
+    }
+}
+
+/// Record the end of the innermost operation in progress
+pub struct EndOperation<'r> {
+    output: &'r dyn fmt::Debug,
+}
+
+impl Command<Trace> for EndOperation<'_> {
+    type Result = ();
+    type Event = OperationEnded;
+
+    fn decide(self, state: &Trace, events: &mut Vec<Self::Event>) {
+        if state.enabled && !state.in_progress.is_empty() {
+            events.push(OperationEnded {
+                output: format!("{:?}", self.output),
+                ended: now(),
+            });
+        }
+    }
+}
+
+impl Command<Trace> for InsertObject {
+    type Result = ();
+    type Event = ObjectInserted;
+
+    fn decide(self, state: &Trace, events: &mut Vec<Self::Event>) {
+        if state.enabled && !state.in_progress.is_empty() {
+            events.push(ObjectInserted {
+                object: self.object.into(),
+            });
+        }
+    }
+}
+
+/// An operation has started
+#[derive(Clone)]
+pub struct OperationStarted {
+    /// The name of the operation
+    pub name: &'static str,
+
+    /// The `Debug` representations of the operation's inputs
+    pub inputs: Vec<String>,
+
+    /// The time the operation started
+    pub started: Option<Instant>,
+}
+
+impl Event<Trace> for OperationStarted {
+    fn evolve(&self, state: &mut Trace) {
+        state.in_progress.push(TracedOperation {
+            name: self.name,
+            inputs: self.inputs.clone(),
+            output: None,
+            inserted: Vec::new(),
+            children: Vec::new(),
+            started: self.started,
+            duration: None,
+        });
+    }
+}
+
+/// The innermost operation in progress has ended
+#[derive(Clone)]
+pub struct OperationEnded {
+    /// The `Debug` representation of the operation's output
+    pub output: String,
+
+    /// The time the operation ended
+    pub ended: Option<Instant>,
+}
+
+impl Event<Trace> for OperationEnded {
+    fn evolve(&self, state: &mut Trace) {
+        let Some(mut operation) = state.in_progress.pop() else {
+            return;
+        };
+
+        operation.output = Some(self.output.clone());
+
+        if let (Some(started), Some(ended)) = (operation.started, self.ended) {
+            operation.duration = Some(ended.duration_since(started));
+        }
+
+        match state.in_progress.last_mut() {
+            Some(parent) => parent.children.push(operation),
+            None => state.operations.push(operation),
+        }
+    }
+}
+
+/// An object has been inserted by the innermost operation in progress
+#[derive(Clone)]
+pub struct ObjectInserted {
+    /// The object that has been inserted
+    pub object: AnyObject<Stored>,
+}
+
+impl Event<Trace> for ObjectInserted {
+    fn evolve(&self, state: &mut Trace) {
+        if let Some(operation) = state.in_progress.last_mut() {
+            operation.inserted.push(self.object.clone());
+        }
+    }
+}
+
+fn now() -> Option<Instant> {
+    // `Instant::now` panics on this target, when running in a browser.
+    if cfg!(target_arch = "wasm32") {
+        None
+    } else {
+        Some(Instant::now())
+    }
+}
@@ -86,9 +86,11 @@ pub mod geometry;
 pub mod layers;
 pub mod objects;
 pub mod operations;
+pub mod prelude;
 pub mod presentation;
 pub mod queries;
 pub mod storage;
+pub mod trace;
 pub mod validate;
 pub mod validation;
 
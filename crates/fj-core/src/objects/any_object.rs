@@ -24,6 +24,17 @@ macro_rules! any_object {
             )*
         }
 
+        impl<F: Form> AnyObject<F> {
+            /// Access the name of the object's type
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(
+                        Self::$ty(_) => $name,
+                    )*
+                }
+            }
+        }
+
         impl AnyObject<Stored> {
             /// Access the ID of the object
             pub fn id(&self) -> ObjectId {
@@ -30,10 +30,14 @@ impl Cycle {
     /// two possible windings, depending on the direction you look at the
     /// surface that the cycle is defined on from.
     pub fn winding(&self) -> Winding {
-        // The cycle could be made up of one or two circles. If that is the
-        // case, the winding of the cycle is determined by the winding of the
-        // first circle.
-        if self.half_edges.len() < 3 {
+        let has_bezier = self.half_edges().iter().any(|half_edge| {
+            matches!(half_edge.path(), SurfacePath::CubicBezier(_))
+        });
+
+        // The cycle could be made up of one or two circles or ellipses. If
+        // that is the case, the winding of the cycle is determined by the
+        // winding of the first one.
+        if self.half_edges.len() < 3 && !has_bezier {
             let first = self
                 .half_edges()
                 .iter()
@@ -43,13 +47,17 @@ impl Cycle {
             let [a, b] = first.boundary().inner;
             let edge_direction_positive = a < b;
 
-            let circle = match first.path() {
-                SurfacePath::Circle(circle) => circle,
-                SurfacePath::Line(_) => unreachable!(
-                    "Invalid cycle: less than 3 edges, but not all are circles"
-                ),
+            let [axis_a, axis_b] = match first.path() {
+                SurfacePath::Circle(circle) => [circle.a(), circle.b()],
+                SurfacePath::Ellipse(ellipse) => [ellipse.a(), ellipse.b()],
+                SurfacePath::CubicBezier(_) | SurfacePath::Line(_) => {
+                    unreachable!(
+                        "Invalid cycle: less than 3 edges, but not all are \
+                        circles or ellipses"
+                    )
+                }
             };
-            let cross_positive = circle.a().cross2d(&circle.b()) > Scalar::ZERO;
+            let cross_positive = axis_a.cross2d(&axis_b) > Scalar::ZERO;
 
             if edge_direction_positive == cross_positive {
                 return Winding::Ccw;
@@ -62,10 +70,31 @@ impl Cycle {
         // cycle as a polygon:
         // https://stackoverflow.com/a/1165943
 
+        // Bézier curves can bulge out far from the line between their ends,
+        // so some points from along their length become part of the polygon.
+        let points = self
+            .half_edges()
+            .iter()
+            .flat_map(|half_edge| {
+                let num_points = match half_edge.path() {
+                    SurfacePath::CubicBezier(_) => 4,
+                    _ => 1,
+                };
+                let [start, end] = half_edge.boundary().inner;
+
+                (0..num_points).map(move |i| {
+                    let t = f64::from(i) / f64::from(num_points);
+                    half_edge
+                        .path()
+                        .point_from_path_coords(start + (end - start) * t)
+                })
+            })
+            .collect::<Vec<_>>();
+
         let mut sum = Scalar::ZERO;
 
-        for (a, b) in self.half_edges().pairs() {
-            let [a, b] = [a, b].map(|edge| edge.start_position());
+        for (i, a) in points.iter().enumerate() {
+            let b = points[(i + 1) % points.len()];
 
             sum += (b.u - a.u) * (b.v + a.v);
         }
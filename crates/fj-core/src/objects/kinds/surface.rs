@@ -1,7 +1,7 @@
 use crate::geometry::SurfaceGeometry;
 
 /// A two-dimensional shape
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub struct Surface {
     geometry: SurfaceGeometry,
 }
@@ -13,7 +13,7 @@ impl Surface {
     }
 
     /// Access the surface's geometry
-    pub fn geometry(&self) -> SurfaceGeometry {
-        self.geometry
+    pub fn geometry(&self) -> &SurfaceGeometry {
+        &self.geometry
     }
 }
@@ -94,7 +94,7 @@ impl Default for Surfaces {
         let xy_plane = store.reserve();
         store.insert(
             xy_plane.clone(),
-            Surface::new(SurfaceGeometry {
+            Surface::new(SurfaceGeometry::Swept {
                 u: GlobalPath::x_axis(),
                 v: Vector::unit_y(),
             }),
@@ -103,7 +103,7 @@ impl Default for Surfaces {
         let xz_plane = store.reserve();
         store.insert(
             xz_plane.clone(),
-            Surface::new(SurfaceGeometry {
+            Surface::new(SurfaceGeometry::Swept {
                 u: GlobalPath::x_axis(),
                 v: Vector::unit_z(),
             }),
@@ -111,7 +111,7 @@ impl Default for Surfaces {
         let yz_plane = store.reserve();
         store.insert(
             yz_plane.clone(),
-            Surface::new(SurfaceGeometry {
+            Surface::new(SurfaceGeometry::Swept {
                 u: GlobalPath::y_axis(),
                 v: Vector::unit_z(),
             }),
@@ -0,0 +1,457 @@
+//! Create sketches from black-and-white raster images
+//!
+//! This makes it possible to digitize shapes from scans or photos, without
+//! going through external vectorization tools first. See [`Bitmap`] and
+//! [`TraceBitmap`].
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+
+use fj_math::{Point, Scalar};
+
+use crate::{
+    objects::{Cycle, Region, Sketch},
+    Core,
+};
+
+use super::{
+    boolean::{contains, distance_to_segment, signed_area},
+    build::BuildCycle,
+    insert::Insert,
+};
+
+/// A black-and-white raster image
+///
+/// Each pixel is either set, meaning it is part of a shape, or not. Pixels are
+/// addressed as `[x, y]`, starting from the top-left corner of the image, as
+/// is common for image formats.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Bitmap {
+    width: usize,
+    height: usize,
+    pixels: Vec<bool>,
+}
+
+impl Bitmap {
+    /// Create a bitmap by calling the provided closure for each pixel
+    pub fn from_fn(
+        width: usize,
+        height: usize,
+        mut f: impl FnMut([usize; 2]) -> bool,
+    ) -> Self {
+        let pixels = (0..height)
+            .flat_map(|y| (0..width).map(move |x| [x, y]))
+            .map(&mut f)
+            .collect();
+
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Create a bitmap from an image
+    ///
+    /// Pixels that are darker than `threshold` are set, unless they are mostly
+    /// transparent.
+    #[cfg(feature = "image")]
+    pub fn from_image(image: &image::DynamicImage, threshold: u8) -> Self {
+        let image = image.to_luma_alpha8();
+
+        Self::from_fn(
+            image.width() as usize,
+            image.height() as usize,
+            |[x, y]| {
+                let image::LumaA([luma, alpha]) =
+                    *image.get_pixel(x as u32, y as u32);
+                luma < threshold && alpha >= 128
+            },
+        )
+    }
+
+    /// Load a bitmap from an image file
+    ///
+    /// See [`Bitmap::from_image`].
+    #[cfg(feature = "image")]
+    pub fn load(
+        path: impl AsRef<std::path::Path>,
+        threshold: u8,
+    ) -> Result<Self, image::ImageError> {
+        let image = image::open(path)?;
+        Ok(Self::from_image(&image, threshold))
+    }
+
+    /// Access the width of the bitmap, in pixels
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Access the height of the bitmap, in pixels
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Indicate whether the pixel at the provided position is set
+    ///
+    /// Pixels outside of the bitmap are never set.
+    pub fn is_set(&self, [x, y]: [usize; 2]) -> bool {
+        x < self.width && y < self.height && self.pixels[y * self.width + x]
+    }
+
+    fn sample(&self, [x, y]: [i64; 2]) -> bool {
+        match (usize::try_from(x), usize::try_from(y)) {
+            (Ok(x), Ok(y)) => self.is_set([x, y]),
+            _ => false,
+        }
+    }
+}
+
+/// Trace the shapes in a [`Bitmap`]
+pub trait TraceBitmap {
+    /// Create a sketch that contains a region for each shape in the bitmap
+    ///
+    /// Each pixel covers a square with a side length of `pixel_size`, in
+    /// surface coordinates. The bottom-left corner of the bitmap ends up at
+    /// the origin, and its top edge points towards positive v.
+    ///
+    /// The contours of the shapes are found using marching squares, then
+    /// simplified, removing as many vertices as possible without the contours
+    /// moving by more than `tolerance`. Holes in a shape become interiors of
+    /// its region. Shapes within those holes become regions of their own.
+    fn trace(
+        &self,
+        pixel_size: impl Into<Scalar>,
+        tolerance: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Sketch;
+}
+
+impl TraceBitmap for Bitmap {
+    fn trace(
+        &self,
+        pixel_size: impl Into<Scalar>,
+        tolerance: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Sketch {
+        let pixel_size = pixel_size.into();
+        let tolerance = tolerance.into();
+
+        // The pixels would make the trace unreadable, so only record the size.
+        let size = [self.width, self.height];
+
+        let inputs: &[&dyn fmt::Debug] = &[&size, &pixel_size, &tolerance];
+        core.trace_operation("trace_bitmap", inputs, |core| {
+            let height = self.height as f64;
+
+            let contours = contours(self)
+                .into_iter()
+                .map(|contour| {
+                    contour
+                        .into_iter()
+                        .map(|[x, y]| {
+                            // Undo the doubling of the coordinates, and move
+                            // from sample coordinates, which are at pixel
+                            // centers, to pixel coordinates.
+                            let [x, y] = [x, y].map(|c| (c as f64 + 1.) / 2.);
+                            Point::from([x, height - y]) * pixel_size.into_f64()
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+
+            // Contours never cross each other, so how many of them contain a
+            // contour tells us whether it is an exterior or an interior.
+            let depths = contours
+                .iter()
+                .enumerate()
+                .map(|(i, contour)| {
+                    contours
+                        .iter()
+                        .enumerate()
+                        .filter(|&(j, other)| {
+                            i != j && contains(other, contour[0])
+                        })
+                        .count()
+                })
+                .collect::<Vec<_>>();
+
+            let cycles = contours
+                .iter()
+                .zip(&depths)
+                .map(|(contour, depth)| {
+                    let mut points = simplify(contour, tolerance);
+                    let area = signed_area(&points);
+                    if points.len() < 3 || area == Scalar::ZERO {
+                        return None;
+                    }
+
+                    // Exteriors must be counter-clockwise, interiors
+                    // clockwise.
+                    let is_exterior = depth % 2 == 0;
+                    if is_exterior != (area > Scalar::ZERO) {
+                        points.reverse();
+                    }
+
+                    Some(points)
+                })
+                .collect::<Vec<_>>();
+
+            let mut regions = BTreeMap::new();
+            for (i, cycle) in cycles.iter().enumerate() {
+                if let (Some(cycle), 0) = (cycle, depths[i] % 2) {
+                    regions.insert(i, (cycle.clone(), Vec::new()));
+                }
+            }
+            for (i, cycle) in cycles.iter().enumerate() {
+                let (Some(cycle), 1) = (cycle, depths[i] % 2) else {
+                    continue;
+                };
+
+                // The region this interior belongs to is the one contour that
+                // is one level further out and contains it.
+                let parent = (0..contours.len()).find(|&j| {
+                    depths[j] + 1 == depths[i]
+                        && contains(&contours[j], contours[i][0])
+                });
+                if let Some((_, interiors)) =
+                    parent.and_then(|j| regions.get_mut(&j))
+                {
+                    interiors.push(cycle.clone());
+                }
+            }
+
+            let regions = regions
+                .into_values()
+                .map(|(exterior, interiors)| {
+                    let exterior = Cycle::polygon(exterior, core).insert(core);
+                    let interiors = interiors
+                        .into_iter()
+                        .map(|interior| {
+                            Cycle::polygon(interior, core).insert(core)
+                        })
+                        .collect::<Vec<_>>();
+
+                    Region::new(exterior, interiors).insert(core)
+                })
+                .collect::<Vec<_>>();
+
+            Sketch::new(regions)
+        })
+    }
+}
+
+/// Find the contours between set and unset pixels, using marching squares
+///
+/// The samples of the grid are the pixel centers, and the vertices of the
+/// contours are at the midpoints between neighboring samples. To keep them
+/// exact, vertices are returned in doubled sample coordinates.
+fn contours(bitmap: &Bitmap) -> Vec<Vec<[i64; 2]>> {
+    let [width, height] = [bitmap.width, bitmap.height]
+        .map(|n| i64::try_from(n).expect("Bitmap size must fit into `i64`"));
+
+    let mut neighbors = BTreeMap::<_, Vec<_>>::new();
+
+    // The cells extend one sample past the bitmap in each direction, so the
+    // contours of shapes that touch the edge of the bitmap are still closed.
+    for j in -1..height {
+        for i in -1..width {
+            let [c00, c10, c11, c01] =
+                [[i, j], [i + 1, j], [i + 1, j + 1], [i, j + 1]]
+                    .map(|sample| bitmap.sample(sample));
+
+            let top = [2 * i + 1, 2 * j];
+            let right = [2 * i + 2, 2 * j + 1];
+            let bottom = [2 * i + 1, 2 * j + 2];
+            let left = [2 * i, 2 * j + 1];
+
+            let crossed = [
+                (top, c00 != c10),
+                (right, c10 != c11),
+                (bottom, c01 != c11),
+                (left, c00 != c01),
+            ]
+            .into_iter()
+            .filter_map(|(vertex, is_crossed)| is_crossed.then_some(vertex))
+            .collect::<Vec<_>>();
+
+            let segments = match crossed[..] {
+                [a, b] => vec![[a, b]],
+                [_, _, _, _] => {
+                    // Diagonally opposite samples are set. Connect them, by
+                    // cutting off the samples that aren't set.
+                    if c00 {
+                        vec![[top, right], [bottom, left]]
+                    } else {
+                        vec![[top, left], [right, bottom]]
+                    }
+                }
+                _ => vec![],
+            };
+
+            for [a, b] in segments {
+                neighbors.entry(a).or_default().push(b);
+                neighbors.entry(b).or_default().push(a);
+            }
+        }
+    }
+
+    // Every vertex is on the edge between two cells, and connected to exactly
+    // one other vertex in each of them.
+    let mut visited = BTreeSet::new();
+    let mut contours = Vec::new();
+
+    for &start in neighbors.keys() {
+        if visited.contains(&start) {
+            continue;
+        }
+
+        let mut contour = Vec::new();
+        let mut previous = start;
+        let mut current = start;
+
+        loop {
+            visited.insert(current);
+            contour.push(current);
+
+            let next = match neighbors[&current][..] {
+                [a, b] => {
+                    if a == previous {
+                        b
+                    } else {
+                        a
+                    }
+                }
+                _ => unreachable!("Contour vertex must have two neighbors"),
+            };
+
+            previous = current;
+            current = next;
+
+            if current == start {
+                break;
+            }
+        }
+
+        contours.push(contour);
+    }
+
+    contours
+}
+
+/// Simplify a closed polyline, using the Ramer-Douglas-Peucker algorithm
+pub(crate) fn simplify(
+    points: &[Point<2>],
+    tolerance: Scalar,
+) -> Vec<Point<2>> {
+    // Split the polyline in two, at the points that are farthest apart.
+    let farthest = (1..points.len())
+        .max_by_key(|&i| points[0].distance_to(&points[i]))
+        .unwrap_or(0);
+
+    let mut simplified = Vec::new();
+    simplify_open(&points[..=farthest], tolerance, &mut simplified);
+    let second_half = points[farthest..]
+        .iter()
+        .chain([&points[0]])
+        .copied()
+        .collect::<Vec<_>>();
+    simplify_open(&second_half, tolerance, &mut simplified);
+
+    simplified
+}
+
+/// Simplify an open polyline
+///
+/// Adds all points that are kept to `simplified`, except for the last one.
+fn simplify_open(
+    points: &[Point<2>],
+    tolerance: Scalar,
+    simplified: &mut Vec<Point<2>>,
+) {
+    let [Some(&first), Some(&last)] = [points.first(), points.last()] else {
+        return;
+    };
+
+    let farthest = (1..points.len() - 1)
+        .max_by_key(|&i| distance_to_segment(points[i], [first, last]));
+
+    match farthest {
+        Some(i)
+            if distance_to_segment(points[i], [first, last]) > tolerance =>
+        {
+            simplify_open(&points[..=i], tolerance, simplified);
+            simplify_open(&points[i..], tolerance, simplified);
+        }
+        _ => simplified.push(first),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Scalar;
+
+    use crate::{
+        objects::Region,
+        operations::{insert::Insert, sweep::SweepSketch},
+        Core,
+    };
+
+    use super::{Bitmap, TraceBitmap};
+
+    #[test]
+    fn trace() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        // A square ring, with a smaller square within its hole.
+        let bitmap = Bitmap::from_fn(10, 10, |[x, y]| {
+            let distance_from_center = x.abs_diff(5).max(y.abs_diff(5));
+            distance_from_center <= 1 || distance_from_center >= 3
+        });
+
+        let sketch = bitmap.trace(0.5, 0.1, &mut core);
+
+        let mut regions = sketch.regions().iter().collect::<Vec<_>>();
+        regions.sort_by_key(|region| region.interiors().len());
+        let [island, ring] = regions[..] else {
+            panic!("Expected two regions");
+        };
+        assert_eq!(island.interiors().len(), 0);
+        assert_eq!(ring.interiors().len(), 1);
+
+        for region in [island, ring] {
+            assert!(region.exterior().winding().is_ccw());
+            for interior in region.interiors() {
+                assert!(!interior.winding().is_ccw());
+            }
+        }
+
+        // The contours run through the middle of the pixels at the edge of a
+        // shape, cutting off their corners. Sharp corners only require one
+        // extra vertex each.
+        let num_vertices =
+            |region: &Region| region.exterior().half_edges().len();
+        assert_eq!(num_vertices(island), 8);
+        assert_eq!(num_vertices(ring), 8);
+
+        let surface = core.layers.objects.surfaces.xy_plane();
+        let _ = sketch
+            .sweep_sketch(surface, [0., 0., 1.], &mut core)
+            .insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn trace_empty() {
+        let mut core = Core::new();
+
+        let bitmap = Bitmap::from_fn(3, 3, |_| false);
+        let sketch = bitmap.trace(Scalar::ONE, 0.1, &mut core);
+
+        assert_eq!(sketch.regions().len(), 0);
+    }
+}
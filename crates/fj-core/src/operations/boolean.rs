@@ -0,0 +1,804 @@
+//! Boolean operations on 2D shapes
+//!
+//! See [`Boolean`].
+
+use std::cmp::Ordering;
+
+use fj_interop::Color;
+use fj_math::{Point, Scalar, Vector};
+
+use crate::{
+    geometry::SurfacePath,
+    objects::{Cycle, HalfEdge, Region, Sketch},
+    storage::Handle,
+    Core,
+};
+
+use super::{
+    build::BuildCycle,
+    insert::Insert,
+    presentation::{GetColor, SetColor},
+};
+
+/// Combine 2D shapes using boolean operations
+///
+/// Both operands are expected to be defined on the same surface. Regions
+/// within a [`Sketch`] must not overlap each other.
+///
+/// The result is a sketch, as the result of any boolean operation might
+/// consist of multiple regions. Its regions are built from scratch, with
+/// counter-clockwise exteriors and clockwise interiors, regardless of the
+/// winding of the operands. If `self` is a sketch, they have the color of its
+/// first region, if that has one.
+///
+/// # Limitations
+///
+/// Only regions that are bounded by line segments are supported.
+pub trait Boolean {
+    /// Compute everything that is within either shape
+    fn union(
+        &self,
+        other: &Self,
+        core: &mut Core,
+    ) -> Result<Sketch, BooleanError>;
+
+    /// Compute everything that is within this shape, but not the other one
+    fn difference(
+        &self,
+        other: &Self,
+        core: &mut Core,
+    ) -> Result<Sketch, BooleanError>;
+
+    /// Compute everything that is within both shapes
+    fn intersection(
+        &self,
+        other: &Self,
+        core: &mut Core,
+    ) -> Result<Sketch, BooleanError>;
+}
+
+impl Boolean for Sketch {
+    fn union(
+        &self,
+        other: &Self,
+        core: &mut Core,
+    ) -> Result<Sketch, BooleanError> {
+        let color = self
+            .regions()
+            .iter()
+            .next()
+            .and_then(|region| region.get_color(core));
+        boolean(
+            self.regions().iter().map(|region| &**region),
+            other.regions().iter().map(|region| &**region),
+            BooleanOperation::Union,
+            color,
+            core,
+        )
+    }
+
+    fn difference(
+        &self,
+        other: &Self,
+        core: &mut Core,
+    ) -> Result<Sketch, BooleanError> {
+        let color = self
+            .regions()
+            .iter()
+            .next()
+            .and_then(|region| region.get_color(core));
+        boolean(
+            self.regions().iter().map(|region| &**region),
+            other.regions().iter().map(|region| &**region),
+            BooleanOperation::Difference,
+            color,
+            core,
+        )
+    }
+
+    fn intersection(
+        &self,
+        other: &Self,
+        core: &mut Core,
+    ) -> Result<Sketch, BooleanError> {
+        let color = self
+            .regions()
+            .iter()
+            .next()
+            .and_then(|region| region.get_color(core));
+        boolean(
+            self.regions().iter().map(|region| &**region),
+            other.regions().iter().map(|region| &**region),
+            BooleanOperation::Intersection,
+            color,
+            core,
+        )
+    }
+}
+
+impl Boolean for Region {
+    fn union(
+        &self,
+        other: &Self,
+        core: &mut Core,
+    ) -> Result<Sketch, BooleanError> {
+        boolean([self], [other], BooleanOperation::Union, None, core)
+    }
+
+    fn difference(
+        &self,
+        other: &Self,
+        core: &mut Core,
+    ) -> Result<Sketch, BooleanError> {
+        boolean([self], [other], BooleanOperation::Difference, None, core)
+    }
+
+    fn intersection(
+        &self,
+        other: &Self,
+        core: &mut Core,
+    ) -> Result<Sketch, BooleanError> {
+        boolean([self], [other], BooleanOperation::Intersection, None, core)
+    }
+}
+
+/// Error computing a boolean operation
+///
+/// See [`Boolean`].
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum BooleanError {
+    /// Half-edge is not a line segment
+    #[error("Boolean operation only supports line segments: {half_edge:#?}")]
+    UnsupportedHalfEdge {
+        /// The half-edge
+        half_edge: Handle<HalfEdge>,
+    },
+
+    /// The boundary of the result could not be assembled into closed cycles
+    ///
+    /// This can happen, if the regions of an operand overlap each other, or if
+    /// an operand has an invalid boundary.
+    #[error("Could not assemble boundary of boolean operation result")]
+    OpenBoundary,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum BooleanOperation {
+    Union,
+    Difference,
+    Intersection,
+}
+
+fn boolean<'r>(
+    a: impl IntoIterator<Item = &'r Region>,
+    b: impl IntoIterator<Item = &'r Region>,
+    operation: BooleanOperation,
+    color: Option<Color>,
+    core: &mut Core,
+) -> Result<Sketch, BooleanError> {
+    let a = a.into_iter().collect::<Vec<_>>();
+    let b = b.into_iter().collect::<Vec<_>>();
+
+    core.trace_operation("boolean", &[&a, &b, &operation, &color], |core| {
+        let tolerance = core.layers.validation.config.identical_max_distance;
+
+        let mut vertices = Vertices::new(tolerance);
+        let a = Operand::new(a.iter().copied(), &mut vertices)?;
+        let b = Operand::new(b.iter().copied(), &mut vertices)?;
+
+        let [splits_a, splits_b] = split_edges(&a, &b, &mut vertices);
+
+        // Which parts of the boundaries end up bounding the result depends on
+        // where they are, relative to the other operand. Where the boundaries
+        // of both operands coincide, only those of `a` are considered, so they
+        // don't end up in the result twice.
+        let mut edges = Vec::new();
+        for (from, to) in a.fragments(splits_a, &vertices) {
+            let keep = match position_of_fragment(from, to, &b, &vertices) {
+                Position::Outside => {
+                    operation != BooleanOperation::Intersection
+                }
+                Position::Inside => operation == BooleanOperation::Intersection,
+                Position::OnBoundarySameDirection => {
+                    operation != BooleanOperation::Difference
+                }
+                Position::OnBoundaryOppositeDirection => {
+                    operation == BooleanOperation::Difference
+                }
+            };
+
+            if keep {
+                edges.push((from, to));
+            }
+        }
+        for (from, to) in b.fragments(splits_b, &vertices) {
+            match (position_of_fragment(from, to, &a, &vertices), operation) {
+                (Position::Outside, BooleanOperation::Union)
+                | (Position::Inside, BooleanOperation::Intersection) => {
+                    edges.push((from, to));
+                }
+                (Position::Inside, BooleanOperation::Difference) => {
+                    // The boundary of `b` becomes a boundary of the result,
+                    // but the result is on the other side of it.
+                    edges.push((to, from));
+                }
+                _ => {}
+            }
+        }
+
+        let cycles = link_edges(edges, &vertices)?;
+
+        let mut exteriors = Vec::new();
+        let mut interiors = Vec::new();
+        for cycle in cycles {
+            let area = signed_area(&cycle);
+            match area.cmp(&Scalar::ZERO) {
+                Ordering::Greater => exteriors.push((cycle, area, Vec::new())),
+                Ordering::Less => interiors.push(cycle),
+                Ordering::Equal => {}
+            }
+        }
+
+        // Each interior belongs to the smallest exterior that contains it.
+        for interior in interiors {
+            let point = interior[0] + (interior[1] - interior[0]) / 2.;
+
+            let (_, _, holes) = exteriors
+                .iter_mut()
+                .filter(|(exterior, _, _)| contains(exterior, point))
+                .min_by_key(|(_, area, _)| *area)
+                .ok_or(BooleanError::OpenBoundary)?;
+            holes.push(interior);
+        }
+
+        let regions = exteriors
+            .into_iter()
+            .map(|(exterior, _, interiors)| {
+                let exterior = Cycle::polygon(exterior, core).insert(core);
+                let interiors = interiors
+                    .into_iter()
+                    .map(|interior| Cycle::polygon(interior, core).insert(core))
+                    .collect::<Vec<_>>();
+
+                let region = Region::new(exterior, interiors).insert(core);
+                if let Some(color) = color {
+                    region.set_color(color, core);
+                }
+
+                region
+            })
+            .collect::<Vec<_>>();
+
+        Ok(Sketch::new(regions))
+    })
+}
+
+/// The vertices of both operands, and the intersections between them
+///
+/// Points that are within the tolerance of each other are the same vertex.
+/// This makes sure that the boundaries of both operands are split at the same
+/// points, and that the resulting fragments can be linked up exactly.
+struct Vertices {
+    points: Vec<Point<2>>,
+    tolerance: Scalar,
+}
+
+impl Vertices {
+    fn new(tolerance: Scalar) -> Self {
+        Self {
+            points: Vec::new(),
+            tolerance,
+        }
+    }
+
+    fn get_or_insert(&mut self, point: Point<2>) -> usize {
+        if let Some(index) = self
+            .points
+            .iter()
+            .position(|p| p.distance_to(&point) <= self.tolerance)
+        {
+            return index;
+        }
+
+        self.points.push(point);
+        self.points.len() - 1
+    }
+
+    fn point(&self, index: usize) -> Point<2> {
+        self.points[index]
+    }
+}
+
+/// One side of a boolean operation, as cycles of vertices
+///
+/// All cycles are oriented, so that the inside of the operand is on their
+/// left.
+struct Operand {
+    cycles: Vec<Vec<usize>>,
+}
+
+impl Operand {
+    fn new<'r>(
+        regions: impl IntoIterator<Item = &'r Region>,
+        vertices: &mut Vertices,
+    ) -> Result<Self, BooleanError> {
+        let mut cycles = Vec::new();
+
+        for region in regions {
+            let is_ccw = region.exterior().winding().is_ccw();
+
+            for cycle in region.all_cycles() {
+                let mut points = Vec::new();
+                for half_edge in cycle.half_edges() {
+                    let SurfacePath::Line(_) = half_edge.path() else {
+                        return Err(BooleanError::UnsupportedHalfEdge {
+                            half_edge: half_edge.clone(),
+                        });
+                    };
+
+                    points.push(
+                        vertices.get_or_insert(half_edge.start_position()),
+                    );
+                }
+
+                // Degenerate half-edges don't bound anything.
+                points.dedup();
+                if points.len() > 1 && points.first() == points.last() {
+                    points.pop();
+                }
+
+                if !is_ccw {
+                    points.reverse();
+                }
+
+                cycles.push(points);
+            }
+        }
+
+        Ok(Self { cycles })
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.cycles.iter().flat_map(|cycle| {
+            (0..cycle.len()).map(|i| (cycle[i], cycle[(i + 1) % cycle.len()]))
+        })
+    }
+
+    /// Split the edges at the provided vertices
+    fn fragments(
+        &self,
+        splits: Vec<Vec<usize>>,
+        vertices: &Vertices,
+    ) -> Vec<(usize, usize)> {
+        let mut fragments = Vec::new();
+
+        for ((from, to), mut splits) in self.edges().zip(splits) {
+            let [start, end] = [from, to].map(|index| vertices.point(index));
+            let direction = end - start;
+
+            splits.sort_by_key(|&index| {
+                (vertices.point(index) - start).dot(&direction)
+            });
+
+            let mut previous = from;
+            for index in splits.into_iter().chain([to]) {
+                if index != previous {
+                    fragments.push((previous, index));
+                }
+                previous = index;
+            }
+        }
+
+        fragments
+    }
+}
+
+/// Find the vertices, at which the edges of each operand need to be split
+///
+/// Returns a list of vertices per edge of each operand, in the order of
+/// [`Operand::edges`].
+fn split_edges(
+    a: &Operand,
+    b: &Operand,
+    vertices: &mut Vertices,
+) -> [Vec<Vec<usize>>; 2] {
+    let edges_a = a.edges().collect::<Vec<_>>();
+    let edges_b = b.edges().collect::<Vec<_>>();
+
+    let mut splits_a = vec![Vec::new(); edges_a.len()];
+    let mut splits_b = vec![Vec::new(); edges_b.len()];
+
+    for (i, &edge_a) in edges_a.iter().enumerate() {
+        for (j, &edge_b) in edges_b.iter().enumerate() {
+            for point in intersect_segments(edge_a, edge_b, vertices) {
+                let index = vertices.get_or_insert(point);
+
+                for (splits, (from, to)) in
+                    [(&mut splits_a[i], edge_a), (&mut splits_b[j], edge_b)]
+                {
+                    if index != from && index != to {
+                        splits.push(index);
+                    }
+                }
+            }
+        }
+    }
+
+    [splits_a, splits_b]
+}
+
+/// Compute the points where two segments touch or cross
+///
+/// Returns the end points of the overlap, if the segments are collinear.
+fn intersect_segments(
+    (a, b): (usize, usize),
+    (c, d): (usize, usize),
+    vertices: &Vertices,
+) -> Vec<Point<2>> {
+    let [a, b, c, d] = [a, b, c, d].map(|index| vertices.point(index));
+    let tolerance = vertices.tolerance;
+
+    let ab = b - a;
+    let cd = d - c;
+    let denominator = ab.cross2d(&cd);
+
+    // Touching end points are caught by the general case below, but
+    // computing them here is more precise.
+    let end_points_on_other_segment = || {
+        [(a, [c, d]), (b, [c, d]), (c, [a, b]), (d, [a, b])]
+            .into_iter()
+            .filter(|&(point, segment)| {
+                distance_to_segment(point, segment) <= tolerance
+            })
+            .map(|(point, _)| point)
+            .collect::<Vec<_>>()
+    };
+
+    if denominator.abs() <= tolerance * ab.magnitude() * cd.magnitude() {
+        // The segments are parallel, so they can only touch where they
+        // overlap.
+        return end_points_on_other_segment();
+    }
+
+    let points = end_points_on_other_segment();
+    if !points.is_empty() {
+        return points;
+    }
+
+    let ac = c - a;
+    let t = ac.cross2d(&cd) / denominator;
+    let s = ac.cross2d(&ab) / denominator;
+
+    let range = Scalar::ZERO..=Scalar::ONE;
+    if range.contains(&t) && range.contains(&s) {
+        vec![a + ab * t]
+    } else {
+        Vec::new()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Position {
+    Inside,
+    Outside,
+    OnBoundarySameDirection,
+    OnBoundaryOppositeDirection,
+}
+
+/// Determine where an edge fragment is, relative to an operand
+fn position_of_fragment(
+    from: usize,
+    to: usize,
+    operand: &Operand,
+    vertices: &Vertices,
+) -> Position {
+    let [start, end] = [from, to].map(|index| vertices.point(index));
+    let direction = end - start;
+    let point = start + direction / 2.;
+
+    for (other_from, other_to) in operand.edges() {
+        let segment = [other_from, other_to].map(|index| vertices.point(index));
+
+        if distance_to_segment(point, segment) <= vertices.tolerance {
+            let [other_start, other_end] = segment;
+            return if direction.dot(&(other_end - other_start)) > Scalar::ZERO {
+                Position::OnBoundarySameDirection
+            } else {
+                Position::OnBoundaryOppositeDirection
+            };
+        }
+    }
+
+    let is_inside = operand
+        .cycles
+        .iter()
+        .filter(|cycle| {
+            let points = cycle
+                .iter()
+                .map(|&index| vertices.point(index))
+                .collect::<Vec<_>>();
+            contains(&points, point)
+        })
+        .count()
+        % 2
+        == 1;
+
+    if is_inside {
+        Position::Inside
+    } else {
+        Position::Outside
+    }
+}
+
+/// Link edges into closed cycles
+///
+/// Where a vertex has multiple outgoing edges, the one that turns left the
+/// most is picked. This results in the smallest possible cycles, so regions
+/// that touch in a single vertex are kept separate.
+fn link_edges(
+    edges: Vec<(usize, usize)>,
+    vertices: &Vertices,
+) -> Result<Vec<Vec<Point<2>>>, BooleanError> {
+    let direction =
+        |(from, to): (usize, usize)| vertices.point(to) - vertices.point(from);
+
+    let mut used = vec![false; edges.len()];
+    let mut cycles = Vec::new();
+
+    while let Some(first) = used.iter().position(|used| !used) {
+        used[first] = true;
+
+        let mut cycle = vec![edges[first].0];
+        let mut current = edges[first];
+
+        while current.1 != edges[first].0 {
+            let incoming = direction(current);
+
+            let next = (0..edges.len())
+                .filter(|&i| !used[i] && edges[i].0 == current.1)
+                .max_by_key(|&i| {
+                    let outgoing = direction(edges[i]);
+                    Scalar::atan2(
+                        incoming.cross2d(&outgoing),
+                        incoming.dot(&outgoing),
+                    )
+                })
+                .ok_or(BooleanError::OpenBoundary)?;
+
+            used[next] = true;
+            cycle.push(edges[next].0);
+            current = edges[next];
+        }
+
+        cycles.push(remove_collinear_vertices(cycle, vertices));
+    }
+
+    Ok(cycles)
+}
+
+/// Remove vertices that are only there, because an edge was split there
+fn remove_collinear_vertices(
+    cycle: Vec<usize>,
+    vertices: &Vertices,
+) -> Vec<Point<2>> {
+    let points = cycle
+        .into_iter()
+        .map(|index| vertices.point(index))
+        .collect::<Vec<_>>();
+    let n = points.len();
+
+    (0..n)
+        .filter(|&i| {
+            let [previous, point, next] =
+                [points[(i + n - 1) % n], points[i], points[(i + 1) % n]];
+            let [incoming, outgoing]: [Vector<2>; 2] =
+                [point - previous, next - point];
+
+            let is_collinear = incoming.dot(&outgoing) > Scalar::ZERO
+                && incoming.normalize().cross2d(&outgoing.normalize()).abs()
+                    <= vertices.tolerance;
+
+            !is_collinear
+        })
+        .map(|i| points[i])
+        .collect()
+}
+
+pub(crate) fn signed_area(points: &[Point<2>]) -> Scalar {
+    let n = points.len();
+    (0..n)
+        .map(|i| points[i].coords.cross2d(&points[(i + 1) % n].coords))
+        .fold(Scalar::ZERO, |sum, area| sum + area)
+        / 2.
+}
+
+/// Determine whether a point is within a polygon, using the even-odd rule
+pub(crate) fn contains(polygon: &[Point<2>], point: Point<2>) -> bool {
+    let n = polygon.len();
+    let mut is_inside = false;
+
+    for i in 0..n {
+        let [a, b] = [polygon[i], polygon[(i + 1) % n]];
+
+        if (a.v > point.v) != (b.v > point.v) {
+            let u = a.u + (point.v - a.v) / (b.v - a.v) * (b.u - a.u);
+            if point.u < u {
+                is_inside = !is_inside;
+            }
+        }
+    }
+
+    is_inside
+}
+
+pub(crate) fn distance_to_segment(
+    point: Point<2>,
+    [a, b]: [Point<2>; 2],
+) -> Scalar {
+    let ab = b - a;
+    let t =
+        ((point - a).dot(&ab) / ab.dot(&ab)).clamp(Scalar::ZERO, Scalar::ONE);
+
+    point.distance_to(&(a + ab * t))
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Scalar;
+
+    use crate::{
+        objects::{Region, Sketch},
+        operations::{
+            build::{BuildRegion, BuildSketch},
+            reverse::Reverse,
+            update::UpdateSketch,
+        },
+        Core,
+    };
+
+    use super::{signed_area, Boolean, BooleanError};
+
+    #[test]
+    fn union() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let a = Region::polygon(
+            [[0., 0.], [2., 0.], [2., 2.], [0., 2.]],
+            &mut core,
+        );
+        let b = Region::polygon(
+            [[1., 1.], [3., 1.], [3., 3.], [1., 3.]],
+            &mut core,
+        );
+
+        let union = a.union(&b, &mut core)?;
+        assert_eq!(areas(&union), vec![(7., 0)]);
+
+        // Regions that only share an edge are merged.
+        let c = Region::polygon(
+            [[2., 0.], [4., 0.], [4., 2.], [2., 2.]],
+            &mut core,
+        );
+        let union = a.union(&c, &mut core)?;
+        assert_eq!(areas(&union), vec![(8., 0)]);
+        assert_eq!(union.regions().first().exterior().half_edges().len(), 4);
+
+        // Regions that don't touch stay separate.
+        let d = Region::polygon(
+            [[5., 0.], [6., 0.], [6., 1.], [5., 1.]],
+            &mut core,
+        );
+        let union = a.union(&d, &mut core)?;
+        assert_eq!(areas(&union), vec![(1., 0), (4., 0)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn difference() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let a = Region::polygon(
+            [[0., 0.], [4., 0.], [4., 4.], [0., 4.]],
+            &mut core,
+        );
+
+        // A region that is within the other one becomes a hole. The winding
+        // of the operands doesn't matter.
+        let b = Region::polygon(
+            [[1., 1.], [2., 1.], [2., 2.], [1., 2.]],
+            &mut core,
+        )
+        .reverse(&mut core);
+        let difference = a.difference(&b, &mut core)?;
+        assert_eq!(areas(&difference), vec![(15., 1)]);
+
+        // Cutting through the middle splits the region in two.
+        let c = Region::polygon(
+            [[1., -1.], [2., -1.], [2., 5.], [1., 5.]],
+            &mut core,
+        );
+        let difference = a.difference(&c, &mut core)?;
+        assert_eq!(areas(&difference), vec![(4., 0), (8., 0)]);
+
+        // The result can be used as an operand again.
+        let sketch = Sketch::empty().add_regions([a], &mut core);
+        let difference = sketch.difference(&difference, &mut core)?;
+        assert_eq!(areas(&difference), vec![(4., 0)]);
+
+        core.layers.validation.take_errors()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn intersection() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let a = Region::polygon(
+            [[0., 0.], [2., 0.], [2., 2.], [0., 2.]],
+            &mut core,
+        );
+        let b = Region::polygon(
+            [[1., 1.], [3., 1.], [3., 3.], [1., 3.]],
+            &mut core,
+        );
+        let intersection = a.intersection(&b, &mut core)?;
+        assert_eq!(areas(&intersection), vec![(1., 0)]);
+
+        let c = Region::polygon(
+            [[5., 0.], [6., 0.], [6., 1.], [5., 1.]],
+            &mut core,
+        );
+        let intersection = a.intersection(&c, &mut core)?;
+        assert_eq!(areas(&intersection), vec![]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn unsupported_half_edge() {
+        let mut core = Core::new();
+
+        let a = Region::polygon(
+            [[0., 0.], [2., 0.], [2., 2.], [0., 2.]],
+            &mut core,
+        );
+        let b = Region::circle([1., 1.], 0.5, &mut core);
+
+        assert!(matches!(
+            a.difference(&b, &mut core),
+            Err(BooleanError::UnsupportedHalfEdge { .. })
+        ));
+    }
+
+    /// The area and number of interiors of each region, ordered by area
+    fn areas(sketch: &Sketch) -> Vec<(f64, usize)> {
+        let mut areas = sketch
+            .regions()
+            .iter()
+            .map(|region| {
+                let area = region
+                    .all_cycles()
+                    .map(|cycle| {
+                        let points = cycle
+                            .half_edges()
+                            .iter()
+                            .map(|half_edge| half_edge.start_position())
+                            .collect::<Vec<_>>();
+                        signed_area(&points)
+                    })
+                    .fold(Scalar::ZERO, |sum, area| sum + area);
+
+                // Intersections are computed, so the area might be slightly
+                // off.
+                let area = (area.into_f64() * 1e9).round() / 1e9;
+
+                (area, region.interiors().len())
+            })
+            .collect::<Vec<_>>();
+        areas.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        areas
+    }
+}
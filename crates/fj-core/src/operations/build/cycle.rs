@@ -1,7 +1,8 @@
-use fj_math::{Point, Scalar};
+use fj_math::{Circle, Point, Scalar};
 use itertools::Itertools;
 
 use crate::{
+    geometry::SurfacePath,
     objects::{Cycle, HalfEdge},
     operations::{build::BuildHalfEdge, insert::Insert, update::UpdateCycle},
     Core,
@@ -18,6 +19,123 @@ pub trait BuildCycle {
         Cycle::new([])
     }
 
+    /// Build a cycle from arcs that each pass through three points
+    ///
+    /// Each of the `segments` provides the interior point and the end point of
+    /// an arc. Each arc starts where the previous one ends, and the first one
+    /// starts where the last one ends.
+    ///
+    /// See [`BuildHalfEdge::arc_through_points`].
+    fn arcs_through_points<P, Ps>(segments: Ps, core: &mut Core) -> Cycle
+    where
+        P: Into<Point<2>>,
+        Ps: IntoIterator<Item = [P; 2]>,
+    {
+        let segments = segments
+            .into_iter()
+            .map(|segment| segment.map(Into::into))
+            .collect::<Vec<_>>();
+
+        let starts = segments
+            .iter()
+            .cycle()
+            .skip(segments.len().saturating_sub(1))
+            .map(|[_, end]| *end);
+        let edges = segments
+            .iter()
+            .zip(starts)
+            .map(|(&[interior, end], start)| {
+                HalfEdge::arc_through_points([start, interior, end], core)
+                    .insert(core)
+            })
+            .collect::<Vec<_>>();
+
+        Cycle::new(edges)
+    }
+
+    /// Build a belt that wraps around a number of pulleys
+    ///
+    /// The belt runs counter-clockwise around the outside of each pulley, and
+    /// follows the outer tangent from each pulley to the next. It consists of
+    /// a line segment for each tangent, and an arc for each pulley.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if fewer than 2 pulleys are provided, if a pulley lies within
+    /// the next one (or the other way around), or if the pulleys are not
+    /// arranged as a convex shape, in counter-clockwise order.
+    fn belt(
+        pulleys: impl IntoIterator<Item = Circle<2>>,
+        core: &mut Core,
+    ) -> Cycle {
+        let pulleys = pulleys.into_iter().collect::<Vec<_>>();
+        assert!(
+            pulleys.len() >= 2,
+            "Belt must wrap around at least 2 pulleys"
+        );
+
+        // Running counter-clockwise, the belt is always to the right of the
+        // line between two pulleys.
+        let spans = pulleys
+            .iter()
+            .circular_tuple_windows()
+            .map(|(a, b)| {
+                let [_, right] = a.outer_tangents(b)[..] else {
+                    panic!(
+                        "Pulleys must not lie within each other: {a:?}, {b:?}"
+                    );
+                };
+                right
+            })
+            .collect::<Vec<_>>();
+
+        let mut half_edges = Vec::new();
+        let mut total_angle = Scalar::ZERO;
+
+        for (i, &[start, end]) in spans.iter().enumerate() {
+            half_edges.push(HalfEdge::line_segment([start, end], None, core));
+
+            // Each span ends on the pulley that the next one starts from.
+            let pulley = pulleys[(i + 1) % pulleys.len()];
+            let [next, _] = spans[(i + 1) % spans.len()];
+
+            let [start_angle, end_angle] = [end, next].map(|point| {
+                let vector = point - pulley.center();
+                vector.v.atan2(vector.u)
+            });
+            let angle = Scalar::from(
+                (end_angle - start_angle)
+                    .into_f64()
+                    .rem_euclid(Scalar::TAU.into_f64()),
+            );
+            total_angle += angle;
+
+            // If a pulley lies exactly on the line between its neighbors, the
+            // belt doesn't wrap around it.
+            if angle == Scalar::ZERO {
+                continue;
+            }
+
+            let path = SurfacePath::circle_from_center_and_radius(
+                pulley.center(),
+                pulley.radius(),
+            );
+            let boundary = [start_angle, start_angle + angle]
+                .map(|coord| Point::from([coord]));
+            half_edges.push(HalfEdge::unjoined(path, boundary, core));
+        }
+
+        // The belt makes exactly one turn, if it wraps around a convex
+        // arrangement of pulleys. Otherwise, it would cross itself.
+        assert!(
+            total_angle < Scalar::TAU * 1.5,
+            "Pulleys must be arranged as a convex shape, in counter-clockwise \
+            order: {pulleys:?}"
+        );
+
+        Cycle::empty().add_half_edges(half_edges, core)
+    }
+
     /// Build a circle
     fn circle(
         center: impl Into<Point<2>>,
@@ -28,6 +146,173 @@ pub trait BuildCycle {
         Cycle::empty().add_half_edges([circle], core)
     }
 
+    /// Build a cycle from cubic Bézier curves
+    ///
+    /// Each of the `segments` provides the two inner control points and the
+    /// end point of a curve. Each curve starts where the previous one ends, and
+    /// the first one starts where the last one ends.
+    fn cubic_bezier<P, Ps>(segments: Ps, core: &mut Core) -> Cycle
+    where
+        P: Into<Point<2>>,
+        Ps: IntoIterator<Item = [P; 3]>,
+    {
+        let segments = segments
+            .into_iter()
+            .map(|segment| segment.map(Into::into))
+            .collect::<Vec<_>>();
+
+        let starts = segments
+            .iter()
+            .cycle()
+            .skip(segments.len().saturating_sub(1))
+            .map(|[_, _, end]| *end);
+        let edges = segments
+            .iter()
+            .zip(starts)
+            .map(|(&[a, b, end], start)| {
+                HalfEdge::cubic_bezier([start, a, b, end], core).insert(core)
+            })
+            .collect::<Vec<_>>();
+
+        Cycle::new(edges)
+    }
+
+    /// Build an axis-aligned ellipse
+    fn ellipse(
+        center: impl Into<Point<2>>,
+        radii: [impl Into<Scalar>; 2],
+        core: &mut Core,
+    ) -> Cycle {
+        let ellipse = HalfEdge::ellipse(center, radii, core);
+        Cycle::empty().add_half_edges([ellipse], core)
+    }
+
+    /// Build a regular polygon
+    ///
+    /// The polygon is centered on the origin, and its first vertex lies on the
+    /// positive u-axis, at a distance of `radius` from the origin.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `num_vertices` is smaller than 3, or if `radius` is not
+    /// positive.
+    fn regular_polygon(
+        num_vertices: usize,
+        radius: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Cycle {
+        let radius = radius.into();
+        assert!(
+            num_vertices >= 3,
+            "A polygon needs at least 3 vertices: {num_vertices}"
+        );
+        assert!(radius > Scalar::ZERO, "Radius must be positive: {radius}");
+
+        let points = (0..num_vertices).map(|i| {
+            let angle =
+                Scalar::TAU / Scalar::from_u64(num_vertices as u64) * i as f64;
+            let (sin, cos) = angle.sin_cos();
+
+            Point::from([cos * radius, sin * radius])
+        });
+
+        Cycle::polygon(points, core)
+    }
+
+    /// Build a rectangle with rounded corners
+    ///
+    /// The rectangle is centered on the origin, and its sides are parallel to
+    /// the axes. Each corner is a quarter circle with the provided radius.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `radius` is not positive, or if it is so large, that the
+    /// corners don't leave any straight part of the sides.
+    fn rounded_rectangle(
+        width: impl Into<Scalar>,
+        height: impl Into<Scalar>,
+        radius: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Cycle {
+        let [width, height, radius] =
+            [width.into(), height.into(), radius.into()];
+        assert!(radius > Scalar::ZERO, "Radius must be positive: {radius}");
+        assert!(
+            radius * 2. < width && radius * 2. < height,
+            "Radius {radius} is too large for rectangle of size \
+            {width} x {height}"
+        );
+
+        let [u, v] = [width / 2., height / 2.];
+        let [a, b] = [u - radius, v - radius];
+
+        let corner = |center: [Scalar; 2], start, end, core: &mut Core| {
+            let center = Point::from(center);
+            let [start, end]: [Point<2>; 2] = [start, end].map(Point::from);
+
+            // The interior point lies halfway between start and end, on the
+            // circle.
+            let interior = center
+                + ((start - center) + (end - center)).normalize() * radius;
+
+            HalfEdge::arc_through_points([start, interior, end], core)
+        };
+
+        let half_edges = [
+            HalfEdge::line_segment([[-a, -v], [a, -v]], None, core),
+            corner([a, -b], [a, -v], [u, -b], core),
+            HalfEdge::line_segment([[u, -b], [u, b]], None, core),
+            corner([a, b], [u, b], [a, v], core),
+            HalfEdge::line_segment([[a, v], [-a, v]], None, core),
+            corner([-a, b], [-a, v], [-u, b], core),
+            HalfEdge::line_segment([[-u, b], [-u, -b]], None, core),
+            corner([-a, -b], [-u, -b], [-a, -v], core),
+        ];
+
+        Cycle::empty().add_half_edges(half_edges, core)
+    }
+
+    /// Build a slot
+    ///
+    /// A slot is a rectangle with semicircles at both of its ends. It is
+    /// centered on the origin, and `length`, which includes the semicircles,
+    /// is measured along the u-axis.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `width` is not positive, or if `length` is not larger than
+    /// `width`.
+    fn slot(
+        length: impl Into<Scalar>,
+        width: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Cycle {
+        let [length, width] = [length.into(), width.into()];
+        assert!(width > Scalar::ZERO, "Width must be positive: {width}");
+        assert!(
+            length > width,
+            "Length {length} of slot must be larger than its width {width}"
+        );
+
+        let radius = width / 2.;
+        let u = length / 2. - radius;
+
+        let half_edges = [
+            HalfEdge::line_segment([[-u, -radius], [u, -radius]], None, core),
+            HalfEdge::arc_through_points(
+                [[u, -radius], [u + radius, Scalar::ZERO], [u, radius]],
+                core,
+            ),
+            HalfEdge::line_segment([[u, radius], [-u, radius]], None, core),
+            HalfEdge::arc_through_points(
+                [[-u, radius], [-u - radius, Scalar::ZERO], [-u, -radius]],
+                core,
+            ),
+        ];
+
+        Cycle::empty().add_half_edges(half_edges, core)
+    }
+
     /// Build a polygon
     fn polygon<P, Ps>(points: Ps, core: &mut Core) -> Cycle
     where
@@ -1,5 +1,5 @@
 use fj_interop::ext::ArrayExt;
-use fj_math::{Arc, Point, Scalar};
+use fj_math::{Arc, Point, Scalar, Vector};
 
 use crate::{
     geometry::{CurveBoundary, SurfacePath},
@@ -66,6 +66,72 @@ pub trait BuildHalfEdge {
         HalfEdge::unjoined(path, boundary, core)
     }
 
+    /// Create an arc that passes through three points
+    ///
+    /// The arc starts at the first point, passes through the second one, and
+    /// ends at the third one.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the three points are collinear, as no arc passes through
+    /// them then.
+    fn arc_through_points(
+        points: [impl Into<Point<2>>; 3],
+        core: &mut Core,
+    ) -> HalfEdge {
+        let [start, interior, end] = points.map(Into::into);
+
+        // Computing the center relative to the start point, to keep the
+        // numbers small.
+        let b = interior - start;
+        let c = end - start;
+
+        // Twice the signed area of the triangle. It is positive, if the points
+        // are arranged counter-clockwise.
+        let d = b.cross2d(&c) * 2.;
+        assert!(
+            !d.is_zero(),
+            "Can't create arc through collinear points: \
+            {start:?}, {interior:?}, {end:?}"
+        );
+
+        let center = {
+            let b2 = b.dot(&b);
+            let c2 = c.dot(&c);
+
+            start
+                + Vector::from([
+                    (c.v * b2 - b.v * c2) / d,
+                    (b.u * c2 - c.u * b2) / d,
+                ])
+        };
+        let radius = (start - center).magnitude();
+
+        let angle_of = |point: Point<2>| {
+            let from_center = point - center;
+            from_center.v.atan2(from_center.u)
+        };
+        let start_angle = angle_of(start);
+
+        // The arc runs counter-clockwise, if the points are arranged that
+        // way. Otherwise it runs clockwise. Either way, it must not pass the
+        // end point before reaching the interior one.
+        let sweep = (angle_of(end) - start_angle)
+            .into_f64()
+            .rem_euclid(Scalar::TAU.into_f64());
+        let end_angle = if d.is_positive() {
+            start_angle + sweep
+        } else {
+            start_angle - (Scalar::TAU - sweep)
+        };
+
+        let path = SurfacePath::circle_from_center_and_radius(center, radius);
+        let boundary =
+            [start_angle, end_angle].map(|coord| Point::from([coord]));
+
+        HalfEdge::unjoined(path, boundary, core)
+    }
+
     /// Create a circle
     fn circle(
         center: impl Into<Point<2>>,
@@ -79,6 +145,36 @@ pub trait BuildHalfEdge {
         HalfEdge::unjoined(path, boundary, core)
     }
 
+    /// Create a cubic Bézier curve
+    ///
+    /// The half-edge starts at the first control point, and ends at the last
+    /// one.
+    fn cubic_bezier(
+        control_points: [impl Into<Point<2>>; 4],
+        core: &mut Core,
+    ) -> HalfEdge {
+        let path = SurfacePath::cubic_bezier(control_points);
+        let boundary =
+            [Scalar::ZERO, Scalar::ONE].map(|coord| Point::from([coord]));
+
+        HalfEdge::unjoined(path, boundary, core)
+    }
+
+    /// Create an axis-aligned ellipse
+    ///
+    /// See [`SurfacePath::ellipse_from_center_and_radii`].
+    fn ellipse(
+        center: impl Into<Point<2>>,
+        radii: [impl Into<Scalar>; 2],
+        core: &mut Core,
+    ) -> HalfEdge {
+        let path = SurfacePath::ellipse_from_center_and_radii(center, radii);
+        let boundary =
+            [Scalar::ZERO, Scalar::TAU].map(|coord| Point::from([coord]));
+
+        HalfEdge::unjoined(path, boundary, core)
+    }
+
     /// Create a line segment
     fn line_segment(
         points_surface: [impl Into<Point<2>>; 2],
@@ -96,3 +192,35 @@ pub trait BuildHalfEdge {
 }
 
 impl BuildHalfEdge for HalfEdge {}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Point;
+    use fj_test_utils::assert_points_eq;
+
+    use crate::{objects::HalfEdge, operations::build::BuildHalfEdge, Core};
+
+    #[test]
+    fn arc_through_points() {
+        let mut core = Core::new();
+
+        // Clockwise and counter-clockwise arcs, with the interior point in the
+        // middle of each.
+        for points in [
+            [[0., 0.], [1., 1.], [2., 0.]],
+            [[2., 0.], [1., 1.], [0., 0.]],
+            [[0., 0.], [1., -1.], [2., 0.]],
+        ] {
+            let half_edge = HalfEdge::arc_through_points(points, &mut core);
+
+            let [start, end] = half_edge.boundary().inner;
+            let middle = Point::from([(start.t + end.t) / 2.]);
+
+            let [a, b, c] = [start, middle, end]
+                .map(|point| half_edge.path().point_from_path_coords(point));
+            for (actual, expected) in [a, b, c].into_iter().zip(points) {
+                assert_points_eq!(actual, expected, 1e-14);
+            }
+        }
+    }
+}
@@ -33,6 +33,8 @@ pub use self::{
     region::BuildRegion,
     shell::{BuildShell, TetrahedronShell},
     sketch::BuildSketch,
-    solid::{BuildSolid, Tetrahedron},
+    solid::{BuildSolid, BuildSolidError, Tetrahedron},
     surface::BuildSurface,
 };
+
+pub(crate) use self::solid::closed_face;
@@ -1,4 +1,4 @@
-use fj_math::{Point, Scalar};
+use fj_math::{Circle, Point, Scalar};
 
 use crate::{
     objects::{Cycle, Region},
@@ -20,6 +20,17 @@ pub trait BuildRegion {
         Region::new(exterior, interiors)
     }
 
+    /// Build a belt that wraps around a number of pulleys
+    ///
+    /// See [`BuildCycle::belt`].
+    fn belt(
+        pulleys: impl IntoIterator<Item = Circle<2>>,
+        core: &mut Core,
+    ) -> Region {
+        let exterior = Cycle::belt(pulleys, core).insert(core);
+        Region::new(exterior, [])
+    }
+
     /// Build a circle
     fn circle(
         center: impl Into<Point<2>>,
@@ -30,6 +41,55 @@ pub trait BuildRegion {
         Region::new(exterior, [])
     }
 
+    /// Build an axis-aligned ellipse
+    fn ellipse(
+        center: impl Into<Point<2>>,
+        radii: [impl Into<Scalar>; 2],
+        core: &mut Core,
+    ) -> Region {
+        let exterior = Cycle::ellipse(center, radii, core).insert(core);
+        Region::new(exterior, [])
+    }
+
+    /// Build a regular polygon
+    ///
+    /// See [`BuildCycle::regular_polygon`].
+    fn regular_polygon(
+        num_vertices: usize,
+        radius: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Region {
+        let exterior =
+            Cycle::regular_polygon(num_vertices, radius, core).insert(core);
+        Region::new(exterior, [])
+    }
+
+    /// Build a rectangle with rounded corners
+    ///
+    /// See [`BuildCycle::rounded_rectangle`].
+    fn rounded_rectangle(
+        width: impl Into<Scalar>,
+        height: impl Into<Scalar>,
+        radius: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Region {
+        let exterior =
+            Cycle::rounded_rectangle(width, height, radius, core).insert(core);
+        Region::new(exterior, [])
+    }
+
+    /// Build a slot
+    ///
+    /// See [`BuildCycle::slot`].
+    fn slot(
+        length: impl Into<Scalar>,
+        width: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Region {
+        let exterior = Cycle::slot(length, width, core).insert(core);
+        Region::new(exterior, [])
+    }
+
     /// Build a polygon
     fn polygon<P, Ps>(points: Ps, core: &mut Core) -> Region
     where
@@ -43,3 +103,77 @@ pub trait BuildRegion {
 }
 
 impl BuildRegion for Region {}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use fj_math::{Circle, Scalar};
+    use fj_test_utils::{assert_volume_eq, assert_watertight};
+
+    use crate::{
+        algorithms::{approx::Tolerance, triangulate::Triangulate},
+        objects::{Region, Sketch},
+        operations::{
+            build::{BuildRegion, BuildSketch},
+            insert::Insert,
+            sweep::SweepSketch,
+            update::UpdateSketch,
+        },
+        Core,
+    };
+
+    #[test]
+    fn profiles() -> anyhow::Result<()> {
+        let mut core = Core::new();
+        let tolerance = Tolerance::from_scalar(0.001)?;
+
+        let profiles = [
+            (
+                Region::regular_polygon(6, 1., &mut core),
+                3. * (PI / 3.).sin(),
+            ),
+            (Region::slot(4., 2., &mut core), 4. + PI),
+            (
+                Region::belt(
+                    [
+                        Circle::from_center_and_radius([0., 0.], 1.),
+                        Circle::from_center_and_radius([3., 0.], 1.),
+                    ],
+                    &mut core,
+                ),
+                6. + PI,
+            ),
+            (
+                Region::belt(
+                    [[0., 0.], [4., 0.], [0., 3.]].map(|center| {
+                        Circle::from_center_and_radius(center, 0.5)
+                    }),
+                    &mut core,
+                ),
+                12. + PI / 4.,
+            ),
+            (
+                Region::rounded_rectangle(4., 3., 0.5, &mut core),
+                12. - (4. - PI) * 0.25,
+            ),
+        ];
+
+        for (region, area) in profiles {
+            let surface = core.layers.objects.surfaces.xy_plane();
+            let solid = Sketch::empty()
+                .add_regions([region], &mut core)
+                .sweep_sketch(surface, [0., 0., 1.], &mut core)
+                .insert(&mut core);
+            core.layers.validation.take_errors()?;
+
+            // The approximation cuts off a bit of any curves.
+            let mesh = (&*solid, tolerance).triangulate(&mut core);
+            assert_watertight!(mesh);
+            assert_volume_eq!(mesh, area, 0.01);
+            assert!(mesh.volume() <= Scalar::from(area + 1e-12));
+        }
+
+        Ok(())
+    }
+}
@@ -1,7 +1,6 @@
 use std::collections::BTreeMap;
 
-use fj_interop::ext::ArrayExt;
-use fj_math::Point;
+use fj_math::{Point, Scalar};
 
 use crate::{
     geometry::CurveBoundary,
@@ -35,87 +34,119 @@ pub trait BuildShell {
         indices: impl IntoIterator<Item = [usize; 3]>,
         core: &mut Core,
     ) -> Shell {
-        let vertices = vertices
-            .into_iter()
-            .enumerate()
-            .map(|(index, position)| {
-                let vertex = Vertex::new().insert(core);
-                let position = position.into();
+        polyhedron(
+            vertices.into_iter().map(Into::into).collect(),
+            indices.into_iter().map(Vec::from),
+            core,
+        )
+    }
 
-                (index, (vertex, position))
-            })
-            .collect::<BTreeMap<_, _>>();
+    /// Build an axis-aligned box from its minimum and maximum corners
+    fn cuboid(
+        min: impl Into<Point<3>>,
+        max: impl Into<Point<3>>,
+        core: &mut Core,
+    ) -> Shell {
+        let [min, max] = [min.into(), max.into()];
 
-        let mut curves = BTreeMap::new();
+        Self::prism(
+            [
+                [min.x, min.y],
+                [max.x, min.y],
+                [max.x, max.y],
+                [min.x, max.y],
+            ],
+            min.z,
+            max.z - min.z,
+            core,
+        )
+    }
 
-        let faces = indices
-            .into_iter()
-            .map(|indices| {
-                let [(a, a_pos), (b, b_pos), (c, c_pos)] = indices
-                    .map(|index| vertices.get(&index).expect("Invalid index"));
+    /// Build a right prism from a polygon
+    ///
+    /// The polygon is defined in the plane parallel to the xy-plane, at the
+    /// provided `z` coordinate. It must be counter-clockwise, when viewed from
+    /// above (from positive z). The prism extends upward from there, by
+    /// `height`, which must be positive.
+    fn prism(
+        polygon: impl IntoIterator<Item = impl Into<Point<2>>>,
+        z: impl Into<Scalar>,
+        height: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Shell {
+        Self::frustum(polygon, z, height, Scalar::ONE, core)
+    }
 
-                let (surface, _) = Surface::plane_from_points(
-                    [a_pos, b_pos, c_pos].map(Clone::clone),
-                    core,
-                );
-
-                let curves_and_boundaries =
-                    [[a, b], [b, c], [c, a]].map(|vertices| {
-                        let vertices = vertices.map(Clone::clone);
-                        let vertices = CurveBoundary::<Vertex>::from(vertices);
-
-                        curves
-                            .get(&vertices.clone().reverse())
-                            .cloned()
-                            .unwrap_or_else(|| {
-                                let curve = Curve::new().insert(core);
-                                let boundary =
-                                    CurveBoundary::<Point<1>>::from([
-                                        [0.],
-                                        [1.],
-                                    ]);
-
-                                curves.insert(
-                                    vertices,
-                                    (curve.clone(), boundary),
-                                );
-
-                                (curve, boundary.reverse())
-                            })
-                    });
-
-                let half_edges = {
-                    let vertices = [a, b, c].map(Clone::clone);
-                    let [a, b, c] = [[0., 0.], [1., 0.], [0., 1.]];
-                    vertices
-                        .zip_ext([[a, b], [b, c], [c, a]])
-                        .zip_ext(curves_and_boundaries)
-                        .map(|((vertex, positions), (curve, boundary))| {
-                            HalfEdge::line_segment(
-                                positions,
-                                Some(boundary.reverse().inner),
-                                core,
-                            )
-                            .update_start_vertex(|_, _| vertex, core)
-                            .update_curve(|_, _| curve, core)
-                        })
-                };
-
-                Face::unbound(surface, core).update_region(
-                    |region, core| {
-                        region.update_exterior(
-                            |cycle, core| {
-                                cycle.add_half_edges(half_edges, core)
-                            },
-                            core,
-                        )
-                    },
-                    core,
-                )
+    /// Build a pyramid from a polygonal base and an apex
+    ///
+    /// The base is defined in the plane parallel to the xy-plane, at the
+    /// provided `z` coordinate. It must be counter-clockwise, when viewed from
+    /// above (from positive z), and the apex must be above it.
+    fn pyramid(
+        base: impl IntoIterator<Item = impl Into<Point<2>>>,
+        z: impl Into<Scalar>,
+        apex: impl Into<Point<3>>,
+        core: &mut Core,
+    ) -> Shell {
+        let z = z.into();
+
+        let mut vertices = base
+            .into_iter()
+            .map(|point| {
+                let point = point.into();
+                Point::from([point.u, point.v, z])
             })
             .collect::<Vec<_>>();
+        let n = vertices.len();
+        vertices.push(apex.into());
+
+        let bottom = (0..n).rev().collect();
+        let sides = (0..n).map(|i| vec![i, (i + 1) % n, n]);
+
+        polyhedron(vertices, [bottom].into_iter().chain(sides), core)
+    }
+
+    /// Build a frustum from a polygonal base
+    ///
+    /// The base is defined in the plane parallel to the xy-plane, at the
+    /// provided `z` coordinate. It must be counter-clockwise, when viewed from
+    /// above (from positive z). The top face is the base, scaled by
+    /// `top_scale` around the z-axis and moved upward by `height`, which must
+    /// be positive.
+    ///
+    /// A `top_scale` of `1` results in a prism. To build a pyramid instead
+    /// (which would correspond to a `top_scale` of `0`), use
+    /// [`BuildShell::pyramid`].
+    fn frustum(
+        base: impl IntoIterator<Item = impl Into<Point<2>>>,
+        z: impl Into<Scalar>,
+        height: impl Into<Scalar>,
+        top_scale: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Shell {
+        let [z, height, top_scale] =
+            [z.into(), height.into(), top_scale.into()];
+
+        let base = base.into_iter().map(Into::into).collect::<Vec<_>>();
+        let n = base.len();
+
+        let vertices = base
+            .iter()
+            .map(|point| Point::from([point.u, point.v, z]))
+            .chain(base.iter().map(|point| {
+                let point = point.coords * top_scale;
+                Point::from([point.u, point.v, z + height])
+            }))
+            .collect();
+
+        let bottom = (0..n).rev().collect();
+        let top = (n..2 * n).collect();
+        let sides = (0..n).map(|i| {
+            let j = (i + 1) % n;
+            vec![i, j, n + j, n + i]
+        });
 
-        Shell::empty().add_faces(faces, core)
+        polyhedron(vertices, [bottom, top].into_iter().chain(sides), core)
     }
 
     /// Build a tetrahedron from the provided points
@@ -280,6 +311,98 @@ pub trait BuildShell {
 
 impl BuildShell for Shell {}
 
+/// Build a polyhedron from its vertices and the polygons that form its faces
+///
+/// Each face is specified by the indices of its vertices, which must be
+/// coplanar and counter-clockwise, when viewed from outside of the polyhedron.
+/// Faces that share an edge share the respective half-edges' curves and
+/// vertices, so they are correctly connected.
+fn polyhedron(
+    vertices: Vec<Point<3>>,
+    faces: impl IntoIterator<Item = Vec<usize>>,
+    core: &mut Core,
+) -> Shell {
+    let vertices = vertices
+        .into_iter()
+        .map(|position| (Vertex::new().insert(core), position))
+        .collect::<Vec<_>>();
+
+    let mut curves = BTreeMap::new();
+
+    let faces = faces
+        .into_iter()
+        .map(|indices| {
+            let face_vertices = indices
+                .iter()
+                .map(|&index| vertices.get(index).expect("Invalid index"))
+                .collect::<Vec<_>>();
+
+            let [(_, a), (_, b), (_, c)] = face_vertices[..3] else {
+                panic!("Face must have at least three vertices");
+            };
+            let (surface, points_surface) =
+                Surface::plane_from_points([*a, *b, *c], core);
+
+            let positions = face_vertices
+                .iter()
+                .enumerate()
+                .map(|(i, (_, position))| {
+                    points_surface.get(i).copied().unwrap_or_else(|| {
+                        surface.geometry().project_global_point(*position)
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            let half_edges = (0..face_vertices.len())
+                .map(|i| {
+                    let j = (i + 1) % face_vertices.len();
+
+                    let vertex = face_vertices[i].0.clone();
+                    let boundary_vertices = CurveBoundary::<Vertex>::from(
+                        [i, j].map(|k| face_vertices[k].0.clone()),
+                    );
+
+                    let (curve, boundary) = curves
+                        .get(&boundary_vertices.clone().reverse())
+                        .cloned()
+                        .unwrap_or_else(|| {
+                            let curve = Curve::new().insert(core);
+                            let boundary =
+                                CurveBoundary::<Point<1>>::from([[0.], [1.]]);
+
+                            curves.insert(
+                                boundary_vertices,
+                                (curve.clone(), boundary),
+                            );
+
+                            (curve, boundary.reverse())
+                        });
+
+                    HalfEdge::line_segment(
+                        [positions[i], positions[j]],
+                        Some(boundary.reverse().inner),
+                        core,
+                    )
+                    .update_start_vertex(|_, _| vertex, core)
+                    .update_curve(|_, _| curve, core)
+                })
+                .collect::<Vec<_>>();
+
+            Face::unbound(surface, core).update_region(
+                |region, core| {
+                    region.update_exterior(
+                        |cycle, core| cycle.add_half_edges(half_edges, core),
+                        core,
+                    )
+                },
+                core,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    Shell::empty().add_faces(faces, core)
+}
+
 /// A tetrahedron
 ///
 /// A tetrahedron is constructed from 4 points and has 4 faces. For the purpose
@@ -303,3 +426,75 @@ pub struct TetrahedronShell<I: IsInserted = IsInsertedNo> {
     /// The face formed by the points `c`, `b`, and `d`.
     pub cbd: Polygon<3, IsInsertedYes>,
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        objects::Shell,
+        operations::{build::BuildShell, insert::Insert},
+        queries::Stats,
+        Core,
+    };
+
+    #[test]
+    fn cuboid() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let cuboid = Shell::cuboid([-1., -2., -3.], [1., 2., 3.], &mut core)
+            .insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        let stats = cuboid.stats();
+        assert_eq!(stats.faces, 6);
+        assert_eq!(stats.curves, 12);
+        assert_eq!(stats.vertices, 8);
+
+        Ok(())
+    }
+
+    #[test]
+    fn prism() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let prism = Shell::prism(
+            [[0., 0.], [2., 0.], [3., 1.], [1., 2.], [-1., 1.]],
+            1.,
+            2.,
+            &mut core,
+        )
+        .insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        let stats = prism.stats();
+        assert_eq!(stats.faces, 7);
+        assert_eq!(stats.curves, 15);
+        assert_eq!(stats.vertices, 10);
+
+        Ok(())
+    }
+
+    #[test]
+    fn pyramid_and_frustum() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let square = [[-1., -1.], [1., -1.], [1., 1.], [-1., 1.]];
+
+        let pyramid = Shell::pyramid(square, 0., [0.5, 0., 2.], &mut core)
+            .insert(&mut core);
+        let frustum =
+            Shell::frustum(square, 0., 1., 0.5, &mut core).insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        let stats = pyramid.stats();
+        assert_eq!(stats.faces, 5);
+        assert_eq!(stats.curves, 8);
+        assert_eq!(stats.vertices, 5);
+
+        let stats = frustum.stats();
+        assert_eq!(stats.faces, 6);
+        assert_eq!(stats.curves, 12);
+        assert_eq!(stats.vertices, 8);
+
+        Ok(())
+    }
+}
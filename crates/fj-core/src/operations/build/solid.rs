@@ -1,12 +1,23 @@
-use fj_math::Point;
+use std::f64::consts::FRAC_1_SQRT_2;
+
+use fj_math::{NurbsSurface, Point, Scalar};
 
 use crate::{
-    objects::{Shell, Solid},
+    geometry::{CurveBoundary, SurfacePath},
+    objects::{
+        Curve, Cycle, Face, HalfEdge, Region, Shell, Sketch, Solid, Surface,
+        Vertex,
+    },
     operations::{
-        build::{BuildShell, TetrahedronShell},
+        build::{
+            BuildRegion, BuildShell, BuildSketch, BuildSurface,
+            TetrahedronShell,
+        },
         insert::{Insert, IsInsertedYes},
-        update::UpdateSolid,
+        sweep::SweepSketch,
+        update::{UpdateSketch, UpdateSolid},
     },
+    storage::Handle,
     Core,
 };
 
@@ -21,6 +32,165 @@ pub trait BuildSolid {
         Solid::new([])
     }
 
+    /// Build a cone
+    ///
+    /// The cone stands on the xy-plane, centered on the z-axis. Its bottom has
+    /// the radius `r1`, its top has the radius `r2`, at a height of `h`. One of
+    /// the radii may be zero, to build a pointed cone. Otherwise, the result
+    /// is a truncated cone.
+    ///
+    /// Returns an error, if a radius is negative, if both radii are zero, or if
+    /// the height is not positive.
+    fn cone(
+        r1: impl Into<Scalar>,
+        r2: impl Into<Scalar>,
+        h: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Result<Solid, BuildSolidError> {
+        let [r1, r2, h] = [r1.into(), r2.into(), h.into()];
+        for radius in [r1, r2] {
+            if radius < Scalar::ZERO {
+                return Err(BuildSolidError::NegativeRadius { radius });
+            }
+        }
+        if r1 == Scalar::ZERO && r2 == Scalar::ZERO {
+            return Err(BuildSolidError::ConeWithoutRadius);
+        }
+        positive("height", h)?;
+
+        let [bottom, top] =
+            [Curve::new(), Curve::new()].map(|c| c.insert(core));
+        let [bottom_vertex, top_vertex] =
+            [Vertex::new(), Vertex::new()].map(|v| v.insert(core));
+
+        let mut faces = vec![closed_face(
+            Profile::line([r1, Scalar::ZERO], [r2, h]).revolve(),
+            [bottom.clone(), top.clone()],
+            [bottom_vertex.clone(), top_vertex.clone()],
+            core,
+        )];
+
+        // A radius of zero means the cone ends in a point there. Otherwise, it
+        // is closed by a disk.
+        if r1 > Scalar::ZERO {
+            let center = Vertex::new().insert(core);
+            let degenerate = Curve::new().insert(core);
+            faces.push(closed_face(
+                Profile::line([Scalar::ZERO, Scalar::ZERO], [r1, Scalar::ZERO])
+                    .revolve(),
+                [degenerate, bottom],
+                [center, bottom_vertex],
+                core,
+            ));
+        }
+        if r2 > Scalar::ZERO {
+            let center = Vertex::new().insert(core);
+            let degenerate = Curve::new().insert(core);
+            faces.push(closed_face(
+                Profile::line([r2, h], [Scalar::ZERO, h]).revolve(),
+                [top, degenerate],
+                [top_vertex, center],
+                core,
+            ));
+        }
+
+        let shell = Shell::new(faces).insert(core);
+        Ok(Solid::empty().add_shells([shell], core))
+    }
+
+    /// Build a cylinder
+    ///
+    /// The cylinder stands on the xy-plane, centered on the z-axis.
+    ///
+    /// Returns an error, if the radius or the height are not positive.
+    fn cylinder(
+        radius: impl Into<Scalar>,
+        height: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Result<Solid, BuildSolidError> {
+        let [radius, height] = [radius.into(), height.into()];
+        positive("radius", radius)?;
+        positive("height", height)?;
+
+        let surface = core.layers.objects.surfaces.xy_plane();
+        Ok(Sketch::empty()
+            .add_regions([Region::circle([0., 0.], radius, core)], core)
+            .sweep_sketch(surface, [Scalar::ZERO, Scalar::ZERO, height], core))
+    }
+
+    /// Build a sphere
+    ///
+    /// The sphere is centered on the origin.
+    ///
+    /// Returns an error, if the radius is not positive.
+    fn sphere(
+        radius: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Result<Solid, BuildSolidError> {
+        let r = radius.into();
+        positive("radius", r)?;
+
+        // The poles of the sphere are the bottom and top of its face. Both
+        // collapse into a single point.
+        let poles = [Curve::new(), Curve::new()].map(|c| c.insert(core));
+        let vertices = [Vertex::new(), Vertex::new()].map(|v| v.insert(core));
+
+        let face = closed_face(
+            Profile::arc([[Scalar::ZERO, -r], [r, -r], [r, Scalar::ZERO]])
+                .then_arc([[r, r], [Scalar::ZERO, r]])
+                .revolve(),
+            poles,
+            vertices,
+            core,
+        );
+
+        let shell = Shell::new([face]).insert(core);
+        Ok(Solid::empty().add_shells([shell], core))
+    }
+
+    /// Build a torus
+    ///
+    /// The torus is centered on the origin, and lies in the xy-plane.
+    /// `major_radius` is the distance between the origin and the center of the
+    /// tube, `minor_radius` is the radius of the tube.
+    ///
+    /// Returns an error, if any radius is not positive, or if `minor_radius`
+    /// is not smaller than `major_radius`.
+    fn torus(
+        major_radius: impl Into<Scalar>,
+        minor_radius: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Result<Solid, BuildSolidError> {
+        let [big_r, r] = [major_radius.into(), minor_radius.into()];
+        positive("minor radius", r)?;
+        if r >= big_r {
+            return Err(BuildSolidError::TorusSelfIntersects {
+                major_radius: big_r,
+                minor_radius: r,
+            });
+        }
+
+        // The tube is closed, so the bottom and top of the face are the same
+        // circle.
+        let curve = Curve::new().insert(core);
+        let vertex = Vertex::new().insert(core);
+
+        let [inner, center, outer] = [big_r - r, big_r, big_r + r];
+        let face = closed_face(
+            Profile::arc([[outer, Scalar::ZERO], [outer, r], [center, r]])
+                .then_arc([[inner, r], [inner, Scalar::ZERO]])
+                .then_arc([[inner, -r], [center, -r]])
+                .then_arc([[outer, -r], [outer, Scalar::ZERO]])
+                .revolve(),
+            [curve.clone(), curve],
+            [vertex.clone(), vertex],
+            core,
+        );
+
+        let shell = Shell::new([face]).insert(core);
+        Ok(Solid::empty().add_shells([shell], core))
+    }
+
     /// Build a tetrahedron from the provided points
     ///
     /// See [`BuildShell::tetrahedron`] for more information.
@@ -47,3 +217,270 @@ pub struct Tetrahedron {
     /// The shell of the tetrahedron
     pub shell: TetrahedronShell<IsInsertedYes>,
 }
+
+/// Error building a [`Solid`]
+///
+/// See [`BuildSolid`].
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum BuildSolidError {
+    /// A dimension of the solid is not positive
+    #[error("The {name} of the solid must be positive: {value}")]
+    NotPositive {
+        /// The name of the dimension
+        name: &'static str,
+
+        /// The value of the dimension
+        value: Scalar,
+    },
+
+    /// A radius of a cone is negative
+    #[error("Radius of cone must not be negative: {radius}")]
+    NegativeRadius {
+        /// The radius
+        radius: Scalar,
+    },
+
+    /// Both radii of a cone are zero
+    #[error("At least one radius of cone must be positive")]
+    ConeWithoutRadius,
+
+    /// The minor radius of a torus is not smaller than its major radius
+    #[error(
+        "Minor radius {minor_radius} of torus must be smaller than major \
+        radius {major_radius}"
+    )]
+    TorusSelfIntersects {
+        /// The major radius
+        major_radius: Scalar,
+
+        /// The minor radius
+        minor_radius: Scalar,
+    },
+}
+
+fn positive(name: &'static str, value: Scalar) -> Result<(), BuildSolidError> {
+    if value <= Scalar::ZERO {
+        return Err(BuildSolidError::NotPositive { name, value });
+    }
+
+    Ok(())
+}
+
+/// A profile that is revolved around the z-axis
+///
+/// The profile is a NURBS curve in the plane spanned by the distance from the
+/// z-axis and the z-axis itself. It consists of segments that are either
+/// straight lines or quarter circles.
+struct Profile {
+    points: Vec<([Scalar; 2], Scalar)>,
+    degree: usize,
+    knots: Vec<Scalar>,
+}
+
+impl Profile {
+    fn line(start: [Scalar; 2], end: [Scalar; 2]) -> Self {
+        Self {
+            points: vec![(start, Scalar::ONE), (end, Scalar::ONE)],
+            degree: 1,
+            knots: [0., 0., 1., 1.].map(Scalar::from).to_vec(),
+        }
+    }
+
+    /// Start a profile with a quarter circle
+    ///
+    /// The arc is defined by its start, the corner of the square that it is
+    /// inscribed in, and its end.
+    fn arc([start, corner, end]: [[Scalar; 2]; 3]) -> Self {
+        Self {
+            points: vec![
+                (start, Scalar::ONE),
+                (corner, Scalar::from(FRAC_1_SQRT_2)),
+                (end, Scalar::ONE),
+            ],
+            degree: 2,
+            knots: [0., 0., 0., 1., 1., 1.].map(Scalar::from).to_vec(),
+        }
+    }
+
+    /// Continue the profile with another quarter circle
+    ///
+    /// The arc starts where the profile currently ends.
+    fn then_arc(mut self, [corner, end]: [[Scalar; 2]; 2]) -> Self {
+        self.points.push((corner, Scalar::from(FRAC_1_SQRT_2)));
+        self.points.push((end, Scalar::ONE));
+
+        // Each arc spans a unit of the parameter. Inserting the knot twice
+        // makes it an end point of both arcs.
+        let last = self.knots[self.knots.len() - 1];
+        let next = last + Scalar::ONE;
+        self.knots.truncate(self.knots.len() - self.degree - 1);
+        self.knots.extend([last, last]);
+        self.knots.extend([next; 3]);
+
+        self
+    }
+
+    /// Revolve the profile into a NURBS surface, with normalized parameters
+    ///
+    /// The surface parameter `u` runs once around the z-axis, counter-clockwise
+    /// when looking down on the xy-plane. The parameter `v` runs along the
+    /// profile.
+    fn revolve(&self) -> NurbsSurface {
+        // A full unit circle, made up of quarter circles.
+        let point = |point: [f64; 2]| point.map(Scalar::from);
+        let circle = Profile::arc([[1., 0.], [1., 1.], [0., 1.]].map(point))
+            .then_arc([[-1., 1.], [-1., 0.]].map(point))
+            .then_arc([[-1., -1.], [0., -1.]].map(point))
+            .then_arc([[1., -1.], [1., 0.]].map(point));
+
+        let control_points = circle.points.iter().map(|&([x, y], _)| {
+            self.points
+                .iter()
+                .map(move |&([radius, z], _)| [x * radius, y * radius, z])
+        });
+        let weights = circle.points.iter().map(|&(_, circle_weight)| {
+            self.points
+                .iter()
+                .map(move |&(_, weight)| circle_weight * weight)
+        });
+
+        let knots = [&circle.knots, &self.knots].map(|knots| {
+            let max = knots[knots.len() - 1];
+            knots.iter().map(|&knot| knot / max).collect()
+        });
+
+        NurbsSurface::new(
+            control_points,
+            weights,
+            [circle.degree, self.degree],
+            knots,
+        )
+    }
+}
+
+/// Build a face that covers a closed NURBS surface completely
+///
+/// The surface must have normalized parameters, and must close onto itself
+/// along `u`, like a surface of revolution does. The face is bounded by the
+/// four sides of its parameter domain. Its left and right sides are the seam
+/// where the surface closes, and are siblings within the face.
+///
+/// The bottom side (where `v` is `0`) lies on the first of the provided curves
+/// and starts and ends at the first of the provided vertices. The top side
+/// lies on the second curve, and starts and ends at the second vertex.
+pub(crate) fn closed_face(
+    surface: NurbsSurface,
+    [bottom, top]: [Handle<Curve>; 2],
+    [bottom_vertex, top_vertex]: [Handle<Vertex>; 2],
+    core: &mut Core,
+) -> Handle<Face> {
+    let surface = Surface::surface_from_nurbs(surface, core);
+    let seam = Curve::new().insert(core);
+
+    let side = |start: [f64; 2], end: [f64; 2], boundary: [f64; 2]| {
+        let path = SurfacePath::line_from_points_with_coords([
+            ([boundary[0]], start),
+            ([boundary[1]], end),
+        ]);
+        let boundary = CurveBoundary::from(boundary.map(|t| [t]));
+        (path, boundary)
+    };
+
+    let half_edges = [
+        (
+            side([0., 0.], [1., 0.], [0., 1.]),
+            bottom,
+            bottom_vertex.clone(),
+        ),
+        (
+            side([1., 0.], [1., 1.], [0., 1.]),
+            seam.clone(),
+            bottom_vertex,
+        ),
+        (side([1., 1.], [0., 1.], [1., 0.]), top, top_vertex.clone()),
+        (side([0., 1.], [0., 0.], [1., 0.]), seam, top_vertex),
+    ]
+    .map(|((path, boundary), curve, start_vertex)| {
+        HalfEdge::new(path, boundary, curve, start_vertex).insert(core)
+    });
+
+    let exterior = Cycle::new(half_edges).insert(core);
+    let region = Region::new(exterior, []).insert(core);
+
+    Face::new(surface, region).insert(core)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use fj_test_utils::{assert_volume_eq, assert_watertight};
+
+    use crate::{
+        algorithms::{approx::Tolerance, triangulate::Triangulate},
+        objects::Solid,
+        operations::insert::Insert,
+        Core,
+    };
+
+    use super::{BuildSolid, BuildSolidError};
+
+    #[test]
+    fn primitives() -> anyhow::Result<()> {
+        let mut core = Core::new();
+        let tolerance = Tolerance::from_scalar(0.02)?;
+
+        // The triangulations of cones are not watertight. Where a line in the
+        // profile meets the axis, they contain zero-area triangles that don't
+        // share edges with their neighbors.
+        let primitives = [
+            (Solid::sphere(1., &mut core)?, 4. / 3. * PI, true),
+            (Solid::cylinder(1., 1., &mut core)?, PI, true),
+            (Solid::cone(1., 0.5, 1., &mut core)?, PI * 1.75 / 3., false),
+            (Solid::cone(1., 0., 1., &mut core)?, PI / 3., false),
+            (Solid::cone(0., 1., 1., &mut core)?, PI / 3., false),
+            (
+                Solid::torus(1., 0.25, &mut core)?,
+                2. * PI * PI * 0.0625,
+                true,
+            ),
+        ];
+
+        for (solid, volume_expected, watertight) in primitives {
+            let solid = solid.insert(&mut core);
+            core.layers.validation.take_errors()?;
+
+            let mesh = (&*solid, tolerance).triangulate(&mut core);
+            if watertight {
+                assert_watertight!(mesh);
+            }
+
+            // The approximation cuts off a bit of any curves.
+            assert_volume_eq!(mesh, volume_expected, volume_expected * 0.05);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_primitives() {
+        let mut core = Core::new();
+
+        assert!(matches!(
+            Solid::sphere(0., &mut core),
+            Err(BuildSolidError::NotPositive { .. })
+        ));
+        assert!(matches!(
+            Solid::cone(-1., 1., 1., &mut core),
+            Err(BuildSolidError::NegativeRadius { .. })
+        ));
+        assert!(matches!(
+            Solid::cone(0., 0., 1., &mut core),
+            Err(BuildSolidError::ConeWithoutRadius)
+        ));
+        assert!(matches!(
+            Solid::torus(1., 1., &mut core),
+            Err(BuildSolidError::TorusSelfIntersects { .. })
+        ));
+    }
+}
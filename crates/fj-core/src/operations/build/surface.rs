@@ -1,4 +1,4 @@
-use fj_math::{Point, Scalar, Vector};
+use fj_math::{NurbsSurface, Point, Scalar, Vector};
 
 use crate::{
     geometry::{GlobalPath, SurfaceGeometry},
@@ -43,11 +43,29 @@ pub trait BuildSurface {
         v: impl Into<Vector<3>>,
         core: &mut Core,
     ) -> Handle<Surface> {
-        let geometry = SurfaceGeometry {
+        let geometry = SurfaceGeometry::Swept {
             u: u.into(),
             v: v.into(),
         };
-        let surface = Surface::new(geometry).insert(core);
+        let surface = Surface::new(geometry.clone()).insert(core);
+
+        core.layers
+            .geometry
+            .define_surface(surface.clone(), geometry);
+
+        surface
+    }
+
+    /// Build a surface from the provided NURBS surface
+    ///
+    /// The surface coordinates of the new surface are the parameters of the
+    /// NURBS surface.
+    fn surface_from_nurbs(
+        nurbs: NurbsSurface,
+        core: &mut Core,
+    ) -> Handle<Surface> {
+        let geometry = SurfaceGeometry::Nurbs(nurbs);
+        let surface = Surface::new(geometry.clone()).insert(core);
 
         core.layers
             .geometry
@@ -0,0 +1,668 @@
+//! Bevel the edges of shapes
+//!
+//! See [`ChamferEdge`].
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+
+use fj_interop::ext::ArrayExt;
+use fj_math::{Point, Scalar, Vector};
+
+use crate::{
+    geometry::{CurveBoundary, GlobalPath, SurfaceGeometry, SurfacePath},
+    objects::{
+        Curve, Cycle, Face, HalfEdge, Region, Shell, Solid, Surface, Vertex,
+    },
+    queries::{BoundingVerticesOfHalfEdge, SiblingOfHalfEdge},
+    storage::Handle,
+    Core,
+};
+
+use super::{
+    build::BuildSurface,
+    derive::DeriveFrom,
+    insert::Insert,
+    presentation::{GetColor, SetColor},
+    update::UpdateSolid,
+};
+
+/// Replace edges of a shape with flat bevel faces
+pub trait ChamferEdge: Sized {
+    /// Chamfer the provided edges
+    ///
+    /// Each edge is identified by either of its half-edges. It is cut off by a
+    /// new planar face, which meets each of the two faces adjacent to the edge
+    /// along a line parallel to it. The [`ChamferDistances`] define how far
+    /// those lines are from the edge.
+    ///
+    /// The faces adjacent to the edge, as well as the faces at its ends, must
+    /// be planar, and the edges that meet the edge must be line segments. Each
+    /// vertex of the edge must be shared by exactly three faces, and the
+    /// chamfered edges must not share any vertices.
+    fn chamfer_edges<'r>(
+        &self,
+        edges: impl IntoIterator<Item = &'r Handle<HalfEdge>>,
+        distances: impl Into<ChamferDistances>,
+        core: &mut Core,
+    ) -> Result<Self, ChamferEdgeError>;
+}
+
+impl ChamferEdge for Shell {
+    fn chamfer_edges<'r>(
+        &self,
+        edges: impl IntoIterator<Item = &'r Handle<HalfEdge>>,
+        distances: impl Into<ChamferDistances>,
+        core: &mut Core,
+    ) -> Result<Self, ChamferEdgeError> {
+        let edges = edges.into_iter().collect::<Vec<_>>();
+        let distances = distances.into();
+
+        let inputs: &[&dyn fmt::Debug] = &[self, &edges, &distances];
+        core.trace_operation("chamfer_edges", inputs, |core| {
+            for distance in distances.0 {
+                if distance <= Scalar::ZERO {
+                    return Err(ChamferEdgeError::DistanceNotPositive {
+                        distance,
+                    });
+                }
+            }
+
+            // Chamfering an edge cuts off the corners at both of its ends.
+            // Edges that share a corner would have to be chamfered together,
+            // which is not supported.
+            let mut vertices = BTreeSet::new();
+            for edge in &edges {
+                let bounding_vertices = self
+                    .bounding_vertices_of_half_edge(edge)
+                    .ok_or_else(|| ChamferEdgeError::EdgeNotFound {
+                        half_edge: (*edge).clone(),
+                    })?;
+
+                for vertex in bounding_vertices.inner {
+                    if !vertices.insert(vertex.clone()) {
+                        return Err(ChamferEdgeError::EdgesShareVertex {
+                            vertex: vertex.into(),
+                        });
+                    }
+                }
+            }
+
+            // Since the edges don't share any vertices, chamfering one of them
+            // doesn't replace the half-edges of any of the others.
+            let mut shell = self.clone();
+            for edge in &edges {
+                shell = chamfer_edge(&shell, edge, distances, core)?;
+            }
+
+            Ok(shell)
+        })
+    }
+}
+
+impl ChamferEdge for Solid {
+    fn chamfer_edges<'r>(
+        &self,
+        edges: impl IntoIterator<Item = &'r Handle<HalfEdge>>,
+        distances: impl Into<ChamferDistances>,
+        core: &mut Core,
+    ) -> Result<Self, ChamferEdgeError> {
+        let mut edges = edges.into_iter().collect::<Vec<_>>();
+        let distances = distances.into();
+
+        let mut solid = self.clone();
+
+        for shell in self.shells() {
+            let (edges_of_shell, rest): (Vec<_>, _) =
+                edges.into_iter().partition(|edge| {
+                    shell.bounding_vertices_of_half_edge(edge).is_some()
+                });
+            edges = rest;
+
+            if edges_of_shell.is_empty() {
+                continue;
+            }
+
+            let chamfered =
+                shell.chamfer_edges(edges_of_shell, distances, core)?;
+            solid = solid.update_shell(shell, |_, _| [chamfered], core);
+        }
+
+        if let Some(edge) = edges.first() {
+            return Err(ChamferEdgeError::EdgeNotFound {
+                half_edge: (*edge).clone(),
+            });
+        }
+
+        Ok(solid)
+    }
+}
+
+/// The distances of a chamfer from the chamfered edge
+///
+/// The first distance is measured within the face of the half-edge that
+/// identifies the edge, the second one within the face of its sibling. A
+/// single distance converts into a symmetric chamfer.
+///
+/// See [`ChamferEdge::chamfer_edges`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ChamferDistances(pub [Scalar; 2]);
+
+impl From<Scalar> for ChamferDistances {
+    fn from(distance: Scalar) -> Self {
+        Self([distance; 2])
+    }
+}
+
+impl From<f64> for ChamferDistances {
+    fn from(distance: f64) -> Self {
+        Self::from(Scalar::from(distance))
+    }
+}
+
+impl From<[Scalar; 2]> for ChamferDistances {
+    fn from(distances: [Scalar; 2]) -> Self {
+        Self(distances)
+    }
+}
+
+impl From<[f64; 2]> for ChamferDistances {
+    fn from(distances: [f64; 2]) -> Self {
+        Self(distances.map(Scalar::from))
+    }
+}
+
+fn chamfer_edge(
+    shell: &Shell,
+    half_edge: &Handle<HalfEdge>,
+    ChamferDistances([distance_a, distance_b]): ChamferDistances,
+    core: &mut Core,
+) -> Result<Shell, ChamferEdgeError> {
+    let edge_not_found = || ChamferEdgeError::EdgeNotFound {
+        half_edge: half_edge.clone(),
+    };
+
+    // The edge goes from `x` to `y` in face `a`, and the other way around in
+    // face `b`. Faces `c_x` and `c_y` are the faces at either end of it.
+    let a = Neighborhood::of(shell, half_edge).ok_or_else(edge_not_found)?;
+    let b = shell
+        .get_sibling_of(half_edge)
+        .and_then(|sibling| Neighborhood::of(shell, &sibling))
+        .ok_or_else(edge_not_found)?;
+    let c_x = Neighborhood::at_corner(shell, &a.prev, &b.next)?;
+    let c_y = Neighborhood::at_corner(shell, &b.prev, &a.next)?;
+
+    for face in [&a.face, &b.face, &c_x.face, &c_y.face] {
+        let SurfaceGeometry::Swept {
+            u: GlobalPath::Line(_),
+            ..
+        } = face.surface().geometry()
+        else {
+            return Err(ChamferEdgeError::SurfaceNotAPlane {
+                face: face.clone(),
+            });
+        };
+    }
+    for half_edge in [&a.prev, &a.half_edge, &a.next, &b.prev, &b.next] {
+        let SurfacePath::Line(_) = half_edge.path() else {
+            return Err(ChamferEdgeError::HalfEdgeNotALine {
+                half_edge: half_edge.clone(),
+            });
+        };
+    }
+
+    let [surface_a, surface_b] =
+        [&a.face, &b.face].map(|face| face.surface().geometry());
+    let direction = {
+        let [x, y] = [&a.half_edge, &a.next].map(|half_edge| {
+            surface_a.point_from_surface_coords(half_edge.start_position())
+        });
+        (y - x).normalize()
+    };
+
+    // The fractions of the half-edges next to the edge, that the chamfer cuts
+    // off. Siblings are cut at the same point.
+    let a_x = fraction(&a.prev, 1, surface_a, direction, distance_a)?;
+    let a_y = fraction(&a.next, 0, surface_a, direction, distance_a)?;
+    let b_x = fraction(&b.next, 0, surface_b, direction, distance_b)?;
+    let b_y = fraction(&b.prev, 1, surface_b, direction, distance_b)?;
+
+    let [vertex_a_x, vertex_a_y, vertex_b_x, vertex_b_y] =
+        [(); 4].map(|()| Vertex::new().insert(core));
+
+    let (a_prev, a_x_in_a) = cut(&a.prev, 1, a_x, a.prev.start_vertex(), core);
+    let (a_next, a_y_in_a) = cut(&a.next, 0, a_y, &vertex_a_y, core);
+    let (b_prev, b_y_in_b) = cut(&b.prev, 1, b_y, b.prev.start_vertex(), core);
+    let (b_next, b_x_in_b) = cut(&b.next, 0, b_x, &vertex_b_x, core);
+    let (c_x_prev, b_x_in_c_x) =
+        cut(&c_x.prev, 1, b_x, c_x.prev.start_vertex(), core);
+    let (c_x_half_edge, a_x_in_c_x) =
+        cut(&c_x.half_edge, 0, a_x, &vertex_a_x, core);
+    let (c_y_prev, a_y_in_c_y) =
+        cut(&c_y.prev, 1, a_y, c_y.prev.start_vertex(), core);
+    let (c_y_half_edge, b_y_in_c_y) =
+        cut(&c_y.half_edge, 0, b_y, &vertex_b_y, core);
+
+    let [a_x_3d, a_y_3d] = [a_x_in_a, a_y_in_a]
+        .map(|point| surface_a.point_from_surface_coords(point));
+    let [b_x_3d, b_y_3d] = [b_x_in_b, b_y_in_b]
+        .map(|point| surface_b.point_from_surface_coords(point));
+
+    // The chamfer is a trapezoid, as its edges in faces `a` and `b` are both
+    // parallel to the chamfered edge.
+    let (surface, [a_y_in_chamfer, a_x_in_chamfer, b_x_in_chamfer]) =
+        Surface::plane_from_points([a_y_3d, a_x_3d, b_x_3d], core);
+    let b_y_in_chamfer = {
+        let edge_a = a_x_3d - a_y_3d;
+        let t = (b_y_3d - b_x_3d).dot(&edge_a) / edge_a.dot(&edge_a);
+        b_x_in_chamfer + (a_x_in_chamfer - a_y_in_chamfer) * t
+    };
+
+    let [edge_a, chamfer_a] = edge(
+        [a_x_in_a, a_y_in_a],
+        [a_x_in_chamfer, a_y_in_chamfer],
+        [&vertex_a_x, &vertex_a_y],
+        core,
+    );
+    let [edge_b, chamfer_b] = edge(
+        [b_y_in_b, b_x_in_b],
+        [b_y_in_chamfer, b_x_in_chamfer],
+        [&vertex_b_y, &vertex_b_x],
+        core,
+    );
+    let [edge_c_x, chamfer_c_x] = edge(
+        [b_x_in_c_x, a_x_in_c_x],
+        [b_x_in_chamfer, a_x_in_chamfer],
+        [&vertex_b_x, &vertex_a_x],
+        core,
+    );
+    let [edge_c_y, chamfer_c_y] = edge(
+        [a_y_in_c_y, b_y_in_c_y],
+        [a_y_in_chamfer, b_y_in_chamfer],
+        [&vertex_a_y, &vertex_b_y],
+        core,
+    );
+
+    let replacements = BTreeMap::from([
+        (a.prev, vec![a_prev]),
+        (a.half_edge, vec![edge_a]),
+        (a.next, vec![a_next]),
+        (b.prev, vec![b_prev]),
+        (b.half_edge, vec![edge_b]),
+        (b.next, vec![b_next]),
+        (c_x.prev, vec![c_x_prev]),
+        (c_x.half_edge, vec![edge_c_x, c_x_half_edge]),
+        (c_y.prev, vec![c_y_prev]),
+        (c_y.half_edge, vec![edge_c_y, c_y_half_edge]),
+    ]);
+
+    let mut faces = Vec::new();
+
+    for face in shell.faces() {
+        let mut cycles = Vec::new();
+        let mut face_updated = false;
+
+        for cycle in face.region().all_cycles() {
+            if !cycle
+                .half_edges()
+                .iter()
+                .any(|half_edge| replacements.contains_key(half_edge))
+            {
+                cycles.push(cycle.clone());
+                continue;
+            }
+
+            let half_edges = cycle.half_edges().iter().flat_map(|half_edge| {
+                replacements
+                    .get(half_edge)
+                    .cloned()
+                    .unwrap_or_else(|| vec![half_edge.clone()])
+            });
+            cycles.push(
+                Cycle::new(half_edges).insert(core).derive_from(cycle, core),
+            );
+            face_updated = true;
+        }
+
+        if !face_updated {
+            faces.push(face.clone());
+            continue;
+        }
+
+        let mut cycles = cycles.into_iter();
+        let exterior =
+            cycles.next().expect("Region must have an exterior cycle");
+        let region = Region::new(exterior, cycles)
+            .insert(core)
+            .derive_from(face.region(), core);
+
+        faces.push(
+            Face::new(face.surface().clone(), region)
+                .insert(core)
+                .derive_from(face, core),
+        );
+    }
+
+    let chamfer = {
+        let exterior =
+            Cycle::new([chamfer_a, chamfer_c_x, chamfer_b, chamfer_c_y])
+                .insert(core);
+        let region = Region::new(exterior, []).insert(core);
+
+        if let Some(color) = a.face.region().get_color(core) {
+            region.set_color(color, core);
+        }
+
+        Face::new(surface, region).insert(core)
+    };
+    faces.push(chamfer);
+
+    Ok(Shell::new(faces))
+}
+
+/// A half-edge, the face it bounds, and its neighbors within its cycle
+struct Neighborhood {
+    face: Handle<Face>,
+    prev: Handle<HalfEdge>,
+    half_edge: Handle<HalfEdge>,
+    next: Handle<HalfEdge>,
+}
+
+impl Neighborhood {
+    fn of(shell: &Shell, half_edge: &Handle<HalfEdge>) -> Option<Self> {
+        for face in shell.faces() {
+            for cycle in face.region().all_cycles() {
+                let half_edges = cycle.half_edges();
+
+                if let Some(index) = half_edges.index_of(half_edge) {
+                    let prev = index + half_edges.len() - 1;
+
+                    return Some(Self {
+                        face: face.clone(),
+                        prev: half_edges.nth_circular(prev).clone(),
+                        half_edge: half_edge.clone(),
+                        next: half_edges.nth_circular(index + 1).clone(),
+                    });
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Find the third face at a vertex that two faces share
+    ///
+    /// Expects a half-edge that arrives at the vertex in one face, and one that
+    /// leaves it in the other face. Returns the neighborhood of the half-edge
+    /// that leaves the vertex in the third face, after making sure that no
+    /// other faces share the vertex.
+    fn at_corner(
+        shell: &Shell,
+        arriving: &Handle<HalfEdge>,
+        leaving: &Handle<HalfEdge>,
+    ) -> Result<Self, ChamferEdgeError> {
+        let unsupported = || ChamferEdgeError::UnsupportedVertex {
+            vertex: leaving.start_vertex().clone(),
+        };
+
+        let corner = shell
+            .get_sibling_of(arriving)
+            .and_then(|sibling| Self::of(shell, &sibling))
+            .ok_or_else(unsupported)?;
+
+        if !shell.are_siblings(&corner.prev, leaving) {
+            return Err(unsupported());
+        }
+
+        Ok(corner)
+    }
+}
+
+/// Compute the fraction of a half-edge that the chamfer cuts off
+///
+/// The half-edge meets the chamfered edge, which has the provided direction, at
+/// the provided end.
+fn fraction(
+    half_edge: &Handle<HalfEdge>,
+    end: usize,
+    surface: &SurfaceGeometry,
+    direction: Vector<3>,
+    distance: Scalar,
+) -> Result<Scalar, ChamferEdgeError> {
+    let points = half_edge.boundary().inner.map(|point| {
+        surface.point_from_surface_coords(
+            half_edge.path().point_from_path_coords(point),
+        )
+    });
+
+    // The distance of the half-edge's other end from the chamfered edge.
+    let edge = points[1 - end] - points[end];
+    let reach = (edge - direction * edge.dot(&direction)).magnitude();
+
+    if reach <= distance {
+        return Err(ChamferEdgeError::ChamferTooLarge {
+            half_edge: half_edge.clone(),
+        });
+    }
+
+    Ok(distance / reach)
+}
+
+/// Cut off a fraction of a half-edge at the provided end
+///
+/// Returns the shortened half-edge, and the surface position of the new end.
+fn cut(
+    half_edge: &Handle<HalfEdge>,
+    end: usize,
+    fraction: Scalar,
+    start_vertex: &Handle<Vertex>,
+    core: &mut Core,
+) -> (Handle<HalfEdge>, Point<2>) {
+    let mut boundary = half_edge.boundary().inner;
+    boundary[end] =
+        boundary[end] + (boundary[1 - end] - boundary[end]) * fraction;
+
+    let position = half_edge.path().point_from_path_coords(boundary[end]);
+
+    let half_edge = HalfEdge::new(
+        half_edge.path(),
+        boundary,
+        half_edge.curve().clone(),
+        start_vertex.clone(),
+    )
+    .insert(core)
+    .derive_from(half_edge, core);
+
+    (half_edge, position)
+}
+
+/// Create an edge between a face and the chamfer
+///
+/// Returns the half-edge in the face, and its sibling in the chamfer.
+fn edge(
+    points: [Point<2>; 2],
+    points_in_chamfer: [Point<2>; 2],
+    [start, end]: [&Handle<Vertex>; 2],
+    core: &mut Core,
+) -> [Handle<HalfEdge>; 2] {
+    let curve = Curve::new().insert(core);
+    let boundary = CurveBoundary::from([[0.], [1.]]);
+
+    let half_edge = HalfEdge::new(
+        SurfacePath::line_from_points_with_coords(
+            boundary.inner.zip_ext(points),
+        ),
+        boundary,
+        curve.clone(),
+        start.clone(),
+    )
+    .insert(core);
+    let sibling = HalfEdge::new(
+        SurfacePath::line_from_points_with_coords(
+            boundary.inner.zip_ext(points_in_chamfer),
+        ),
+        boundary.reverse(),
+        curve,
+        end.clone(),
+    )
+    .insert(core);
+
+    [half_edge, sibling]
+}
+
+/// Error chamfering edges
+///
+/// See [`ChamferEdge::chamfer_edges`].
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum ChamferEdgeError {
+    /// An edge is not part of the shape
+    #[error("Edge is not part of the shape: {half_edge:#?}")]
+    EdgeNotFound {
+        /// A half-edge of the edge
+        half_edge: Handle<HalfEdge>,
+    },
+
+    /// A distance is zero or negative
+    #[error("Chamfer distance must be positive: {distance}")]
+    DistanceNotPositive {
+        /// The distance
+        distance: Scalar,
+    },
+
+    /// Multiple edges share a vertex
+    #[error("Chamfered edges share a vertex: {vertex:#?}")]
+    EdgesShareVertex {
+        /// The vertex
+        vertex: Handle<Vertex>,
+    },
+
+    /// A vertex of an edge is not shared by exactly three faces
+    #[error(
+        "Vertex of edge is not shared by exactly three faces: {vertex:#?}"
+    )]
+    UnsupportedVertex {
+        /// The vertex
+        vertex: Handle<Vertex>,
+    },
+
+    /// A face next to an edge is not planar
+    #[error("Face next to edge is not planar: {face:#?}")]
+    SurfaceNotAPlane {
+        /// The face
+        face: Handle<Face>,
+    },
+
+    /// An edge, or a half-edge that meets it, is not a line segment
+    #[error("Half-edge is not a line: {half_edge:#?}")]
+    HalfEdgeNotALine {
+        /// The half-edge
+        half_edge: Handle<HalfEdge>,
+    },
+
+    /// The chamfer would cut off a half-edge that meets the edge completely
+    #[error("Chamfer would cut off half-edge completely: {half_edge:#?}")]
+    ChamferTooLarge {
+        /// The half-edge
+        half_edge: Handle<HalfEdge>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Point;
+    use fj_test_utils::{assert_volume_eq, assert_watertight};
+
+    use crate::{
+        algorithms::{approx::Tolerance, triangulate::Triangulate},
+        objects::{HalfEdge, Shell},
+        operations::{build::BuildShell, insert::Insert},
+        storage::Handle,
+        Core,
+    };
+
+    use super::{ChamferEdge, ChamferEdgeError};
+
+    #[test]
+    fn chamfer_edges() -> anyhow::Result<()> {
+        let mut core = Core::new();
+        let tolerance = Tolerance::from_scalar(0.01)?;
+
+        let cube = Shell::cuboid([0., 0., 0.], [4., 4., 4.], &mut core);
+
+        // Two opposite edges of the top face.
+        let front = half_edge_between(&cube, [0., 0., 4.], [4., 0., 4.]);
+        let back = half_edge_between(&cube, [4., 4., 4.], [0., 4., 4.]);
+
+        let chamfered = cube
+            .chamfer_edges([&front, &back], [1., 2.], &mut core)?
+            .insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        assert_eq!(chamfered.faces().len(), 8);
+
+        // Each chamfer cuts off a prism with a right triangle as its base.
+        let mesh = (&*chamfered, tolerance).triangulate(&mut core);
+        let expected_volume = 64. - 2. * (1. * 2. / 2. * 4.);
+        assert_watertight!(mesh);
+        assert_volume_eq!(mesh, expected_volume, 1e-9);
+
+        Ok(())
+    }
+
+    #[test]
+    fn unsupported_chamfers() {
+        let mut core = Core::new();
+
+        let cube = Shell::cuboid([0., 0., 0.], [4., 4., 4.], &mut core);
+        let front = half_edge_between(&cube, [0., 0., 4.], [4., 0., 4.]);
+        let right = half_edge_between(&cube, [4., 0., 4.], [4., 4., 4.]);
+
+        let result = cube.chamfer_edges([&front, &right], 1., &mut core);
+        assert!(matches!(
+            result,
+            Err(ChamferEdgeError::EdgesShareVertex { .. })
+        ));
+
+        let result = cube.chamfer_edges([&front], 4., &mut core);
+        assert!(matches!(
+            result,
+            Err(ChamferEdgeError::ChamferTooLarge { .. })
+        ));
+
+        let result = cube.chamfer_edges([&front], 0., &mut core);
+        assert!(matches!(
+            result,
+            Err(ChamferEdgeError::DistanceNotPositive { .. })
+        ));
+    }
+
+    fn half_edge_between(
+        shell: &Shell,
+        start: [f64; 3],
+        end: [f64; 3],
+    ) -> Handle<HalfEdge> {
+        let [start, end] = [start, end].map(Point::from);
+
+        shell
+            .faces()
+            .iter()
+            .flat_map(|face| {
+                let surface = face.surface().geometry();
+
+                face.region().exterior().half_edges().iter().filter(
+                    move |half_edge| {
+                        let points = half_edge.boundary().inner.map(|point| {
+                            surface.point_from_surface_coords(
+                                half_edge.path().point_from_path_coords(point),
+                            )
+                        });
+                        points == [start, end]
+                    },
+                )
+            })
+            .next()
+            .expect("Expected half-edge between points")
+            .clone()
+    }
+}
@@ -0,0 +1,516 @@
+//! Remove faces from the inside of solids
+//!
+//! See [`RemoveInternalFaces`].
+
+use std::collections::BTreeMap;
+
+use fj_interop::ext::ArrayExt;
+use fj_math::{Point, Vector};
+
+use crate::{
+    geometry::SurfacePath,
+    objects::{
+        Cycle, Face, HalfEdge, Handedness, Region, Shell, Solid, Vertex,
+    },
+    queries::SiblingOfHalfEdge,
+    storage::{Handle, ObjectId},
+    validation::ValidationConfig,
+    Core,
+};
+
+use super::{derive::DeriveFrom, insert::Insert};
+
+/// Remove internal and duplicate faces from a [`Solid`]
+pub trait RemoveInternalFaces {
+    /// Remove internal and duplicate faces
+    ///
+    /// [Merging] solids that touch each other results in a solid, whose shells
+    /// have faces in common: Where the solids touch, each shell has a face.
+    /// Those faces coincide, but point in opposite directions, and they are on
+    /// the inside of the merged solid. This operation removes those faces, and
+    /// joins the faces next to them across the resulting gap. The shells they
+    /// belonged to are merged into one.
+    ///
+    /// Shells that duplicate an earlier shell of the solid, meaning that each of
+    /// their faces coincides with a face of that shell and points in the same
+    /// direction, are removed completely.
+    ///
+    /// # Limitations
+    ///
+    /// Faces are only considered coincident, if they are bounded by the same
+    /// number of half-edges, and each of those coincides with a half-edge of
+    /// the other face. Faces that only overlap partially are left alone.
+    ///
+    /// A pair of internal faces is only removed, if none of the faces next to
+    /// them are removed too, and if the half-edges that are joined across the
+    /// gap are line segments within their respective surfaces. Otherwise, the
+    /// pair of faces is left alone.
+    ///
+    /// [Merging]: super::merge::Merge
+    #[must_use]
+    fn remove_internal_faces(&self, core: &mut Core) -> Self;
+}
+
+impl RemoveInternalFaces for Solid {
+    fn remove_internal_faces(&self, core: &mut Core) -> Self {
+        core.trace_operation("remove_internal_faces", &[self], |core| {
+            let config = core.layers.validation.config;
+
+            let mut shells = Vec::<(Handle<Shell>, Vec<Outline>)>::new();
+            for shell in self.shells() {
+                let outlines =
+                    shell.faces().iter().map(Outline::new).collect::<Vec<_>>();
+
+                let is_duplicate = shells.iter().any(|(_, other)| {
+                    shell_is_duplicate(&outlines, other, &config)
+                });
+                if !is_duplicate {
+                    shells.push((shell.clone(), outlines));
+                }
+            }
+
+            let faces = shells
+                .iter()
+                .enumerate()
+                .flat_map(|(i, (_, outlines))| {
+                    outlines.iter().map(move |outline| (i, outline))
+                })
+                .collect::<Vec<_>>();
+            let face_by_half_edge = faces
+                .iter()
+                .enumerate()
+                .flat_map(|(i, (_, outline))| {
+                    outline
+                        .edges
+                        .iter()
+                        .map(move |edge| (edge.half_edge.id(), i))
+                })
+                .collect::<BTreeMap<_, _>>();
+
+            let mut pairs = Vec::new();
+            let mut is_internal = vec![false; faces.len()];
+            for i in 0..faces.len() {
+                for j in i + 1..faces.len() {
+                    if is_internal[i] || is_internal[j] {
+                        continue;
+                    }
+
+                    if let Some(edges) =
+                        faces[i].1.match_edges(faces[j].1, true, &config)
+                    {
+                        is_internal[i] = true;
+                        is_internal[j] = true;
+                        pairs.push(([i, j], edges));
+                    }
+                }
+            }
+
+            let mut removed = vec![false; faces.len()];
+            let mut groups = (0..shells.len()).collect::<Vec<_>>();
+            let mut joins = BTreeMap::new();
+            let mut vertices = VertexMerger::default();
+
+            'pairs: for ([i, j], edges) in pairs {
+                let mut pair_joins = Vec::new();
+
+                for [a, b] in edges {
+                    // The half-edges of the internal faces are going away.
+                    // Their siblings need to become each other's siblings.
+                    let half_edge_a = &faces[i].1.edges[a].half_edge;
+                    let half_edge_b = &faces[j].1.edges[b].half_edge;
+
+                    let siblings = [(i, half_edge_a), (j, half_edge_b)].map(
+                        |(face, half_edge)| {
+                            shells[faces[face].0].0.get_sibling_of(half_edge)
+                        },
+                    );
+                    let [Some(sibling_a), Some(sibling_b)] = siblings else {
+                        continue 'pairs;
+                    };
+
+                    for sibling in [&sibling_a, &sibling_b] {
+                        let SurfacePath::Line(_) = sibling.path() else {
+                            continue 'pairs;
+                        };
+
+                        let next_to_internal_face = face_by_half_edge
+                            .get(&sibling.id())
+                            .map_or(true, |&face| is_internal[face]);
+                        if next_to_internal_face {
+                            continue 'pairs;
+                        }
+                    }
+
+                    pair_joins.push((
+                        half_edge_b.clone(),
+                        sibling_a,
+                        sibling_b,
+                    ));
+                }
+
+                removed[i] = true;
+                removed[j] = true;
+
+                let [a, b] = [i, j].map(|face| root(&groups, faces[face].0));
+                groups[b] = a;
+
+                for (half_edge_b, sibling_a, sibling_b) in pair_joins {
+                    // `half_edge_b` starts where `sibling_a` starts, and ends
+                    // where `sibling_b` starts.
+                    vertices.merge(
+                        half_edge_b.start_vertex(),
+                        sibling_a.start_vertex(),
+                    );
+                    joins.insert(sibling_b.id(), sibling_a);
+                }
+            }
+
+            let mut faces_by_group =
+                BTreeMap::<usize, Vec<Handle<Face>>>::new();
+            let mut group_updated = vec![false; shells.len()];
+
+            for (k, (shell, outline)) in faces.iter().enumerate() {
+                let group = root(&groups, *shell);
+
+                if removed[k] {
+                    group_updated[group] = true;
+                    continue;
+                }
+
+                faces_by_group.entry(group).or_default().push(rebuild_face(
+                    &outline.face,
+                    &joins,
+                    &vertices,
+                    core,
+                ));
+            }
+
+            let shells =
+                shells.iter().enumerate().filter_map(|(i, (shell, _))| {
+                    if root(&groups, i) != i {
+                        // This shell's faces have been merged into another.
+                        return None;
+                    }
+
+                    if !group_updated[i] {
+                        return Some(shell.clone());
+                    }
+
+                    let faces = faces_by_group.remove(&i).unwrap_or_default();
+                    Some(
+                        Shell::new(faces).insert(core).derive_from(shell, core),
+                    )
+                });
+
+            Solid::new(shells.collect::<Vec<_>>())
+        })
+    }
+}
+
+/// The outline of a face, as points in global coordinates
+struct Outline {
+    face: Handle<Face>,
+    edges: Vec<EdgeOutline>,
+}
+
+impl Outline {
+    fn new(face: &Handle<Face>) -> Self {
+        let surface = face.surface().geometry();
+
+        // The orientation of a face depends on the winding of its exterior, in
+        // addition to the normal of its surface.
+        let orientation = match face.coord_handedness() {
+            Handedness::RightHanded => 1.,
+            Handedness::LeftHanded => -1.,
+        };
+
+        let edges = face
+            .region()
+            .all_cycles()
+            .flat_map(|cycle| cycle.half_edges())
+            .map(|half_edge| {
+                let [start, end] = half_edge.boundary().inner;
+                let middle = start + (end - start) / 2.;
+
+                let [start, middle, end] = [start, middle, end].map(|point| {
+                    half_edge.path().point_from_path_coords(point)
+                });
+
+                EdgeOutline {
+                    half_edge: half_edge.clone(),
+                    points: [start, middle, end]
+                        .map(|point| surface.point_from_surface_coords(point)),
+                    normal: surface.normal(start) * orientation,
+                }
+            })
+            .collect();
+
+        Self {
+            face: face.clone(),
+            edges,
+        }
+    }
+
+    /// Match the half-edges of this outline to those of another one
+    ///
+    /// Returns pairs of indices into the half-edges of both outlines, if each
+    /// half-edge of this outline coincides with exactly one of the other.
+    /// Whether the other outline is expected to be reversed compared to this
+    /// one, is determined by `reversed`.
+    fn match_edges(
+        &self,
+        other: &Self,
+        reversed: bool,
+        config: &ValidationConfig,
+    ) -> Option<Vec<[usize; 2]>> {
+        if self.edges.len() != other.edges.len() {
+            return None;
+        }
+
+        let mut matched = vec![false; other.edges.len()];
+        let mut matches = Vec::new();
+
+        for (i, a) in self.edges.iter().enumerate() {
+            let j = other.edges.iter().enumerate().position(|(j, b)| {
+                !matched[j] && a.coincides_with(b, reversed, config)
+            })?;
+
+            matched[j] = true;
+            matches.push([i, j]);
+        }
+
+        Some(matches)
+    }
+}
+
+struct EdgeOutline {
+    half_edge: Handle<HalfEdge>,
+    points: [Point<3>; 3],
+    normal: Vector<3>,
+}
+
+impl EdgeOutline {
+    fn coincides_with(
+        &self,
+        other: &Self,
+        reversed: bool,
+        config: &ValidationConfig,
+    ) -> bool {
+        let (points, normal) = if reversed {
+            let [start, middle, end] = other.points;
+            ([end, middle, start], -other.normal)
+        } else {
+            (other.points, other.normal)
+        };
+
+        // If the edges coincide, but go in opposite directions, the normals
+        // are not at the same point. That's fine, as coincident surfaces have
+        // the same normal everywhere.
+        let points_coincide =
+            self.points.zip_ext(points).into_iter().all(|(a, b)| {
+                a.distance_to(&b) <= config.identical_max_distance
+            });
+        let normals_coincide =
+            (self.normal - normal).magnitude() < config.distinct_min_distance;
+
+        points_coincide && normals_coincide
+    }
+}
+
+/// Merges vertices that end up in the same place
+#[derive(Default)]
+struct VertexMerger {
+    merged_into: BTreeMap<ObjectId, Handle<Vertex>>,
+}
+
+impl VertexMerger {
+    fn merge(&mut self, vertex: &Handle<Vertex>, into: &Handle<Vertex>) {
+        let [vertex, into] = [vertex, into].map(|vertex| self.get(vertex));
+
+        if vertex.id() != into.id() {
+            self.merged_into.insert(vertex.id(), into);
+        }
+    }
+
+    fn get(&self, vertex: &Handle<Vertex>) -> Handle<Vertex> {
+        let mut vertex = vertex.clone();
+
+        while let Some(into) = self.merged_into.get(&vertex.id()) {
+            vertex = into.clone();
+        }
+
+        vertex
+    }
+}
+
+fn shell_is_duplicate(
+    shell: &[Outline],
+    other: &[Outline],
+    config: &ValidationConfig,
+) -> bool {
+    shell.len() == other.len()
+        && shell.iter().all(|face| {
+            other
+                .iter()
+                .any(|other| face.match_edges(other, false, config).is_some())
+        })
+}
+
+fn root(groups: &[usize], mut i: usize) -> usize {
+    while groups[i] != i {
+        i = groups[i];
+    }
+
+    i
+}
+
+fn rebuild_face(
+    face: &Handle<Face>,
+    joins: &BTreeMap<ObjectId, Handle<HalfEdge>>,
+    vertices: &VertexMerger,
+    core: &mut Core,
+) -> Handle<Face> {
+    let mut face_updated = false;
+    let mut cycles = Vec::new();
+
+    for cycle in face.region().all_cycles() {
+        let mut cycle_updated = false;
+        let mut half_edges = Vec::new();
+
+        for half_edge in cycle.half_edges() {
+            let start_vertex = vertices.get(half_edge.start_vertex());
+            let sibling = joins.get(&half_edge.id());
+
+            if sibling.is_none()
+                && start_vertex.id() == half_edge.start_vertex().id()
+            {
+                half_edges.push(half_edge.clone());
+                continue;
+            }
+
+            let updated = match sibling {
+                Some(sibling) => {
+                    // The half-edge is now the sibling of a half-edge from
+                    // another face, so it has to use its curve and its
+                    // curve coordinates.
+                    let boundary = sibling.boundary().reverse();
+                    let points = half_edge.boundary().inner.map(|point| {
+                        half_edge.path().point_from_path_coords(point)
+                    });
+                    let path = SurfacePath::line_from_points_with_coords(
+                        boundary.inner.zip_ext(points),
+                    );
+
+                    HalfEdge::new(
+                        path,
+                        boundary,
+                        sibling.curve().clone(),
+                        start_vertex,
+                    )
+                }
+                None => HalfEdge::new(
+                    half_edge.path(),
+                    half_edge.boundary(),
+                    half_edge.curve().clone(),
+                    start_vertex,
+                ),
+            };
+
+            half_edges.push(updated.insert(core).derive_from(half_edge, core));
+            cycle_updated = true;
+        }
+
+        let cycle = if cycle_updated {
+            face_updated = true;
+            Cycle::new(half_edges).insert(core).derive_from(cycle, core)
+        } else {
+            cycle.clone()
+        };
+        cycles.push(cycle);
+    }
+
+    if !face_updated {
+        return face.clone();
+    }
+
+    let mut cycles = cycles.into_iter();
+    let exterior = cycles.next().expect("Region must have an exterior cycle");
+    let region = Region::new(exterior, cycles)
+        .insert(core)
+        .derive_from(face.region(), core);
+
+    Face::new(face.surface().clone(), region)
+        .insert(core)
+        .derive_from(face, core)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        objects::{Shell, Solid},
+        operations::{
+            build::{BuildShell, BuildSolid},
+            insert::Insert,
+            merge::Merge,
+            transform::TransformObject,
+            update::UpdateSolid,
+        },
+        Core,
+    };
+
+    use super::RemoveInternalFaces;
+
+    #[test]
+    fn touching_cuboids() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let [a, b, c] = [0., 1., 2.].map(|x| {
+            Shell::cuboid([x, 0., 0.], [x + 1., 1., 1.], &mut core)
+                .insert(&mut core)
+        });
+        let solid = Solid::empty().add_shells([a, b, c], &mut core);
+
+        let solid = solid.remove_internal_faces(&mut core).insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        assert_eq!(solid.shells().len(), 1);
+        assert_eq!(solid.shells().only().faces().len(), 14);
+
+        Ok(())
+    }
+
+    #[test]
+    fn stacked_cylinders() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let a = Solid::cylinder(1., 1., &mut core)?;
+        let b = Solid::cylinder(1., 1., &mut core)?
+            .translate([0., 0., 1.], &mut core);
+
+        let solid = a
+            .merge(&b, &mut core)
+            .remove_internal_faces(&mut core)
+            .insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        assert_eq!(solid.shells().len(), 1);
+        assert_eq!(solid.shells().only().faces().len(), 4);
+
+        Ok(())
+    }
+
+    #[test]
+    fn duplicate_shells() {
+        let mut core = Core::new();
+
+        let [a, b] = [(), ()].map(|()| {
+            Shell::cuboid([0., 0., 0.], [1., 1., 1.], &mut core)
+                .insert(&mut core)
+        });
+        let solid = Solid::empty().add_shells([a.clone(), b], &mut core);
+
+        let solid = solid.remove_internal_faces(&mut core);
+        assert_eq!(solid.shells().len(), 1);
+        assert_eq!(solid.shells().only().id(), a.id());
+    }
+}
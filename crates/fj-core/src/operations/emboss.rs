@@ -0,0 +1,631 @@
+//! Sweep sketches along the normal of a curved surface
+//!
+//! See [`Emboss`].
+
+use std::fmt;
+
+use fj_interop::Color;
+use fj_math::{Circle, Line, Point, Scalar, Vector};
+
+use crate::{
+    geometry::{GlobalPath, SurfaceGeometry, SurfacePath},
+    objects::{
+        Curve, Cycle, Face, HalfEdge, Region, Shell, Sketch, Solid, Surface,
+        Vertex,
+    },
+    storage::Handle,
+    Core,
+};
+
+use super::{
+    build::BuildSurface,
+    insert::Insert,
+    presentation::{GetColor, SetColor},
+    sweep::{SweepDirection, SweepSketch, SweepSketchError},
+};
+
+/// Emboss a [`Sketch`] or [`Region`] onto a surface, creating a [`Solid`]
+pub trait Emboss {
+    /// Sweep along the normal of the provided surface
+    ///
+    /// The sketch is defined in the coordinates of the provided surface, and
+    /// swept by `height` along the surface's normal, which points in the
+    /// direction of `u` cross `v`. A negative height sweeps the other way.
+    ///
+    /// On a plane, this works like [`SweepSketch::sweep_sketch`]. On a
+    /// cylinder, `u` is the angle around the axis, and `v` runs along it. The
+    /// sketch is swept outward or inward, onto a concentric cylinder. This
+    /// can be used to add features like ribs or slots to round shapes.
+    ///
+    /// Each region is swept into its own [`Shell`] of the resulting solid.
+    /// Regions must be wound counter-clockwise.
+    ///
+    /// # Limitations
+    ///
+    /// Surfaces can only be defined by sweeping a path along a straight line
+    /// so far. On a cylinder, this means regions must be bounded by straight
+    /// edges that are parallel to either the `u` or the `v` axis. Any other
+    /// edge would sweep out a helicoid.
+    fn emboss(
+        &self,
+        surface: Handle<Surface>,
+        height: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Result<Solid, EmbossError>;
+}
+
+impl Emboss for Sketch {
+    fn emboss(
+        &self,
+        surface: Handle<Surface>,
+        height: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Result<Solid, EmbossError> {
+        let height = height.into();
+
+        let inputs: &[&dyn fmt::Debug] = &[self, &surface, &height];
+        core.trace_operation("emboss_sketch", inputs, |core| {
+            emboss_regions(self.regions().iter(), &surface, height, core)
+        })
+    }
+}
+
+impl Emboss for Region {
+    fn emboss(
+        &self,
+        surface: Handle<Surface>,
+        height: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Result<Solid, EmbossError> {
+        let height = height.into();
+
+        let inputs: &[&dyn fmt::Debug] = &[self, &surface, &height];
+        core.trace_operation("emboss_region", inputs, |core| {
+            let region = self.clone().insert(core);
+            emboss_regions([&region], &surface, height, core)
+        })
+    }
+}
+
+/// Error embossing a [`Sketch`] or [`Region`]
+///
+/// See [`Emboss::emboss`].
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum EmbossError {
+    /// The height is (almost) zero
+    #[error("Height of emboss is zero: {height}")]
+    ZeroHeight {
+        /// The height
+        height: Scalar,
+    },
+
+    /// The surface is neither a plane nor a cylinder
+    #[error("Surface is neither a plane nor a cylinder: {surface:#?}")]
+    SurfaceNotSupported {
+        /// The surface
+        surface: Handle<Surface>,
+    },
+
+    /// Sweeping inward would reach the axis of the cylinder
+    #[error("Height of emboss exceeds radius of cylinder: {height}")]
+    HeightExceedsRadius {
+        /// The height
+        height: Scalar,
+    },
+
+    /// A region is not wound counter-clockwise
+    #[error("Region is not wound counter-clockwise: {region:#?}")]
+    RegionNotCounterClockwise {
+        /// The offending region
+        region: Handle<Region>,
+    },
+
+    /// A half-edge is not parallel to either axis of the cylinder's surface
+    #[error(
+        "Half-edge is not parallel to either axis of the surface: \
+        {half_edge:#?}"
+    )]
+    UnsupportedHalfEdge {
+        /// The offending half-edge
+        half_edge: Handle<HalfEdge>,
+    },
+
+    /// Sweeping the sketch along the normal of a plane failed
+    #[error(transparent)]
+    Sweep(#[from] SweepSketchError),
+}
+
+fn emboss_regions<'r>(
+    regions: impl IntoIterator<Item = &'r Handle<Region>>,
+    surface: &Handle<Surface>,
+    height: Scalar,
+    core: &mut Core,
+) -> Result<Solid, EmbossError> {
+    let tolerance = core.layers.validation.config.identical_max_distance;
+
+    if height.abs() <= tolerance {
+        return Err(EmbossError::ZeroHeight { height });
+    }
+
+    let SurfaceGeometry::Swept { u, v } = surface.geometry() else {
+        return Err(EmbossError::SurfaceNotSupported {
+            surface: surface.clone(),
+        });
+    };
+    let v = *v;
+    let circle = match *u {
+        GlobalPath::Line(u) => {
+            let normal = u.direction().cross(&v).normalize();
+            let sketch = Sketch::new(regions.into_iter().cloned());
+
+            return Ok(sketch.sweep_sketch_in_direction(
+                surface.clone(),
+                normal * height,
+                SweepDirection::Forward,
+                core,
+            )?);
+        }
+        GlobalPath::Circle(circle) => circle,
+        GlobalPath::CubicBezier(_) | GlobalPath::Ellipse(_) => {
+            return Err(EmbossError::SurfaceNotSupported {
+                surface: surface.clone(),
+            });
+        }
+    };
+
+    // The sketch can only be swept straight away from the axis, if `v` runs
+    // along it.
+    let axis = circle.a().cross(&circle.b()).normalize();
+    if axis.cross(&v).magnitude() > tolerance {
+        return Err(EmbossError::SurfaceNotSupported {
+            surface: surface.clone(),
+        });
+    }
+
+    let radius = circle.radius();
+    let is_outward = v.dot(&axis) > Scalar::ZERO;
+    let offset_radius = if is_outward {
+        radius + height
+    } else {
+        radius - height
+    };
+    if offset_radius <= tolerance {
+        return Err(EmbossError::HeightExceedsRadius { height });
+    }
+
+    let cylinder = Cylinder {
+        circle,
+        v,
+        radius,
+        offset_radius,
+        tolerance,
+    };
+    let offset_surface = {
+        let scale = offset_radius / radius;
+        Surface::surface_from_uv(
+            GlobalPath::Circle(Circle::new(
+                circle.center(),
+                circle.a() * scale,
+                circle.b() * scale,
+            )),
+            v,
+            core,
+        )
+    };
+
+    let mut shells = Vec::new();
+    for region in regions {
+        if !region.exterior().winding().is_ccw() {
+            return Err(EmbossError::RegionNotCounterClockwise {
+                region: region.clone(),
+            });
+        }
+
+        let mut corners = Vec::new();
+        for cycle in region.all_cycles() {
+            let mut positions = Vec::new();
+            for (half_edge, next) in cycle.half_edges().pairs() {
+                let start = half_edge.start_position();
+                let end = next.start_position();
+
+                let SurfacePath::Line(_) = half_edge.path() else {
+                    return Err(EmbossError::UnsupportedHalfEdge {
+                        half_edge: half_edge.clone(),
+                    });
+                };
+                if cylinder.edge_direction(start, end).is_none() {
+                    return Err(EmbossError::UnsupportedHalfEdge {
+                        half_edge: half_edge.clone(),
+                    });
+                }
+
+                positions.push(start);
+            }
+
+            // The region bounds the material at the bottom, so it needs to
+            // face against the direction of the sweep.
+            if height > Scalar::ZERO {
+                positions.reverse();
+            }
+
+            corners.push(positions);
+        }
+
+        let color = region.get_color(core);
+        let mut faces = Vec::new();
+        let mut bottom_cycles = Vec::new();
+        let mut top_cycles = Vec::new();
+
+        for positions in corners {
+            let (bottom, top, sides) =
+                cylinder.sweep_cycle(&positions, color, core);
+
+            faces.extend(sides);
+            bottom_cycles.push(bottom);
+            top_cycles.push(top);
+        }
+
+        for (surface, mut cycles) in [
+            (surface.clone(), bottom_cycles),
+            (offset_surface.clone(), top_cycles),
+        ] {
+            let exterior = cycles.remove(0);
+            let region = Region::new(exterior, cycles).insert(core);
+            if let Some(color) = color {
+                region.set_color(color, core);
+            }
+
+            faces.push(Face::new(surface, region).insert(core));
+        }
+
+        shells.push(Shell::new(faces).insert(core));
+    }
+
+    Ok(Solid::new(shells))
+}
+
+struct Cylinder {
+    circle: Circle<3>,
+    v: Vector<3>,
+    radius: Scalar,
+    offset_radius: Scalar,
+    tolerance: Scalar,
+}
+
+impl Cylinder {
+    /// Determine which axis of the surface an edge is parallel to
+    ///
+    /// Returns `None`, if the edge is parallel to neither of them.
+    fn edge_direction(
+        &self,
+        start: Point<2>,
+        end: Point<2>,
+    ) -> Option<EdgeDirection> {
+        let offset = end - start;
+
+        if (offset.u * self.radius).abs() <= self.tolerance {
+            Some(EdgeDirection::Axial)
+        } else if (offset.v * self.v.magnitude()).abs() <= self.tolerance {
+            Some(EdgeDirection::Circumferential)
+        } else {
+            None
+        }
+    }
+
+    /// Compute the radial direction at the provided angle
+    fn radial(&self, angle: Scalar) -> Vector<3> {
+        let (sin, cos) = angle.sin_cos();
+        (self.circle.a() * cos + self.circle.b() * sin) / self.radius
+    }
+
+    /// Convert a point on the surface into the coordinates of a side face
+    ///
+    /// The side face is the one created from an edge in the provided direction,
+    /// and `r` is the distance from the axis.
+    fn side_coords(
+        &self,
+        direction: &EdgeDirection,
+        position: Point<2>,
+        r: Scalar,
+    ) -> Point<2> {
+        match direction {
+            EdgeDirection::Axial => Point::from([r - self.radius, position.v]),
+            EdgeDirection::Circumferential => {
+                let (sin, cos) = position.u.sin_cos();
+                Point::from([r * cos, r * sin])
+            }
+        }
+    }
+
+    /// Sweep a cycle that is defined by the positions of its corners
+    ///
+    /// Returns the cycles that bound the bottom and the top of the sweep, and
+    /// the side faces.
+    fn sweep_cycle(
+        &self,
+        positions: &[Point<2>],
+        color: Option<Color>,
+        core: &mut Core,
+    ) -> (Handle<Cycle>, Handle<Cycle>, Vec<Handle<Face>>) {
+        let [bottom_vertices, top_vertices] = [(); 2].map(|()| {
+            positions
+                .iter()
+                .map(|_| Vertex::new().insert(core))
+                .collect::<Vec<_>>()
+        });
+        let vertical_curves = positions
+            .iter()
+            .map(|_| Curve::new().insert(core))
+            .collect::<Vec<_>>();
+
+        let mut bottom = Vec::new();
+        let mut top = Vec::new();
+        let mut sides = Vec::new();
+
+        for i in 0..positions.len() {
+            let j = (i + 1) % positions.len();
+            let [start, end] = [positions[i], positions[j]];
+
+            let direction = self
+                .edge_direction(start, end)
+                .expect("Edge direction has been checked before");
+
+            // The curves are parametrized by the surface coordinate that
+            // changes along them, which makes them easy to define on the side
+            // face too.
+            let [a, b] = match direction {
+                EdgeDirection::Axial => [start.v, end.v],
+                EdgeDirection::Circumferential => [start.u, end.u],
+            }
+            .map(|coord| Point::from([coord]));
+            let path = SurfacePath::line_from_points_with_coords([
+                (a, start),
+                (b, end),
+            ]);
+
+            let [bottom_curve, top_curve] =
+                [(); 2].map(|()| Curve::new().insert(core));
+
+            bottom.push(
+                HalfEdge::new(
+                    path,
+                    [a, b],
+                    bottom_curve.clone(),
+                    bottom_vertices[i].clone(),
+                )
+                .insert(core),
+            );
+            top.push(
+                HalfEdge::new(
+                    path,
+                    [b, a],
+                    top_curve.clone(),
+                    top_vertices[j].clone(),
+                )
+                .insert(core),
+            );
+
+            // The side face is defined in a plane that contains the edge. Its
+            // coordinates are chosen, so the bottom and top curves can keep
+            // their parametrization.
+            let surface = match direction {
+                EdgeDirection::Axial => {
+                    let radial = self.radial(start.u);
+                    Surface::surface_from_uv(
+                        GlobalPath::Line(Line::from_origin_and_direction(
+                            self.circle.center() + radial * self.radius,
+                            radial,
+                        )),
+                        self.v,
+                        core,
+                    )
+                }
+                EdgeDirection::Circumferential => Surface::surface_from_uv(
+                    GlobalPath::Line(Line::from_origin_and_direction(
+                        self.circle.center() + self.v * start.v,
+                        self.circle.a() / self.radius,
+                    )),
+                    self.circle.b() / self.radius,
+                    core,
+                ),
+            };
+            let [bottom_path, top_path] = [self.radius, self.offset_radius]
+                .map(|r| match direction {
+                    EdgeDirection::Axial => {
+                        SurfacePath::line_from_points_with_coords([
+                            (a, self.side_coords(&direction, start, r)),
+                            (b, self.side_coords(&direction, end, r)),
+                        ])
+                    }
+                    EdgeDirection::Circumferential => {
+                        SurfacePath::circle_from_center_and_radius([0., 0.], r)
+                    }
+                });
+            let vertical = |position| {
+                SurfacePath::line_from_points_with_coords([
+                    ([0.], self.side_coords(&direction, position, self.radius)),
+                    (
+                        [1.],
+                        self.side_coords(
+                            &direction,
+                            position,
+                            self.offset_radius,
+                        ),
+                    ),
+                ])
+            };
+
+            let [zero, one] =
+                [Scalar::ZERO, Scalar::ONE].map(|coord| Point::from([coord]));
+            let half_edges = [
+                HalfEdge::new(
+                    bottom_path,
+                    [b, a],
+                    bottom_curve,
+                    bottom_vertices[j].clone(),
+                ),
+                HalfEdge::new(
+                    vertical(start),
+                    [zero, one],
+                    vertical_curves[i].clone(),
+                    bottom_vertices[i].clone(),
+                ),
+                HalfEdge::new(
+                    top_path,
+                    [a, b],
+                    top_curve,
+                    top_vertices[i].clone(),
+                ),
+                HalfEdge::new(
+                    vertical(end),
+                    [one, zero],
+                    vertical_curves[j].clone(),
+                    top_vertices[j].clone(),
+                ),
+            ];
+
+            let exterior =
+                Cycle::new(half_edges.map(|half_edge| half_edge.insert(core)))
+                    .insert(core);
+            let region = Region::new(exterior, []).insert(core);
+            if let Some(color) = color {
+                region.set_color(color, core);
+            }
+
+            sides.push(Face::new(surface, region).insert(core));
+        }
+
+        // The top cycle needs to run the other way, to face away from the
+        // bottom.
+        top.reverse();
+
+        (
+            Cycle::new(bottom).insert(core),
+            Cycle::new(top).insert(core),
+            sides,
+        )
+    }
+}
+
+enum EdgeDirection {
+    /// The edge runs along the axis of the cylinder
+    Axial,
+
+    /// The edge runs around the axis of the cylinder
+    Circumferential,
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Circle, Scalar};
+    use fj_test_utils::{assert_volume_eq, assert_watertight};
+
+    use crate::{
+        algorithms::{approx::Tolerance, triangulate::Triangulate},
+        geometry::GlobalPath,
+        objects::{Region, Sketch, Surface},
+        operations::{
+            build::{BuildRegion, BuildSketch, BuildSurface},
+            insert::Insert,
+            update::UpdateSketch,
+        },
+        storage::Handle,
+        Core,
+    };
+
+    use super::{Emboss, EmbossError};
+
+    #[test]
+    fn emboss_on_cylinder() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let surface = cylinder(&mut core);
+        let tolerance = Tolerance::from_scalar(0.001)?;
+
+        for (height, inner, outer) in [(0.5, 2., 2.5), (-0.5, 1.5, 2.)] {
+            let sketch = Sketch::empty().add_regions(
+                [Region::polygon(
+                    [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+                    &mut core,
+                )],
+                &mut core,
+            );
+
+            let solid = sketch
+                .emboss(surface.clone(), height, &mut core)?
+                .insert(&mut core);
+            core.layers.validation.take_errors()?;
+
+            assert_eq!(solid.shells().only().faces().len(), 6);
+
+            // The region spans one radian around the axis, and one unit along
+            // it.
+            let mesh = (&*solid, tolerance).triangulate(&mut core);
+            let expected_volume =
+                Scalar::from(outer * outer - inner * inner) / 2.;
+            assert_watertight!(mesh);
+            assert_volume_eq!(mesh, expected_volume, expected_volume * 0.01);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn emboss_on_plane() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let surface = core.layers.objects.surfaces.xy_plane();
+        let tolerance = Tolerance::from_scalar(0.001)?;
+
+        let region = Region::polygon(
+            [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+            &mut core,
+        );
+        let solid = region.emboss(surface, -2., &mut core)?.insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        let mesh = (&*solid, tolerance).triangulate(&mut core);
+        assert_watertight!(mesh);
+        assert_volume_eq!(mesh, 2., 1e-9);
+
+        Ok(())
+    }
+
+    #[test]
+    fn unsupported_emboss() {
+        let mut core = Core::new();
+
+        let surface = cylinder(&mut core);
+        let square = Region::polygon(
+            [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+            &mut core,
+        );
+        let triangle =
+            Region::polygon([[0., 0.], [1., 0.], [0., 1.]], &mut core);
+
+        assert!(matches!(
+            square.emboss(surface.clone(), 0., &mut core),
+            Err(EmbossError::ZeroHeight { .. })
+        ));
+        assert!(matches!(
+            square.emboss(surface.clone(), -2., &mut core),
+            Err(EmbossError::HeightExceedsRadius { .. })
+        ));
+        assert!(matches!(
+            triangle.emboss(surface, 1., &mut core),
+            Err(EmbossError::UnsupportedHalfEdge { .. })
+        ));
+    }
+
+    fn cylinder(core: &mut Core) -> Handle<Surface> {
+        Surface::surface_from_uv(
+            GlobalPath::Circle(Circle::new(
+                [0., 0., 0.],
+                [2., 0., 0.],
+                [0., 2., 0.],
+            )),
+            [0., 0., 1.],
+            core,
+        )
+    }
+}
@@ -0,0 +1,255 @@
+//! Access and extend the untrimmed surface of a face
+//!
+//! See [`ExtendFace`].
+
+use fj_math::{Aabb, Scalar};
+
+use crate::{
+    algorithms::bounding_volume::BoundingVolume,
+    geometry::{GlobalPath, SurfaceGeometry},
+    objects::{Face, Handedness},
+    Core,
+};
+
+use super::{
+    build::BuildFace,
+    presentation::{GetColor, SetColor},
+};
+
+/// Access and extend the untrimmed surface of a [`Face`]
+///
+/// A face is a part of its surface, trimmed by the face's boundary. Operations
+/// that re-trim a face, or patch a gap next to it, need the part of the surface
+/// around that boundary. This trait provides it.
+pub trait ExtendFace {
+    /// Compute the bounds of the face, in surface coordinates
+    ///
+    /// Returns `None`, if the face has no boundary.
+    fn untrimmed_bounds(&self) -> Option<Aabb<2>>;
+
+    /// Create a face that covers the untrimmed bounds, enlarged by a margin
+    ///
+    /// The new face is defined on the same surface as the original one, and
+    /// bounded by a rectangle in surface coordinates. That rectangle contains
+    /// the original face, with at least `margin` (as a distance in 3D space)
+    /// to spare on each side.
+    ///
+    /// The new face has the same orientation and color as the original one.
+    fn extend(
+        &self,
+        margin: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Result<Face, ExtendFaceError>;
+}
+
+impl ExtendFace for Face {
+    fn untrimmed_bounds(&self) -> Option<Aabb<2>> {
+        self.region().exterior().aabb()
+    }
+
+    fn extend(
+        &self,
+        margin: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Result<Face, ExtendFaceError> {
+        let margin = margin.into();
+
+        if margin < Scalar::ZERO {
+            return Err(ExtendFaceError::NegativeMargin { margin });
+        }
+        let Some(bounds) = self.untrimmed_bounds() else {
+            return Err(ExtendFaceError::FaceHasNoBoundary {
+                face: self.clone(),
+            });
+        };
+
+        // Convert the margin into surface coordinates, so it's not less than
+        // the requested distance along either axis.
+        let SurfaceGeometry::Swept { u, v } = self.surface().geometry() else {
+            return Err(ExtendFaceError::SurfaceNotSupported {
+                face: self.clone(),
+            });
+        };
+        let margin_u = match u {
+            GlobalPath::Circle(circle) => margin / circle.radius(),
+            GlobalPath::CubicBezier(_) => {
+                // How far a change in `u` reaches varies along the curve, and
+                // the curve can't be extended beyond its ends anyway.
+                return Err(ExtendFaceError::SurfaceNotSupported {
+                    face: self.clone(),
+                });
+            }
+            GlobalPath::Ellipse(ellipse) => {
+                // The shorter semi-axis is where a change in `u` covers the
+                // least distance.
+                margin / ellipse.a().magnitude().min(ellipse.b().magnitude())
+            }
+            GlobalPath::Line(line) => margin / line.direction().magnitude(),
+        };
+        let margin_v = margin / v.magnitude();
+
+        let [min_u, min_v] = [bounds.min.u - margin_u, bounds.min.v - margin_v];
+        let [max_u, max_v] = [bounds.max.u + margin_u, bounds.max.v + margin_v];
+
+        if let GlobalPath::Circle(_) | GlobalPath::Ellipse(_) = u {
+            if max_u - min_u >= Scalar::TAU {
+                return Err(ExtendFaceError::WrapsAroundSurface {
+                    face: self.clone(),
+                });
+            }
+        }
+
+        let mut corners = vec![
+            [min_u, min_v],
+            [max_u, min_v],
+            [max_u, max_v],
+            [min_u, max_v],
+        ];
+        if self.coord_handedness() == Handedness::LeftHanded {
+            corners.reverse();
+        }
+
+        let face = Face::polygon(self.surface().clone(), corners, core);
+        if let Some(color) = self.region().get_color(core) {
+            face.region().set_color(color, core);
+        }
+
+        Ok(face)
+    }
+}
+
+/// Error extending a [`Face`]
+///
+/// See [`ExtendFace::extend`].
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum ExtendFaceError {
+    /// The margin is negative
+    #[error("Margin for extending face is negative: {margin}")]
+    NegativeMargin {
+        /// The margin
+        margin: Scalar,
+    },
+
+    /// The face has no boundary, so its extent is unknown
+    #[error("Face to extend has no boundary: {face:#?}")]
+    FaceHasNoBoundary {
+        /// The face
+        face: Face,
+    },
+
+    /// The extended face would wrap all the way around a curved surface
+    #[error("Extended face would wrap around its surface: {face:#?}")]
+    WrapsAroundSurface {
+        /// The face
+        face: Face,
+    },
+
+    /// The face's surface is not supported
+    #[error("Surface of face to extend is not supported: {face:#?}")]
+    SurfaceNotSupported {
+        /// The face
+        face: Face,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Aabb, Circle, Point, Scalar};
+
+    use crate::{
+        algorithms::{
+            approx::{Approx, Tolerance},
+            triangulate::Triangulate,
+        },
+        geometry::GlobalPath,
+        objects::{Face, Handedness, Surface},
+        operations::{
+            build::{BuildFace, BuildSurface},
+            insert::Insert,
+            reverse::Reverse,
+        },
+        Core,
+    };
+
+    use super::{ExtendFace, ExtendFaceError};
+
+    #[test]
+    fn extend() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let surface = core.layers.objects.surfaces.xz_plane();
+        let face = Face::polygon(
+            surface,
+            [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+            &mut core,
+        );
+
+        assert_eq!(
+            face.untrimmed_bounds(),
+            Some(Aabb {
+                min: Point::from([0., 0.]),
+                max: Point::from([1., 1.]),
+            })
+        );
+
+        for face in [face.clone(), face.reverse(&mut core)] {
+            let extended = face.extend(0.5, &mut core)?;
+
+            assert_eq!(
+                extended.untrimmed_bounds(),
+                Some(Aabb {
+                    min: Point::from([-0.5, -0.5]),
+                    max: Point::from([1.5, 1.5]),
+                })
+            );
+            assert_eq!(extended.coord_handedness(), face.coord_handedness());
+            assert_eq!(extended.surface(), face.surface());
+
+            let extended = extended.insert(&mut core);
+            core.layers.validation.take_errors()?;
+
+            let area = (&*extended)
+                .approx(Tolerance::from_scalar(0.001)?, &mut core)
+                .triangulate(&mut core)
+                .triangles()
+                .map(|triangle| {
+                    let [a, b, c] = triangle.inner.points();
+                    (b - a).cross(&(c - a)).magnitude() / 2.
+                })
+                .fold(Scalar::ZERO, |sum, area| sum + area);
+            assert_eq!(area, Scalar::from(4.));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn extend_around_cylinder() {
+        let mut core = Core::new();
+
+        let surface = Surface::surface_from_uv(
+            GlobalPath::Circle(Circle::from_center_and_radius(
+                [0., 0., 0.],
+                1.,
+            )),
+            [0., 0., 1.],
+            &mut core,
+        );
+        let face = Face::polygon(
+            surface,
+            [[0., 0.], [3., 0.], [3., 1.], [0., 1.]],
+            &mut core,
+        );
+        assert_eq!(face.coord_handedness(), Handedness::RightHanded);
+
+        assert!(face.extend(0.5, &mut core).is_ok());
+        assert!(matches!(
+            face.extend(2., &mut core),
+            Err(ExtendFaceError::WrapsAroundSurface { .. })
+        ));
+        assert!(matches!(
+            face.extend(-1., &mut core),
+            Err(ExtendFaceError::NegativeMargin { .. })
+        ));
+    }
+}
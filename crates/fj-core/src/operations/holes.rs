@@ -1,5 +1,7 @@
 //! Add holes to shapes
 
+use std::fmt;
+
 use fj_math::{Point, Scalar, Vector};
 
 use crate::{
@@ -44,6 +46,12 @@ impl AddHole for Shell {
         path: impl Into<Vector<3>>,
         core: &mut Core,
     ) -> Self {
+        let radius = radius.into();
+        let path = path.into();
+
+        let inputs: &[&dyn fmt::Debug] = &[self, &location, &radius, &path];
+        core.layers.trace.start_operation("add_blind_hole", inputs);
+
         let entry =
             HalfEdge::circle(location.position, radius, core).insert(core);
         let hole = Region::empty(core)
@@ -61,7 +69,7 @@ impl AddHole for Shell {
             .all_faces()
             .collect::<Vec<_>>();
 
-        self.update_face(
+        let shell = self.update_face(
             location.face,
             |face, core| {
                 [face.update_region(
@@ -82,8 +90,11 @@ impl AddHole for Shell {
                 )]
             },
             core,
-        )
-        .add_faces(hole, core)
+        );
+        let shell = shell.add_faces(hole, core);
+
+        core.layers.trace.end_operation(&shell);
+        shell
     }
 
     fn add_through_hole(
@@ -94,6 +105,12 @@ impl AddHole for Shell {
     ) -> Self {
         let radius = radius.into();
 
+        let inputs: &[&dyn fmt::Debug] =
+            &[self, &entry_location, &exit_location, &radius];
+        core.layers
+            .trace
+            .start_operation("add_through_hole", inputs);
+
         let entry = HalfEdge::circle(entry_location.position, radius, core)
             .insert(core);
 
@@ -134,7 +151,7 @@ impl AddHole for Shell {
             .half_edges()
             .only();
 
-        self.update_face(
+        let shell = self.update_face(
             entry_location.face,
             |face, core| {
                 [face.update_region(
@@ -155,8 +172,8 @@ impl AddHole for Shell {
                 )]
             },
             core,
-        )
-        .update_face(
+        );
+        let shell = shell.update_face(
             exit_location.face,
             |face, core| {
                 [face.update_region(
@@ -173,12 +190,16 @@ impl AddHole for Shell {
                 )]
             },
             core,
-        )
-        .add_faces(hole, core)
+        );
+        let shell = shell.add_faces(hole, core);
+
+        core.layers.trace.end_operation(&shell);
+        shell
     }
 }
 
 /// Defines the location of a hole
+#[derive(Debug)]
 pub struct HoleLocation<'r> {
     /// The face that the hole is in
     pub face: &'r Handle<Face>,
@@ -0,0 +1,463 @@
+//! Imprint sketches on the faces of shapes
+//!
+//! Used by [`Pad`] and [`Pocket`], which both imprint a sketch on a face, then
+//! sweep it away from or into the shape.
+//!
+//! [`Pad`]: super::pad::Pad
+//! [`Pocket`]: super::pocket::Pocket
+
+use fj_math::{Point, Scalar, Vector};
+
+use crate::{
+    algorithms::intersect::curve_curve::{
+        conic_coords, derivative, CurveCurveIntersection,
+    },
+    geometry::{GlobalPath, SurfaceGeometry, SurfacePath},
+    objects::{
+        Cycle, Face, HalfEdge, Handedness, Region, Shell, Sketch, Solid,
+    },
+    storage::Handle,
+    validation::ValidationConfig,
+    Core,
+};
+
+use super::{
+    derive::DeriveFrom,
+    insert::Insert,
+    presentation::GetColor,
+    reverse::Reverse,
+    sweep::{SweepCache, SweepRegion, SweptRegion},
+    update::{UpdateFace, UpdateRegion, UpdateShell},
+};
+
+/// The result of imprinting a sketch on a face
+pub struct Imprint {
+    /// The cycles that bound the imprinted regions, to be added to the face
+    pub interiors: Vec<Handle<Cycle>>,
+
+    /// The faces within the interiors of the imprinted regions
+    pub islands: Vec<Face>,
+
+    /// The result of sweeping each imprinted region
+    pub swept: Vec<SweptRegion>,
+}
+
+/// Imprint a sketch on a face, and sweep its regions along the provided path
+///
+/// The sketch is defined in the surface coordinates of the face. The face
+/// itself is left unchanged. Adding the interiors of the returned imprint to it
+/// is left to the caller.
+pub fn imprint(
+    face: &Handle<Face>,
+    sketch: &Sketch,
+    path: Vector<3>,
+    core: &mut Core,
+) -> Imprint {
+    let color = face.region().get_color(core);
+    let face_winding = face.region().exterior().winding();
+
+    let mut cache = SweepCache::default();
+    let mut imprint = Imprint {
+        interiors: Vec::new(),
+        islands: Vec::new(),
+        swept: Vec::new(),
+    };
+
+    for region in sketch.regions() {
+        // To become an interior of the face, the region's exterior needs to be
+        // wound the other way than the face's exterior.
+        let region = if region.exterior().winding() == face_winding {
+            region.reverse(core).insert(core).derive_from(region, core)
+        } else {
+            region.clone()
+        };
+
+        // The faces created by the sweep below are bounded by the siblings of
+        // the region's half-edges. This means the region's cycles can be used
+        // as they are, to bound the face.
+        imprint.interiors.push(region.exterior().clone());
+
+        // The interiors of the region are still part of the face, but separated
+        // from the rest of it by the sweep. They become faces of their own.
+        for interior in region.interiors() {
+            let island = Region::new(interior.clone(), [])
+                .insert(core)
+                .derive_from(face.region(), core);
+            imprint
+                .islands
+                .push(Face::new(face.surface().clone(), island));
+        }
+
+        imprint.swept.push(region.sweep_region(
+            face.surface(),
+            color,
+            path,
+            &mut cache,
+            core,
+        ));
+    }
+
+    imprint
+}
+
+/// Add the provided interiors to a face of the shell
+pub fn add_interiors(
+    shell: &Shell,
+    face: &Handle<Face>,
+    interiors: Vec<Handle<Cycle>>,
+    core: &mut Core,
+) -> Shell {
+    shell.update_face(
+        face,
+        |face, core| {
+            [face.update_region(
+                |region, core| region.add_interiors(interiors, core),
+                core,
+            )]
+        },
+        core,
+    )
+}
+
+/// Find the shell of a solid that contains the provided face
+pub fn shell_of_face<'r>(
+    solid: &'r Solid,
+    face: &Handle<Face>,
+) -> Option<&'r Handle<Shell>> {
+    solid
+        .shells()
+        .iter()
+        .find(|shell| contains_face(shell, face))
+}
+
+/// Determine whether the shell contains the provided face
+pub fn contains_face(shell: &Shell, face: &Handle<Face>) -> bool {
+    shell.faces().iter().any(|f| f.id() == face.id())
+}
+
+/// Compute the normal of a planar face, pointing away from the shape
+///
+/// Returns `None`, if the face is not planar.
+pub fn outward_normal(face: &Face) -> Option<Vector<3>> {
+    let SurfaceGeometry::Swept {
+        u: GlobalPath::Line(u),
+        v,
+    } = face.surface().geometry()
+    else {
+        return None;
+    };
+
+    let normal = u.direction().cross(v).normalize();
+    Some(match face.coord_handedness() {
+        Handedness::RightHanded => normal,
+        Handedness::LeftHanded => -normal,
+    })
+}
+
+/// The reason a sketch can't be imprinted on a face
+#[derive(Debug)]
+pub enum SketchError {
+    /// A region of the sketch doesn't lie within the face
+    NotInFace,
+
+    /// Two regions of the sketch overlap or touch
+    OverlappingRegions([Handle<Region>; 2]),
+}
+
+/// Check that the sketch can be imprinted on the face
+///
+/// Each region of the sketch must lie within the face, without touching its
+/// boundary, and the regions must neither overlap nor touch each other. Where
+/// that can't be decided, because a boundary passes too close to a point that
+/// is checked against it, the sketch is rejected.
+pub fn check_sketch(
+    face: &Face,
+    sketch: &Sketch,
+    config: &ValidationConfig,
+) -> Result<(), SketchError> {
+    let regions = sketch.regions().iter().collect::<Vec<_>>();
+
+    for region in &regions {
+        // The region must be within the face, and none of the face's boundary
+        // may be within the region. The latter catches regions that cover a
+        // hole in the face.
+        let within_face = !boundaries_meet(face.region(), region, config)
+            && contains_cycle(face.region(), region.exterior()) == Some(true)
+            && face
+                .region()
+                .all_cycles()
+                .all(|cycle| contains_cycle(region, cycle) == Some(false));
+
+        if !within_face {
+            return Err(SketchError::NotInFace);
+        }
+    }
+
+    for (i, &a) in regions.iter().enumerate() {
+        for &b in &regions[i + 1..] {
+            // If the boundaries don't meet, the regions can still overlap by
+            // one lying within the other.
+            let separate = !boundaries_meet(a, b, config)
+                && contains_cycle(a, b.exterior()) == Some(false)
+                && contains_cycle(b, a.exterior()) == Some(false);
+
+            if !separate {
+                return Err(SketchError::OverlappingRegions([
+                    a.clone(),
+                    b.clone(),
+                ]));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Determine whether the boundaries of two regions touch or cross
+fn boundaries_meet(a: &Region, b: &Region, config: &ValidationConfig) -> bool {
+    let half_edges = |region: &Region| {
+        region
+            .all_cycles()
+            .flat_map(|cycle| cycle.half_edges().iter().cloned())
+            .collect::<Vec<_>>()
+    };
+    let [a, b] = [a, b].map(half_edges);
+
+    a.iter()
+        .any(|a| b.iter().any(|b| half_edges_meet(a, b, config)))
+}
+
+/// Determine whether two half-edges touch or cross
+fn half_edges_meet(
+    a: &HalfEdge,
+    b: &HalfEdge,
+    config: &ValidationConfig,
+) -> bool {
+    match CurveCurveIntersection::compute([&a.path(), &b.path()], config) {
+        None => false,
+        Some(CurveCurveIntersection::Points(points)) => {
+            points.iter().any(|point| {
+                let [t_a, t_b] = point.points_on_curves;
+                is_within(a, t_a, config) && is_within(b, t_b, config)
+            })
+        }
+        Some(CurveCurveIntersection::Coincident) => {
+            // The half-edges lie on the same curve. They overlap, if one of
+            // them contains an end point or the middle of the other.
+            let overlaps = |a: &HalfEdge, b: &HalfEdge| {
+                let [start, end] = b.boundary().inner;
+                let middle = Point::from([(start.t + end.t) / 2.]);
+
+                [start, middle, end].into_iter().any(|t| {
+                    let point = b.path().point_from_path_coords(t);
+                    path_coords(&a.path(), point)
+                        .is_some_and(|t| is_within(a, t, config))
+                })
+            };
+
+            overlaps(a, b) || overlaps(b, a)
+        }
+    }
+}
+
+/// Determine whether a point on the path of a half-edge is within its boundary
+fn is_within(
+    half_edge: &HalfEdge,
+    t: Point<1>,
+    config: &ValidationConfig,
+) -> bool {
+    let path = half_edge.path();
+    let boundary = half_edge.boundary().inner;
+
+    // Close to the end points of the half-edge, the curve coordinates are
+    // compared as distances, to not depend on the parametrization of the path.
+    let point = path.point_from_path_coords(t);
+    let at_end_point = boundary.iter().any(|&end| {
+        (path.point_from_path_coords(end) - point).magnitude()
+            <= config.identical_max_distance
+    });
+    if at_end_point {
+        return true;
+    }
+
+    let [start, end] = boundary.map(|point| point.t);
+    let [min, max] = if start <= end {
+        [start, end]
+    } else {
+        [end, start]
+    };
+
+    // Coordinates on circles and ellipses repeat every full turn.
+    let candidates = match path {
+        SurfacePath::Circle(_) | SurfacePath::Ellipse(_) => {
+            vec![t.t - Scalar::TAU, t.t, t.t + Scalar::TAU]
+        }
+        SurfacePath::CubicBezier(_) | SurfacePath::Line(_) => vec![t.t],
+    };
+
+    candidates.into_iter().any(|t| min <= t && t <= max)
+}
+
+/// Compute the curve coordinates of a point on a path
+///
+/// Returns `None` for Bézier curves, which are never reported as coincident.
+fn path_coords(path: &SurfacePath, point: Point<2>) -> Option<Point<1>> {
+    match path {
+        SurfacePath::Circle(circle) => Some(conic_coords(
+            circle.center(),
+            [circle.a(), circle.b()],
+            point,
+        )),
+        SurfacePath::CubicBezier(_) => None,
+        SurfacePath::Ellipse(ellipse) => Some(conic_coords(
+            ellipse.center(),
+            [ellipse.a(), ellipse.b()],
+            point,
+        )),
+        SurfacePath::Line(line) => Some(line.point_to_line_coords(point)),
+    }
+}
+
+/// Determine whether a region contains a cycle that doesn't meet its boundary
+///
+/// Since the cycle doesn't meet the boundary of the region, it's enough to
+/// check a single point of it.
+fn contains_cycle(region: &Region, cycle: &Cycle) -> Option<bool> {
+    let point = cycle.half_edges().first().start_position();
+    contains_point(region, point)
+}
+
+/// Determine whether a region contains a point
+///
+/// Counts how often each cycle of the region winds around the point. The point
+/// is within the region, if the sum is odd.
+///
+/// Returns `None`, if the point lies on the boundary of the region, or so close
+/// to it that this can't be decided.
+fn contains_point(region: &Region, point: Point<2>) -> Option<bool> {
+    let mut windings = 0;
+
+    for cycle in region.all_cycles() {
+        let mut angle = Scalar::ZERO;
+
+        for half_edge in cycle.half_edges() {
+            let boundary = half_edge.boundary().inner.map(|point| point.t);
+            angle +=
+                swept_angle(&half_edge.path(), boundary, point, MAX_SPLITS)?;
+        }
+
+        let winding = (angle / Scalar::TAU).into_f64().round() as i64;
+        windings += winding;
+    }
+
+    Some(windings % 2 != 0)
+}
+
+/// The maximum number of times a section of a path is split in two
+///
+/// See [`swept_angle`].
+const MAX_SPLITS: u32 = 48;
+
+/// Compute the angle that a section of a path sweeps, as seen from a point
+///
+/// If the point lies outside of a convex hull of the section, that angle is the
+/// one between the end points of the section. Otherwise, the section is split
+/// in two and the angles of the halves are added up.
+///
+/// Returns `None`, if the point still lies within the hull of a section that
+/// has been split `splits` times.
+fn swept_angle(
+    path: &SurfacePath,
+    [start, end]: [Scalar; 2],
+    point: Point<2>,
+    splits: u32,
+) -> Option<Scalar> {
+    if let Some(hull) = hull(path, [start, end]) {
+        if !hull_contains(&hull, point) {
+            let [a, b] =
+                [start, end].map(|t| path.point_from_path_coords([t]) - point);
+            return Some(a.cross2d(&b).atan2(a.dot(&b)));
+        }
+    }
+
+    let splits = splits.checked_sub(1)?;
+    let middle = (start + end) / 2.;
+
+    Some(
+        swept_angle(path, [start, middle], point, splits)?
+            + swept_angle(path, [middle, end], point, splits)?,
+    )
+}
+
+/// Compute points whose convex hull contains a section of a path
+///
+/// Returns `None`, if the section of a circle or ellipse is too long for its
+/// hull to be computed.
+fn hull(
+    path: &SurfacePath,
+    [start, end]: [Scalar; 2],
+) -> Option<Vec<Point<2>>> {
+    let [a, b] = [start, end].map(|t| path.point_from_path_coords([t]));
+
+    let center = match path {
+        SurfacePath::Circle(circle) => circle.center(),
+        SurfacePath::Ellipse(ellipse) => ellipse.center(),
+        SurfacePath::CubicBezier(_) => {
+            // The control points of the section
+            let length = (end - start) / 3.;
+            return Some(vec![
+                a,
+                a + derivative(path, start) * length,
+                b - derivative(path, end) * length,
+                b,
+            ]);
+        }
+        SurfacePath::Line(_) => return Some(vec![a, b]),
+    };
+
+    // The hull of an arc is the triangle between its end points and the point
+    // where the tangents at those meet.
+    let half_angle = (end - start).abs() / 2.;
+    if half_angle > Scalar::PI / 4. {
+        return None;
+    }
+    let middle = path.point_from_path_coords([(start + end) / 2.]);
+    let tangents = center + (middle - center) / half_angle.cos();
+
+    Some(vec![a, tangents, b])
+}
+
+/// Determine whether the convex hull of some points contains a point
+///
+/// Points on the boundary of the hull count as contained.
+fn hull_contains(hull: &[Point<2>], point: Point<2>) -> bool {
+    let within_aabb = (0..2).all(|i| {
+        let coords = hull.iter().map(|p| p.coords.components[i]);
+        let coord = point.coords.components[i];
+
+        coords.clone().min().is_some_and(|min| min <= coord)
+            && coords.max().is_some_and(|max| coord <= max)
+    });
+    if !within_aabb {
+        return false;
+    }
+
+    // The convex hull is the union of all triangles between the points. Some
+    // of those triangles are degenerate. If all points are collinear, the
+    // check against the bounding box above makes up for that.
+    let n = hull.len();
+    (0..n).any(|i| {
+        (i + 1..n).any(|j| {
+            (j..n)
+                .any(|k| triangle_contains([hull[i], hull[j], hull[k]], point))
+        })
+    })
+}
+
+fn triangle_contains(triangle: [Point<2>; 3], point: Point<2>) -> bool {
+    let [a, b, c] = triangle.map(|p| p - point);
+    let sides = [a.cross2d(&b), b.cross2d(&c), c.cross2d(&a)];
+
+    sides.iter().all(|side| *side >= Scalar::ZERO)
+        || sides.iter().all(|side| *side <= Scalar::ZERO)
+}
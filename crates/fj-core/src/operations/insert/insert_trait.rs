@@ -43,6 +43,7 @@ macro_rules! impl_insert {
                     core.layers.objects.insert(
                         object,
                         &mut core.layers.validation,
+                        &mut core.layers.trace,
                     );
                     handle
                 }
@@ -5,17 +5,90 @@
 
 use crate::{objects::Solid, Core};
 
-use super::update::UpdateSolid;
+use super::{cleanup::RemoveInternalFaces, update::UpdateSolid};
 
 /// Merge two [`Solid`]s
 pub trait Merge {
     /// Merge this solid with another
+    ///
+    /// The shells of both solids are kept as they are. If the solids touch
+    /// each other, the faces where they do end up on the inside of the merged
+    /// solid. Those can be removed using [`RemoveInternalFaces`].
+    ///
+    /// [`RemoveInternalFaces`]: super::cleanup::RemoveInternalFaces
     #[must_use]
     fn merge(&self, other: &Self, core: &mut Core) -> Self;
+
+    /// Glue this solid to another one, that it touches
+    ///
+    /// This is a cheap alternative to a boolean union, for solids that touch
+    /// each other on faces that coincide exactly, like extrusions that are
+    /// stacked on top of each other. The faces where the solids touch are
+    /// removed, and the shells they belonged to are joined into one.
+    ///
+    /// Solids that don't touch each other are merged, as if by [`Merge::merge`].
+    /// See [`RemoveInternalFaces`] for the exact conditions under which faces
+    /// are removed.
+    ///
+    /// [`RemoveInternalFaces`]: super::cleanup::RemoveInternalFaces
+    #[must_use]
+    fn glue(&self, other: &Self, core: &mut Core) -> Self;
 }
 
 impl Merge for Solid {
     fn merge(&self, other: &Self, core: &mut Core) -> Self {
-        self.add_shells(other.shells().iter().cloned(), core)
+        core.trace_operation("merge", &[self, other], |core| {
+            self.add_shells(other.shells().iter().cloned(), core)
+        })
+    }
+
+    fn glue(&self, other: &Self, core: &mut Core) -> Self {
+        core.trace_operation("glue", &[self, other], |core| {
+            self.merge(other, core).remove_internal_faces(core)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        objects::{Region, Sketch},
+        operations::{
+            build::{BuildRegion, BuildSketch},
+            insert::Insert,
+            sweep::SweepSketch,
+            transform::TransformObject,
+            update::UpdateSketch,
+        },
+        Core,
+    };
+
+    use super::Merge;
+
+    #[test]
+    fn glue_stacked_extrusions() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let [bottom, top] = [0., 1.].map(|z| {
+            let surface = core.layers.objects.surfaces.xy_plane();
+            Sketch::empty()
+                .add_regions(
+                    [Region::polygon(
+                        [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+                        &mut core,
+                    )],
+                    &mut core,
+                )
+                .sweep_sketch(surface, [0., 0., 1.], &mut core)
+                .translate([0., 0., z], &mut core)
+        });
+
+        let solid = bottom.glue(&top, &mut core).insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        assert_eq!(solid.shells().len(), 1);
+        assert_eq!(solid.shells().only().faces().len(), 10);
+
+        Ok(())
     }
 }
@@ -38,15 +38,30 @@
 //! assume that the code in question is outdated. Feel free to open an issue or
 //! send a pull request!
 
+pub mod bitmap;
+pub mod boolean;
 pub mod build;
+pub mod chamfer;
+pub mod cleanup;
 pub mod derive;
+pub mod emboss;
+pub mod extend;
 pub mod holes;
+mod imprint;
 pub mod insert;
 pub mod join;
 pub mod merge;
+pub mod move_vertex;
+pub mod offset;
+pub mod pad;
+pub mod pipe;
+pub mod place;
+pub mod pocket;
 pub mod presentation;
 pub mod replace;
 pub mod reverse;
+pub mod revolve;
+pub mod section;
 pub mod split;
 pub mod sweep;
 pub mod transform;
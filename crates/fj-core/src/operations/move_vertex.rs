@@ -0,0 +1,357 @@
+//! Move vertices of shapes
+//!
+//! See [`MoveVertex`].
+
+use std::{collections::BTreeMap, fmt};
+
+use fj_interop::ext::ArrayExt;
+use fj_math::{Point, Scalar};
+
+use crate::{
+    geometry::{GlobalPath, SurfaceGeometry, SurfacePath},
+    objects::{Curve, Cycle, Face, HalfEdge, Region, Shell, Vertex},
+    storage::Handle,
+    validation::ValidationConfig,
+    Core,
+};
+
+use super::{derive::DeriveFrom, insert::Insert, update::UpdateHalfEdge};
+
+/// Move a [`Vertex`] to a new position
+pub trait MoveVertex: Sized {
+    /// Move the vertex to the provided position
+    ///
+    /// Updates all half-edges that start or end at the vertex, so they connect
+    /// to the vertex at its new position. Those half-edges need to be line
+    /// segments, and the faces they bound must be planar. The updated
+    /// half-edges are placed on new curves.
+    ///
+    /// The surfaces of the faces are not changed, so the new position must lie
+    /// within the surfaces of all faces that the vertex bounds. For a vertex on
+    /// a straight edge, this means it can be moved along that edge. Otherwise,
+    /// an error is returned.
+    fn move_vertex(
+        &self,
+        vertex: &Handle<Vertex>,
+        position: impl Into<Point<3>>,
+        core: &mut Core,
+    ) -> Result<Self, MoveVertexError>;
+}
+
+impl MoveVertex for Shell {
+    fn move_vertex(
+        &self,
+        vertex: &Handle<Vertex>,
+        position: impl Into<Point<3>>,
+        core: &mut Core,
+    ) -> Result<Self, MoveVertexError> {
+        let position = position.into();
+
+        let inputs: &[&dyn fmt::Debug] = &[self, vertex, &position];
+        core.trace_operation("move_vertex", inputs, |core| {
+            let config = core.layers.validation.config;
+
+            let mut vertex_found = false;
+            let mut faces = Vec::new();
+
+            // The geometry of each updated half-edge changes, so it needs a new
+            // curve. Its sibling must end up on the same curve. Other
+            // half-edges on the original curve might not be updated, or be
+            // updated differently, so we can't just use one new curve for
+            // each original one.
+            let mut curves = BTreeMap::new();
+
+            for face in self.faces() {
+                let mut position_surface = None;
+                let mut cycles = Vec::new();
+
+                for cycle in face.region().all_cycles() {
+                    let mut half_edges = Vec::new();
+                    let mut cycle_updated = false;
+
+                    for (half_edge, next) in cycle.half_edges().pairs() {
+                        let starts_at_vertex =
+                            half_edge.start_vertex().id() == vertex.id();
+                        let ends_at_vertex =
+                            next.start_vertex().id() == vertex.id();
+
+                        if !starts_at_vertex && !ends_at_vertex {
+                            half_edges.push(half_edge.clone());
+                            continue;
+                        }
+
+                        let SurfacePath::Line(_) = half_edge.path() else {
+                            return Err(MoveVertexError::HalfEdgeNotALine {
+                                half_edge: half_edge.clone(),
+                            });
+                        };
+
+                        let new_position = match position_surface {
+                            Some(position_surface) => position_surface,
+                            None => *position_surface.insert(
+                                project_onto_surface(face, position, &config)?,
+                            ),
+                        };
+
+                        let other_vertex = if starts_at_vertex {
+                            next.start_vertex()
+                        } else {
+                            half_edge.start_vertex()
+                        };
+                        let curve = curves
+                            .entry((half_edge.curve().id(), other_vertex.id()))
+                            .or_insert_with(|| Curve::new().insert(core))
+                            .clone();
+
+                        let [start, end] =
+                            half_edge.boundary().inner.map(|point| {
+                                half_edge.path().point_from_path_coords(point)
+                            });
+                        let start = if starts_at_vertex {
+                            new_position
+                        } else {
+                            start
+                        };
+                        let end =
+                            if ends_at_vertex { new_position } else { end };
+
+                        if start.distance_to(&end)
+                            < config.distinct_min_distance
+                        {
+                            return Err(MoveVertexError::HalfEdgeCollapsed {
+                                half_edge: half_edge.clone(),
+                            });
+                        }
+
+                        let path = SurfacePath::line_from_points_with_coords(
+                            half_edge.boundary().inner.zip_ext([start, end]),
+                        );
+
+                        half_edges.push(
+                            half_edge
+                                .update_path(|_| path)
+                                .update_curve(|_, _| curve, core)
+                                .insert(core)
+                                .derive_from(half_edge, core),
+                        );
+                        cycle_updated = true;
+                    }
+
+                    let updated_cycle = if cycle_updated {
+                        Cycle::new(half_edges)
+                            .insert(core)
+                            .derive_from(cycle, core)
+                    } else {
+                        cycle.clone()
+                    };
+                    cycles.push(updated_cycle);
+                }
+
+                if position_surface.is_none() {
+                    faces.push(face.clone());
+                    continue;
+                }
+                vertex_found = true;
+
+                let mut cycles = cycles.into_iter();
+                let exterior =
+                    cycles.next().expect("Region must have an exterior cycle");
+                let region = Region::new(exterior, cycles)
+                    .insert(core)
+                    .derive_from(face.region(), core);
+
+                faces.push(
+                    Face::new(face.surface().clone(), region)
+                        .insert(core)
+                        .derive_from(face, core),
+                );
+            }
+
+            if !vertex_found {
+                return Err(MoveVertexError::VertexNotFound {
+                    vertex: vertex.clone(),
+                });
+            }
+
+            Ok(Shell::new(faces))
+        })
+    }
+}
+
+/// Convert the position into surface coordinates of the face
+fn project_onto_surface(
+    face: &Handle<Face>,
+    position: Point<3>,
+    config: &ValidationConfig,
+) -> Result<Point<2>, MoveVertexError> {
+    let surface = face.surface().geometry();
+
+    let SurfaceGeometry::Swept {
+        u: GlobalPath::Line(line),
+        v,
+    } = surface
+    else {
+        return Err(MoveVertexError::SurfaceNotAPlane { face: face.clone() });
+    };
+
+    // Solve for the surface coordinates that come closest to the position, by
+    // projecting it onto both axes. The axes don't need to be orthogonal, so
+    // we need to solve a linear system of equations for that.
+    let [u, v] = [line.direction(), *v];
+    let d = position - line.origin();
+
+    let [uu, uv, vv] = [u.dot(&u), u.dot(&v), v.dot(&v)];
+    let [du, dv] = [d.dot(&u), d.dot(&v)];
+    let det = uu * vv - uv * uv;
+
+    let position_surface =
+        Point::from([(du * vv - dv * uv) / det, (dv * uu - du * uv) / det]);
+
+    let distance = surface
+        .point_from_surface_coords(position_surface)
+        .distance_to(&position);
+    if distance > config.identical_max_distance {
+        return Err(MoveVertexError::PositionNotOnSurface {
+            face: face.clone(),
+            distance,
+        });
+    }
+
+    Ok(position_surface)
+}
+
+/// Error moving a [`Vertex`]
+///
+/// See [`MoveVertex::move_vertex`].
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum MoveVertexError {
+    /// The vertex is not part of the shape
+    #[error("Vertex is not part of the shape: {vertex:#?}")]
+    VertexNotFound {
+        /// The vertex
+        vertex: Handle<Vertex>,
+    },
+
+    /// A face that the vertex bounds is not planar
+    #[error("Face bounded by vertex is not planar: {face:#?}")]
+    SurfaceNotAPlane {
+        /// The face
+        face: Handle<Face>,
+    },
+
+    /// The new position is not within the surface of a face
+    #[error(
+        "New position of vertex is not within the surface of a face it bounds\n\
+        - Distance from surface: {distance}\n\
+        - Face: {face:#?}"
+    )]
+    PositionNotOnSurface {
+        /// The face
+        face: Handle<Face>,
+
+        /// The distance between the new position and the surface of the face
+        distance: Scalar,
+    },
+
+    /// A half-edge that starts or ends at the vertex is not a line segment
+    #[error("Half-edge connected to vertex is not a line: {half_edge:#?}")]
+    HalfEdgeNotALine {
+        /// The half-edge
+        half_edge: Handle<HalfEdge>,
+    },
+
+    /// Moving the vertex would collapse a half-edge to zero length
+    #[error("Moving vertex would collapse half-edge: {half_edge:#?}")]
+    HalfEdgeCollapsed {
+        /// The half-edge
+        half_edge: Handle<HalfEdge>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Point;
+
+    use crate::{
+        objects::{Shell, Vertex},
+        operations::{
+            build::{BuildShell, TetrahedronShell},
+            insert::Insert,
+            split::SplitFace,
+        },
+        storage::Handle,
+        Core,
+    };
+
+    use super::{MoveVertex, MoveVertexError};
+
+    #[test]
+    fn move_vertex_along_edge() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let TetrahedronShell { shell, abc, .. } = Shell::tetrahedron(
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]],
+            &mut core,
+        );
+        let (shell, _) = shell.split_face(
+            &abc.face,
+            [(&abc.half_edges[0], [0.5]), (&abc.half_edges[1], [0.5])],
+            &mut core,
+        );
+
+        let vertex_at = |position: [f64; 3]| -> Handle<Vertex> {
+            let position = Point::from(position);
+            shell
+                .faces()
+                .iter()
+                .flat_map(|face| {
+                    face.region().exterior().half_edges().iter().filter_map(
+                        |half_edge| {
+                            let start = face
+                                .surface()
+                                .geometry()
+                                .point_from_surface_coords(
+                                    half_edge.start_position(),
+                                );
+                            (start == position)
+                                .then(|| half_edge.start_vertex().clone())
+                        },
+                    )
+                })
+                .next()
+                .expect("Expected vertex at position")
+        };
+
+        // The vertex created by the split can be moved along the edge it's on.
+        let vertex = vertex_at([0.5, 0., 0.]);
+        let _shell = shell
+            .move_vertex(&vertex, [0.25, 0., 0.], &mut core)?
+            .insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        // But not away from it.
+        let result = shell.move_vertex(&vertex, [0.25, 0.25, 0.], &mut core);
+        assert!(matches!(
+            result,
+            Err(MoveVertexError::PositionNotOnSurface { .. })
+        ));
+
+        // And not onto the next vertex.
+        let result = shell.move_vertex(&vertex, [0., 0., 0.], &mut core);
+        assert!(matches!(
+            result,
+            Err(MoveVertexError::HalfEdgeCollapsed { .. })
+        ));
+
+        // The corners of the tetrahedron are on three non-coplanar faces, so
+        // they can't be moved at all.
+        let corner = vertex_at([0., 0., 0.]);
+        let result = shell.move_vertex(&corner, [-1., 0., 0.], &mut core);
+        assert!(matches!(
+            result,
+            Err(MoveVertexError::PositionNotOnSurface { .. })
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,797 @@
+//! Inset or outset regions
+//!
+//! See [`OffsetRegion`].
+
+use fj_math::{Circle, Point, Scalar, Vector};
+
+use crate::{
+    geometry::SurfacePath,
+    objects::{Cycle, HalfEdge, Region},
+    storage::Handle,
+    Core,
+};
+
+use super::{boolean::signed_area, build::BuildHalfEdge, insert::Insert};
+
+/// Inset or outset a [`Region`]
+pub trait OffsetRegion {
+    /// Move the boundary of the region by the provided distance
+    ///
+    /// A positive distance grows the region (outset), a negative one shrinks
+    /// it (inset). The distance is measured in surface coordinates.
+    ///
+    /// Each line segment and arc of the boundary is moved sideways by the
+    /// distance. Where this opens gaps at corners, those are closed by arcs
+    /// around the original corner. Where it makes neighboring segments
+    /// overlap, they are trimmed where they intersect, and segments that are
+    /// trimmed away completely are removed.
+    ///
+    /// Interior cycles that shrink to nothing are removed. If that happens to
+    /// the exterior cycle, or if the boundary ends up intersecting itself in
+    /// a way that would split the region, an error is returned.
+    fn offset(
+        &self,
+        distance: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Result<Region, OffsetRegionError>;
+}
+
+impl OffsetRegion for Region {
+    fn offset(
+        &self,
+        distance: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Result<Region, OffsetRegionError> {
+        let distance = distance.into();
+
+        core.trace_operation("offset", &[self, &distance], |core| {
+            let tolerance =
+                core.layers.validation.config.identical_max_distance;
+
+            for cycle in self.all_cycles() {
+                for half_edge in cycle.half_edges() {
+                    if let SurfacePath::CubicBezier(_)
+                    | SurfacePath::Ellipse(_) = half_edge.path()
+                    {
+                        return Err(OffsetRegionError::UnsupportedHalfEdge {
+                            half_edge: half_edge.clone(),
+                        });
+                    }
+                }
+            }
+
+            // On the front side of the region, the region is to the left of
+            // its half-edges. Growing it means moving them to the right. If
+            // the exterior is clockwise in surface coordinates, we're looking
+            // at its back side, and it's the other way around.
+            let to_the_right = if self.exterior().winding().is_ccw() {
+                distance
+            } else {
+                -distance
+            };
+
+            let exterior = offset_cycle(
+                self.exterior(),
+                distance,
+                to_the_right,
+                tolerance,
+            )?
+            .ok_or(OffsetRegionError::RegionVanishes { distance })?;
+
+            let mut interiors = Vec::new();
+            for interior in self.interiors() {
+                interiors.extend(offset_cycle(
+                    interior,
+                    distance,
+                    to_the_right,
+                    tolerance,
+                )?);
+            }
+
+            let cycles = [&exterior]
+                .into_iter()
+                .chain(&interiors)
+                .map(|elements| polyline(elements))
+                .collect::<Vec<_>>();
+            if intersect_each_other(&cycles) {
+                return Err(OffsetRegionError::SelfIntersection { distance });
+            }
+
+            let exterior = build_cycle(exterior, core).insert(core);
+            let interiors = interiors
+                .into_iter()
+                .map(|interior| build_cycle(interior, core).insert(core))
+                .collect::<Vec<_>>();
+
+            Ok(Region::new(exterior, interiors))
+        })
+    }
+}
+
+/// Error offsetting a [`Region`]
+///
+/// See [`OffsetRegion::offset`].
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum OffsetRegionError {
+    /// The region shrinks to nothing
+    #[error("Offsetting region by {distance} makes it vanish")]
+    RegionVanishes {
+        /// The distance
+        distance: Scalar,
+    },
+
+    /// Neighboring segments of the offset boundary don't meet
+    #[error("Could not join boundary of region offset by {distance}")]
+    CouldNotJoin {
+        /// The distance
+        distance: Scalar,
+    },
+
+    /// The offset boundary intersects itself
+    #[error("Boundary of region offset by {distance} intersects itself")]
+    SelfIntersection {
+        /// The distance
+        distance: Scalar,
+    },
+
+    /// The region is bounded by a half-edge that can't be offset
+    ///
+    /// Only lines and circles are supported.
+    #[error("Can't offset half-edge: {half_edge:#?}")]
+    UnsupportedHalfEdge {
+        /// The half-edge
+        half_edge: Handle<HalfEdge>,
+    },
+}
+
+/// Offset a cycle to the right of its half-edges
+///
+/// `distance` is the distance that the region is offset by, and is only used
+/// for error reporting. The cycle is offset by `to_the_right`.
+///
+/// Returns `None`, if the cycle vanishes, and an error, if two of the offset
+/// elements don't intersect where they need to be joined.
+fn offset_cycle(
+    cycle: &Cycle,
+    distance: Scalar,
+    to_the_right: Scalar,
+    tolerance: Scalar,
+) -> Result<Option<Vec<Element>>, OffsetRegionError> {
+    let original = cycle
+        .half_edges()
+        .iter()
+        .map(|half_edge| Element::from_half_edge(half_edge))
+        .collect::<Vec<_>>();
+    let original_area = signed_area(&polyline(&original));
+
+    let offset = original
+        .iter()
+        .map(|element| element.offset(to_the_right, tolerance))
+        .collect::<Vec<_>>();
+
+    // Each element comes with the original corner that follows it, as long as
+    // the next element is its original neighbor. That corner is the center of
+    // the arc that closes a gap between both elements.
+    let n = original.len();
+    let mut elements = Vec::new();
+    for (i, element) in offset.iter().enumerate() {
+        let Some(element) = element else {
+            continue;
+        };
+
+        let corner = offset[(i + 1) % n].map(|_| original[i].end());
+        elements.push((*element, corner));
+    }
+
+    loop {
+        let n = elements.len();
+        if n == 0 {
+            return Ok(None);
+        }
+
+        let mut trimmed = elements
+            .iter()
+            .map(|&(element, _)| element)
+            .collect::<Vec<_>>();
+        let mut joins = vec![None; n];
+
+        for i in 0..n {
+            let j = (i + 1) % n;
+            let [end, start] = [trimmed[i].end(), trimmed[j].start()];
+
+            if end.distance_to(&start) <= tolerance {
+                continue;
+            }
+
+            let turn = trimmed[i]
+                .end_tangent()
+                .cross2d(&trimmed[j].start_tangent());
+            if let (Some(corner), true) =
+                (elements[i].1, turn * to_the_right > Scalar::ZERO)
+            {
+                joins[i] = Some(Element::round_join(corner, end, start, turn));
+                continue;
+            }
+
+            let near = end + (start - end) / 2.;
+            let Some(point) = elements[i]
+                .0
+                .intersect(&elements[j].0, tolerance)
+                .into_iter()
+                .min_by_key(|point| point.distance_to(&near))
+            else {
+                // If the elements were not neighbors originally, the ones
+                // between them have been trimmed away. If they don't meet
+                // either, nothing is left between them.
+                return match elements[i].1 {
+                    Some(_) => {
+                        Err(OffsetRegionError::CouldNotJoin { distance })
+                    }
+                    None => Ok(None),
+                };
+            };
+
+            trimmed[i].set_end(point);
+            trimmed[j].set_start(point);
+        }
+
+        // If trimming reversed an element, it was trimmed away completely.
+        // Remove it, and join its neighbors directly.
+        let reversed = (0..n)
+            .filter(|&i| trimmed[i].is_reversed_from(&elements[i].0, tolerance))
+            .collect::<Vec<_>>();
+        if !reversed.is_empty() {
+            for &i in &reversed {
+                elements[(i + n - 1) % n].1 = None;
+            }
+            for &i in reversed.iter().rev() {
+                elements.remove(i);
+            }
+            continue;
+        }
+
+        let mut cycle = Vec::new();
+        for (element, join) in trimmed.into_iter().zip(joins) {
+            cycle.push(element);
+            cycle.extend(join);
+        }
+
+        // A cycle that has turned inside out has vanished.
+        let area = signed_area(&polyline(&cycle));
+        if area.abs() <= tolerance || area.sign() != original_area.sign() {
+            return Ok(None);
+        }
+
+        return Ok(Some(cycle));
+    }
+}
+
+/// A line segment or arc of an offset boundary
+#[derive(Clone, Copy, Debug)]
+enum Element {
+    Line {
+        start: Point<2>,
+        end: Point<2>,
+    },
+    Arc {
+        circle: Circle<2>,
+        boundary: [Scalar; 2],
+    },
+}
+
+impl Element {
+    fn from_half_edge(half_edge: &HalfEdge) -> Self {
+        let boundary = half_edge.boundary().inner;
+
+        match half_edge.path() {
+            SurfacePath::Line(_) => Self::Line {
+                start: half_edge.start_position(),
+                end: half_edge.path().point_from_path_coords(boundary[1]),
+            },
+            SurfacePath::Circle(circle) => Self::Arc {
+                circle,
+                boundary: boundary.map(|point| point.t),
+            },
+            SurfacePath::CubicBezier(_) | SurfacePath::Ellipse(_) => {
+                unreachable!(
+                    "Bézier curves and ellipses are rejected before offsetting"
+                )
+            }
+        }
+    }
+
+    /// Create an arc around `corner`, from `start` to `end`
+    fn round_join(
+        corner: Point<2>,
+        start: Point<2>,
+        end: Point<2>,
+        turn: Scalar,
+    ) -> Self {
+        let [a, to_end] = [start - corner, end - corner];
+
+        let b = perpendicular(a);
+        let b = if turn > Scalar::ZERO { b } else { -b };
+
+        let angle = Scalar::atan2(a.cross2d(&to_end).abs(), a.dot(&to_end));
+
+        Self::Arc {
+            circle: Circle::new(corner, a, b),
+            boundary: [Scalar::ZERO, angle],
+        }
+    }
+
+    fn start(&self) -> Point<2> {
+        match self {
+            Self::Line { start, .. } => *start,
+            Self::Arc { circle, boundary } => {
+                circle.point_from_circle_coords([boundary[0]])
+            }
+        }
+    }
+
+    fn end(&self) -> Point<2> {
+        match self {
+            Self::Line { end, .. } => *end,
+            Self::Arc { circle, boundary } => {
+                circle.point_from_circle_coords([boundary[1]])
+            }
+        }
+    }
+
+    fn start_tangent(&self) -> Vector<2> {
+        match self {
+            Self::Line { start, end } => *end - *start,
+            Self::Arc { boundary, .. } => self.arc_tangent(boundary[0]),
+        }
+    }
+
+    fn end_tangent(&self) -> Vector<2> {
+        match self {
+            Self::Line { start, end } => *end - *start,
+            Self::Arc { boundary, .. } => self.arc_tangent(boundary[1]),
+        }
+    }
+
+    fn arc_tangent(&self, t: Scalar) -> Vector<2> {
+        let Self::Arc { circle, boundary } = self else {
+            unreachable!("Only called for arcs");
+        };
+
+        let (sin, cos) = t.sin_cos();
+        let tangent = circle.b() * cos - circle.a() * sin;
+
+        if boundary[1] < boundary[0] {
+            -tangent
+        } else {
+            tangent
+        }
+    }
+
+    /// Move the element to the right, relative to its direction
+    ///
+    /// Returns `None`, if the element is an arc that shrinks to nothing.
+    fn offset(&self, distance: Scalar, tolerance: Scalar) -> Option<Self> {
+        match *self {
+            Self::Line { start, end } => {
+                let right = -perpendicular(end - start).normalize();
+                let offset = right * distance;
+
+                Some(Self::Line {
+                    start: start + offset,
+                    end: end + offset,
+                })
+            }
+            Self::Arc { circle, boundary } => {
+                // To the right of a counter-clockwise arc is the outside of
+                // its circle.
+                let orientation = circle.a().cross2d(&circle.b());
+                let is_ccw =
+                    (orientation > Scalar::ZERO) == (boundary[1] > boundary[0]);
+                let radius = if is_ccw {
+                    circle.radius() + distance
+                } else {
+                    circle.radius() - distance
+                };
+
+                if radius <= tolerance {
+                    return None;
+                }
+
+                let a = circle.a().normalize() * radius;
+                let b = perpendicular(a);
+                let b = if orientation > Scalar::ZERO { b } else { -b };
+
+                Some(Self::Arc {
+                    circle: Circle::new(circle.center(), a, b),
+                    boundary,
+                })
+            }
+        }
+    }
+
+    fn set_start(&mut self, point: Point<2>) {
+        match self {
+            Self::Line { start, .. } => *start = point,
+            Self::Arc { circle, boundary } => {
+                boundary[0] = circle_coord(circle, point, boundary[0]);
+            }
+        }
+    }
+
+    fn set_end(&mut self, point: Point<2>) {
+        match self {
+            Self::Line { end, .. } => *end = point,
+            Self::Arc { circle, boundary } => {
+                boundary[1] = circle_coord(circle, point, boundary[1]);
+            }
+        }
+    }
+
+    fn is_reversed_from(&self, original: &Self, tolerance: Scalar) -> bool {
+        match (self, original) {
+            (
+                Self::Line { start, end },
+                Self::Line {
+                    start: original_start,
+                    end: original_end,
+                },
+            ) => {
+                let direction = *end - *start;
+                direction.magnitude() <= tolerance
+                    || direction.dot(&(*original_end - *original_start))
+                        <= Scalar::ZERO
+            }
+            (
+                Self::Arc { circle, boundary },
+                Self::Arc {
+                    boundary: original_boundary,
+                    ..
+                },
+            ) => {
+                let length = boundary[1] - boundary[0];
+                let original_length =
+                    original_boundary[1] - original_boundary[0];
+
+                length.abs() * circle.radius() <= tolerance
+                    || length.sign() != original_length.sign()
+            }
+            _ => unreachable!("Trimming doesn't change the kind of element"),
+        }
+    }
+
+    /// Intersect the line or circle that each element is part of
+    fn intersect(&self, other: &Self, tolerance: Scalar) -> Vec<Point<2>> {
+        match (*self, *other) {
+            (
+                Self::Line { start, end },
+                Self::Line {
+                    start: other_start,
+                    end: other_end,
+                },
+            ) => {
+                let [d, e] = [end - start, other_end - other_start];
+
+                let denominator = d.cross2d(&e);
+                if denominator.abs()
+                    <= tolerance * d.magnitude() * e.magnitude()
+                {
+                    return Vec::new();
+                }
+
+                let s = (other_start - start).cross2d(&e) / denominator;
+                vec![start + d * s]
+            }
+            (Self::Line { start, end }, Self::Arc { circle, .. })
+            | (Self::Arc { circle, .. }, Self::Line { start, end }) => {
+                let d = end - start;
+                let f = start - circle.center();
+
+                let a = d.dot(&d);
+                let b = f.dot(&d) * 2.;
+                let c = f.dot(&f) - circle.radius() * circle.radius();
+
+                let discriminant = b * b - a * c * 4.;
+                if discriminant < -tolerance {
+                    return Vec::new();
+                }
+                let root = discriminant.max(Scalar::ZERO).sqrt();
+
+                [-b - root, -b + root]
+                    .map(|t| start + d * (t / (a * 2.)))
+                    .to_vec()
+            }
+            (
+                Self::Arc { circle, .. },
+                Self::Arc {
+                    circle: other_circle,
+                    ..
+                },
+            ) => {
+                let between = other_circle.center() - circle.center();
+                let distance = between.magnitude();
+                if distance <= tolerance {
+                    return Vec::new();
+                }
+
+                let [r0, r1] = [circle.radius(), other_circle.radius()];
+                let a =
+                    (r0 * r0 - r1 * r1 + distance * distance) / (distance * 2.);
+                let h_squared = r0 * r0 - a * a;
+                if h_squared < -tolerance {
+                    return Vec::new();
+                }
+                let h = h_squared.max(Scalar::ZERO).sqrt();
+
+                let direction = between / distance;
+                let middle = circle.center() + direction * a;
+                let offset = perpendicular(direction) * h;
+
+                vec![middle - offset, middle + offset]
+            }
+        }
+    }
+}
+
+/// Approximate a cycle of elements as a polygon
+fn polyline(elements: &[Element]) -> Vec<Point<2>> {
+    let mut points = Vec::new();
+
+    for element in elements {
+        match element {
+            Element::Line { start, .. } => points.push(*start),
+            Element::Arc { circle, boundary } => {
+                let length = boundary[1] - boundary[0];
+                let steps = (length.abs() / (Scalar::TAU / 64.))
+                    .ceil()
+                    .max(Scalar::ONE)
+                    .into_u64();
+
+                for i in 0..steps {
+                    let t = boundary[0] + length * (i as f64 / steps as f64);
+                    points.push(circle.point_from_circle_coords([t]));
+                }
+            }
+        }
+    }
+
+    points
+}
+
+/// Determine whether any of the polygons intersect themselves or each other
+fn intersect_each_other(polygons: &[Vec<Point<2>>]) -> bool {
+    let segments = polygons
+        .iter()
+        .enumerate()
+        .flat_map(|(i, polygon)| {
+            let n = polygon.len();
+            (0..n).map(move |j| (i, j, n, [polygon[j], polygon[(j + 1) % n]]))
+        })
+        .collect::<Vec<_>>();
+
+    for (k, &(i, j, n, [a, b])) in segments.iter().enumerate() {
+        for &(other_i, other_j, _, [c, d]) in &segments[k + 1..] {
+            let are_neighbors = i == other_i
+                && (other_j == j + 1 || (j == 0 && other_j == n - 1));
+            if are_neighbors {
+                continue;
+            }
+
+            let [o1, o2] = [c, d].map(|p| (b - a).cross2d(&(p - a)));
+            let [o3, o4] = [a, b].map(|p| (d - c).cross2d(&(p - c)));
+
+            if o1 * o2 < Scalar::ZERO && o3 * o4 < Scalar::ZERO {
+                return true;
+            }
+        }
+    }
+
+    false
+}
+
+fn build_cycle(elements: Vec<Element>, core: &mut Core) -> Cycle {
+    let half_edges = elements
+        .into_iter()
+        .map(|element| {
+            let half_edge = match element {
+                Element::Line { start, end } => {
+                    HalfEdge::line_segment([start, end], None, core)
+                }
+                Element::Arc { circle, boundary } => HalfEdge::unjoined(
+                    SurfacePath::Circle(circle),
+                    boundary.map(|t| Point::from([t])),
+                    core,
+                ),
+            };
+
+            half_edge.insert(core)
+        })
+        .collect::<Vec<_>>();
+
+    Cycle::new(half_edges)
+}
+
+/// Compute the coordinate of a point on a circle, closest to `near`
+fn circle_coord(circle: &Circle<2>, point: Point<2>, near: Scalar) -> Scalar {
+    let vector = point - circle.center();
+    let t = Scalar::atan2(vector.dot(&circle.b()), vector.dot(&circle.a()));
+
+    t + Scalar::TAU * ((near - t) / Scalar::TAU).round()
+}
+
+/// Rotate a vector by 90 degrees, counter-clockwise
+fn perpendicular(vector: Vector<2>) -> Vector<2> {
+    Vector::from([-vector.v, vector.u])
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    use fj_math::Scalar;
+    use fj_test_utils::assert_scalars_eq;
+
+    use crate::{
+        algorithms::{
+            approx::{Approx, Tolerance},
+            triangulate::Triangulate,
+        },
+        geometry::SurfacePath,
+        objects::{Cycle, Face, HalfEdge, Region},
+        operations::{
+            build::{BuildCycle, BuildHalfEdge, BuildRegion},
+            insert::Insert,
+            reverse::Reverse,
+            update::UpdateCycle,
+        },
+        Core,
+    };
+
+    use super::{OffsetRegion, OffsetRegionError};
+
+    #[test]
+    fn offset_polygon() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let square = Region::polygon(
+            [[0., 0.], [2., 0.], [2., 2.], [0., 2.]],
+            &mut core,
+        );
+
+        // Outsetting rounds the corners.
+        let outset = square.offset(1., &mut core)?;
+        assert_eq!(outset.exterior().half_edges().len(), 8);
+        assert_area(&outset, 4. + 8. + Scalar::PI.into_f64(), &mut core)?;
+
+        // Insetting keeps them sharp. The winding doesn't matter.
+        for square in [square.clone(), square.reverse(&mut core)] {
+            let inset = square.offset(-0.5, &mut core)?;
+            assert_eq!(inset.exterior().half_edges().len(), 4);
+            assert_area(&inset, 1., &mut core)?;
+        }
+
+        assert!(matches!(
+            square.offset(-1.5, &mut core),
+            Err(OffsetRegionError::RegionVanishes { .. })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn offset_with_arcs() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let circle = Region::circle([0., 0.], 1., &mut core);
+        let inset = circle.offset(-0.5, &mut core)?;
+        assert_area(&inset, Scalar::PI.into_f64() / 4., &mut core)?;
+
+        // A stadium shape, with arcs that continue smoothly into the lines.
+        let mut arc = |center: [f64; 2], [start, end]: [f64; 2]| {
+            HalfEdge::unjoined(
+                SurfacePath::circle_from_center_and_radius(center, 1.),
+                [[start], [end]],
+                &mut core,
+            )
+        };
+        let [right, left] = [
+            arc([2., 1.], [-FRAC_PI_2, FRAC_PI_2]),
+            arc([0., 1.], [FRAC_PI_2, PI + FRAC_PI_2]),
+        ];
+        let stadium = Region::new(
+            Cycle::empty()
+                .add_half_edges(
+                    [
+                        HalfEdge::line_segment(
+                            [[0., 0.], [2., 0.]],
+                            None,
+                            &mut core,
+                        ),
+                        right,
+                        HalfEdge::line_segment(
+                            [[2., 2.], [0., 2.]],
+                            None,
+                            &mut core,
+                        ),
+                        left,
+                    ],
+                    &mut core,
+                )
+                .insert(&mut core),
+            [],
+        );
+        let outset = stadium.offset(1., &mut core)?;
+        assert_eq!(outset.exterior().half_edges().len(), 4);
+        assert_area(&outset, 8. + Scalar::PI.into_f64() * 4., &mut core)?;
+
+        let inset = stadium.offset(-0.5, &mut core)?;
+        assert_area(&inset, 2. + Scalar::PI.into_f64() / 4., &mut core)?;
+
+        // Insetting it by more than the radius of the arcs collapses them, and
+        // the lines along with them.
+        assert!(matches!(
+            stadium.offset(-1.5, &mut core),
+            Err(OffsetRegionError::RegionVanishes { .. })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn offset_with_holes() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let hole = |[u, v]: [f64; 2], core: &mut Core| {
+            Cycle::polygon(
+                [[u, v], [u, v + 2.], [u + 2., v + 2.], [u + 2., v]],
+                core,
+            )
+            .insert(core)
+        };
+        let region = Region::new(
+            Cycle::polygon(
+                [[0., 0.], [10., 0.], [10., 10.], [0., 10.]],
+                &mut core,
+            )
+            .insert(&mut core),
+            [hole([2., 2.], &mut core), hole([4.5, 2.], &mut core)],
+        );
+
+        // The holes shrink to nothing.
+        let outset = region.offset(1.5, &mut core)?;
+        assert_eq!(outset.interiors().len(), 0);
+
+        let inset = region.offset(-0.1, &mut core)?;
+        assert_eq!(inset.interiors().len(), 2);
+
+        // The holes grow into each other.
+        assert!(matches!(
+            region.offset(-0.5, &mut core),
+            Err(OffsetRegionError::SelfIntersection { .. })
+        ));
+
+        Ok(())
+    }
+
+    fn assert_area(
+        region: &Region,
+        expected: f64,
+        core: &mut Core,
+    ) -> anyhow::Result<()> {
+        let surface = core.layers.objects.surfaces.xy_plane();
+        let face = Face::new(surface, region.clone().insert(core));
+
+        let area = (&face)
+            .approx(Tolerance::from_scalar(0.0001)?, core)
+            .triangulate(core)
+            .triangles()
+            .map(|triangle| {
+                let [a, b, c] = triangle.inner.points();
+                (b - a).cross(&(c - a)).magnitude() / 2.
+            })
+            .fold(Scalar::ZERO, |sum, area| sum + area);
+
+        assert_scalars_eq!(area, expected, 0.001);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,334 @@
+//! Extrude sketches from the faces of shapes
+//!
+//! See [`Pad`].
+
+use fj_math::{Scalar, Vector};
+
+use crate::{
+    objects::{Face, Region, Shell, Sketch, Solid},
+    storage::Handle,
+    Core,
+};
+
+use super::{
+    imprint::{
+        add_interiors, check_sketch, contains_face, imprint, outward_normal,
+        shell_of_face, SketchError,
+    },
+    update::{UpdateShell, UpdateSolid},
+};
+
+/// Extrude a [`Sketch`] from a face, joining the result to the shape
+pub trait Pad: Sized {
+    /// Extrude the sketch from the provided face
+    ///
+    /// The sketch is defined in the surface coordinates of the face, and its
+    /// regions must lie within the face. Each region is imprinted on the face,
+    /// then extruded along the path, which must point away from the shape. The
+    /// result is a single shape that includes the extrusions.
+    fn pad(
+        &self,
+        face: &Handle<Face>,
+        sketch: &Sketch,
+        path: impl Into<Vector<3>>,
+        core: &mut Core,
+    ) -> Result<Self, PadError>;
+}
+
+impl Pad for Shell {
+    fn pad(
+        &self,
+        face: &Handle<Face>,
+        sketch: &Sketch,
+        path: impl Into<Vector<3>>,
+        core: &mut Core,
+    ) -> Result<Self, PadError> {
+        let path = path.into();
+
+        core.trace_operation("pad", &[self, face, sketch, &path], |core| {
+            if !contains_face(self, face) {
+                return Err(PadError::FaceNotFound { face: face.clone() });
+            }
+
+            let normal = outward_normal(face).ok_or_else(|| {
+                PadError::SurfaceNotAPlane { face: face.clone() }
+            })?;
+            if normal.dot(&path) <= Scalar::ZERO {
+                return Err(PadError::PathNotOutward { path });
+            }
+
+            check_sketch(face, sketch, &core.layers.validation.config)
+                .map_err(|err| match err {
+                    SketchError::NotInFace => {
+                        PadError::SketchNotInFace { face: face.clone() }
+                    }
+                    SketchError::OverlappingRegions(regions) => {
+                        PadError::OverlappingRegions { regions }
+                    }
+                })?;
+
+            let imprint = imprint(face, sketch, path, core);
+
+            let mut faces = imprint.islands;
+            for swept in imprint.swept {
+                faces.extend(swept.all_faces());
+            }
+
+            Ok(add_interiors(self, face, imprint.interiors, core)
+                .add_faces(faces, core))
+        })
+    }
+}
+
+impl Pad for Solid {
+    fn pad(
+        &self,
+        face: &Handle<Face>,
+        sketch: &Sketch,
+        path: impl Into<Vector<3>>,
+        core: &mut Core,
+    ) -> Result<Self, PadError> {
+        let shell = shell_of_face(self, face)
+            .ok_or_else(|| PadError::FaceNotFound { face: face.clone() })?;
+
+        let padded = shell.pad(face, sketch, path, core)?;
+
+        Ok(self.update_shell(shell, |_, _| [padded], core))
+    }
+}
+
+/// Error extruding a [`Sketch`] from a face
+///
+/// See [`Pad::pad`].
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum PadError {
+    /// The face is not part of the shape
+    #[error("Face is not part of the shape: {face:#?}")]
+    FaceNotFound {
+        /// The face
+        face: Handle<Face>,
+    },
+
+    /// The face is not planar
+    #[error("Face is not planar: {face:#?}")]
+    SurfaceNotAPlane {
+        /// The face
+        face: Handle<Face>,
+    },
+
+    /// The path doesn't point away from the shape
+    #[error("Path doesn't point away from the shape: {path:?}")]
+    PathNotOutward {
+        /// The path
+        path: Vector<3>,
+    },
+
+    /// The sketch doesn't lie within the face
+    #[error("Sketch doesn't lie within the face: {face:#?}")]
+    SketchNotInFace {
+        /// The face
+        face: Handle<Face>,
+    },
+
+    /// Two regions of the sketch overlap or touch
+    #[error("Regions of sketch overlap or touch: {regions:#?}")]
+    OverlappingRegions {
+        /// The regions
+        regions: [Handle<Region>; 2],
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar, Vector};
+
+    use crate::{
+        algorithms::bounding_volume::BoundingVolume,
+        objects::{Cycle, Face, HalfEdge, Region, Sketch, Solid},
+        operations::{
+            build::{BuildCycle, BuildHalfEdge, BuildRegion, BuildSketch},
+            insert::Insert,
+            sweep::SweepSketch,
+            update::{UpdateCycle, UpdateRegion, UpdateSketch},
+        },
+        storage::Handle,
+        Core,
+    };
+
+    use super::{Pad, PadError};
+
+    #[test]
+    fn pad() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let (cube, top) = cube(&mut core);
+
+        let tube = Region::polygon(
+            [[1., 1.], [3., 1.], [3., 3.], [1., 3.]],
+            &mut core,
+        )
+        .add_interiors(
+            [Cycle::polygon(
+                [[1.5, 1.5], [1.5, 2.5], [2.5, 2.5]],
+                &mut core,
+            )],
+            &mut core,
+        );
+        let sketch = Sketch::empty().add_regions([tube], &mut core);
+
+        let padded = cube
+            .pad(&top, &sketch, [0., 0., 2.], &mut core)?
+            .insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        let aabb = padded.aabb().expect("Expected non-empty solid");
+        assert_eq!(aabb.max.z, Scalar::from(6.));
+
+        // 6 faces of the cube, 1 island in the top face, 4 outer and 3 inner
+        // walls of the tube, 1 top face of the tube.
+        assert_eq!(padded.shells().only().faces().len(), 15);
+
+        let result = cube.pad(&top, &sketch, [0., 0., -2.], &mut core);
+        assert!(matches!(result, Err(PadError::PathNotOutward { .. })));
+
+        let outside = Sketch::empty().add_regions(
+            [Region::polygon(
+                [[3., 3.], [5., 3.], [5., 5.], [3., 5.]],
+                &mut core,
+            )],
+            &mut core,
+        );
+        let result = cube.pad(&top, &outside, [0., 0., 2.], &mut core);
+        assert!(matches!(result, Err(PadError::SketchNotInFace { .. })));
+
+        Ok(())
+    }
+
+    #[test]
+    fn arc_leaving_face() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let (cube, top) = cube(&mut core);
+
+        // A region bounded by an arc and a line segment. The arc bulges out
+        // towards the edge of the face at `u = 4`, reaching it at an angle of
+        // `0`. Its boundary doesn't start or end there, nor is that point in
+        // the middle of it, so it's easy to miss when sampling the arc.
+        let region = |apex: f64, core: &mut Core| {
+            let radius = 1.25;
+            let center = Point::from([apex - radius, 2.]);
+            let [start, interior, end] = [0.9, -0.2, -1.3].map(|angle| {
+                let (sin, cos) = f64::sin_cos(angle);
+                center + Vector::from([cos, sin]) * radius
+            });
+
+            let exterior = Cycle::empty()
+                .add_half_edges(
+                    [
+                        HalfEdge::arc_through_points(
+                            [start, interior, end],
+                            core,
+                        ),
+                        HalfEdge::line_segment([end, start], None, core),
+                    ],
+                    core,
+                )
+                .insert(core);
+            Sketch::empty().add_regions([Region::new(exterior, [])], core)
+        };
+
+        let inside = region(4. - 1e-5, &mut core);
+        cube.pad(&top, &inside, [0., 0., 2.], &mut core)?;
+
+        let outside = region(4. + 1e-5, &mut core);
+        let result = cube.pad(&top, &outside, [0., 0., 2.], &mut core);
+        assert!(matches!(result, Err(PadError::SketchNotInFace { .. })));
+
+        Ok(())
+    }
+
+    #[test]
+    fn overlapping_regions() {
+        let mut core = Core::new();
+
+        let (cube, top) = cube(&mut core);
+
+        let crossing = Sketch::empty().add_regions(
+            [
+                Region::polygon(
+                    [[1., 1.], [2.5, 1.], [2.5, 2.5], [1., 2.5]],
+                    &mut core,
+                ),
+                Region::polygon(
+                    [[2., 2.], [3., 2.], [3., 3.], [2., 3.]],
+                    &mut core,
+                ),
+            ],
+            &mut core,
+        );
+        let result = cube.pad(&top, &crossing, [0., 0., 2.], &mut core);
+        assert!(matches!(result, Err(PadError::OverlappingRegions { .. })));
+
+        let nested = Sketch::empty().add_regions(
+            [
+                Region::polygon(
+                    [[1., 1.], [3., 1.], [3., 3.], [1., 3.]],
+                    &mut core,
+                ),
+                Region::polygon(
+                    [[1.5, 1.5], [2.5, 1.5], [2.5, 2.5], [1.5, 2.5]],
+                    &mut core,
+                ),
+            ],
+            &mut core,
+        );
+        let result = cube.pad(&top, &nested, [0., 0., 2.], &mut core);
+        assert!(matches!(result, Err(PadError::OverlappingRegions { .. })));
+
+        // A region within the hole of another doesn't overlap it.
+        let island = Sketch::empty().add_regions(
+            [
+                Region::polygon(
+                    [[1., 1.], [3., 1.], [3., 3.], [1., 3.]],
+                    &mut core,
+                )
+                .add_interiors(
+                    [Cycle::polygon(
+                        [[1.5, 1.5], [1.5, 2.5], [2.5, 2.5], [2.5, 1.5]],
+                        &mut core,
+                    )],
+                    &mut core,
+                ),
+                Region::polygon(
+                    [[1.75, 1.75], [2.25, 1.75], [2.25, 2.25], [1.75, 2.25]],
+                    &mut core,
+                ),
+            ],
+            &mut core,
+        );
+        let result = cube.pad(&top, &island, [0., 0., 2.], &mut core);
+        assert!(!matches!(result, Err(PadError::OverlappingRegions { .. })));
+    }
+
+    fn cube(core: &mut Core) -> (Solid, Handle<Face>) {
+        let surface = core.layers.objects.surfaces.xy_plane();
+        let cube = Sketch::empty()
+            .add_regions(
+                [Region::polygon(
+                    [[0., 0.], [4., 0.], [4., 4.], [0., 4.]],
+                    core,
+                )],
+                core,
+            )
+            .sweep_sketch(surface, [0., 0., 4.], core);
+        let top = cube
+            .shells()
+            .only()
+            .faces()
+            .nth(5)
+            .expect("Expected top face")
+            .clone();
+
+        (cube, top)
+    }
+}
@@ -0,0 +1,609 @@
+//! Build pipes that follow a path
+//!
+//! See [`Pipe`].
+
+use std::f64::consts::FRAC_1_SQRT_2;
+
+use fj_math::{NurbsSurface, Point, Scalar, Transform, Vector};
+
+use crate::{
+    objects::{Curve, Shell, Solid, Vertex},
+    storage::Handle,
+    Core,
+};
+
+use super::{build::closed_face, insert::Insert};
+
+/// A pipe with a circular cross-section, that follows a path
+///
+/// Call [`Pipe::build`] to create the [`Solid`] that represents the pipe.
+#[derive(Clone, Debug)]
+pub struct Pipe {
+    /// The outer diameter of the pipe
+    pub outer_diameter: Scalar,
+
+    /// The thickness of the pipe's wall
+    pub wall_thickness: Scalar,
+
+    /// The path that the center line of the pipe follows
+    pub path: PipePath,
+}
+
+impl Pipe {
+    /// Create a pipe
+    pub fn new(
+        outer_diameter: impl Into<Scalar>,
+        wall_thickness: impl Into<Scalar>,
+        path: PipePath,
+    ) -> Self {
+        Self {
+            outer_diameter: outer_diameter.into(),
+            wall_thickness: wall_thickness.into(),
+            path,
+        }
+    }
+
+    /// The bend radius that all bends of the pipe must exceed
+    ///
+    /// Bend radii are measured at the center line of the pipe. If a bend is
+    /// not wider than the outer radius of the pipe, the inner side of the bend
+    /// collapses into a point, or folds onto itself.
+    pub fn min_bend_radius(&self) -> Scalar {
+        self.outer_diameter / 2.
+    }
+
+    /// Build the solid that represents the pipe
+    ///
+    /// The straight segments of the path become cylindrical sections of the
+    /// pipe, its bends become sections of tori. Both ends of the pipe are
+    /// closed by annular faces.
+    pub fn build(&self, core: &mut Core) -> Result<Solid, PipeError> {
+        core.trace_operation("pipe", &[self], |core| {
+            let config = &core.layers.validation.config;
+            let tolerance = config.identical_max_distance;
+            let max_angle = config.identical_max_angle;
+
+            let outer_radius = self.outer_diameter / 2.;
+            let inner_radius = outer_radius - self.wall_thickness;
+            if self.wall_thickness <= Scalar::ZERO
+                || inner_radius <= Scalar::ZERO
+            {
+                return Err(PipeError::InvalidWallThickness {
+                    outer_diameter: self.outer_diameter,
+                    wall_thickness: self.wall_thickness,
+                });
+            }
+
+            let sections = self.path.sections(
+                self.min_bend_radius(),
+                tolerance,
+                max_angle,
+            )?;
+
+            let start = self.path.points[0];
+            let mut direction = (self.path.points[1] - start).normalize();
+
+            let mut rings = {
+                // Any direction perpendicular to the path will do, to define
+                // where the circles around it start.
+                let helper = if direction.x.abs() < Scalar::from(0.9) {
+                    Vector::unit_x()
+                } else {
+                    Vector::unit_y()
+                };
+                let e1 = direction.cross(&helper).normalize();
+                let e2 = direction.cross(&e1);
+
+                [outer_radius, inner_radius]
+                    .map(|radius| ring(start, [e1, e2], radius))
+            };
+            let mut position = start;
+
+            let mut sweeps = Vec::new();
+            for section in sections {
+                let sweep = match section {
+                    Section::Straight { length } => {
+                        let offset = direction * length;
+                        position += offset;
+                        Sweep::straight(rings, offset)
+                    }
+                    Section::Bend {
+                        axis,
+                        angle,
+                        radius,
+                    } => {
+                        let center = position + axis.cross(&direction) * radius;
+                        let rotation = Transform::rotation(axis * angle);
+
+                        position = center
+                            + rotation.transform_vector(&(position - center));
+                        direction = rotation.transform_vector(&direction);
+
+                        Sweep::bend(rings, center, axis, angle)
+                    }
+                };
+
+                rings = sweep.rings[sweep.rings.len() - 1];
+                sweeps.push(sweep);
+            }
+
+            // Each ring where two faces meet has its own curves and vertices,
+            // for its outer and inner circle.
+            let edges = (0..=sweeps.len())
+                .map(|_| {
+                    [(); 2].map(|()| {
+                        (Curve::new().insert(core), Vertex::new().insert(core))
+                    })
+                })
+                .collect::<Vec<_>>();
+            let face = |surface,
+                        [a, b]: [&(Handle<Curve>, Handle<Vertex>); 2],
+                        core: &mut Core| {
+                closed_face(
+                    surface,
+                    [a.0.clone(), b.0.clone()],
+                    [a.1.clone(), b.1.clone()],
+                    core,
+                )
+            };
+
+            let mut faces = Vec::new();
+
+            // The caps span from one circle of a ring to the other. The start
+            // cap runs from the inside to the outside, to face backwards.
+            let [first, last] = [&sweeps[0].rings[0], &rings];
+            let [first_edges, last_edges] = [&edges[0], &edges[sweeps.len()]];
+            faces.push(face(
+                cap([&first[1], &first[0]]),
+                [&first_edges[1], &first_edges[0]],
+                core,
+            ));
+            faces.push(face(
+                cap([&last[0], &last[1]]),
+                [&last_edges[0], &last_edges[1]],
+                core,
+            ));
+
+            // The inner faces run backwards along the path, to face the
+            // inside of the pipe.
+            for (sweep, [start, end]) in sweeps
+                .iter()
+                .zip(edges.windows(2).map(|edges| [&edges[0], &edges[1]]))
+            {
+                let [outer, inner] = sweep.surfaces();
+                faces.push(face(outer, [&start[0], &end[0]], core));
+                faces.push(face(inner, [&end[1], &start[1]], core));
+            }
+
+            let shell = Shell::new(faces).insert(core);
+            Ok(Solid::new([shell]))
+        })
+    }
+}
+
+/// The path that the center line of a [`Pipe`] follows
+///
+/// The path consists of straight segments. Where two segments meet, the pipe
+/// bends with the radius that was provided for that point.
+#[derive(Clone, Debug)]
+pub struct PipePath {
+    points: Vec<Point<3>>,
+    bend_radii: Vec<Scalar>,
+}
+
+impl PipePath {
+    /// Start a path with a straight segment
+    pub fn line(start: impl Into<Point<3>>, end: impl Into<Point<3>>) -> Self {
+        Self {
+            points: vec![start.into(), end.into()],
+            bend_radii: Vec::new(),
+        }
+    }
+
+    /// Continue the path with another straight segment
+    ///
+    /// The segment starts where the path currently ends. Where the path
+    /// changes direction, the pipe bends with the provided radius, measured at
+    /// its center line. The bend cuts the corner, so the center line doesn't
+    /// pass through the point where both segments meet.
+    pub fn then_line(
+        mut self,
+        end: impl Into<Point<3>>,
+        bend_radius: impl Into<Scalar>,
+    ) -> Self {
+        self.points.push(end.into());
+        self.bend_radii.push(bend_radius.into());
+        self
+    }
+
+    /// Convert the path into the sections that the pipe consists of
+    fn sections(
+        &self,
+        min_bend_radius: Scalar,
+        tolerance: Scalar,
+        max_angle: Scalar,
+    ) -> Result<Vec<Section>, PipeError> {
+        let segments = self
+            .points
+            .windows(2)
+            .map(|points| {
+                let [start, end] = [points[0], points[1]];
+                let length = (end - start).magnitude();
+                if length <= tolerance {
+                    return Err(PipeError::ZeroLengthSegment { point: start });
+                }
+
+                Ok((start, end, (end - start) / length, length))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Where the path changes direction, the bend takes up some of the
+        // length of the segments on either side.
+        let mut bends = Vec::new();
+        for ((&(_, corner, a, _), &(_, _, b, _)), &radius) in
+            segments.iter().zip(&segments[1..]).zip(&self.bend_radii)
+        {
+            let cross = a.cross(&b);
+            let angle = cross.magnitude().atan2(a.dot(&b));
+
+            if angle <= max_angle {
+                bends.push(None);
+                continue;
+            }
+            if Scalar::PI - angle <= max_angle {
+                return Err(PipeError::PathReverses { corner });
+            }
+            if radius <= min_bend_radius {
+                return Err(PipeError::BendRadiusTooSmall {
+                    corner,
+                    bend_radius: radius,
+                    min_bend_radius,
+                });
+            }
+
+            let (sin, cos) = (angle / 2.).sin_cos();
+            let tangent_length = radius * sin / cos;
+
+            let bend = Section::Bend {
+                axis: cross.normalize(),
+                angle,
+                radius,
+            };
+            bends.push(Some((bend, tangent_length)));
+        }
+
+        let tangent_length = |bend: Option<&Option<(Section, Scalar)>>| {
+            bend.and_then(|bend| bend.as_ref())
+                .map(|&(_, tangent_length)| tangent_length)
+                .unwrap_or(Scalar::ZERO)
+        };
+
+        let mut sections = Vec::new();
+        for (i, &(start, end, _, length)) in segments.iter().enumerate() {
+            let before = i.checked_sub(1).and_then(|i| bends.get(i));
+            let after = bends.get(i);
+
+            let length =
+                length - tangent_length(before) - tangent_length(after);
+            if length < -tolerance {
+                return Err(PipeError::SegmentTooShort { start, end });
+            }
+            if length > tolerance {
+                sections.push(Section::Straight { length });
+            }
+
+            if let Some(Some((bend, _))) = after {
+                sections.push(*bend);
+            }
+        }
+
+        Ok(sections)
+    }
+}
+
+/// Error building a [`Pipe`]
+///
+/// See [`Pipe::build`].
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum PipeError {
+    /// The wall thickness is not positive, or not smaller than the radius
+    #[error(
+        "Wall thickness {wall_thickness} must be positive, and smaller than \
+        half the outer diameter {outer_diameter}"
+    )]
+    InvalidWallThickness {
+        /// The outer diameter of the pipe
+        outer_diameter: Scalar,
+
+        /// The thickness of the pipe's wall
+        wall_thickness: Scalar,
+    },
+
+    /// A segment of the path has zero length
+    #[error("Segment of pipe path has zero length: {point:?}")]
+    ZeroLengthSegment {
+        /// The point where the segment starts and ends
+        point: Point<3>,
+    },
+
+    /// The path turns back onto itself
+    #[error("Pipe path turns back onto itself at {corner:?}")]
+    PathReverses {
+        /// The point where the path turns back
+        corner: Point<3>,
+    },
+
+    /// A bend radius does not exceed the minimum bend radius
+    #[error(
+        "Bend radius {bend_radius} at {corner:?} must be larger than \
+        {min_bend_radius}"
+    )]
+    BendRadiusTooSmall {
+        /// The point where the path changes direction
+        corner: Point<3>,
+
+        /// The radius of the bend
+        bend_radius: Scalar,
+
+        /// The minimum bend radius of the pipe
+        ///
+        /// See [`Pipe::min_bend_radius`].
+        min_bend_radius: Scalar,
+    },
+
+    /// A segment is too short to fit the bends at its ends
+    #[error(
+        "Segment of pipe path from {start:?} to {end:?} is too short for the \
+        bends at its ends"
+    )]
+    SegmentTooShort {
+        /// The start of the segment
+        start: Point<3>,
+
+        /// The end of the segment
+        end: Point<3>,
+    },
+}
+
+#[derive(Clone, Copy)]
+enum Section {
+    Straight {
+        length: Scalar,
+    },
+    Bend {
+        axis: Vector<3>,
+        angle: Scalar,
+        radius: Scalar,
+    },
+}
+
+/// The control points of a circle around the center line of the pipe
+///
+/// The circle is a NURBS curve made up of quarter circles, that runs
+/// counter-clockwise around the direction of the path.
+type Ring = [Point<3>; 9];
+
+const RING_WEIGHTS: [f64; 9] = [
+    1.,
+    FRAC_1_SQRT_2,
+    1.,
+    FRAC_1_SQRT_2,
+    1.,
+    FRAC_1_SQRT_2,
+    1.,
+    FRAC_1_SQRT_2,
+    1.,
+];
+
+fn ring(center: Point<3>, [e1, e2]: [Vector<3>; 2], radius: Scalar) -> Ring {
+    [
+        [1., 0.],
+        [1., 1.],
+        [0., 1.],
+        [-1., 1.],
+        [-1., 0.],
+        [-1., -1.],
+        [0., -1.],
+        [1., -1.],
+        [1., 0.],
+    ]
+    .map(|[x, y]| center + e1 * radius * x + e2 * radius * y)
+}
+
+/// A section of the pipe, as the outer and inner rings that it sweeps through
+struct Sweep {
+    rings: Vec<[Ring; 2]>,
+    weights: Vec<Scalar>,
+    degree: usize,
+    knots: Vec<Scalar>,
+}
+
+impl Sweep {
+    fn straight(start: [Ring; 2], offset: Vector<3>) -> Self {
+        let end = start.map(|ring| ring.map(|point| point + offset));
+
+        Self {
+            rings: vec![start, end],
+            weights: vec![Scalar::ONE; 2],
+            degree: 1,
+            knots: [0., 0., 1., 1.].map(Scalar::from).to_vec(),
+        }
+    }
+
+    fn bend(
+        start: [Ring; 2],
+        center: Point<3>,
+        axis: Vector<3>,
+        angle: Scalar,
+    ) -> Self {
+        // An arc of a NURBS curve must span less than a half turn. Let's not
+        // make them wider than a quarter turn.
+        let num_arcs = (angle / (Scalar::PI / 2.)).ceil().into_u64().max(1);
+        let arc_angle = angle / num_arcs as f64;
+
+        let rotate = |point: Point<3>, angle: Scalar| {
+            center
+                + Transform::rotation(axis * angle)
+                    .transform_vector(&(point - center))
+        };
+
+        // The middle control point of an arc is where the tangents at its
+        // ends meet.
+        let middle_weight = (arc_angle / 2.).cos();
+        let middle = |point: Point<3>, angle: Scalar| {
+            let on_axis = center + axis * axis.dot(&(point - center));
+            on_axis
+                + Transform::rotation(axis * angle)
+                    .transform_vector(&(point - on_axis))
+                    / middle_weight
+        };
+
+        let mut rings = vec![start];
+        let mut weights = vec![Scalar::ONE];
+        let mut knots = vec![Scalar::ZERO; 3];
+        for i in 0..num_arcs {
+            let angle = arc_angle * i as f64;
+
+            rings.push(start.map(|ring| {
+                ring.map(|point| middle(point, angle + arc_angle / 2.))
+            }));
+            rings.push(start.map(|ring| {
+                ring.map(|point| rotate(point, angle + arc_angle))
+            }));
+            weights.extend([middle_weight, Scalar::ONE]);
+
+            let knot = Scalar::from_u64(i + 1) / num_arcs as f64;
+            knots.extend([knot; 2]);
+        }
+        knots.push(Scalar::ONE);
+
+        Self {
+            rings,
+            weights,
+            degree: 2,
+            knots,
+        }
+    }
+
+    /// The outer and inner surfaces of the section
+    ///
+    /// The inner surface runs backwards along the path, so it faces the inside
+    /// of the pipe.
+    fn surfaces(&self) -> [NurbsSurface; 2] {
+        let outer = self.rings.iter().map(|[outer, _]| outer);
+        let inner = self.rings.iter().rev().map(|[_, inner]| inner);
+
+        [
+            surface(outer, &self.weights, self.degree, &self.knots),
+            surface(inner, &self.weights, self.degree, &self.knots),
+        ]
+    }
+}
+
+/// The surface of an annulus, between the provided rings
+fn cap([a, b]: [&Ring; 2]) -> NurbsSurface {
+    let knots = [0., 0., 1., 1.].map(Scalar::from);
+    surface([a, b], &[Scalar::ONE; 2], 1, &knots)
+}
+
+/// A surface that sweeps the circle of a ring along `v`
+fn surface<'r>(
+    rings: impl IntoIterator<Item = &'r Ring>,
+    weights: &[Scalar],
+    degree: usize,
+    knots: &[Scalar],
+) -> NurbsSurface {
+    let rings = rings.into_iter().collect::<Vec<_>>();
+
+    let control_points =
+        (0..RING_WEIGHTS.len()).map(|i| rings.iter().map(move |ring| ring[i]));
+    let weights = RING_WEIGHTS
+        .iter()
+        .map(|&ring_weight| weights.iter().map(move |&w| w * ring_weight));
+    let ring_knots = [0., 0., 0., 1., 1., 2., 2., 3., 3., 4., 4., 4.]
+        .map(|knot| Scalar::from(knot / 4.))
+        .to_vec();
+
+    NurbsSurface::new(
+        control_points,
+        weights,
+        [2, degree],
+        [ring_knots, knots.to_vec()],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use fj_test_utils::{assert_volume_eq, assert_watertight};
+
+    use crate::{
+        algorithms::{approx::Tolerance, triangulate::Triangulate},
+        operations::insert::Insert,
+        Core,
+    };
+
+    use super::{Pipe, PipeError, PipePath};
+
+    #[test]
+    fn pipe() -> anyhow::Result<()> {
+        let mut core = Core::new();
+        let tolerance = Tolerance::from_scalar(0.03)?;
+
+        // The first bend is a quarter turn. The second one turns by more than
+        // that, in another plane.
+        let path = PipePath::line([0., 0., 0.], [0., 0., 10.])
+            .then_line([10., 0., 10.], 2.)
+            .then_line([0., 10., 10.], 2.);
+        let pipe = Pipe::new(2., 0.2, path);
+
+        let solid = pipe.build(&mut core)?.insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        // Two caps, and an outer and inner face for each of the 3 straight
+        // sections and 2 bends.
+        assert_eq!(solid.shells().only().faces().len(), 12);
+
+        let mesh = (&*solid, tolerance).triangulate(&mut core);
+
+        let second_tangent = 2. * (PI * 3. / 8.).tan();
+        let length = (10. - 2.)
+            + (10. - 2. - second_tangent)
+            + (200_f64.sqrt() - second_tangent)
+            + 2. * PI / 2.
+            + 2. * PI * 3. / 4.;
+        let volume_expected = PI * (1. - 0.8 * 0.8) * length;
+        assert_watertight!(mesh);
+        assert_volume_eq!(mesh, volume_expected, volume_expected * 0.05);
+
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_pipes() {
+        let mut core = Core::new();
+
+        let path = PipePath::line([0., 0., 0.], [0., 0., 10.])
+            .then_line([10., 0., 10.], 2.);
+
+        let result = Pipe::new(2., 1., path.clone()).build(&mut core);
+        assert!(matches!(
+            result,
+            Err(PipeError::InvalidWallThickness { .. })
+        ));
+
+        let result = Pipe::new(5., 0.5, path).build(&mut core);
+        assert!(matches!(result, Err(PipeError::BendRadiusTooSmall { .. })));
+
+        let path = PipePath::line([0., 0., 0.], [0., 0., 1.])
+            .then_line([10., 0., 1.], 2.);
+        let result = Pipe::new(2., 0.2, path).build(&mut core);
+        assert!(matches!(result, Err(PipeError::SegmentTooShort { .. })));
+
+        let path = PipePath::line([0., 0., 0.], [0., 0., 10.])
+            .then_line([0., 0., 5.], 2.);
+        let result = Pipe::new(2., 0.2, path).build(&mut core);
+        assert!(matches!(result, Err(PipeError::PathReverses { .. })));
+    }
+}
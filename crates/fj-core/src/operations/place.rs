@@ -0,0 +1,171 @@
+//! Place objects in model space, without transforming them
+//!
+//! See [`Placed`].
+
+use fj_math::{Transform, Vector};
+
+use crate::{
+    objects::{AnyObject, Stored},
+    storage::Handle,
+    Core,
+};
+
+use super::{insert::Insert, transform::TransformObject};
+
+/// An object, together with a transform that places it in model space
+///
+/// Transforming an object using [`TransformObject`] creates a new version of
+/// every object it references, down to the vertices. For big objects, like a
+/// complete part that is moved into an assembly, that is a lot of work that
+/// is not required for most uses.
+///
+/// A `Placed` object stores the transform instead. Algorithms that support it
+/// (like approximation, and therefore triangulation, and bounding volume
+/// computation) apply it to their results, which is much cheaper than
+/// transforming the object itself. Placing an object that already is placed
+/// composes both transforms.
+///
+/// Where an operation requires a transformed object, [`Placed::realize`] can be
+/// used to create it. Like [`TransformObject`], a placement that reflects the
+/// object turns its faces inside out. See [`Mirror`] for how to deal with that.
+///
+/// [`Mirror`]: super::transform::Mirror
+#[derive(Clone, Debug)]
+pub struct Placed<T> {
+    object: Handle<T>,
+    transform: Transform,
+}
+
+impl<T> Placed<T> {
+    /// Access the object that is placed
+    pub fn object(&self) -> &Handle<T> {
+        &self.object
+    }
+
+    /// Access the transform that places the object in model space
+    pub fn transform(&self) -> &Transform {
+        &self.transform
+    }
+
+    /// Place the already placed object somewhere else
+    ///
+    /// The provided transform is applied after the existing one.
+    pub fn place(&self, transform: &Transform) -> Self {
+        Self {
+            object: self.object.clone(),
+            transform: *transform * self.transform,
+        }
+    }
+
+    /// Translate the placed object
+    ///
+    /// Convenience wrapper around [`Placed::place`].
+    pub fn translate(&self, offset: impl Into<Vector<3>>) -> Self {
+        self.place(&Transform::translation(offset))
+    }
+
+    /// Rotate the placed object
+    ///
+    /// Convenience wrapper around [`Placed::place`].
+    pub fn rotate(&self, axis_angle: impl Into<Vector<3>>) -> Self {
+        self.place(&Transform::rotation(axis_angle))
+    }
+
+    /// Create the transformed object that this placement describes
+    ///
+    /// This does all the work that placing the object avoided, so only use it
+    /// where the transformed object itself is required.
+    pub fn realize(&self, core: &mut Core) -> Handle<T>
+    where
+        T: Clone + Insert<Inserted = Handle<T>> + TransformObject + 'static,
+        Handle<T>: Into<AnyObject<Stored>>,
+    {
+        self.object.transform(&self.transform, core)
+    }
+}
+
+/// Place an object in model space, without transforming it
+///
+/// See [`Placed`].
+pub trait Place<T> {
+    /// Place the object using the provided transform
+    fn place(&self, transform: &Transform) -> Placed<T>;
+}
+
+impl<T> Place<T> for Handle<T> {
+    fn place(&self, transform: &Transform) -> Placed<T> {
+        Placed {
+            object: self.clone(),
+            transform: *transform,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Scalar, Transform, Vector};
+    use fj_test_utils::{assert_mesh_eq, assert_points_eq};
+
+    use crate::{
+        algorithms::{
+            approx::Tolerance, bounding_volume::BoundingVolume,
+            triangulate::Triangulate,
+        },
+        objects::{Region, Sketch, Solid},
+        operations::{
+            build::{BuildRegion, BuildSketch},
+            insert::Insert,
+            sweep::SweepSketch,
+            update::UpdateSketch,
+        },
+        queries::Stats,
+        Core,
+    };
+
+    use super::Place;
+
+    #[test]
+    fn place() -> anyhow::Result<()> {
+        let mut core = Core::new();
+        let tolerance = Tolerance::from_scalar(0.001)?;
+
+        let surface = core.layers.objects.surfaces.xy_plane();
+        let solid = Sketch::empty()
+            .add_regions(
+                [Region::polygon(
+                    [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+                    &mut core,
+                )],
+                &mut core,
+            )
+            .sweep_sketch(surface, [0., 0., 1.], &mut core)
+            .insert(&mut core);
+        let num_objects = core.layers.objects.solids.num_reserved();
+
+        let placed = solid
+            .place(&Transform::translation([2., 0., 0.]))
+            .rotate(Vector::unit_z() * Scalar::PI / 2.);
+        assert_eq!(core.layers.objects.solids.num_reserved(), num_objects);
+
+        let aabb = placed.aabb().expect("Solid is not empty");
+        assert_points_eq!(aabb.min, [-1., 2., 0.], 1e-12);
+        assert_points_eq!(aabb.max, [0., 3., 1.], 1e-12);
+        assert_eq!(placed.stats().to_string(), solid.stats().to_string());
+
+        // The placement and the realized object must end up in the same
+        // place, for any transform.
+        for (i, placed) in
+            [placed.clone(), placed.place(&Transform::scale(-2.))]
+                .into_iter()
+                .enumerate()
+        {
+            let realized: Solid = placed.realize(&mut core).clone_object();
+            let expected = (&realized, tolerance).triangulate(&mut core);
+            let actual = (&placed, tolerance).triangulate(&mut core);
+
+            assert_mesh_eq!(actual, expected, 1e-12, "placement {i}");
+        }
+
+        Ok(())
+    }
+}
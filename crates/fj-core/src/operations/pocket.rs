@@ -0,0 +1,490 @@
+//! Cut sketches into the faces of shapes
+//!
+//! See [`Pocket`].
+
+use fj_math::{Scalar, Transform, Vector};
+
+use crate::{
+    algorithms::intersect::{face_point::FacePointIntersection, Intersect},
+    geometry::{SurfaceGeometry, SurfacePath},
+    objects::{Face, Region, Shell, Sketch, Solid},
+    storage::Handle,
+    Core,
+};
+
+use super::{
+    derive::DeriveFrom,
+    imprint::{
+        add_interiors, check_sketch, contains_face, imprint, outward_normal,
+        shell_of_face, SketchError,
+    },
+    insert::Insert,
+    update::{UpdateShell, UpdateSolid},
+};
+
+/// Cut a [`Sketch`] into a face, removing material from the shape
+pub trait Pocket: Sized {
+    /// Cut the sketch into the provided face
+    ///
+    /// The sketch is defined in the surface coordinates of the face, and its
+    /// regions must lie within the face. Each region is imprinted on the face,
+    /// then the material below it is removed, up to the provided depth.
+    ///
+    /// # Limitations
+    ///
+    /// With [`PocketDepth::ThroughAll`], the cut ends at the nearest face on
+    /// the other side of the shape that is parallel to the provided one, and
+    /// contains the whole sketch. That face must be bounded by straight edges
+    /// only, and its surface must be parametrized like the surface of the
+    /// provided face, just shifted along the cut. This is the case, if both
+    /// faces were created by the same sweep.
+    fn pocket(
+        &self,
+        face: &Handle<Face>,
+        sketch: &Sketch,
+        depth: PocketDepth,
+        core: &mut Core,
+    ) -> Result<Self, PocketError>;
+}
+
+impl Pocket for Shell {
+    fn pocket(
+        &self,
+        face: &Handle<Face>,
+        sketch: &Sketch,
+        depth: PocketDepth,
+        core: &mut Core,
+    ) -> Result<Self, PocketError> {
+        core.trace_operation("pocket", &[self, face, sketch, &depth], |core| {
+            if !contains_face(self, face) {
+                return Err(PocketError::FaceNotFound { face: face.clone() });
+            }
+
+            // Cutting into the shape means going against the face's normal.
+            let direction = -outward_normal(face).ok_or_else(|| {
+                PocketError::SurfaceNotAPlane { face: face.clone() }
+            })?;
+
+            check_sketch(face, sketch, &core.layers.validation.config)
+                .map_err(|err| match err {
+                    SketchError::NotInFace => {
+                        PocketError::SketchNotInFace { face: face.clone() }
+                    }
+                    SketchError::OverlappingRegions(regions) => {
+                        PocketError::OverlappingRegions { regions }
+                    }
+                })?;
+
+            let exit = find_exit_face(self, face, sketch, direction, core);
+
+            let (path, exit_face) = match depth {
+                PocketDepth::Blind(depth) => {
+                    if depth <= Scalar::ZERO {
+                        return Err(PocketError::DepthNotPositive { depth });
+                    }
+
+                    let thickness = match exit {
+                        Ok((_, distance)) => distance,
+                        Err(_) => extent(self, face, direction),
+                    };
+                    let max_distance =
+                        core.layers.validation.config.identical_max_distance;
+                    if depth >= thickness - max_distance {
+                        return Err(PocketError::DepthExceedsShape {
+                            depth,
+                            thickness,
+                        });
+                    }
+
+                    (direction * depth, None)
+                }
+                PocketDepth::ThroughAll => {
+                    let (exit_face, distance) = exit?;
+                    (direction * distance, Some(exit_face))
+                }
+            };
+
+            let imprint = imprint(face, sketch, path, core);
+
+            let mut faces = imprint.islands;
+            let mut exit_interiors = Vec::new();
+
+            for swept in imprint.swept {
+                faces.extend(swept.side_faces);
+                faces.extend(swept.interior_side_faces.into_iter().flatten());
+
+                match &exit_face {
+                    None => {
+                        // The top face of the sweep is the bottom of the
+                        // pocket.
+                        faces.push(swept.top_face);
+                    }
+                    Some(exit_face) => {
+                        // The cut goes all the way through. The top face of
+                        // the sweep is where it leaves the shape, so its
+                        // exterior becomes a hole in the exit face, and its
+                        // interiors become islands within that hole.
+                        let top = swept.top_face.region();
+                        exit_interiors.push(top.exterior().clone());
+
+                        for interior in top.interiors() {
+                            let island = Region::new(interior.clone(), [])
+                                .insert(core)
+                                .derive_from(exit_face.region(), core);
+                            faces.push(Face::new(
+                                exit_face.surface().clone(),
+                                island,
+                            ));
+                        }
+                    }
+                }
+            }
+
+            let mut shell = add_interiors(self, face, imprint.interiors, core);
+            if let Some(exit_face) = &exit_face {
+                shell = add_interiors(&shell, exit_face, exit_interiors, core);
+            }
+
+            Ok(shell.add_faces(faces, core))
+        })
+    }
+}
+
+impl Pocket for Solid {
+    fn pocket(
+        &self,
+        face: &Handle<Face>,
+        sketch: &Sketch,
+        depth: PocketDepth,
+        core: &mut Core,
+    ) -> Result<Self, PocketError> {
+        let shell = shell_of_face(self, face)
+            .ok_or_else(|| PocketError::FaceNotFound { face: face.clone() })?;
+
+        let pocketed = shell.pocket(face, sketch, depth, core)?;
+
+        Ok(self.update_shell(shell, |_, _| [pocketed], core))
+    }
+}
+
+/// The depth of a pocket
+///
+/// See [`Pocket::pocket`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PocketDepth {
+    /// Cut into the shape up to the provided depth
+    Blind(Scalar),
+
+    /// Cut all the way through the shape
+    ThroughAll,
+}
+
+/// Error cutting a [`Sketch`] into a face
+///
+/// See [`Pocket::pocket`].
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum PocketError {
+    /// The face is not part of the shape
+    #[error("Face is not part of the shape: {face:#?}")]
+    FaceNotFound {
+        /// The face
+        face: Handle<Face>,
+    },
+
+    /// The face is not planar
+    #[error("Face is not planar: {face:#?}")]
+    SurfaceNotAPlane {
+        /// The face
+        face: Handle<Face>,
+    },
+
+    /// The sketch doesn't lie within the face
+    #[error("Sketch doesn't lie within the face: {face:#?}")]
+    SketchNotInFace {
+        /// The face
+        face: Handle<Face>,
+    },
+
+    /// Two regions of the sketch overlap or touch
+    #[error("Regions of sketch overlap or touch: {regions:#?}")]
+    OverlappingRegions {
+        /// The regions
+        regions: [Handle<Region>; 2],
+    },
+
+    /// The depth of the pocket is zero or negative
+    #[error("Depth of pocket must be positive: {depth}")]
+    DepthNotPositive {
+        /// The depth
+        depth: Scalar,
+    },
+
+    /// The pocket would cut through the shape
+    ///
+    /// Use [`PocketDepth::ThroughAll`] to cut all the way through.
+    #[error(
+        "Depth of pocket ({depth}) reaches through the shape ({thickness})"
+    )]
+    DepthExceedsShape {
+        /// The depth
+        depth: Scalar,
+
+        /// The thickness of the shape below the sketch
+        thickness: Scalar,
+    },
+
+    /// No face was found, where a through-all cut could leave the shape
+    #[error("No face found where cut through shape would end: {face:#?}")]
+    NoExitFace {
+        /// The face that the cut starts at
+        face: Handle<Face>,
+    },
+}
+
+/// Compute how far the shape extends beyond the face, in the provided direction
+///
+/// This is an upper bound for the thickness of the shape below the face. It is
+/// computed from the vertices of the shape, and doesn't account for curved
+/// edges bulging beyond them.
+fn extent(shell: &Shell, face: &Face, direction: Vector<3>) -> Scalar {
+    let origin = face
+        .surface()
+        .geometry()
+        .point_from_surface_coords([0., 0.]);
+
+    shell
+        .faces()
+        .iter()
+        .flat_map(|face| {
+            let surface = face.surface().geometry();
+            face.region().all_cycles().flat_map(|cycle| {
+                cycle.half_edges().iter().map(|half_edge| {
+                    surface
+                        .point_from_surface_coords(half_edge.start_position())
+                })
+            })
+        })
+        .map(|point| (point - origin).dot(&direction))
+        .max()
+        .unwrap_or(Scalar::ZERO)
+}
+
+/// Find the face where a cut through the shape ends
+///
+/// Returns the face, and the distance from the face where the cut starts.
+fn find_exit_face(
+    shell: &Shell,
+    face: &Handle<Face>,
+    sketch: &Sketch,
+    direction: Vector<3>,
+    core: &Core,
+) -> Result<(Handle<Face>, Scalar), PocketError> {
+    let config = core.layers.validation.config;
+    let surface = face.surface().geometry();
+
+    let mut exit = None;
+
+    for candidate in shell.faces() {
+        let Some(normal) = outward_normal(candidate) else {
+            continue;
+        };
+        if (normal - direction).magnitude() > config.identical_max_distance {
+            continue;
+        }
+
+        let candidate_surface = candidate.surface().geometry();
+        let distance = (candidate_surface.point_from_surface_coords([0., 0.])
+            - surface.point_from_surface_coords([0., 0.]))
+        .dot(&direction);
+        if distance <= Scalar::ZERO {
+            continue;
+        }
+        if let Some((_, exit_distance)) = exit {
+            if distance >= exit_distance {
+                continue;
+            }
+        }
+
+        let translated = surface
+            .clone()
+            .transform(&Transform::translation(direction * distance));
+        if !same_parametrization(
+            &translated,
+            candidate_surface,
+            config.identical_max_distance,
+        ) {
+            continue;
+        }
+
+        // We can only check whether the sketch lies within the face, if the
+        // face is bounded by straight edges.
+        let only_lines = candidate.region().all_cycles().all(|cycle| {
+            cycle.half_edges().iter().all(|half_edge| {
+                matches!(half_edge.path(), SurfacePath::Line(_))
+            })
+        });
+        if !only_lines {
+            continue;
+        }
+
+        // Since the surfaces are parametrized the same way, the sketch has the
+        // same coordinates in both.
+        let contains_sketch = sketch.regions().iter().all(|region| {
+            region.exterior().half_edges().iter().all(|half_edge| {
+                matches!(
+                    (&**candidate, &half_edge.start_position()).intersect(),
+                    Some(FacePointIntersection::PointIsInsideFace)
+                )
+            })
+        });
+        if !contains_sketch {
+            continue;
+        }
+
+        exit = Some((candidate.clone(), distance));
+    }
+
+    exit.ok_or_else(|| PocketError::NoExitFace { face: face.clone() })
+}
+
+fn same_parametrization(
+    a: &SurfaceGeometry,
+    b: &SurfaceGeometry,
+    max_distance: Scalar,
+) -> bool {
+    [[0., 0.], [1., 0.], [0., 1.]].into_iter().all(|point| {
+        a.point_from_surface_coords(point)
+            .distance_to(&b.point_from_surface_coords(point))
+            <= max_distance
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Scalar;
+
+    use crate::{
+        algorithms::bounding_volume::BoundingVolume,
+        objects::{Region, Sketch},
+        operations::{
+            build::{BuildRegion, BuildSketch},
+            insert::Insert,
+            sweep::SweepSketch,
+            update::UpdateSketch,
+        },
+        Core,
+    };
+
+    use super::{Pocket, PocketDepth, PocketError};
+
+    #[test]
+    fn pocket() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let surface = core.layers.objects.surfaces.xy_plane();
+        let cube = Sketch::empty()
+            .add_regions(
+                [Region::polygon(
+                    [[0., 0.], [4., 0.], [4., 4.], [0., 4.]],
+                    &mut core,
+                )],
+                &mut core,
+            )
+            .sweep_sketch(surface, [0., 0., 4.], &mut core);
+        let top = cube
+            .shells()
+            .only()
+            .faces()
+            .nth(5)
+            .expect("Expected top face")
+            .clone();
+
+        let sketch = Sketch::empty().add_regions(
+            [Region::polygon(
+                [[1., 1.], [3., 1.], [3., 3.], [1., 3.]],
+                &mut core,
+            )],
+            &mut core,
+        );
+
+        let blind = cube
+            .pocket(
+                &top,
+                &sketch,
+                PocketDepth::Blind(Scalar::from(2.)),
+                &mut core,
+            )?
+            .insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        // 6 faces of the cube, 4 walls and 1 bottom face of the pocket.
+        assert_eq!(blind.shells().only().faces().len(), 11);
+        let aabb = blind.aabb().expect("Expected non-empty solid");
+        assert_eq!(aabb.max.z, Scalar::from(4.));
+
+        let through = cube
+            .pocket(&top, &sketch, PocketDepth::ThroughAll, &mut core)?
+            .insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        // 6 faces of the cube, 4 walls of the hole.
+        assert_eq!(through.shells().only().faces().len(), 10);
+
+        let result = cube.pocket(
+            &top,
+            &sketch,
+            PocketDepth::Blind(Scalar::ZERO),
+            &mut core,
+        );
+        assert!(matches!(result, Err(PocketError::DepthNotPositive { .. })));
+
+        let result = cube.pocket(
+            &top,
+            &sketch,
+            PocketDepth::Blind(Scalar::from(4.)),
+            &mut core,
+        );
+        assert!(matches!(result, Err(PocketError::DepthExceedsShape { .. })));
+
+        let outside = Sketch::empty().add_regions(
+            [Region::polygon(
+                [[3., 3.], [5., 3.], [5., 5.], [3., 5.]],
+                &mut core,
+            )],
+            &mut core,
+        );
+        let result = cube.pocket(
+            &top,
+            &outside,
+            PocketDepth::Blind(Scalar::from(2.)),
+            &mut core,
+        );
+        assert!(matches!(result, Err(PocketError::SketchNotInFace { .. })));
+
+        let overlapping = Sketch::empty().add_regions(
+            [
+                Region::polygon(
+                    [[1., 1.], [2.5, 1.], [2.5, 2.5], [1., 2.5]],
+                    &mut core,
+                ),
+                Region::polygon(
+                    [[2., 2.], [3., 2.], [3., 3.], [2., 3.]],
+                    &mut core,
+                ),
+            ],
+            &mut core,
+        );
+        let result = cube.pocket(
+            &top,
+            &overlapping,
+            PocketDepth::Blind(Scalar::from(2.)),
+            &mut core,
+        );
+        assert!(matches!(
+            result,
+            Err(PocketError::OverlappingRegions { .. })
+        ));
+
+        Ok(())
+    }
+}
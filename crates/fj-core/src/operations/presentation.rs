@@ -29,3 +29,31 @@ impl SetColor for Handle<Region> {
             .set_color(self.clone(), color.into());
     }
 }
+
+/// Get the name of an object
+pub trait GetName {
+    /// Get the name of the object
+    fn get_name(&self, core: &mut Core) -> Option<String>;
+}
+
+impl GetName for Handle<Region> {
+    fn get_name(&self, core: &mut Core) -> Option<String> {
+        core.layers.presentation.name.get(self).cloned()
+    }
+}
+
+/// Set the name of an object
+///
+/// Names are carried through to exported files, where the format supports it.
+/// Giving multiple objects the same name makes them a named group, which
+/// downstream software can select as a whole.
+pub trait SetName {
+    /// Set the name of the object
+    fn set_name(&self, name: impl Into<String>, core: &mut Core);
+}
+
+impl SetName for Handle<Region> {
+    fn set_name(&self, name: impl Into<String>, core: &mut Core) {
+        core.layers.presentation.set_name(self.clone(), name.into());
+    }
+}
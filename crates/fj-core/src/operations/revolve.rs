@@ -0,0 +1,886 @@
+//! Revolve sketches around an axis
+//!
+//! See [`Revolve`].
+
+use std::{collections::BTreeMap, fmt};
+
+use fj_interop::Color;
+use fj_math::{Circle, Line, Point, Scalar, Transform, Vector};
+
+use crate::{
+    geometry::{GlobalPath, SurfaceGeometry, SurfacePath},
+    objects::{
+        Curve, Cycle, Face, HalfEdge, Region, Shell, Sketch, Solid, Surface,
+        Vertex,
+    },
+    storage::{Handle, ObjectId},
+    Core,
+};
+
+use super::{
+    build::BuildSurface,
+    derive::DeriveFrom,
+    insert::Insert,
+    presentation::{GetColor, SetColor},
+    reverse::Reverse,
+    transform::TransformObject,
+};
+
+/// Revolve a [`Sketch`] or [`Region`] around an axis, creating a [`Solid`]
+pub trait Revolve {
+    /// Revolve around the provided axis
+    ///
+    /// The axis is defined in the coordinates of the provided surface, which
+    /// must be a plane. The revolution follows the right-hand rule around the
+    /// direction of the axis. Revolving by a full turn ([`Scalar::TAU`])
+    /// results in a closed ring. Revolving by less than that adds faces at
+    /// the start and the end of the revolution.
+    ///
+    /// Each region is revolved into its own [`Shell`] of the resulting solid.
+    /// Regions must be wound counter-clockwise, and must lie completely on
+    /// one side of the axis. They may touch it.
+    ///
+    /// This is a shorthand for [`Revolve::revolve_between`], starting at the
+    /// position of the sketch.
+    ///
+    /// # Limitations
+    ///
+    /// The revolution only creates surfaces that are defined by sweeping a path
+    /// along a straight line, so far. This means regions must be bounded by
+    /// straight edges that are either parallel or perpendicular to the axis.
+    /// Those become cylindrical and planar faces, respectively.
+    ///
+    /// Cones, spheres, and tori require NURBS surfaces, which the revolution
+    /// doesn't create yet. Those shapes are available as primitives through
+    /// [`BuildSolid`] instead.
+    ///
+    /// [`BuildSolid`]: super::build::BuildSolid
+    fn revolve(
+        &self,
+        surface: Handle<Surface>,
+        axis: Line<2>,
+        angle: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Result<Solid, RevolveError> {
+        self.revolve_between(surface, axis, [Scalar::ZERO, angle.into()], core)
+    }
+
+    /// Revolve around the provided axis, between the provided angles
+    ///
+    /// Angles are measured from the position of the sketch, following the
+    /// same right-hand rule as the revolution itself. The revolution covers
+    /// the angular range from the first angle to the second, which must be
+    /// larger than the first, by no more than a full turn. An elbow that is
+    /// centered on the sketch plane could be revolved between `-PI / 4` and
+    /// `PI / 4`, for example.
+    ///
+    /// A revolution by a full turn closes onto itself, and the faces at its
+    /// start and end are left out. A revolution that is almost a full turn,
+    /// such that its start and end faces would be closer to each other than
+    /// the validation tolerance allows, can't produce a valid solid. It is
+    /// rejected with [`RevolveError::AlmostFullTurn`].
+    ///
+    /// See [`Revolve::revolve`] for all other requirements and limitations.
+    fn revolve_between(
+        &self,
+        surface: Handle<Surface>,
+        axis: Line<2>,
+        angles: [impl Into<Scalar>; 2],
+        core: &mut Core,
+    ) -> Result<Solid, RevolveError>;
+}
+
+impl Revolve for Sketch {
+    fn revolve_between(
+        &self,
+        surface: Handle<Surface>,
+        axis: Line<2>,
+        angles: [impl Into<Scalar>; 2],
+        core: &mut Core,
+    ) -> Result<Solid, RevolveError> {
+        let angles = angles.map(Into::into);
+
+        let inputs: &[&dyn fmt::Debug] = &[self, &surface, &axis, &angles];
+        core.trace_operation("revolve_sketch", inputs, |core| {
+            revolve_regions(self.regions().iter(), &surface, axis, angles, core)
+        })
+    }
+}
+
+impl Revolve for Region {
+    fn revolve_between(
+        &self,
+        surface: Handle<Surface>,
+        axis: Line<2>,
+        angles: [impl Into<Scalar>; 2],
+        core: &mut Core,
+    ) -> Result<Solid, RevolveError> {
+        let angles = angles.map(Into::into);
+
+        let inputs: &[&dyn fmt::Debug] = &[self, &surface, &axis, &angles];
+        core.trace_operation("revolve_region", inputs, |core| {
+            let region = self.clone().insert(core);
+            revolve_regions([&region], &surface, axis, angles, core)
+        })
+    }
+}
+
+/// Error revolving a [`Sketch`] or [`Region`]
+///
+/// See [`Revolve::revolve`] and [`Revolve::revolve_between`].
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum RevolveError {
+    /// The surface is not a plane
+    #[error("Surface is not a plane: {surface:#?}")]
+    SurfaceNotAPlane {
+        /// The surface
+        surface: Handle<Surface>,
+    },
+
+    /// The direction of the axis has (almost) zero length
+    #[error("Direction of revolution axis has zero length: {axis:?}")]
+    ZeroLengthAxis {
+        /// The axis
+        axis: Line<2>,
+    },
+
+    /// The angle is not within the range (0, 2pi] radians
+    #[error("Angle of revolution must be in the range (0, 2pi]: {angle}")]
+    AngleOutOfRange {
+        /// The angle
+        angle: Scalar,
+    },
+
+    /// The angle is so close to a full turn, that the revolution can't close
+    ///
+    /// The faces at the start and the end of the revolution would be closer
+    /// to each other than the validation tolerance allows. Use exactly
+    /// [`Scalar::TAU`] to revolve by a full turn.
+    #[error(
+        "Angle of revolution is almost, but not exactly, a full turn: {angle}"
+    )]
+    AlmostFullTurn {
+        /// The angle
+        angle: Scalar,
+    },
+
+    /// A region is not wound counter-clockwise
+    #[error("Region is not wound counter-clockwise: {region:#?}")]
+    RegionNotCounterClockwise {
+        /// The offending region
+        region: Handle<Region>,
+    },
+
+    /// A region does not lie completely on one side of the axis
+    #[error("Region crosses the revolution axis: {region:#?}")]
+    RegionCrossesAxis {
+        /// The offending region
+        region: Handle<Region>,
+    },
+
+    /// A half-edge is neither parallel nor perpendicular to the axis
+    #[error(
+        "Half-edge is neither parallel nor perpendicular to the revolution \
+        axis: {half_edge:#?}"
+    )]
+    UnsupportedHalfEdge {
+        /// The offending half-edge
+        half_edge: Handle<HalfEdge>,
+    },
+}
+
+fn revolve_regions<'r>(
+    regions: impl IntoIterator<Item = &'r Handle<Region>>,
+    surface: &Handle<Surface>,
+    axis: Line<2>,
+    angles: [Scalar; 2],
+    core: &mut Core,
+) -> Result<Solid, RevolveError> {
+    let tolerance = core.layers.validation.config.identical_max_distance;
+
+    let geometry = surface.geometry();
+    let SurfaceGeometry::Swept {
+        u: GlobalPath::Line(u),
+        v,
+    } = geometry
+    else {
+        return Err(RevolveError::SurfaceNotAPlane {
+            surface: surface.clone(),
+        });
+    };
+    let normal = u.direction().cross(v).normalize();
+
+    let origin = geometry.point_from_surface_coords(axis.origin());
+    let direction = geometry.vector_from_surface_coords(axis.direction());
+    if direction.magnitude() <= tolerance {
+        return Err(RevolveError::ZeroLengthAxis { axis });
+    }
+    let direction = direction.normalize();
+
+    let [start_angle, end_angle] = angles;
+    let angle = end_angle - start_angle;
+    if angle <= Scalar::ZERO || angle > Scalar::TAU {
+        return Err(RevolveError::AngleOutOfRange { angle });
+    }
+
+    let mut shells = Vec::new();
+    for region in regions {
+        if !region.exterior().winding().is_ccw() {
+            return Err(RevolveError::RegionNotCounterClockwise {
+                region: region.clone(),
+            });
+        }
+
+        // The radial direction is perpendicular to the axis, within the
+        // surface. It needs to point towards the region.
+        let radial = {
+            let radial = normal.cross(&direction);
+
+            let distances = region
+                .all_cycles()
+                .flat_map(|cycle| cycle.half_edges().iter())
+                .map(|half_edge| {
+                    let point = geometry
+                        .point_from_surface_coords(half_edge.start_position());
+                    (point - origin).dot(&radial)
+                })
+                .collect::<Vec<_>>();
+
+            let is_negative =
+                distances.iter().any(|distance| *distance < -tolerance);
+            let is_positive =
+                distances.iter().any(|distance| *distance > tolerance);
+
+            // The gap between the start and end of the revolution is widest
+            // at the point farthest from the axis. If that is within the
+            // tolerance, the revolution would be a full turn with a seam that
+            // doesn't close.
+            let max_distance = distances
+                .iter()
+                .map(|distance| distance.abs())
+                .fold(Scalar::ZERO, Scalar::max);
+            if angle < Scalar::TAU
+                && max_distance * (Scalar::TAU - angle) <= tolerance
+            {
+                return Err(RevolveError::AlmostFullTurn { angle });
+            }
+
+            match (is_negative, is_positive) {
+                (true, true) => {
+                    return Err(RevolveError::RegionCrossesAxis {
+                        region: region.clone(),
+                    });
+                }
+                (true, false) => -radial,
+                _ => radial,
+            }
+        };
+
+        let mut revolution = Revolution {
+            origin,
+            axis: direction,
+            radial,
+            tangential: direction.cross(&radial),
+            angle,
+            tolerance,
+            color: region.get_color(core),
+            vertices: BTreeMap::new(),
+            curves: BTreeMap::new(),
+            arcs: BTreeMap::new(),
+        };
+
+        // The region bounds the material of the revolution at its start, so
+        // it needs to face against the direction of the revolution.
+        let start_region = if normal.dot(&revolution.tangential) > Scalar::ZERO
+        {
+            region.reverse(core).insert(core).derive_from(region, core)
+        } else {
+            region.clone()
+        };
+
+        // The side faces are bounded by the siblings of the start region's
+        // half-edges. The end region is bounded by rotated versions of those
+        // siblings.
+        let reversed = start_region.reverse(core);
+
+        let mut faces = Vec::new();
+        if !revolution.is_full_turn() {
+            faces.push(Face::new(surface.clone(), start_region).insert(core));
+        }
+        for cycle in reversed.all_cycles() {
+            for (half_edge, next) in cycle.half_edges().pairs() {
+                faces.extend(revolution.revolve_half_edge(
+                    half_edge,
+                    next.start_vertex(),
+                    geometry,
+                    core,
+                )?);
+            }
+        }
+
+        if !revolution.is_full_turn() {
+            let end_surface =
+                surface.transform(&rotation(origin, direction, angle), core);
+
+            let mut cycles = reversed
+                .all_cycles()
+                .map(|cycle| revolution.rotate_cycle(cycle, core))
+                .collect::<Vec<_>>();
+            let exterior = cycles.remove(0);
+
+            let end_region = Region::new(exterior, cycles).insert(core);
+            if let Some(color) = revolution.color {
+                end_region.set_color(color, core);
+            }
+
+            faces.push(Face::new(end_surface, end_region).insert(core));
+        }
+
+        shells.push(Shell::new(faces).insert(core));
+    }
+
+    let solid = Solid::new(shells);
+
+    // The revolution is built starting at the position of the regions. If it
+    // is supposed to start elsewhere, it needs to be rotated there.
+    if start_angle == Scalar::ZERO {
+        return Ok(solid);
+    }
+    Ok(solid.transform(&rotation(origin, direction, start_angle), core))
+}
+
+/// The rotation around the axis through `origin` by the provided angle
+fn rotation(origin: Point<3>, axis: Vector<3>, angle: Scalar) -> Transform {
+    Transform::translation(origin.coords)
+        * Transform::rotation(axis * angle)
+        * Transform::translation(-origin.coords)
+}
+
+struct Revolution {
+    origin: Point<3>,
+    axis: Vector<3>,
+    radial: Vector<3>,
+    tangential: Vector<3>,
+    angle: Scalar,
+    tolerance: Scalar,
+    color: Option<Color>,
+
+    /// The rotated versions of the region's vertices
+    vertices: BTreeMap<ObjectId, Handle<Vertex>>,
+
+    /// The rotated versions of the region's curves
+    curves: BTreeMap<ObjectId, Handle<Curve>>,
+
+    /// The curves that the region's vertices are revolved into
+    arcs: BTreeMap<ObjectId, Handle<Curve>>,
+}
+
+impl Revolution {
+    fn is_full_turn(&self) -> bool {
+        self.angle == Scalar::TAU
+    }
+
+    /// Convert a point into its distance from the axis and its height along it
+    fn cylindrical_coords(&self, point: Point<3>) -> [Scalar; 2] {
+        let offset = point - self.origin;
+
+        let mut r = offset.dot(&self.radial);
+        if r.abs() <= self.tolerance {
+            r = Scalar::ZERO;
+        }
+
+        [r, offset.dot(&self.axis)]
+    }
+
+    /// Revolve a half-edge into a side face
+    ///
+    /// Returns `None`, if the half-edge lies on the axis, and therefore doesn't
+    /// sweep out any area.
+    fn revolve_half_edge(
+        &mut self,
+        half_edge: &Handle<HalfEdge>,
+        end_vertex: &Handle<Vertex>,
+        surface: &SurfaceGeometry,
+        core: &mut Core,
+    ) -> Result<Option<Handle<Face>>, RevolveError> {
+        let unsupported = || RevolveError::UnsupportedHalfEdge {
+            half_edge: half_edge.clone(),
+        };
+
+        let SurfacePath::Line(_) = half_edge.path() else {
+            return Err(unsupported());
+        };
+
+        let [[r_start, z_start], [r_end, z_end]] =
+            half_edge.boundary().inner.map(|point| {
+                let point = half_edge.path().point_from_path_coords(point);
+                self.cylindrical_coords(
+                    surface.point_from_surface_coords(point),
+                )
+            });
+        let [b_start, b_end] = half_edge.boundary().inner;
+        let start_vertex = half_edge.start_vertex();
+
+        let is_on_axis = r_start == Scalar::ZERO && r_end == Scalar::ZERO;
+        let rotated_start = self.rotate_vertex(start_vertex, r_start, core);
+        let rotated_end = self.rotate_vertex(end_vertex, r_end, core);
+        let rotated_curve =
+            self.rotate_curve(half_edge.curve(), is_on_axis, core);
+
+        let angle = self.angle;
+        let [start, end] =
+            [Scalar::ZERO, angle].map(|coord| Point::from([coord]));
+
+        let (surface, exterior, interiors) = if (r_start - r_end).abs()
+            <= self.tolerance
+        {
+            if is_on_axis {
+                return Ok(None);
+            }
+
+            // An edge parallel to the axis sweeps out a cylinder. Its `u`
+            // coordinate is the angle of the revolution.
+            let surface = Surface::surface_from_uv(
+                GlobalPath::Circle(self.circle(z_start, r_start)),
+                self.axis * (z_end - z_start),
+                core,
+            );
+
+            let line = |from: [Scalar; 2], to: [Scalar; 2], boundary| {
+                let [a, b] = boundary;
+                SurfacePath::line_from_points_with_coords([(a, from), (b, to)])
+            };
+            let [zero, one] = [Scalar::ZERO, Scalar::ONE];
+
+            let half_edges = [
+                HalfEdge::new(
+                    line([zero, zero], [zero, one], [b_start, b_end]),
+                    [b_start, b_end],
+                    half_edge.curve().clone(),
+                    start_vertex.clone(),
+                ),
+                HalfEdge::new(
+                    line([zero, one], [angle, one], [start, end]),
+                    [start, end],
+                    self.arc(end_vertex, core),
+                    end_vertex.clone(),
+                ),
+                HalfEdge::new(
+                    line([angle, one], [angle, zero], [b_end, b_start]),
+                    [b_end, b_start],
+                    rotated_curve,
+                    rotated_end,
+                ),
+                HalfEdge::new(
+                    line([angle, zero], [zero, zero], [end, start]),
+                    [end, start],
+                    self.arc(start_vertex, core),
+                    rotated_start,
+                ),
+            ];
+
+            let exterior =
+                Cycle::new(half_edges.map(|half_edge| half_edge.insert(core)))
+                    .insert(core);
+
+            (surface, exterior, Vec::new())
+        } else if (z_start - z_end).abs() <= self.tolerance {
+            // An edge perpendicular to the axis sweeps out a plane, or part of
+            // one.
+            let surface = Surface::surface_from_uv(
+                GlobalPath::Line(Line::from_origin_and_direction(
+                    self.origin + self.axis * z_start,
+                    self.radial,
+                )),
+                self.tangential,
+                core,
+            );
+
+            let circle =
+                |r| SurfacePath::circle_from_center_and_radius([0., 0.], r);
+
+            if self.is_full_turn() {
+                // The edge sweeps out a disk, or an annulus. Those are bounded
+                // by the circles that the vertices sweep out.
+                let mut cycles = Vec::new();
+                if r_end > Scalar::ZERO {
+                    let half_edge = HalfEdge::new(
+                        circle(r_end),
+                        [start, end],
+                        self.arc(end_vertex, core),
+                        end_vertex.clone(),
+                    );
+                    cycles.push((r_end, half_edge));
+                }
+                if r_start > Scalar::ZERO {
+                    let half_edge = HalfEdge::new(
+                        circle(r_start),
+                        [end, start],
+                        self.arc(start_vertex, core),
+                        start_vertex.clone(),
+                    );
+                    cycles.push((r_start, half_edge));
+                }
+                cycles.sort_by_key(|(r, _)| -*r);
+
+                let mut cycles = cycles.into_iter().map(|(_, half_edge)| {
+                    Cycle::new([half_edge.insert(core)]).insert(core)
+                });
+                let exterior = cycles.next().expect(
+                    "Edge is not on axis, so at least one vertex is not",
+                );
+
+                (surface, exterior, cycles.collect())
+            } else {
+                let (sin, cos) = angle.sin_cos();
+                let rotated = |r: Scalar| [r * cos, r * sin];
+
+                let mut half_edges = vec![HalfEdge::new(
+                    SurfacePath::line_from_points_with_coords([
+                        (b_start, [r_start, Scalar::ZERO]),
+                        (b_end, [r_end, Scalar::ZERO]),
+                    ]),
+                    [b_start, b_end],
+                    half_edge.curve().clone(),
+                    start_vertex.clone(),
+                )];
+                if r_end > Scalar::ZERO {
+                    half_edges.push(HalfEdge::new(
+                        circle(r_end),
+                        [start, end],
+                        self.arc(end_vertex, core),
+                        end_vertex.clone(),
+                    ));
+                }
+                half_edges.push(HalfEdge::new(
+                    SurfacePath::line_from_points_with_coords([
+                        (b_end, rotated(r_end)),
+                        (b_start, rotated(r_start)),
+                    ]),
+                    [b_end, b_start],
+                    rotated_curve,
+                    rotated_end,
+                ));
+                if r_start > Scalar::ZERO {
+                    half_edges.push(HalfEdge::new(
+                        circle(r_start),
+                        [end, start],
+                        self.arc(start_vertex, core),
+                        rotated_start,
+                    ));
+                }
+
+                let exterior = Cycle::new(
+                    half_edges
+                        .into_iter()
+                        .map(|half_edge| half_edge.insert(core)),
+                )
+                .insert(core);
+
+                (surface, exterior, Vec::new())
+            }
+        } else {
+            return Err(unsupported());
+        };
+
+        let region = Region::new(exterior, interiors).insert(core);
+        if let Some(color) = self.color {
+            region.set_color(color, core);
+        }
+
+        Ok(Some(Face::new(surface, region).insert(core)))
+    }
+
+    /// Create the cycle that bounds the end of a partial revolution
+    fn rotate_cycle(&self, cycle: &Cycle, core: &mut Core) -> Handle<Cycle> {
+        let half_edges = cycle.half_edges().iter().map(|half_edge| {
+            HalfEdge::new(
+                half_edge.path(),
+                half_edge.boundary(),
+                self.curves[&half_edge.curve().id()].clone(),
+                self.vertices[&half_edge.start_vertex().id()].clone(),
+            )
+            .insert(core)
+        });
+
+        Cycle::new(half_edges.collect::<Vec<_>>()).insert(core)
+    }
+
+    fn rotate_vertex(
+        &mut self,
+        vertex: &Handle<Vertex>,
+        r: Scalar,
+        core: &mut Core,
+    ) -> Handle<Vertex> {
+        // Rotating a vertex on the axis, or by a full turn, doesn't move it.
+        let is_unmoved = r == Scalar::ZERO || self.is_full_turn();
+
+        self.vertices
+            .entry(vertex.id())
+            .or_insert_with(|| {
+                if is_unmoved {
+                    vertex.clone()
+                } else {
+                    Vertex::new().insert(core)
+                }
+            })
+            .clone()
+    }
+
+    fn rotate_curve(
+        &mut self,
+        curve: &Handle<Curve>,
+        is_on_axis: bool,
+        core: &mut Core,
+    ) -> Handle<Curve> {
+        let is_unmoved = is_on_axis || self.is_full_turn();
+
+        self.curves
+            .entry(curve.id())
+            .or_insert_with(|| {
+                if is_unmoved {
+                    curve.clone()
+                } else {
+                    Curve::new().insert(core)
+                }
+            })
+            .clone()
+    }
+
+    fn arc(
+        &mut self,
+        vertex: &Handle<Vertex>,
+        core: &mut Core,
+    ) -> Handle<Curve> {
+        self.arcs
+            .entry(vertex.id())
+            .or_insert_with(|| Curve::new().insert(core))
+            .clone()
+    }
+
+    /// The circle that a point at the provided height and radius sweeps out
+    fn circle(&self, z: Scalar, r: Scalar) -> Circle<3> {
+        let a = self.radial * r;
+        let b = self.tangential * r;
+
+        // `Circle` requires both vectors to be of exactly the same length,
+        // which rounding errors might violate.
+        let b = b * (a.magnitude() / b.magnitude());
+
+        Circle::new(self.origin + self.axis * z, a, b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Line, Point, Scalar, Vector};
+    use fj_test_utils::{
+        assert_scalars_eq, assert_volume_eq, assert_watertight,
+    };
+
+    use crate::{
+        algorithms::{approx::Tolerance, triangulate::Triangulate},
+        objects::{Region, Sketch},
+        operations::{
+            build::{BuildRegion, BuildSketch},
+            insert::Insert,
+            update::UpdateSketch,
+        },
+        Core,
+    };
+
+    use super::{Revolve, RevolveError};
+
+    #[test]
+    fn revolve() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let surface = core.layers.objects.surfaces.xy_plane();
+        let y_axis = Line::from_origin_and_direction(
+            Point::origin(),
+            Vector::from([0., 1.]),
+        );
+        let tolerance = Tolerance::from_scalar(0.01)?;
+
+        for (square, angle, num_faces, min_x_expected) in [
+            // A square away from the axis becomes a tube, or part of one.
+            ([1., 0.], Scalar::TAU, 4, -2.),
+            ([1., 0.], Scalar::PI, 6, -2.),
+            // A square touching the axis becomes a cylinder, or part of one.
+            ([0., 0.], Scalar::TAU, 3, -1.),
+            ([0., 0.], Scalar::PI / 2., 5, 0.),
+        ] {
+            let [x, y] = square;
+            let sketch = Sketch::empty().add_regions(
+                [Region::polygon(
+                    [[x, y], [x + 1., y], [x + 1., y + 1.], [x, y + 1.]],
+                    &mut core,
+                )],
+                &mut core,
+            );
+
+            let solid = sketch
+                .revolve(surface.clone(), y_axis, angle, &mut core)?
+                .insert(&mut core);
+            core.layers.validation.take_errors()?;
+
+            assert_eq!(solid.shells().only().faces().len(), num_faces);
+
+            // The AABB of curved faces is not precise, so let's check the
+            // triangle mesh instead.
+            let mesh = (&*solid, tolerance).triangulate(&mut core);
+            let min_x = mesh
+                .vertices()
+                .map(|point| point.x)
+                .min()
+                .expect("Expected non-empty mesh");
+            let max_y = mesh.vertices().map(|point| point.y).max();
+            assert_scalars_eq!(min_x, min_x_expected, 0.01);
+            assert_eq!(max_y, Some(Scalar::from(y + 1.)));
+
+            let expected_volume = angle / 2. * ((x + 1.) * (x + 1.) - x * x);
+            assert_watertight!(mesh);
+            assert_volume_eq!(mesh, expected_volume, expected_volume * 0.05);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn revolve_between() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let surface = core.layers.objects.surfaces.xy_plane();
+        let y_axis = Line::from_origin_and_direction(
+            Point::origin(),
+            Vector::from([0., 1.]),
+        );
+        let tolerance = Tolerance::from_scalar(0.01)?;
+
+        let square = Region::polygon(
+            [[1., 0.], [2., 0.], [2., 1.], [1., 1.]],
+            &mut core,
+        );
+
+        // An elbow that is centered on the sketch plane.
+        let elbow = square
+            .revolve_between(
+                surface.clone(),
+                y_axis,
+                [-Scalar::PI / 4., Scalar::PI / 4.],
+                &mut core,
+            )?
+            .insert(&mut core);
+        core.layers.validation.take_errors()?;
+        assert_eq!(elbow.shells().only().faces().len(), 6);
+
+        let mesh = (&*elbow, tolerance).triangulate(&mut core);
+        let [min_x, max_x, min_z, max_z] = [
+            mesh.vertices().map(|point| point.x).min(),
+            mesh.vertices().map(|point| point.x).max(),
+            mesh.vertices().map(|point| point.z).min(),
+            mesh.vertices().map(|point| point.z).max(),
+        ]
+        .map(|value| value.expect("Expected non-empty mesh"));
+        let (sin, cos) = (Scalar::PI / 4.).sin_cos();
+        for (actual, expected) in [
+            (min_x, cos),
+            (max_x, Scalar::from(2.)),
+            (min_z, sin * -2.),
+            (max_z, sin * 2.),
+        ] {
+            assert_scalars_eq!(actual, expected, 0.01);
+        }
+
+        // A full turn that starts elsewhere still closes onto itself.
+        let ring = square
+            .revolve_between(
+                surface.clone(),
+                y_axis,
+                [Scalar::PI, Scalar::PI * 3.],
+                &mut core,
+            )?
+            .insert(&mut core);
+        core.layers.validation.take_errors()?;
+        assert_eq!(ring.shells().only().faces().len(), 4);
+
+        Ok(())
+    }
+
+    #[test]
+    fn almost_full_turn() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let surface = core.layers.objects.surfaces.xy_plane();
+        let y_axis = Line::from_origin_and_direction(
+            Point::origin(),
+            Vector::from([0., 1.]),
+        );
+
+        let square = Region::polygon(
+            [[1., 0.], [2., 0.], [2., 1.], [1., 1.]],
+            &mut core,
+        );
+
+        let result = square.revolve(
+            surface.clone(),
+            y_axis,
+            Scalar::TAU - Scalar::from(1e-15),
+            &mut core,
+        );
+        assert!(matches!(result, Err(RevolveError::AlmostFullTurn { .. })));
+
+        // An angle that leaves a noticeable gap is fine.
+        let _ = square
+            .revolve(
+                surface,
+                y_axis,
+                Scalar::TAU - Scalar::from(0.1),
+                &mut core,
+            )?
+            .insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn unsupported_regions() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let surface = core.layers.objects.surfaces.xy_plane();
+        let y_axis = Line::from_origin_and_direction(
+            Point::origin(),
+            Vector::from([0., 1.]),
+        );
+
+        let crossing = Region::polygon(
+            [[-1., 0.], [1., 0.], [1., 1.], [-1., 1.]],
+            &mut core,
+        );
+        let result =
+            crossing.revolve(surface.clone(), y_axis, Scalar::TAU, &mut core);
+        assert!(matches!(
+            result,
+            Err(RevolveError::RegionCrossesAxis { .. })
+        ));
+
+        let triangle =
+            Region::polygon([[1., 0.], [2., 0.], [1., 1.]], &mut core);
+        let result =
+            triangle.revolve(surface.clone(), y_axis, Scalar::TAU, &mut core);
+        assert!(matches!(
+            result,
+            Err(RevolveError::UnsupportedHalfEdge { .. })
+        ));
+
+        let square = Region::polygon(
+            [[1., 0.], [2., 0.], [2., 1.], [1., 1.]],
+            &mut core,
+        );
+        let result = square.revolve(surface, y_axis, Scalar::ZERO, &mut core);
+        assert!(matches!(result, Err(RevolveError::AngleOutOfRange { .. })));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,233 @@
+//! Section solids with a plane
+//!
+//! See [`SectionWithPlane`].
+
+use std::fmt;
+
+use fj_math::Plane;
+
+use crate::{
+    algorithms::{
+        approx::Tolerance,
+        slice::{section, SlicePolygon},
+        triangulate::Triangulate,
+    },
+    geometry::{GlobalPath, SurfaceGeometry},
+    objects::{Cycle, Region, Sketch, Solid, Surface},
+    storage::Handle,
+    Core,
+};
+
+use super::{build::BuildCycle, insert::Insert};
+
+/// Intersect a shape with a plane
+pub trait SectionWithPlane {
+    /// Create a sketch of the cross-section of the shape with the plane
+    ///
+    /// The sketch is defined in the coordinates of the provided surface, which
+    /// must be a plane. It contains a region for each area, in which the plane
+    /// cuts through the shape. Cavities of the shape become interiors of those
+    /// regions. Since the orientation of the cycles is derived from that of the
+    /// surface, the sketch can be swept on it right away.
+    ///
+    /// # Implementation Note
+    ///
+    /// The shape is approximated with the provided tolerance, and the
+    /// approximation is intersected with the plane. Where the resulting cycles
+    /// run along curved faces, they are polygons that are within the tolerance
+    /// of the actual cross-section. Straight sections of the cycles are
+    /// simplified, so they don't have more vertices than necessary.
+    ///
+    /// Faces that lie within the plane are considered to be on the positive
+    /// side of it (the side its normal points to). A plane that contains the
+    /// bottom face of a shape doesn't cut through it, for example.
+    fn section_with_plane(
+        &self,
+        surface: &Handle<Surface>,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Result<Sketch, SectionWithPlaneError>;
+}
+
+impl SectionWithPlane for Solid {
+    fn section_with_plane(
+        &self,
+        surface: &Handle<Surface>,
+        tolerance: impl Into<Tolerance>,
+        core: &mut Core,
+    ) -> Result<Sketch, SectionWithPlaneError> {
+        let tolerance = tolerance.into();
+
+        let SurfaceGeometry::Swept {
+            u: GlobalPath::Line(u),
+            v,
+        } = surface.geometry()
+        else {
+            return Err(SectionWithPlaneError::SurfaceNotAPlane {
+                surface: surface.clone(),
+            });
+        };
+        let plane = Plane::from_parametric(u.origin(), u.direction(), *v);
+
+        let inputs: &[&dyn fmt::Debug] = &[self, surface, &tolerance];
+        core.trace_operation("section_with_plane", inputs, |core| {
+            let mesh = (self, tolerance).triangulate(core);
+            let polygons = section(
+                mesh.triangles().map(|triangle| triangle.inner),
+                &plane,
+                tolerance,
+            );
+
+            let regions = polygons
+                .into_iter()
+                .map(
+                    |SlicePolygon {
+                         exterior,
+                         interiors,
+                     }| {
+                        let exterior =
+                            Cycle::polygon(exterior, core).insert(core);
+                        let interiors = interiors
+                            .into_iter()
+                            .map(|interior| {
+                                Cycle::polygon(interior, core).insert(core)
+                            })
+                            .collect::<Vec<_>>();
+
+                        Region::new(exterior, interiors).insert(core)
+                    },
+                )
+                .collect::<Vec<_>>();
+
+            Ok(Sketch::new(regions))
+        })
+    }
+}
+
+/// Error sectioning a shape with a plane
+///
+/// See [`SectionWithPlane::section_with_plane`].
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum SectionWithPlaneError {
+    /// The surface is not a plane
+    #[error("Surface is not a plane: {surface:#?}")]
+    SurfaceNotAPlane {
+        /// The surface
+        surface: Handle<Surface>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Scalar;
+    use fj_test_utils::assert_scalars_eq;
+
+    use crate::{
+        algorithms::measure::Measure,
+        geometry::GlobalPath,
+        objects::{Shell, Solid, Surface},
+        operations::{
+            build::{BuildShell, BuildSolid, BuildSurface},
+            insert::Insert,
+            sweep::SweepSketch,
+            update::UpdateSolid,
+        },
+        Core,
+    };
+
+    use super::{SectionWithPlane, SectionWithPlaneError};
+
+    #[test]
+    fn section_cuboid() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let shell = Shell::cuboid([0., 0., 0.], [2., 1., 1.], &mut core)
+            .insert(&mut core);
+        let solid = Solid::empty().add_shells([shell], &mut core);
+
+        // A plane that cuts through the cuboid diagonally.
+        let (surface, _) = Surface::plane_from_points(
+            [[0., 0., 0.5], [2., 0., 0.5], [0., 1., 0.25]],
+            &mut core,
+        );
+        let sketch = solid.section_with_plane(&surface, 0.01, &mut core)?;
+
+        let [region] = sketch.regions().iter().collect::<Vec<_>>()[..] else {
+            panic!("Expected one region");
+        };
+        assert!(region.exterior().winding().is_ccw());
+        assert_eq!(region.exterior().half_edges().len(), 4);
+        assert_eq!(region.interiors().len(), 0);
+
+        // The axes of the plane are longer than the unit vectors, so in
+        // surface coordinates, the cross-section is a unit square.
+        let area = region.area(0.01, &mut core);
+        assert_scalars_eq!(area, 1., 1e-9);
+
+        let _ = sketch
+            .sweep_sketch(surface, [0., 0., 0.1], &mut core)
+            .insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn section_hollow_solid() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let [outer, inner] = [
+            Shell::cuboid([-2., -2., -1.], [2., 2., 3.], &mut core),
+            Shell::cuboid([-1., -1., 0.], [1., 1., 2.], &mut core),
+        ];
+        let solid = Solid::empty().add_shells(
+            [outer, inner].map(|shell| shell.insert(&mut core)),
+            &mut core,
+        );
+
+        let (surface, _) = Surface::plane_from_points(
+            [[0., 0., 1.], [1., 0., 1.], [0., 1., 1.]],
+            &mut core,
+        );
+        let sketch = solid.section_with_plane(&surface, 0.01, &mut core)?;
+
+        let [region] = sketch.regions().iter().collect::<Vec<_>>()[..] else {
+            panic!("Expected one region");
+        };
+        assert_eq!(region.interiors().len(), 1);
+        for interior in region.interiors() {
+            assert!(!interior.winding().is_ccw());
+        }
+        assert_eq!(region.area(0.01, &mut core), Scalar::from(12.));
+
+        // A plane that misses the solid results in an empty sketch.
+        let (surface, _) = Surface::plane_from_points(
+            [[0., 0., 5.], [1., 0., 5.], [0., 1., 5.]],
+            &mut core,
+        );
+        let sketch = solid.section_with_plane(&surface, 0.01, &mut core)?;
+        assert_eq!(sketch.regions().len(), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn section_with_curved_surface() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let solid = Solid::cylinder(1., 2., &mut core)?;
+        let surface = Surface::surface_from_uv(
+            GlobalPath::circle_from_radius(1.),
+            [0., 0., 1.],
+            &mut core,
+        );
+
+        let result = solid.section_with_plane(&surface, 0.01, &mut core);
+        assert!(matches!(
+            result,
+            Err(SectionWithPlaneError::SurfaceNotAPlane { .. })
+        ));
+
+        Ok(())
+    }
+}
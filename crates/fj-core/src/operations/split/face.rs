@@ -1,9 +1,13 @@
+use std::fmt;
+
 use fj_interop::ext::ArrayExt;
-use fj_math::Point;
+use fj_math::{Point, Scalar};
 use itertools::Itertools;
 
 use crate::{
-    objects::{Cycle, Face, HalfEdge, Shell},
+    algorithms::intersect::curve_curve::CurveCurveIntersection,
+    geometry::{CurveBoundary, SurfacePath},
+    objects::{Curve, Cycle, Face, HalfEdge, Shell},
     operations::{
         build::{BuildCycle, BuildHalfEdge},
         derive::DeriveFrom,
@@ -14,6 +18,7 @@ use crate::{
         },
     },
     storage::Handle,
+    validation::ValidationConfig,
     Core,
 };
 
@@ -42,6 +47,26 @@ pub trait SplitFace: Sized {
         line: [(&Handle<HalfEdge>, impl Into<Point<1>>); 2],
         core: &mut Core,
     ) -> (Self, [Handle<Face>; 2]);
+
+    /// Split the face into two, along the provided path
+    ///
+    /// The path must lie within the surface of the face. The provided boundary
+    /// defines the section of the path that splits the face. It must start and
+    /// end on half-edges of the face's exterior, but not on any of their
+    /// vertices, and must not cross the boundary of the face anywhere else.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if either end of the section is not on a half-edge of the
+    /// face's exterior, or if both ends are on the same half-edge.
+    #[must_use]
+    fn split_face_along_path(
+        &self,
+        face: &Handle<Face>,
+        path: SurfacePath,
+        boundary: impl Into<CurveBoundary<Point<1>>>,
+        core: &mut Core,
+    ) -> (Self, [Handle<Face>; 2]);
 }
 
 impl SplitFace for Shell {
@@ -70,130 +95,236 @@ impl SplitFace for Shell {
             assert!(exterior.half_edges().contains(b));
         }
 
-        let mut self_ = self.clone();
-
-        let [[a, b], [c, d]] = line.map(|(half_edge, point)| {
-            let (shell, [[a, b], _]) = self_.split_edge(half_edge, point, core);
-            self_ = shell;
-            [a, b]
-        });
-
-        // The original face doesn't exist in the updated shell, as it's been
-        // replaced by a new version due to the edge splitting. Let's find the
-        // face that replaced it.
-        let mut updated_face_after_split_edges = None;
-        for f in self_.faces() {
-            let half_edges = f.region().exterior().half_edges();
-
-            if half_edges.contains(&a)
-                && half_edges.contains(&b)
-                && half_edges.contains(&c)
-                && half_edges.contains(&d)
-            {
-                assert!(
-                    updated_face_after_split_edges.is_none(),
-                    "There should never be two faces that share half-edges"
-                );
-                updated_face_after_split_edges = Some(f);
-            }
-        }
-        let updated_face_after_split_edges = updated_face_after_split_edges
-            .expect("Updated shell must contain updated face");
-
-        // Build the edge that's going to divide the new faces.
-        let dividing_half_edge_a_to_d = HalfEdge::line_segment(
-            [b.start_position(), d.start_position()],
-            None,
-            core,
-        )
-        .update_start_vertex(|_, _| b.start_vertex().clone(), core);
-        let dividing_half_edge_c_to_b = HalfEdge::from_sibling(
-            &dividing_half_edge_a_to_d,
-            d.start_vertex().clone(),
-        );
+        let line = line.map(|(half_edge, point)| (half_edge, point.into()));
 
-        let mut half_edges_of_face_starting_at_b =
-            updated_face_after_split_edges
-                .region()
-                .exterior()
-                .half_edges()
-                .iter()
-                .cloned()
-                .cycle()
-                .skip_while(|half_edge| half_edge != &b);
-
-        let half_edges_b_to_c_inclusive = half_edges_of_face_starting_at_b
-            .take_while_ref(|half_edge| half_edge != &d);
-        let split_face_a = updated_face_after_split_edges
-            .update_region(
-                |region, core| {
-                    region.update_exterior(
-                        |_, core| {
-                            Cycle::empty()
-                                .add_half_edges(
-                                    half_edges_b_to_c_inclusive,
-                                    core,
-                                )
-                                .add_half_edges(
-                                    [dividing_half_edge_c_to_b],
-                                    core,
-                                )
-                        },
+        core.trace_operation("split_face", &[self, face, &line], |core| {
+            split_face(
+                self,
+                line,
+                |[b, d], core| {
+                    HalfEdge::line_segment(
+                        [b.start_position(), d.start_position()],
+                        None,
                         core,
                     )
+                    .update_start_vertex(|_, _| b.start_vertex().clone(), core)
                 },
                 core,
             )
-            .insert(core)
-            .derive_from(updated_face_after_split_edges, core);
-
-        // The previous operation has moved the iterator along.
-        let half_edges_of_face_starting_at_d = half_edges_of_face_starting_at_b;
-
-        let half_edges_d_to_a_inclusive = half_edges_of_face_starting_at_d
-            .take_while(|half_edge| half_edge != &b);
-        let split_face_b = updated_face_after_split_edges
-            .update_region(
-                |region, core| {
-                    region.update_exterior(
-                        |_, core| {
-                            Cycle::empty()
-                                .add_half_edges(
-                                    half_edges_d_to_a_inclusive,
-                                    core,
-                                )
-                                .add_half_edges(
-                                    [dividing_half_edge_a_to_d],
-                                    core,
-                                )
-                        },
-                        core,
+        })
+    }
+
+    fn split_face_along_path(
+        &self,
+        face: &Handle<Face>,
+        path: SurfacePath,
+        boundary: impl Into<CurveBoundary<Point<1>>>,
+        core: &mut Core,
+    ) -> (Self, [Handle<Face>; 2]) {
+        let boundary = boundary.into();
+
+        let inputs: &[&dyn fmt::Debug] = &[self, face, &path, &boundary];
+        core.trace_operation("split_face_along_path", inputs, |core| {
+            let config = core.layers.validation.config;
+
+            let [a, b] = boundary
+                .inner
+                .map(|point| find_half_edge_at(face, &path, point, &config));
+            assert_ne!(
+                a.0.id(),
+                b.0.id(),
+                "Both ends of split path must not be on the same half-edge"
+            );
+
+            split_face(
+                self,
+                [(&a.0, a.1), (&b.0, b.1)],
+                |[b, _], core| {
+                    HalfEdge::new(
+                        path,
+                        boundary,
+                        Curve::new().insert(core),
+                        b.start_vertex().clone(),
                     )
                 },
                 core,
             )
-            .insert(core)
-            .derive_from(updated_face_after_split_edges, core);
+        })
+    }
+}
+
+/// Split a face along a dividing half-edge
+///
+/// Splits the half-edges at the provided points first. The dividing half-edge
+/// is then built from the second parts of those, and must go from the start of
+/// the first to the start of the second.
+fn split_face(
+    shell: &Shell,
+    line: [(&Handle<HalfEdge>, Point<1>); 2],
+    dividing_half_edge: impl FnOnce([&Handle<HalfEdge>; 2], &mut Core) -> HalfEdge,
+    core: &mut Core,
+) -> (Shell, [Handle<Face>; 2]) {
+    let mut self_ = shell.clone();
+
+    let [[a, b], [c, d]] = line.map(|(half_edge, point)| {
+        let (shell, [[a, b], _]) = self_.split_edge(half_edge, point, core);
+        self_ = shell;
+        [a, b]
+    });
+
+    // The original face doesn't exist in the updated shell, as it's been
+    // replaced by a new version due to the edge splitting. Let's find the face
+    // that replaced it.
+    let mut updated_face_after_split_edges = None;
+    for f in self_.faces() {
+        let half_edges = f.region().exterior().half_edges();
+
+        if half_edges.contains(&a)
+            && half_edges.contains(&b)
+            && half_edges.contains(&c)
+            && half_edges.contains(&d)
+        {
+            assert!(
+                updated_face_after_split_edges.is_none(),
+                "There should never be two faces that share half-edges"
+            );
+            updated_face_after_split_edges = Some(f);
+        }
+    }
+    let updated_face_after_split_edges = updated_face_after_split_edges
+        .expect("Updated shell must contain updated face");
+
+    // Build the edge that's going to divide the new faces.
+    let dividing_half_edge_a_to_d = dividing_half_edge([&b, &d], core);
+    let dividing_half_edge_c_to_b = HalfEdge::from_sibling(
+        &dividing_half_edge_a_to_d,
+        d.start_vertex().clone(),
+    );
+
+    let mut half_edges_of_face_starting_at_b = updated_face_after_split_edges
+        .region()
+        .exterior()
+        .half_edges()
+        .iter()
+        .cloned()
+        .cycle()
+        .skip_while(|half_edge| half_edge != &b);
 
-        let faces = [split_face_a, split_face_b];
-        let self_ = self_.update_face(
-            updated_face_after_split_edges,
-            |_, _| faces.clone(),
+    let half_edges_b_to_c_inclusive = half_edges_of_face_starting_at_b
+        .take_while_ref(|half_edge| half_edge != &d);
+    let split_face_a = updated_face_after_split_edges
+        .update_region(
+            |region, core| {
+                region.update_exterior(
+                    |_, core| {
+                        Cycle::empty()
+                            .add_half_edges(half_edges_b_to_c_inclusive, core)
+                            .add_half_edges([dividing_half_edge_c_to_b], core)
+                    },
+                    core,
+                )
+            },
             core,
-        );
+        )
+        .insert(core)
+        .derive_from(updated_face_after_split_edges, core);
+
+    // The previous operation has moved the iterator along.
+    let half_edges_of_face_starting_at_d = half_edges_of_face_starting_at_b;
+
+    let half_edges_d_to_a_inclusive = half_edges_of_face_starting_at_d
+        .take_while(|half_edge| half_edge != &b);
+    let split_face_b = updated_face_after_split_edges
+        .update_region(
+            |region, core| {
+                region.update_exterior(
+                    |_, core| {
+                        Cycle::empty()
+                            .add_half_edges(half_edges_d_to_a_inclusive, core)
+                            .add_half_edges([dividing_half_edge_a_to_d], core)
+                    },
+                    core,
+                )
+            },
+            core,
+        )
+        .insert(core)
+        .derive_from(updated_face_after_split_edges, core);
+
+    let faces = [split_face_a, split_face_b];
+    let self_ = self_.update_face(
+        updated_face_after_split_edges,
+        |_, _| faces.clone(),
+        core,
+    );
+
+    (self_, faces)
+}
+
+/// Find the half-edge of the face's exterior, that the point on the path is on
+///
+/// Returns the half-edge, and the position of the point on it.
+fn find_half_edge_at(
+    face: &Face,
+    path: &SurfacePath,
+    point: Point<1>,
+    config: &ValidationConfig,
+) -> (Handle<HalfEdge>, Point<1>) {
+    let point_surface = path.point_from_path_coords(point);
+
+    for half_edge in face.region().exterior().half_edges() {
+        let Some(CurveCurveIntersection::Points(intersections)) =
+            CurveCurveIntersection::compute([path, &half_edge.path()], config)
+        else {
+            continue;
+        };
+
+        let [start, end] = half_edge.boundary().inner.map(|point| point.t);
+        let (min, max) = if start < end {
+            (start, end)
+        } else {
+            (end, start)
+        };
+
+        for intersection in intersections {
+            if intersection.point_surface.distance_to(&point_surface)
+                > config.distinct_min_distance
+            {
+                continue;
+            }
 
-        (self_, faces)
+            // The coordinates of intersections with circles and ellipses are
+            // normalized, but the boundary of the half-edge might not be.
+            let t = intersection.points_on_curves[1].t;
+            let candidates = match half_edge.path() {
+                SurfacePath::Circle(_) | SurfacePath::Ellipse(_) => {
+                    vec![t - Scalar::TAU, t, t + Scalar::TAU]
+                }
+                SurfacePath::CubicBezier(_) | SurfacePath::Line(_) => vec![t],
+            };
+
+            for t in candidates {
+                if min < t && t < max {
+                    return (half_edge.clone(), Point::from([t]));
+                }
+            }
+        }
     }
+
+    panic!("End of split path is not on the exterior of the face");
 }
 
 #[cfg(test)]
 mod tests {
     use fj_interop::Color;
+    use fj_math::{Point, Scalar};
 
     use crate::{
+        geometry::SurfacePath,
         objects::Shell,
         operations::{
             build::BuildShell,
+            insert::Insert,
             presentation::{GetColor, SetColor},
             split::SplitFace,
         },
@@ -225,4 +356,53 @@ mod tests {
         assert_eq!(face_a.region().get_color(&mut core), Some(color));
         assert_eq!(face_b.region().get_color(&mut core), Some(color));
     }
+
+    #[test]
+    fn split_face_along_path() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let cuboid = Shell::cuboid([0., 0., 0.], [1., 1., 1.], &mut core);
+        let face = cuboid.faces().first().clone();
+
+        // Cut off one corner of the face, using an arc around that corner.
+        let half_edges = face.region().exterior().half_edges();
+        let [previous, next] = [0, 1].map(|i| half_edges.nth_circular(i));
+        let corner = next.start_position();
+        let angle_of = |point: Point<2>| {
+            let direction = point - corner;
+            direction.v.atan2(direction.u)
+        };
+        let start = angle_of(
+            next.path().point_from_path_coords(next.boundary().inner[1]),
+        );
+        let mut end = angle_of(previous.start_position());
+        if end < start {
+            end += Scalar::TAU;
+        }
+        assert!(end - start < Scalar::PI, "Arc must be within the face");
+
+        let (shell, faces) = cuboid.split_face_along_path(
+            &face,
+            SurfacePath::circle_from_center_and_radius(corner, 0.5),
+            [[start], [end]],
+            &mut core,
+        );
+        let shell = shell.insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        assert_eq!(shell.faces().len(), 7);
+        for face in faces {
+            let has_arc =
+                face.region()
+                    .exterior()
+                    .half_edges()
+                    .iter()
+                    .any(|half_edge| {
+                        matches!(half_edge.path(), SurfacePath::Circle(_))
+                    });
+            assert!(has_arc);
+        }
+
+        Ok(())
+    }
 }
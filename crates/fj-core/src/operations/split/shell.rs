@@ -0,0 +1,66 @@
+use crate::{objects::Shell, queries::ConnectedFaces, Core};
+
+/// Split a shell into its connected parts
+pub trait SplitShell: Sized {
+    /// Split the shell into the parts that are connected to each other
+    ///
+    /// Returns one shell for each part. If the shell is already connected, the
+    /// only returned shell has the same faces as the original one.
+    ///
+    /// See [`ConnectedFaces`] for what it means for faces to be connected.
+    #[must_use]
+    fn split_into_components(&self, core: &mut Core) -> Vec<Self>;
+}
+
+impl SplitShell for Shell {
+    fn split_into_components(&self, core: &mut Core) -> Vec<Self> {
+        core.trace_operation("split_into_components", &[self], |_| {
+            self.connected_components()
+                .into_iter()
+                .map(Shell::new)
+                .collect()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        objects::Shell,
+        operations::build::BuildShell,
+        validation::{checks::ShellNotConnected, ValidationCheck},
+        Core,
+    };
+
+    use super::SplitShell;
+
+    #[test]
+    fn split_into_components() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let a = Shell::tetrahedron(
+            [[0., 0., 0.], [0., 1., 0.], [1., 0., 0.], [0., 0., 1.]],
+            &mut core,
+        );
+        let b = Shell::tetrahedron(
+            [[2., 0., 0.], [2., 1., 0.], [3., 0., 0.], [2., 0., 1.]],
+            &mut core,
+        );
+
+        let connected = a.shell.split_into_components(&mut core);
+        assert_eq!(connected.len(), 1);
+        assert_eq!(connected[0].faces(), a.shell.faces());
+
+        let shell =
+            Shell::new(a.shell.faces().iter().chain(b.shell.faces()).cloned());
+        let parts = shell.split_into_components(&mut core);
+
+        assert_eq!(parts.len(), 2);
+        for (part, expected) in parts.into_iter().zip([a.shell, b.shell]) {
+            ShellNotConnected::check_and_return_first_error(&part)?;
+            assert_eq!(part.faces(), expected.faces());
+        }
+
+        Ok(())
+    }
+}
@@ -1,3 +1,5 @@
+use std::fmt;
+
 use fj_interop::Color;
 use fj_math::Vector;
 
@@ -57,40 +59,44 @@ impl SweepCycle for Cycle {
     ) -> SweptCycle {
         let path = path.into();
 
-        let mut faces = Vec::new();
-        let mut top_edges = Vec::new();
+        let inputs: &[&dyn fmt::Debug] = &[self, surface, &color, &path];
+        core.trace_operation("sweep_cycle", inputs, |core| {
+            let mut faces = Vec::new();
+            let mut top_edges = Vec::new();
 
-        for bottom_half_edge_pair in self.half_edges().pairs() {
-            let (bottom_half_edge, bottom_half_edge_next) =
-                bottom_half_edge_pair;
+            for bottom_half_edge_pair in self.half_edges().pairs() {
+                let (bottom_half_edge, bottom_half_edge_next) =
+                    bottom_half_edge_pair;
 
-            let (side_face, top_edge) = bottom_half_edge.sweep_half_edge(
-                bottom_half_edge_next.start_vertex().clone(),
-                surface,
-                color,
-                path,
-                cache,
-                core,
-            );
+                let (side_face, top_edge) = bottom_half_edge.sweep_half_edge(
+                    bottom_half_edge_next.start_vertex().clone(),
+                    surface,
+                    color,
+                    path,
+                    cache,
+                    core,
+                );
 
-            faces.push(side_face);
+                faces.push(side_face);
 
-            top_edges.push((
-                top_edge,
-                bottom_half_edge.path(),
-                bottom_half_edge.boundary(),
-            ));
-        }
+                top_edges.push((
+                    top_edge,
+                    bottom_half_edge.path(),
+                    bottom_half_edge.boundary(),
+                ));
+            }
 
-        let top_cycle = Cycle::empty().add_joined_edges(top_edges, core);
+            let top_cycle = Cycle::empty().add_joined_edges(top_edges, core);
 
-        SweptCycle { faces, top_cycle }
+            SweptCycle { faces, top_cycle }
+        })
     }
 }
 
 /// The result of sweeping a [`Cycle`]
 ///
 /// See [`SweepCycle`].
+#[derive(Debug)]
 pub struct SweptCycle {
     /// The faces created by sweeping each half-edge of the cycle
     ///
@@ -16,53 +16,169 @@ use super::{SweepCache, SweepRegion};
 /// [module documentation]: super
 pub trait SweepFace {
     /// # Sweep the [`Face`] into a [`Shell`]
+    #[deprecated(
+        since = "0.49.0",
+        note = "Use `SweepFace::sweep_face_into_parts`, then \
+            `SweptShell::into_shell`."
+    )]
     fn sweep_face(
         &self,
         path: impl Into<Vector<3>>,
         cache: &mut SweepCache,
         core: &mut Core,
-    ) -> Shell;
+    ) -> Shell {
+        self.sweep_face_into_parts(path, cache, core).into_shell()
+    }
+
+    /// # Sweep the [`Face`] into a [`Shell`], keeping track of its parts
+    ///
+    /// Returns the faces of the shell, sorted by their role in the sweep. Call
+    /// [`SweptShell::into_shell`] to get the [`Shell`] itself.
+    fn sweep_face_into_parts(
+        &self,
+        path: impl Into<Vector<3>>,
+        cache: &mut SweepCache,
+        core: &mut Core,
+    ) -> SweptShell;
 }
 
 impl SweepFace for Handle<Face> {
-    fn sweep_face(
+    fn sweep_face_into_parts(
         &self,
         path: impl Into<Vector<3>>,
         cache: &mut SweepCache,
         core: &mut Core,
-    ) -> Shell {
-        // Please note that this function uses the words "bottom" and "top" in a
-        // specific sense:
-        //
-        // - "Bottom" refers to the origin of the sweep. The bottom face is the
-        //   original face, or a face in the same place.
-        // - "Top" refers to the location of the face that was created by
-        //   translating the bottom face along the path.
-        // - "Side" refers to new faces created in between bottom and top.
-        //
-        // These words are specifically *not* meant in the sense of z-axis
-        // locations, and depending on the direction of `path`, the two meanings
-        // might actually be opposite.
-
+    ) -> SweptShell {
         let path = path.into();
 
-        let bottom_face = self;
-        let other_faces = bottom_face
-            .region()
-            .sweep_region(
-                bottom_face.surface(),
-                bottom_face.region().get_color(core),
+        core.trace_operation("sweep_face", &[self, &path], |core| {
+            // Please note that this function uses the words "bottom" and "top"
+            // in a specific sense:
+            //
+            // - "Bottom" refers to the origin of the sweep. The bottom face is
+            //   the original face, or a face in the same place.
+            // - "Top" refers to the location of the face that was created by
+            //   translating the bottom face along the path.
+            // - "Side" refers to new faces created in between bottom and top.
+            //
+            // These words are specifically *not* meant in the sense of z-axis
+            // locations, and depending on the direction of `path`, the two
+            // meanings might actually be opposite.
+
+            let swept_region = self.region().sweep_region(
+                self.surface(),
+                self.region().get_color(core),
                 path,
                 cache,
                 core,
-            )
-            .all_faces()
-            .map(|side_face| side_face.insert(core));
+            );
+
+            let sides = swept_region
+                .side_faces
+                .into_iter()
+                .chain(swept_region.interior_side_faces.into_iter().flatten())
+                .map(|side_face| side_face.insert(core))
+                .collect();
+            let top = swept_region.top_face.insert(core);
+
+            SweptShell {
+                bottom: self.clone(),
+                top,
+                sides,
+            }
+        })
+    }
+}
+
+/// The result of sweeping a [`Face`]
+///
+/// See [`SweepFace`].
+#[derive(Debug)]
+pub struct SweptShell {
+    /// The original face, which the sweep started from
+    pub bottom: Handle<Face>,
+
+    /// The face created by translating the bottom face along the sweep path
+    pub top: Handle<Face>,
+
+    /// The faces created in between the bottom and top faces
+    ///
+    /// Contains the faces created by sweeping the exterior cycle of the bottom
+    /// face first, followed by those created by sweeping its interior cycles.
+    pub sides: Vec<Handle<Face>>,
+}
+
+impl SweptShell {
+    /// Return an iterator over all of the faces
+    pub fn all_faces(&self) -> impl Iterator<Item = &Handle<Face>> {
+        [&self.bottom]
+            .into_iter()
+            .chain(&self.sides)
+            .chain([&self.top])
+    }
+
+    /// Convert into a [`Shell`] that consists of all of the faces
+    pub fn into_shell(self) -> Shell {
+        Shell::new(self.all_faces().cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Scalar;
+
+    use crate::{
+        objects::{Face, Region},
+        operations::{
+            build::BuildRegion,
+            insert::Insert,
+            sweep::{SweepCache, SweepFace},
+        },
+        Core,
+    };
+
+    #[test]
+    fn sweep_face() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let surface = core.layers.objects.surfaces.xy_plane();
+        let region = Region::polygon(
+            [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+            &mut core,
+        )
+        .insert(&mut core);
+        let face = Face::new(surface, region).insert(&mut core);
+
+        let swept = face.sweep_face_into_parts(
+            [0., 0., 1.],
+            &mut SweepCache::default(),
+            &mut core,
+        );
+
+        assert_eq!(swept.bottom.id(), face.id());
+        assert_eq!(swept.sides.len(), 4);
+
+        // The top face is the bottom face, moved along the path.
+        let top = swept
+            .top
+            .surface()
+            .geometry()
+            .point_from_surface_coords([0., 0.]);
+        assert_eq!(top.z, Scalar::ONE);
+
+        let shell = swept.into_shell().insert(&mut core);
+        assert_eq!(shell.faces().len(), 6);
+        core.layers.validation.take_errors()?;
 
-        let mut faces = Vec::new();
-        faces.push(bottom_face.clone());
-        faces.extend(other_faces);
+        // The deprecated method still returns the shell directly.
+        #[allow(deprecated)]
+        let shell = face.sweep_face(
+            [0., 0., 1.],
+            &mut SweepCache::default(),
+            &mut core,
+        );
+        assert_eq!(shell.faces().len(), 6);
 
-        Shell::new(faces)
+        Ok(())
     }
 }
@@ -1,3 +1,5 @@
+use std::fmt;
+
 use fj_interop::{ext::ArrayExt, Color};
 use fj_math::{Point, Scalar, Vector};
 
@@ -58,92 +60,111 @@ impl SweepHalfEdge for HalfEdge {
     ) -> (Face, Handle<HalfEdge>) {
         let path = path.into();
 
-        let surface = self.path().sweep_surface_path(surface, path, core);
+        let inputs: &[&dyn fmt::Debug] =
+            &[self, &end_vertex, surface, &color, &path];
+        core.trace_operation("sweep_half_edge", inputs, |core| {
+            // Vertices don't have a position, but half-edges do. We need those
+            // positions below, to make sure that distinct vertices at the same
+            // position are swept into the same curve.
+            let [position_a, position_b] = self.boundary().inner.map(|point| {
+                let point = self.path().point_from_path_coords(point);
+                surface.point_from_surface_coords(point)
+            });
 
-        // Next, we need to define the boundaries of the face. Let's start with
-        // the global vertices and edges.
-        let (vertices, curves) = {
-            let [a, b] = [self.start_vertex().clone(), end_vertex];
-            let (curve_up, c) = b.clone().sweep_vertex(cache, core);
-            let (curve_down, d) = a.clone().sweep_vertex(cache, core);
+            let surface = self.path().sweep_surface_path(surface, path, core);
+
+            // Next, we need to define the boundaries of the face. Let's start
+            // with the global vertices and edges.
+            let (vertices, curves) = {
+                let [a, b] = [self.start_vertex().clone(), end_vertex.clone()];
+                let max_distance =
+                    core.layers.validation.config.identical_max_distance;
+                let swept_a =
+                    cache.canonical_vertex(a.clone(), position_a, max_distance);
+                let swept_b =
+                    cache.canonical_vertex(b.clone(), position_b, max_distance);
+
+                let (curve_up, c) = swept_b.sweep_vertex(cache, core);
+                let (curve_down, d) = swept_a.sweep_vertex(cache, core);
+
+                (
+                    [a, b, c, d],
+                    [
+                        Some(self.curve().clone()),
+                        Some(curve_up),
+                        None,
+                        Some(curve_down),
+                    ],
+                )
+            };
+
+            // Let's figure out the surface coordinates of the edge vertices.
+            let surface_points = {
+                let [a, b] = self.boundary().inner;
 
-            (
-                [a, b, c, d],
                 [
-                    Some(self.curve().clone()),
-                    Some(curve_up),
-                    None,
-                    Some(curve_down),
-                ],
-            )
-        };
-
-        // Let's figure out the surface coordinates of the edge vertices.
-        let surface_points = {
-            let [a, b] = self.boundary().inner;
-
-            [
-                [a.t, Scalar::ZERO],
-                [b.t, Scalar::ZERO],
-                [b.t, Scalar::ONE],
-                [a.t, Scalar::ONE],
-            ]
-            .map(Point::from)
-        };
-        let surface_points_next = {
-            let mut points = surface_points;
-            points.rotate_left(1);
-            points
-        };
-
-        // Now, the boundaries of each edge.
-        let boundaries = {
-            let [a, b] = self.boundary().inner;
-            let [c, d] = [0., 1.].map(|coord| Point::from([coord]));
-
-            [[a, b], [c, d], [b, a], [d, c]]
-        };
-
-        let mut exterior = Cycle::empty();
-
-        // Armed with all of that, we're ready to create the edges.
-        let [_edge_bottom, _edge_up, edge_top, _edge_down] = boundaries
-            .zip_ext(surface_points)
-            .zip_ext(surface_points_next)
-            .zip_ext(vertices)
-            .zip_ext(curves)
-            .map(|((((boundary, start), end), start_vertex), curve)| {
-                let edge = {
-                    let edge = HalfEdge::line_segment(
-                        [start, end],
-                        Some(boundary),
-                        core,
-                    )
-                    .update_start_vertex(|_, _| start_vertex, core);
-
-                    let edge = if let Some(curve) = curve {
-                        edge.update_curve(|_, _| curve, core)
-                    } else {
-                        edge
+                    [a.t, Scalar::ZERO],
+                    [b.t, Scalar::ZERO],
+                    [b.t, Scalar::ONE],
+                    [a.t, Scalar::ONE],
+                ]
+                .map(Point::from)
+            };
+            let surface_points_next = {
+                let mut points = surface_points;
+                points.rotate_left(1);
+                points
+            };
+
+            // Now, the boundaries of each edge.
+            let boundaries = {
+                let [a, b] = self.boundary().inner;
+                let [c, d] = [0., 1.].map(|coord| Point::from([coord]));
+
+                [[a, b], [c, d], [b, a], [d, c]]
+            };
+
+            let mut exterior = Cycle::empty();
+
+            // Armed with all of that, we're ready to create the edges.
+            let [_edge_bottom, _edge_up, edge_top, _edge_down] = boundaries
+                .zip_ext(surface_points)
+                .zip_ext(surface_points_next)
+                .zip_ext(vertices)
+                .zip_ext(curves)
+                .map(|((((boundary, start), end), start_vertex), curve)| {
+                    let edge = {
+                        let edge = HalfEdge::line_segment(
+                            [start, end],
+                            Some(boundary),
+                            core,
+                        )
+                        .update_start_vertex(|_, _| start_vertex, core);
+
+                        let edge = if let Some(curve) = curve {
+                            edge.update_curve(|_, _| curve, core)
+                        } else {
+                            edge
+                        };
+
+                        edge.insert(core)
                     };
 
-                    edge.insert(core)
-                };
+                    exterior = exterior.add_half_edges([edge.clone()], core);
 
-                exterior = exterior.add_half_edges([edge.clone()], core);
-
-                edge
-            });
+                    edge
+                });
 
-        let exterior = exterior.insert(core);
-        let region = Region::new(exterior, []).insert(core);
+            let exterior = exterior.insert(core);
+            let region = Region::new(exterior, []).insert(core);
 
-        if let Some(color) = color {
-            region.set_color(color, core);
-        }
+            if let Some(color) = color {
+                region.set_color(color, core);
+            }
 
-        let face = Face::new(surface, region);
+            let face = Face::new(surface, region);
 
-        (face, edge_top)
+            (face, edge_top)
+        })
     }
 }
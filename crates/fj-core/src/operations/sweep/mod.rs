@@ -10,21 +10,24 @@ mod path;
 mod region;
 mod shell_face;
 mod sketch;
+mod taper;
 mod vertex;
 
 pub use self::{
     cycle::{SweepCycle, SweptCycle},
-    face::SweepFace,
+    face::{SweepFace, SweptShell},
     half_edge::SweepHalfEdge,
     path::SweepSurfacePath,
     region::{SweepRegion, SweptRegion},
     shell_face::SweepFaceOfShell,
-    sketch::SweepSketch,
+    sketch::{SweepDirection, SweepOptions, SweepSketch, SweepSketchError},
     vertex::SweepVertex,
 };
 
 use std::collections::BTreeMap;
 
+use fj_math::{Point, Scalar};
+
 use crate::{
     objects::{Curve, Vertex},
     storage::{Handle, ObjectId},
@@ -38,4 +41,129 @@ pub struct SweepCache {
 
     /// Cache for vertices
     pub vertices: BTreeMap<ObjectId, Handle<Vertex>>,
+
+    /// The first vertex swept at any given global position
+    ///
+    /// The vertices are sorted into a grid of cubic cells, the size of the
+    /// maximum distance passed to [`SweepCache::canonical_vertex`], so each
+    /// lookup only needs to check the neighboring cells.
+    vertices_by_position: BTreeMap<[i64; 3], Vec<(Point<3>, Handle<Vertex>)>>,
+}
+
+impl SweepCache {
+    /// Return the vertex that stands in for the provided one during the sweep
+    ///
+    /// The other caches are keyed by object identity. That means distinct
+    /// vertices that happen to be located at the same position (for example,
+    /// because they were created separately, or by transforming a copy of some
+    /// base geometry) would each be swept into their own curve. The resulting
+    /// side edges are coincident, but not identical, which trips validation
+    /// later on.
+    ///
+    /// This method returns the first vertex that was passed to it for a
+    /// position within `max_distance` of the provided one. Sweeping that vertex
+    /// instead of the provided one makes sure that every position results in
+    /// exactly one curve. Positions computed in different ways rarely match
+    /// exactly, so `max_distance` should be the configured
+    /// [`ValidationConfig::identical_max_distance`]. The same `max_distance`
+    /// must be passed on every call for a given cache.
+    ///
+    /// [`ValidationConfig::identical_max_distance`]: crate::validation::ValidationConfig::identical_max_distance
+    pub fn canonical_vertex(
+        &mut self,
+        vertex: Handle<Vertex>,
+        position: Point<3>,
+        max_distance: Scalar,
+    ) -> Handle<Vertex> {
+        let cell_size = if max_distance > Scalar::ZERO {
+            max_distance
+        } else {
+            Scalar::ONE
+        };
+        let [x, y, z] = position
+            .coords
+            .components
+            .map(|c| (c / cell_size).floor().into_f64() as i64);
+
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    let Some(cell) = self.vertices_by_position.get(&[
+                        x + dx,
+                        y + dy,
+                        z + dz,
+                    ]) else {
+                        continue;
+                    };
+
+                    let existing = cell.iter().find(|(p, _)| {
+                        p.distance_to(&position) <= max_distance
+                    });
+                    if let Some((_, canonical)) = existing {
+                        return canonical.clone();
+                    }
+                }
+            }
+        }
+
+        self.vertices_by_position
+            .entry([x, y, z])
+            .or_default()
+            .push((position, vertex.clone()));
+        vertex
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use crate::{
+        objects::{Face, Region},
+        operations::{build::BuildRegion, insert::Insert},
+        Core,
+    };
+
+    use super::{SweepCache, SweepFace};
+
+    #[test]
+    fn sweep_jittered_coincident_vertices_into_one_curve() {
+        let mut core = Core::new();
+
+        // The two squares share an edge, but the second one was computed
+        // differently, so its vertices on that edge are slightly off.
+        let jitter = core.layers.validation.config.identical_max_distance / 5.;
+        let x = 1. + jitter.into_f64();
+
+        let surface = core.layers.objects.surfaces.xy_plane();
+        let faces = [
+            Region::polygon(
+                [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+                &mut core,
+            ),
+            Region::polygon([[x, 0.], [2., 0.], [2., 1.], [x, 1.]], &mut core),
+        ]
+        .map(|region| {
+            let region = region.insert(&mut core);
+            Face::new(surface.clone(), region).insert(&mut core)
+        });
+
+        let mut cache = SweepCache::default();
+        let mut curves = BTreeSet::new();
+
+        for face in faces {
+            let swept =
+                face.sweep_face_into_parts([0., 0., 1.], &mut cache, &mut core);
+
+            for side in swept.sides {
+                for half_edge in side.region().exterior().half_edges() {
+                    curves.insert(half_edge.curve().id());
+                }
+            }
+        }
+
+        // Each square results in 4 bottom, 4 top, and 4 vertical curves. The 2
+        // vertical curves at the shared edge must only exist once.
+        assert_eq!(curves.len(), 2 * 12 - 2);
+    }
 }
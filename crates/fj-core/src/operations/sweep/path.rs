@@ -1,4 +1,4 @@
-use fj_math::{Circle, Line, Vector};
+use fj_math::{Circle, CubicBezier, Ellipse, Line, Vector};
 
 use crate::{
     geometry::{GlobalPath, SurfaceGeometry, SurfacePath},
@@ -39,8 +39,15 @@ impl SweepSurfacePath for SurfacePath {
         path: impl Into<Vector<3>>,
         core: &mut Core,
     ) -> Handle<Surface> {
-        match surface.u {
-            GlobalPath::Circle(_) => {
+        match surface {
+            SurfaceGeometry::Swept {
+                u:
+                    GlobalPath::Circle(_)
+                    | GlobalPath::CubicBezier(_)
+                    | GlobalPath::Ellipse(_),
+                ..
+            }
+            | SurfaceGeometry::Nurbs(_) => {
                 // Sweeping a `Curve` creates a `Surface`. The u-axis of that
                 // `Surface` is a `GlobalPath`, which we are computing below.
                 // That computation might or might not work with an arbitrary
@@ -57,7 +64,10 @@ impl SweepSurfacePath for SurfacePath {
                     not supported yet."
                 )
             }
-            GlobalPath::Line(_) => {
+            SurfaceGeometry::Swept {
+                u: GlobalPath::Line(_),
+                ..
+            } => {
                 // We're sweeping from a curve on a flat surface, which is
                 // supported. Carry on.
             }
@@ -73,6 +83,23 @@ impl SweepSurfacePath for SurfacePath {
 
                 GlobalPath::Circle(circle)
             }
+            SurfacePath::CubicBezier(bezier) => {
+                let control_points = bezier
+                    .control_points()
+                    .map(|point| surface.point_from_surface_coords(point));
+
+                GlobalPath::CubicBezier(CubicBezier::new(control_points))
+            }
+            SurfacePath::Ellipse(ellipse) => {
+                let center =
+                    surface.point_from_surface_coords(ellipse.center());
+                let a = surface.vector_from_surface_coords(ellipse.a());
+                let b = surface.vector_from_surface_coords(ellipse.b());
+
+                let ellipse = Ellipse::new(center, a, b);
+
+                GlobalPath::Ellipse(ellipse)
+            }
             SurfacePath::Line(line) => {
                 let origin = surface.point_from_surface_coords(line.origin());
                 let direction =
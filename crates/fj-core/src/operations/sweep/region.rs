@@ -1,3 +1,5 @@
+use std::fmt;
+
 use fj_interop::Color;
 use fj_math::Vector;
 
@@ -24,6 +26,11 @@ pub trait SweepRegion {
     /// Sweep the region into multiple sets of faces. Each set of faces is
     /// formed by sweeping one of the region's cycles, then adding a top face.
     ///
+    /// The faces swept from the exterior cycle face away from the region. The
+    /// faces swept from the interior cycles (the walls of the region's holes)
+    /// face towards the inside of their respective hole. Either way, they
+    /// face away from the material enclosed by the sweep.
+    ///
     /// Requires the surface that the face that the region belongs to is defined
     /// in.
     ///
@@ -51,46 +58,58 @@ impl SweepRegion for Region {
     ) -> SweptRegion {
         let path = path.into();
 
-        let mut faces = Vec::new();
+        let inputs: &[&dyn fmt::Debug] = &[self, surface, &color, &path];
+        core.trace_operation("sweep_region", inputs, |core| {
+            let mut side_faces = Vec::new();
 
-        let top_exterior = sweep_cycle(
-            self.exterior(),
-            &surface.geometry(),
-            color,
-            &mut faces,
-            path,
-            cache,
-            core,
-        );
+            let top_exterior = sweep_cycle(
+                self.exterior(),
+                surface.geometry(),
+                color,
+                &mut side_faces,
+                path,
+                cache,
+                core,
+            );
 
-        let top_interiors = self
-            .interiors()
-            .iter()
-            .map(|bottom_cycle| {
-                sweep_cycle(
-                    bottom_cycle,
-                    &surface.geometry(),
-                    color,
-                    &mut faces,
-                    path,
-                    cache,
-                    core,
-                )
-            })
-            .collect::<Vec<_>>();
+            let mut interior_side_faces = Vec::new();
 
-        let top_face = {
-            let top_surface = surface.translate(path, core).insert(core);
-            let top_region =
-                Region::new(top_exterior, top_interiors).insert(core);
+            let top_interiors = self
+                .interiors()
+                .iter()
+                .map(|bottom_cycle| {
+                    let mut faces = Vec::new();
 
-            Face::new(top_surface, top_region)
-        };
+                    let top_cycle = sweep_cycle(
+                        bottom_cycle,
+                        surface.geometry(),
+                        color,
+                        &mut faces,
+                        path,
+                        cache,
+                        core,
+                    );
 
-        SweptRegion {
-            top_face,
-            side_faces: faces,
-        }
+                    interior_side_faces.push(faces);
+
+                    top_cycle
+                })
+                .collect::<Vec<_>>();
+
+            let top_face = {
+                let top_surface = surface.translate(path, core).insert(core);
+                let top_region =
+                    Region::new(top_exterior, top_interiors).insert(core);
+
+                Face::new(top_surface, top_region)
+            };
+
+            SweptRegion {
+                side_faces,
+                interior_side_faces,
+                top_face,
+            }
+        })
     }
 }
 
@@ -119,10 +138,18 @@ fn sweep_cycle(
 /// The result of sweeping a [`Region`]
 ///
 /// See [`SweepRegion`].
+#[derive(Debug)]
 pub struct SweptRegion {
-    /// The side faces created by the sweep
+    /// The side faces created by sweeping the exterior cycle
     pub side_faces: Vec<Face>,
 
+    /// The side faces created by sweeping the interior cycles
+    ///
+    /// Contains one set of faces per interior cycle of the original region, in
+    /// the same order as [`Region::interiors`]. Those faces form the walls of
+    /// the region's holes.
+    pub interior_side_faces: Vec<Vec<Face>>,
+
     /// The top face created by the sweep
     pub top_face: Face,
 }
@@ -130,6 +157,158 @@ pub struct SweptRegion {
 impl SweptRegion {
     /// Return an iterator over all of the faces
     pub fn all_faces(self) -> impl Iterator<Item = Face> {
-        self.side_faces.into_iter().chain([self.top_face])
+        self.side_faces
+            .into_iter()
+            .chain(self.interior_side_faces.into_iter().flatten())
+            .chain([self.top_face])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar, Vector};
+
+    use crate::{
+        geometry::{GlobalPath, SurfaceGeometry},
+        objects::{Cycle, Face, Handedness, Region, Sketch},
+        operations::{
+            build::{BuildCycle, BuildSketch},
+            insert::Insert,
+            reverse::Reverse,
+            sweep::{SweepCache, SweepSketch},
+            update::UpdateSketch,
+        },
+        Core,
+    };
+
+    use super::SweepRegion;
+
+    #[test]
+    fn interior_side_faces_face_into_hole() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let surface = core.layers.objects.surfaces.xy_plane();
+        let center = Point::from([2., 2., 0.]);
+
+        // This is how `SweepSketch` prepares a region for a sweep along the
+        // positive normal of the surface.
+        let region =
+            square_with_holes([([0., 0.], 4.), ([1., 1.], 2.)], &mut core)
+                .reverse(&mut core);
+
+        let swept = region.sweep_region(
+            &surface,
+            None,
+            [0., 0., 1.],
+            &mut SweepCache::default(),
+            &mut core,
+        );
+
+        assert_eq!(swept.side_faces.len(), 4);
+        for face in &swept.side_faces {
+            assert!(
+                front_normal(face).dot(&(point_on(face) - center))
+                    > Scalar::ZERO
+            );
+        }
+
+        let [hole] = swept.interior_side_faces.as_slice() else {
+            panic!("Expected faces for exactly one hole");
+        };
+        assert_eq!(hole.len(), 4);
+        for face in hole {
+            assert!(
+                front_normal(face).dot(&(point_on(face) - center))
+                    < Scalar::ZERO
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn sweep_nested_holes() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let surface = core.layers.objects.surfaces.xy_plane();
+
+        // A square with two holes, and another square with a hole, placed
+        // within the first hole of the first square.
+        let outer = square_with_holes(
+            [([0., 0.], 12.), ([1., 1.], 6.), ([8., 8.], 2.)],
+            &mut core,
+        );
+        let inner =
+            square_with_holes([([2., 2.], 4.), ([3., 3.], 2.)], &mut core);
+
+        let sketch = Sketch::empty().add_regions([outer, inner], &mut core);
+        let solid = sketch
+            .sweep_sketch(surface, [0., 0., 1.], &mut core)
+            .insert(&mut core);
+
+        assert_eq!(solid.shells().len(), 2);
+        core.layers.validation.take_errors()?;
+
+        Ok(())
+    }
+
+    /// Build a square region with square holes
+    ///
+    /// Each square is specified as its minimum corner and its side length. The
+    /// first square is the exterior; all others are interiors.
+    fn square_with_holes(
+        squares: impl IntoIterator<Item = ([f64; 2], f64)>,
+        core: &mut Core,
+    ) -> Region {
+        let cycles = squares
+            .into_iter()
+            .map(|([x, y], size)| {
+                let points = [
+                    [x, y],
+                    [x + size, y],
+                    [x + size, y + size],
+                    [x, y + size],
+                ];
+                Cycle::polygon(points, core)
+            })
+            .collect::<Vec<_>>();
+
+        let (exterior, interiors) =
+            cycles.split_first().expect("Expected at least one square");
+        let exterior = exterior.clone().insert(core);
+        let interiors = interiors
+            .iter()
+            .map(|cycle| cycle.reverse(core).insert(core))
+            .collect::<Vec<_>>();
+
+        Region::new(exterior, interiors)
+    }
+
+    fn front_normal(face: &Face) -> Vector<3> {
+        let SurfaceGeometry::Swept {
+            u: GlobalPath::Line(u),
+            v,
+        } = face.surface().geometry()
+        else {
+            unreachable!("Only expecting planar faces in these tests");
+        };
+        let normal = u.direction().cross(v);
+
+        match face.coord_handedness() {
+            Handedness::RightHanded => normal,
+            Handedness::LeftHanded => -normal,
+        }
+    }
+
+    fn point_on(face: &Face) -> Point<3> {
+        let position = face
+            .region()
+            .exterior()
+            .half_edges()
+            .first()
+            .start_position();
+        face.surface()
+            .geometry()
+            .point_from_surface_coords(position)
     }
 }
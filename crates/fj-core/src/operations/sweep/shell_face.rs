@@ -1,3 +1,5 @@
+use std::fmt;
+
 use fj_math::Vector;
 
 use crate::{
@@ -49,33 +51,36 @@ impl SweepFaceOfShell for Shell {
     ) -> Self {
         let path = path.into();
 
-        if !face.region().interiors().is_empty() {
-            todo!(
-                "Sweeping shell faces with interior cycles is not yet \
-                supported."
-            )
-        }
+        let inputs: &[&dyn fmt::Debug] = &[self, &face, &path];
+        core.trace_operation("sweep_face_of_shell", inputs, |core| {
+            if !face.region().interiors().is_empty() {
+                todo!(
+                    "Sweeping shell faces with interior cycles is not yet \
+                    supported."
+                )
+            }
 
-        let mut cache = SweepCache::default();
+            let mut cache = SweepCache::default();
 
-        let exterior = face
-            .region()
-            .exterior()
-            .reverse(core)
-            .insert(core)
-            .derive_from(face.region().exterior(), core);
-        let region = Region::new(exterior, []);
-        let faces = region
-            .sweep_region(
-                face.surface(),
-                face.region().get_color(core),
-                path,
-                &mut cache,
-                core,
-            )
-            .all_faces()
-            .collect::<Vec<_>>();
+            let exterior = face
+                .region()
+                .exterior()
+                .reverse(core)
+                .insert(core)
+                .derive_from(face.region().exterior(), core);
+            let region = Region::new(exterior, []);
+            let faces = region
+                .sweep_region(
+                    face.surface(),
+                    face.region().get_color(core),
+                    path,
+                    &mut cache,
+                    core,
+                )
+                .all_faces()
+                .collect::<Vec<_>>();
 
-        self.remove_face(&face).add_faces(faces, core)
+            self.remove_face(&face).add_faces(faces, core)
+        })
     }
 }
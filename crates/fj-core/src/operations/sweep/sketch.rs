@@ -1,14 +1,19 @@
+use std::fmt;
+
 use fj_math::{Scalar, Vector};
 
 use crate::{
-    geometry::GlobalPath,
-    objects::{Face, Sketch, Solid, Surface},
-    operations::{derive::DeriveFrom, insert::Insert, reverse::Reverse},
+    geometry::{GlobalPath, SurfaceGeometry, SurfacePath},
+    objects::{Face, HalfEdge, Region, Sketch, Solid, Surface},
+    operations::{
+        boolean::signed_area, derive::DeriveFrom, insert::Insert,
+        reverse::Reverse, transform::TransformObject,
+    },
     storage::Handle,
     Core,
 };
 
-use super::{face::SweepFace, SweepCache};
+use super::{face::SweepFace, taper::sweep_region_with_taper, SweepCache};
 
 /// # Sweep a [`Sketch`]
 ///
@@ -17,58 +22,650 @@ use super::{face::SweepFace, SweepCache};
 /// [module documentation]: super
 pub trait SweepSketch {
     /// # Sweep the [`Sketch`]
+    ///
+    /// Each region of the sketch is swept into its own [`Shell`] of the
+    /// resulting [`Solid`]. Sketches with multiple disjoint regions therefore
+    /// result in a solid that consists of multiple separate parts.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the sweep would be degenerate. See
+    /// [`SweepSketch::sweep_sketch_in_direction`] for a non-panicking version
+    /// of this method, and [`SweepSketchError`] for the reasons it can fail.
+    ///
+    /// [`Shell`]: crate::objects::Shell
     fn sweep_sketch(
         &self,
         surface: Handle<Surface>,
         path: impl Into<Vector<3>>,
         core: &mut Core,
-    ) -> Solid;
+    ) -> Solid {
+        self.sweep_sketch_in_direction(
+            surface,
+            path,
+            SweepDirection::Forward,
+            core,
+        )
+        .unwrap_or_else(|err| panic!("Failed to sweep sketch: {err}"))
+    }
+
+    /// # Sweep the [`Sketch`] in the provided direction
+    ///
+    /// Works like [`SweepSketch::sweep_sketch`], but the sketch can also be
+    /// swept backwards, or to both sides of the surface it is defined on. See
+    /// [`SweepDirection`].
+    ///
+    /// Returns an error, instead of creating degenerate objects that would
+    /// only fail validation later on.
+    fn sweep_sketch_in_direction(
+        &self,
+        surface: Handle<Surface>,
+        path: impl Into<Vector<3>>,
+        direction: SweepDirection,
+        core: &mut Core,
+    ) -> Result<Solid, SweepSketchError> {
+        self.sweep_sketch_with_options(
+            surface,
+            path,
+            SweepOptions {
+                direction,
+                ..SweepOptions::default()
+            },
+            core,
+        )
+    }
+
+    /// # Sweep the [`Sketch`] with the provided options
+    ///
+    /// Works like [`SweepSketch::sweep_sketch_in_direction`], but also allows
+    /// tapering the sweep. See [`SweepOptions`].
+    fn sweep_sketch_with_options(
+        &self,
+        surface: Handle<Surface>,
+        path: impl Into<Vector<3>>,
+        options: SweepOptions,
+        core: &mut Core,
+    ) -> Result<Solid, SweepSketchError>;
 }
 
 impl SweepSketch for Sketch {
-    fn sweep_sketch(
+    fn sweep_sketch_with_options(
         &self,
         surface: Handle<Surface>,
         path: impl Into<Vector<3>>,
+        options: SweepOptions,
         core: &mut Core,
-    ) -> Solid {
+    ) -> Result<Solid, SweepSketchError> {
         let path = path.into();
-        let mut cache = SweepCache::default();
+        let SweepOptions {
+            direction,
+            taper_angle,
+        } = options;
+
+        let normal = {
+            let SurfaceGeometry::Swept {
+                u: GlobalPath::Line(line),
+                v,
+            } = surface.geometry()
+            else {
+                todo!(
+                    "Sweeping sketch from a rounded surfaces is not supported"
+                )
+            };
+
+            line.direction().cross(v)
+        };
+
+        let inputs: &[&dyn fmt::Debug] = &[self, &surface, &path, &options];
+        core.trace_operation("sweep_sketch", inputs, |core| {
+            let config = core.layers.validation.config;
+
+            if path.magnitude() <= config.identical_max_distance {
+                return Err(SweepSketchError::ZeroLengthPath { path });
+            }
+            if normal.normalize().dot(&path.normalize()).abs()
+                <= config.identical_max_angle.sin_cos().0
+            {
+                return Err(SweepSketchError::PathParallelToSurface { path });
+            }
+            if taper_angle.abs() >= Scalar::PI / 2. {
+                return Err(SweepSketchError::TaperAngleOutOfRange {
+                    angle: taper_angle,
+                });
+            }
+
+            for region in self.regions() {
+                let exterior = region.exterior();
+
+                if exterior.half_edges().is_empty()
+                    || has_no_area(region, config.identical_max_distance)
+                {
+                    return Err(SweepSketchError::RegionHasNoArea {
+                        region: region.clone(),
+                    });
+                }
 
-        let mut shells = Vec::new();
-        for region in self.regions() {
-            let region = {
                 // The following code assumes that the sketch is winded counter-
-                // clockwise. Let's check that real quick.
-                assert!(region.exterior().winding().is_ccw());
-
-                let is_negative_sweep = {
-                    let u = match surface.geometry().u {
-                        GlobalPath::Circle(_) => todo!(
-                            "Sweeping sketch from a rounded surfaces is not \
-                            supported"
-                        ),
-                        GlobalPath::Line(line) => line.direction(),
-                    };
-                    let v = surface.geometry().v;
-
-                    let normal = u.cross(&v);
-
-                    normal.dot(&path) < Scalar::ZERO
-                };
+                // clockwise.
+                if !exterior.winding().is_ccw() {
+                    return Err(SweepSketchError::RegionNotCounterClockwise {
+                        region: region.clone(),
+                    });
+                }
+            }
+
+            let (surface, path) = match direction {
+                SweepDirection::Forward => (surface.clone(), path),
+                SweepDirection::Backward => (surface.clone(), -path),
+                SweepDirection::Symmetric => {
+                    // Start the sweep half the path length behind the surface,
+                    // so the result ends up centered on it.
+                    let surface =
+                        surface.translate(-path / 2., core).insert(core);
+                    (surface, path)
+                }
+                SweepDirection::TwoSided { backward } => {
+                    if backward < Scalar::ZERO {
+                        return Err(SweepSketchError::NegativeBackwardLength {
+                            length: backward,
+                        });
+                    }
+
+                    // Start the sweep behind the surface, and extend the path
+                    // accordingly, so it still ends at the same place.
+                    let offset = path.normalize() * backward;
+                    let surface = surface.translate(-offset, core).insert(core);
+                    (surface, path + offset)
+                }
+            };
+
+            let is_negative_sweep = normal.dot(&path) < Scalar::ZERO;
+
+            // The side faces are inclined towards the inside of the regions by
+            // this distance over the length of the sweep.
+            let taper = {
+                let (sin, cos) = taper_angle.sin_cos();
+                path.magnitude() * sin / cos
+            };
+
+            let mut cache = SweepCache::default();
 
-                if is_negative_sweep {
+            let mut shells = Vec::new();
+            for region in self.regions() {
+                let region = if is_negative_sweep {
                     region.clone()
                 } else {
                     region.reverse(core).insert(core).derive_from(region, core)
-                }
-            };
+                };
+
+                let shell = if taper_angle == Scalar::ZERO {
+                    let face =
+                        Face::new(surface.clone(), region.clone()).insert(core);
+                    face.sweep_face_into_parts(path, &mut cache, core)
+                        .into_shell()
+                } else {
+                    sweep_region_with_taper(
+                        &region, &surface, normal, path, taper, core,
+                    )?
+                };
+                shells.push(shell.insert(core));
+            }
+
+            Ok(Solid::new(shells))
+        })
+    }
+}
+
+/// Determine whether the exterior of the region is a polygon without area
+///
+/// A polygon has no area, if it is no wider than `max_distance`. Then its area
+/// is at most `max_distance` times half its perimeter.
+///
+/// Exteriors that include curved half-edges are not polygons, and are never
+/// considered degenerate.
+fn has_no_area(region: &Region, max_distance: Scalar) -> bool {
+    let exterior = region.exterior();
+
+    let is_polygon = exterior
+        .half_edges()
+        .iter()
+        .all(|half_edge| matches!(half_edge.path(), SurfacePath::Line(_)));
+    if !is_polygon {
+        return false;
+    }
+
+    let points = exterior
+        .half_edges()
+        .iter()
+        .map(|half_edge| half_edge.start_position())
+        .collect::<Vec<_>>();
+    let perimeter = exterior
+        .half_edges()
+        .pairs()
+        .map(|(a, b)| a.start_position().distance_to(&b.start_position()))
+        .fold(Scalar::ZERO, |sum, length| sum + length);
+
+    signed_area(&points).abs() <= max_distance * perimeter / 2.
+}
+
+/// Error sweeping a [`Sketch`]
+///
+/// See [`SweepSketch::sweep_sketch_in_direction`].
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum SweepSketchError {
+    /// The sweep path has (almost) zero length
+    #[error("Sweep path has zero length: {path:?}")]
+    ZeroLengthPath {
+        /// The sweep path
+        path: Vector<3>,
+    },
+
+    /// The sweep path is (almost) parallel to the sketch's surface
+    #[error("Sweep path is parallel to the sketch's surface: {path:?}")]
+    PathParallelToSurface {
+        /// The sweep path
+        path: Vector<3>,
+    },
+
+    /// A region of the sketch encloses no area
+    #[error("Region of sketch encloses no area: {region:#?}")]
+    RegionHasNoArea {
+        /// The degenerate region
+        region: Handle<Region>,
+    },
+
+    /// A region of the sketch is not wound counter-clockwise
+    #[error("Region of sketch is not wound counter-clockwise: {region:#?}")]
+    RegionNotCounterClockwise {
+        /// The offending region
+        region: Handle<Region>,
+    },
+
+    /// The taper angle is not within the range (-pi/2, pi/2) radians
+    #[error("Taper angle must be in the range (-pi/2, pi/2): {angle}")]
+    TaperAngleOutOfRange {
+        /// The taper angle
+        angle: Scalar,
+    },
 
-            let face = Face::new(surface.clone(), region.clone()).insert(core);
-            let shell = face.sweep_face(path, &mut cache, core).insert(core);
-            shells.push(shell);
+    /// The length of a two-sided sweep against the sweep path is negative
+    #[error("Backward length of two-sided sweep is negative: {length}")]
+    NegativeBackwardLength {
+        /// The backward length
+        length: Scalar,
+    },
+
+    /// A half-edge of a tapered region is not a line segment
+    #[error("Tapered sweep only supports line segments: {half_edge:#?}")]
+    UnsupportedTaperedHalfEdge {
+        /// The offending half-edge
+        half_edge: Handle<HalfEdge>,
+    },
+
+    /// The taper is so large, that a tapered region would collapse
+    #[error("Taper is too large for region: {region:#?}")]
+    TaperTooLarge {
+        /// The offending region
+        region: Handle<Region>,
+    },
+}
+
+/// Options for sweeping a [`Sketch`]
+///
+/// See [`SweepSketch::sweep_sketch_with_options`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SweepOptions {
+    /// The direction in which the sketch is swept
+    pub direction: SweepDirection,
+
+    /// The angle between the side faces and the sweep path, in radians
+    ///
+    /// With a positive angle, the side faces are inclined towards the inside
+    /// of the regions, making the end of the sweep smaller than its start. A
+    /// negative angle inclines them outward. The default of zero results in a
+    /// straight prism.
+    ///
+    /// The taper always starts at the start of the sweep, which is not on the
+    /// sketch's surface, for [`SweepDirection::Symmetric`] and
+    /// [`SweepDirection::TwoSided`]. Only regions that are bounded by line
+    /// segments can be tapered.
+    pub taper_angle: Scalar,
+}
+
+/// The direction in which a sketch is swept
+///
+/// See [`SweepSketch::sweep_sketch_in_direction`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum SweepDirection {
+    /// Sweep along the sweep path, starting at the sketch's surface
+    #[default]
+    Forward,
+
+    /// Sweep against the sweep path, starting at the sketch's surface
+    Backward,
+
+    /// Sweep along the sweep path, centered on the sketch's surface
+    ///
+    /// The result extends half the length of the sweep path to either side of
+    /// the surface.
+    Symmetric,
+
+    /// Sweep along the sweep path and against it, by different lengths
+    ///
+    /// The result extends the full length of the sweep path to one side of the
+    /// surface, and the provided length to the other side. This is equivalent
+    /// to sweeping in both directions and joining the results, without the
+    /// seam that would result.
+    TwoSided {
+        /// The length of the sweep against the sweep path
+        ///
+        /// Must not be negative.
+        backward: Scalar,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Scalar;
+    use fj_test_utils::{
+        assert_scalars_eq, assert_volume_eq, assert_watertight,
+    };
+
+    use crate::{
+        algorithms::{
+            approx::Tolerance, bounding_volume::BoundingVolume,
+            triangulate::Triangulate,
+        },
+        objects::{Cycle, Region, Sketch},
+        operations::{
+            build::{BuildCycle, BuildRegion, BuildSketch},
+            insert::Insert,
+            update::{UpdateRegion, UpdateSketch},
+        },
+        Core,
+    };
+
+    use super::{SweepDirection, SweepOptions, SweepSketch, SweepSketchError};
+
+    #[test]
+    fn sweep_in_direction() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let surface = core.layers.objects.surfaces.xy_plane();
+        let sketch = Sketch::empty().add_regions(
+            [Region::polygon(
+                [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+                &mut core,
+            )],
+            &mut core,
+        );
+
+        for (direction, [min_z, max_z]) in [
+            (SweepDirection::Forward, [0., 2.]),
+            (SweepDirection::Backward, [-2., 0.]),
+            (SweepDirection::Symmetric, [-1., 1.]),
+            (
+                SweepDirection::TwoSided {
+                    backward: Scalar::from(0.5),
+                },
+                [-0.5, 2.],
+            ),
+        ] {
+            let solid = sketch
+                .sweep_sketch_in_direction(
+                    surface.clone(),
+                    [0., 0., 2.],
+                    direction,
+                    &mut core,
+                )?
+                .insert(&mut core);
+
+            let aabb = solid.aabb().expect("Expected non-empty solid");
+            assert_eq!(aabb.min.z, Scalar::from(min_z));
+            assert_eq!(aabb.max.z, Scalar::from(max_z));
+        }
+
+        core.layers.validation.take_errors()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn sweep_ellipse() -> anyhow::Result<()> {
+        let mut core = Core::new();
+        let tolerance = Tolerance::from_scalar(0.01)?;
+
+        let surface = core.layers.objects.surfaces.xy_plane();
+        let solid = Sketch::empty()
+            .add_regions(
+                [Region::ellipse([0., 0.], [2., 1.], &mut core)],
+                &mut core,
+            )
+            .sweep_sketch(surface, [0., 0., 1.], &mut core)
+            .insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        let mesh = (&*solid, tolerance).triangulate(&mut core);
+        assert!(mesh.triangles().count() > 0);
+
+        // The approximation only consists of points on the boundary of the
+        // ellipse.
+        for triangle in mesh.triangles() {
+            for point in triangle.inner.points() {
+                let on_ellipse = point.x * point.x / 4. + point.y * point.y;
+                assert_scalars_eq!(on_ellipse, 1., 1e-12);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn sweep_cubic_bezier() -> anyhow::Result<()> {
+        let mut core = Core::new();
+        let tolerance = Tolerance::from_scalar(0.001)?;
+
+        // A lens-shaped region, bounded by two curves that bulge away from
+        // each other. Its area is `2.1`.
+        let exterior = Cycle::cubic_bezier(
+            [
+                [[0.5, -1.], [1.5, -1.], [2., 0.]],
+                [[1.5, 1.], [0.5, 1.], [0., 0.]],
+            ],
+            &mut core,
+        )
+        .insert(&mut core);
+        let region = Region::new(exterior, []);
+
+        let surface = core.layers.objects.surfaces.xy_plane();
+        let solid = Sketch::empty()
+            .add_regions([region], &mut core)
+            .sweep_sketch(surface, [0., 0., 1.], &mut core)
+            .insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        // The approximation cuts off a bit of the curves.
+        let mesh = (&*solid, tolerance).triangulate(&mut core);
+        assert_watertight!(mesh);
+        assert_volume_eq!(mesh, 2.1, 0.01);
+        assert!(mesh.volume() < Scalar::from(2.1));
+
+        Ok(())
+    }
+
+    #[test]
+    fn degenerate_sweep() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let surface = core.layers.objects.surfaces.xy_plane();
+        let square = Region::polygon(
+            [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+            &mut core,
+        );
+        let sketch = Sketch::empty().add_regions([square], &mut core);
+
+        let result = sketch.sweep_sketch_in_direction(
+            surface.clone(),
+            [0., 0., 0.],
+            SweepDirection::Forward,
+            &mut core,
+        );
+        assert!(matches!(
+            result,
+            Err(SweepSketchError::ZeroLengthPath { .. })
+        ));
+
+        let result = sketch.sweep_sketch_in_direction(
+            surface.clone(),
+            [1., 0., 0.],
+            SweepDirection::Forward,
+            &mut core,
+        );
+        assert!(matches!(
+            result,
+            Err(SweepSketchError::PathParallelToSurface { .. })
+        ));
+
+        let result = sketch.sweep_sketch_in_direction(
+            surface.clone(),
+            [0., 0., 1.],
+            SweepDirection::TwoSided {
+                backward: Scalar::from(-1.),
+            },
+            &mut core,
+        );
+        assert!(matches!(
+            result,
+            Err(SweepSketchError::NegativeBackwardLength { .. })
+        ));
+
+        let line = Region::polygon([[0., 0.], [1., 0.], [2., 0.]], &mut core)
+            .insert(&mut core);
+        let sketch = Sketch::empty().add_regions([line.clone()], &mut core);
+
+        let result = sketch.sweep_sketch_in_direction(
+            surface.clone(),
+            [0., 0., 1.],
+            SweepDirection::Forward,
+            &mut core,
+        );
+        assert!(matches!(
+            result,
+            Err(SweepSketchError::RegionHasNoArea { region }) if region == line
+        ));
+
+        // A polygon that is narrower than the configured distance has no area
+        // either, even if floating-point error gives it some.
+        let jitter = core.layers.validation.config.identical_max_distance / 5.;
+        let sliver = Region::polygon(
+            [[0., 0.], [2., 0.], [1., jitter.into_f64()]],
+            &mut core,
+        )
+        .insert(&mut core);
+        let sketch = Sketch::empty().add_regions([sliver.clone()], &mut core);
+
+        let result = sketch.sweep_sketch_in_direction(
+            surface,
+            [0., 0., 1.],
+            SweepDirection::Forward,
+            &mut core,
+        );
+        assert!(matches!(
+            result,
+            Err(SweepSketchError::RegionHasNoArea { region }) if region == sliver
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn sweep_with_taper() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let surface = core.layers.objects.surfaces.xy_plane();
+        let square = Region::polygon(
+            [[0., 0.], [4., 0.], [4., 4.], [0., 4.]],
+            &mut core,
+        );
+        let square_with_hole = square.add_interiors(
+            [Cycle::polygon(
+                [[1., 1.], [1., 3.], [3., 3.], [3., 1.]],
+                &mut core,
+            )],
+            &mut core,
+        );
+
+        for (region, taper_angle, path, expected_volume) in [
+            // The square shrinks to 3x3 at the top.
+            (square.clone(), 0.5_f64.atan(), [0., 0., 1.], 37. / 3.),
+            (square.clone(), 0.5_f64.atan(), [0., 0., -1.], 37. / 3.),
+            // The square grows to 5x5 at the top.
+            (square, -(0.5_f64.atan()), [0., 0., 1.], 61. / 3.),
+            // The hole grows, while the square shrinks.
+            (square_with_hole, 0.25_f64.atan(), [0., 0., 1.], 9.),
+        ] {
+            let sketch = Sketch::empty().add_regions([region], &mut core);
+
+            let solid = sketch
+                .sweep_sketch_with_options(
+                    surface.clone(),
+                    path,
+                    SweepOptions {
+                        taper_angle: Scalar::from(taper_angle),
+                        ..SweepOptions::default()
+                    },
+                    &mut core,
+                )?
+                .insert(&mut core);
+            core.layers.validation.take_errors()?;
+
+            let mesh = (&*solid, Tolerance::from_scalar(0.001)?)
+                .triangulate(&mut core);
+            assert_watertight!(mesh);
+            assert_volume_eq!(mesh, expected_volume, 1e-9);
         }
 
-        Solid::new(shells)
+        Ok(())
+    }
+
+    #[test]
+    fn unsupported_taper() {
+        let mut core = Core::new();
+
+        let surface = core.layers.objects.surfaces.xy_plane();
+        let square = Sketch::empty().add_regions(
+            [Region::polygon(
+                [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+                &mut core,
+            )],
+            &mut core,
+        );
+        let circle = Sketch::empty()
+            .add_regions([Region::circle([0., 0.], 1., &mut core)], &mut core);
+
+        let mut sweep = |sketch: &Sketch, taper_angle: f64| {
+            sketch.sweep_sketch_with_options(
+                surface.clone(),
+                [0., 0., 1.],
+                SweepOptions {
+                    taper_angle: Scalar::from(taper_angle),
+                    ..SweepOptions::default()
+                },
+                &mut core,
+            )
+        };
+
+        assert!(matches!(
+            sweep(&square, std::f64::consts::FRAC_PI_2),
+            Err(SweepSketchError::TaperAngleOutOfRange { .. })
+        ));
+        assert!(matches!(
+            sweep(&square, 1.),
+            Err(SweepSketchError::TaperTooLarge { .. })
+        ));
+        assert!(matches!(
+            sweep(&circle, 0.1),
+            Err(SweepSketchError::UnsupportedTaperedHalfEdge { .. })
+        ));
     }
 }
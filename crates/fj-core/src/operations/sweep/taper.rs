@@ -0,0 +1,206 @@
+use fj_math::{Point, Scalar, Vector};
+
+use crate::{
+    geometry::SurfacePath,
+    objects::{Curve, Cycle, Face, HalfEdge, Region, Shell, Surface, Vertex},
+    operations::{
+        build::BuildSurface,
+        insert::Insert,
+        presentation::{GetColor, SetColor},
+        transform::TransformObject,
+    },
+    storage::Handle,
+    Core,
+};
+
+use super::SweepSketchError;
+
+/// Sweep a region into a shell, inclining the side faces
+///
+/// Expects the region that bounds the bottom of the sweep, oriented to face
+/// against the sweep path. Each corner of the region moves towards the inside
+/// of the region, so that each side of the region moves by `taper` over the
+/// length of the sweep.
+pub fn sweep_region_with_taper(
+    region: &Handle<Region>,
+    surface: &Handle<Surface>,
+    normal: Vector<3>,
+    path: Vector<3>,
+    taper: Scalar,
+    core: &mut Core,
+) -> Result<Shell, SweepSketchError> {
+    let tolerance = core.layers.validation.config.identical_max_distance;
+    let geometry = surface.geometry();
+
+    // The inside of a face is to the left of its half-edges, when looking at
+    // its front side.
+    let front = if region.exterior().winding().is_ccw() {
+        normal
+    } else {
+        -normal
+    }
+    .normalize();
+
+    let mut cycles = Vec::new();
+    for cycle in region.all_cycles() {
+        let half_edges = cycle.half_edges().iter().collect::<Vec<_>>();
+
+        for half_edge in &half_edges {
+            let SurfacePath::Line(_) = half_edge.path() else {
+                return Err(SweepSketchError::UnsupportedTaperedHalfEdge {
+                    half_edge: (*half_edge).clone(),
+                });
+            };
+        }
+
+        let corners = half_edges
+            .iter()
+            .map(|half_edge| {
+                geometry.point_from_surface_coords(half_edge.start_position())
+            })
+            .collect::<Vec<_>>();
+        let n = corners.len();
+
+        let left = (0..n)
+            .map(|i| front.cross(&(corners[(i + 1) % n] - corners[i])))
+            .map(|direction| direction.normalize())
+            .collect::<Vec<_>>();
+
+        // Each corner moves, so that both of its sides move by the taper.
+        let mut tapered = Vec::new();
+        for i in 0..n {
+            let [a, b] = [left[(i + n - 1) % n], left[i]];
+
+            let denominator = Scalar::ONE + a.dot(&b);
+            if denominator <= tolerance {
+                return Err(SweepSketchError::TaperTooLarge {
+                    region: region.clone(),
+                });
+            }
+
+            tapered.push(corners[i] + (a + b) * (taper / denominator));
+        }
+
+        // If a side flips around, the taper has made it collapse.
+        for i in 0..n {
+            let j = (i + 1) % n;
+            let [side, tapered_side] =
+                [&corners, &tapered].map(|points| points[j] - points[i]);
+
+            if side.dot(&tapered_side) <= tolerance {
+                return Err(SweepSketchError::TaperTooLarge {
+                    region: region.clone(),
+                });
+            }
+        }
+
+        cycles.push((half_edges, corners, tapered));
+    }
+
+    let color = region.get_color(core);
+    let top_surface = surface.translate(path, core).insert(core);
+
+    let mut faces =
+        vec![Face::new(surface.clone(), region.clone()).insert(core)];
+    let mut top_cycles = Vec::new();
+
+    for (half_edges, corners, tapered) in cycles {
+        let n = corners.len();
+
+        let top_vertices = (0..n)
+            .map(|_| Vertex::new().insert(core))
+            .collect::<Vec<_>>();
+        let side_curves = (0..n)
+            .map(|_| Curve::new().insert(core))
+            .collect::<Vec<_>>();
+
+        let line = |a: Point<2>, b: Point<2>| {
+            SurfacePath::line_from_points_with_coords([([0.], a), ([1.], b)])
+        };
+        let [zero, one] =
+            [Scalar::ZERO, Scalar::ONE].map(|coord| Point::from([coord]));
+
+        let mut top_half_edges = Vec::new();
+        for (i, half_edge) in half_edges.iter().enumerate() {
+            let j = (i + 1) % n;
+
+            let [top_start, top_end] = [tapered[i] + path, tapered[j] + path];
+            let (side_surface, [start, end, top_end_side]) =
+                Surface::plane_from_points(
+                    [corners[i], corners[j], top_end],
+                    core,
+                );
+            let top_start_side =
+                side_surface.geometry().project_global_point(top_start);
+
+            let top_curve = Curve::new().insert(core);
+            let [b_start, b_end] = half_edge.boundary().inner;
+
+            let side_half_edges = [
+                HalfEdge::new(
+                    SurfacePath::line_from_points_with_coords([
+                        (b_start, start),
+                        (b_end, end),
+                    ]),
+                    [b_end, b_start],
+                    half_edge.curve().clone(),
+                    half_edges[j].start_vertex().clone(),
+                ),
+                HalfEdge::new(
+                    line(start, top_start_side),
+                    [zero, one],
+                    side_curves[i].clone(),
+                    half_edge.start_vertex().clone(),
+                ),
+                HalfEdge::new(
+                    line(top_start_side, top_end_side),
+                    [zero, one],
+                    top_curve.clone(),
+                    top_vertices[i].clone(),
+                ),
+                HalfEdge::new(
+                    line(end, top_end_side),
+                    [one, zero],
+                    side_curves[j].clone(),
+                    top_vertices[j].clone(),
+                ),
+            ];
+
+            let exterior = Cycle::new(
+                side_half_edges.map(|half_edge| half_edge.insert(core)),
+            )
+            .insert(core);
+            let side_region = Region::new(exterior, []).insert(core);
+            if let Some(color) = color {
+                side_region.set_color(color, core);
+            }
+            faces.push(Face::new(side_surface, side_region).insert(core));
+
+            let [top_start, top_end] = [tapered[i], tapered[j]]
+                .map(|point| geometry.project_global_point(point));
+            top_half_edges.push(
+                HalfEdge::new(
+                    line(top_start, top_end),
+                    [one, zero],
+                    top_curve,
+                    top_vertices[j].clone(),
+                )
+                .insert(core),
+            );
+        }
+
+        // The top needs to face the other way than the bottom, so its cycles
+        // run in the opposite direction.
+        top_half_edges.reverse();
+        top_cycles.push(Cycle::new(top_half_edges).insert(core));
+    }
+
+    let exterior = top_cycles.remove(0);
+    let top_region = Region::new(exterior, top_cycles).insert(core);
+    if let Some(color) = color {
+        top_region.set_color(color, core);
+    }
+    faces.push(Face::new(top_surface, top_region).insert(core));
+
+    Ok(Shell::new(faces))
+}
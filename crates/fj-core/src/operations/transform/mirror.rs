@@ -0,0 +1,134 @@
+use fj_math::{Point, Transform, Vector};
+
+use crate::{
+    objects::{Face, Shell, Solid},
+    operations::{derive::DeriveFrom, insert::Insert, reverse::Reverse},
+    Core,
+};
+
+use super::TransformObject;
+
+/// Reflect an object across a plane
+pub trait Mirror: Sized {
+    /// Reflect the object across the plane with the provided origin and normal
+    ///
+    /// A reflection turns faces inside out. To make up for that, the
+    /// orientation of all faces is reversed, so faces that pointed outward
+    /// before, still do afterwards.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `normal` is zero.
+    fn mirror(
+        &self,
+        origin: impl Into<Point<3>>,
+        normal: impl Into<Vector<3>>,
+        core: &mut Core,
+    ) -> Self;
+}
+
+impl Mirror for Face {
+    fn mirror(
+        &self,
+        origin: impl Into<Point<3>>,
+        normal: impl Into<Vector<3>>,
+        core: &mut Core,
+    ) -> Self {
+        let transform = Transform::reflection(origin, normal);
+
+        core.trace_operation("mirror", &[self, &transform], |core| {
+            self.transform(&transform, core).reverse(core)
+        })
+    }
+}
+
+impl Mirror for Shell {
+    fn mirror(
+        &self,
+        origin: impl Into<Point<3>>,
+        normal: impl Into<Vector<3>>,
+        core: &mut Core,
+    ) -> Self {
+        let transform = Transform::reflection(origin, normal);
+
+        core.trace_operation("mirror", &[self, &transform], |core| {
+            reverse_faces(&self.transform(&transform, core), core)
+        })
+    }
+}
+
+impl Mirror for Solid {
+    fn mirror(
+        &self,
+        origin: impl Into<Point<3>>,
+        normal: impl Into<Vector<3>>,
+        core: &mut Core,
+    ) -> Self {
+        let transform = Transform::reflection(origin, normal);
+
+        core.trace_operation("mirror", &[self, &transform], |core| {
+            let solid = self.transform(&transform, core);
+
+            let shells = solid.shells().iter().map(|shell| {
+                reverse_faces(shell, core)
+                    .insert(core)
+                    .derive_from(shell, core)
+            });
+
+            Solid::new(shells)
+        })
+    }
+}
+
+fn reverse_faces(shell: &Shell, core: &mut Core) -> Shell {
+    // Siblings are reversed together, so they stay siblings.
+    let faces = shell
+        .faces()
+        .iter()
+        .map(|face| face.reverse(core).insert(core).derive_from(face, core));
+
+    Shell::new(faces)
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Scalar;
+    use fj_test_utils::{assert_volume_eq, assert_watertight};
+
+    use crate::{
+        algorithms::{approx::Tolerance, triangulate::Triangulate},
+        objects::{Shell, Solid},
+        operations::{build::BuildShell, insert::Insert},
+        Core,
+    };
+
+    use super::Mirror;
+
+    #[test]
+    fn mirror() -> anyhow::Result<()> {
+        let mut core = Core::new();
+        let tolerance = Tolerance::from_scalar(0.01)?;
+
+        let shell = Shell::cuboid([1., 0., 0.], [2., 1., 3.], &mut core)
+            .insert(&mut core);
+        let solid = Solid::new([shell]);
+
+        let mirrored = solid
+            .mirror([0., 0., 0.], [1., 1., 0.], &mut core)
+            .insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        let mesh = (&*mirrored, tolerance).triangulate(&mut core);
+
+        // The cuboid ends up on the other side of the plane.
+        assert!(mesh
+            .vertices()
+            .all(|point| point.x + point.y <= Scalar::from(1e-9)));
+
+        // The faces must still point outward.
+        assert_watertight!(mesh);
+        assert_volume_eq!(mesh, 3., 1e-9);
+
+        Ok(())
+    }
+}
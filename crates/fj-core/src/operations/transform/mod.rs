@@ -4,13 +4,17 @@ mod curve;
 mod cycle;
 mod edge;
 mod face;
+mod mirror;
 mod region;
 mod shell;
 mod solid;
 mod surface;
 mod vertex;
 
-use std::collections::{btree_map, BTreeMap};
+use std::{
+    collections::{btree_map, BTreeMap},
+    fmt,
+};
 
 use fj_math::{Transform, Vector};
 use type_map::TypeMap;
@@ -24,6 +28,8 @@ use crate::{
 
 use super::derive::DeriveFrom;
 
+pub use self::mirror::Mirror;
+
 /// Transform an object
 ///
 /// # Implementation Note
@@ -33,11 +39,13 @@ use super::derive::DeriveFrom;
 ///
 /// More convenience methods can be added as required. The only reason this
 /// hasn't been done so far, is that no one has put in the work yet.
-pub trait TransformObject: Sized {
+pub trait TransformObject: Sized + fmt::Debug {
     /// Transform the object
     fn transform(&self, transform: &Transform, core: &mut Core) -> Self {
-        let mut cache = TransformCache::default();
-        self.transform_with_cache(transform, core, &mut cache)
+        core.trace_operation("transform", &[self, transform], |core| {
+            let mut cache = TransformCache::default();
+            self.transform_with_cache(transform, core, &mut cache)
+        })
     }
 
     /// Transform the object using the provided cache
@@ -16,8 +16,8 @@ impl TransformObject for Handle<Surface> {
         cache
             .entry(self)
             .or_insert_with(|| {
-                let geometry = self.geometry().transform(transform);
-                let surface = Surface::new(geometry).insert(core);
+                let geometry = self.geometry().clone().transform(transform);
+                let surface = Surface::new(geometry.clone()).insert(core);
 
                 core.layers
                     .geometry
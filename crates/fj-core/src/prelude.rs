@@ -0,0 +1,64 @@
+//! # Convenient access to the most commonly used items
+//!
+//! Most of the functionality in this crate is provided by the extension traits
+//! in [`operations`], which need to be in scope to be used. Importing each of
+//! them individually requires knowledge of the module layout, and results in
+//! long lists of `use` statements.
+//!
+//! This module re-exports all operation traits, as well as the object types
+//! and other items that are needed to define a model. It is intended to be
+//! used with a glob import:
+//!
+//! ```
+//! use fj_core::prelude::*;
+//! ```
+//!
+//! [`operations`]: crate::operations
+
+pub use crate::{
+    objects::{
+        Curve, Cycle, Face, HalfEdge, Region, Shell, Sketch, Solid, Surface,
+        Vertex,
+    },
+    operations::{
+        bitmap::TraceBitmap,
+        boolean::Boolean,
+        build::{
+            BuildCycle, BuildFace, BuildHalfEdge, BuildRegion, BuildShell,
+            BuildSketch, BuildSolid, BuildSurface,
+        },
+        chamfer::ChamferEdge,
+        cleanup::RemoveInternalFaces,
+        derive::DeriveFrom,
+        emboss::Emboss,
+        extend::ExtendFace,
+        holes::AddHole,
+        insert::Insert,
+        join::JoinCycle,
+        merge::Merge,
+        move_vertex::MoveVertex,
+        offset::OffsetRegion,
+        pad::Pad,
+        pipe::{Pipe, PipePath},
+        place::Place,
+        pocket::{Pocket, PocketDepth},
+        presentation::{GetColor, GetName, SetColor, SetName},
+        replace::{ReplaceCurve, ReplaceHalfEdge, ReplaceVertex},
+        reverse::{Reverse, ReverseCurveCoordinateSystems},
+        revolve::Revolve,
+        section::SectionWithPlane,
+        split::{SplitEdge, SplitFace, SplitHalfEdge, SplitShell},
+        sweep::{
+            SweepCycle, SweepDirection, SweepFace, SweepFaceOfShell,
+            SweepHalfEdge, SweepRegion, SweepSketch, SweepSurfacePath,
+            SweepVertex,
+        },
+        transform::{Mirror, TransformObject},
+        update::{
+            UpdateCycle, UpdateFace, UpdateHalfEdge, UpdateRegion, UpdateShell,
+            UpdateSketch, UpdateSolid,
+        },
+    },
+    storage::Handle,
+    Core,
+};
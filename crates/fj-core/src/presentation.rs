@@ -10,8 +10,8 @@ use crate::{objects::Region, storage::Handle};
 
 /// Presentation data for the object graph
 ///
-/// Assigns attributes relating to the presentation of objects (currently just a
-/// color) to those objects (currently only to regions).
+/// Assigns attributes relating to the presentation of objects (currently a
+/// color and a name) to those objects (currently only to regions).
 ///
 /// This data is made available through [`Layers`].
 ///
@@ -23,4 +23,14 @@ pub struct Presentation {
     /// Having a color is optional, so map does not necessarily contain
     /// assignments for all existing regions.
     pub color: BTreeMap<Handle<Region>, Color>,
+
+    /// Name assigned to regions
+    ///
+    /// Names are carried through to exported files, where supported. Multiple
+    /// regions can have the same name, which makes them a named group (for
+    /// example, all the holes that need to be tapped).
+    ///
+    /// Having a name is optional, so map does not necessarily contain
+    /// assignments for all existing regions.
+    pub name: BTreeMap<Handle<Region>, String>,
 }
@@ -0,0 +1,73 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    objects::{Face, Shell},
+    storage::{Handle, ObjectId},
+};
+
+/// Queries related to how the faces of an object are connected
+pub trait ConnectedFaces {
+    /// Group the faces into parts that are connected to each other
+    ///
+    /// Faces are connected, if they have half-edges that share a curve. Each
+    /// face is part of exactly one group. Groups, and faces within them, are
+    /// ordered by the first occurrence of a face in the object.
+    fn connected_components(&self) -> Vec<Vec<Handle<Face>>>;
+}
+
+impl ConnectedFaces for Shell {
+    fn connected_components(&self) -> Vec<Vec<Handle<Face>>> {
+        let faces = self.faces().iter().collect::<Vec<_>>();
+
+        let mut curves_by_face = Vec::new();
+        let mut faces_by_curve = BTreeMap::<ObjectId, Vec<usize>>::new();
+
+        for (i, face) in faces.iter().enumerate() {
+            let curves = face
+                .region()
+                .all_cycles()
+                .flat_map(|cycle| cycle.half_edges())
+                .map(|half_edge| half_edge.curve().id())
+                .collect::<Vec<_>>();
+
+            for &curve in &curves {
+                faces_by_curve.entry(curve).or_default().push(i);
+            }
+
+            curves_by_face.push(curves);
+        }
+
+        let mut visited = vec![false; faces.len()];
+        let mut components = Vec::new();
+
+        for start in 0..faces.len() {
+            if visited[start] {
+                continue;
+            }
+
+            let mut component = Vec::new();
+            let mut to_visit = vec![start];
+            visited[start] = true;
+
+            while let Some(i) = to_visit.pop() {
+                component.push(i);
+
+                for curve in &curves_by_face[i] {
+                    for &j in &faces_by_curve[curve] {
+                        if !visited[j] {
+                            visited[j] = true;
+                            to_visit.push(j);
+                        }
+                    }
+                }
+            }
+
+            component.sort();
+            components.push(
+                component.into_iter().map(|i| faces[i].clone()).collect(),
+            );
+        }
+
+        components
+    }
+}
@@ -0,0 +1,283 @@
+use fj_math::{Point, Scalar, Vector};
+
+use crate::{
+    geometry::{GlobalPath, SurfaceGeometry, SurfacePath},
+    objects::{Cycle, Face, HalfEdge, Region},
+    storage::Handle,
+    validation::ValidationConfig,
+};
+
+/// Determine how smoothly a half-edge continues into the next one
+pub trait ContinuityOfHalfEdges {
+    /// Determine the continuity between a half-edge and the next one
+    ///
+    /// The next half-edge is the one that follows the provided half-edge in its
+    /// cycle. Returns `None`, if the provided half-edge is not part of the
+    /// object this method is called on.
+    fn continuity_after_half_edge(
+        &self,
+        half_edge: &Handle<HalfEdge>,
+    ) -> Option<Continuity>;
+}
+
+impl ContinuityOfHalfEdges for Cycle {
+    /// Determine the continuity in surface coordinates
+    ///
+    /// A cycle doesn't know the surface it is defined on. The distances and
+    /// angles reported are measured in surface coordinates, which might be
+    /// distorted, compared to model coordinates. Whether the half-edges are
+    /// continuous or not is not affected by that.
+    fn continuity_after_half_edge(
+        &self,
+        half_edge: &Handle<HalfEdge>,
+    ) -> Option<Continuity> {
+        let next = self.half_edges().after(half_edge)?;
+
+        let [end, start] = [end_of(half_edge), start_of(next)];
+
+        Some(Continuity::new(
+            start.position - end.position,
+            end.tangent,
+            start.tangent,
+        ))
+    }
+}
+
+impl ContinuityOfHalfEdges for Region {
+    /// Determine the continuity in surface coordinates
+    ///
+    /// See the implementation for [`Cycle`] for more information.
+    fn continuity_after_half_edge(
+        &self,
+        half_edge: &Handle<HalfEdge>,
+    ) -> Option<Continuity> {
+        self.all_cycles()
+            .find_map(|cycle| cycle.continuity_after_half_edge(half_edge))
+    }
+}
+
+impl ContinuityOfHalfEdges for Face {
+    /// Determine the continuity in model coordinates
+    fn continuity_after_half_edge(
+        &self,
+        half_edge: &Handle<HalfEdge>,
+    ) -> Option<Continuity> {
+        let next = self
+            .region()
+            .all_cycles()
+            .find_map(|cycle| cycle.half_edges().after(half_edge))?;
+
+        let surface = self.surface().geometry();
+        let [end, start] = [end_of(half_edge), start_of(next)].map(|point| {
+            let position = surface.point_from_surface_coords(point.position);
+            let tangent =
+                tangent_in_model_coords(surface, point.position, point.tangent);
+
+            (position, tangent)
+        });
+
+        Some(Continuity::new(start.0 - end.0, end.1, start.1))
+    }
+}
+
+/// The continuity between a half-edge and the next one
+///
+/// See [`ContinuityOfHalfEdges`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Continuity {
+    /// The distance between the end of the half-edge and the start of the next
+    pub position_gap: Scalar,
+
+    /// The angle between the directions of the half-edges, in radians
+    ///
+    /// This is the angle between the direction at the end of the half-edge,
+    /// and the direction at the start of the next one. It is zero, if one
+    /// continues straight into the other, and pi, if the next half-edge turns
+    /// back on itself.
+    pub tangent_angle: Scalar,
+}
+
+impl Continuity {
+    fn new<const D: usize>(
+        gap: Vector<D>,
+        tangent_of_first: Vector<D>,
+        tangent_of_second: Vector<D>,
+    ) -> Self {
+        let [a, b] = [tangent_of_first, tangent_of_second]
+            .map(|tangent| tangent.normalize());
+
+        // Computing the angle from both sine and cosine is more precise for
+        // small angles than computing it from the cosine alone.
+        let cos = a.dot(&b);
+        let sin = (b - a * cos).magnitude();
+
+        Self {
+            position_gap: gap.magnitude(),
+            tangent_angle: Scalar::atan2(sin, cos),
+        }
+    }
+
+    /// Indicate whether the half-edges are positionally continuous (G0)
+    pub fn is_g0(&self, config: &ValidationConfig) -> bool {
+        self.position_gap <= config.identical_max_distance
+    }
+
+    /// Indicate whether the half-edges are tangentially continuous (G1)
+    ///
+    /// Half-edges that are G1 continuous are also G0 continuous, and meet
+    /// without forming a corner.
+    pub fn is_g1(&self, config: &ValidationConfig) -> bool {
+        self.is_g0(config) && self.tangent_angle <= config.identical_max_angle
+    }
+}
+
+struct PointWithTangent {
+    position: Point<2>,
+    tangent: Vector<2>,
+}
+
+fn start_of(half_edge: &HalfEdge) -> PointWithTangent {
+    let [start, end] = half_edge.boundary().inner;
+    point_with_tangent(half_edge.path(), start, end)
+}
+
+fn end_of(half_edge: &HalfEdge) -> PointWithTangent {
+    let [start, end] = half_edge.boundary().inner;
+    let mut point = point_with_tangent(half_edge.path(), end, start);
+
+    // The direction was computed towards the start of the half-edge, but it
+    // needs to point the way the half-edge runs.
+    point.tangent = -point.tangent;
+    point
+}
+
+/// Compute the point on the path, and the direction towards the other point
+fn point_with_tangent(
+    path: SurfacePath,
+    point: Point<1>,
+    towards: Point<1>,
+) -> PointWithTangent {
+    let position = path.point_from_path_coords(point);
+    let tangent = match path {
+        SurfacePath::Circle(circle) => {
+            let (sin, cos) = point.t.sin_cos();
+            circle.b() * cos - circle.a() * sin
+        }
+        SurfacePath::CubicBezier(bezier) => bezier.derivative(point),
+        SurfacePath::Ellipse(ellipse) => {
+            let (sin, cos) = point.t.sin_cos();
+            ellipse.b() * cos - ellipse.a() * sin
+        }
+        SurfacePath::Line(line) => line.direction(),
+    };
+
+    let tangent = if towards.t < point.t {
+        -tangent
+    } else {
+        tangent
+    };
+
+    PointWithTangent { position, tangent }
+}
+
+fn tangent_in_model_coords(
+    surface: &SurfaceGeometry,
+    position: Point<2>,
+    tangent: Vector<2>,
+) -> Vector<3> {
+    let [u, v] = match surface {
+        SurfaceGeometry::Swept { u, v } => {
+            let u = match u {
+                GlobalPath::Circle(circle) => {
+                    let (sin, cos) = position.u.sin_cos();
+                    circle.b() * cos - circle.a() * sin
+                }
+                GlobalPath::CubicBezier(bezier) => {
+                    bezier.derivative([position.u])
+                }
+                GlobalPath::Ellipse(ellipse) => {
+                    let (sin, cos) = position.u.sin_cos();
+                    ellipse.b() * cos - ellipse.a() * sin
+                }
+                GlobalPath::Line(line) => line.direction(),
+            };
+
+            [u, *v]
+        }
+        SurfaceGeometry::Nurbs(nurbs) => nurbs.derivatives(position),
+    };
+
+    u * tangent.u + v * tangent.v
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Scalar;
+    use fj_test_utils::assert_scalars_eq;
+
+    use crate::{
+        objects::{Cycle, Face, HalfEdge, Region},
+        operations::{
+            build::{BuildCycle, BuildHalfEdge},
+            insert::Insert,
+            update::UpdateCycle,
+        },
+        validation::ValidationConfig,
+        Core,
+    };
+
+    use super::ContinuityOfHalfEdges;
+
+    #[test]
+    fn continuity_after_half_edge() {
+        let mut core = Core::new();
+        let config = ValidationConfig::default();
+
+        // A square with a rounded corner, where the arc continues smoothly
+        // into the lines.
+        let cycle = Cycle::empty().add_half_edges(
+            [
+                HalfEdge::line_segment([[0., 0.], [1., 0.]], None, &mut core),
+                HalfEdge::arc([1., 0.], [2., 1.], Scalar::PI / 2., &mut core),
+                HalfEdge::line_segment([[2., 1.], [2., 2.]], None, &mut core),
+                HalfEdge::line_segment([[2., 2.], [0., 2.]], None, &mut core),
+                HalfEdge::line_segment([[0., 2.], [0., 0.]], None, &mut core),
+            ],
+            &mut core,
+        );
+        let half_edges = cycle.half_edges().iter().collect::<Vec<_>>();
+
+        for (half_edge, is_g1) in
+            half_edges.iter().zip([true, true, false, false, false])
+        {
+            let continuity = cycle
+                .continuity_after_half_edge(half_edge)
+                .expect("Half-edge is part of cycle");
+
+            assert!(continuity.is_g0(&config));
+            assert_eq!(continuity.is_g1(&config), is_g1);
+        }
+
+        let corner = cycle
+            .continuity_after_half_edge(half_edges[2])
+            .expect("Half-edge is part of cycle");
+        assert_scalars_eq!(corner.tangent_angle, Scalar::PI / 2., 1e-12);
+
+        // The face is on the xz-plane, whose coordinates are not distorted, so
+        // it should report the same.
+        let surface = core.layers.objects.surfaces.xz_plane();
+        let face = Face::new(
+            surface,
+            Region::new(cycle.clone().insert(&mut core), []).insert(&mut core),
+        );
+        assert_eq!(
+            face.continuity_after_half_edge(half_edges[2]),
+            Some(corner)
+        );
+
+        let other =
+            HalfEdge::line_segment([[0., 0.], [1., 0.]], None, &mut core)
+                .insert(&mut core);
+        assert!(cycle.continuity_after_half_edge(&other).is_none());
+    }
+}
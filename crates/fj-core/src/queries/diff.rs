@@ -0,0 +1,330 @@
+use std::fmt;
+
+use fj_math::{Point, Scalar, Vector};
+
+use crate::{
+    geometry::{GlobalPath, SurfaceGeometry},
+    objects::{Face, Shell, Solid},
+    storage::Handle,
+};
+
+use super::{ObjectStats, Stats};
+
+/// Compare two shapes
+pub trait Diff: Stats {
+    /// Compare this shape to another one
+    ///
+    /// `self` is considered to be the old version of the shape, `other` the new
+    /// one. Faces are matched up by identity, or by geometry: Faces are
+    /// considered to be the same, if their surfaces and boundaries are the
+    /// same. Points that are no more than `max_distance` apart are considered
+    /// to be the same.
+    ///
+    /// Faces that can't be matched that way, but are on the same surface as a
+    /// face of the other shape, are considered to be changed. All others have
+    /// been added or removed.
+    fn diff(&self, other: &Self, max_distance: impl Into<Scalar>) -> ShapeDiff {
+        let max_distance = max_distance.into();
+
+        let mut old = faces_with_geometry(self.all_faces());
+        let mut new = faces_with_geometry(other.all_faces());
+
+        let unchanged = match_faces(&mut old, &mut new, |a, b| {
+            a.is_same_as(b, max_distance)
+        });
+        let changed = match_faces(&mut old, &mut new, |a, b| {
+            a.is_on_same_surface_as(b, max_distance)
+        });
+
+        ShapeDiff {
+            old_stats: self.stats(),
+            new_stats: other.stats(),
+            unchanged,
+            changed,
+            removed: old.into_iter().map(|(face, _)| face).collect(),
+            added: new.into_iter().map(|(face, _)| face).collect(),
+        }
+    }
+
+    /// Access all faces of the shape
+    ///
+    /// This is an implementation detail of [`Diff::diff`], which should be
+    /// called instead.
+    #[doc(hidden)]
+    fn all_faces(&self) -> Vec<Handle<Face>>;
+}
+
+impl Diff for Solid {
+    fn all_faces(&self) -> Vec<Handle<Face>> {
+        self.shells()
+            .iter()
+            .flat_map(|shell| shell.faces().iter().cloned())
+            .collect()
+    }
+}
+
+impl Diff for Shell {
+    fn all_faces(&self) -> Vec<Handle<Face>> {
+        self.faces().iter().cloned().collect()
+    }
+}
+
+/// The differences between two shapes
+///
+/// Returned by [`Diff::diff`].
+#[derive(Clone, Debug)]
+pub struct ShapeDiff {
+    /// The number of objects that make up the old shape
+    pub old_stats: ObjectStats,
+
+    /// The number of objects that make up the new shape
+    pub new_stats: ObjectStats,
+
+    /// The faces that are the same in both shapes, as `[old, new]`
+    pub unchanged: Vec<[Handle<Face>; 2]>,
+
+    /// The faces that are on the same surface, but have a different boundary
+    ///
+    /// Stored as `[old, new]`.
+    pub changed: Vec<[Handle<Face>; 2]>,
+
+    /// The faces of the old shape that don't exist in the new one
+    pub removed: Vec<Handle<Face>>,
+
+    /// The faces of the new shape that don't exist in the old one
+    pub added: Vec<Handle<Face>>,
+}
+
+impl ShapeDiff {
+    /// Indicate whether the two shapes are geometrically the same
+    ///
+    /// The number of objects that make up the two shapes is not considered.
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty()
+            && self.removed.is_empty()
+            && self.added.is_empty()
+    }
+}
+
+impl fmt::Display for ShapeDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "old: {}", self.old_stats)?;
+        writeln!(f, "new: {}", self.new_stats)?;
+        write!(
+            f,
+            "{} faces unchanged, {} changed, {} removed, {} added",
+            self.unchanged.len(),
+            self.changed.len(),
+            self.removed.len(),
+            self.added.len(),
+        )
+    }
+}
+
+/// Remove all matching faces from the two lists, and return them as pairs
+///
+/// Identical faces always match.
+fn match_faces(
+    a: &mut Vec<(Handle<Face>, FaceGeometry)>,
+    b: &mut Vec<(Handle<Face>, FaceGeometry)>,
+    matches: impl Fn(&FaceGeometry, &FaceGeometry) -> bool,
+) -> Vec<[Handle<Face>; 2]> {
+    let mut matched = Vec::new();
+
+    let mut i = 0;
+    while i < a.len() {
+        let (face_a, geometry_a) = &a[i];
+
+        let j = b.iter().position(|(face_b, geometry_b)| {
+            face_a.id() == face_b.id() || matches(geometry_a, geometry_b)
+        });
+
+        match j {
+            Some(j) => {
+                let (face_a, _) = a.remove(i);
+                let (face_b, _) = b.remove(j);
+                matched.push([face_a, face_b]);
+            }
+            None => {
+                i += 1;
+            }
+        }
+    }
+
+    matched
+}
+
+fn faces_with_geometry(
+    faces: Vec<Handle<Face>>,
+) -> Vec<(Handle<Face>, FaceGeometry)> {
+    faces
+        .into_iter()
+        .map(|face| {
+            let geometry = FaceGeometry::new(&face);
+            (face, geometry)
+        })
+        .collect()
+}
+
+/// The geometry of a face, in global coordinates
+struct FaceGeometry {
+    surface: SurfaceKind,
+
+    /// Points on the boundary, for each cycle
+    ///
+    /// Contains the start and middle of each half-edge.
+    cycles: Vec<Vec<Point<3>>>,
+}
+
+impl FaceGeometry {
+    fn new(face: &Face) -> Self {
+        let surface = face.surface().geometry();
+
+        let cycles = face
+            .region()
+            .all_cycles()
+            .map(|cycle| {
+                cycle
+                    .half_edges()
+                    .iter()
+                    .flat_map(|half_edge| {
+                        let [start, end] = half_edge.boundary().inner;
+                        let middle = start + (end - start) / 2.;
+
+                        [start, middle].map(|point| {
+                            surface.point_from_surface_coords(
+                                half_edge.path().point_from_path_coords(point),
+                            )
+                        })
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let surface = match surface {
+            SurfaceGeometry::Swept {
+                u: GlobalPath::Line(line),
+                v,
+            } => SurfaceKind::Plane {
+                origin: line.origin(),
+                normal: line.direction().cross(v).normalize(),
+            },
+            SurfaceGeometry::Swept {
+                u:
+                    GlobalPath::Circle(_)
+                    | GlobalPath::CubicBezier(_)
+                    | GlobalPath::Ellipse(_),
+                ..
+            }
+            | SurfaceGeometry::Nurbs(_) => SurfaceKind::Curved {
+                points: [[0., 0.], [1., 0.], [0., 1.]]
+                    .map(|point| surface.point_from_surface_coords(point)),
+            },
+        };
+
+        Self { surface, cycles }
+    }
+
+    fn is_same_as(&self, other: &Self, max_distance: Scalar) -> bool {
+        if !self.is_on_same_surface_as(other, max_distance) {
+            return false;
+        }
+        if self.cycles.len() != other.cycles.len() {
+            return false;
+        }
+
+        self.cycles.iter().all(|a| {
+            other.cycles.iter().any(|b| same_points(a, b, max_distance))
+        })
+    }
+
+    fn is_on_same_surface_as(
+        &self,
+        other: &Self,
+        max_distance: Scalar,
+    ) -> bool {
+        match (self.surface, other.surface) {
+            (
+                SurfaceKind::Plane { origin, normal },
+                SurfaceKind::Plane {
+                    origin: other_origin,
+                    normal: other_normal,
+                },
+            ) => {
+                (normal - other_normal).magnitude() <= max_distance
+                    && (other_origin - origin).dot(&normal).abs()
+                        <= max_distance
+            }
+            (
+                SurfaceKind::Curved { points },
+                SurfaceKind::Curved {
+                    points: other_points,
+                },
+            ) => points
+                .iter()
+                .zip(other_points)
+                .all(|(a, b)| a.distance_to(&b) <= max_distance),
+            _ => false,
+        }
+    }
+}
+
+/// The surface of a face, in global coordinates
+#[derive(Clone, Copy)]
+enum SurfaceKind {
+    /// A plane, which is the same, regardless of how it is parametrized
+    Plane { origin: Point<3>, normal: Vector<3> },
+
+    /// A curved surface, defined by points at its origin and along its axes
+    ///
+    /// Curved surfaces are only considered to be the same, if they are
+    /// parametrized the same way.
+    Curved { points: [Point<3>; 3] },
+}
+
+/// Determine whether two lists of points contain the same points
+///
+/// The order of the points is not considered.
+fn same_points(a: &[Point<3>], b: &[Point<3>], max_distance: Scalar) -> bool {
+    let contains_all = |a: &[Point<3>], b: &[Point<3>]| {
+        a.iter()
+            .all(|&a| b.iter().any(|&b| a.distance_to(&b) <= max_distance))
+    };
+
+    a.len() == b.len() && contains_all(a, b) && contains_all(b, a)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        objects::Shell, operations::build::BuildShell, queries::Diff, Core,
+    };
+
+    #[test]
+    fn diff() {
+        let mut core = Core::new();
+
+        let a = Shell::cuboid([0., 0., 0.], [1., 1., 1.], &mut core);
+        let b = Shell::cuboid([0., 0., 0.], [1., 1., 1.], &mut core);
+        let c = Shell::cuboid([0., 0., 0.], [1., 1., 2.], &mut core);
+
+        let diff = a.diff(&a, 0.);
+        assert!(diff.is_empty());
+        assert_eq!(diff.unchanged.len(), 6);
+
+        // Different objects, but the same geometry.
+        let diff = a.diff(&b, 0.);
+        assert!(diff.is_empty());
+        assert_eq!(diff.unchanged.len(), 6);
+        assert_eq!(diff.old_stats, diff.new_stats);
+
+        // The bottom face stays the same, the side faces are stretched, and the
+        // top face moves.
+        let diff = a.diff(&c, 0.);
+        assert!(!diff.is_empty());
+        assert_eq!(diff.unchanged.len(), 1);
+        assert_eq!(diff.changed.len(), 4);
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.added.len(), 1);
+    }
+}
@@ -11,10 +11,18 @@
 
 mod all_half_edges_with_surface;
 mod bounding_vertices_of_half_edge;
+mod connected_faces;
+mod continuity_of_half_edges;
+mod diff;
 mod sibling_of_half_edge;
+mod stats;
 
 pub use self::{
     all_half_edges_with_surface::AllHalfEdgesWithSurface,
     bounding_vertices_of_half_edge::BoundingVerticesOfHalfEdge,
+    connected_faces::ConnectedFaces,
+    continuity_of_half_edges::{Continuity, ContinuityOfHalfEdges},
+    diff::{Diff, ShapeDiff},
     sibling_of_half_edge::SiblingOfHalfEdge,
+    stats::{ObjectStats, Stats, Visited},
 };
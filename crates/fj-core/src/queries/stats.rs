@@ -0,0 +1,212 @@
+use std::{collections::BTreeSet, fmt};
+
+use crate::{
+    objects::{Cycle, Face, HalfEdge, Region, Shell, Sketch, Solid},
+    operations::place::Placed,
+    storage::ObjectId,
+};
+
+/// Count the objects that make up an object
+pub trait Stats {
+    /// Count the distinct objects that make up this object, by type
+    ///
+    /// Objects that are referenced multiple times (like a curve that is shared
+    /// by two half-edges) are only counted once.
+    fn stats(&self) -> ObjectStats {
+        let mut visited = Visited::default();
+        self.visit(&mut visited);
+        visited.into()
+    }
+
+    /// Visit all objects that make up this object
+    ///
+    /// This is an implementation detail of [`Stats::stats`], which should be
+    /// called instead.
+    #[doc(hidden)]
+    fn visit(&self, visited: &mut Visited);
+}
+
+impl Stats for Solid {
+    fn visit(&self, visited: &mut Visited) {
+        visited.solids += 1;
+
+        for shell in self.shells() {
+            if visited.shells.insert(shell.id()) {
+                shell.visit(visited);
+            }
+        }
+    }
+}
+
+impl<T: Stats> Stats for Placed<T> {
+    fn visit(&self, visited: &mut Visited) {
+        self.object().visit(visited);
+    }
+}
+
+impl Stats for Shell {
+    fn visit(&self, visited: &mut Visited) {
+        for face in self.faces() {
+            if visited.faces.insert(face.id()) {
+                face.visit(visited);
+            }
+        }
+    }
+}
+
+impl Stats for Face {
+    fn visit(&self, visited: &mut Visited) {
+        visited.surfaces.insert(self.surface().id());
+
+        if visited.regions.insert(self.region().id()) {
+            self.region().visit(visited);
+        }
+    }
+}
+
+impl Stats for Sketch {
+    fn visit(&self, visited: &mut Visited) {
+        for region in self.regions() {
+            if visited.regions.insert(region.id()) {
+                region.visit(visited);
+            }
+        }
+    }
+}
+
+impl Stats for Region {
+    fn visit(&self, visited: &mut Visited) {
+        for cycle in self.all_cycles() {
+            if visited.cycles.insert(cycle.id()) {
+                cycle.visit(visited);
+            }
+        }
+    }
+}
+
+impl Stats for Cycle {
+    fn visit(&self, visited: &mut Visited) {
+        for half_edge in self.half_edges() {
+            if visited.half_edges.insert(half_edge.id()) {
+                half_edge.visit(visited);
+            }
+        }
+    }
+}
+
+impl Stats for HalfEdge {
+    fn visit(&self, visited: &mut Visited) {
+        visited.curves.insert(self.curve().id());
+        visited.vertices.insert(self.start_vertex().id());
+    }
+}
+
+/// The objects visited while computing [`ObjectStats`]
+///
+/// See [`Stats`].
+#[doc(hidden)]
+#[derive(Default)]
+pub struct Visited {
+    solids: usize,
+    shells: BTreeSet<ObjectId>,
+    faces: BTreeSet<ObjectId>,
+    surfaces: BTreeSet<ObjectId>,
+    regions: BTreeSet<ObjectId>,
+    cycles: BTreeSet<ObjectId>,
+    half_edges: BTreeSet<ObjectId>,
+    curves: BTreeSet<ObjectId>,
+    vertices: BTreeSet<ObjectId>,
+}
+
+impl From<Visited> for ObjectStats {
+    fn from(visited: Visited) -> Self {
+        Self {
+            solids: visited.solids,
+            shells: visited.shells.len(),
+            faces: visited.faces.len(),
+            surfaces: visited.surfaces.len(),
+            regions: visited.regions.len(),
+            cycles: visited.cycles.len(),
+            half_edges: visited.half_edges.len(),
+            curves: visited.curves.len(),
+            vertices: visited.vertices.len(),
+        }
+    }
+}
+
+/// The number of distinct objects that make up an object, by type
+///
+/// Returned by [`Stats::stats`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ObjectStats {
+    /// The number of solids
+    pub solids: usize,
+
+    /// The number of shells
+    pub shells: usize,
+
+    /// The number of faces
+    pub faces: usize,
+
+    /// The number of surfaces
+    pub surfaces: usize,
+
+    /// The number of regions
+    pub regions: usize,
+
+    /// The number of cycles
+    pub cycles: usize,
+
+    /// The number of half-edges
+    pub half_edges: usize,
+
+    /// The number of curves
+    pub curves: usize,
+
+    /// The number of vertices
+    pub vertices: usize,
+}
+
+impl fmt::Display for ObjectStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} solids, {} shells, {} faces, {} surfaces, {} regions, \
+            {} cycles, {} half-edges, {} curves, {} vertices",
+            self.solids,
+            self.shells,
+            self.faces,
+            self.surfaces,
+            self.regions,
+            self.cycles,
+            self.half_edges,
+            self.curves,
+            self.vertices,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        objects::Shell, operations::build::BuildShell, queries::Stats, Core,
+    };
+
+    #[test]
+    fn tetrahedron_stats() {
+        let mut core = Core::new();
+
+        let tetrahedron = Shell::tetrahedron(
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]],
+            &mut core,
+        );
+        let stats = tetrahedron.shell.stats();
+
+        assert_eq!(stats.faces, 4);
+        assert_eq!(stats.regions, 4);
+        assert_eq!(stats.cycles, 4);
+        assert_eq!(stats.half_edges, 12);
+        assert_eq!(stats.curves, 6);
+        assert_eq!(stats.vertices, 4);
+    }
+}
@@ -50,6 +50,24 @@ impl<T> Blocks<T> {
         block.insert(index.object_index, object);
     }
 
+    /// Compute the position of an object, counting across all blocks
+    ///
+    /// All blocks have the same size, so this results in a dense numbering of
+    /// all slots, in the order they were reserved.
+    pub fn dense_index(&self, index: Index) -> usize {
+        index.block_index.0 * self.block_size + index.object_index.0
+    }
+
+    /// The number of slots that have been reserved
+    pub fn num_reserved(&self) -> usize {
+        match self.inner.last() {
+            Some(block) => {
+                (self.inner.len() - 1) * self.block_size + block.len()
+            }
+            None => 0,
+        }
+    }
+
     pub fn get_and_inc(&self, index: &mut Index) -> Option<&Option<T>> {
         let block = self.inner.get(index.block_index.0)?;
         let object = block.get(index.object_index);
@@ -51,6 +51,7 @@ use super::{blocks::Index, store::StoreInner};
 pub struct Handle<T> {
     pub(super) store: StoreInner<T>,
     pub(super) index: Index,
+    pub(super) store_index: StoreIndex,
     pub(super) ptr: *const Option<T>,
 }
 
@@ -60,6 +61,13 @@ impl<T> Handle<T> {
         ObjectId::from_ptr(self.ptr)
     }
 
+    /// Access the object's index within its store
+    ///
+    /// See [`StoreIndex`].
+    pub fn store_index(&self) -> StoreIndex {
+        self.store_index
+    }
+
     /// Return a bare object, which is a clone of the referenced stored object
     pub fn clone_object(&self) -> T
     where
@@ -120,6 +128,7 @@ impl<T> Clone for Handle<T> {
         Self {
             store: self.store.clone(),
             index: self.index,
+            store_index: self.store_index,
             ptr: self.ptr,
         }
     }
@@ -201,6 +210,10 @@ unsafe impl<T> Sync for Handle<T> {}
 ///
 /// You can access a stored object's ID via [`Handle::id`]. Please refer to the
 /// documentation of [`Handle`] for an explanation of object identity.
+///
+/// The ID of an object never changes, and is not shared with any other object,
+/// for as long as the object's store exists. It carries no meaning beyond
+/// that. If you need to number objects consecutively, use [`StoreIndex`].
 #[derive(Clone, Copy, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub struct ObjectId(pub(crate) u64);
 
@@ -217,6 +230,35 @@ impl fmt::Debug for ObjectId {
     }
 }
 
+/// The index of a stored object within its store
+///
+/// You can access a stored object's index via [`Handle::store_index`]. Unlike
+/// [`ObjectId`], which is opaque, store indices are dense: The slots of a store
+/// are numbered consecutively, starting at zero, in the order they were
+/// reserved. [`Store::num_reserved`] returns the number of slots.
+///
+/// This makes store indices suitable for side tables backed by a `Vec`, which
+/// are faster than maps keyed by [`ObjectId`]. But please note that indices are
+/// only unique within a store. Objects of different types, or objects in
+/// different stores, can have the same index.
+///
+/// [`Store::num_reserved`]: super::Store::num_reserved
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct StoreIndex(u32);
+
+impl StoreIndex {
+    pub(super) fn new(index: usize) -> Self {
+        let index = u32::try_from(index)
+            .expect("Number of objects in store exceeds `u32::MAX`");
+        Self(index)
+    }
+
+    /// Convert the index into a `usize`, for indexing into a `Vec`
+    pub fn to_usize(self) -> usize {
+        self.0 as usize
+    }
+}
+
 /// A wrapper around [`Handle`] that defines equality based on identity
 ///
 /// `HandleWrapper` implements [`Eq`]/[`PartialEq`] and other common traits
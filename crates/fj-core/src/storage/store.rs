@@ -27,7 +27,7 @@ use parking_lot::RwLock;
 
 use super::{
     blocks::{Blocks, Index},
-    Handle,
+    Handle, StoreIndex,
 };
 
 /// Append-only object storage
@@ -68,14 +68,25 @@ impl<T> Store<T> {
         let mut inner = self.inner.write();
 
         let (index, ptr) = inner.blocks.reserve();
+        let store_index = StoreIndex::new(inner.blocks.dense_index(index));
 
         Handle {
             store: self.inner.clone(),
             index,
+            store_index,
             ptr,
         }
     }
 
+    /// The number of slots that have been reserved in this store
+    ///
+    /// The [`StoreIndex`] of any object in this store is smaller than this
+    /// number, which makes it the required size for side tables that are
+    /// indexed by it.
+    pub fn num_reserved(&self) -> usize {
+        self.inner.read().blocks.num_reserved()
+    }
+
     /// Insert an object into the store
     ///
     /// # Panics
@@ -138,6 +149,7 @@ impl<'a, T: 'a> Iterator for Iter<'a, T> {
             return Some(Handle {
                 store: self.store.clone(),
                 index,
+                store_index: StoreIndex::new(inner.blocks.dense_index(index)),
                 ptr,
             });
         }
@@ -181,4 +193,31 @@ mod tests {
         let objects = store.iter().collect::<Vec<_>>();
         assert_eq!(objects, [a, b]);
     }
+
+    #[test]
+    fn store_index() {
+        let mut store = Store::with_block_size(2);
+
+        let handles: Vec<Handle<i32>> =
+            (0..5).map(|_| store.reserve()).collect();
+        for (i, handle) in handles.iter().enumerate() {
+            store.insert(handle.clone(), i as i32);
+        }
+
+        // Indices are dense, even across blocks.
+        for (i, handle) in handles.iter().enumerate() {
+            assert_eq!(handle.store_index().to_usize(), i);
+        }
+        assert_eq!(store.num_reserved(), 5);
+
+        let indices = store
+            .iter()
+            .map(|handle| handle.store_index())
+            .collect::<Vec<_>>();
+        let expected = handles
+            .iter()
+            .map(|handle| handle.store_index())
+            .collect::<Vec<_>>();
+        assert_eq!(indices, expected);
+    }
 }
@@ -0,0 +1,189 @@
+//! Trace of the operations used to construct objects
+//!
+//! See [`Trace`].
+
+use std::{
+    collections::BTreeMap,
+    fmt,
+    time::{Duration, Instant},
+};
+
+use crate::objects::{AnyObject, Stored};
+
+/// Trace of the operations used to construct objects
+///
+/// Records which operations were called, with which inputs, what they returned,
+/// how they were nested, which objects each of them inserted, and how long they
+/// took. This can help understanding
+/// how model code maps onto the objects that make up the resulting shape.
+///
+/// Tracing is disabled by default, and needs to be enabled before the
+/// operations of interest are called. The trace can then be printed as a tree,
+/// using its `Display` implementation, or inspected via its fields.
+///
+/// This data is made available through [`Layers`].
+///
+/// [`Layers`]: crate::layers::Layers
+#[derive(Debug, Default)]
+pub struct Trace {
+    /// Whether operations are currently being traced
+    pub enabled: bool,
+
+    /// The completed operations that weren't called by another operation
+    pub operations: Vec<TracedOperation>,
+
+    /// The operations that are currently in progress
+    ///
+    /// The innermost operation comes last.
+    pub in_progress: Vec<TracedOperation>,
+}
+
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for operation in &self.operations {
+            operation.fmt_tree(0, f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// An operation that has been recorded in a [`Trace`]
+#[derive(Clone, Debug)]
+pub struct TracedOperation {
+    /// The name of the operation
+    pub name: &'static str,
+
+    /// The `Debug` representations of the inputs the operation was called with
+    ///
+    /// For methods, the first input is the object the method was called on.
+    /// Objects stored in handles are represented by their type and ID, which
+    /// can be matched with the objects inserted by earlier operations.
+    pub inputs: Vec<String>,
+
+    /// The `Debug` representation of the operation's output
+    ///
+    /// This is `None`, if the operation is still in progress.
+    pub output: Option<String>,
+
+    /// The objects the operation inserted
+    ///
+    /// This only includes objects the operation inserted itself, not those
+    /// inserted by the operations it called.
+    pub inserted: Vec<AnyObject<Stored>>,
+
+    /// The operations called by this operation
+    pub children: Vec<TracedOperation>,
+
+    /// The time the operation started
+    ///
+    /// This is `None`, if time measurement is not available on the current
+    /// platform.
+    pub started: Option<Instant>,
+
+    /// The time the operation took to complete
+    ///
+    /// This is `None`, if the operation is still in progress, or if time
+    /// measurement is not available on the current platform.
+    pub duration: Option<Duration>,
+}
+
+impl TracedOperation {
+    fn fmt_tree(&self, depth: usize, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:indent$}{}({})",
+            "",
+            self.name,
+            self.inputs.join(", "),
+            indent = depth * 2,
+        )?;
+
+        if let Some(output) = &self.output {
+            write!(f, " -> {output}")?;
+        }
+
+        if let Some(duration) = self.duration {
+            write!(f, " ({duration:?})")?;
+        }
+
+        let mut inserted = BTreeMap::new();
+        for object in &self.inserted {
+            *inserted.entry(object.name()).or_insert(0) += 1;
+        }
+
+        if !inserted.is_empty() {
+            let inserted = inserted
+                .into_iter()
+                .map(|(name, count)| format!("{count} {name}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            write!(f, ": inserted {inserted}")?;
+        }
+
+        writeln!(f)?;
+
+        for child in &self.children {
+            child.fmt_tree(depth + 1, f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        objects::{Region, Sketch},
+        operations::{
+            build::{BuildRegion, BuildSketch},
+            sweep::SweepSketch,
+            update::UpdateSketch,
+        },
+        Core,
+    };
+
+    #[test]
+    fn trace_operations() {
+        let mut core = Core::new();
+
+        let surface = core.layers.objects.surfaces.xy_plane();
+        let sketch = Sketch::empty().add_regions(
+            [Region::polygon([[0., 0.], [1., 0.], [0., 1.]], &mut core)],
+            &mut core,
+        );
+
+        // Tracing is disabled by default.
+        let _ = sketch.sweep_sketch(surface.clone(), [0., 0., 1.], &mut core);
+        assert!(core.layers.trace.operations.is_empty());
+
+        core.layers.trace.set_enabled(true);
+        let _ = sketch.sweep_sketch(surface, [0., 0., 1.], &mut core);
+
+        let [sweep_sketch] = core.layers.trace.operations.as_slice() else {
+            panic!("Expected exactly one top-level operation");
+        };
+        assert_eq!(sweep_sketch.name, "sweep_sketch");
+        assert_eq!(sweep_sketch.inputs.len(), 4);
+        assert!(sweep_sketch.inputs[0].starts_with("Sketch"));
+        assert!(sweep_sketch.inputs[1].starts_with("Surface @ "));
+        assert!(sweep_sketch
+            .output
+            .as_ref()
+            .unwrap()
+            .starts_with("Ok(Solid"));
+        assert!(sweep_sketch.duration.is_some());
+        assert!(core.layers.trace.in_progress.is_empty());
+
+        let [sweep_face] = sweep_sketch.children.as_slice() else {
+            panic!("Expected exactly one nested operation");
+        };
+        assert_eq!(sweep_face.name, "sweep_face");
+
+        let trace = core.layers.trace.to_string();
+        assert!(trace.starts_with("sweep_sketch(Sketch"));
+        assert!(trace.contains("\n  sweep_face"));
+        assert!(trace.contains("\n    sweep_region"));
+    }
+}
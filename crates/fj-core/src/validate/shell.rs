@@ -9,6 +9,7 @@ use crate::{
         AllHalfEdgesWithSurface, BoundingVerticesOfHalfEdge, SiblingOfHalfEdge,
     },
     storage::{Handle, HandleWrapper},
+    validation::{checks::ShellNotConnected, ValidationCheck},
 };
 
 use super::{Validate, ValidationConfig, ValidationError};
@@ -20,8 +21,9 @@ impl Validate for Shell {
         errors: &mut Vec<ValidationError>,
     ) {
         ShellValidationError::check_curve_coordinates(self, config, errors);
-        ShellValidationError::check_half_edge_pairs(self, errors);
+        ShellValidationError::check_half_edge_pairs(self, config, errors);
         ShellValidationError::check_half_edge_coincidence(self, config, errors);
+        errors.extend(ShellNotConnected::check(self, config).map(Into::into));
     }
 }
 
@@ -139,17 +141,17 @@ impl ShellValidationError {
 
                 compare_curve_coords(
                     edge_a,
-                    &surface_a.geometry(),
+                    surface_a.geometry(),
                     edge_b,
-                    &surface_b.geometry(),
+                    surface_b.geometry(),
                     config,
                     &mut mismatches,
                 );
                 compare_curve_coords(
                     edge_b,
-                    &surface_b.geometry(),
+                    surface_b.geometry(),
                     edge_a,
-                    &surface_a.geometry(),
+                    surface_a.geometry(),
                     config,
                     &mut mismatches,
                 );
@@ -164,12 +166,27 @@ impl ShellValidationError {
     }
 
     /// Check that each half-edge is part of a pair
-    fn check_half_edge_pairs(shell: &Shell, errors: &mut Vec<ValidationError>) {
+    ///
+    /// Half-edges that collapse into a single point, like those at the poles of
+    /// a sphere, don't bound anything, and don't need a sibling.
+    fn check_half_edge_pairs(
+        shell: &Shell,
+        config: &ValidationConfig,
+        errors: &mut Vec<ValidationError>,
+    ) {
         let mut unmatched_half_edges = BTreeMap::new();
 
         for face in shell.faces() {
             for cycle in face.region().all_cycles() {
                 for half_edge in cycle.half_edges() {
+                    if is_degenerate(
+                        half_edge,
+                        face.surface().geometry(),
+                        config,
+                    ) {
+                        continue;
+                    }
+
                     let curve = HandleWrapper::from(half_edge.curve().clone());
                     let boundary = half_edge.boundary();
                     let vertices =
@@ -235,9 +252,9 @@ impl ShellValidationError {
                 // `distinct_min_distance`, that's a problem.
                 if distances(
                     half_edge_a.clone(),
-                    &surface_a.geometry(),
+                    surface_a.geometry(),
                     half_edge_b.clone(),
-                    &surface_b.geometry(),
+                    surface_b.geometry(),
                 )
                 .all(|d| d < config.distinct_min_distance)
                 {
@@ -354,6 +371,27 @@ impl fmt::Display for CoincidentHalfEdgeVertices {
     }
 }
 
+/// Determine whether a half-edge collapses into a single point in 3D
+fn is_degenerate(
+    half_edge: &HalfEdge,
+    surface: &SurfaceGeometry,
+    config: &ValidationConfig,
+) -> bool {
+    let [a, d] = half_edge.boundary().inner;
+    let b = a + (d - a) * 1. / 3.;
+    let c = a + (d - a) * 2. / 3.;
+
+    let [a, b, c, d] = [a, b, c, d].map(|point_curve| {
+        surface.point_from_surface_coords(
+            half_edge.path().point_from_path_coords(point_curve),
+        )
+    });
+
+    [b, c, d]
+        .into_iter()
+        .all(|point| a.distance_to(&point) <= config.identical_max_distance)
+}
+
 /// Sample two edges at various (currently 3) points in 3D along them.
 ///
 /// Returns an [`Iterator`] of the distance at each sample.
@@ -100,4 +100,73 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn adjacent_half_edges_connected_with_cubic_bezier() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let valid = Cycle::empty().add_half_edges(
+            [
+                HalfEdge::cubic_bezier(
+                    [[0., 0.], [0.5, -1.], [1.5, -1.], [2., 0.]],
+                    &mut core,
+                ),
+                HalfEdge::line_segment([[2., 0.], [0., 0.]], None, &mut core),
+            ],
+            &mut core,
+        );
+        AdjacentHalfEdgesNotConnected::check_and_return_first_error(&valid)?;
+
+        let invalid = valid.update_half_edge(
+            valid.half_edges().first(),
+            |_, core| {
+                [HalfEdge::cubic_bezier(
+                    [[0., 0.], [0.5, -1.], [1.5, -1.], [2., 1.]],
+                    core,
+                )]
+            },
+            &mut core,
+        );
+        AdjacentHalfEdgesNotConnected::check_and_expect_one_error(&invalid);
+
+        Ok(())
+    }
+
+    #[test]
+    fn adjacent_half_edges_connected_with_arcs_through_points(
+    ) -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let valid = Cycle::empty().add_half_edges(
+            [
+                HalfEdge::arc_through_points(
+                    [[0., 0.], [1., 1.], [2., 0.]],
+                    &mut core,
+                ),
+                HalfEdge::line_segment([[2., 0.], [0., 0.]], None, &mut core),
+            ],
+            &mut core,
+        );
+        AdjacentHalfEdgesNotConnected::check_and_return_first_error(&valid)?;
+
+        let valid = Cycle::arcs_through_points(
+            [[[1., 1.], [2., 0.]], [[1., -1.], [0., 0.]]],
+            &mut core,
+        );
+        AdjacentHalfEdgesNotConnected::check_and_return_first_error(&valid)?;
+
+        let invalid = valid.update_half_edge(
+            valid.half_edges().first(),
+            |_, core| {
+                [HalfEdge::arc_through_points(
+                    [[0., 0.], [1., 1.], [2., 1.]],
+                    core,
+                )]
+            },
+            &mut core,
+        );
+        AdjacentHalfEdgesNotConnected::check_and_expect_one_error(&invalid);
+
+        Ok(())
+    }
 }
@@ -3,5 +3,9 @@
 //! See documentation of [parent module](super) for more information.
 
 mod half_edge_connection;
+mod shell_connection;
 
-pub use self::half_edge_connection::AdjacentHalfEdgesNotConnected;
+pub use self::{
+    half_edge_connection::AdjacentHalfEdgesNotConnected,
+    shell_connection::ShellNotConnected,
+};
@@ -0,0 +1,83 @@
+use crate::{
+    objects::{Face, Shell},
+    queries::ConnectedFaces,
+    storage::Handle,
+    validation::{validation_check::ValidationCheck, ValidationConfig},
+};
+
+/// [`Shell`] consists of multiple parts that are not connected to each other
+///
+/// A [`Shell`] is expected to form a single, connected boundary. Faces are
+/// connected, if they have half-edges that share a curve.
+///
+/// Solids that consist of multiple separate parts are perfectly valid, but
+/// each of those parts must be represented by its own [`Shell`]. A [`Shell`]
+/// that falls apart into multiple pieces is most likely the result of an
+/// operation that didn't connect its faces correctly. Such a [`Shell`] can be
+/// split into its connected parts, using [`SplitShell`].
+///
+/// [`SplitShell`]: crate::operations::split::SplitShell
+#[derive(Clone, Debug, thiserror::Error)]
+#[error(
+    "`Shell` consists of {} parts that are not connected to each other\n\
+    - Faces, grouped by part: {components:#?}",
+    components.len()
+)]
+pub struct ShellNotConnected {
+    /// The faces of the shell, grouped by the connected part they belong to
+    pub components: Vec<Vec<Handle<Face>>>,
+}
+
+impl ValidationCheck<Shell> for ShellNotConnected {
+    fn check(
+        object: &Shell,
+        _: &ValidationConfig,
+    ) -> impl Iterator<Item = Self> {
+        let components = object.connected_components();
+
+        let error = if components.len() > 1 {
+            Some(ShellNotConnected { components })
+        } else {
+            None
+        };
+
+        error.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        objects::Shell, operations::build::BuildShell,
+        validation::ValidationCheck, Core,
+    };
+
+    use super::ShellNotConnected;
+
+    #[test]
+    fn shell_connected() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let valid = Shell::tetrahedron(
+            [[0., 0., 0.], [0., 1., 0.], [1., 0., 0.], [0., 0., 1.]],
+            &mut core,
+        );
+        ShellNotConnected::check_and_return_first_error(&valid.shell)?;
+
+        let other = Shell::tetrahedron(
+            [[2., 0., 0.], [2., 1., 0.], [3., 0., 0.], [2., 0., 1.]],
+            &mut core,
+        );
+        let invalid = Shell::new(
+            valid
+                .shell
+                .faces()
+                .iter()
+                .chain(other.shell.faces())
+                .cloned(),
+        );
+        ShellNotConnected::check_and_expect_one_error(&invalid);
+
+        Ok(())
+    }
+}
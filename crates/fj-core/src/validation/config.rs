@@ -1,6 +1,13 @@
 use fj_math::Scalar;
 
 /// Configuration required for the validation process
+///
+/// Besides validation, the tolerances defined here are also used by other code
+/// that needs to compare geometry, like intersection tests. They can be
+/// adapted to the scale of a model, by passing a custom configuration to
+/// [`Core::with_validation_config`].
+///
+/// [`Core::with_validation_config`]: crate::Core::with_validation_config
 #[derive(Debug, Clone, Copy)]
 pub struct ValidationConfig {
     /// The minimum distance between distinct objects
@@ -16,6 +23,14 @@ pub struct ValidationConfig {
     /// that distance is less than the one defined in this field, can not be
     /// considered identical.
     pub identical_max_distance: Scalar,
+
+    /// The maximum angle between identical directions, in radians
+    ///
+    /// Directions that are considered identical (or parallel, or orthogonal)
+    /// might still have an angle between them, due to inaccuracies of the
+    /// numerical representation. If that angle is less than the one defined in
+    /// this field, they can not be considered identical.
+    pub identical_max_angle: Scalar,
 }
 
 impl Default for ValidationConfig {
@@ -28,6 +43,10 @@ impl Default for ValidationConfig {
             // false positives due to floating-point accuracy issues), we can
             // adjust it.
             identical_max_distance: Scalar::from_f64(5e-14),
+
+            // Chosen to be on the same order as `identical_max_distance`,
+            // relative to a model of unit size.
+            identical_max_angle: Scalar::from_f64(5e-14),
         }
     }
 }
@@ -5,7 +5,7 @@ use crate::validate::{
     SketchValidationError, SolidValidationError,
 };
 
-use super::checks::AdjacentHalfEdgesNotConnected;
+use super::checks::{AdjacentHalfEdgesNotConnected, ShellNotConnected};
 
 /// An error that can occur during a validation
 #[derive(Clone, Debug, thiserror::Error)]
@@ -14,6 +14,10 @@ pub enum ValidationError {
     #[error(transparent)]
     HalfEdgesInCycleNotConnected(#[from] AdjacentHalfEdgesNotConnected),
 
+    /// `Shell` not connected
+    #[error(transparent)]
+    ShellNotConnected(#[from] ShellNotConnected),
+
     /// `Edge` validation error
     #[error("`Edge` validation error")]
     Edge(#[from] EdgeValidationError),
@@ -8,49 +8,280 @@
 //!
 //! [Fornjot]: https://www.fornjot.app/
 
+mod scene;
+
 use std::{
+    collections::BTreeMap,
     fs::File,
     io::{Seek, Write},
-    path::Path,
+    path::{Path, PathBuf},
+    str::FromStr,
 };
 
 use thiserror::Error;
 
-use fj_interop::Mesh;
+use fj_interop::{FaceIndex, Mesh};
 use fj_math::{Point, Triangle};
 
+pub use self::scene::{export_scene_glb, Scene, SceneMesh, SceneNode};
+
 /// Export the provided mesh to the file at the given path.
 ///
 /// This function will create a file if it does not exist, and will truncate it if it does.
 ///
-/// Currently 3MF & STL file types are supported. The case insensitive file extension of
-/// the provided path is used to switch between supported types.
+/// Currently 3MF, STL, OBJ, and binary glTF file types are supported. The file type is
+/// inferred from the path. See [`Format::from_path`].
 pub fn export(mesh: &Mesh<Point<3>>, path: &Path) -> Result<(), Error> {
-    match path.extension() {
-        Some(extension) if extension.to_ascii_uppercase() == "3MF" => {
-            let mut file = File::create(path)?;
-            export_3mf(mesh, &mut file)
-        }
-        Some(extension) if extension.to_ascii_uppercase() == "STL" => {
-            let mut file = File::create(path)?;
-            export_stl(mesh, &mut file)
+    export_with_config(mesh, path, &ExportConfig::default())
+}
+
+/// Export the provided mesh to the file at the given path, using the provided
+/// configuration
+///
+/// See [`export`].
+pub fn export_with_config(
+    mesh: &Mesh<Point<3>>,
+    path: &Path,
+    config: &ExportConfig,
+) -> Result<(), Error> {
+    let format = Format::from_path(path)?;
+    let mut file = File::create(path)?;
+    format.export_with_config(mesh, &mut file, config)
+}
+
+/// Export each part of the provided mesh to its own file
+///
+/// See [`Mesh::parts`]. The files are named after the provided path, with a
+/// label for each part appended to the file stem. That label is the name of
+/// the part, if all of its faces share one, or its number otherwise. For
+/// example, `model.3mf` might become `model-wheel.3mf` and `model-2.3mf`.
+///
+/// Returns the paths of the files that were written.
+pub fn export_parts(
+    mesh: &Mesh<Point<3>>,
+    path: &Path,
+) -> Result<Vec<PathBuf>, Error> {
+    export_parts_with_config(mesh, path, &ExportConfig::default())
+}
+
+/// Export each part of the provided mesh to its own file, using the provided
+/// configuration
+///
+/// See [`export_parts`].
+pub fn export_parts_with_config(
+    mesh: &Mesh<Point<3>>,
+    path: &Path,
+    config: &ExportConfig,
+) -> Result<Vec<PathBuf>, Error> {
+    let format = Format::from_path(path)?;
+    let parts = mesh.parts();
+
+    let labels = parts
+        .iter()
+        .enumerate()
+        .map(|(i, part)| match part.name() {
+            Some(name) => name
+                .chars()
+                .map(|c| {
+                    if c.is_alphanumeric() || c == '-' || c == '_' {
+                        c
+                    } else {
+                        '_'
+                    }
+                })
+                .collect(),
+            None => (i + 1).to_string(),
+        })
+        .collect::<Vec<String>>();
+
+    let mut paths = Vec::new();
+    for (i, (part, label)) in parts.iter().zip(&labels).enumerate() {
+        // Parts can have the same name. Their numbers tell them apart.
+        let label = if labels.iter().filter(|&other| other == label).count() > 1
+        {
+            format!("{label}-{}", i + 1)
+        } else {
+            label.clone()
+        };
+
+        let mut file_name = path.file_stem().unwrap_or_default().to_owned();
+        file_name.push(format!("-{label}."));
+        file_name.push(path.extension().unwrap_or_default());
+        let path = path.with_file_name(file_name);
+
+        let mut file = File::create(&path)?;
+        format.export_with_config(part, &mut file, config)?;
+
+        paths.push(path);
+    }
+
+    Ok(paths)
+}
+
+/// A file format that meshes can be exported to
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Format {
+    /// 3D Manufacturing Format
+    ThreeMf,
+
+    /// STL, in its binary variant
+    Stl,
+
+    /// Wavefront OBJ
+    Obj,
+
+    /// glTF, in its binary variant
+    Glb,
+}
+
+impl Format {
+    /// Infer the format from the case insensitive extension of a path
+    pub fn from_path(path: &Path) -> Result<Self, Error> {
+        match path.extension() {
+            Some(extension) if extension.to_ascii_uppercase() == "3MF" => {
+                Ok(Self::ThreeMf)
+            }
+            Some(extension) if extension.to_ascii_uppercase() == "STL" => {
+                Ok(Self::Stl)
+            }
+            Some(extension) if extension.to_ascii_uppercase() == "OBJ" => {
+                Ok(Self::Obj)
+            }
+            Some(extension) if extension.to_ascii_uppercase() == "GLB" => {
+                Ok(Self::Glb)
+            }
+            Some(extension) => Err(Error::InvalidExtension(
+                extension.to_string_lossy().into_owned(),
+            )),
+            None => Err(Error::NoExtension),
         }
-        Some(extension) if extension.to_ascii_uppercase() == "OBJ" => {
-            let mut file = File::create(path)?;
-            export_obj(mesh, &mut file)
+    }
+
+    /// Export the provided mesh to the provided writer in this format
+    pub fn export(
+        &self,
+        mesh: &Mesh<Point<3>>,
+        write: impl Write + Seek,
+    ) -> Result<(), Error> {
+        self.export_with_config(mesh, write, &ExportConfig::default())
+    }
+
+    /// Export the provided mesh to the provided writer in this format, using
+    /// the provided configuration
+    pub fn export_with_config(
+        &self,
+        mesh: &Mesh<Point<3>>,
+        write: impl Write + Seek,
+        config: &ExportConfig,
+    ) -> Result<(), Error> {
+        match self {
+            Self::ThreeMf => export_3mf(mesh, write),
+            Self::Stl => write_stl(mesh, write, config.stl_color),
+            Self::Obj => export_obj(mesh, write),
+            Self::Glb => export_glb(mesh, write),
         }
-        Some(extension) => Err(Error::InvalidExtension(
-            extension.to_string_lossy().into_owned(),
-        )),
-        None => Err(Error::NoExtension),
     }
 }
 
+/// Configuration for exporting meshes
+///
+/// Options that only apply to some formats are ignored by the others.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ExportConfig {
+    /// Store the colors of triangles in STL files, using this convention
+    ///
+    /// See [`export_stl_with_color`]. Colors are not stored, if this is
+    /// `None`.
+    pub stl_color: Option<StlColorFormat>,
+}
+
 /// Export the provided mesh to the provided writer in the 3MF format.
 pub fn export_3mf(
     mesh: &Mesh<Point<3>>,
     write: impl Write + Seek,
 ) -> Result<(), Error> {
+    threemf::write(write, threemf_mesh(mesh))?;
+    Ok(())
+}
+
+/// Export the provided mesh to the provided writer in the 3MF format, grouping
+/// triangles by face
+///
+/// The triangles of each face are exported as a separate mesh object, named
+/// after the face (or the name the face was given in the model). Those are
+/// combined into a single object using components, so the result is still a
+/// single part.
+///
+/// Please note that the individual faces are not closed meshes, which some
+/// software (like slicers) might complain about. Use [`export_3mf`] for
+/// printing.
+pub fn export_3mf_with_faces(
+    mesh: &Mesh<Point<3>>,
+    write: impl Write + Seek,
+) -> Result<(), Error> {
+    let mut meshes_by_face = BTreeMap::new();
+    for triangle in mesh.triangles() {
+        meshes_by_face
+            .entry(triangle.face)
+            .or_insert_with(Mesh::new)
+            .push_triangle_of_face(
+                triangle.inner,
+                triangle.color,
+                triangle.face,
+            );
+    }
+
+    let mut objects = Vec::new();
+    let mut components = Vec::new();
+
+    for (face, face_mesh) in meshes_by_face {
+        let id = objects.len() + 1;
+
+        objects.push(threemf::model::Object {
+            id,
+            partnumber: None,
+            name: Some(face_name(mesh, face)),
+            pid: None,
+            object: threemf::model::ObjectData::Mesh(threemf_mesh(&face_mesh)),
+        });
+        components.push(threemf::model::Component {
+            objectid: id,
+            transform: None,
+        });
+    }
+
+    let id = objects.len() + 1;
+    objects.push(threemf::model::Object {
+        id,
+        partnumber: None,
+        name: None,
+        pid: None,
+        object: threemf::model::ObjectData::Components {
+            component: components,
+        },
+    });
+
+    let model = threemf::model::Model {
+        resources: threemf::model::Resources {
+            object: objects,
+            basematerials: None,
+        },
+        build: threemf::model::Build {
+            item: vec![threemf::model::Item {
+                objectid: id,
+                transform: None,
+                partnumber: None,
+            }],
+        },
+        ..threemf::model::Model::default()
+    };
+
+    threemf::write(write, model)?;
+
+    Ok(())
+}
+
+fn threemf_mesh(mesh: &Mesh<Point<3>>) -> threemf::Mesh {
     let vertices = mesh
         .vertices()
         .map(|point| threemf::model::Vertex {
@@ -70,32 +301,107 @@ pub fn export_3mf(
         })
         .collect();
 
-    let mesh = threemf::Mesh {
+    threemf::Mesh {
         vertices: threemf::model::Vertices { vertex: vertices },
         triangles: threemf::model::Triangles {
             triangle: triangles,
         },
-    };
-
-    threemf::write(write, mesh)?;
+    }
+}
 
-    Ok(())
+/// The name of the group or object that a face is exported as
+///
+/// Faces that have been named in the model are exported under that name, so
+/// downstream software can select all faces with the same name as a group.
+fn face_name(mesh: &Mesh<Point<3>>, face: Option<FaceIndex>) -> String {
+    match face {
+        Some(face) => match mesh.face_name(face) {
+            Some(name) => name.to_string(),
+            None => format!("face-{face}"),
+        },
+        None => "default".to_string(),
+    }
 }
 
 /// Export the provided mesh to the provided writer in the STL format.
 pub fn export_stl(
+    mesh: &Mesh<Point<3>>,
+    write: impl Write,
+) -> Result<(), Error> {
+    write_stl(mesh, write, None)
+}
+
+/// Export the provided mesh to the provided writer in the STL format, including
+/// colors
+///
+/// The STL format has no standard way of storing colors. This function stores
+/// the color of each triangle in the attribute bytes, using the provided
+/// (non-standard) convention. Only use this, if the software reading the file
+/// supports that convention.
+///
+/// Colors are stored with a precision of 5 bits per channel. Alpha is ignored.
+pub fn export_stl_with_color(
+    mesh: &Mesh<Point<3>>,
+    write: impl Write,
+    format: StlColorFormat,
+) -> Result<(), Error> {
+    write_stl(mesh, write, Some(format))
+}
+
+/// A convention for storing colors in an STL file
+///
+/// See [`export_stl_with_color`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum StlColorFormat {
+    /// The convention used by VisCAM and SolidView
+    ///
+    /// Bit 15 of the attribute bytes is set, to mark the color as valid. Bits
+    /// 0-4 contain blue, bits 5-9 green, and bits 10-14 red.
+    VisCam,
+
+    /// The convention used by Materialise Magics
+    ///
+    /// Bit 15 of the attribute bytes is cleared, to mark the color as specific
+    /// to the triangle. Bits 0-4 contain red, bits 5-9 green, and bits 10-14
+    /// blue. The header contains a default color.
+    Magics,
+}
+
+impl FromStr for StlColorFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "viscam" => Ok(Self::VisCam),
+            "magics" => Ok(Self::Magics),
+            _ => Err(Error::InvalidStlColorFormat(s.to_string())),
+        }
+    }
+}
+
+fn write_stl(
     mesh: &Mesh<Point<3>>,
     mut write: impl Write,
+    color_format: Option<StlColorFormat>,
 ) -> Result<(), Error> {
     let points = mesh
         .triangles()
         .map(|triangle| triangle.inner.points())
         .collect::<Vec<_>>();
 
-    let vertices = points.iter().map(|points| {
-        points.map(|point| point.coords.components.map(|s| s.into_f32()))
+    let attributes = mesh.triangles().map(|triangle| {
+        let [r, g, b, _] =
+            triangle.color.0.map(|channel| u16::from(channel >> 3));
+
+        match color_format {
+            Some(StlColorFormat::VisCam) => 1 << 15 | r << 10 | g << 5 | b,
+            Some(StlColorFormat::Magics) => b << 10 | g << 5 | r,
+            None => 0,
+        }
     });
 
+    let vertices = mesh.triangles_f32();
+
     let normals = points
         .iter()
         .map(|&points| points.into())
@@ -104,18 +410,27 @@ pub fn export_stl(
 
     let triangles = vertices
         .zip(normals)
-        .map(|([v1, v2, v3], normal)| stl::Triangle {
+        .zip(attributes)
+        .map(|(([v1, v2, v3], normal), attr_byte_count)| stl::Triangle {
             normal,
             v1,
             v2,
             v3,
-            attr_byte_count: 0,
+            attr_byte_count,
         })
         .collect::<Vec<_>>();
 
+    let mut header = [0u8; 80];
+    if color_format == Some(StlColorFormat::Magics) {
+        // Magics expects a default color in the header. It is only used for
+        // triangles that don't specify their own color, which none do.
+        let default_color = b"COLOR=\x80\x80\x80\xff";
+        header[..default_color.len()].copy_from_slice(default_color);
+    }
+
     let binary_stl_file = stl::BinaryStlFile {
         header: stl::BinaryStlHeader {
-            header: [0u8; 80],
+            header,
             num_triangles: triangles
                 .len()
                 .try_into()
@@ -130,11 +445,30 @@ pub fn export_stl(
 }
 
 /// Export the provided mesh to the provided writer in the OBJ format.
+///
+/// If the mesh has information about the faces its triangles were created
+/// from, the triangles of each face are exported as a separate group. Faces
+/// that have been given the same name end up in the same group.
 pub fn export_obj(
     mesh: &Mesh<Point<3>>,
     mut write: impl Write,
 ) -> Result<(), Error> {
+    let mut current_face = None;
+
     for (cnt, t) in mesh.triangles().enumerate() {
+        if t.face != current_face {
+            wavefront_rs::obj::writer::Writer { auto_newline: true }
+                .write(
+                    &mut write,
+                    &wavefront_rs::obj::entity::Entity::Group {
+                        name: face_name(mesh, t.face),
+                    },
+                )
+                .or(Err(Error::OBJ))?;
+
+            current_face = t.face;
+        }
+
         // write each point of the triangle
         for v in t.inner.points() {
             wavefront_rs::obj::writer::Writer { auto_newline: true }
@@ -180,6 +514,100 @@ pub fn export_obj(
     Ok(())
 }
 
+/// Export the provided mesh to the provided writer as binary glTF
+///
+/// The mesh is exported as a scene with a single node. Use
+/// [`export_scene_glb`] to export a scene with multiple nodes.
+pub fn export_glb(
+    mesh: &Mesh<Point<3>>,
+    write: impl Write,
+) -> Result<(), Error> {
+    let mut scene = Scene::new();
+    let mesh = scene.add_mesh(mesh.clone());
+    scene.add_node(SceneNode::from_mesh(mesh));
+
+    export_scene_glb(&scene, write)
+}
+
+/// Convert the provided mesh to the provided axis convention
+///
+/// Fornjot uses a right-handed coordinate system, in which the z-axis points
+/// up. This is common for CAD applications, but many graphics applications
+/// and file formats (like OBJ) expect the y-axis to point up instead. Without
+/// conversion, models would end up lying on their side there.
+pub fn convert_axes(
+    mesh: &Mesh<Point<3>>,
+    convention: AxisConvention,
+) -> Mesh<Point<3>> {
+    let mut converted = Mesh::new();
+
+    for triangle in mesh.triangles() {
+        let mut points = triangle.inner.points().map(|point| {
+            let [x, y, z] = point.coords.components;
+
+            match convention {
+                AxisConvention::ZUp => Point::from([x, y, z]),
+                AxisConvention::YUp => Point::from([x, z, -y]),
+                AxisConvention::YUpLeftHanded => Point::from([x, z, y]),
+            }
+        });
+
+        if convention.is_left_handed() {
+            // Mirroring the coordinate system reverses the winding of all
+            // triangles. Restore it, so they keep facing outwards.
+            points.swap(1, 2);
+        }
+
+        converted.push_triangle_of_face(points, triangle.color, triangle.face);
+    }
+    for (face, name) in mesh.face_names() {
+        converted.set_face_name(face, name);
+    }
+
+    converted
+}
+
+/// An axis convention that a mesh can be converted to
+///
+/// See [`convert_axes`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum AxisConvention {
+    /// Right-handed, z-axis pointing up
+    ///
+    /// This is the convention Fornjot uses, so no conversion happens.
+    #[default]
+    ZUp,
+
+    /// Right-handed, y-axis pointing up
+    ///
+    /// Used by OBJ, glTF, and many graphics applications.
+    YUp,
+
+    /// Left-handed, y-axis pointing up
+    ///
+    /// Used by some game engines.
+    YUpLeftHanded,
+}
+
+impl AxisConvention {
+    fn is_left_handed(&self) -> bool {
+        matches!(self, Self::YUpLeftHanded)
+    }
+}
+
+impl FromStr for AxisConvention {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "z-up" => Ok(Self::ZUp),
+            "y-up" => Ok(Self::YUp),
+            "y-up-left-handed" => Ok(Self::YUpLeftHanded),
+            _ => Err(Error::InvalidAxisConvention(s.to_string())),
+        }
+    }
+}
+
 /// An error that can occur while exporting
 #[derive(Debug, Error)]
 pub enum Error {
@@ -206,4 +634,380 @@ pub enum Error {
     /// OBJ exporter error whilst exporting to OBJ file
     #[error("obj error whilst exporting to OBJ file")]
     OBJ,
+
+    /// glTF error whilst exporting to glTF file
+    #[error("gltf error whilst exporting to glTF file")]
+    Gltf,
+
+    /// Unrecognized axis convention
+    #[error(
+        "unrecognized axis convention `{0}` (expected `z-up`, `y-up`, or \
+        `y-up-left-handed`)"
+    )]
+    InvalidAxisConvention(String),
+
+    /// Unrecognized convention for storing colors in STL files
+    #[error(
+        "unrecognized STL color format `{0}` (expected `viscam` or `magics`)"
+    )]
+    InvalidStlColorFormat(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use std::collections::BTreeSet;
+
+    use fj_interop::{Color, Mesh};
+    use fj_math::{Point, Scalar};
+
+    use crate::{
+        convert_axes, export_3mf_with_faces, export_obj, export_parts,
+        export_stl, export_stl_with_color, AxisConvention, ExportConfig,
+        Format, StlColorFormat,
+    };
+
+    #[test]
+    fn stl() -> anyhow::Result<()> {
+        let mesh = two_triangles();
+
+        for stl_color in [
+            None,
+            Some(StlColorFormat::VisCam),
+            Some(StlColorFormat::Magics),
+        ] {
+            let mut buffer = Cursor::new(Vec::new());
+            Format::Stl.export_with_config(
+                &mesh,
+                &mut buffer,
+                &ExportConfig { stl_color },
+            )?;
+            buffer.set_position(0);
+            let stl = stl::read_stl(&mut buffer)?;
+
+            assert_eq!(stl.header.num_triangles, 2);
+            assert_eq!(stl.triangles.len(), 2);
+
+            let [a, b] = [&stl.triangles[0], &stl.triangles[1]];
+            assert_eq!(
+                [a.v1, a.v2, a.v3],
+                [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]]
+            );
+            assert_eq!(a.normal, [0., 0., 1.]);
+            assert_eq!(
+                [b.v1, b.v2, b.v3],
+                [[0., 0., 0.], [0., 0., 1.], [1., 0., 0.]]
+            );
+            assert_eq!(b.normal, [0., 1., 0.]);
+
+            // Only Magics expects a default color in the header.
+            let header = stl.header.header;
+            if stl_color == Some(StlColorFormat::Magics) {
+                assert_eq!(&header[..10], b"COLOR=\x80\x80\x80\xff");
+                assert!(header[10..].iter().all(|&byte| byte == 0));
+            } else {
+                assert!(header.iter().all(|&byte| byte == 0));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn stl_color() -> anyhow::Result<()> {
+        let mesh = two_triangles();
+
+        // Each channel is stored with 5 bits. 255 becomes 31, 128 becomes 16,
+        // and 64 becomes 8.
+        let expected = [[31, 16, 0], [0, 8, 31]];
+
+        for format in [StlColorFormat::VisCam, StlColorFormat::Magics] {
+            let mut buffer = Vec::new();
+            export_stl_with_color(&mesh, &mut buffer, format)?;
+            let stl = stl::read_stl(&mut Cursor::new(buffer))?;
+
+            for (triangle, expected) in stl.triangles.iter().zip(expected) {
+                let attributes = triangle.attr_byte_count;
+                let [low, middle, high] =
+                    [0, 5, 10].map(|shift| (attributes >> shift) & 0b11111);
+                let is_valid = attributes >> 15 == 1;
+
+                let rgb = match format {
+                    StlColorFormat::VisCam => {
+                        assert!(is_valid);
+                        [high, middle, low]
+                    }
+                    StlColorFormat::Magics => {
+                        assert!(!is_valid);
+                        [low, middle, high]
+                    }
+                };
+                assert_eq!(rgb, expected);
+            }
+        }
+
+        let mut buffer = Vec::new();
+        export_stl(&mesh, &mut buffer)?;
+
+        let stl = stl::read_stl(&mut Cursor::new(buffer))?;
+        for triangle in stl.triangles {
+            assert_eq!(triangle.attr_byte_count, 0);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn axis_conventions() -> anyhow::Result<()> {
+        // A tetrahedron, with a named face at the bottom.
+        let [a, b, c, d] =
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]];
+        let color = Color([255, 0, 0, 255]);
+
+        let mut mesh = Mesh::new();
+        let bottom = mesh.add_face();
+        mesh.set_face_name(bottom, "bottom");
+        mesh.push_triangle_of_face([a, c, b], color, Some(bottom));
+        for triangle in [[a, b, d], [b, c, d], [c, a, d]] {
+            mesh.push_triangle(triangle, color);
+        }
+
+        type Convert = fn([Scalar; 3]) -> [Scalar; 3];
+        let conventions: [(AxisConvention, Convert, [f64; 3]); 3] = [
+            (AxisConvention::ZUp, |[x, y, z]| [x, y, z], [0., 0., -1.]),
+            (AxisConvention::YUp, |[x, y, z]| [x, z, -y], [0., -1., 0.]),
+            (
+                AxisConvention::YUpLeftHanded,
+                |[x, y, z]| [x, z, y],
+                [0., -1., 0.],
+            ),
+        ];
+
+        for (convention, convert, down) in conventions {
+            let converted = convert_axes(&mesh, convention);
+
+            let expected = mesh
+                .vertices()
+                .map(|point| Point::from(convert(point.coords.components)))
+                .collect::<BTreeSet<_>>();
+            assert_eq!(converted.vertices().collect::<BTreeSet<_>>(), expected);
+
+            // The triangles still face outwards, even if the coordinate system
+            // has been mirrored.
+            assert_eq!(converted.volume(), mesh.volume());
+
+            let [first, ..] = converted.triangles().collect::<Vec<_>>()[..]
+            else {
+                panic!("Converted mesh has triangles");
+            };
+            assert_eq!(first.inner.normal(), down.into());
+            assert_eq!(first.face, Some(bottom));
+            assert_eq!(first.color, color);
+            assert_eq!(converted.face_name(bottom), Some("bottom"));
+
+            // The converted coordinates are what ends up in the file.
+            let mut buffer = Vec::new();
+            export_stl(&converted, &mut buffer)?;
+            let stl = stl::read_stl(&mut Cursor::new(buffer))?;
+
+            let triangle = &stl.triangles[0];
+            assert_eq!(
+                [triangle.v1, triangle.v2, triangle.v3],
+                first
+                    .inner
+                    .points()
+                    .map(|point| point.coords.components.map(|s| s.into_f32())),
+            );
+            assert_eq!(triangle.normal, down.map(|s| s as f32));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn axis_convention_from_str() {
+        for (s, convention) in [
+            ("z-up", AxisConvention::ZUp),
+            ("y-up", AxisConvention::YUp),
+            ("y-up-left-handed", AxisConvention::YUpLeftHanded),
+        ] {
+            assert_eq!(s.parse::<AxisConvention>().ok(), Some(convention));
+        }
+        assert!("x-up".parse::<AxisConvention>().is_err());
+    }
+
+    #[test]
+    fn obj_groups() -> anyhow::Result<()> {
+        let mesh = named_faces();
+
+        let mut buffer = Vec::new();
+        export_obj(&mesh, &mut buffer)?;
+        let obj = String::from_utf8(buffer)?;
+
+        let mut groups = Vec::new();
+        let mut vertices = Vec::new();
+        let mut faces = Vec::new();
+        for line in obj.lines() {
+            let mut tokens = line.split_whitespace();
+            match tokens.next() {
+                Some("g") => groups.push(tokens.collect::<Vec<_>>().join(" ")),
+                Some("v") => {
+                    vertices.push(
+                        tokens
+                            .map(|token| token.parse::<f64>())
+                            .collect::<Result<Vec<_>, _>>()?,
+                    )
+                }
+                Some("f") => faces.push(
+                    tokens
+                        .map(|token| token.parse::<usize>())
+                        .collect::<Result<Vec<_>, _>>()?,
+                ),
+                _ => {}
+            }
+        }
+
+        // Faces with the same name end up in groups of the same name.
+        assert_eq!(groups, ["lid", "face-1", "lid", "default"]);
+        assert_eq!(faces, [[1, 2, 3], [4, 5, 6], [7, 8, 9], [10, 11, 12]]);
+
+        let expected = mesh
+            .triangles()
+            .flat_map(|triangle| triangle.inner.points())
+            .map(|point| point.coords.components.map(|s| s.into_f64()).to_vec())
+            .collect::<Vec<_>>();
+        assert_eq!(vertices, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn threemf_with_faces() -> anyhow::Result<()> {
+        let mesh = named_faces();
+
+        let mut buffer = Cursor::new(Vec::new());
+        export_3mf_with_faces(&mesh, &mut buffer)?;
+        buffer.set_position(0);
+        let models = threemf::read(buffer)?;
+
+        let [model] = &models[..] else {
+            panic!("Expected a single model");
+        };
+        let objects = &model.resources.object;
+
+        // The triangles are grouped by face. Faces with the same name still
+        // become separate objects, as they are not connected.
+        let [default, lid_a, face_1, lid_b, combined] = &objects[..] else {
+            panic!("Expected an object per face, and a combined one");
+        };
+        for (object, name, num_triangles) in [
+            (default, "default", 1),
+            (lid_a, "lid", 1),
+            (face_1, "face-1", 1),
+            (lid_b, "lid", 1),
+        ] {
+            assert_eq!(object.name.as_deref(), Some(name));
+            let threemf::model::ObjectData::Mesh(mesh) = &object.object else {
+                panic!("Expected a mesh for each face");
+            };
+            assert_eq!(mesh.triangles.triangle.len(), num_triangles);
+            assert_eq!(mesh.vertices.vertex.len(), 3);
+        }
+
+        let threemf::model::ObjectData::Components { component } =
+            &combined.object
+        else {
+            panic!("Expected components for the combined object");
+        };
+        let ids = component
+            .iter()
+            .map(|component| component.objectid)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, [default.id, lid_a.id, face_1.id, lid_b.id]);
+
+        let [item] = &model.build.item[..] else {
+            panic!("Expected a single build item");
+        };
+        assert_eq!(item.objectid, combined.id);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parts() -> anyhow::Result<()> {
+        // Two tetrahedra that don't touch, one of which is named.
+        let mut mesh = Mesh::new();
+        for (offset, name) in [(0., Some("front wheel")), (2., None)] {
+            let face = mesh.add_face();
+            if let Some(name) = name {
+                mesh.set_face_name(face, name);
+            }
+
+            let [a, b, c, d] =
+                [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]]
+                    .map(|[x, y, z]| Point::from([x + offset, y, z]));
+            for triangle in [[a, c, b], [a, b, d], [b, c, d], [c, a, d]] {
+                mesh.push_triangle_of_face(
+                    triangle,
+                    Color::default(),
+                    Some(face),
+                );
+            }
+        }
+
+        let dir = tempfile::tempdir()?;
+        let paths = export_parts(&mesh, &dir.path().join("model.stl"))?;
+
+        let names = paths
+            .iter()
+            .map(|path| path.file_name().and_then(|name| name.to_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(names, [Some("model-front_wheel.stl"), Some("model-2.stl")]);
+
+        for (path, offset) in paths.iter().zip([0., 2.]) {
+            let stl = stl::read_stl(&mut std::fs::File::open(path)?)?;
+            assert_eq!(stl.triangles.len(), 4);
+            assert!(stl
+                .triangles
+                .iter()
+                .flat_map(|triangle| [triangle.v1, triangle.v2, triangle.v3])
+                .all(|[x, ..]| x == offset || x == offset + 1.));
+        }
+
+        Ok(())
+    }
+
+    /// A triangle for each of a few faces, some of which share a name
+    fn named_faces() -> Mesh<Point<3>> {
+        let mut mesh = Mesh::new();
+
+        let faces = [(); 3].map(|()| mesh.add_face());
+        mesh.set_face_name(faces[0], "lid");
+        mesh.set_face_name(faces[2], "lid");
+
+        for (i, face) in faces.into_iter().map(Some).chain([None]).enumerate() {
+            let z = i as f64;
+            mesh.push_triangle_of_face(
+                [[0., 0., z], [1., 0., z], [0., 1., z]],
+                Color::default(),
+                face,
+            );
+        }
+
+        mesh
+    }
+
+    fn two_triangles() -> Mesh<Point<3>> {
+        let mut mesh = Mesh::new();
+        mesh.push_triangle(
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]],
+            Color([255, 128, 0, 255]),
+        );
+        mesh.push_triangle(
+            [[0., 0., 0.], [0., 0., 1.], [1., 0., 0.]],
+            Color([0, 64, 255, 255]),
+        );
+        mesh
+    }
 }
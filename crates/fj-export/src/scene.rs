@@ -0,0 +1,532 @@
+//! Export of scenes, made up of multiple meshes
+//!
+//! See [`Scene`].
+
+use std::{collections::BTreeMap, io::Write};
+
+use serde_json::{json, Value};
+
+use fj_interop::Mesh;
+use fj_math::{Point, Transform};
+
+use crate::Error;
+
+/// A scene, made up of a hierarchy of nodes that refer to meshes
+///
+/// Exporting a model as a single mesh loses its structure. A scene keeps it:
+/// Each node has a name and a transform, and can refer to a mesh and have
+/// child nodes. Nodes that refer to the same mesh are instances of it, and the
+/// mesh is only stored once. This is the right choice for assemblies, where the
+/// same part is used multiple times.
+///
+/// Transforms of child nodes are relative to their parent node.
+///
+/// Use [`export_scene_glb`] to export a scene.
+#[derive(Clone, Debug, Default)]
+pub struct Scene {
+    meshes: Vec<Mesh<Point<3>>>,
+    nodes: Vec<SceneNode>,
+}
+
+impl Scene {
+    /// Construct an empty scene
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a mesh to the scene
+    ///
+    /// Returns a reference to the mesh, which can be passed to any number of
+    /// nodes.
+    pub fn add_mesh(&mut self, mesh: Mesh<Point<3>>) -> SceneMesh {
+        let index = self.meshes.len();
+        self.meshes.push(mesh);
+        SceneMesh(index)
+    }
+
+    /// Add a node to the root of the scene
+    pub fn add_node(&mut self, node: SceneNode) {
+        self.nodes.push(node);
+    }
+
+    /// Access the meshes of the scene
+    pub fn meshes(&self) -> &[Mesh<Point<3>>] {
+        &self.meshes
+    }
+
+    /// Access the nodes at the root of the scene
+    pub fn nodes(&self) -> &[SceneNode] {
+        &self.nodes
+    }
+}
+
+/// A reference to a mesh in a [`Scene`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct SceneMesh(usize);
+
+/// A node in a [`Scene`]
+#[derive(Clone, Debug)]
+pub struct SceneNode {
+    /// The name of the node
+    pub name: Option<String>,
+
+    /// The transform of the node, relative to its parent
+    pub transform: Transform,
+
+    /// The mesh that the node refers to, if any
+    pub mesh: Option<SceneMesh>,
+
+    /// The children of the node
+    pub children: Vec<SceneNode>,
+}
+
+impl SceneNode {
+    /// Construct a node without name, mesh, or children
+    pub fn new() -> Self {
+        Self {
+            name: None,
+            transform: Transform::identity(),
+            mesh: None,
+            children: Vec::new(),
+        }
+    }
+
+    /// Construct a node that refers to the provided mesh
+    pub fn from_mesh(mesh: SceneMesh) -> Self {
+        Self {
+            mesh: Some(mesh),
+            ..Self::new()
+        }
+    }
+
+    /// Name the node
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set the transform of the node
+    pub fn with_transform(mut self, transform: Transform) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    /// Add a child to the node
+    pub fn with_child(mut self, child: SceneNode) -> Self {
+        self.children.push(child);
+        self
+    }
+}
+
+impl Default for SceneNode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Export the provided scene to the provided writer as binary glTF
+///
+/// The triangles of each mesh are grouped by color, and each group becomes a
+/// primitive with a matching material. Normals are not exported, so the
+/// triangles are shaded flat.
+///
+/// glTF expects the y-axis to point up. Fornjot's z-axis is not converted
+/// automatically. If required, give the root nodes of the scene a transform
+/// that does the conversion.
+pub fn export_scene_glb(scene: &Scene, write: impl Write) -> Result<(), Error> {
+    let mut buffer = Vec::new();
+    let mut buffer_views = Vec::new();
+    let mut accessors = Vec::new();
+    let mut materials = Vec::new();
+    let mut material_by_color = BTreeMap::new();
+
+    let meshes = scene
+        .meshes
+        .iter()
+        .map(|mesh| {
+            let mut triangles_by_color = BTreeMap::new();
+            for triangle in mesh.triangles() {
+                triangles_by_color
+                    .entry(triangle.color.0)
+                    .or_insert_with(Vec::new)
+                    .push(triangle.inner);
+            }
+
+            let primitives = triangles_by_color
+                .into_iter()
+                .map(|(color, triangles)| {
+                    let material =
+                        *material_by_color.entry(color).or_insert_with(|| {
+                            materials.push(material(color));
+                            materials.len() - 1
+                        });
+
+                    let points = triangles
+                        .iter()
+                        .flat_map(|triangle| triangle.points())
+                        .map(|point| {
+                            point.coords.components.map(|s| s.into_f32())
+                        })
+                        .collect::<Vec<_>>();
+
+                    let mut min = [f32::INFINITY; 3];
+                    let mut max = [f32::NEG_INFINITY; 3];
+                    for point in &points {
+                        for i in 0..3 {
+                            min[i] = min[i].min(point[i]);
+                            max[i] = max[i].max(point[i]);
+                        }
+                    }
+
+                    let offset = buffer.len();
+                    for component in points.iter().flatten() {
+                        buffer.extend_from_slice(&component.to_le_bytes());
+                    }
+
+                    buffer_views.push(json!({
+                        "buffer": 0,
+                        "byteOffset": offset,
+                        "byteLength": buffer.len() - offset,
+                        "target": ARRAY_BUFFER,
+                    }));
+                    accessors.push(json!({
+                        "bufferView": buffer_views.len() - 1,
+                        "componentType": FLOAT,
+                        "count": points.len(),
+                        "type": "VEC3",
+                        "min": min,
+                        "max": max,
+                    }));
+
+                    json!({
+                        "attributes": { "POSITION": accessors.len() - 1 },
+                        "material": material,
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            json!({ "primitives": primitives })
+        })
+        .collect::<Vec<_>>();
+
+    let mut nodes = Vec::new();
+    let roots = scene
+        .nodes
+        .iter()
+        .map(|node| push_node(node, &mut nodes))
+        .collect::<Vec<_>>();
+
+    let mut document = json!({
+        "asset": {
+            "version": "2.0",
+            "generator": "Fornjot",
+        },
+        "scene": 0,
+        "scenes": [{ "nodes": roots }],
+        "nodes": nodes,
+        "meshes": meshes,
+        "materials": materials,
+        "accessors": accessors,
+        "bufferViews": buffer_views,
+    });
+    if !buffer.is_empty() {
+        document["buffers"] = json!([{ "byteLength": buffer.len() }]);
+    }
+    if let Value::Object(properties) = &mut document {
+        // glTF doesn't allow empty arrays.
+        properties.retain(|_, value| {
+            !matches!(value, Value::Array(values) if values.is_empty())
+        });
+    }
+
+    write_glb(&document, &buffer, write)
+}
+
+/// Add a node and its children to the flat list of nodes that glTF expects
+///
+/// Returns the index of the node.
+fn push_node(node: &SceneNode, nodes: &mut Vec<Value>) -> usize {
+    let index = nodes.len();
+    nodes.push(json!({}));
+
+    let children = node
+        .children
+        .iter()
+        .map(|child| push_node(child, nodes))
+        .collect::<Vec<_>>();
+
+    let mut value = json!({});
+    if let Some(name) = &node.name {
+        value["name"] = json!(name);
+    }
+    if node.transform.data() != Transform::identity().data() {
+        // Both glTF and `Transform` store matrices in column-major order.
+        value["matrix"] = json!(node.transform.data());
+    }
+    if let Some(SceneMesh(mesh)) = node.mesh {
+        value["mesh"] = json!(mesh);
+    }
+    if !children.is_empty() {
+        value["children"] = json!(children);
+    }
+
+    nodes[index] = value;
+    index
+}
+
+fn material(color: [u8; 4]) -> Value {
+    let [r, g, b, a] = color.map(|channel| f64::from(channel) / 255.);
+
+    let mut material = json!({
+        "pbrMetallicRoughness": {
+            "baseColorFactor": [r, g, b, a],
+            "metallicFactor": 0.,
+            "roughnessFactor": 1.,
+        },
+    });
+    if color[3] < 255 {
+        material["alphaMode"] = json!("BLEND");
+    }
+
+    material
+}
+
+fn write_glb(
+    document: &Value,
+    buffer: &[u8],
+    mut write: impl Write,
+) -> Result<(), Error> {
+    // Chunks must be aligned to 4 bytes. The JSON chunk is padded with spaces,
+    // the binary chunk with zeros.
+    let mut json = serde_json::to_vec(document).map_err(|_| Error::Gltf)?;
+    while json.len() % 4 != 0 {
+        json.push(b' ');
+    }
+    let mut bin = buffer.to_vec();
+    while bin.len() % 4 != 0 {
+        bin.push(0);
+    }
+
+    let mut chunks = vec![(CHUNK_JSON, json)];
+    if !bin.is_empty() {
+        chunks.push((CHUNK_BIN, bin));
+    }
+
+    let length =
+        12 + chunks.iter().map(|(_, data)| 8 + data.len()).sum::<usize>();
+    let length = u32::try_from(length).map_err(|_| Error::Gltf)?;
+
+    write.write_all(&GLB_MAGIC.to_le_bytes())?;
+    write.write_all(&GLB_VERSION.to_le_bytes())?;
+    write.write_all(&length.to_le_bytes())?;
+
+    for (kind, data) in chunks {
+        let chunk_length =
+            u32::try_from(data.len()).map_err(|_| Error::Gltf)?;
+
+        write.write_all(&chunk_length.to_le_bytes())?;
+        write.write_all(&kind.to_le_bytes())?;
+        write.write_all(&data)?;
+    }
+
+    Ok(())
+}
+
+const GLB_MAGIC: u32 = 0x4654_6C67;
+const GLB_VERSION: u32 = 2;
+const CHUNK_JSON: u32 = 0x4E4F_534A;
+const CHUNK_BIN: u32 = 0x004E_4942;
+
+const ARRAY_BUFFER: u32 = 34962;
+const FLOAT: u32 = 5126;
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::{Color, Mesh};
+    use fj_math::{Point, Transform};
+    use serde_json::{json, Value};
+
+    use super::{
+        export_scene_glb, Scene, SceneNode, ARRAY_BUFFER, CHUNK_BIN,
+        CHUNK_JSON, FLOAT, GLB_MAGIC, GLB_VERSION,
+    };
+
+    #[test]
+    fn glb_layout() -> anyhow::Result<()> {
+        let [red, blue] = [Color([255, 0, 0, 255]), Color([0, 0, 255, 128])];
+
+        let mut scene = Scene::new();
+        let wheel = scene.add_mesh(mesh([(red, 0.), (blue, 1.), (red, 2.)]));
+        let axle = scene.add_mesh(mesh([(blue, 0.)]));
+        scene.add_node(
+            SceneNode::new()
+                .with_name("car")
+                .with_child(SceneNode::from_mesh(wheel).with_name("left"))
+                .with_child(
+                    SceneNode::from_mesh(wheel)
+                        .with_name("right")
+                        .with_transform(Transform::translation([0., 2., 0.])),
+                ),
+        );
+        scene.add_node(SceneNode::from_mesh(axle));
+
+        let (document, bin) = read_glb(&scene)?;
+
+        // Nodes are flattened, children after their parents.
+        assert_eq!(document["scenes"], json!([{ "nodes": [0, 3] }]));
+        assert_eq!(document["scene"], json!(0));
+        let nodes = document["nodes"].as_array().unwrap();
+        assert_eq!(nodes.len(), 4);
+        assert_eq!(nodes[0], json!({ "name": "car", "children": [1, 2] }));
+        assert_eq!(nodes[1], json!({ "name": "left", "mesh": 0 }));
+        assert_eq!(nodes[2]["name"], json!("right"));
+        assert_eq!(nodes[2]["mesh"], json!(0));
+        assert_eq!(nodes[3], json!({ "mesh": 1 }));
+
+        // Both wheels share the mesh. The transform is a column-major matrix,
+        // with the translation in the last column.
+        let matrix = nodes[2]["matrix"].as_array().unwrap();
+        assert_eq!(matrix.len(), 16);
+        assert_eq!(matrix[12..15], [json!(0.), json!(2.), json!(0.)]);
+
+        // Each color gets a single material, shared between meshes. Colors are
+        // sorted, so blue comes first.
+        let materials = document["materials"].as_array().unwrap();
+        assert_eq!(materials.len(), 2);
+        let base_color = |material: &Value| {
+            material["pbrMetallicRoughness"]["baseColorFactor"].clone()
+        };
+        assert_eq!(base_color(&materials[0]), json!([0., 0., 1., 128. / 255.]));
+        assert_eq!(materials[0]["alphaMode"], json!("BLEND"));
+        assert_eq!(base_color(&materials[1]), json!([1., 0., 0., 1.]));
+        assert_eq!(materials[1].get("alphaMode"), None);
+
+        // The triangles of each mesh are grouped into a primitive per color.
+        let meshes = document["meshes"].as_array().unwrap();
+        let primitives = meshes
+            .iter()
+            .map(|mesh| {
+                mesh["primitives"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|primitive| {
+                        let accessor =
+                            primitive["attributes"]["POSITION"].as_u64();
+                        (accessor.unwrap(), primitive["material"].as_u64())
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            primitives,
+            [vec![(0, Some(0)), (1, Some(1))], vec![(2, Some(0))]]
+        );
+
+        let accessors = document["accessors"].as_array().unwrap();
+        let buffer_views = document["bufferViews"].as_array().unwrap();
+        assert_eq!(document["buffers"], json!([{ "byteLength": bin.len() }]));
+
+        for (accessor, num_triangles, zs) in [
+            (&accessors[0], 1, vec![1.]),
+            (&accessors[1], 2, vec![0., 2.]),
+            (&accessors[2], 1, vec![0.]),
+        ] {
+            assert_eq!(accessor["componentType"], json!(FLOAT));
+            assert_eq!(accessor["type"], json!("VEC3"));
+            assert_eq!(accessor["count"], json!(num_triangles * 3));
+
+            let view = &buffer_views
+                [accessor["bufferView"].as_u64().unwrap() as usize];
+            assert_eq!(view["buffer"], json!(0));
+            assert_eq!(view["target"], json!(ARRAY_BUFFER));
+
+            let offset = view["byteOffset"].as_u64().unwrap() as usize;
+            let length = view["byteLength"].as_u64().unwrap() as usize;
+            assert_eq!(length, num_triangles * 3 * 3 * 4);
+
+            let positions = bin[offset..offset + length]
+                .chunks(4)
+                .map(|bytes| f32::from_le_bytes(bytes.try_into().unwrap()))
+                .collect::<Vec<_>>();
+            let expected = zs
+                .iter()
+                .flat_map(|&z| triangle(z))
+                .flatten()
+                .map(|s| s as f32)
+                .collect::<Vec<_>>();
+            assert_eq!(positions, expected);
+
+            let [min_z, max_z] = [zs[0], zs[zs.len() - 1]];
+            assert_eq!(accessor["min"], json!([0., 0., min_z]));
+            assert_eq!(accessor["max"], json!([1., 1., max_z]));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn glb_empty() -> anyhow::Result<()> {
+        let mut scene = Scene::new();
+        scene.add_node(SceneNode::new().with_name("empty"));
+
+        let (document, bin) = read_glb(&scene)?;
+
+        // glTF doesn't allow empty arrays, and there's no binary data.
+        assert!(bin.is_empty());
+        assert_eq!(document.get("buffers"), None);
+        assert_eq!(document.get("meshes"), None);
+        assert_eq!(document.get("materials"), None);
+        assert_eq!(document["nodes"], json!([{ "name": "empty" }]));
+
+        Ok(())
+    }
+
+    /// Export the scene, check the container format, and return its contents
+    fn read_glb(scene: &Scene) -> anyhow::Result<(Value, Vec<u8>)> {
+        let mut glb = Vec::new();
+        export_scene_glb(scene, &mut glb)?;
+
+        let u32_at = |offset: usize| {
+            u32::from_le_bytes(glb[offset..offset + 4].try_into().unwrap())
+        };
+
+        assert_eq!(u32_at(0), GLB_MAGIC);
+        assert_eq!(u32_at(4), GLB_VERSION);
+        assert_eq!(u32_at(8) as usize, glb.len());
+
+        let mut chunks = Vec::new();
+        let mut offset = 12;
+        while offset < glb.len() {
+            let length = u32_at(offset) as usize;
+            let kind = u32_at(offset + 4);
+            assert_eq!(length % 4, 0, "Chunks must be aligned to 4 bytes");
+
+            chunks.push((kind, &glb[offset + 8..offset + 8 + length]));
+            offset += 8 + length;
+        }
+        assert_eq!(offset, glb.len());
+
+        let (document, bin) = match chunks[..] {
+            [(CHUNK_JSON, json)] => (json, Vec::new()),
+            [(CHUNK_JSON, json), (CHUNK_BIN, bin)] => (json, bin.to_vec()),
+            _ => panic!("Unexpected chunks"),
+        };
+
+        Ok((serde_json::from_slice(document)?, bin))
+    }
+
+    fn mesh(
+        triangles: impl IntoIterator<Item = (Color, f64)>,
+    ) -> Mesh<Point<3>> {
+        let mut mesh = Mesh::new();
+        for (color, z) in triangles {
+            mesh.push_triangle(triangle(z), color);
+        }
+        mesh
+    }
+
+    fn triangle(z: f64) -> [[f64; 3]; 3] {
+        [[0., 0., z], [1., 0., z], [0., 1., z]]
+    }
+}
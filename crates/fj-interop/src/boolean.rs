@@ -0,0 +1,271 @@
+use fj_math::Point;
+use parry3d_f64::{
+    math::Isometry,
+    query::PointQueryWithLocation,
+    shape::{TriMesh, TriMeshFlags},
+    transformation::intersect_meshes,
+};
+
+use crate::{Color, FaceIndex, Mesh, Triangle};
+
+impl Mesh<Point<3>> {
+    /// Compute the union of this mesh and another
+    ///
+    /// See [`Mesh::boolean`].
+    pub fn union(&self, other: &Self) -> Result<Self, MeshBooleanError> {
+        self.boolean(other, MeshBoolean::Union)
+    }
+
+    /// Subtract another mesh from this one
+    ///
+    /// See [`Mesh::boolean`].
+    pub fn difference(&self, other: &Self) -> Result<Self, MeshBooleanError> {
+        self.boolean(other, MeshBoolean::Difference)
+    }
+
+    /// Compute the intersection of this mesh and another
+    ///
+    /// See [`Mesh::boolean`].
+    pub fn intersection(&self, other: &Self) -> Result<Self, MeshBooleanError> {
+        self.boolean(other, MeshBoolean::Intersection)
+    }
+
+    /// Compute a boolean operation between this mesh and another
+    ///
+    /// This works on the triangle meshes directly, and doesn't require the
+    /// b-rep that the meshes might have been created from. This allows mixing
+    /// generated geometry with meshes from other sources, like imported STL
+    /// files.
+    ///
+    /// Both meshes must be closed, with all triangles facing outward. The
+    /// triangles of the result keep the color and face of the triangle they
+    /// were cut from. The faces of `other` are renumbered, to follow those of
+    /// `self`.
+    ///
+    /// # Limitations
+    ///
+    /// The computation is done with floating-point numbers, and doesn't handle
+    /// degenerate cases, like coplanar triangles, reliably. The result should
+    /// be checked, before relying on it.
+    pub fn boolean(
+        &self,
+        other: &Self,
+        operation: MeshBoolean,
+    ) -> Result<Self, MeshBooleanError> {
+        let a = to_trimesh(self)?;
+        let b = to_trimesh(other)?;
+
+        // An intersection keeps the parts of both meshes that are within the
+        // other one. Flipping a mesh turns its inside out, which makes it
+        // possible to express the other operations in terms of intersections.
+        let (flip_a, flip_b, flip_result) = match operation {
+            MeshBoolean::Union => (true, true, true),
+            MeshBoolean::Difference => (false, true, false),
+            MeshBoolean::Intersection => (false, false, false),
+        };
+
+        let result = intersect_meshes(
+            &Isometry::identity(),
+            &a,
+            flip_a,
+            &Isometry::identity(),
+            &b,
+            flip_b,
+        )
+        .map_err(|err| MeshBooleanError::Failed(err.to_string()))?;
+
+        let mut mesh = Mesh::new();
+
+        let Some(result) = result else {
+            return Ok(mesh);
+        };
+
+        for (face, name) in self.face_names() {
+            mesh.set_face_name(face, name);
+        }
+        for (face, name) in other.face_names() {
+            mesh.set_face_name(self.num_faces() + face, name);
+        }
+
+        let sources = [
+            (self.triangles().collect::<Vec<_>>(), &a, 0),
+            (other.triangles().collect(), &b, self.num_faces()),
+        ];
+
+        for triangle in result.triangles() {
+            let mut points =
+                [triangle.a, triangle.b, triangle.c].map(Point::from);
+            if flip_result {
+                points.swap(1, 2);
+            }
+
+            let (color, face) = source_of_triangle(triangle.center(), &sources);
+
+            mesh.push_triangle_of_face(points, color, face);
+        }
+
+        Ok(mesh)
+    }
+}
+
+/// A boolean operation between two meshes
+///
+/// See [`Mesh::boolean`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MeshBoolean {
+    /// Everything that is within either mesh
+    Union,
+
+    /// Everything that is within the first mesh, but not the second
+    Difference,
+
+    /// Everything that is within both meshes
+    Intersection,
+}
+
+/// Error computing a boolean operation between two meshes
+///
+/// See [`Mesh::boolean`].
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum MeshBooleanError {
+    /// A mesh has no triangles
+    #[error("Mesh has no triangles")]
+    Empty,
+
+    /// A mesh is not closed, or its triangles don't face the same way
+    #[error("Mesh is not closed and consistently oriented")]
+    NotClosed,
+
+    /// The boolean operation failed
+    #[error("Failed to compute boolean operation: {0}")]
+    Failed(String),
+}
+
+fn to_trimesh(mesh: &Mesh<Point<3>>) -> Result<TriMesh, MeshBooleanError> {
+    let vertices: Vec<_> = mesh.vertices().map(|point| point.to_na()).collect();
+    let indices: Vec<_> = mesh
+        .indices()
+        .collect::<Vec<_>>()
+        .chunks(3)
+        .map(|triangle| [triangle[0], triangle[1], triangle[2]])
+        .collect();
+
+    if indices.is_empty() {
+        return Err(MeshBooleanError::Empty);
+    }
+
+    let mut trimesh = TriMesh::new(vertices, indices);
+    trimesh
+        .set_flags(TriMeshFlags::HALF_EDGE_TOPOLOGY | TriMeshFlags::ORIENTED)
+        .map_err(|_| MeshBooleanError::NotClosed)?;
+
+    Ok(trimesh)
+}
+
+/// Find the color and face of the original triangle, that a triangle was cut
+/// from
+///
+/// The result of the boolean operation doesn't track where its triangles came
+/// from, but each of them lies within an original triangle. That is the
+/// closest triangle to its center, in either of the original meshes.
+fn source_of_triangle(
+    center: parry3d_f64::math::Point<f64>,
+    sources: &[(Vec<Triangle>, &TriMesh, FaceIndex); 2],
+) -> (Color, Option<FaceIndex>) {
+    let mut closest = None;
+
+    for (triangles, trimesh, face_offset) in sources {
+        let (projection, (index, _)) =
+            trimesh.project_local_point_and_get_location(&center, false);
+        let distance = (projection.point - center).norm();
+
+        if let Some((closest_distance, _)) = closest {
+            if distance >= closest_distance {
+                continue;
+            }
+        }
+
+        let triangle = triangles[index as usize];
+        let face = triangle.face.map(|face| face_offset + face);
+
+        closest = Some((distance, (triangle.color, face)));
+    }
+
+    let (_, source) = closest.expect("Checked two meshes");
+    source
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar};
+
+    use crate::{Color, Mesh};
+
+    #[test]
+    fn boolean() -> anyhow::Result<()> {
+        let a = cube([0., 0., 0.], 2.);
+        let b = cube([1.1, 1.2, 1.3], 2.);
+
+        let union = a.union(&b)?;
+        let difference = a.difference(&b)?;
+        let intersection = a.intersection(&b)?;
+
+        // The cubes overlap in a cuboid of 0.9 x 0.8 x 0.7.
+        let overlap = 0.9 * 0.8 * 0.7;
+        assert_volume(&union, 8. + 8. - overlap);
+        assert_volume(&difference, 8. - overlap);
+        assert_volume(&intersection, overlap);
+
+        // The faces of the second cube follow those of the first.
+        assert!(union
+            .triangles()
+            .any(|triangle| triangle.face == Some(6 + 5)));
+
+        Ok(())
+    }
+
+    fn cube(origin: [f64; 3], size: f64) -> Mesh<Point<3>> {
+        let [x, y, z] = origin;
+        let [a, b, c, d, e, f, g, h] = [
+            [x, y, z],
+            [x + size, y, z],
+            [x + size, y + size, z],
+            [x, y + size, z],
+            [x, y, z + size],
+            [x + size, y, z + size],
+            [x + size, y + size, z + size],
+            [x, y + size, z + size],
+        ];
+
+        let mut mesh = Mesh::new();
+        for quad in [
+            [a, d, c, b],
+            [e, f, g, h],
+            [a, b, f, e],
+            [b, c, g, f],
+            [c, d, h, g],
+            [d, a, e, h],
+        ] {
+            let face = mesh.add_face();
+            let [p, q, r, s] = quad;
+            for triangle in [[p, q, r], [p, r, s]] {
+                mesh.push_triangle_of_face(
+                    triangle,
+                    Color::default(),
+                    Some(face),
+                );
+            }
+        }
+
+        mesh
+    }
+
+    fn assert_volume(mesh: &Mesh<Point<3>>, expected: f64) {
+        let volume = mesh.volume();
+
+        assert!(
+            (volume - Scalar::from(expected)).abs() < Scalar::from(1e-9),
+            "Unexpected volume: {volume} (expected {expected})"
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use std::collections::BTreeMap;
+
+use fj_math::{Point, Scalar, Vector};
+
+use crate::{Mesh, Triangle};
+
+impl Mesh<Point<3>> {
+    /// Create a simplified version of the mesh with fewer triangles
+    ///
+    /// Space is divided into a grid of cubes with the provided edge length,
+    /// and all vertices within one cube are merged into one, at their average
+    /// position. Triangles that collapse as a result are removed. This is known
+    /// as vertex clustering.
+    ///
+    /// No vertex moves further than the diagonal of a cube, but the result is
+    /// not guaranteed to be closed or free of self-intersections. It's meant
+    /// for displaying the mesh, where the difference is too small to see, not
+    /// for further processing.
+    pub fn decimate(&self, cell_size: impl Into<Scalar>) -> Self {
+        let cell_size = cell_size.into();
+        assert!(
+            cell_size > Scalar::ZERO,
+            "Cell size for decimation must be positive"
+        );
+
+        let cell_of = |point: Point<3>| {
+            point
+                .coords
+                .components
+                .map(|c| (c / cell_size).floor().into_f64() as i64)
+        };
+
+        let mut clusters: BTreeMap<[i64; 3], (Vector<3>, usize)> =
+            BTreeMap::new();
+        for vertex in self.vertices() {
+            let (sum, count) = clusters.entry(cell_of(vertex)).or_default();
+            *sum = *sum + vertex.coords;
+            *count += 1;
+        }
+
+        let centers: BTreeMap<_, _> = clusters
+            .into_iter()
+            .map(|(cell, (sum, count))| {
+                (
+                    cell,
+                    Point {
+                        coords: sum / count as f64,
+                    },
+                )
+            })
+            .collect();
+
+        self.with_triangles(self.triangles().filter_map(|triangle| {
+            let cells = triangle.inner.points().map(cell_of);
+            let [a, b, c] = cells;
+            if a == b || b == c || c == a {
+                return None;
+            }
+
+            let points = cells.map(|cell| centers[&cell]);
+            let inner = fj_math::Triangle::from_points(points).ok()?;
+
+            Some(Triangle { inner, ..triangle })
+        }))
+        .remove_duplicate_triangles()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar};
+
+    use crate::{Color, Mesh};
+
+    #[test]
+    fn decimate() {
+        // A grid of 10 x 10 squares in the xy-plane, each made of two
+        // triangles.
+        let mut mesh = Mesh::new();
+        for x in 0..10 {
+            for y in 0..10 {
+                let [x, y] = [x, y].map(f64::from);
+                let [a, b, c, d] = [
+                    [x, y, 0.],
+                    [x + 1., y, 0.],
+                    [x + 1., y + 1., 0.],
+                    [x, y + 1., 0.],
+                ];
+                mesh.push_triangle([a, b, c], Color::default());
+                mesh.push_triangle([a, c, d], Color::default());
+            }
+        }
+
+        let decimated = mesh.decimate(2.5);
+        let num_triangles = decimated.triangles().count();
+        assert!(num_triangles > 0);
+        assert!(num_triangles < 200 / 4);
+
+        // Vertices are moved, but not out of the original mesh's bounds.
+        for vertex in decimated.vertices() {
+            assert!(vertex
+                .coords
+                .components
+                .iter()
+                .all(|&c| { c >= Scalar::ZERO && c <= Scalar::from(10.) }));
+            assert_eq!(vertex.z, Scalar::ZERO);
+        }
+
+        // A small enough cell size doesn't change anything.
+        let unchanged = mesh.decimate(0.1);
+        assert_eq!(unchanged.triangles().count(), 200);
+        assert_eq!(
+            unchanged.vertices().collect::<Vec<_>>(),
+            mesh.vertices().collect::<Vec<Point<3>>>()
+        );
+    }
+}
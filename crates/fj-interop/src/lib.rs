@@ -9,14 +9,20 @@
 //!
 //! [Fornjot]: https://www.fornjot.app/
 
+mod boolean;
 mod color;
+mod decimate;
 mod mesh;
 mod model;
+mod parts;
+mod repair;
 
 pub mod ext;
 
 pub use self::{
+    boolean::{MeshBoolean, MeshBooleanError},
     color::Color,
-    mesh::{Index, Mesh, Triangle},
+    mesh::{FaceIndex, Index, Mesh, Triangle},
     model::Model,
+    repair::RepairConfig,
 };
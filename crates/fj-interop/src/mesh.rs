@@ -1,6 +1,9 @@
-use std::{collections::HashMap, hash::Hash};
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::Hash,
+};
 
-use fj_math::Point;
+use fj_math::{Point, Scalar};
 
 use crate::Color;
 
@@ -12,6 +15,8 @@ pub struct Mesh<V> {
 
     indices_by_vertex: HashMap<V, Index>,
     triangles: Vec<Triangle>,
+    num_faces: FaceIndex,
+    face_names: BTreeMap<FaceIndex, String>,
 }
 
 impl<V> Mesh<V>
@@ -69,6 +74,43 @@ where
     pub fn triangles(&self) -> impl Iterator<Item = Triangle> + '_ {
         self.triangles.iter().copied()
     }
+
+    /// Add a face to the mesh
+    ///
+    /// Returns the index of the new face, which can be passed to
+    /// [`Mesh::push_triangle_of_face`], to mark the triangles that were
+    /// created from the face.
+    pub fn add_face(&mut self) -> FaceIndex {
+        let face = self.num_faces;
+        self.num_faces += 1;
+        face
+    }
+
+    /// Access the number of faces the triangles of the mesh were created from
+    pub fn num_faces(&self) -> FaceIndex {
+        self.num_faces
+    }
+
+    /// Set the name of a face
+    ///
+    /// Multiple faces can have the same name, making them a named group.
+    /// Exporters use the name to label the triangles of the face, where the
+    /// file format supports it.
+    pub fn set_face_name(&mut self, face: FaceIndex, name: impl Into<String>) {
+        self.face_names.insert(face, name.into());
+    }
+
+    /// Access the name of a face, if it has one
+    pub fn face_name(&self, face: FaceIndex) -> Option<&str> {
+        self.face_names.get(&face).map(String::as_str)
+    }
+
+    /// Access the names of all faces that have one
+    pub fn face_names(&self) -> impl Iterator<Item = (FaceIndex, &str)> + '_ {
+        self.face_names
+            .iter()
+            .map(|(&face, name)| (face, name.as_str()))
+    }
 }
 
 impl Mesh<Point<3>> {
@@ -77,6 +119,18 @@ impl Mesh<Point<3>> {
         &mut self,
         triangle: impl Into<fj_math::Triangle<3>>,
         color: Color,
+    ) {
+        self.push_triangle_of_face(triangle, color, None);
+    }
+
+    /// Add a triangle to the mesh, that was created from the provided face
+    ///
+    /// See [`Mesh::add_face`].
+    pub fn push_triangle_of_face(
+        &mut self,
+        triangle: impl Into<fj_math::Triangle<3>>,
+        color: Color,
+        face: Option<FaceIndex>,
     ) {
         let triangle = triangle.into();
 
@@ -84,11 +138,46 @@ impl Mesh<Point<3>> {
             self.push_vertex(point);
         }
 
+        if let Some(face) = face {
+            self.num_faces = self.num_faces.max(face + 1);
+        }
+
         self.triangles.push(Triangle {
             inner: triangle,
             color,
+            face,
         });
     }
+
+    /// Access the points of the mesh's triangles, converted to `f32`
+    ///
+    /// This is lossy, and is meant for passing meshes to code that works with
+    /// `f32` (like graphics code, or some file formats) explicitly.
+    pub fn triangles_f32(&self) -> impl Iterator<Item = [[f32; 3]; 3]> + '_ {
+        self.triangles
+            .iter()
+            .map(|triangle| triangle.inner.points().map(<[f32; 3]>::from))
+    }
+
+    /// Compute the volume that the mesh encloses
+    ///
+    /// The volume is positive, if the triangles face outward, and negative, if
+    /// they face inward. The result is only meaningful for watertight meshes.
+    pub fn volume(&self) -> Scalar {
+        self.triangles
+            .iter()
+            .map(|triangle| signed_volume(triangle.inner))
+            .fold(Scalar::ZERO, |sum, volume| sum + volume)
+    }
+}
+
+/// Compute the signed volume of the tetrahedron between a triangle and origin
+///
+/// Summing this up over all triangles of a watertight mesh yields the volume
+/// of the mesh.
+pub(crate) fn signed_volume(triangle: fj_math::Triangle<3>) -> Scalar {
+    let [a, b, c] = triangle.points().map(|point| point.coords);
+    a.dot(&b.cross(&c)) / 6.
 }
 
 // This needs to be a manual implementation. Deriving `Default` would require
@@ -100,6 +189,8 @@ impl<V> Default for Mesh<V> {
             indices: Vec::default(),
             indices_by_vertex: HashMap::default(),
             triangles: Vec::default(),
+            num_faces: 0,
+            face_names: BTreeMap::default(),
         }
     }
 }
@@ -107,9 +198,17 @@ impl<V> Default for Mesh<V> {
 /// An index that refers to a vertex in a mesh
 pub type Index = u32;
 
+/// An index that identifies the face a triangle in a mesh was created from
+///
+/// Triangles that were created from the same face of a model share a face
+/// index. This allows tools that work with the mesh to select whole faces,
+/// instead of individual triangles.
+pub type FaceIndex = u32;
+
 /// A triangle
 ///
-/// Extension of [`fj_math::Triangle`] that also includes a color.
+/// Extension of [`fj_math::Triangle`] that also includes a color, and the face
+/// it was created from.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
 pub struct Triangle {
     /// The points of the triangle
@@ -117,4 +216,7 @@ pub struct Triangle {
 
     /// The color of the triangle
     pub color: Color,
+
+    /// The face that the triangle was created from, if known
+    pub face: Option<FaceIndex>,
 }
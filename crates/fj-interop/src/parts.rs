@@ -0,0 +1,138 @@
+use std::collections::BTreeMap;
+
+use fj_math::Point;
+
+use crate::{FaceIndex, Index, Mesh};
+
+impl Mesh<Point<3>> {
+    /// Split the mesh into its separate parts
+    ///
+    /// Triangles that share a vertex, directly or by way of other triangles,
+    /// belong to the same part. For a model that consists of multiple solids,
+    /// or of a solid with multiple disjoint shells, this results in one part
+    /// per shell.
+    ///
+    /// The parts are ordered by their first triangle in this mesh. Each part
+    /// keeps the faces that its triangles were created from, renumbered in the
+    /// order they appear in, as well as their names.
+    pub fn parts(&self) -> Vec<Self> {
+        let indices = self.indices().collect::<Vec<_>>();
+
+        let mut parents =
+            (0..self.vertices().count() as Index).collect::<Vec<_>>();
+        for triangle in indices.chunks(3) {
+            let [a, b, c] = [triangle[0], triangle[1], triangle[2]];
+            union(&mut parents, a, b);
+            union(&mut parents, a, c);
+        }
+
+        let mut parts: Vec<Self> = Vec::new();
+        let mut part_by_root = BTreeMap::new();
+        let mut faces = Vec::new();
+
+        for (triangle, vertices) in self.triangles().zip(indices.chunks(3)) {
+            let root = find(&mut parents, vertices[0]);
+            let part = *part_by_root.entry(root).or_insert_with(|| {
+                parts.push(Mesh::new());
+                faces.push(BTreeMap::new());
+                parts.len() - 1
+            });
+
+            let face = triangle.face.map(|face| {
+                let next_face: FaceIndex = faces[part].len() as FaceIndex;
+                *faces[part].entry(face).or_insert(next_face)
+            });
+
+            parts[part].push_triangle_of_face(
+                triangle.inner,
+                triangle.color,
+                face,
+            );
+        }
+
+        for (part, faces) in parts.iter_mut().zip(faces) {
+            for (face, part_face) in faces {
+                if let Some(name) = self.face_name(face) {
+                    part.set_face_name(part_face, name);
+                }
+            }
+        }
+
+        parts
+    }
+
+    /// Access the name that all faces of the mesh share, if any
+    ///
+    /// Giving every face of a part the same name labels the part as a whole.
+    /// Returns `None`, if the mesh has no faces, or if any of them has no name
+    /// or a different one.
+    pub fn name(&self) -> Option<&str> {
+        let mut names = (0..self.num_faces()).map(|face| self.face_name(face));
+
+        let name = names.next()??;
+        names.all(|other| other == Some(name)).then_some(name)
+    }
+}
+
+fn find(parents: &mut [Index], mut index: Index) -> Index {
+    while parents[index as usize] != index {
+        let parent = parents[index as usize];
+        parents[index as usize] = parents[parent as usize];
+        index = parent;
+    }
+
+    index
+}
+
+fn union(parents: &mut [Index], a: Index, b: Index) {
+    let a = find(parents, a);
+    let b = find(parents, b);
+    parents[a.max(b) as usize] = a.min(b);
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Point;
+
+    use crate::{Color, Mesh};
+
+    #[test]
+    fn parts() {
+        let mut mesh = Mesh::new();
+
+        // Two tetrahedra that don't touch, with faces in mixed order.
+        for (i, offset) in [0., 2., 0., 2.].into_iter().enumerate() {
+            let [a, b, c, d] =
+                [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]]
+                    .map(|[x, y, z]| Point::from([x + offset, y, z]));
+
+            let face = mesh.add_face();
+            mesh.set_face_name(face, if offset == 0. { "a" } else { "b" });
+
+            let triangles = if i < 2 {
+                [[a, c, b], [a, b, d]]
+            } else {
+                [[b, c, d], [a, d, c]]
+            };
+            for triangle in triangles {
+                mesh.push_triangle_of_face(
+                    triangle,
+                    Color::default(),
+                    Some(face),
+                );
+            }
+        }
+
+        let parts = mesh.parts();
+
+        assert_eq!(parts.len(), 2);
+        for (part, name) in parts.iter().zip(["a", "b"]) {
+            assert_eq!(part.triangles().count(), 4);
+            assert_eq!(part.num_faces(), 2);
+            assert_eq!(part.name(), Some(name));
+        }
+
+        // The whole mesh has faces with different names.
+        assert_eq!(mesh.name(), None);
+    }
+}
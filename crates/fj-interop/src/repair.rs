@@ -0,0 +1,530 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+use fj_math::{Aabb, Point, Scalar};
+
+use crate::{mesh::signed_volume, Mesh, Triangle};
+
+impl Mesh<Point<3>> {
+    /// Repair the mesh
+    ///
+    /// Applies all repair operations, in an order where each can benefit from
+    /// the previous ones:
+    ///
+    /// 1. [`Mesh::weld_vertices`]
+    /// 2. [`Mesh::remove_degenerate_triangles`]
+    /// 3. [`Mesh::remove_duplicate_triangles`]
+    /// 4. [`Mesh::unify_winding`]
+    /// 5. [`Mesh::fill_holes`]
+    ///
+    /// Meshes created by Fornjot should not need any of this. It is intended
+    /// for meshes from other sources, and as a last step before export, to
+    /// make up for any flaws in the triangulation.
+    pub fn repair(&self, config: &RepairConfig) -> Self {
+        self.weld_vertices(config.weld_distance)
+            .remove_degenerate_triangles(config.min_triangle_area)
+            .remove_duplicate_triangles()
+            .unify_winding()
+            .fill_holes(config.max_hole_edges)
+    }
+
+    /// Merge vertices that are no more than `max_distance` apart
+    ///
+    /// Each group of close vertices is replaced by the first vertex of that
+    /// group. Triangles that collapse as a result are removed.
+    pub fn weld_vertices(&self, max_distance: impl Into<Scalar>) -> Self {
+        let max_distance = max_distance.into();
+
+        // Sort the vertices into a grid, so we only need to compare each of
+        // them to the vertices in neighboring cells.
+        let cell_size = if max_distance > Scalar::ZERO {
+            max_distance
+        } else {
+            Scalar::ONE
+        };
+        let cell_of = |point: Point<3>| {
+            point
+                .coords
+                .components
+                .map(|c| (c / cell_size).floor().into_f64() as i64)
+        };
+
+        let mut cells: BTreeMap<[i64; 3], Vec<Point<3>>> = BTreeMap::new();
+        let mut welded = BTreeMap::new();
+
+        for vertex in self.vertices() {
+            let [x, y, z] = cell_of(vertex);
+
+            let mut target = None;
+            'search: for dx in -1..=1 {
+                for dy in -1..=1 {
+                    for dz in -1..=1 {
+                        let Some(cell) = cells.get(&[x + dx, y + dy, z + dz])
+                        else {
+                            continue;
+                        };
+
+                        target = cell
+                            .iter()
+                            .find(|other| {
+                                other.distance_to(&vertex) <= max_distance
+                            })
+                            .copied();
+                        if target.is_some() {
+                            break 'search;
+                        }
+                    }
+                }
+            }
+
+            let target = target.unwrap_or_else(|| {
+                cells.entry([x, y, z]).or_default().push(vertex);
+                vertex
+            });
+            welded.insert(vertex, target);
+        }
+
+        self.with_triangles(self.triangles().filter_map(|triangle| {
+            let points = triangle.inner.points().map(|point| welded[&point]);
+            let inner = fj_math::Triangle::from_points(points).ok()?;
+
+            Some(Triangle { inner, ..triangle })
+        }))
+    }
+
+    /// Remove triangles that have an area smaller than `min_area`
+    ///
+    /// This can open gaps in the mesh, if the vertices of a removed triangle
+    /// are not coincident. Use [`Mesh::fill_holes`] to close them again.
+    pub fn remove_degenerate_triangles(
+        &self,
+        min_area: impl Into<Scalar>,
+    ) -> Self {
+        let min_area = min_area.into();
+
+        self.with_triangles(self.triangles().filter(|triangle| {
+            let [a, b, c] = triangle.inner.points();
+            (b - a).cross(&(c - a)).magnitude() / 2. >= min_area
+        }))
+    }
+
+    /// Remove triangles that share all of their vertices with another triangle
+    ///
+    /// Of multiple triangles that face the same way, only the first one is
+    /// kept. Two triangles that face opposite ways form an infinitely thin
+    /// wall, and are both removed.
+    pub fn remove_duplicate_triangles(&self) -> Self {
+        let triangles: Vec<_> = self.triangles().collect();
+
+        let mut by_points: BTreeMap<_, Vec<usize>> = BTreeMap::new();
+        for (i, triangle) in triangles.iter().enumerate() {
+            by_points
+                .entry(triangle.inner.normalize())
+                .or_default()
+                .push(i);
+        }
+
+        self.with_triangles(triangles.iter().enumerate().filter_map(
+            |(i, triangle)| {
+                let duplicates = &by_points[&triangle.inner.normalize()];
+
+                let is_first = duplicates[0] == i;
+                let has_opposite = duplicates.iter().any(|&j| {
+                    !same_winding(triangle.inner, triangles[j].inner)
+                });
+
+                (is_first && !has_opposite).then_some(*triangle)
+            },
+        ))
+    }
+
+    /// Make all triangles face the same way
+    ///
+    /// Within each connected part of the mesh, triangles are flipped to be
+    /// consistent with their neighbors. Afterwards, each part is flipped as a
+    /// whole, if its triangles face inward.
+    pub fn unify_winding(&self) -> Self {
+        let mut triangles: Vec<_> = self.triangles().collect();
+
+        let mut triangles_by_edge: BTreeMap<_, Vec<usize>> = BTreeMap::new();
+        for (i, triangle) in triangles.iter().enumerate() {
+            for [a, b] in edges(triangle.inner) {
+                triangles_by_edge
+                    .entry(undirected([a, b]))
+                    .or_default()
+                    .push(i);
+            }
+        }
+
+        let mut visited = vec![false; triangles.len()];
+
+        for start in 0..triangles.len() {
+            if visited[start] {
+                continue;
+            }
+            visited[start] = true;
+
+            let mut part = vec![start];
+            let mut queue = VecDeque::from([start]);
+
+            while let Some(i) = queue.pop_front() {
+                for [a, b] in edges(triangles[i].inner) {
+                    for &j in &triangles_by_edge[&undirected([a, b])] {
+                        if visited[j] {
+                            continue;
+                        }
+                        visited[j] = true;
+
+                        // Neighbors facing the same way traverse their shared
+                        // edge in opposite directions.
+                        if edges(triangles[j].inner).contains(&[a, b]) {
+                            triangles[j].inner = flip(triangles[j].inner);
+                        }
+
+                        part.push(j);
+                        queue.push_back(j);
+                    }
+                }
+            }
+
+            let volume = part
+                .iter()
+                .map(|&i| signed_volume(triangles[i].inner))
+                .fold(Scalar::ZERO, |sum, volume| sum + volume);
+            if volume < Scalar::ZERO {
+                for i in part {
+                    triangles[i].inner = flip(triangles[i].inner);
+                }
+            }
+        }
+
+        self.with_triangles(triangles)
+    }
+
+    /// Close holes that are bounded by no more than `max_edges` edges
+    ///
+    /// A hole is a loop of edges that are only part of one triangle. Holes are
+    /// closed using a fan of triangles, which works well for small holes, but
+    /// produces bad results for large, non-convex ones. That is what the limit
+    /// is for.
+    ///
+    /// Holes that share a vertex are closed separately.
+    pub fn fill_holes(&self, max_edges: usize) -> Self {
+        let mut triangles: Vec<_> = self.triangles().collect();
+
+        let directed: BTreeSet<_> = triangles
+            .iter()
+            .flat_map(|triangle| edges(triangle.inner))
+            .collect();
+
+        // Multiple boundary edges can start at the same vertex, if holes
+        // touch each other there.
+        let mut boundary: BTreeMap<_, Vec<_>> = BTreeMap::new();
+        for triangle in &triangles {
+            for [a, b] in edges(triangle.inner) {
+                if !directed.contains(&[b, a]) {
+                    boundary.entry(a).or_default().push((b, *triangle));
+                }
+            }
+        }
+
+        let mut holes = Vec::new();
+        while let Some((&start, _)) = boundary.first_key_value() {
+            let mut path: Vec<(Point<3>, Triangle)> = Vec::new();
+            let mut current = start;
+
+            while let Some(edges) = boundary.get_mut(&current) {
+                let (next, triangle) =
+                    edges.pop().expect("Only non-empty lists are stored");
+                if edges.is_empty() {
+                    boundary.remove(&current);
+                }
+
+                path.push((current, triangle));
+                current = next;
+
+                // If the path has come back to a vertex that it already
+                // visited, that closes a hole. The rest of the path might
+                // still become part of another one.
+                if let Some(i) = path.iter().position(|&(p, _)| p == current) {
+                    holes.push(path.split_off(i));
+                }
+            }
+        }
+
+        for hole in holes {
+            if hole.len() < 3 || hole.len() > max_edges {
+                continue;
+            }
+
+            // The new triangles need to traverse the boundary edges in the
+            // opposite direction, to face the same way as their neighbors.
+            let (first, neighbor) = hole[0];
+            let fan: Option<Vec<_>> = hole[1..]
+                .windows(2)
+                .map(|window| {
+                    let [(b, _), (c, _)] = [window[0], window[1]];
+                    fj_math::Triangle::from_points([first, c, b]).ok()
+                })
+                .collect();
+
+            if let Some(fan) = fan {
+                triangles.extend(fan.into_iter().map(|inner| Triangle {
+                    inner,
+                    color: neighbor.color,
+                    face: None,
+                }));
+            }
+        }
+
+        self.with_triangles(triangles)
+    }
+
+    /// Create a mesh with the provided triangles, and the face names of this
+    /// one
+    pub(crate) fn with_triangles(
+        &self,
+        triangles: impl IntoIterator<Item = Triangle>,
+    ) -> Self {
+        let mut mesh = Self::new();
+
+        for (face, name) in self.face_names() {
+            mesh.set_face_name(face, name);
+        }
+        for triangle in triangles {
+            mesh.push_triangle_of_face(
+                triangle.inner,
+                triangle.color,
+                triangle.face,
+            );
+        }
+
+        mesh
+    }
+}
+
+/// Configuration for [`Mesh::repair`]
+#[derive(Clone, Copy, Debug)]
+pub struct RepairConfig {
+    /// The distance within which vertices are merged
+    ///
+    /// See [`Mesh::weld_vertices`].
+    pub weld_distance: Scalar,
+
+    /// The area below which triangles are removed
+    ///
+    /// See [`Mesh::remove_degenerate_triangles`].
+    pub min_triangle_area: Scalar,
+
+    /// The maximum number of edges of holes that are filled
+    ///
+    /// See [`Mesh::fill_holes`].
+    pub max_hole_edges: usize,
+}
+
+impl RepairConfig {
+    /// Create a configuration that is suitable for the provided mesh
+    ///
+    /// The distances of the default configuration are suitable for meshes
+    /// that are about one unit in size. This scales them to the size of the
+    /// provided mesh.
+    pub fn for_mesh(mesh: &Mesh<Point<3>>) -> Self {
+        let config = Self::default();
+
+        let size = Aabb::<3>::from_points(mesh.vertices()).size().magnitude();
+        if size == Scalar::ZERO {
+            return config;
+        }
+
+        Self {
+            weld_distance: config.weld_distance * size,
+            min_triangle_area: config.min_triangle_area * size * size,
+            ..config
+        }
+    }
+}
+
+impl Default for RepairConfig {
+    fn default() -> Self {
+        Self {
+            weld_distance: Scalar::from(1e-9),
+            min_triangle_area: Scalar::from(1e-18),
+            max_hole_edges: 16,
+        }
+    }
+}
+
+fn edges(triangle: fj_math::Triangle<3>) -> [[Point<3>; 2]; 3] {
+    let [a, b, c] = triangle.points();
+    [[a, b], [b, c], [c, a]]
+}
+
+fn undirected([a, b]: [Point<3>; 2]) -> [Point<3>; 2] {
+    if a <= b {
+        [a, b]
+    } else {
+        [b, a]
+    }
+}
+
+fn flip(triangle: fj_math::Triangle<3>) -> fj_math::Triangle<3> {
+    let [a, b, c] = triangle.points();
+    fj_math::Triangle::from([a, c, b])
+}
+
+fn same_winding(a: fj_math::Triangle<3>, b: fj_math::Triangle<3>) -> bool {
+    let [a, ..] = edges(a);
+    edges(b).contains(&a)
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar};
+
+    use crate::{Color, Mesh};
+
+    use super::{flip, RepairConfig};
+
+    #[test]
+    fn weld_vertices() {
+        let mesh = tetrahedron();
+
+        let mut shifted = Mesh::new();
+        for triangle in mesh.triangles() {
+            let [a, b, c] = triangle.inner.points();
+            shifted.push_triangle([a, b, c + [1e-12, 0., 0.]], triangle.color);
+        }
+        assert_ne!(shifted.vertices().count(), 4);
+
+        let welded = shifted.weld_vertices(1e-9);
+        assert_eq!(welded.vertices().count(), 4);
+    }
+
+    #[test]
+    fn remove_duplicate_triangles() {
+        let mesh = tetrahedron();
+
+        let mut duplicated = mesh.clone();
+        let triangle = mesh.triangles().next().expect("Mesh has triangles");
+        duplicated.push_triangle(triangle.inner, triangle.color);
+        assert_eq!(
+            duplicated.remove_duplicate_triangles().triangles().count(),
+            4
+        );
+
+        // An opposite pair of triangles is removed altogether.
+        let mut wall = mesh.clone();
+        wall.push_triangle(flip(triangle.inner), triangle.color);
+        assert_eq!(wall.remove_duplicate_triangles().triangles().count(), 3);
+    }
+
+    #[test]
+    fn unify_winding() {
+        let mesh = tetrahedron();
+
+        let mut flipped = Mesh::new();
+        for (i, triangle) in mesh.triangles().enumerate() {
+            let inner = if i % 2 == 0 {
+                flip(triangle.inner)
+            } else {
+                triangle.inner
+            };
+            flipped.push_triangle(inner, triangle.color);
+        }
+
+        let unified = flipped.unify_winding();
+        let mut expected: Vec<_> = mesh.triangles().collect();
+        let mut actual: Vec<_> = unified.triangles().collect();
+        expected.sort();
+        actual.sort();
+        assert_eq!(actual, expected);
+        assert!(unified.volume() > Scalar::ZERO);
+    }
+
+    #[test]
+    fn fill_holes() {
+        let mesh = tetrahedron();
+
+        let mut open = Mesh::new();
+        for triangle in mesh.triangles().skip(1) {
+            open.push_triangle(triangle.inner, triangle.color);
+        }
+
+        // Nothing happens, if the hole is too large.
+        assert_eq!(open.fill_holes(2).triangles().count(), 3);
+
+        let filled = open.fill_holes(3);
+        assert_eq!(filled.triangles().count(), 4);
+        assert_eq!(filled.volume(), mesh.volume());
+    }
+
+    #[test]
+    fn fill_holes_touching_at_vertex() {
+        let mesh = tetrahedron();
+
+        // Two open tetrahedra, stacked on top of each other. The top vertex of
+        // the lower one touches the hole in the bottom of the upper one, and
+        // is part of its own hole.
+        let mut open = Mesh::new();
+        for triangle in mesh.triangles().skip(1) {
+            open.push_triangle(triangle.inner, triangle.color);
+        }
+        for (i, triangle) in mesh.triangles().enumerate() {
+            if i == 2 {
+                continue;
+            }
+
+            let points = triangle.inner.points().map(|p| p - [0., 0., 1.]);
+            open.push_triangle(points, triangle.color);
+        }
+
+        let filled = open.fill_holes(3);
+        assert_eq!(filled.triangles().count(), 8);
+        assert_eq!(filled.volume(), mesh.volume() * 2.);
+    }
+
+    #[test]
+    fn repair_config_for_mesh() {
+        let scale = 1e6;
+        let mesh = tetrahedron();
+
+        // The vertices are off by the same relative amount as in
+        // `weld_vertices`, but the default configuration doesn't account for
+        // the larger scale.
+        let mut shifted = Mesh::new();
+        for triangle in mesh.triangles() {
+            let [a, b, c] = triangle.inner.points().map(|point| point * scale);
+            let c = c + [1e-12 * scale, 0., 0.];
+            shifted.push_triangle([a, b, c], triangle.color);
+        }
+
+        let default = shifted.repair(&RepairConfig::default());
+        assert_ne!(default.vertices().count(), 4);
+
+        let config = RepairConfig::for_mesh(&shifted);
+        let repaired = shifted.repair(&config);
+        assert_eq!(repaired.vertices().count(), 4);
+    }
+
+    #[test]
+    fn repair() {
+        let mesh = tetrahedron();
+        assert_eq!(
+            mesh.repair(&RepairConfig::default())
+                .triangles()
+                .collect::<Vec<_>>(),
+            mesh.triangles().collect::<Vec<_>>(),
+        );
+    }
+
+    fn tetrahedron() -> Mesh<Point<3>> {
+        let [a, b, c, d] =
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]];
+
+        let mut mesh = Mesh::new();
+        for triangle in [[a, c, b], [a, b, d], [b, c, d], [c, a, d]] {
+            mesh.push_triangle(triangle, Color::default());
+        }
+
+        mesh
+    }
+}
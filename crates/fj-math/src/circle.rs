@@ -1,6 +1,8 @@
 use approx::AbsDiffEq;
 
-use crate::{Aabb, Point, Scalar, Vector};
+use num_traits::Float;
+
+use crate::{Aabb, Line, Point, Scalar, Vector};
 
 /// An n-dimensional circle
 ///
@@ -163,6 +165,203 @@ impl<const D: usize> Circle<D> {
     }
 }
 
+impl Circle<2> {
+    /// Construct the circles with the given radius that are tangent to 2 lines
+    ///
+    /// This is the construction of a fillet between two lines. There is one
+    /// circle in each of the 4 angles between the lines. They are returned in
+    /// the following order, relative to the directions of the lines:
+    ///
+    /// - Left of the first line, left of the second line
+    /// - Left of the first line, right of the second line
+    /// - Right of the first line, left of the second line
+    /// - Right of the first line, right of the second line
+    ///
+    /// Returns no circles, if the lines are parallel. In that case, there are
+    /// either no solutions, or infinitely many.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the radius is not positive.
+    pub fn tangent_to_lines(
+        lines: [&Line<2>; 2],
+        radius: impl Into<Scalar>,
+    ) -> Vec<Self> {
+        let radius = radius.into();
+        assert!(
+            radius > Scalar::ZERO,
+            "Radius of tangent circle must be positive: {radius}"
+        );
+
+        let [a, b] = lines.map(|line| {
+            let normal = left_normal(line.direction());
+            (normal, normal.dot(&line.origin().coords))
+        });
+
+        let determinant = a.0.cross2d(&b.0);
+        if determinant == Scalar::ZERO {
+            return Vec::new();
+        }
+
+        let mut circles = Vec::new();
+        for side_a in [Scalar::ONE, -Scalar::ONE] {
+            for side_b in [Scalar::ONE, -Scalar::ONE] {
+                // The center has the (signed) distance `radius` to each line.
+                let distance_a = a.1 + radius * side_a;
+                let distance_b = b.1 + radius * side_b;
+
+                let center = Point::from([
+                    (distance_a * b.0.v - distance_b * a.0.v) / determinant,
+                    (a.0.u * distance_b - b.0.u * distance_a) / determinant,
+                ]);
+                circles.push(Self::from_center_and_radius(center, radius));
+            }
+        }
+
+        circles
+    }
+
+    /// Construct the circles with the given radius, through a point, tangent to
+    /// a line
+    ///
+    /// Returns no circles, if the point is farther away from the line than the
+    /// diameter of the circle. Returns a single circle, if it is precisely as
+    /// far away as the diameter. Returns two circles otherwise, ordered by
+    /// their position along the line.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the radius is not positive.
+    pub fn through_point_tangent_to_line(
+        point: impl Into<Point<2>>,
+        line: &Line<2>,
+        radius: impl Into<Scalar>,
+    ) -> Vec<Self> {
+        let point = point.into();
+        let radius = radius.into();
+        assert!(
+            radius > Scalar::ZERO,
+            "Radius of tangent circle must be positive: {radius}"
+        );
+
+        let direction = line.direction().normalize();
+        let normal = left_normal(line.direction());
+
+        let distance = normal.dot(&(point - line.origin()));
+        let foot = point - normal * distance;
+
+        // The circle must be on the same side of the line as the point. If the
+        // point is on the line, that is true for both sides.
+        let sides = if distance == Scalar::ZERO {
+            vec![Scalar::ONE, -Scalar::ONE]
+        } else {
+            vec![distance.signum()]
+        };
+
+        let mut circles = Vec::new();
+        for side in sides {
+            // The offset along the line, from the point to the center.
+            let offset_squared = radius * radius
+                - (radius - distance.abs()) * (radius - distance.abs());
+            if offset_squared < Scalar::ZERO {
+                continue;
+            }
+            let offset = offset_squared.sqrt();
+
+            let offsets = if offset == Scalar::ZERO {
+                vec![offset]
+            } else {
+                vec![-offset, offset]
+            };
+
+            for offset in offsets {
+                let center = foot + normal * radius * side + direction * offset;
+                circles.push(Self::from_center_and_radius(center, radius));
+            }
+        }
+
+        circles
+    }
+
+    /// Compute the outer tangents of this circle and another one
+    ///
+    /// Outer tangents touch both circles on the same side, like an open belt
+    /// running around two pulleys. Each tangent is returned as the point where
+    /// it touches this circle, followed by the point where it touches the
+    /// other one.
+    ///
+    /// There are two outer tangents, unless one circle lies within the other.
+    /// The tangent to the left of the line from this circle's center to the
+    /// other's comes first.
+    pub fn outer_tangents(&self, other: &Self) -> Vec<[Point<2>; 2]> {
+        self.tangents(other, other.radius())
+    }
+
+    /// Compute the inner tangents of this circle and another one
+    ///
+    /// Inner tangents pass between the circles, like a crossed belt running
+    /// around two pulleys. Each tangent is returned as the point where it
+    /// touches this circle, followed by the point where it touches the other
+    /// one.
+    ///
+    /// There are two inner tangents, unless the circles overlap. The tangent
+    /// that touches this circle left of the line from its center to the
+    /// other's comes first.
+    pub fn inner_tangents(&self, other: &Self) -> Vec<[Point<2>; 2]> {
+        self.tangents(other, -other.radius())
+    }
+
+    /// Compute tangents, with a signed radius for the other circle
+    ///
+    /// If the radius is negative, the tangents touch the other circle on the
+    /// opposite side.
+    fn tangents(
+        &self,
+        other: &Self,
+        other_radius: Scalar,
+    ) -> Vec<[Point<2>; 2]> {
+        let offset = other.center() - self.center();
+        let distance = offset.magnitude();
+        if distance == Scalar::ZERO {
+            return Vec::new();
+        }
+
+        // The normal of each tangent is at this angle to the line between the
+        // centers.
+        let cos = (self.radius() - other_radius) / distance;
+        if cos.abs() > Scalar::ONE {
+            return Vec::new();
+        }
+        let sin = (Scalar::ONE - cos * cos).sqrt();
+
+        let sides = if sin == Scalar::ZERO {
+            vec![Scalar::ONE]
+        } else {
+            vec![Scalar::ONE, -Scalar::ONE]
+        };
+
+        let along = offset / distance;
+        let across = left_normal(along);
+
+        sides
+            .into_iter()
+            .map(|side| {
+                let normal = along * cos + across * sin * side;
+                [
+                    self.center() + normal * self.radius(),
+                    other.center() + normal * other_radius,
+                ]
+            })
+            .collect()
+    }
+}
+
+/// Compute the unit vector that points to the left of the provided one
+fn left_normal(vector: Vector<2>) -> Vector<2> {
+    let vector = vector.normalize();
+    Vector::from([-vector.v, vector.u])
+}
+
 impl<const D: usize> approx::AbsDiffEq for Circle<D> {
     type Epsilon = <Scalar as approx::AbsDiffEq>::Epsilon;
 
@@ -181,7 +380,9 @@ impl<const D: usize> approx::AbsDiffEq for Circle<D> {
 mod tests {
     use std::f64::consts::{FRAC_PI_2, PI};
 
-    use crate::{Point, Vector};
+    use approx::assert_abs_diff_eq;
+
+    use crate::{Line, Point, Scalar, Vector};
 
     use super::Circle;
 
@@ -210,4 +411,114 @@ mod tests {
             Point::from([FRAC_PI_2 * 3.]),
         );
     }
+
+    #[test]
+    fn tangent_to_lines() {
+        let (a, _) = Line::from_points([[0., 0.], [1., 0.]]);
+        let (b, _) = Line::from_points([[0., 0.], [1., 1.]]);
+
+        let circles = Circle::tangent_to_lines([&a, &b], 0.5);
+        assert_eq!(circles.len(), 4);
+
+        for circle in &circles {
+            for line in [&a, &b] {
+                assert_abs_diff_eq!(
+                    distance_to_line(circle.center(), line),
+                    Scalar::from(0.5),
+                    epsilon = Scalar::from(1e-12)
+                );
+            }
+        }
+
+        // The first circle is left of both lines, the second is left of the
+        // first line and right of the second, in the acute angle between them.
+        let [first, second] = [0, 1].map(|i| circles[i].center());
+        assert!(first.v > Scalar::ZERO && first.v > first.u);
+        assert!(second.v > Scalar::ZERO && second.v < second.u);
+
+        let (parallel, _) = Line::from_points([[0., 1.], [1., 1.]]);
+        assert!(Circle::tangent_to_lines([&a, &parallel], 0.5).is_empty());
+    }
+
+    #[test]
+    fn through_point_tangent_to_line() {
+        let (line, _) = Line::from_points([[0., 0.], [1., 0.]]);
+
+        for (point, num_circles) in [
+            ([0., 0.], 2),
+            ([0., 0.5], 2),
+            ([0., -0.5], 2),
+            ([0., 2.], 1),
+            ([0., 3.], 0),
+        ] {
+            let circles =
+                Circle::through_point_tangent_to_line(point, &line, 1.);
+            assert_eq!(circles.len(), num_circles);
+
+            for circle in circles {
+                assert_abs_diff_eq!(
+                    (circle.center() - Point::from(point)).magnitude(),
+                    Scalar::ONE,
+                    epsilon = Scalar::from(1e-12)
+                );
+                assert_abs_diff_eq!(
+                    distance_to_line(circle.center(), &line),
+                    Scalar::ONE,
+                    epsilon = Scalar::from(1e-12)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn outer_and_inner_tangents() {
+        let a = Circle::from_center_and_radius([0., 0.], 2.);
+        let b = Circle::from_center_and_radius([5., 0.], 1.);
+
+        for tangents in [a.outer_tangents(&b), a.inner_tangents(&b)] {
+            assert_eq!(tangents.len(), 2);
+
+            for [point_a, point_b] in tangents {
+                let tangent = point_b - point_a;
+
+                for (circle, point) in [(a, point_a), (b, point_b)] {
+                    let radius = point - circle.center();
+                    assert_abs_diff_eq!(
+                        radius.magnitude(),
+                        circle.radius(),
+                        epsilon = Scalar::from(1e-12)
+                    );
+                    assert_abs_diff_eq!(
+                        radius.dot(&tangent),
+                        Scalar::ZERO,
+                        epsilon = Scalar::from(1e-12)
+                    );
+                }
+            }
+        }
+
+        // Outer tangents touch both circles on the same side, inner tangents
+        // on opposite sides.
+        let [outer, _] = a.outer_tangents(&b)[..] else {
+            unreachable!()
+        };
+        assert!(outer[0].v > Scalar::ZERO && outer[1].v > Scalar::ZERO);
+        let [inner, _] = a.inner_tangents(&b)[..] else {
+            unreachable!()
+        };
+        assert!(inner[0].v > Scalar::ZERO && inner[1].v < Scalar::ZERO);
+
+        // Overlapping circles have no inner tangents. Nested circles have no
+        // tangents at all.
+        let c = Circle::from_center_and_radius([2., 0.], 1.);
+        assert!(a.inner_tangents(&c).is_empty());
+        assert_eq!(a.outer_tangents(&c).len(), 2);
+        let d = Circle::from_center_and_radius([0.5, 0.], 1.);
+        assert!(a.outer_tangents(&d).is_empty());
+    }
+
+    fn distance_to_line(point: Point<2>, line: &Line<2>) -> Scalar {
+        let direction = line.direction().normalize();
+        direction.cross2d(&(point - line.origin())).abs()
+    }
 }
@@ -0,0 +1,191 @@
+use crate::{Aabb, Point, Scalar, Vector};
+
+/// An n-dimensional cubic Bézier curve
+///
+/// The dimensionality of the curve is defined by the const generic `D`
+/// parameter.
+///
+/// The curve is defined by four control points. It starts at the first and
+/// ends at the last one, while the two in between define its shape. The
+/// coordinate of a point on the curve is `0` at its start and `1` at its end.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct CubicBezier<const D: usize> {
+    control_points: [Point<D>; 4],
+}
+
+impl<const D: usize> CubicBezier<D> {
+    /// Construct a cubic Bézier curve from its control points
+    ///
+    /// # Panics
+    ///
+    /// Panics, if all control points are identical.
+    pub fn new(control_points: [impl Into<Point<D>>; 4]) -> Self {
+        let control_points = control_points.map(Into::into);
+
+        let [first, ..] = control_points;
+        assert!(
+            control_points.iter().any(|&point| point != first),
+            "Invalid Bézier curve; all control points are identical: {first:?}"
+        );
+
+        Self { control_points }
+    }
+
+    /// Access the control points of the curve
+    pub fn control_points(&self) -> [Point<D>; 4] {
+        self.control_points
+    }
+
+    /// Access the point where the curve starts
+    pub fn start(&self) -> Point<D> {
+        self.control_points[0]
+    }
+
+    /// Access the point where the curve ends
+    pub fn end(&self) -> Point<D> {
+        self.control_points[3]
+    }
+
+    /// Create a new instance that is reversed
+    ///
+    /// The reversed curve has the same shape, but a point with the coordinate
+    /// `t` on this curve, has the coordinate `1 - t` on the reversed one.
+    #[must_use]
+    pub fn reverse(mut self) -> Self {
+        self.control_points.reverse();
+        self
+    }
+
+    /// Convert a point in curve coordinates into a `D`-dimensional point
+    pub fn point_from_bezier_coords(
+        &self,
+        point: impl Into<Point<1>>,
+    ) -> Point<D> {
+        let t = point.into().t;
+
+        // De Casteljau's algorithm
+        let [a, b, c, d] = self.control_points;
+        let [ab, bc, cd] = [[a, b], [b, c], [c, d]].map(|[p, q]| lerp(p, q, t));
+        let [abc, bcd] = [[ab, bc], [bc, cd]].map(|[p, q]| lerp(p, q, t));
+
+        lerp(abc, bcd, t)
+    }
+
+    /// Convert a vector in curve coordinates into a `D`-dimensional vector
+    ///
+    /// The vector points from the start of the curve to the point that the
+    /// vector leads to, if it is applied to the start.
+    pub fn vector_from_bezier_coords(
+        &self,
+        vector: impl Into<Vector<1>>,
+    ) -> Vector<D> {
+        let t = vector.into().t;
+        self.point_from_bezier_coords([t]) - self.start()
+    }
+
+    /// Compute the derivative of the curve at the provided point
+    pub fn derivative(&self, point: impl Into<Point<1>>) -> Vector<D> {
+        let t = point.into().t;
+
+        // The derivative of a cubic Bézier curve is a quadratic Bézier curve.
+        let [a, b, c, d] = self.control_points;
+        let [ab, bc, cd] = [[a, b], [b, c], [c, d]].map(|[p, q]| (q - p) * 3.);
+        let [abc, bcd] = [[ab, bc], [bc, cd]].map(|[p, q]| p + (q - p) * t);
+
+        abc + (bcd - abc) * t
+    }
+
+    /// Compute the largest second difference of the control points
+    ///
+    /// This bounds the second derivative of the curve, and thereby how far the
+    /// curve can deviate from a straight line between two of its points.
+    pub fn max_second_difference(&self) -> Scalar {
+        let [a, b, c, d] = self.control_points;
+
+        [[a, b, c], [b, c, d]]
+            .map(|[p, q, r]| ((r - q) - (q - p)).magnitude())
+            .into_iter()
+            .fold(Scalar::ZERO, Scalar::max)
+    }
+
+    /// Calculate an AABB for the curve
+    ///
+    /// A Bézier curve is contained in the convex hull of its control points,
+    /// so this is the AABB of those.
+    pub fn aabb(&self) -> Aabb<D> {
+        let [first, rest @ ..] = self.control_points;
+
+        rest.into_iter().fold(
+            Aabb {
+                min: first,
+                max: first,
+            },
+            |aabb, point| {
+                let mut min = aabb.min;
+                let mut max = aabb.max;
+
+                for i in 0..D {
+                    let c = point.coords.components[i];
+                    min.coords.components[i] = min.coords.components[i].min(c);
+                    max.coords.components[i] = max.coords.components[i].max(c);
+                }
+
+                Aabb { min, max }
+            },
+        )
+    }
+}
+
+impl<const D: usize> approx::AbsDiffEq for CubicBezier<D> {
+    type Epsilon = <Scalar as approx::AbsDiffEq>::Epsilon;
+
+    fn default_epsilon() -> Self::Epsilon {
+        Scalar::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        self.control_points
+            .iter()
+            .zip(&other.control_points)
+            .all(|(a, b)| a.abs_diff_eq(b, epsilon))
+    }
+}
+
+fn lerp<const D: usize>(a: Point<D>, b: Point<D>, t: Scalar) -> Point<D> {
+    a + (b - a) * t
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Point, Scalar, Vector};
+
+    use super::CubicBezier;
+
+    #[test]
+    fn point_from_bezier_coords() {
+        let bezier = CubicBezier::new([[0., 0.], [0., 1.], [1., 1.], [1., 0.]]);
+
+        assert_eq!(bezier.point_from_bezier_coords([0.]), bezier.start());
+        assert_eq!(bezier.point_from_bezier_coords([1.]), bezier.end());
+        assert_eq!(
+            bezier.point_from_bezier_coords([0.5]),
+            Point::from([0.5, 0.75])
+        );
+
+        let reversed = bezier.reverse();
+        for t in [0., 0.25, 0.5, 0.75, 1.] {
+            let a = bezier.point_from_bezier_coords([t]);
+            let b = reversed.point_from_bezier_coords([1. - t]);
+            assert!(a.distance_to(&b) < Scalar::from(1e-15));
+        }
+    }
+
+    #[test]
+    fn derivative() {
+        let bezier = CubicBezier::new([[0., 0.], [0., 1.], [1., 1.], [1., 0.]]);
+
+        assert_eq!(bezier.derivative([0.]), Vector::from([0., 3.]));
+        assert_eq!(bezier.derivative([0.5]), Vector::from([1.5, 0.]));
+        assert_eq!(bezier.derivative([1.]), Vector::from([0., -3.]));
+    }
+}
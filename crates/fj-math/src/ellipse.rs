@@ -0,0 +1,186 @@
+use approx::AbsDiffEq;
+
+use crate::{Aabb, Point, Scalar, Vector};
+
+/// An n-dimensional ellipse
+///
+/// The dimensionality of the ellipse is defined by the const generic `D`
+/// parameter.
+///
+/// Ellipses use the same coordinate system as [`Circle`]s, so a point with
+/// the coordinate `t` is `center + a * cos(t) + b * sin(t)`. Unlike with a
+/// circle, `a` and `b` can be of different lengths.
+///
+/// [`Circle`]: crate::Circle
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Ellipse<const D: usize> {
+    center: Point<D>,
+    a: Vector<D>,
+    b: Vector<D>,
+}
+
+impl<const D: usize> Ellipse<D> {
+    /// Construct an ellipse
+    ///
+    /// # Panics
+    ///
+    /// Panics, if any of the following requirements are not met:
+    ///
+    /// - Neither `a` nor `b` must be zero.
+    /// - `a` and `b` must be perpendicular to each other.
+    pub fn new(
+        center: impl Into<Point<D>>,
+        a: impl Into<Vector<D>>,
+        b: impl Into<Vector<D>>,
+    ) -> Self {
+        let center = center.into();
+        let a = a.into();
+        let b = b.into();
+
+        assert_ne!(a.magnitude(), Scalar::ZERO, "`a` must not be zero");
+        assert_ne!(b.magnitude(), Scalar::ZERO, "`b` must not be zero");
+        // See `Circle::new` on why this isn't checked precisely.
+        assert!(
+            a.dot(&b).abs() < Scalar::default_epsilon(),
+            "`a` and `b` must be perpendicular to each other"
+        );
+
+        Self { center, a, b }
+    }
+
+    /// Access the center point of the ellipse
+    pub fn center(&self) -> Point<D> {
+        self.center
+    }
+
+    /// Access the vector that defines the starting point of the ellipse
+    ///
+    /// The point where this vector points from the ellipse center, is the
+    /// zero coordinate of the ellipse's coordinate system. The length of the
+    /// vector is one of the ellipse's semi-axes.
+    pub fn a(&self) -> Vector<D> {
+        self.a
+    }
+
+    /// Access the vector that defines the plane of the ellipse
+    ///
+    /// Also defines the direction of the ellipse's coordinate system. The
+    /// length of the vector is the other semi-axis of the ellipse, and this
+    /// vector is perpendicular to [`Self::a`].
+    pub fn b(&self) -> Vector<D> {
+        self.b
+    }
+
+    /// Access the length of the longer semi-axis of the ellipse
+    pub fn max_radius(&self) -> Scalar {
+        self.a.magnitude().max(self.b.magnitude())
+    }
+
+    /// Create a new instance that is reversed
+    #[must_use]
+    pub fn reverse(mut self) -> Self {
+        self.b = -self.b;
+        self
+    }
+
+    /// Convert a `D`-dimensional point to ellipse coordinates
+    ///
+    /// Converts the provided point into ellipse coordinates between `0.`
+    /// (inclusive) and `PI * 2.` (exclusive).
+    ///
+    /// Like [`Circle::point_to_circle_coords`], this doesn't check whether the
+    /// point is actually on the ellipse.
+    ///
+    /// [`Circle::point_to_circle_coords`]: crate::Circle::point_to_circle_coords
+    pub fn point_to_ellipse_coords(
+        &self,
+        point: impl Into<Point<D>>,
+    ) -> Point<1> {
+        let vector = point.into() - self.center;
+
+        let [cos, sin] =
+            [self.a, self.b].map(|axis| vector.dot(&axis) / axis.dot(&axis));
+        let atan = Scalar::atan2(sin, cos);
+        let coord = if atan >= Scalar::ZERO {
+            atan
+        } else {
+            atan + Scalar::TAU
+        };
+        Point::from([coord])
+    }
+
+    /// Convert a point in ellipse coordinates into a `D`-dimensional point
+    pub fn point_from_ellipse_coords(
+        &self,
+        point: impl Into<Point<1>>,
+    ) -> Point<D> {
+        self.center + self.vector_from_ellipse_coords(point.into().coords)
+    }
+
+    /// Convert a vector in ellipse coordinates into a `D`-dimensional point
+    pub fn vector_from_ellipse_coords(
+        &self,
+        vector: impl Into<Vector<1>>,
+    ) -> Vector<D> {
+        let angle = vector.into().t;
+        let (sin, cos) = angle.sin_cos();
+
+        self.a * cos + self.b * sin
+    }
+
+    /// Calculate an AABB for the ellipse
+    pub fn aabb(&self) -> Aabb<D> {
+        let center_to_min_max = Vector::from_component(self.max_radius());
+
+        Aabb {
+            min: self.center() - center_to_min_max,
+            max: self.center() + center_to_min_max,
+        }
+    }
+}
+
+impl<const D: usize> approx::AbsDiffEq for Ellipse<D> {
+    type Epsilon = <Scalar as approx::AbsDiffEq>::Epsilon;
+
+    fn default_epsilon() -> Self::Epsilon {
+        Scalar::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        self.center.abs_diff_eq(&other.center, epsilon)
+            && self.a.abs_diff_eq(&other.a, epsilon)
+            && self.b.abs_diff_eq(&other.b, epsilon)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    use crate::{Point, Scalar};
+
+    use super::Ellipse;
+
+    #[test]
+    fn ellipse_coords() {
+        let ellipse = Ellipse::new([1., 2.], [2., 0.], [0., 1.]);
+
+        for (point, coord) in [
+            ([3., 2.], 0.),
+            ([1., 3.], FRAC_PI_2),
+            ([-1., 2.], PI),
+            ([1., 1.], FRAC_PI_2 * 3.),
+        ] {
+            assert_eq!(
+                ellipse.point_to_ellipse_coords(point),
+                Point::from([coord])
+            );
+            assert!(
+                ellipse
+                    .point_from_ellipse_coords([coord])
+                    .distance_to(&Point::from(point))
+                    < Scalar::from(1e-15)
+            );
+        }
+    }
+}
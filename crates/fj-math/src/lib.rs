@@ -35,10 +35,14 @@ mod aabb;
 mod arc;
 mod circle;
 mod coordinates;
+mod cubic_bezier;
+mod ellipse;
 mod line;
+mod nurbs_surface;
 mod plane;
 mod point;
 mod poly_chain;
+mod ray;
 mod scalar;
 mod segment;
 mod transform;
@@ -50,10 +54,14 @@ pub use self::{
     arc::Arc,
     circle::Circle,
     coordinates::{Uv, Xyz, T},
+    cubic_bezier::CubicBezier,
+    ellipse::Ellipse,
     line::Line,
+    nurbs_surface::NurbsSurface,
     plane::Plane,
     point::Point,
     poly_chain::PolyChain,
+    ray::Ray,
     scalar::{Scalar, Sign},
     segment::Segment,
     transform::Transform,
@@ -0,0 +1,449 @@
+use crate::{Aabb, Point, Scalar, Vector};
+
+/// A NURBS (non-uniform rational B-spline) surface
+///
+/// The surface is defined by a grid of control points, each of which has a
+/// weight, a degree in each direction, and a knot vector for each direction.
+/// Its parameters are referred to as `u` and `v`, where `u` runs along the
+/// outer dimension of the control point grid, and `v` along the inner one.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct NurbsSurface {
+    /// The control points, stored row by row, each row running along `v`
+    control_points: Vec<Point<3>>,
+    weights: Vec<Scalar>,
+    num_control_points: [usize; 2],
+    degrees: [usize; 2],
+    knots: [Vec<Scalar>; 2],
+}
+
+impl NurbsSurface {
+    /// Construct a NURBS surface
+    ///
+    /// `control_points` and `weights` are grids of the same shape. Each row of
+    /// those grids contains the control points (or weights) for one `u`, along
+    /// `v`.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if any of the following requirements are not met:
+    ///
+    /// - Both degrees must be at least one.
+    /// - The grids must have at least `degree + 1` rows and columns, and all
+    ///   rows must have the same length.
+    /// - All weights must be positive.
+    /// - Each knot vector must be non-decreasing, and contain exactly
+    ///   `num_control_points + degree + 1` knots.
+    pub fn new(
+        control_points: impl IntoIterator<
+            Item = impl IntoIterator<Item = impl Into<Point<3>>>,
+        >,
+        weights: impl IntoIterator<
+            Item = impl IntoIterator<Item = impl Into<Scalar>>,
+        >,
+        degrees: [usize; 2],
+        knots: [Vec<Scalar>; 2],
+    ) -> Self {
+        let (control_points, num_control_points) = flatten(control_points);
+        let (weights, num_weights) = flatten(weights);
+
+        assert!(
+            degrees.iter().all(|&degree| degree >= 1),
+            "Degrees must be at least one"
+        );
+        assert_eq!(
+            num_control_points, num_weights,
+            "Must provide a weight for each control point"
+        );
+        for (num, degree) in num_control_points.into_iter().zip(degrees) {
+            assert!(num > degree, "Not enough control points for degree");
+        }
+        assert!(
+            weights.iter().all(|&weight| weight > Scalar::ZERO),
+            "Weights must be positive"
+        );
+        for ((knots, num), degree) in
+            knots.iter().zip(num_control_points).zip(degrees)
+        {
+            assert_eq!(
+                knots.len(),
+                num + degree + 1,
+                "Unexpected number of knots"
+            );
+            assert!(
+                knots.windows(2).all(|pair| pair[0] <= pair[1]),
+                "Knots must be non-decreasing"
+            );
+        }
+
+        Self {
+            control_points,
+            weights,
+            num_control_points,
+            degrees,
+            knots,
+        }
+    }
+
+    /// Construct a non-rational NURBS surface with clamped, uniform knots
+    ///
+    /// The surface passes through the control points at its corners, and its
+    /// parameters range from `0` to `1` in both directions.
+    ///
+    /// See [`NurbsSurface::new`] for the requirements on the arguments.
+    pub fn clamped(
+        control_points: impl IntoIterator<
+            Item = impl IntoIterator<Item = impl Into<Point<3>>>,
+        >,
+        degrees: [usize; 2],
+    ) -> Self {
+        let (control_points, [num_u, num_v]) = flatten(control_points);
+        let rows = control_points
+            .chunks(num_v.max(1))
+            .map(<[_]>::to_vec)
+            .collect::<Vec<_>>();
+        let weights = vec![vec![Scalar::ONE; num_v]; num_u];
+        let knots =
+            [0, 1].map(|i| clamped_knots([num_u, num_v][i], degrees[i]));
+
+        Self::new(rows, weights, degrees, knots)
+    }
+
+    /// Access the degrees of the surface, along `u` and `v`
+    pub fn degrees(&self) -> [usize; 2] {
+        self.degrees
+    }
+
+    /// Access the range of parameters that defines the surface
+    ///
+    /// Returns the minimum and maximum parameter, for `u` and `v`.
+    pub fn domain(&self) -> [[Scalar; 2]; 2] {
+        [0, 1].map(|i| {
+            let knots = &self.knots[i];
+            let degree = self.degrees[i];
+            let num = self.num_control_points[i];
+
+            [knots[degree], knots[num]]
+        })
+    }
+
+    /// Access the control points of the surface
+    ///
+    /// The control points are returned row by row, each row running along
+    /// `v`.
+    pub fn control_points(&self) -> &[Point<3>] {
+        &self.control_points
+    }
+
+    /// Create a new instance, with each control point replaced
+    pub fn map_control_points(
+        &self,
+        f: impl FnMut(&Point<3>) -> Point<3>,
+    ) -> Self {
+        Self {
+            control_points: self.control_points.iter().map(f).collect(),
+            ..self.clone()
+        }
+    }
+
+    /// Convert a point in surface parameters into a 3-dimensional point
+    ///
+    /// Parameters outside of the [domain] are clamped into it.
+    ///
+    /// [domain]: Self::domain
+    pub fn point_from_surface_coords(
+        &self,
+        point: impl Into<Point<2>>,
+    ) -> Point<3> {
+        let [point, _, _] = self.evaluate(point.into());
+        point
+    }
+
+    /// Compute the partial derivatives of the surface, along `u` and `v`
+    ///
+    /// Parameters outside of the [domain] are clamped into it.
+    ///
+    /// [domain]: Self::domain
+    pub fn derivatives(&self, point: impl Into<Point<2>>) -> [Vector<3>; 2] {
+        let [_, du, dv] = self.evaluate(point.into());
+        [du.coords, dv.coords]
+    }
+
+    /// Compute the normal of the surface
+    ///
+    /// The normal has unit length, and points into the direction of the cross
+    /// product of the partial derivatives along `u` and `v`.
+    pub fn normal(&self, point: impl Into<Point<2>>) -> Vector<3> {
+        let [du, dv] = self.derivatives(point);
+        du.cross(&dv).normalize()
+    }
+
+    /// Calculate an AABB for the surface
+    ///
+    /// A NURBS surface is contained in the convex hull of its control points,
+    /// so this is the AABB of those.
+    pub fn aabb(&self) -> Aabb<3> {
+        Aabb::<3>::from_points(self.control_points.iter().copied())
+    }
+
+    /// Evaluate the surface and its derivatives
+    ///
+    /// To avoid a separate return type, the derivatives are returned as points
+    /// whose coordinates are the components of the derivative vectors.
+    fn evaluate(&self, point: Point<2>) -> [Point<3>; 3] {
+        let [(span_u, [n_u, dn_u]), (span_v, [n_v, dn_v])] = [0, 1].map(|i| {
+            let [min, max] = self.domain()[i];
+            let t = point.coords.components[i].max(min).min(max);
+
+            let knots = self.knots[i]
+                .iter()
+                .map(|knot| knot.into_f64())
+                .collect::<Vec<_>>();
+            let span = find_span(
+                &knots,
+                self.degrees[i],
+                self.num_control_points[i],
+                t.into_f64(),
+            );
+            let basis =
+                basis_functions(&knots, self.degrees[i], span, t.into_f64());
+
+            (span, basis)
+        });
+        let [degree_u, degree_v] = self.degrees;
+        let num_v = self.num_control_points[1];
+
+        // Sums of the weighted control points (`a`) and the weights (`w`),
+        // multiplied by the basis functions, and their derivatives.
+        let mut a = [[0.; 3]; 3];
+        let mut w = [0.; 3];
+
+        for (i, (n_u, dn_u)) in n_u.iter().zip(&dn_u).enumerate() {
+            for (j, (n_v, dn_v)) in n_v.iter().zip(&dn_v).enumerate() {
+                let index =
+                    (span_u - degree_u + i) * num_v + (span_v - degree_v + j);
+                let weight = self.weights[index].into_f64();
+                let control_point = self.control_points[index];
+
+                for (k, factor) in
+                    [n_u * n_v, dn_u * n_v, n_u * dn_v].into_iter().enumerate()
+                {
+                    let factor = factor * weight;
+
+                    for (a, c) in
+                        a[k].iter_mut().zip(control_point.coords.components)
+                    {
+                        *a += factor * c.into_f64();
+                    }
+                    w[k] += factor;
+                }
+            }
+        }
+
+        let point = a[0].map(|a| a / w[0]);
+        let derivative = |k: usize| {
+            let mut derivative = [0.; 3];
+            for (c, d) in derivative.iter_mut().enumerate() {
+                *d = (a[k][c] - w[k] * point[c]) / w[0];
+            }
+            derivative
+        };
+        let [du, dv] = [1, 2].map(derivative);
+
+        [point, du, dv].map(Point::from)
+    }
+}
+
+fn flatten<T>(
+    rows: impl IntoIterator<Item = impl IntoIterator<Item = impl Into<T>>>,
+) -> (Vec<T>, [usize; 2]) {
+    let mut elements = Vec::new();
+    let mut num_rows = 0;
+    let mut row_length = None;
+
+    for row in rows {
+        let len_before = elements.len();
+        elements.extend(row.into_iter().map(Into::into));
+        let len = elements.len() - len_before;
+
+        assert!(
+            row_length.is_none() || row_length == Some(len),
+            "All rows must have the same length"
+        );
+        row_length = Some(len);
+        num_rows += 1;
+    }
+
+    (elements, [num_rows, row_length.unwrap_or(0)])
+}
+
+fn clamped_knots(num_control_points: usize, degree: usize) -> Vec<Scalar> {
+    let num_spans = num_control_points.saturating_sub(degree).max(1);
+
+    let mut knots = vec![Scalar::ZERO; degree + 1];
+    knots.extend(
+        (1..num_spans).map(|i| Scalar::from(i as f64 / num_spans as f64)),
+    );
+    knots.extend(vec![Scalar::ONE; degree + 1]);
+
+    knots
+}
+
+/// Find the index of the knot span that contains `t`
+fn find_span(
+    knots: &[f64],
+    degree: usize,
+    num_control_points: usize,
+    t: f64,
+) -> usize {
+    let n = num_control_points - 1;
+
+    if t >= knots[n + 1] {
+        return n;
+    }
+    if t <= knots[degree] {
+        return degree;
+    }
+
+    let mut low = degree;
+    let mut high = n + 1;
+    let mut mid = (low + high) / 2;
+    while t < knots[mid] || t >= knots[mid + 1] {
+        if t < knots[mid] {
+            high = mid;
+        } else {
+            low = mid;
+        }
+        mid = (low + high) / 2;
+    }
+
+    mid
+}
+
+/// Compute the non-zero basis functions and their first derivatives
+///
+/// This is algorithm A2.3 from "The NURBS Book" by Piegl and Tiller, limited
+/// to the first derivative.
+fn basis_functions(
+    knots: &[f64],
+    degree: usize,
+    span: usize,
+    t: f64,
+) -> [Vec<f64>; 2] {
+    let p = degree;
+
+    let mut ndu = vec![vec![0.; p + 1]; p + 1];
+    let mut left = vec![0.; p + 1];
+    let mut right = vec![0.; p + 1];
+
+    ndu[0][0] = 1.;
+    for j in 1..=p {
+        left[j] = t - knots[span + 1 - j];
+        right[j] = knots[span + j] - t;
+
+        let mut saved = 0.;
+        for r in 0..j {
+            ndu[j][r] = right[r + 1] + left[j - r];
+            let temp = ndu[r][j - 1] / ndu[j][r];
+
+            ndu[r][j] = saved + right[r + 1] * temp;
+            saved = left[j - r] * temp;
+        }
+        ndu[j][j] = saved;
+    }
+
+    let values = (0..=p).map(|j| ndu[j][p]).collect();
+    let derivatives = (0..=p)
+        .map(|r| {
+            let mut derivative = 0.;
+            if r >= 1 {
+                derivative += ndu[r - 1][p - 1] / ndu[p][r - 1];
+            }
+            if r < p {
+                derivative -= ndu[r][p - 1] / ndu[p][r];
+            }
+            derivative * p as f64
+        })
+        .collect();
+
+    [values, derivatives]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::FRAC_1_SQRT_2;
+
+    use crate::{Point, Scalar, Vector};
+
+    use super::NurbsSurface;
+
+    #[test]
+    fn bilinear() {
+        let surface = NurbsSurface::clamped(
+            [[[0., 0., 0.], [0., 1., 0.]], [[1., 0., 0.], [1., 1., 1.]]],
+            [1, 1],
+        );
+
+        assert_eq!(
+            surface.point_from_surface_coords([0.5, 0.5]),
+            Point::from([0.5, 0.5, 0.25])
+        );
+        assert_eq!(
+            surface.derivatives([0.5, 0.5]),
+            [Vector::from([1., 0., 0.5]), Vector::from([0., 1., 0.5])]
+        );
+    }
+
+    #[test]
+    fn cylinder() {
+        // A quarter of a cylinder with radius 1 around the z-axis. Only a
+        // rational surface can represent this exactly.
+        let w = FRAC_1_SQRT_2;
+        let surface = NurbsSurface::new(
+            [
+                [[1., 0., 0.], [1., 0., 1.]],
+                [[1., 1., 0.], [1., 1., 1.]],
+                [[0., 1., 0.], [0., 1., 1.]],
+            ],
+            [[1., 1.], [w, w], [1., 1.]],
+            [2, 1],
+            [
+                [0., 0., 0., 1., 1., 1.].map(Scalar::from).to_vec(),
+                [0., 0., 1., 1.].map(Scalar::from).to_vec(),
+            ],
+        );
+
+        for i in 0..=10 {
+            let u = f64::from(i) / 10.;
+            let point = surface.point_from_surface_coords([u, u]);
+
+            let radius = Vector::from([point.x, point.y, Scalar::ZERO]);
+            assert!((radius.magnitude() - 1.).abs() < Scalar::from(1e-12));
+            assert!((point.z - u).abs() < Scalar::from(1e-12));
+
+            // The normal of a cylinder points away from its axis.
+            let normal = surface.normal([u, u]);
+            assert!((normal - radius).magnitude() < Scalar::from(1e-12));
+
+            // Compare derivatives to finite differences.
+            let h = 1e-6;
+            let [du, dv] = surface.derivatives([u, u]);
+            for (derivative, [a, b]) in [
+                (du, [[u - h, u], [u + h, u]]),
+                (dv, [[u, u - h], [u, u + h]]),
+            ] {
+                let [a, b] = [a, b]
+                    .map(|point| surface.point_from_surface_coords(point));
+                let approx = (b - a) / (2. * h);
+
+                // Differences at the edge of the domain are one-sided, due
+                // to clamping.
+                let approx = if u == 0. || u == 1. {
+                    approx * 2.
+                } else {
+                    approx
+                };
+                assert!((derivative - approx).magnitude() < Scalar::from(1e-4));
+            }
+        }
+    }
+}
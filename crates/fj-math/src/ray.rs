@@ -0,0 +1,76 @@
+use crate::{Point, Scalar, Vector};
+
+/// An n-dimensional ray, defined by an origin and a direction
+///
+/// A ray starts at its origin, and extends infinitely in its direction. The
+/// direction is always normalized, so positions along the ray are distances
+/// from its origin.
+///
+/// The dimensionality of the ray is defined by the const generic `D`
+/// parameter.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[repr(C)]
+pub struct Ray<const D: usize> {
+    origin: Point<D>,
+    direction: Vector<D>,
+}
+
+impl<const D: usize> Ray<D> {
+    /// Create a ray from a point and a vector
+    ///
+    /// The direction is normalized.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `direction` has a length of zero.
+    pub fn from_origin_and_direction(
+        origin: impl Into<Point<D>>,
+        direction: impl Into<Vector<D>>,
+    ) -> Self {
+        let origin = origin.into();
+        let direction = direction.into();
+
+        assert!(
+            direction.magnitude() != Scalar::ZERO,
+            "Can't construct `Ray`. Direction is zero: {direction:?}"
+        );
+
+        Self {
+            origin,
+            direction: direction.normalize(),
+        }
+    }
+
+    /// Access the origin of the ray
+    pub fn origin(&self) -> Point<D> {
+        self.origin
+    }
+
+    /// Access the direction of the ray
+    ///
+    /// The direction is normalized.
+    pub fn direction(&self) -> Vector<D> {
+        self.direction
+    }
+
+    /// Compute the point on the ray, at the given distance from its origin
+    pub fn point_at(&self, distance: impl Into<Scalar>) -> Point<D> {
+        self.origin + self.direction * distance.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Point, Scalar, Vector};
+
+    use super::Ray;
+
+    #[test]
+    fn point_at() {
+        let ray = Ray::from_origin_and_direction([1., 2., 3.], [0., 0., 2.]);
+
+        assert_eq!(ray.direction(), Vector::from([0., 0., 1.]));
+        assert_eq!(ray.point_at(Scalar::ZERO), ray.origin());
+        assert_eq!(ray.point_at(2.), Point::from([1., 2., 5.]));
+    }
+}
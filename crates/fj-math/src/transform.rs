@@ -2,7 +2,7 @@ use std::ops;
 
 use nalgebra::Perspective3;
 
-use crate::{Circle, Line, Scalar};
+use crate::{Circle, CubicBezier, Ellipse, Line, NurbsSurface, Scalar};
 
 use super::{Aabb, Point, Segment, Triangle, Vector};
 
@@ -40,6 +40,57 @@ impl Transform {
         ))
     }
 
+    /// Construct a view transform
+    ///
+    /// The transform moves `eye` to the origin, and `target` onto the negative
+    /// z-axis. `up` ends up pointing towards positive y.
+    pub fn look_at(
+        eye: impl Into<Point<3>>,
+        target: impl Into<Point<3>>,
+        up: impl Into<Vector<3>>,
+    ) -> Self {
+        let isometry = nalgebra::Isometry3::look_at_rh(
+            &eye.into().to_na(),
+            &target.into().to_na(),
+            &up.into().to_na(),
+        );
+
+        Self(nalgebra::Transform::from_matrix_unchecked(
+            isometry.to_homogeneous(),
+        ))
+    }
+
+    /// Construct a reflection across a plane
+    ///
+    /// The plane is defined by a point on it, and its normal.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `normal` is zero.
+    pub fn reflection(
+        origin: impl Into<Point<3>>,
+        normal: impl Into<Vector<3>>,
+    ) -> Self {
+        let origin = origin.into();
+        let normal = normal.into();
+
+        assert_ne!(
+            normal.magnitude(),
+            Scalar::ZERO,
+            "normal of reflection plane must not be zero"
+        );
+        let normal = normal.normalize();
+
+        let reflection = nalgebra::Matrix3::identity()
+            - normal.to_na() * normal.to_na().transpose() * 2.;
+        let offset = normal * normal.dot(&origin.coords) * 2.;
+
+        Self::translation(offset)
+            * Self(nalgebra::Transform::from_matrix_unchecked(
+                reflection.to_homogeneous(),
+            ))
+    }
+
     /// Construct a scaling
     pub fn scale(scaling_factor: f64) -> Self {
         Self(nalgebra::Transform::from_matrix_unchecked(
@@ -95,6 +146,35 @@ impl Transform {
         )
     }
 
+    /// Transform the given ellipse
+    pub fn transform_ellipse(&self, ellipse: &Ellipse<3>) -> Ellipse<3> {
+        Ellipse::new(
+            self.transform_point(&ellipse.center()),
+            self.transform_vector(&ellipse.a()),
+            self.transform_vector(&ellipse.b()),
+        )
+    }
+
+    /// Transform the given cubic Bézier curve
+    pub fn transform_cubic_bezier(
+        &self,
+        bezier: &CubicBezier<3>,
+    ) -> CubicBezier<3> {
+        CubicBezier::new(
+            bezier
+                .control_points()
+                .map(|point| self.transform_point(&point)),
+        )
+    }
+
+    /// Transform the given NURBS surface
+    pub fn transform_nurbs_surface(
+        &self,
+        surface: &NurbsSurface,
+    ) -> NurbsSurface {
+        surface.map_control_points(|point| self.transform_point(point))
+    }
+
     /// Inverse transform
     pub fn inverse(&self) -> Self {
         Self(self.0.inverse())
@@ -132,11 +212,15 @@ impl Transform {
     }
 
     /// Transform the given axis-aligned bounding box
+    ///
+    /// The result is the axis-aligned bounding box that contains the
+    /// transformed box, which is larger than it, if the transform rotates.
     pub fn transform_aabb(&self, aabb: &Aabb<3>) -> Aabb<3> {
-        Aabb {
-            min: self.transform_point(&aabb.min),
-            max: self.transform_point(&aabb.max),
-        }
+        Aabb::<3>::from_points(
+            aabb.vertices()
+                .into_iter()
+                .map(|vertex| self.transform_point(&vertex)),
+        )
     }
 
     /// Exposes the data of this Transform as a slice of f64.
@@ -144,6 +228,20 @@ impl Transform {
         self.0.matrix().data.as_slice()
     }
 
+    /// Convert the data of this transform to `f32`, in column-major order
+    ///
+    /// This is lossy, and is meant for passing transforms to code that works
+    /// with `f32` (like graphics code) explicitly.
+    pub fn to_f32_array(&self) -> [f32; 16] {
+        let mut array = [0.; 16];
+
+        for (f32, &f64) in array.iter_mut().zip(self.data()) {
+            *f32 = f64 as f32;
+        }
+
+        array
+    }
+
     /// Extract the rotation component of this transform
     pub fn extract_rotation(&self) -> Self {
         Self(nalgebra::Transform::from_matrix_unchecked(
@@ -224,4 +322,52 @@ mod tests {
             epsilon = 1e-8,
         );
     }
+
+    #[test]
+    fn look_at() {
+        let transform =
+            Transform::look_at([1., 0., 0.], [1., 2., 0.], [0., 0., 1.]);
+
+        assert_abs_diff_eq!(
+            transform.transform_point(&Point::from([1., 2., 0.])),
+            Point::from([0., 0., -2.]),
+            epsilon = Scalar::from(1e-8),
+        );
+        assert_abs_diff_eq!(
+            transform.transform_vector(&Vector::from([0., 0., 1.])),
+            Vector::from([0., 1., 0.]),
+            epsilon = Scalar::from(1e-8),
+        );
+    }
+
+    #[test]
+    fn reflection() {
+        let transform = Transform::reflection([1., 1., 0.], [1., 1., 0.]);
+
+        assert_abs_diff_eq!(
+            transform.transform_point(&Point::from([0., 0., 1.])),
+            Point::from([2., 2., 1.]),
+            epsilon = Scalar::from(1e-8),
+        );
+        assert_abs_diff_eq!(
+            transform.transform_vector(&Vector::from([1., -1., 1.])),
+            Vector::from([1., -1., 1.]),
+            epsilon = Scalar::from(1e-8),
+        );
+    }
+
+    #[test]
+    fn to_f32_array() {
+        let transform = Transform::translation([1., 2., 3.]);
+
+        assert_eq!(
+            transform.to_f32_array(),
+            [
+                1., 0., 0., 0., //
+                0., 1., 0., 0., //
+                0., 0., 1., 0., //
+                1., 2., 3., 1., //
+            ]
+        );
+    }
 }
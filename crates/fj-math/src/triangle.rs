@@ -1,4 +1,7 @@
-use parry3d_f64::query::{Ray, RayCast as _};
+use parry3d_f64::{
+    math::Isometry,
+    query::{PointQuery as _, Ray, RayCast as _},
+};
 
 use crate::Vector;
 
@@ -102,6 +105,27 @@ impl Triangle<3> {
             .map(Into::into)
     }
 
+    /// Compute the distance between the triangle and a point
+    pub fn distance_to_point(&self, point: impl Into<Point<3>>) -> Scalar {
+        self.to_parry()
+            .distance_to_local_point(&point.into().to_na(), true)
+            .into()
+    }
+
+    /// Compute the distance between this triangle and another one
+    ///
+    /// Returns zero, if the triangles touch or intersect.
+    pub fn distance_to_triangle(&self, other: &Self) -> Scalar {
+        parry3d_f64::query::distance(
+            &Isometry::identity(),
+            &self.to_parry(),
+            &Isometry::identity(),
+            &other.to_parry(),
+        )
+        .expect("distance between triangles is supported")
+        .into()
+    }
+
     /// Compute the triangle's normal
     pub fn normal(&self) -> Vector<3> {
         self.to_parry()
@@ -151,7 +175,7 @@ impl Winding {
 
 #[cfg(test)]
 mod tests {
-    use crate::{Point, Vector};
+    use crate::{Point, Scalar, Vector};
 
     use super::Triangle;
 
@@ -189,6 +213,35 @@ mod tests {
         let _triangle = Triangle::from([a, b, c]);
     }
 
+    #[test]
+    fn distance_to_point() {
+        let triangle =
+            Triangle::from([[0.0, 0.0, 0.0], [2.0, 0.0, 0.0], [0.0, 2.0, 0.0]]);
+
+        assert_eq!(triangle.distance_to_point([0.5, 0.5, 0.0]), Scalar::ZERO);
+        assert_eq!(
+            triangle.distance_to_point([0.5, 0.5, 3.0]),
+            Scalar::from(3.0)
+        );
+        assert_eq!(triangle.distance_to_point([-1.0, 0.0, 0.0]), Scalar::ONE);
+    }
+
+    #[test]
+    fn distance_to_triangle() {
+        let a =
+            Triangle::from([[0.0, 0.0, 0.0], [2.0, 0.0, 0.0], [0.0, 2.0, 0.0]]);
+        let b =
+            Triangle::from([[0.5, 0.5, 1.0], [2.0, 2.0, 1.0], [0.5, 2.0, 3.0]]);
+        let c = Triangle::from([
+            [0.5, 0.5, -1.0],
+            [0.5, 0.5, 1.0],
+            [2.0, 2.0, 0.0],
+        ]);
+
+        assert_eq!(a.distance_to_triangle(&b), Scalar::ONE);
+        assert_eq!(a.distance_to_triangle(&c), Scalar::ZERO);
+    }
+
     #[test]
     fn normal() {
         let triangle =
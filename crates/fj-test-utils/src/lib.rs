@@ -0,0 +1,206 @@
+//! # Fornjot Test Utilities
+//!
+//! [Fornjot] is an early-stage b-rep CAD kernel written in Rust. The kernel is
+//! split into multiple libraries that can be used semi-independently, and this
+//! is one of those.
+//!
+//! This library provides utilities for testing code that creates geometry.
+//! Geometry is computed using floating-point numbers, so comparing it exactly
+//! is fragile. The comparisons provided here accept differences up to a
+//! tolerance instead.
+//!
+//! The utilities only work with the types from `fj-math` and `fj-interop`.
+//! They don't depend on the kernel, so the kernel's own tests can use them.
+//! Shapes are compared by triangulating them first.
+//!
+//! Each comparison function has a corresponding assertion macro. Like the
+//! assertion macros from the standard library, those accept an optional
+//! message, with format arguments.
+//!
+//! [Fornjot]: https://www.fornjot.app/
+
+mod mesh;
+mod mismatch;
+mod points;
+mod scalars;
+mod shape;
+
+pub use self::{
+    mesh::{check_watertight, compare_meshes, compare_volume, find_open_edge},
+    mismatch::Mismatch,
+    points::compare_points,
+    scalars::compare_scalars,
+    shape::{compare_shapes, shape_deviation},
+};
+
+/// Panic with a failed comparison
+///
+/// Used by the assertion macros. Not part of the public API.
+#[doc(hidden)]
+#[track_caller]
+pub fn fail(
+    assertion: &str,
+    message: Option<std::fmt::Arguments>,
+    mismatch: Mismatch,
+) -> ! {
+    match message {
+        Some(message) => {
+            panic!("assertion failed: `{assertion}`: {message}\n{mismatch}")
+        }
+        None => panic!("assertion failed: `{assertion}`\n{mismatch}"),
+    }
+}
+
+/// Assert that two scalars are within the provided tolerance of each other
+///
+/// See [`compare_scalars`].
+#[macro_export]
+macro_rules! assert_scalars_eq {
+    ($a:expr, $b:expr, $tolerance:expr $(,)?) => {
+        if let Err(mismatch) = $crate::compare_scalars($a, $b, $tolerance) {
+            $crate::fail(
+                concat!(stringify!($a), " ≈ ", stringify!($b)),
+                None,
+                mismatch,
+            );
+        }
+    };
+    ($a:expr, $b:expr, $tolerance:expr, $($arg:tt)+) => {
+        if let Err(mismatch) = $crate::compare_scalars($a, $b, $tolerance) {
+            $crate::fail(
+                concat!(stringify!($a), " ≈ ", stringify!($b)),
+                Some(format_args!($($arg)+)),
+                mismatch,
+            );
+        }
+    };
+}
+
+/// Assert that two points are within the provided tolerance of each other
+///
+/// See [`compare_points`].
+#[macro_export]
+macro_rules! assert_points_eq {
+    ($a:expr, $b:expr, $tolerance:expr $(,)?) => {
+        if let Err(mismatch) = $crate::compare_points($a, $b, $tolerance) {
+            $crate::fail(
+                concat!(stringify!($a), " ≈ ", stringify!($b)),
+                None,
+                mismatch,
+            );
+        }
+    };
+    ($a:expr, $b:expr, $tolerance:expr, $($arg:tt)+) => {
+        if let Err(mismatch) = $crate::compare_points($a, $b, $tolerance) {
+            $crate::fail(
+                concat!(stringify!($a), " ≈ ", stringify!($b)),
+                Some(format_args!($($arg)+)),
+                mismatch,
+            );
+        }
+    };
+}
+
+/// Assert that two meshes are equal, within the provided tolerance
+///
+/// See [`compare_meshes`].
+#[macro_export]
+macro_rules! assert_mesh_eq {
+    ($a:expr, $b:expr, $tolerance:expr $(,)?) => {
+        if let Err(mismatch) = $crate::compare_meshes(&$a, &$b, $tolerance) {
+            $crate::fail(
+                concat!(stringify!($a), " ≈ ", stringify!($b)),
+                None,
+                mismatch,
+            );
+        }
+    };
+    ($a:expr, $b:expr, $tolerance:expr, $($arg:tt)+) => {
+        if let Err(mismatch) = $crate::compare_meshes(&$a, &$b, $tolerance) {
+            $crate::fail(
+                concat!(stringify!($a), " ≈ ", stringify!($b)),
+                Some(format_args!($($arg)+)),
+                mismatch,
+            );
+        }
+    };
+}
+
+/// Assert that two triangulated shapes are equal, within the provided tolerance
+///
+/// See [`compare_shapes`].
+#[macro_export]
+macro_rules! assert_shapes_eq {
+    ($a:expr, $b:expr, $tolerance:expr $(,)?) => {
+        if let Err(mismatch) = $crate::compare_shapes(&$a, &$b, $tolerance) {
+            $crate::fail(
+                concat!(stringify!($a), " ≈ ", stringify!($b)),
+                None,
+                mismatch,
+            );
+        }
+    };
+    ($a:expr, $b:expr, $tolerance:expr, $($arg:tt)+) => {
+        if let Err(mismatch) = $crate::compare_shapes(&$a, &$b, $tolerance) {
+            $crate::fail(
+                concat!(stringify!($a), " ≈ ", stringify!($b)),
+                Some(format_args!($($arg)+)),
+                mismatch,
+            );
+        }
+    };
+}
+
+/// Assert that a mesh encloses the expected volume, within the tolerance
+///
+/// See [`compare_volume`].
+#[macro_export]
+macro_rules! assert_volume_eq {
+    ($mesh:expr, $expected:expr, $tolerance:expr $(,)?) => {
+        if let Err(mismatch) =
+            $crate::compare_volume(&$mesh, $expected, $tolerance)
+        {
+            $crate::fail(
+                concat!("volume of ", stringify!($mesh), " ≈ ", stringify!($expected)),
+                None,
+                mismatch,
+            );
+        }
+    };
+    ($mesh:expr, $expected:expr, $tolerance:expr, $($arg:tt)+) => {
+        if let Err(mismatch) =
+            $crate::compare_volume(&$mesh, $expected, $tolerance)
+        {
+            $crate::fail(
+                concat!("volume of ", stringify!($mesh), " ≈ ", stringify!($expected)),
+                Some(format_args!($($arg)+)),
+                mismatch,
+            );
+        }
+    };
+}
+
+/// Assert that a mesh is watertight
+///
+/// See [`check_watertight`].
+#[macro_export]
+macro_rules! assert_watertight {
+    ($mesh:expr $(,)?) => {
+        if let Err(mismatch) = $crate::check_watertight(&$mesh) {
+            $crate::fail(
+                concat!(stringify!($mesh), " is watertight"),
+                None,
+                mismatch,
+            );
+        }
+    };
+    ($mesh:expr, $($arg:tt)+) => {
+        if let Err(mismatch) = $crate::check_watertight(&$mesh) {
+            $crate::fail(
+                concat!(stringify!($mesh), " is watertight"),
+                Some(format_args!($($arg)+)),
+                mismatch,
+            );
+        }
+    };
+}
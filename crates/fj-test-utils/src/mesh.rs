@@ -0,0 +1,191 @@
+use std::collections::BTreeMap;
+
+use fj_interop::Mesh;
+use fj_math::{Point, Scalar};
+
+use crate::Mismatch;
+
+/// Compare two meshes, accepting differences up to the provided tolerance
+///
+/// The meshes match, if they have the same number of triangles, and each
+/// triangle of one has a counterpart in the other, with all vertices within the
+/// tolerance of each other. Counterparts must have the same orientation, but
+/// might start at a different vertex. The order of the triangles within each
+/// mesh doesn't matter.
+///
+/// Only the geometry of the meshes is compared. Colors and other metadata are
+/// ignored.
+pub fn compare_meshes(
+    a: &Mesh<Point<3>>,
+    b: &Mesh<Point<3>>,
+    tolerance: impl Into<Scalar>,
+) -> Result<(), Mismatch> {
+    let tolerance = tolerance.into();
+
+    let [triangles_a, triangles_b] = [a, b].map(|mesh| {
+        mesh.triangles()
+            .map(|triangle| triangle.inner.points())
+            .collect::<Vec<_>>()
+    });
+
+    if triangles_a.len() != triangles_b.len() {
+        return Err(Mismatch::NumberOfTriangles {
+            a: triangles_a.len(),
+            b: triangles_b.len(),
+        });
+    }
+
+    // Each triangle can only be the counterpart of one other triangle.
+    let mut unmatched = triangles_b;
+
+    for triangle in triangles_a {
+        let counterpart = unmatched.iter().position(|&[d, e, f]| {
+            [[d, e, f], [e, f, d], [f, d, e]].into_iter().any(|other| {
+                triangle
+                    .into_iter()
+                    .zip(other)
+                    .all(|(p, q)| p.distance_to(&q) <= tolerance)
+            })
+        });
+
+        match counterpart {
+            Some(i) => {
+                unmatched.swap_remove(i);
+            }
+            None => {
+                return Err(Mismatch::Triangle {
+                    triangle,
+                    tolerance,
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Check that a mesh encloses the expected volume
+///
+/// See [`Mesh::volume`]. The volume is only meaningful for watertight meshes,
+/// so consider checking that too. See [`check_watertight`].
+pub fn compare_volume(
+    mesh: &Mesh<Point<3>>,
+    expected: impl Into<Scalar>,
+    tolerance: impl Into<Scalar>,
+) -> Result<(), Mismatch> {
+    let [expected, tolerance] = [expected.into(), tolerance.into()];
+
+    let volume = mesh.volume();
+    if (volume - expected).abs() > tolerance {
+        return Err(Mismatch::Volume {
+            volume,
+            expected,
+            tolerance,
+        });
+    }
+
+    Ok(())
+}
+
+/// Check that a mesh is watertight
+///
+/// See [`find_open_edge`].
+pub fn check_watertight(mesh: &Mesh<Point<3>>) -> Result<(), Mismatch> {
+    match find_open_edge(mesh) {
+        Some(edge) => Err(Mismatch::OpenEdge { edge }),
+        None => Ok(()),
+    }
+}
+
+/// Find an edge of the mesh that is not matched by an opposite edge
+///
+/// In a watertight mesh, every triangle edge is shared with exactly one other
+/// triangle, which has the same edge in the opposite direction. Vertices are
+/// compared exactly, as a triangulation must use the same vertices for both
+/// sides of an edge.
+pub fn find_open_edge(mesh: &Mesh<Point<3>>) -> Option<[Point<3>; 2]> {
+    let mut edges = BTreeMap::new();
+    for triangle in mesh.triangles() {
+        let [a, b, c] = triangle.inner.points();
+
+        for edge in [[a, b], [b, c], [c, a]] {
+            *edges.entry(edge).or_insert(0) += 1;
+        }
+    }
+
+    edges
+        .iter()
+        .find(|(&[a, b], &count)| count != 1 || edges.get(&[b, a]) != Some(&1))
+        .map(|(&edge, _)| edge)
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::{Color, Mesh};
+    use fj_math::Point;
+
+    use crate::Mismatch;
+
+    use super::{check_watertight, compare_meshes, compare_volume};
+
+    #[test]
+    fn compare() {
+        let triangles = [
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]],
+            [[1., 0., 0.], [1., 1., 0.], [0., 1., 0.]],
+        ];
+
+        let a = mesh(triangles);
+
+        // Different order of triangles, different starting vertex, slight
+        // offset.
+        let b = mesh([
+            [[1., 1., 0.], [0., 1., 0.], [1., 0., 0.]],
+            [[0., 0., 1e-10], [1., 0., 0.], [0., 1., 0.]],
+        ]);
+        crate::assert_mesh_eq!(a, b, 1e-9);
+
+        // Different orientation.
+        let [[p, q, r], other] = triangles;
+        let c = mesh([[p, r, q], other]);
+        assert!(matches!(
+            compare_meshes(&a, &c, 1e-9),
+            Err(Mismatch::Triangle { .. })
+        ));
+
+        // Different number of triangles.
+        let d = mesh([other]);
+        assert!(matches!(
+            compare_meshes(&a, &d, 1e-9),
+            Err(Mismatch::NumberOfTriangles { a: 2, b: 1 })
+        ));
+    }
+
+    #[test]
+    fn volume_and_watertightness() {
+        let [a, b, c, d] =
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]];
+        let tetrahedron = mesh([[a, c, b], [a, b, d], [b, c, d], [c, a, d]]);
+
+        crate::assert_watertight!(tetrahedron);
+        crate::assert_volume_eq!(tetrahedron, 1. / 6., 1e-12);
+        assert!(matches!(
+            compare_volume(&tetrahedron, -1. / 6., 1e-12),
+            Err(Mismatch::Volume { .. })
+        ));
+
+        let open = mesh([[a, c, b], [a, b, d], [b, c, d]]);
+        assert!(matches!(
+            check_watertight(&open),
+            Err(Mismatch::OpenEdge { .. })
+        ));
+    }
+
+    fn mesh<const N: usize>(triangles: [[[f64; 3]; 3]; N]) -> Mesh<Point<3>> {
+        let mut mesh = Mesh::new();
+        for triangle in triangles {
+            mesh.push_triangle(triangle, Color::default());
+        }
+        mesh
+    }
+}
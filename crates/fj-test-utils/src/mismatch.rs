@@ -0,0 +1,111 @@
+use fj_math::{Point, Scalar};
+
+/// A difference from the expected geometry, that exceeds the tolerance
+///
+/// Returned by the comparison functions of this crate.
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum Mismatch {
+    /// The scalars are too far apart
+    #[error(
+        "Scalars differ by {difference:?} (tolerance: {tolerance:?})\n\
+        - a: {a:?}\n\
+        - b: {b:?}"
+    )]
+    Scalars {
+        /// The first scalar
+        a: Scalar,
+
+        /// The second scalar
+        b: Scalar,
+
+        /// The absolute difference between the scalars
+        difference: Scalar,
+
+        /// The tolerance that the difference exceeds
+        tolerance: Scalar,
+    },
+
+    /// The points are too far apart
+    #[error(
+        "Points are {distance:?} apart (tolerance: {tolerance:?})\n\
+        - a: {a:?}\n\
+        - b: {b:?}"
+    )]
+    Points {
+        /// The first point, with its components converted to `f64`
+        a: Vec<f64>,
+
+        /// The second point, with its components converted to `f64`
+        b: Vec<f64>,
+
+        /// The distance between the points
+        distance: Scalar,
+
+        /// The tolerance that the distance exceeds
+        tolerance: Scalar,
+    },
+
+    /// The meshes have a different number of triangles
+    #[error("Meshes have different number of triangles: {a} vs {b}")]
+    NumberOfTriangles {
+        /// The number of triangles in the first mesh
+        a: usize,
+
+        /// The number of triangles in the second mesh
+        b: usize,
+    },
+
+    /// A triangle of the first mesh has no counterpart in the second one
+    #[error(
+        "Triangle of first mesh has no counterpart in second mesh \
+        (tolerance: {tolerance:?})\n\
+        - triangle: {triangle:?}"
+    )]
+    Triangle {
+        /// The triangle that has no counterpart
+        triangle: [Point<3>; 3],
+
+        /// The tolerance used to look for a counterpart
+        tolerance: Scalar,
+    },
+
+    /// The shapes deviate from each other by more than the tolerance
+    #[error(
+        "Shapes deviate from each other by {deviation:?} \
+        (tolerance: {tolerance:?})"
+    )]
+    Shapes {
+        /// The largest distance found between the shapes
+        deviation: Scalar,
+
+        /// The tolerance that the deviation exceeds
+        tolerance: Scalar,
+    },
+
+    /// The mesh doesn't enclose the expected volume
+    #[error(
+        "Mesh encloses a volume of {volume:?}, expected {expected:?} \
+        (tolerance: {tolerance:?})"
+    )]
+    Volume {
+        /// The volume that the mesh encloses
+        volume: Scalar,
+
+        /// The expected volume
+        expected: Scalar,
+
+        /// The tolerance that the difference exceeds
+        tolerance: Scalar,
+    },
+
+    /// The mesh has an edge that isn't matched by exactly one opposite edge
+    #[error(
+        "Mesh is not watertight: Edge isn't matched by exactly one opposite \
+        edge\n\
+        - edge: {edge:?}"
+    )]
+    OpenEdge {
+        /// The edge, as it is traversed by one of the triangles
+        edge: [Point<3>; 2],
+    },
+}
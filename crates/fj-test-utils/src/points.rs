@@ -0,0 +1,51 @@
+use fj_math::{Point, Scalar};
+
+use crate::Mismatch;
+
+/// Compare two points, accepting differences up to the provided tolerance
+///
+/// The points match, if the distance between them is not larger than the
+/// tolerance.
+pub fn compare_points<const D: usize>(
+    a: impl Into<Point<D>>,
+    b: impl Into<Point<D>>,
+    tolerance: impl Into<Scalar>,
+) -> Result<(), Mismatch> {
+    let [a, b] = [a.into(), b.into()];
+    let tolerance = tolerance.into();
+
+    let distance = a.distance_to(&b);
+    if distance > tolerance {
+        return Err(Mismatch::Points {
+            a: a.coords.components.map(Scalar::into_f64).to_vec(),
+            b: b.coords.components.map(Scalar::into_f64).to_vec(),
+            distance,
+            tolerance,
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Point;
+
+    use super::compare_points;
+
+    #[test]
+    fn compare() {
+        let a = Point::from([1., 2., 3.]);
+
+        assert!(compare_points(a, [1., 2., 3. + 1e-10], 1e-9).is_ok());
+        assert!(compare_points(a, [1., 2., 3. + 1e-8], 1e-9).is_err());
+
+        crate::assert_points_eq!(a, [1., 2. - 1e-10, 3.], 1e-9);
+    }
+
+    #[test]
+    #[should_panic]
+    fn assert_fails() {
+        crate::assert_points_eq!(Point::from([0., 0.]), [0., 1.], 0.5);
+    }
+}
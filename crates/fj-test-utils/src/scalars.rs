@@ -0,0 +1,50 @@
+use fj_math::Scalar;
+
+use crate::Mismatch;
+
+/// Compare two scalars, accepting differences up to the provided tolerance
+///
+/// The scalars match, if their absolute difference is not larger than the
+/// tolerance. Lengths, areas, and angles computed from geometry can be
+/// compared like this.
+pub fn compare_scalars(
+    a: impl Into<Scalar>,
+    b: impl Into<Scalar>,
+    tolerance: impl Into<Scalar>,
+) -> Result<(), Mismatch> {
+    let [a, b, tolerance] = [a.into(), b.into(), tolerance.into()];
+
+    let difference = (a - b).abs();
+    if difference > tolerance {
+        return Err(Mismatch::Scalars {
+            a,
+            b,
+            difference,
+            tolerance,
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Scalar;
+
+    use super::compare_scalars;
+
+    #[test]
+    fn compare() {
+        assert!(compare_scalars(1., 1. + 1e-10, 1e-9).is_ok());
+        assert!(compare_scalars(1., 1. + 1e-8, 1e-9).is_err());
+        assert!(compare_scalars(-1., 1., 1.).is_err());
+
+        crate::assert_scalars_eq!(Scalar::from(0.1) * 3., 0.3, 1e-12);
+    }
+
+    #[test]
+    #[should_panic(expected = "case 3")]
+    fn assert_fails_with_message() {
+        crate::assert_scalars_eq!(1., 2., 0.5, "case {}", 3);
+    }
+}
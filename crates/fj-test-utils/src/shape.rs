@@ -0,0 +1,96 @@
+use fj_interop::Mesh;
+use fj_math::{Point, Scalar};
+
+use crate::Mismatch;
+
+/// Compare two triangulated shapes, accepting differences up to the tolerance
+///
+/// Unlike [`compare_meshes`](crate::compare_meshes), this doesn't depend on how
+/// the shapes are triangulated. Two shapes that were constructed in different
+/// ways match, as long as their surfaces are within the tolerance of each
+/// other. See [`shape_deviation`].
+///
+/// Triangulate both shapes with half the tolerance, so the triangulations of
+/// two identical shapes deviate by no more than the tolerance.
+pub fn compare_shapes(
+    a: &Mesh<Point<3>>,
+    b: &Mesh<Point<3>>,
+    tolerance: impl Into<Scalar>,
+) -> Result<(), Mismatch> {
+    let tolerance = tolerance.into();
+
+    let deviation = shape_deviation(a, b);
+    if deviation > tolerance {
+        return Err(Mismatch::Shapes {
+            deviation,
+            tolerance,
+        });
+    }
+
+    Ok(())
+}
+
+/// Compute how far two triangulated shapes deviate from each other
+///
+/// The deviation is measured as the largest distance of any vertex or triangle
+/// center of either triangulation from the other one.
+pub fn shape_deviation(a: &Mesh<Point<3>>, b: &Mesh<Point<3>>) -> Scalar {
+    mesh_deviation(a, b).max(mesh_deviation(b, a))
+}
+
+/// Compute the largest distance of any sample point of `a` from `b`
+fn mesh_deviation(a: &Mesh<Point<3>>, b: &Mesh<Point<3>>) -> Scalar {
+    let samples = a.triangles().flat_map(|triangle| {
+        let [p, q, r] = triangle.inner.points();
+        let center = Point {
+            coords: (p.coords + q.coords + r.coords) / 3.,
+        };
+
+        [p, q, r, center]
+    });
+
+    samples
+        .map(|point| {
+            b.triangles()
+                .map(|triangle| triangle.inner.distance_to_point(point))
+                .min()
+                .unwrap_or(Scalar::MAX)
+        })
+        .max()
+        .unwrap_or(Scalar::ZERO)
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::{Color, Mesh};
+    use fj_math::{Point, Scalar};
+
+    use crate::Mismatch;
+
+    use super::compare_shapes;
+
+    #[test]
+    fn compare() {
+        // The same square, triangulated in two different ways.
+        let a = square([[0., 0.], [1., 0.], [1., 1.], [0., 1.]], 0.);
+        let b = square([[1., 0.], [1., 1.], [0., 1.], [0., 0.]], 0.);
+        crate::assert_shapes_eq!(a, b, 1e-12);
+
+        let c = square([[0., 0.], [1., 0.], [1., 1.], [0., 1.]], 0.1);
+        let result = compare_shapes(&a, &c, 0.01);
+        let Err(Mismatch::Shapes { deviation, .. }) = result else {
+            panic!("Expected shapes to deviate");
+        };
+        assert!(deviation > Scalar::from(0.09));
+    }
+
+    /// Triangulate a square along the diagonal from its first corner
+    fn square(corners: [[f64; 2]; 4], z: f64) -> Mesh<Point<3>> {
+        let [a, b, c, d] = corners.map(|[x, y]| [x, y, z]);
+
+        let mut mesh = Mesh::new();
+        mesh.push_triangle([a, b, c], Color::default());
+        mesh.push_triangle([a, c, d], Color::default());
+        mesh
+    }
+}
@@ -1,10 +1,12 @@
 //! Viewer camera module
 use std::f64::consts::FRAC_PI_2;
 
-use fj_interop::{Mesh, Model};
+use fj_interop::{Mesh, Model, Triangle};
 use fj_math::{Aabb, Point, Scalar, Transform, Vector};
 
-use crate::screen::NormalizedScreenPosition;
+use crate::{
+    pick::face_center, scene::CameraView, screen::NormalizedScreenPosition,
+};
 
 /// The camera abstraction
 ///
@@ -12,7 +14,7 @@ use crate::screen::NormalizedScreenPosition;
 /// is handled, for example) is not that of a camera freely flying through a
 /// static scene. Instead, the camera is static, and the model is freely
 /// translated and rotated.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Camera {
     /// The distance to the near plane
     near_plane: f64,
@@ -78,6 +80,21 @@ impl Camera {
         self.camera_to_model().inverse_transform_point(&cursor)
     }
 
+    /// Transform a point in model space to normalized screen coordinates.
+    ///
+    /// Returns `None`, if the point is behind the camera.
+    pub fn model_to_screen(&self, point: Point<3>) -> Option<[f64; 2]> {
+        let point = self.camera_to_model().transform_point(&point);
+        if point.z >= Scalar::ZERO {
+            return None;
+        }
+
+        let f = (self.field_of_view_in_x() / 2.).tan();
+        let [x, y] = [point.x, point.y].map(|c| (c / -point.z).into_f64() / f);
+
+        Some([x, y])
+    }
+
     /// Compute the point on the model, that the cursor currently points to.
     pub fn focus_point(
         &self,
@@ -88,17 +105,49 @@ impl Camera {
             .unwrap_or_else(|| FocusPoint(model.aabb.center()))
     }
 
+    /// Compute a rotation center from the face that the cursor points to
+    ///
+    /// Returns the center of that face. If the mesh doesn't track which face
+    /// its triangles belong to, returns the point the cursor points to
+    /// instead. Returns `None`, if the cursor doesn't point at the model.
+    pub fn rotation_center(
+        &self,
+        cursor: Option<NormalizedScreenPosition>,
+        model: &Model,
+    ) -> Option<FocusPoint> {
+        let (point, triangle) = self.cast_ray(cursor, &model.mesh)?;
+
+        let Some(face) = triangle.face else {
+            return Some(FocusPoint(point));
+        };
+
+        let center = face_center(model, face).unwrap_or(point);
+        Some(FocusPoint(center))
+    }
+
     fn calculate_focus_point(
         &self,
         cursor: Option<NormalizedScreenPosition>,
         mesh: &Mesh<Point<3>>,
     ) -> Option<FocusPoint> {
+        let (point, _) = self.cast_ray(cursor, mesh)?;
+        Some(FocusPoint(point))
+    }
+
+    /// Find the point on the mesh, that the cursor points to
+    ///
+    /// Also returns the triangle that the point is on.
+    pub(crate) fn cast_ray(
+        &self,
+        cursor: Option<NormalizedScreenPosition>,
+        mesh: &Mesh<Point<3>>,
+    ) -> Option<(Point<3>, Triangle)> {
         // Transform camera and cursor positions to model space.
         let origin = self.position();
         let cursor = self.cursor_to_model_space(cursor?);
         let dir = (cursor - origin).normalize();
 
-        let mut min_t = None;
+        let mut closest = None;
 
         for triangle in mesh.triangles() {
             let t =
@@ -107,13 +156,14 @@ impl Camera {
                     .cast_local_ray(origin, dir, f64::INFINITY, true);
 
             if let Some(t) = t {
-                if t <= min_t.unwrap_or(t) {
-                    min_t = Some(t);
+                if closest.map_or(true, |(min_t, _)| t <= min_t) {
+                    closest = Some((t, triangle));
                 }
             }
         }
 
-        Some(FocusPoint(origin + dir * min_t?))
+        let (t, triangle) = closest?;
+        Some((origin + dir * t, triangle))
     }
 
     /// Access the transform from camera to model space.
@@ -128,6 +178,34 @@ impl Camera {
         transform
     }
 
+    /// Compute the view that the camera currently has
+    ///
+    /// The target of the returned view is at unit distance in front of the
+    /// camera. Moving the camera to that view with [`Camera::set_view`]
+    /// restores the current position and orientation.
+    pub fn view(&self) -> CameraView {
+        let camera_to_model = self.camera_to_model().inverse();
+
+        let eye = camera_to_model.transform_point(&Point::origin());
+        let direction =
+            camera_to_model.transform_vector(&Vector::from([0., 0., -1.]));
+        let up = camera_to_model.transform_vector(&Vector::from([0., 1., 0.]));
+
+        CameraView {
+            eye,
+            target: eye + direction,
+            up,
+        }
+    }
+
+    /// Move the camera to the provided view
+    pub fn set_view(&mut self, view: &CameraView) {
+        let transform = Transform::look_at(view.eye, view.target, view.up);
+
+        self.rotation = transform.extract_rotation();
+        self.translation = transform.extract_translation();
+    }
+
     /// Initialize the planes
     ///
     /// Call this, if a shape is available for the first time.
@@ -232,5 +310,6 @@ impl Default for Camera {
 ///
 /// This will be the point on the model that the cursor is currently pointing at if such a point exists,
 /// falling back to the center point of the model's bounding volume otherwise.
+/// For rotations, a rotation center can be set instead. See [`Camera::rotation_center`].
 #[derive(Clone, Copy)]
 pub struct FocusPoint(pub Point<3>);
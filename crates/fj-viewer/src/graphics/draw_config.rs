@@ -6,6 +6,22 @@ pub struct DrawConfig {
 
     /// Toggle for displaying the wireframe model
     pub draw_mesh: bool,
+
+    /// Toggle for coloring the model by the orientation of its triangles
+    ///
+    /// Triangles are colored blue, where their front side faces the viewer,
+    /// and red, where their back side does. On a correctly oriented model,
+    /// only blue should be visible from the outside.
+    pub draw_face_orientation: bool,
+
+    /// Toggle for displaying the reference mesh, if there is one
+    pub draw_reference: bool,
+
+    /// Toggle for displaying performance statistics
+    pub draw_stats: bool,
+
+    /// Toggle for displaying the position of the point under the cursor
+    pub draw_cursor_position: bool,
 }
 
 impl Default for DrawConfig {
@@ -13,6 +29,10 @@ impl Default for DrawConfig {
         Self {
             draw_model: true,
             draw_mesh: false,
+            draw_face_orientation: false,
+            draw_reference: true,
+            draw_stats: false,
+            draw_cursor_position: false,
         }
     }
 }
@@ -1,22 +1,46 @@
 use super::{
+    frustum::Frustum,
     geometries::{Geometries, Geometry},
     pipelines::{Pipeline, Pipelines},
 };
 
 pub struct Drawables<'r> {
     pub model: Drawable<'r>,
+    pub face_orientation: Drawable<'r>,
     pub mesh: Option<Drawable<'r>>,
+    pub reference: Option<Drawable<'r>>,
 }
 
 impl<'r> Drawables<'r> {
-    pub fn new(geometries: &'r Geometries, pipelines: &'r Pipelines) -> Self {
-        let model = Drawable::new(&geometries.mesh, &pipelines.model);
+    /// Create the drawables
+    ///
+    /// The model is drawn using the provided level of detail, if any. The mesh
+    /// is always drawn in full detail, to show the actual triangulation.
+    pub fn new(
+        geometries: &'r Geometries,
+        pipelines: &'r Pipelines,
+        lod: Option<&'r Geometry>,
+        reference: Option<&'r Geometry>,
+    ) -> Self {
+        let model =
+            Drawable::new(lod.unwrap_or(&geometries.mesh), &pipelines.model);
+        let face_orientation = Drawable::new(
+            lod.unwrap_or(&geometries.mesh),
+            &pipelines.face_orientation,
+        );
         let mesh = pipelines
             .mesh
             .as_ref()
             .map(|pipeline| Drawable::new(&geometries.mesh, pipeline));
+        let reference = reference
+            .map(|geometry| Drawable::new(geometry, &pipelines.reference));
 
-        Self { model, mesh }
+        Self {
+            model,
+            face_orientation,
+            mesh,
+            reference,
+        }
     }
 }
 
@@ -30,7 +54,14 @@ impl<'a> Drawable<'a> {
         Self { geometry, pipeline }
     }
 
-    pub fn draw<'b>(&self, render_pass: &mut wgpu::RenderPass<'b>)
+    /// Draw the parts of the geometry that are within the frustum
+    ///
+    /// Returns the number of draw calls made.
+    pub fn draw<'b>(
+        &self,
+        render_pass: &mut wgpu::RenderPass<'b>,
+        frustum: &Frustum,
+    ) -> u32
     where
         'a: 'b,
     {
@@ -41,6 +72,14 @@ impl<'a> Drawable<'a> {
             wgpu::IndexFormat::Uint32,
         );
 
-        render_pass.draw_indexed(0..self.geometry.num_indices, 0, 0..1);
+        let mut num_draw_calls = 0;
+        for chunk in &self.geometry.chunks {
+            if frustum.intersects(&chunk.bounds) {
+                render_pass.draw_indexed(chunk.indices.clone(), 0, 0..1);
+                num_draw_calls += 1;
+            }
+        }
+
+        num_draw_calls
     }
 }
@@ -0,0 +1,52 @@
+use super::{geometries::Bounds, transform::Transform};
+
+/// The part of space that is visible on screen
+///
+/// Used to skip drawing parts of the model that are not visible anyway.
+pub struct Frustum {
+    transform: [f32; 16],
+}
+
+impl Frustum {
+    /// Create the frustum from the transform that is used for vertices
+    ///
+    /// See [`Transform::for_vertices`].
+    pub fn new(transform: &Transform) -> Self {
+        Self {
+            transform: transform.0,
+        }
+    }
+
+    /// Check whether any part of the provided bounding box might be visible
+    ///
+    /// This is conservative: A box that is not visible might not be detected
+    /// as such, but a visible box is never reported as invisible.
+    pub fn intersects(&self, bounds: &Bounds) -> bool {
+        let corners = bounds.corners().map(|point| self.to_clip_space(point));
+
+        // A box is definitely invisible, if all of its corners are on the
+        // outside of the same clipping plane.
+        let outside_plane = |is_outside: fn([f32; 4]) -> bool| {
+            corners.iter().all(|&corner| is_outside(corner))
+        };
+
+        let planes: [fn([f32; 4]) -> bool; 6] = [
+            |[x, _, _, w]| x < -w,
+            |[x, _, _, w]| x > w,
+            |[_, y, _, w]| y < -w,
+            |[_, y, _, w]| y > w,
+            |[_, _, z, w]| z < -w,
+            |[_, _, z, w]| z > w,
+        ];
+
+        !planes.into_iter().any(outside_plane)
+    }
+
+    fn to_clip_space(&self, [x, y, z]: [f32; 3]) -> [f32; 4] {
+        // The transform is stored in column-major order.
+        let m = &self.transform;
+        [0, 1, 2, 3].map(|row| {
+            m[row] * x + m[4 + row] * y + m[8 + row] * z + m[12 + row]
+        })
+    }
+}
@@ -1,19 +1,46 @@
-use std::convert::TryInto;
+use std::{convert::TryInto, ops::Range};
 
 use wgpu::util::DeviceExt;
 
-use super::vertices::{Vertex, Vertices};
+use fj_interop::Mesh;
+use fj_math::{Aabb, Point};
+
+use crate::ScalarField;
+
+use super::{
+    lod::LevelsOfDetail,
+    vertices::{Vertex, Vertices},
+};
 
 #[derive(Debug)]
 pub struct Geometries {
     pub mesh: Geometry,
+    pub lods: LevelsOfDetail,
 }
 
 impl Geometries {
-    pub fn new(device: &wgpu::Device, mesh: &Vertices) -> Self {
+    pub fn new(
+        device: &wgpu::Device,
+        mesh: &Mesh<Point<3>>,
+        aabb: Aabb<3>,
+        scalar_field: Option<&ScalarField>,
+    ) -> Self {
+        let corner_colors =
+            scalar_field.map(|field| field.triangle_colors(mesh));
+
+        // Decimation doesn't keep track of where vertices came from, so a
+        // scalar field can't be applied to the decimated meshes. Analyses
+        // need the full detail anyway.
+        let lods = if corner_colors.is_some() {
+            LevelsOfDetail::none(aabb)
+        } else {
+            LevelsOfDetail::new(device, mesh, aabb)
+        };
+
+        let mesh = Vertices::new(mesh, corner_colors.as_deref());
         let mesh = Geometry::new(device, mesh.vertices(), mesh.indices());
 
-        Self { mesh }
+        Self { mesh, lods }
     }
 }
 
@@ -22,14 +49,25 @@ pub struct Geometry {
     pub vertex_buffer: wgpu::Buffer,
     pub index_buffer: wgpu::Buffer,
     pub num_indices: u32,
+
+    /// Spatially coherent parts of the geometry, that can be culled separately
+    pub chunks: Vec<Chunk>,
 }
 
 impl Geometry {
-    fn new(
+    /// The number of triangles, that a chunk should contain on average
+    ///
+    /// Every chunk that is drawn costs a draw call, so chunks should not be too
+    /// small.
+    const TRIANGLES_PER_CHUNK: usize = 4096;
+
+    pub fn new(
         device: &wgpu::Device,
         vertices: &[Vertex],
         indices: &[u32],
     ) -> Self {
+        let (indices, chunks) = Self::chunk(vertices, indices);
+
         Self {
             vertex_buffer: device.create_buffer_init(
                 &wgpu::util::BufferInitDescriptor {
@@ -41,7 +79,7 @@ impl Geometry {
             index_buffer: device.create_buffer_init(
                 &wgpu::util::BufferInitDescriptor {
                     label: None,
-                    contents: bytemuck::cast_slice(indices),
+                    contents: bytemuck::cast_slice(&indices),
                     usage: wgpu::BufferUsages::INDEX,
                 },
             ),
@@ -49,6 +87,131 @@ impl Geometry {
                 .len()
                 .try_into()
                 .expect("`usize` couldn't be cast to `u32`"),
+            chunks,
+        }
+    }
+
+    /// Sort the triangles into chunks
+    ///
+    /// The bounding box of all triangles is divided into a grid, and each grid
+    /// cell that contains triangles becomes a chunk. Returns the indices,
+    /// reordered so each chunk covers a contiguous range of them.
+    fn chunk(vertices: &[Vertex], indices: &[u32]) -> (Vec<u32>, Vec<Chunk>) {
+        let position = |index: u32| vertices[index as usize].position;
+
+        let mut triangles: Vec<[u32; 3]> = indices
+            .chunks_exact(3)
+            .map(|triangle| [triangle[0], triangle[1], triangle[2]])
+            .collect();
+
+        let bounds = Bounds::from_points(indices.iter().copied().map(position));
+        let cells_per_axis =
+            (triangles.len() / Self::TRIANGLES_PER_CHUNK).max(1) as f32;
+        let cells_per_axis = cells_per_axis.cbrt().ceil().max(1.);
+
+        let cell_of = |triangle: &[u32; 3]| {
+            let [a, b, c] = triangle.map(position);
+            let mut cell = [0; 3];
+            for i in 0..3 {
+                let center = (a[i] + b[i] + c[i]) / 3.;
+                let size = bounds.max[i] - bounds.min[i];
+                let t = if size > 0. {
+                    (center - bounds.min[i]) / size
+                } else {
+                    0.
+                };
+                cell[i] =
+                    (t * cells_per_axis).clamp(0., cells_per_axis - 1.) as u32;
+            }
+            cell
+        };
+        triangles.sort_by_cached_key(cell_of);
+
+        let mut reordered = Vec::with_capacity(indices.len());
+        let mut chunks: Vec<Chunk> = Vec::new();
+        let mut current_cell = None;
+
+        for triangle in &triangles {
+            let cell = cell_of(triangle);
+            let start = reordered.len() as u32;
+            reordered.extend_from_slice(triangle);
+            let end = reordered.len() as u32;
+            let triangle_bounds =
+                Bounds::from_points(triangle.iter().copied().map(position));
+
+            match chunks.last_mut() {
+                Some(chunk) if current_cell == Some(cell) => {
+                    chunk.indices.end = end;
+                    chunk.bounds = chunk.bounds.merged(&triangle_bounds);
+                }
+                _ => {
+                    chunks.push(Chunk {
+                        indices: start..end,
+                        bounds: triangle_bounds,
+                    });
+                }
+            }
+            current_cell = Some(cell);
+        }
+
+        (reordered, chunks)
+    }
+}
+
+/// A part of a [`Geometry`], that can be culled separately
+#[derive(Debug)]
+pub struct Chunk {
+    /// The range of the index buffer that makes up this chunk
+    pub indices: Range<u32>,
+
+    /// The bounding box of the chunk's triangles
+    pub bounds: Bounds,
+}
+
+/// An axis-aligned bounding box, in the coordinates the GPU works with
+#[derive(Clone, Copy, Debug)]
+pub struct Bounds {
+    pub min: [f32; 3],
+    pub max: [f32; 3],
+}
+
+impl Bounds {
+    fn from_points(points: impl IntoIterator<Item = [f32; 3]>) -> Self {
+        let mut bounds = Self {
+            min: [f32::INFINITY; 3],
+            max: [f32::NEG_INFINITY; 3],
+        };
+
+        for point in points {
+            bounds = bounds.merged(&Self {
+                min: point,
+                max: point,
+            });
         }
+
+        bounds
+    }
+
+    fn merged(&self, other: &Self) -> Self {
+        Self {
+            min: [0, 1, 2].map(|i| self.min[i].min(other.min[i])),
+            max: [0, 1, 2].map(|i| self.max[i].max(other.max[i])),
+        }
+    }
+
+    /// The corners of the bounding box
+    pub fn corners(&self) -> [[f32; 3]; 8] {
+        let [min, max] = [self.min, self.max];
+
+        [
+            [min[0], min[1], min[2]],
+            [max[0], min[1], min[2]],
+            [min[0], max[1], min[2]],
+            [max[0], max[1], min[2]],
+            [min[0], min[1], max[2]],
+            [max[0], min[1], max[2]],
+            [min[0], max[1], max[2]],
+            [max[0], max[1], max[2]],
+        ]
     }
 }
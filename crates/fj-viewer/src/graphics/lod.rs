@@ -0,0 +1,128 @@
+use fj_interop::Mesh;
+use fj_math::{Aabb, Point, Scalar};
+
+use crate::camera::Camera;
+
+use super::{geometries::Geometry, vertices::Vertices};
+
+/// Simplified versions of the model, for when it's small on screen
+///
+/// Drawing a large model with all of its triangles is wasteful, if many of
+/// them end up being smaller than a pixel. This provides decimated versions
+/// of the model, and selects the coarsest one that looks the same as the full
+/// model, from where the camera currently is.
+#[derive(Debug)]
+pub struct LevelsOfDetail {
+    aabb: Aabb<3>,
+
+    /// The decimated meshes, ordered from finest to coarsest
+    levels: Vec<Level>,
+}
+
+impl LevelsOfDetail {
+    /// Models with fewer triangles are always drawn in full detail
+    const MIN_TRIANGLES: usize = 10_000;
+
+    /// The cell sizes of the levels, as fractions of the model's size
+    const CELL_SIZES: [f64; 3] = [1. / 512., 1. / 128., 1. / 32.];
+
+    /// The maximum on-screen error of a level, for it to be selected
+    const MAX_ERROR_IN_PIXELS: f64 = 1.;
+
+    pub fn new(
+        device: &wgpu::Device,
+        mesh: &Mesh<Point<3>>,
+        aabb: Aabb<3>,
+    ) -> Self {
+        let mut levels = Vec::new();
+
+        let mut num_triangles = mesh.triangles().count();
+        let size = aabb.size().magnitude();
+
+        if num_triangles >= Self::MIN_TRIANGLES && size > Scalar::ZERO {
+            for fraction in Self::CELL_SIZES {
+                let cell_size = size * fraction;
+                let decimated = mesh.decimate(cell_size);
+
+                // A level that doesn't save much over the previous one is not
+                // worth the memory.
+                let num_decimated = decimated.triangles().count();
+                if num_decimated * 2 > num_triangles {
+                    continue;
+                }
+                num_triangles = num_decimated;
+
+                let vertices = Vertices::from(&decimated);
+                levels.push(Level {
+                    // No vertex is moved further than the diagonal of a cell.
+                    max_error: cell_size.into_f64() * 3f64.sqrt(),
+                    geometry: Geometry::new(
+                        device,
+                        vertices.vertices(),
+                        vertices.indices(),
+                    ),
+                });
+            }
+        }
+
+        Self { aabb, levels }
+    }
+
+    /// Don't provide any decimated meshes, always drawing the full model
+    pub fn none(aabb: Aabb<3>) -> Self {
+        Self {
+            aabb,
+            levels: Vec::new(),
+        }
+    }
+
+    /// Select the coarsest level that looks the same as the full model
+    ///
+    /// Returns `None`, if the full model should be drawn.
+    pub fn select(
+        &self,
+        camera: &Camera,
+        screen_width: u32,
+    ) -> Option<&Geometry> {
+        let position = camera.position();
+        let closest = Point::from([0, 1, 2].map(|i| {
+            position.coords.components[i]
+                .max(self.aabb.min.coords.components[i])
+                .min(self.aabb.max.coords.components[i])
+        }));
+        let distance = position.distance_to(&closest).into_f64();
+        if distance <= 0. {
+            return None;
+        }
+
+        // The size of a pixel at the distance of the model's closest point.
+        let pixel_size =
+            distance * (camera.field_of_view_in_x() / 2.).tan() * 2.
+                / f64::from(screen_width.max(1));
+
+        self.levels
+            .iter()
+            .rev()
+            .find(|level| {
+                level.max_error <= pixel_size * Self::MAX_ERROR_IN_PIXELS
+            })
+            .map(|level| &level.geometry)
+    }
+
+    /// The number of bytes used by the buffers of all levels
+    pub fn buffer_size(&self) -> u64 {
+        self.levels
+            .iter()
+            .map(|level| {
+                level.geometry.vertex_buffer.size()
+                    + level.geometry.index_buffer.size()
+            })
+            .sum()
+    }
+}
+
+#[derive(Debug)]
+struct Level {
+    max_error: f64,
+    geometry: Geometry,
+}
@@ -3,9 +3,12 @@
 mod device;
 mod draw_config;
 mod drawables;
+mod frustum;
 mod geometries;
+mod lod;
 mod model;
 mod navigation_cube;
+mod overlay;
 mod pipelines;
 mod renderer;
 mod shaders;
@@ -17,6 +20,7 @@ mod vertices;
 pub use self::{
     device::DeviceError,
     draw_config::DrawConfig,
+    overlay::Legend,
     renderer::{Renderer, RendererInitError},
 };
 
@@ -188,6 +188,11 @@ impl NavigationCubeRenderer {
         render_pass.draw_model(&self.cube_model);
     }
 
+    /// The number of draw calls that [`NavigationCubeRenderer::draw`] makes
+    pub fn num_draw_calls(&self) -> u32 {
+        self.cube_model.meshes.len() as u32
+    }
+
     fn get_mvp_matrix(rotation: Transform, aspect_ratio: f64) -> [f32; 16] {
         let scale = Transform::scale(SCALE_FACTOR);
         let world_translation = Transform::translation([0.0, 0.0, -1.0]);
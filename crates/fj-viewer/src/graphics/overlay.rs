@@ -0,0 +1,555 @@
+use std::mem::size_of;
+
+use ab_glyph::{Font, FontRef, PxScale, ScaleFont};
+use fj_interop::Color;
+use wgpu::util::DeviceExt;
+
+use crate::screen::ScreenSize;
+
+/// Renders text on top of the model
+///
+/// Used to display statistics, and the legend of a scalar field. The text is
+/// rendered using a monospace font, so laying it out is trivial.
+#[derive(Debug)]
+pub struct OverlayRenderer {
+    render_pipeline: wgpu::RenderPipeline,
+    bind_group: wgpu::BindGroup,
+    atlas: GlyphAtlas,
+
+    text: String,
+    legend: Option<Legend>,
+    vertices: Option<(wgpu::Buffer, u32)>,
+    vertices_screen_size: Option<ScreenSize>,
+}
+
+impl OverlayRenderer {
+    pub fn new(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        config: &wgpu::SurfaceConfiguration,
+    ) -> Self {
+        let atlas = GlyphAtlas::new(device, queue);
+
+        let bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            multisampled: false,
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            sample_type: wgpu::TextureSampleType::Float {
+                                filterable: true,
+                            },
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler(
+                            wgpu::SamplerBindingType::Filtering,
+                        ),
+                        count: None,
+                    },
+                ],
+                label: Some("overlay_bind_group_layout"),
+            });
+
+        let view = atlas
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Nearest,
+            min_filter: wgpu::FilterMode::Nearest,
+            mipmap_filter: wgpu::FilterMode::Nearest,
+            ..Default::default()
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&sampler),
+                },
+            ],
+            label: Some("overlay_bind_group"),
+        });
+
+        let shader =
+            device.create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some("Overlay Shader"),
+                source: wgpu::ShaderSource::Wgsl(
+                    include_str!("overlay.wgsl").into(),
+                ),
+            });
+
+        let render_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Overlay Pipeline Layout"),
+                bind_group_layouts: &[&bind_group_layout],
+                push_constant_ranges: &[],
+            });
+
+        let render_pipeline =
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some("Overlay Renderer"),
+                layout: Some(&render_pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: "vertex",
+                    buffers: &[OverlayVertex::desc()],
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: "fragment",
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: config.format,
+                        blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                }),
+                primitive: wgpu::PrimitiveState {
+                    topology: wgpu::PrimitiveTopology::TriangleList,
+                    strip_index_format: None,
+                    front_face: wgpu::FrontFace::Ccw,
+                    cull_mode: None,
+                    polygon_mode: wgpu::PolygonMode::Fill,
+                    unclipped_depth: false,
+                    conservative: false,
+                },
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState {
+                    count: 1,
+                    mask: !0,
+                    alpha_to_coverage_enabled: false,
+                },
+                multiview: None,
+            });
+
+        Self {
+            render_pipeline,
+            bind_group,
+            atlas,
+
+            text: String::new(),
+            legend: None,
+            vertices: None,
+            vertices_screen_size: None,
+        }
+    }
+
+    /// Set the text that is displayed
+    pub fn set_text(&mut self, text: &str) {
+        if self.text != text {
+            self.text = text.to_string();
+            self.vertices = None;
+        }
+    }
+
+    /// Set the legend that is displayed in the bottom-left corner
+    pub fn set_legend(&mut self, legend: Option<Legend>) {
+        if self.legend != legend {
+            self.legend = legend;
+            self.vertices = None;
+        }
+    }
+
+    /// The amount of GPU memory used for the overlay, in bytes
+    pub fn gpu_memory(&self) -> u64 {
+        let vertices = self
+            .vertices
+            .as_ref()
+            .map(|(buffer, _)| buffer.size())
+            .unwrap_or(0);
+
+        self.atlas.gpu_memory() + vertices
+    }
+
+    /// Draw the text and the legend, unless there's neither
+    ///
+    /// Returns the number of draw calls made.
+    pub fn draw(
+        &mut self,
+        view: &wgpu::TextureView,
+        encoder: &mut wgpu::CommandEncoder,
+        device: &wgpu::Device,
+        screen_size: ScreenSize,
+    ) -> u32 {
+        if self.vertices_screen_size != Some(screen_size) {
+            self.vertices = None;
+        }
+
+        let (buffer, num_vertices) = self.vertices.get_or_insert_with(|| {
+            let mut vertices = self.atlas.layout(&self.text, screen_size);
+            if let Some(legend) = &self.legend {
+                vertices.extend(self.atlas.layout_legend(legend, screen_size));
+            }
+            let buffer =
+                device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some("Overlay Vertex Buffer"),
+                    contents: bytemuck::cast_slice(&vertices),
+                    usage: wgpu::BufferUsages::VERTEX,
+                });
+
+            (buffer, vertices.len() as u32)
+        });
+        self.vertices_screen_size = Some(screen_size);
+
+        if *num_vertices == 0 {
+            return 0;
+        }
+
+        let mut render_pass =
+            encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                ..Default::default()
+            });
+        render_pass.set_pipeline(&self.render_pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.set_vertex_buffer(0, buffer.slice(..));
+        render_pass.draw(0..*num_vertices, 0..1);
+
+        1
+    }
+}
+
+/// A texture that contains the printable ASCII characters
+///
+/// The characters are arranged in a grid of equally sized cells. An additional
+/// cell is completely filled, for drawing the background.
+#[derive(Debug)]
+struct GlyphAtlas {
+    texture: wgpu::Texture,
+    cell_size: [u32; 2],
+}
+
+impl GlyphAtlas {
+    const FIRST_CHAR: u8 = b' ';
+    const LAST_CHAR: u8 = b'~';
+    const COLUMNS: u32 = 16;
+
+    /// The size of the font, in pixels
+    const FONT_SIZE: f32 = 16.;
+
+    /// The space around the text, in pixels
+    const MARGIN: f32 = 8.;
+
+    fn new(device: &wgpu::Device, queue: &wgpu::Queue) -> Self {
+        let font = FontRef::try_from_slice(include_bytes!(
+            "fonts/B612Mono-Regular.ttf"
+        ))
+        .expect("Embedded font is valid");
+        let font = font.as_scaled(PxScale::from(Self::FONT_SIZE));
+
+        let cell_size = [
+            font.h_advance(font.glyph_id('M')).ceil() as u32,
+            font.height().ceil() as u32,
+        ];
+        let [cell_width, cell_height] = cell_size;
+
+        let num_cells = u32::from(Self::LAST_CHAR - Self::FIRST_CHAR) + 2;
+        let rows = num_cells.div_ceil(Self::COLUMNS);
+        let [width, height] = [Self::COLUMNS * cell_width, rows * cell_height];
+
+        let mut pixels = vec![0u8; (width * height) as usize];
+
+        for c in Self::FIRST_CHAR..=Self::LAST_CHAR {
+            let [x0, y0] = Self::cell_origin(cell_size, Self::cell(c));
+
+            let glyph = font.scaled_glyph(char::from(c));
+            let Some(outline) = font.outline_glyph(glyph) else {
+                // Whitespace doesn't have an outline.
+                continue;
+            };
+            let bounds = outline.px_bounds();
+
+            outline.draw(|x, y, coverage| {
+                let x = bounds.min.x + x as f32;
+                let y = bounds.min.y + font.ascent() + y as f32;
+
+                if x < 0. || y < 0. {
+                    return;
+                }
+                let [x, y] = [x as u32, y as u32];
+                if x >= cell_width || y >= cell_height {
+                    return;
+                }
+
+                let i = ((y0 + y) * width + x0 + x) as usize;
+                pixels[i] = (coverage * 255.) as u8;
+            });
+        }
+
+        let [x0, y0] = Self::cell_origin(cell_size, Self::background_cell());
+        for y in y0..y0 + cell_height {
+            for x in x0..x0 + cell_width {
+                pixels[(y * width + x) as usize] = u8::MAX;
+            }
+        }
+
+        let texture = device.create_texture_with_data(
+            queue,
+            &wgpu::TextureDescriptor {
+                label: Some("Glyph Atlas"),
+                size: wgpu::Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::R8Unorm,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING
+                    | wgpu::TextureUsages::COPY_DST,
+                view_formats: &[],
+            },
+            wgpu::util::TextureDataOrder::LayerMajor,
+            &pixels,
+        );
+
+        Self { texture, cell_size }
+    }
+
+    fn gpu_memory(&self) -> u64 {
+        let size = self.texture.size();
+        u64::from(size.width) * u64::from(size.height)
+    }
+
+    /// Create the vertices for displaying the text in the top-left corner
+    fn layout(
+        &self,
+        text: &str,
+        screen_size: ScreenSize,
+    ) -> Vec<OverlayVertex> {
+        let mut vertices = Vec::new();
+
+        if text.is_empty() {
+            return vertices;
+        }
+
+        let [cell_width, cell_height] = self.cell_size.map(|size| size as f32);
+        let num_columns =
+            text.lines().map(|line| line.len()).max().unwrap_or(0) as f32;
+        let num_lines = text.lines().count() as f32;
+
+        let background = [
+            [0., 0.],
+            [
+                num_columns * cell_width + Self::MARGIN * 2.,
+                num_lines * cell_height + Self::MARGIN * 2.,
+            ],
+        ];
+        self.push_quad(
+            background,
+            Self::background_cell(),
+            [0., 0., 0., 0.6],
+            screen_size,
+            &mut vertices,
+        );
+
+        for (row, line) in text.lines().enumerate() {
+            for (column, c) in line.bytes().enumerate() {
+                let c = if (Self::FIRST_CHAR..=Self::LAST_CHAR).contains(&c) {
+                    c
+                } else {
+                    b'?'
+                };
+
+                let min = [
+                    Self::MARGIN + column as f32 * cell_width,
+                    Self::MARGIN + row as f32 * cell_height,
+                ];
+                let max = [min[0] + cell_width, min[1] + cell_height];
+
+                self.push_quad(
+                    [min, max],
+                    Self::cell(c),
+                    [1., 1., 1., 1.],
+                    screen_size,
+                    &mut vertices,
+                );
+            }
+        }
+
+        vertices
+    }
+
+    /// Create the vertices for displaying a legend in the bottom-left corner
+    fn layout_legend(
+        &self,
+        legend: &Legend,
+        screen_size: ScreenSize,
+    ) -> Vec<OverlayVertex> {
+        let mut vertices = Vec::new();
+
+        let [cell_width, cell_height] = self.cell_size.map(|size| size as f32);
+
+        // Each entry is a color swatch, two characters wide, followed by a
+        // space and the label.
+        let lines = [(None, legend.title.as_str())]
+            .into_iter()
+            .chain(
+                legend
+                    .entries
+                    .iter()
+                    .map(|(label, color)| (Some(*color), label.as_str())),
+            )
+            .collect::<Vec<_>>();
+        let num_columns = lines
+            .iter()
+            .map(|(color, text)| text.len() + color.map_or(0, |_| 3))
+            .max()
+            .unwrap_or(0) as f32;
+        let num_lines = lines.len() as f32;
+
+        let height = num_lines * cell_height + Self::MARGIN * 2.;
+        let top = screen_size.height as f32 - height;
+
+        self.push_quad(
+            [
+                [0., top],
+                [num_columns * cell_width + Self::MARGIN * 2., top + height],
+            ],
+            Self::background_cell(),
+            [0., 0., 0., 0.6],
+            screen_size,
+            &mut vertices,
+        );
+
+        for (row, (color, text)) in lines.into_iter().enumerate() {
+            let y = top + Self::MARGIN + row as f32 * cell_height;
+            let mut x = Self::MARGIN;
+
+            if let Some(color) = color {
+                self.push_quad(
+                    [[x, y + 1.], [x + cell_width * 2., y + cell_height - 1.]],
+                    Self::background_cell(),
+                    color.0.map(|v| f32::from(v) / 255.),
+                    screen_size,
+                    &mut vertices,
+                );
+                x += cell_width * 3.;
+            }
+
+            for c in text.bytes() {
+                let c = if (Self::FIRST_CHAR..=Self::LAST_CHAR).contains(&c) {
+                    c
+                } else {
+                    b'?'
+                };
+
+                self.push_quad(
+                    [[x, y], [x + cell_width, y + cell_height]],
+                    Self::cell(c),
+                    [1., 1., 1., 1.],
+                    screen_size,
+                    &mut vertices,
+                );
+                x += cell_width;
+            }
+        }
+
+        vertices
+    }
+
+    /// Push a quad, defined by its corners in pixel coordinates
+    fn push_quad(
+        &self,
+        [min, max]: [[f32; 2]; 2],
+        cell: u32,
+        color: [f32; 4],
+        screen_size: ScreenSize,
+        vertices: &mut Vec<OverlayVertex>,
+    ) {
+        let size = self.texture.size();
+        let [x0, y0] = Self::cell_origin(self.cell_size, cell);
+        let [x1, y1] = [x0 + self.cell_size[0], y0 + self.cell_size[1]];
+        let [u0, u1] = [x0, x1].map(|x| x as f32 / size.width as f32);
+        let [v0, v1] = [y0, y1].map(|y| y as f32 / size.height as f32);
+
+        let [width, height] = [screen_size.width, screen_size.height]
+            .map(|size| size.max(1) as f32);
+        let to_ndc =
+            |[x, y]: [f32; 2]| [x / width * 2. - 1., 1. - y / height * 2.];
+
+        let vertex = |position, tex_coords| OverlayVertex {
+            position: to_ndc(position),
+            tex_coords,
+            color,
+        };
+        let [a, b, c, d] = [
+            vertex([min[0], min[1]], [u0, v0]),
+            vertex([max[0], min[1]], [u1, v0]),
+            vertex([max[0], max[1]], [u1, v1]),
+            vertex([min[0], max[1]], [u0, v1]),
+        ];
+
+        vertices.extend([a, b, c, a, c, d]);
+    }
+
+    fn cell(c: u8) -> u32 {
+        u32::from(c - Self::FIRST_CHAR)
+    }
+
+    fn background_cell() -> u32 {
+        Self::cell(Self::LAST_CHAR) + 1
+    }
+
+    fn cell_origin([cell_width, cell_height]: [u32; 2], cell: u32) -> [u32; 2] {
+        [
+            cell % Self::COLUMNS * cell_width,
+            cell / Self::COLUMNS * cell_height,
+        ]
+    }
+}
+
+/// A legend that explains the colors of a scalar field
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Legend {
+    /// The title of the legend
+    pub title: String,
+
+    /// The labels and colors of the entries, from top to bottom
+    pub entries: Vec<(String, Color)>,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct OverlayVertex {
+    position: [f32; 2],
+    tex_coords: [f32; 2],
+    color: [f32; 4],
+}
+
+impl OverlayVertex {
+    const ATTRIBUTES: [wgpu::VertexAttribute; 3] = wgpu::vertex_attr_array![
+        0 => Float32x2,
+        1 => Float32x2,
+        2 => Float32x4,
+    ];
+
+    fn desc() -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: size_of::<Self>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &Self::ATTRIBUTES,
+        }
+    }
+}
@@ -9,7 +9,9 @@ use super::{
 #[derive(Debug)]
 pub struct Pipelines {
     pub model: Pipeline,
+    pub face_orientation: Pipeline,
     pub mesh: Option<Pipeline>,
+    pub reference: Pipeline,
 }
 
 impl Pipelines {
@@ -18,6 +20,7 @@ impl Pipelines {
         bind_group_layout: &wgpu::BindGroupLayout,
         color_format: wgpu::TextureFormat,
         features: wgpu::Features,
+        shaders: &Shaders,
     ) -> Self {
         let pipeline_layout =
             device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
@@ -26,8 +29,6 @@ impl Pipelines {
                 push_constant_ranges: &[],
             });
 
-        let shaders = Shaders::new(device);
-
         let model = Pipeline::new(
             device,
             &pipeline_layout,
@@ -35,6 +36,16 @@ impl Pipelines {
             wgpu::PrimitiveTopology::TriangleList,
             wgpu::PolygonMode::Fill,
             color_format,
+            false,
+        );
+        let face_orientation = Pipeline::new(
+            device,
+            &pipeline_layout,
+            shaders.face_orientation(),
+            wgpu::PrimitiveTopology::TriangleList,
+            wgpu::PolygonMode::Fill,
+            color_format,
+            false,
         );
 
         let mesh = if features.contains(wgpu::Features::POLYGON_MODE_LINE) {
@@ -48,12 +59,28 @@ impl Pipelines {
                 wgpu::PrimitiveTopology::TriangleList,
                 wgpu::PolygonMode::Line,
                 color_format,
+                false,
             ))
         } else {
             None
         };
 
-        Self { model, mesh }
+        let reference = Pipeline::new(
+            device,
+            &pipeline_layout,
+            shaders.model(),
+            wgpu::PrimitiveTopology::TriangleList,
+            wgpu::PolygonMode::Fill,
+            color_format,
+            true,
+        );
+
+        Self {
+            model,
+            face_orientation,
+            mesh,
+            reference,
+        }
     }
 }
 
@@ -61,6 +88,10 @@ impl Pipelines {
 pub struct Pipeline(pub wgpu::RenderPipeline);
 
 impl Pipeline {
+    /// Create a pipeline
+    ///
+    /// Transparent geometry is blended with whatever has been drawn before,
+    /// but doesn't hide anything that is drawn after it.
     fn new(
         device: &wgpu::Device,
         pipeline_layout: &wgpu::PipelineLayout,
@@ -68,6 +99,7 @@ impl Pipeline {
         topology: wgpu::PrimitiveTopology,
         polygon_mode: wgpu::PolygonMode,
         color_format: wgpu::TextureFormat,
+        transparent: bool,
     ) -> Self {
         let pipeline =
             device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
@@ -97,7 +129,7 @@ impl Pipeline {
                 },
                 depth_stencil: Some(wgpu::DepthStencilState {
                     format: DEPTH_FORMAT,
-                    depth_write_enabled: true,
+                    depth_write_enabled: !transparent,
                     depth_compare: wgpu::CompareFunction::LessEqual,
                     stencil: wgpu::StencilState {
                         front: wgpu::StencilFaceState::IGNORE,
@@ -110,7 +142,7 @@ impl Pipeline {
                 multisample: wgpu::MultisampleState {
                     count: SAMPLE_COUNT,
                     mask: !0,
-                    alpha_to_coverage_enabled: true,
+                    alpha_to_coverage_enabled: !transparent,
                 },
                 fragment: Some(wgpu::FragmentState {
                     module: shader.module,
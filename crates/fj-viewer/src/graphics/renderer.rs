@@ -1,5 +1,7 @@
 use std::{io, mem::size_of, vec};
 
+use fj_interop::{Color, Mesh};
+use fj_math::{Aabb, Point};
 use thiserror::Error;
 use tracing::{debug, error, trace};
 use wgpu::util::DeviceExt as _;
@@ -7,13 +9,23 @@ use wgpu::util::DeviceExt as _;
 use crate::{
     camera::Camera,
     screen::{Screen, ScreenSize},
+    ReferenceMesh, ScalarField,
 };
 
 use super::{
-    device::Device, draw_config::DrawConfig, drawables::Drawables,
-    geometries::Geometries, navigation_cube::NavigationCubeRenderer,
-    pipelines::Pipelines, transform::Transform, uniforms::Uniforms,
-    vertices::Vertices, DeviceError, DEPTH_FORMAT, SAMPLE_COUNT,
+    device::Device,
+    draw_config::DrawConfig,
+    drawables::Drawables,
+    frustum::Frustum,
+    geometries::{Geometries, Geometry},
+    navigation_cube::NavigationCubeRenderer,
+    overlay::{Legend, OverlayRenderer},
+    pipelines::Pipelines,
+    shaders::Shaders,
+    transform::Transform,
+    uniforms::Uniforms,
+    vertices::Vertices,
+    DeviceError, DEPTH_FORMAT, SAMPLE_COUNT,
 };
 
 /// Graphics rendering state and target abstraction
@@ -29,10 +41,26 @@ pub struct Renderer {
     uniform_buffer: wgpu::Buffer,
     bind_group: wgpu::BindGroup,
 
+    // The reference mesh is placed independently of the model, so it needs
+    // its own uniforms.
+    reference_uniform_buffer: wgpu::Buffer,
+    reference_bind_group: wgpu::BindGroup,
+
     geometries: Geometries,
     pipelines: Pipelines,
+    reference: Option<Geometry>,
+    reference_placement: fj_math::Transform,
 
     navigation_cube_renderer: NavigationCubeRenderer,
+    overlay_renderer: OverlayRenderer,
+
+    num_draw_calls: u32,
+
+    // Needed to re-create the pipelines, when the shaders are reloaded.
+    #[cfg(feature = "hot-reload")]
+    bind_group_layout: wgpu::BindGroupLayout,
+    #[cfg(feature = "hot-reload")]
+    features: wgpu::Features,
 }
 
 impl Renderer {
@@ -127,14 +155,6 @@ impl Renderer {
         let depth_view =
             Self::create_depth_buffer(&device.device, &surface_config);
 
-        let uniform_buffer = device.device.create_buffer_init(
-            &wgpu::util::BufferInitDescriptor {
-                label: None,
-                contents: bytemuck::cast_slice(&[Uniforms::default()]),
-                usage: wgpu::BufferUsages::UNIFORM
-                    | wgpu::BufferUsages::COPY_DST,
-            },
-        );
         let bind_group_layout = device.device.create_bind_group_layout(
             &wgpu::BindGroupLayoutDescriptor {
                 entries: &[wgpu::BindGroupLayoutEntry {
@@ -154,28 +174,23 @@ impl Renderer {
                 label: None,
             },
         );
-        let bind_group =
-            device.device.create_bind_group(&wgpu::BindGroupDescriptor {
-                layout: &bind_group_layout,
-                entries: &[wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: wgpu::BindingResource::Buffer(
-                        wgpu::BufferBinding {
-                            buffer: &uniform_buffer,
-                            offset: 0,
-                            size: None,
-                        },
-                    ),
-                }],
-                label: None,
-            });
+        let (uniform_buffer, bind_group) =
+            Self::create_uniforms(&device.device, &bind_group_layout);
+        let (reference_uniform_buffer, reference_bind_group) =
+            Self::create_uniforms(&device.device, &bind_group_layout);
 
-        let geometries = Geometries::new(&device.device, &Vertices::empty());
+        let geometries = Geometries::new(
+            &device.device,
+            &Mesh::new(),
+            Aabb::default(),
+            None,
+        );
         let pipelines = Pipelines::new(
             &device.device,
             &bind_group_layout,
             color_format,
             features,
+            &Shaders::new(&device.device),
         );
 
         let navigation_cube_renderer = NavigationCubeRenderer::new(
@@ -183,6 +198,11 @@ impl Renderer {
             &device.queue,
             &surface_config,
         );
+        let overlay_renderer = OverlayRenderer::new(
+            &device.device,
+            &device.queue,
+            &surface_config,
+        );
 
         Ok(Self {
             surface,
@@ -195,16 +215,118 @@ impl Renderer {
             uniform_buffer,
             bind_group,
 
+            reference_uniform_buffer,
+            reference_bind_group,
+
             geometries,
             pipelines,
+            reference: None,
+            reference_placement: fj_math::Transform::identity(),
 
             navigation_cube_renderer,
+            overlay_renderer,
+
+            num_draw_calls: 0,
+
+            #[cfg(feature = "hot-reload")]
+            bind_group_layout,
+            #[cfg(feature = "hot-reload")]
+            features,
         })
     }
 
+    /// Replace the shaders used for rendering the model
+    ///
+    /// The source is expected to be valid WGSL that provides the same entry
+    /// points as the built-in shaders.
+    #[cfg(feature = "hot-reload")]
+    pub fn reload_shaders(&mut self, source: &str) {
+        self.pipelines = Pipelines::new(
+            &self.device.device,
+            &self.bind_group_layout,
+            self.surface_config.format,
+            self.features,
+            &Shaders::from_source(&self.device.device, source),
+        );
+    }
+
     /// Updates the geometry of the model being rendered.
-    pub fn update_geometry(&mut self, mesh: Vertices) {
-        self.geometries = Geometries::new(&self.device.device, &mesh);
+    ///
+    /// If a scalar field is provided, the model is colored accordingly.
+    pub fn update_geometry(
+        &mut self,
+        mesh: &Mesh<Point<3>>,
+        aabb: Aabb<3>,
+        scalar_field: Option<&ScalarField>,
+    ) {
+        self.geometries =
+            Geometries::new(&self.device.device, mesh, aabb, scalar_field);
+    }
+
+    /// Updates the geometry of the reference mesh, or removes it
+    ///
+    /// The reference mesh is colored according to its opacity. Its placement
+    /// is not taken into account here. See [`Renderer::set_reference_placement`].
+    pub fn update_reference_geometry(
+        &mut self,
+        reference: Option<&ReferenceMesh>,
+    ) {
+        self.reference = reference.map(|reference| {
+            let Color([r, g, b, _]) = ReferenceMesh::COLOR;
+            let a = (reference.opacity() * 255.).round() as u8;
+
+            let mesh = reference.mesh();
+            let corner_colors =
+                vec![[Color([r, g, b, a]); 3]; mesh.triangles().count()];
+
+            let vertices = Vertices::new(mesh, Some(&corner_colors));
+            Geometry::new(
+                &self.device.device,
+                vertices.vertices(),
+                vertices.indices(),
+            )
+        });
+    }
+
+    /// Set the transform that places the reference mesh within the model
+    pub fn set_reference_placement(&mut self, placement: fj_math::Transform) {
+        self.reference_placement = placement;
+    }
+
+    /// Set the legend that is displayed on top of the model
+    pub fn set_overlay_legend(&mut self, legend: Option<Legend>) {
+        self.overlay_renderer.set_legend(legend);
+    }
+
+    /// Set the text that is displayed on top of the model
+    ///
+    /// Nothing is displayed, if the text is empty.
+    pub fn set_overlay_text(&mut self, text: &str) {
+        self.overlay_renderer.set_text(text);
+    }
+
+    /// Access statistics about the rendering
+    pub fn stats(&self) -> RenderStats {
+        let num_pixels = u64::from(self.surface_config.width)
+            * u64::from(self.surface_config.height);
+
+        // This is just an estimate. We don't know the exact layout of the
+        // buffers in GPU memory, nor what the surface itself occupies.
+        let render_targets = num_pixels * 4 * u64::from(SAMPLE_COUNT) * 2;
+        let geometries = self.geometries.mesh.vertex_buffer.size()
+            + self.geometries.mesh.index_buffer.size()
+            + self.geometries.lods.buffer_size()
+            + self.reference.as_ref().map_or(0, |reference| {
+                reference.vertex_buffer.size() + reference.index_buffer.size()
+            });
+
+        RenderStats {
+            num_triangles: self.geometries.mesh.num_indices / 3,
+            num_draw_calls: self.num_draw_calls,
+            gpu_memory: render_targets
+                + geometries
+                + self.overlay_renderer.gpu_memory(),
+        }
     }
 
     /// Resizes the render surface.
@@ -236,16 +358,25 @@ impl Renderer {
     ) -> Result<(), DrawError> {
         let aspect_ratio = f64::from(self.surface_config.width)
             / f64::from(self.surface_config.height);
-        let uniforms = Uniforms {
-            transform: Transform::for_vertices(camera, aspect_ratio),
-            transform_normals: Transform::for_normals(camera),
+        let uniforms_for = |placement: &fj_math::Transform| Uniforms {
+            transform: Transform::for_vertices(camera, placement, aspect_ratio),
+            transform_normals: Transform::for_normals(camera, placement),
         };
+        let uniforms = uniforms_for(&fj_math::Transform::identity());
+        let reference_uniforms = uniforms_for(&self.reference_placement);
+        let frustum = Frustum::new(&uniforms.transform);
+        let reference_frustum = Frustum::new(&reference_uniforms.transform);
 
         self.device.queue.write_buffer(
             &self.uniform_buffer,
             0,
             bytemuck::cast_slice(&[uniforms]),
         );
+        self.device.queue.write_buffer(
+            &self.reference_uniform_buffer,
+            0,
+            bytemuck::cast_slice(&[reference_uniforms]),
+        );
 
         let surface_texture = match self.surface.get_current_texture() {
             Ok(surface_texture) => surface_texture,
@@ -269,6 +400,8 @@ impl Renderer {
             &wgpu::CommandEncoderDescriptor { label: None },
         );
 
+        let mut num_draw_calls = 0;
+
         // Need this block here, as a render pass only takes effect once it's
         // dropped.
         {
@@ -299,15 +432,43 @@ impl Renderer {
                 });
             render_pass.set_bind_group(0, &self.bind_group, &[]);
 
-            let drawables = Drawables::new(&self.geometries, &self.pipelines);
+            let lod = self
+                .geometries
+                .lods
+                .select(camera, self.surface_config.width);
+            let drawables = Drawables::new(
+                &self.geometries,
+                &self.pipelines,
+                lod,
+                self.reference.as_ref(),
+            );
 
             if config.draw_model {
-                drawables.model.draw(&mut render_pass);
+                let drawable = if config.draw_face_orientation {
+                    &drawables.face_orientation
+                } else {
+                    &drawables.model
+                };
+                num_draw_calls += drawable.draw(&mut render_pass, &frustum);
             }
 
             if let Some(drawable) = drawables.mesh {
                 if config.draw_mesh {
-                    drawable.draw(&mut render_pass);
+                    num_draw_calls += drawable.draw(&mut render_pass, &frustum);
+                }
+            }
+
+            // The reference mesh is transparent, so it must be drawn last, to
+            // let the model shine through.
+            if let Some(drawable) = drawables.reference {
+                if config.draw_reference {
+                    render_pass.set_bind_group(
+                        0,
+                        &self.reference_bind_group,
+                        &[],
+                    );
+                    num_draw_calls +=
+                        drawable.draw(&mut render_pass, &reference_frustum);
                 }
             }
         }
@@ -319,6 +480,19 @@ impl Renderer {
             aspect_ratio,
             camera.rotation,
         );
+        num_draw_calls += self.navigation_cube_renderer.num_draw_calls();
+
+        num_draw_calls += self.overlay_renderer.draw(
+            &color_view,
+            &mut encoder,
+            &self.device.device,
+            ScreenSize {
+                width: self.surface_config.width,
+                height: self.surface_config.height,
+            },
+        );
+
+        self.num_draw_calls = num_draw_calls;
 
         let command_buffer = encoder.finish();
         self.device.queue.submit(Some(command_buffer));
@@ -330,6 +504,33 @@ impl Renderer {
         Ok(())
     }
 
+    fn create_uniforms(
+        device: &wgpu::Device,
+        bind_group_layout: &wgpu::BindGroupLayout,
+    ) -> (wgpu::Buffer, wgpu::BindGroup) {
+        let uniform_buffer =
+            device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: None,
+                contents: bytemuck::cast_slice(&[Uniforms::default()]),
+                usage: wgpu::BufferUsages::UNIFORM
+                    | wgpu::BufferUsages::COPY_DST,
+            });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                    buffer: &uniform_buffer,
+                    offset: 0,
+                    size: None,
+                }),
+            }],
+            label: None,
+        });
+
+        (uniform_buffer, bind_group)
+    }
+
     fn create_frame_buffer(
         device: &wgpu::Device,
         surface_config: &wgpu::SurfaceConfiguration,
@@ -374,6 +575,21 @@ impl Renderer {
     }
 }
 
+/// Statistics about the rendering
+///
+/// Returned by [`Renderer::stats`].
+#[derive(Clone, Copy, Debug)]
+pub struct RenderStats {
+    /// The number of triangles of the model
+    pub num_triangles: u32,
+
+    /// The number of draw calls made during the last frame
+    pub num_draw_calls: u32,
+
+    /// An estimate of the GPU memory used, in bytes
+    pub gpu_memory: u64,
+}
+
 /// Error describing the set of render surface initialization errors
 #[derive(Error, Debug)]
 pub enum RendererInitError {
@@ -56,3 +56,30 @@ fn frag_mesh(in: VertexOutput) -> FragmentOutput {
     out.color = vec4<f32>(1.0 - in.color.rgb, in.color.a);
     return out;
 }
+
+@fragment
+fn frag_face_orientation(
+    in: VertexOutput,
+    @builtin(front_facing) front_facing: bool,
+) -> FragmentOutput {
+    // The normal is derived from the winding of the triangle, so it points
+    // away from the viewer, if we're looking at the back of the triangle.
+    var color = vec3<f32>(0.2, 0.4, 1.0);
+    var normal = in.normal;
+    if !front_facing {
+        color = vec3<f32>(1.0, 0.2, 0.2);
+        normal = -normal;
+    }
+
+    let light = vec3<f32>(0.0, 0.0, -1.0);
+
+    let angle = acos(dot(light, -normal));
+    let f_angle = angle / (pi * 0.75);
+
+    let f_normal = max(1.0 - f_angle, 0.0);
+
+    var out: FragmentOutput;
+    out.color = vec4<f32>(color * f_normal, 1.0);
+
+    return out;
+}
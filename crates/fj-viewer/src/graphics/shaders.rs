@@ -4,12 +4,14 @@ pub struct Shaders(wgpu::ShaderModule);
 
 impl Shaders {
     pub fn new(device: &wgpu::Device) -> Self {
+        Self::from_source(device, include_str!("shader.wgsl"))
+    }
+
+    pub fn from_source(device: &wgpu::Device, source: &str) -> Self {
         let module =
             device.create_shader_module(wgpu::ShaderModuleDescriptor {
                 label: None,
-                source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(include_str!(
-                    "shader.wgsl"
-                ))),
+                source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(source)),
             });
 
         Self(module)
@@ -28,6 +30,13 @@ impl Shaders {
             frag_entry: "frag_mesh",
         }
     }
+
+    pub fn face_orientation(&self) -> Shader {
+        Shader {
+            module: &self.0,
+            frag_entry: "frag_face_orientation",
+        }
+    }
 }
 
 #[derive(Clone, Copy)]
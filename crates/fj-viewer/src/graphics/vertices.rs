@@ -1,5 +1,5 @@
 use bytemuck::{Pod, Zeroable};
-use fj_interop::{Index, Mesh};
+use fj_interop::{Color, Index, Mesh};
 
 #[derive(Debug)]
 pub struct Vertices {
@@ -8,35 +8,27 @@ pub struct Vertices {
 }
 
 impl Vertices {
-    pub fn empty() -> Self {
-        Self {
-            vertices: Vec::new(),
-            indices: Vec::new(),
-        }
-    }
-
-    pub fn vertices(&self) -> &[Vertex] {
-        self.vertices.as_slice()
-    }
-
-    pub fn indices(&self) -> &[Index] {
-        self.indices.as_slice()
-    }
-}
-
-impl From<&Mesh<fj_math::Point<3>>> for Vertices {
-    fn from(mesh: &Mesh<fj_math::Point<3>>) -> Self {
+    /// Convert the mesh, coloring the corners of its triangles as provided
+    ///
+    /// The colors of the triangles are used, if no corner colors are provided.
+    pub fn new(
+        mesh: &Mesh<fj_math::Point<3>>,
+        corner_colors: Option<&[[Color; 3]]>,
+    ) -> Self {
         let mut m = Mesh::new();
 
-        for triangle in mesh.triangles() {
+        for (i, triangle) in mesh.triangles().enumerate() {
             let [a, b, c] = triangle.inner.points();
 
             let normal = (b - a).cross(&(c - a)).normalize();
-            let color = triangle.color;
+            let [color_a, color_b, color_c] = corner_colors
+                .and_then(|colors| colors.get(i))
+                .copied()
+                .unwrap_or([triangle.color; 3]);
 
-            m.push_vertex((a, normal, color));
-            m.push_vertex((b, normal, color));
-            m.push_vertex((c, normal, color));
+            m.push_vertex((a, normal, color_a));
+            m.push_vertex((b, normal, color_b));
+            m.push_vertex((c, normal, color_c));
         }
 
         let vertices = m
@@ -52,6 +44,20 @@ impl From<&Mesh<fj_math::Point<3>>> for Vertices {
 
         Self { vertices, indices }
     }
+
+    pub fn vertices(&self) -> &[Vertex] {
+        self.vertices.as_slice()
+    }
+
+    pub fn indices(&self) -> &[Index] {
+        self.indices.as_slice()
+    }
+}
+
+impl From<&Mesh<fj_math::Point<3>>> for Vertices {
+    fn from(mesh: &Mesh<fj_math::Point<3>>) -> Self {
+        Self::new(mesh, None)
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
@@ -0,0 +1,230 @@
+//! Hot reloading of shaders and renderer configuration
+//!
+//! This is a development aid, that is only available with the `hot-reload`
+//! feature. It allows iterating on the shaders and on the [`DrawConfig`]
+//! without recompiling and restarting the viewer.
+//!
+//! The files are polled for changes on every draw. The shader is read from the
+//! path in the `FJ_VIEWER_SHADER` environment variable, or from the built-in
+//! shader's source file, if that is not set. The renderer configuration is
+//! only loaded, if the `FJ_VIEWER_CONFIG` environment variable is set. It
+//! expects lines in the form of `key = value`, where the keys are the fields
+//! of [`DrawConfig`].
+//!
+//! Invalid shaders or configuration files are reported, and the previous ones
+//! stay in use.
+
+use std::{
+    fs, io,
+    path::PathBuf,
+    time::{Duration, Instant, SystemTime},
+};
+
+use tracing::{info, warn};
+use wgpu::naga::{
+    front::wgsl,
+    valid::{Capabilities, ValidationFlags, Validator},
+};
+
+use crate::graphics::{DrawConfig, Renderer};
+
+/// Watches the shader and configuration files, and applies their changes
+pub struct HotReload {
+    shader: WatchedFile,
+    config: Option<WatchedFile>,
+    last_check: Option<Instant>,
+}
+
+impl HotReload {
+    /// How often the files are checked for changes
+    const INTERVAL: Duration = Duration::from_millis(250);
+
+    /// Construct a new instance of `HotReload`
+    ///
+    /// The built-in shader is what the renderer starts out with, so it isn't
+    /// reloaded until it actually changes.
+    pub fn new() -> Self {
+        let shader = match std::env::var_os("FJ_VIEWER_SHADER") {
+            Some(path) => WatchedFile::new(path.into()),
+            None => WatchedFile::unchanged(
+                concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/src/graphics/shader.wgsl"
+                )
+                .into(),
+            ),
+        };
+        let config = std::env::var_os("FJ_VIEWER_CONFIG")
+            .map(|path| WatchedFile::new(path.into()));
+
+        Self {
+            shader,
+            config,
+            last_check: None,
+        }
+    }
+
+    /// Reload the files that have changed since the last check
+    pub fn update(
+        &mut self,
+        renderer: &mut Renderer,
+        draw_config: &mut DrawConfig,
+    ) {
+        let now = Instant::now();
+        if let Some(last_check) = self.last_check {
+            if now.duration_since(last_check) < Self::INTERVAL {
+                return;
+            }
+        }
+        self.last_check = Some(now);
+
+        if let Some(source) = self.shader.read_if_changed() {
+            match validate_shader(&source) {
+                Ok(()) => {
+                    renderer.reload_shaders(&source);
+                    info!(
+                        "Reloaded shader from `{}`",
+                        self.shader.path.display()
+                    );
+                }
+                Err(err) => {
+                    warn!(
+                        "Not reloading invalid shader from `{}`:\n{err}",
+                        self.shader.path.display()
+                    );
+                }
+            }
+        }
+
+        if let Some(config) = &mut self.config {
+            if let Some(source) = config.read_if_changed() {
+                match parse_config(&source) {
+                    Ok(new_config) => {
+                        *draw_config = new_config;
+                        info!(
+                            "Reloaded config from `{}`",
+                            config.path.display()
+                        );
+                    }
+                    Err(err) => {
+                        warn!(
+                            "Not reloading invalid config from `{}`: {err}",
+                            config.path.display()
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Default for HotReload {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+struct WatchedFile {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+}
+
+impl WatchedFile {
+    /// Watch a file that has not been loaded yet
+    fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            modified: None,
+        }
+    }
+
+    /// Watch a file whose current contents are already in use
+    fn unchanged(path: PathBuf) -> Self {
+        let modified = modified(&path).ok();
+        Self { path, modified }
+    }
+
+    fn read_if_changed(&mut self) -> Option<String> {
+        let modified = match modified(&self.path) {
+            Ok(modified) => modified,
+            Err(err) => {
+                // Only report the error once, not on every check.
+                if self.modified.take().is_some() {
+                    warn!("Can't watch `{}`: {err}", self.path.display());
+                }
+                return None;
+            }
+        };
+
+        if self.modified == Some(modified) {
+            return None;
+        }
+        self.modified = Some(modified);
+
+        match fs::read_to_string(&self.path) {
+            Ok(source) => Some(source),
+            Err(err) => {
+                warn!("Failed to read `{}`: {err}", self.path.display());
+                None
+            }
+        }
+    }
+}
+
+fn modified(path: &PathBuf) -> io::Result<SystemTime> {
+    fs::metadata(path)?.modified()
+}
+
+/// Check the shader, before handing it to the renderer
+///
+/// `wgpu` treats invalid shaders as a fatal error, so we can't just try to use
+/// them and see what happens.
+fn validate_shader(source: &str) -> Result<(), String> {
+    let module =
+        wgsl::parse_str(source).map_err(|err| err.emit_to_string(source))?;
+    Validator::new(ValidationFlags::all(), Capabilities::all())
+        .validate(&module)
+        .map_err(|err| err.emit_to_string(source))?;
+
+    Ok(())
+}
+
+fn parse_config(source: &str) -> Result<DrawConfig, String> {
+    let mut config = DrawConfig::default();
+
+    for (i, line) in source.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let line_number = i + 1;
+
+        let Some((key, value)) = line.split_once('=') else {
+            return Err(format!(
+                "Expected `key = value` in line {line_number}"
+            ));
+        };
+        let value = value.trim().parse::<bool>().map_err(|err| {
+            format!("Invalid value in line {line_number}: {err}")
+        })?;
+
+        match key.trim() {
+            "draw_model" => config.draw_model = value,
+            "draw_mesh" => config.draw_mesh = value,
+            "draw_face_orientation" => {
+                config.draw_face_orientation = value;
+            }
+            "draw_reference" => config.draw_reference = value,
+            "draw_stats" => config.draw_stats = value,
+            "draw_cursor_position" => config.draw_cursor_position = value,
+            key => {
+                return Err(format!(
+                    "Unknown key `{key}` in line {line_number}"
+                ))
+            }
+        }
+    }
+
+    Ok(config)
+}
@@ -0,0 +1,130 @@
+use std::collections::VecDeque;
+
+use crate::screen::NormalizedScreenPosition;
+
+/// A semantic input action
+///
+/// Actions describe what the user wants to do, independently of the physical
+/// input that triggered them. Whoever handles the physical input (a window, for
+/// example) translates it into actions, according to its bindings, and pushes
+/// them into an [`ActionQueue`].
+#[derive(Clone, Copy, Debug)]
+pub enum Action {
+    /// Start rotating the model around the focus point
+    ///
+    /// The model is rotated as the cursor moves, until [`Action::RotateEnd`].
+    RotateStart,
+
+    /// Stop rotating the model
+    RotateEnd,
+
+    /// Start moving the model up, down, left or right
+    ///
+    /// The model is moved as the cursor moves, until [`Action::PanEnd`].
+    PanStart,
+
+    /// Stop moving the model
+    PanEnd,
+
+    /// The cursor has moved to the provided position
+    CursorMoved(NormalizedScreenPosition),
+
+    /// Move the view forwards and backwards
+    ZoomDelta(f64),
+
+    /// Move and rotate the model in all six degrees of freedom
+    ///
+    /// This is meant for 6-DoF input devices, like space mice. All values are
+    /// relative to the screen, with x pointing right, y pointing up, and z
+    /// pointing towards the viewer.
+    Motion {
+        /// The movement of the model
+        ///
+        /// Relative to the distance between the camera and the focus point.
+        translation: [f64; 3],
+
+        /// The rotation of the model around the focus point
+        ///
+        /// Angles (in radians) around the x, y, and z axes.
+        rotation: [f64; 3],
+    },
+
+    /// Stop moving the model in all six degrees of freedom
+    ///
+    /// Sent once the input device that sent [`Action::Motion`] is at rest.
+    MotionEnd,
+
+    /// Rotate around the face under the cursor, from now on
+    ///
+    /// Instead of the point under the cursor when a rotation starts, the center
+    /// of the face is used. If the cursor doesn't point at the model, the
+    /// previously set rotation center is cleared.
+    SetRotationCenter,
+
+    /// Toggle the "draw model" setting
+    ToggleDrawModel,
+
+    /// Toggle the "draw mesh" setting
+    ToggleDrawMesh,
+
+    /// Toggle the "draw face orientation" setting
+    ToggleDrawFaceOrientation,
+
+    /// Toggle the "draw stats" setting
+    ToggleDrawStats,
+
+    /// Toggle the "draw cursor position" setting
+    ToggleDrawCursorPosition,
+
+    /// Toggle the "draw reference" setting
+    ToggleDrawReference,
+
+    /// Toggle alignment of the reference mesh
+    ///
+    /// While alignment is active, rotating, panning, and zooming move the
+    /// reference mesh relative to the model, instead of moving the view.
+    ///
+    /// See [`Viewer::set_reference_mesh`](crate::Viewer::set_reference_mesh).
+    ToggleAlignReference,
+
+    /// Make the reference mesh more opaque
+    ///
+    /// Once it is fully opaque, it becomes almost transparent again.
+    CycleReferenceOpacity,
+
+    /// Toggle snapping of the cursor position to features of the model
+    ///
+    /// See [`Viewer::cursor_position`](crate::Viewer::cursor_position).
+    ToggleSnapping,
+
+    /// Exit the application
+    ///
+    /// This is not handled by the viewer. It is up to the application to
+    /// react to it.
+    Exit,
+}
+
+/// A queue of actions, waiting to be handled
+///
+/// Actions are handled in the order they have been pushed.
+#[derive(Debug, Default)]
+pub struct ActionQueue {
+    actions: VecDeque<Action>,
+}
+
+impl ActionQueue {
+    /// Push an action to the back of the queue
+    pub fn push(&mut self, action: Action) {
+        self.actions.push_back(action);
+    }
+
+    /// Remove the action from the front of the queue
+    pub fn pop(&mut self) -> Option<Action> {
+        self.actions.pop_front()
+    }
+
+    /// Indicate whether the queue is empty
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+}
@@ -0,0 +1,39 @@
+/// Configuration of how the camera responds to input
+///
+/// The defaults are meant to feel right for most users, but preferences
+/// differ. Sensitivities are factors applied to the respective input, so
+/// higher values move the camera more with the same input.
+#[derive(Clone, Debug)]
+pub struct NavigationConfig {
+    /// How fast the model rotates, as the cursor moves
+    ///
+    /// Specified in radians per change in normalized screen position.
+    pub rotation_sensitivity: f64,
+
+    /// How fast the view moves forwards and backwards, when zooming
+    pub zoom_sensitivity: f64,
+
+    /// Keep rotating the model, after it has been released while rotating
+    ///
+    /// The rotation then slows down, according to
+    /// [`NavigationConfig::rotation_decay`].
+    pub rotation_inertia: bool,
+
+    /// How fast an inertial rotation slows down
+    ///
+    /// The speed of the rotation decays exponentially, by a factor of
+    /// `e^-rotation_decay` per second. Only has an effect, if
+    /// [`NavigationConfig::rotation_inertia`] is enabled.
+    pub rotation_decay: f64,
+}
+
+impl Default for NavigationConfig {
+    fn default() -> Self {
+        Self {
+            rotation_sensitivity: 5.,
+            zoom_sensitivity: 1.,
+            rotation_inertia: false,
+            rotation_decay: 4.,
+        }
+    }
+}
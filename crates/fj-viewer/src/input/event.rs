@@ -1,6 +1,10 @@
 use crate::screen::NormalizedScreenPosition;
 
 /// An input event
+///
+/// Input events are derived from [`Action`]s, and applied to the camera.
+///
+/// [`Action`]: super::Action
 pub enum InputEvent {
     /// Move the model up, down, left or right
     Translation {
@@ -16,6 +20,16 @@ pub enum InputEvent {
         angle_x: f64,
         /// The angle around the screen y axis to rotate (in radians)
         angle_y: f64,
+        /// The angle around the screen z axis to rotate (in radians)
+        angle_z: f64,
+    },
+
+    /// Move and rotate the model in all six degrees of freedom
+    Motion {
+        /// The movement of the model, relative to the focus point distance
+        translation: [f64; 3],
+        /// The angles around the screen axes to rotate (in radians)
+        rotation: [f64; 3],
     },
 
     /// Move the view forwards and backwards
@@ -1,13 +1,153 @@
-use super::{movement::Movement, rotation::Rotation, zoom::Zoom, InputEvent};
-use crate::camera::{Camera, FocusPoint};
+use std::time::{Duration, Instant};
+
+use super::{
+    motion::Motion, movement::Movement, rotation::Rotation, zoom::Zoom, Action,
+    InputEvent, NavigationConfig,
+};
+use crate::{
+    camera::{Camera, FocusPoint},
+    screen::NormalizedScreenPosition,
+};
 
 /// Input handling abstraction
 ///
-/// Takes user input and applies them to application state.
+/// Translates actions into input events, and applies those to application
+/// state. Keeps track of whether the model is currently being dragged, and of
+/// any inertial rotation that continues after it has been released.
 #[derive(Default)]
-pub struct InputHandler;
+pub struct InputHandler {
+    drag: Option<Drag>,
+    rotation_velocity: Option<RotationVelocity>,
+    fling: Option<Fling>,
+}
 
 impl InputHandler {
+    /// Translate an action into an input event, if it results in one
+    ///
+    /// Keeps `cursor` up to date, as the cursor moves.
+    pub fn handle_action(
+        &mut self,
+        action: Action,
+        cursor: &mut Option<NormalizedScreenPosition>,
+        config: &NavigationConfig,
+    ) -> Option<InputEvent> {
+        match action {
+            Action::RotateStart => {
+                self.drag = Some(Drag::Rotate);
+                self.rotation_velocity = None;
+                self.fling = None;
+                None
+            }
+            Action::PanStart => {
+                self.drag = Some(Drag::Pan);
+                self.fling = None;
+                None
+            }
+            Action::RotateEnd | Action::PanEnd => {
+                self.drag = None;
+                None
+            }
+            Action::CursorMoved(current) => {
+                let previous = cursor.replace(current)?;
+
+                match self.drag? {
+                    Drag::Rotate => {
+                        let diff_x = current.x - previous.x;
+                        let diff_y = current.y - previous.y;
+                        let angle_x = -diff_y * config.rotation_sensitivity;
+                        let angle_y = diff_x * config.rotation_sensitivity;
+
+                        self.track_rotation([angle_x, angle_y]);
+
+                        Some(InputEvent::Rotation {
+                            angle_x,
+                            angle_y,
+                            angle_z: 0.,
+                        })
+                    }
+                    Drag::Pan => {
+                        Some(InputEvent::Translation { previous, current })
+                    }
+                }
+            }
+            Action::ZoomDelta(delta) => {
+                Some(InputEvent::Zoom(delta * config.zoom_sensitivity))
+            }
+            Action::Motion {
+                translation,
+                rotation,
+            } => Some(InputEvent::Motion {
+                translation,
+                rotation,
+            }),
+            Action::MotionEnd
+            | Action::SetRotationCenter
+            | Action::ToggleDrawModel
+            | Action::ToggleDrawMesh
+            | Action::ToggleDrawFaceOrientation
+            | Action::ToggleDrawStats
+            | Action::ToggleDrawCursorPosition
+            | Action::ToggleDrawReference
+            | Action::ToggleAlignReference
+            | Action::CycleReferenceOpacity
+            | Action::ToggleSnapping
+            | Action::Exit => None,
+        }
+    }
+
+    /// Start an inertial rotation, if the model was released while rotating
+    ///
+    /// Does nothing, unless enabled in the configuration.
+    pub fn start_fling(
+        &mut self,
+        focus_point: Option<FocusPoint>,
+        config: &NavigationConfig,
+    ) {
+        let rotation_velocity = self.rotation_velocity.take();
+
+        if !config.rotation_inertia {
+            return;
+        }
+        let (Some(focus_point), Some(rotation_velocity), Some(now)) =
+            (focus_point, rotation_velocity, now())
+        else {
+            return;
+        };
+
+        // If the cursor stopped moving before the model was released, there
+        // shouldn't be any inertia.
+        if now.duration_since(rotation_velocity.updated) > MAX_FLING_DELAY {
+            return;
+        }
+
+        self.fling = Some(Fling {
+            focus_point,
+            velocity: rotation_velocity.velocity,
+            updated: now,
+        });
+    }
+
+    /// Advance any inertial rotation that is in progress
+    pub fn update(&mut self, config: &NavigationConfig, camera: &mut Camera) {
+        let (Some(fling), Some(now)) = (&mut self.fling, now()) else {
+            return;
+        };
+
+        let dt = now.duration_since(fling.updated).as_secs_f64();
+        fling.updated = now;
+
+        let [angle_x, angle_y] = fling.velocity.map(|v| v * dt);
+        Rotation::apply([angle_x, angle_y, 0.], fling.focus_point, camera);
+
+        let decay = (-config.rotation_decay * dt).exp();
+        fling.velocity = fling.velocity.map(|v| v * decay);
+
+        let [v_x, v_y] = fling.velocity;
+        if v_x.hypot(v_y) < MIN_FLING_VELOCITY {
+            self.fling = None;
+        }
+    }
+
     /// Handle an input event
     pub fn handle_event(
         event: InputEvent,
@@ -18,12 +158,86 @@ impl InputHandler {
             InputEvent::Translation { previous, current } => {
                 Movement::apply(previous, current, focus_point, camera);
             }
-            InputEvent::Rotation { angle_x, angle_y } => {
-                Rotation::apply(angle_x, angle_y, focus_point, camera);
+            InputEvent::Rotation {
+                angle_x,
+                angle_y,
+                angle_z,
+            } => {
+                Rotation::apply(
+                    [angle_x, angle_y, angle_z],
+                    focus_point,
+                    camera,
+                );
+            }
+            InputEvent::Motion {
+                translation,
+                rotation,
+            } => {
+                Motion::apply(translation, rotation, focus_point, camera);
             }
             InputEvent::Zoom(zoom_delta) => {
                 Zoom::apply(zoom_delta, focus_point, camera);
             }
         }
     }
+
+    fn track_rotation(&mut self, angles: [f64; 2]) {
+        let Some(now) = now() else {
+            return;
+        };
+
+        let velocity = match self.rotation_velocity {
+            Some(previous) => {
+                let dt = now.duration_since(previous.updated).as_secs_f64();
+                if dt <= 0. {
+                    return;
+                }
+
+                // Smooth out the velocity a bit, as cursor movement tends to
+                // be jittery.
+                let current = angles.map(|angle| angle / dt);
+                [0, 1].map(|i| (previous.velocity[i] + current[i]) / 2.)
+            }
+            None => [0.; 2],
+        };
+
+        self.rotation_velocity = Some(RotationVelocity {
+            velocity,
+            updated: now,
+        });
+    }
 }
+
+#[derive(Clone, Copy)]
+enum Drag {
+    Rotate,
+    Pan,
+}
+
+#[derive(Clone, Copy)]
+struct RotationVelocity {
+    velocity: [f64; 2],
+    updated: Instant,
+}
+
+struct Fling {
+    focus_point: FocusPoint,
+    velocity: [f64; 2],
+    updated: Instant,
+}
+
+pub(crate) fn now() -> Option<Instant> {
+    // `Instant::now` panics on this target, when running in a browser. There's
+    // no inertia there, for now.
+    if cfg!(target_arch = "wasm32") {
+        None
+    } else {
+        Some(Instant::now())
+    }
+}
+
+/// How long the cursor can be still before releasing, without losing inertia
+const MAX_FLING_DELAY: Duration = Duration::from_millis(50);
+
+/// Inertial rotations that are slower than this (in radians per second) stop
+const MIN_FLING_VELOCITY: f64 = 0.01;
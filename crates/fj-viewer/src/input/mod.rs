@@ -1,9 +1,19 @@
 //! User input parsing and propagation.
 
+mod action;
+mod config;
 mod event;
 mod handler;
+mod motion;
 mod movement;
 mod rotation;
 mod zoom;
 
-pub use self::{event::InputEvent, handler::InputHandler};
+pub use self::{
+    action::{Action, ActionQueue},
+    config::NavigationConfig,
+    event::InputEvent,
+    handler::InputHandler,
+};
+
+pub(crate) use self::handler::now;
@@ -0,0 +1,23 @@
+use fj_math::{Transform, Vector};
+
+use crate::camera::{Camera, FocusPoint};
+
+use super::rotation::Rotation;
+
+pub struct Motion;
+
+impl Motion {
+    pub fn apply(
+        translation: [f64; 3],
+        rotation: [f64; 3],
+        focus_point: FocusPoint,
+        camera: &mut Camera,
+    ) {
+        let distance = (focus_point.0 - camera.position()).magnitude();
+        let displacement = translation.map(|t| t * distance.into_f64());
+        camera.translation = camera.translation
+            * Transform::translation(Vector::from(displacement));
+
+        Rotation::apply(rotation, focus_point, camera);
+    }
+}
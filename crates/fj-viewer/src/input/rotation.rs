@@ -6,8 +6,7 @@ pub struct Rotation;
 
 impl Rotation {
     pub fn apply(
-        angle_x: f64,
-        angle_y: f64,
+        [angle_x, angle_y, angle_z]: [f64; 3],
         focus_point: FocusPoint,
         camera: &mut Camera,
     ) {
@@ -17,9 +16,11 @@ impl Rotation {
         let camera_rotation = camera.rotation.inverse();
         let right_vector = right_vector(&camera_rotation);
         let up_vector = up_vector(&camera_rotation);
+        let forward_vector = forward_vector(&camera_rotation);
 
         let rotation = Transform::rotation(right_vector * angle_x)
-            * Transform::rotation(up_vector * angle_y);
+            * Transform::rotation(up_vector * angle_y)
+            * Transform::rotation(forward_vector * angle_z);
 
         let transform = camera.camera_to_model()
             * rotate_around
@@ -36,6 +37,11 @@ fn up_vector(rotation: &Transform) -> Vector<3> {
     Vector::from([d[4], d[5], d[6]])
 }
 
+fn forward_vector(rotation: &Transform) -> Vector<3> {
+    let d = rotation.data();
+    Vector::from([d[8], d[9], d[10]])
+}
+
 fn right_vector(rotation: &Transform) -> Vector<3> {
     let d = rotation.data();
     Vector::from([d[0], d[1], d[2]])
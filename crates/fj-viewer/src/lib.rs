@@ -11,13 +11,25 @@
 mod assets;
 mod camera;
 mod graphics;
+#[cfg(feature = "hot-reload")]
+mod hot_reload;
 mod input;
+mod pick;
+mod reference;
+mod scalar_field;
+mod scene;
 mod screen;
+mod stats;
 mod viewer;
 
 pub use self::{
     graphics::{DeviceError, RendererInitError},
-    input::InputEvent,
+    input::{Action, ActionQueue, NavigationConfig},
+    pick::{CursorPosition, PickTarget},
+    reference::{LoadReferenceError, ReferenceMesh},
+    scalar_field::{ColorMap, ScalarField},
+    scene::{CameraKeyframe, CameraScript, CameraView, SceneConfig, UpAxis},
     screen::{NormalizedScreenPosition, Screen, ScreenSize},
+    stats::ViewerStats,
     viewer::Viewer,
 };
@@ -0,0 +1,204 @@
+//! Find the point on the model that the cursor points to
+
+use std::{collections::BTreeMap, fmt};
+
+use fj_interop::{FaceIndex, Model};
+use fj_math::{Point, Scalar, Vector};
+
+use crate::{camera::Camera, screen::NormalizedScreenPosition};
+
+/// The point in the model's coordinate system, that the cursor points to
+///
+/// Returned by [`Viewer::cursor_position`](crate::Viewer::cursor_position).
+#[derive(Clone, Copy, Debug)]
+pub struct CursorPosition {
+    /// The position
+    pub point: Point<3>,
+
+    /// What the cursor points to
+    pub target: PickTarget,
+}
+
+impl fmt::Display for CursorPosition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let [x, y, z] = self.point.coords.components.map(Scalar::into_f64);
+        write!(f, "X: {x:.3}  Y: {y:.3}  Z: {z:.3}  ({})", self.target)
+    }
+}
+
+/// What the cursor points to
+///
+/// See [`CursorPosition`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PickTarget {
+    /// A point on a face of the model
+    Face,
+
+    /// A vertex on the boundary of a face
+    ///
+    /// Only returned, if snapping is enabled.
+    Vertex,
+
+    /// The midpoint of an edge on the boundary of a face
+    ///
+    /// Only returned, if snapping is enabled.
+    EdgeMidpoint,
+
+    /// The center of a face
+    ///
+    /// Only returned, if snapping is enabled.
+    FaceCenter,
+
+    /// A point on the ground plane, which is the xy-plane
+    GroundPlane,
+}
+
+impl fmt::Display for PickTarget {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Self::Face => "face",
+            Self::Vertex => "vertex",
+            Self::EdgeMidpoint => "edge midpoint",
+            Self::FaceCenter => "face center",
+            Self::GroundPlane => "ground plane",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+/// The maximum distance on screen, for snapping to a feature
+///
+/// In normalized screen coordinates, where the width of the screen is 2.
+const SNAP_DISTANCE: f64 = 0.02;
+
+/// Find the point that the cursor points to
+///
+/// If the cursor points at the model, that's the point on the model.
+/// Otherwise, it's the point on the ground plane, if the cursor points at it.
+///
+/// If `snap` is enabled, a point on the model snaps to the vertices, edge
+/// midpoints, and center of the face it is on, if one of those is close by
+/// on screen.
+pub fn pick(
+    camera: &Camera,
+    cursor: NormalizedScreenPosition,
+    model: &Model,
+    snap: bool,
+) -> Option<CursorPosition> {
+    let Some((point, triangle)) = camera.cast_ray(Some(cursor), &model.mesh)
+    else {
+        return pick_ground_plane(camera, cursor);
+    };
+
+    let snapped = triangle
+        .face
+        .filter(|_| snap)
+        .and_then(|face| snap_to_face_features(camera, cursor, model, face));
+
+    Some(snapped.unwrap_or(CursorPosition {
+        point,
+        target: PickTarget::Face,
+    }))
+}
+
+/// Compute the center of a face, weighted by area
+///
+/// Returns `None`, if the face has no triangles with a non-zero area.
+pub fn face_center(model: &Model, face: FaceIndex) -> Option<Point<3>> {
+    // Weigh the center of each triangle by its area, so the result doesn't
+    // depend on how the face was triangulated.
+    let mut weighted_sum = Vector::from([0., 0., 0.]);
+    let mut total_area = Scalar::ZERO;
+
+    for triangle in model.mesh.triangles() {
+        if triangle.face != Some(face) {
+            continue;
+        }
+
+        let [a, b, c] = triangle.inner.points();
+        let area = (b - a).cross(&(c - a)).magnitude() / 2.;
+        let center = (a.coords + b.coords + c.coords) / 3.;
+
+        weighted_sum = weighted_sum + center * area;
+        total_area += area;
+    }
+
+    if total_area == Scalar::ZERO {
+        return None;
+    }
+
+    Some(Point {
+        coords: weighted_sum / total_area,
+    })
+}
+
+fn pick_ground_plane(
+    camera: &Camera,
+    cursor: NormalizedScreenPosition,
+) -> Option<CursorPosition> {
+    let origin = camera.position();
+    let direction = camera.cursor_to_model_space(cursor) - origin;
+
+    if direction.z == Scalar::ZERO {
+        return None;
+    }
+    let t = -origin.z / direction.z;
+    if t <= Scalar::ZERO {
+        // The ground plane is behind the camera.
+        return None;
+    }
+
+    Some(CursorPosition {
+        point: origin + direction * t,
+        target: PickTarget::GroundPlane,
+    })
+}
+
+fn snap_to_face_features(
+    camera: &Camera,
+    cursor: NormalizedScreenPosition,
+    model: &Model,
+    face: FaceIndex,
+) -> Option<CursorPosition> {
+    // The boundary of the face consists of the edges that are only used by
+    // one of its triangles. All other edges are internal to the face's
+    // triangulation, and don't mean anything to the user.
+    let mut edges = BTreeMap::new();
+    for triangle in model.mesh.triangles() {
+        if triangle.face != Some(face) {
+            continue;
+        }
+
+        let [a, b, c] = triangle.inner.points();
+        for [a, b] in [[a, b], [b, c], [c, a]] {
+            let edge = if a < b { [a, b] } else { [b, a] };
+            *edges.entry(edge).or_insert(0) += 1;
+        }
+    }
+    let boundary = edges
+        .into_iter()
+        .filter(|&(_, count)| count == 1)
+        .map(|(edge, _)| edge);
+
+    let mut candidates = Vec::new();
+    for [a, b] in boundary {
+        candidates.push((a, PickTarget::Vertex));
+        candidates.push((b, PickTarget::Vertex));
+        candidates.push((a + (b - a) / 2., PickTarget::EdgeMidpoint));
+    }
+    if let Some(center) = face_center(model, face) {
+        candidates.push((center, PickTarget::FaceCenter));
+    }
+
+    candidates
+        .into_iter()
+        .filter_map(|(point, target)| {
+            let [x, y] = camera.model_to_screen(point)?;
+            let distance =
+                ((x - cursor.x).powi(2) + (y - cursor.y).powi(2)).sqrt();
+            (distance <= SNAP_DISTANCE).then_some((distance, point, target))
+        })
+        .min_by(|(a, _, _), (b, _, _)| a.total_cmp(b))
+        .map(|(_, point, target)| CursorPosition { point, target })
+}
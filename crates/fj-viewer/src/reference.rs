@@ -0,0 +1,287 @@
+//! Reference geometry, that is displayed alongside the model
+
+use std::{
+    fs::File,
+    io::{self, BufReader, Read},
+    path::Path,
+};
+
+use fj_interop::{Color, Mesh, Model};
+use fj_math::{Aabb, Point, Scalar, Transform};
+
+use crate::{
+    camera::{Camera, FocusPoint},
+    input::{InputEvent, InputHandler},
+    screen::NormalizedScreenPosition,
+};
+
+/// A mesh that is displayed alongside the model, for reference
+///
+/// This is meant for existing geometry, like a 3D scan or a vendor-provided
+/// part, that the model needs to fit. The reference mesh is drawn with
+/// transparency, so the model remains visible where the two overlap.
+///
+/// The reference mesh can be aligned with the model interactively. See
+/// [`Action::ToggleAlignReference`](crate::Action::ToggleAlignReference).
+#[derive(Clone, Debug)]
+pub struct ReferenceMesh {
+    model: Model,
+    placement: Transform,
+    opacity: f64,
+}
+
+impl ReferenceMesh {
+    /// The color of the reference mesh
+    ///
+    /// Its alpha channel is replaced by the opacity.
+    pub const COLOR: Color = Color([255, 140, 0, 255]);
+
+    /// The opacity of the reference mesh, unless specified otherwise
+    pub const DEFAULT_OPACITY: f64 = 0.5;
+
+    /// Create a reference mesh from a mesh
+    pub fn from_mesh(mesh: Mesh<Point<3>>) -> Self {
+        let aabb = if mesh.vertices().next().is_some() {
+            Aabb::<3>::from_points(mesh.vertices())
+        } else {
+            Aabb::default()
+        };
+
+        Self {
+            model: Model { mesh, aabb },
+            placement: Transform::identity(),
+            opacity: Self::DEFAULT_OPACITY,
+        }
+    }
+
+    /// Load a reference mesh from an STL or OBJ file
+    ///
+    /// The format is chosen according to the file extension. Both binary and
+    /// ASCII STL files are supported.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, LoadReferenceError> {
+        let path = path.as_ref();
+
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(|extension| extension.to_ascii_lowercase());
+        let mesh = match extension.as_deref() {
+            Some("stl") => read_stl(File::open(path)?)?,
+            Some("obj") => read_obj(path)?,
+            _ => {
+                return Err(LoadReferenceError::UnknownFormat {
+                    path: path.display().to_string(),
+                })
+            }
+        };
+
+        Ok(Self::from_mesh(mesh))
+    }
+
+    /// Place the reference mesh using the provided transform
+    ///
+    /// The transform replaces any previous placement, including the result of
+    /// interactive alignment.
+    pub fn with_placement(mut self, placement: Transform) -> Self {
+        self.placement = placement;
+        self
+    }
+
+    /// Use the provided opacity, instead of the default one
+    ///
+    /// The opacity is clamped to the range from `0` (invisible) to `1`
+    /// (opaque).
+    pub fn with_opacity(mut self, opacity: f64) -> Self {
+        self.opacity = opacity.clamp(0., 1.);
+        self
+    }
+
+    /// Access the mesh, in its own coordinate system
+    pub fn mesh(&self) -> &Mesh<Point<3>> {
+        &self.model.mesh
+    }
+
+    /// Access the transform from the mesh's coordinate system to the model's
+    ///
+    /// After interactive alignment, this is the transform that aligns the
+    /// reference mesh with the model.
+    pub fn placement(&self) -> Transform {
+        self.placement
+    }
+
+    /// Access the opacity of the reference mesh
+    pub fn opacity(&self) -> f64 {
+        self.opacity
+    }
+
+    /// Compute the bounding box of the reference mesh, as it is placed
+    pub fn aabb(&self) -> Aabb<3> {
+        self.placement.transform_aabb(&self.model.aabb)
+    }
+
+    /// Switch to the next opacity, wrapping around to the most transparent
+    pub(crate) fn cycle_opacity(&mut self) {
+        const STEP: f64 = 0.25;
+
+        let opacity = (self.opacity / STEP).floor() * STEP + STEP;
+        self.opacity = if opacity > 1. { STEP } else { opacity };
+    }
+
+    /// Compute the point on the reference mesh, that the cursor points to
+    ///
+    /// The point is in the model's coordinate system. Falls back to the center
+    /// of the reference mesh, if the cursor doesn't point at it.
+    pub(crate) fn focus_point(
+        &self,
+        camera: &Camera,
+        cursor: Option<NormalizedScreenPosition>,
+    ) -> FocusPoint {
+        // A camera that looks at the reference mesh in its own coordinate
+        // system, like the real one looks at the model.
+        let mut camera = camera.clone();
+        camera.rotation = camera.rotation * self.placement;
+
+        let FocusPoint(point) = camera.focus_point(cursor, &self.model);
+        FocusPoint(self.placement.transform_point(&point))
+    }
+
+    /// Move the reference mesh according to an input event
+    ///
+    /// The event moves the reference mesh relative to the screen, exactly as
+    /// it would otherwise move the model and the reference mesh together.
+    pub(crate) fn align(
+        &mut self,
+        event: InputEvent,
+        focus_point: FocusPoint,
+        camera: &Camera,
+    ) {
+        let mut moved = camera.clone();
+        InputHandler::handle_event(event, focus_point, &mut moved);
+
+        // Both cameras must show the reference mesh at the same place, but the
+        // moved camera must show it with its original placement.
+        self.placement = camera.camera_to_model().inverse()
+            * moved.camera_to_model()
+            * self.placement;
+    }
+}
+
+/// Error loading a reference mesh
+///
+/// See [`ReferenceMesh::load`].
+#[derive(Debug, thiserror::Error)]
+pub enum LoadReferenceError {
+    /// I/O error while reading the file
+    #[error("I/O error while loading reference mesh")]
+    Io(#[from] io::Error),
+
+    /// Error loading an OBJ file
+    #[error("Error loading OBJ file")]
+    Obj(#[from] tobj::LoadError),
+
+    /// The file is an ASCII STL file, but its contents are invalid
+    #[error("Invalid ASCII STL file: {reason}")]
+    InvalidAsciiStl {
+        /// Why the file is invalid
+        reason: String,
+    },
+
+    /// The file extension doesn't indicate a supported format
+    #[error("Unknown reference mesh format: `{path}` (expected STL or OBJ)")]
+    UnknownFormat {
+        /// The path of the file
+        path: String,
+    },
+}
+
+fn read_stl(reader: impl Read) -> Result<Mesh<Point<3>>, LoadReferenceError> {
+    let mut bytes = Vec::new();
+    BufReader::new(reader).read_to_end(&mut bytes)?;
+
+    // Binary STL files may start with "solid" too, but if they do, they
+    // aren't valid UTF-8, as a rule.
+    let ascii = bytes
+        .starts_with(b"solid")
+        .then(|| std::str::from_utf8(&bytes).ok())
+        .flatten()
+        .filter(|source| source.contains("facet"));
+    if let Some(source) = ascii {
+        return read_ascii_stl(source);
+    }
+
+    let stl = stl::read_stl(&mut bytes.as_slice())?;
+
+    let mut mesh = Mesh::new();
+    for triangle in stl.triangles {
+        let points = [triangle.v1, triangle.v2, triangle.v3]
+            .map(|point| Point::from(point.map(f64::from)));
+        mesh.push_triangle(points, ReferenceMesh::COLOR);
+    }
+
+    Ok(mesh)
+}
+
+fn read_ascii_stl(source: &str) -> Result<Mesh<Point<3>>, LoadReferenceError> {
+    let mut mesh = Mesh::new();
+    let mut points = Vec::new();
+
+    for (i, line) in source.lines().enumerate() {
+        let mut words = line.split_whitespace();
+        if words.next() != Some("vertex") {
+            continue;
+        }
+
+        let coords = words
+            .map(|word| word.parse::<f64>())
+            .collect::<Result<Vec<_>, _>>()
+            .ok()
+            .filter(|coords| coords.len() == 3)
+            .ok_or_else(|| LoadReferenceError::InvalidAsciiStl {
+                reason: format!("Invalid vertex in line {}", i + 1),
+            })?;
+        points.push(Point::from([coords[0], coords[1], coords[2]]));
+
+        if let [a, b, c] = points[..] {
+            mesh.push_triangle([a, b, c], ReferenceMesh::COLOR);
+            points.clear();
+        }
+    }
+
+    if !points.is_empty() {
+        return Err(LoadReferenceError::InvalidAsciiStl {
+            reason: "Incomplete triangle at end of file".to_string(),
+        });
+    }
+
+    Ok(mesh)
+}
+
+fn read_obj(path: &Path) -> Result<Mesh<Point<3>>, LoadReferenceError> {
+    let (models, _) = tobj::load_obj(
+        path,
+        &tobj::LoadOptions {
+            triangulate: true,
+            single_index: true,
+            ..Default::default()
+        },
+    )?;
+
+    let mut mesh = Mesh::new();
+    for model in models {
+        let positions = &model.mesh.positions;
+        let point = |index: u32| {
+            let i = index as usize * 3;
+            Point::from(
+                [positions[i], positions[i + 1], positions[i + 2]]
+                    .map(|coord| Scalar::from(f64::from(coord))),
+            )
+        };
+
+        for triangle in model.mesh.indices.chunks_exact(3) {
+            let [a, b, c] = [triangle[0], triangle[1], triangle[2]].map(point);
+            mesh.push_triangle([a, b, c], ReferenceMesh::COLOR);
+        }
+    }
+
+    Ok(mesh)
+}
@@ -0,0 +1,225 @@
+use std::collections::BTreeMap;
+
+use fj_interop::{Color, FaceIndex, Mesh};
+use fj_math::Point;
+
+use crate::graphics::Legend;
+
+/// A scalar field on the model, displayed by coloring the model
+///
+/// This is the common way to visualize the results of analyses, like the
+/// deviation from a reference, wall thickness, or overhang angles. Each value
+/// is mapped to a color, according to a [`ColorMap`], and a legend shows which
+/// color stands for which value.
+///
+/// Values refer to the mesh of the model that is currently displayed. Parts of
+/// the model that have no value keep their original color.
+#[derive(Clone, Debug)]
+pub struct ScalarField {
+    name: String,
+    values: ScalarValues,
+    color_map: ColorMap,
+    range: Option<[f64; 2]>,
+}
+
+impl ScalarField {
+    /// The number of entries in the legend
+    const LEGEND_ENTRIES: usize = 5;
+
+    /// Create a field with one value per vertex of the mesh
+    ///
+    /// The values are in the order of [`Mesh::vertices`]. Colors are
+    /// interpolated across each triangle.
+    pub fn per_vertex(
+        name: impl Into<String>,
+        values: impl IntoIterator<Item = f64>,
+    ) -> Self {
+        Self::new(name, ScalarValues::PerVertex(values.into_iter().collect()))
+    }
+
+    /// Create a field with one value per face of the mesh
+    ///
+    /// Faces are identified by the face index of their triangles.
+    pub fn per_face(
+        name: impl Into<String>,
+        values: impl IntoIterator<Item = (FaceIndex, f64)>,
+    ) -> Self {
+        Self::new(name, ScalarValues::PerFace(values.into_iter().collect()))
+    }
+
+    fn new(name: impl Into<String>, values: ScalarValues) -> Self {
+        Self {
+            name: name.into(),
+            values,
+            color_map: ColorMap::default(),
+            range: None,
+        }
+    }
+
+    /// Use the provided color map, instead of the default one
+    pub fn with_color_map(mut self, color_map: ColorMap) -> Self {
+        self.color_map = color_map;
+        self
+    }
+
+    /// Map the provided range of values to the full color map
+    ///
+    /// By default, the range between the smallest and the largest value is
+    /// used. Values outside of the range get the color of the closer end.
+    pub fn with_range(mut self, min: f64, max: f64) -> Self {
+        self.range = Some([min, max]);
+        self
+    }
+
+    /// Access the name of the field
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Access the range of values that is mapped to the full color map
+    pub fn range(&self) -> [f64; 2] {
+        if let Some(range) = self.range {
+            return range;
+        }
+
+        let values: Box<dyn Iterator<Item = f64>> = match &self.values {
+            ScalarValues::PerVertex(values) => Box::new(values.iter().copied()),
+            ScalarValues::PerFace(values) => Box::new(values.values().copied()),
+        };
+
+        values
+            .filter(|value| value.is_finite())
+            .fold(None, |range: Option<[f64; 2]>, value| match range {
+                None => Some([value, value]),
+                Some([min, max]) => Some([value.min(min), value.max(max)]),
+            })
+            .unwrap_or([0., 0.])
+    }
+
+    /// Compute the color of a value
+    pub fn color(&self, value: f64) -> Color {
+        self.color_in_range(value, self.range())
+    }
+
+    fn color_in_range(&self, value: f64, [min, max]: [f64; 2]) -> Color {
+        let t = if max > min {
+            (value - min) / (max - min)
+        } else {
+            0.5
+        };
+
+        self.color_map.color(t)
+    }
+
+    /// Compute the colors of the corners of each triangle of the mesh
+    pub(crate) fn triangle_colors(
+        &self,
+        mesh: &Mesh<Point<3>>,
+    ) -> Vec<[Color; 3]> {
+        let indices = mesh.indices().collect::<Vec<_>>();
+        let range = self.range();
+
+        mesh.triangles()
+            .zip(indices.chunks_exact(3))
+            .map(|(triangle, indices)| match &self.values {
+                ScalarValues::PerVertex(values) => {
+                    [indices[0], indices[1], indices[2]].map(|index| {
+                        values
+                            .get(index as usize)
+                            .map(|&value| self.color_in_range(value, range))
+                            .unwrap_or(triangle.color)
+                    })
+                }
+                ScalarValues::PerFace(values) => {
+                    let color = triangle
+                        .face
+                        .and_then(|face| values.get(&face))
+                        .map(|&value| self.color_in_range(value, range))
+                        .unwrap_or(triangle.color);
+                    [color; 3]
+                }
+            })
+            .collect()
+    }
+
+    /// Create the legend that explains the colors
+    pub(crate) fn legend(&self) -> Legend {
+        let [min, max] = self.range();
+
+        // The largest value is on top, like in most charts.
+        let entries = (0..Self::LEGEND_ENTRIES)
+            .rev()
+            .map(|i| {
+                let t = i as f64 / (Self::LEGEND_ENTRIES - 1) as f64;
+                let value = min + (max - min) * t;
+                (format!("{value:.3}"), self.color_map.color(t))
+            })
+            .collect();
+
+        Legend {
+            title: self.name.clone(),
+            entries,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum ScalarValues {
+    PerVertex(Vec<f64>),
+    PerFace(BTreeMap<FaceIndex, f64>),
+}
+
+/// A mapping from values to colors
+///
+/// See [`ScalarField`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ColorMap {
+    /// From dark blue over green to yellow
+    ///
+    /// Perceptually uniform, and readable for people with color vision
+    /// deficiencies. Suited for values that go from low to high.
+    #[default]
+    Viridis,
+
+    /// From blue over light gray to red
+    ///
+    /// Suited for values that deviate in either direction from a value in the
+    /// middle, like the deviation from a reference.
+    CoolWarm,
+
+    /// From black to white
+    Grayscale,
+}
+
+impl ColorMap {
+    /// Compute the color for the provided position within the color map
+    ///
+    /// The position is clamped to the range from 0 (the start of the color
+    /// map) to 1 (the end).
+    pub fn color(&self, t: f64) -> Color {
+        let stops: &[[u8; 3]] = match self {
+            Self::Viridis => &[
+                [68, 1, 84],
+                [59, 82, 139],
+                [33, 145, 140],
+                [94, 201, 98],
+                [253, 231, 37],
+            ],
+            Self::CoolWarm => &[[59, 76, 192], [221, 221, 221], [180, 4, 38]],
+            Self::Grayscale => &[[0, 0, 0], [255, 255, 255]],
+        };
+
+        let t = if t.is_nan() { 0. } else { t.clamp(0., 1.) };
+        let position = t * (stops.len() - 1) as f64;
+        let i = (position.floor() as usize).min(stops.len() - 2);
+        let t = position - i as f64;
+
+        let [a, b] = [stops[i], stops[i + 1]];
+        let [r, g, b] = [0, 1, 2].map(|c| {
+            let [a, b] = [a[c], b[c]].map(f64::from);
+            (a + (b - a) * t).round() as u8
+        });
+
+        Color([r, g, b, 255])
+    }
+}
@@ -0,0 +1,250 @@
+//! Presentation of the model, as specified by the model itself
+
+use std::time::Duration;
+
+use fj_math::{Aabb, Point, Scalar, Vector};
+
+use crate::ReferenceMesh;
+
+/// How the model presents itself, when it is displayed
+///
+/// This allows models to specify the view that shows them best, instead of
+/// relying on the default view that the viewer picks. Demo models can also
+/// script camera movements, to show themselves off.
+///
+/// As soon as the user moves the camera, any scripted movement stops.
+#[derive(Clone, Debug, Default)]
+pub struct SceneConfig {
+    /// The axis of the model that points up
+    ///
+    /// If this is set, and no initial view is specified, the model is
+    /// initially viewed from the front, at an angle, with this axis pointing
+    /// up on the screen.
+    pub up_axis: Option<UpAxis>,
+
+    /// The view of the model, when it is first displayed
+    ///
+    /// If no initial view is set, but a camera script is, the script's first
+    /// keyframe is used.
+    pub initial_view: Option<CameraView>,
+
+    /// Camera movements that play, once the model is displayed
+    pub camera_script: Option<CameraScript>,
+
+    /// A mesh that is displayed alongside the model, for reference
+    ///
+    /// See [`ReferenceMesh`].
+    pub reference_mesh: Option<ReferenceMesh>,
+}
+
+impl SceneConfig {
+    /// Compute the view of the model, when it is first displayed
+    ///
+    /// Returns `None`, if the viewer should pick its default view.
+    pub fn initial_view(&self, aabb: &Aabb<3>) -> Option<CameraView> {
+        if let Some(view) = self.initial_view {
+            return Some(view);
+        }
+        if let Some(keyframe) = self
+            .camera_script
+            .as_ref()
+            .and_then(|script| script.keyframes.first())
+        {
+            return Some(keyframe.view);
+        }
+
+        let up_axis = self.up_axis?;
+        let up = up_axis.vector();
+        let front = up_axis.front();
+        let right = up.cross(&front);
+
+        Some(CameraView::fitting(aabb, front + right + up, up))
+    }
+}
+
+/// An axis of the model
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UpAxis {
+    /// The x-axis
+    X,
+
+    /// The y-axis
+    Y,
+
+    /// The z-axis
+    Z,
+}
+
+impl UpAxis {
+    fn vector(&self) -> Vector<3> {
+        match self {
+            Self::X => Vector::unit_x(),
+            Self::Y => Vector::unit_y(),
+            Self::Z => Vector::unit_z(),
+        }
+    }
+
+    /// The direction that the front of the model faces
+    fn front(&self) -> Vector<3> {
+        match self {
+            Self::X | Self::Y => Vector::unit_z(),
+            Self::Z => -Vector::unit_y(),
+        }
+    }
+}
+
+/// A view of the model
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CameraView {
+    /// The position of the camera
+    pub eye: Point<3>,
+
+    /// The point that the camera looks at
+    pub target: Point<3>,
+
+    /// The direction that points up on the screen
+    ///
+    /// Must not be parallel to the direction that the camera looks in.
+    pub up: Vector<3>,
+}
+
+impl CameraView {
+    /// Create a view that looks at a bounding box from the provided direction
+    ///
+    /// The camera looks at the center of the bounding box, from far enough
+    /// away that all of it is visible.
+    pub fn fitting(
+        aabb: &Aabb<3>,
+        direction: impl Into<Vector<3>>,
+        up: impl Into<Vector<3>>,
+    ) -> Self {
+        let target = aabb.center();
+
+        let radius = (aabb.max - aabb.min).magnitude() / 2.;
+        let radius = if radius > Scalar::ZERO {
+            radius
+        } else {
+            Scalar::ONE
+        };
+
+        Self {
+            eye: target + direction.into().normalize() * radius * 2.,
+            target,
+            up: up.into(),
+        }
+    }
+
+    /// Interpolate between this view and another
+    ///
+    /// The camera moves on an arc around the targets, so it keeps looking at
+    /// the model while it's moving.
+    pub fn interpolate(&self, other: &Self, t: f64) -> Self {
+        let [offset_a, offset_b] =
+            [self, other].map(|view| view.eye - view.target);
+
+        let target = self.target + (other.target - self.target) * t;
+        let distance = offset_a.magnitude()
+            + (offset_b.magnitude() - offset_a.magnitude()) * t;
+        let direction = nlerp(offset_a, offset_b, t);
+
+        Self {
+            eye: target + direction * distance,
+            target,
+            up: nlerp(self.up, other.up, t),
+        }
+    }
+}
+
+/// Camera movements, defined by a series of views
+#[derive(Clone, Debug)]
+pub struct CameraScript {
+    keyframes: Vec<CameraKeyframe>,
+    repeat: bool,
+}
+
+impl CameraScript {
+    /// Create a script from the provided keyframes
+    ///
+    /// The keyframes don't need to be in order. The camera moves smoothly
+    /// from each keyframe to the next.
+    pub fn new(keyframes: impl IntoIterator<Item = CameraKeyframe>) -> Self {
+        let mut keyframes = keyframes.into_iter().collect::<Vec<_>>();
+        keyframes.sort_by_key(|keyframe| keyframe.time);
+
+        Self {
+            keyframes,
+            repeat: false,
+        }
+    }
+
+    /// Start over from the first keyframe, after the last one is reached
+    pub fn repeat(mut self) -> Self {
+        self.repeat = true;
+        self
+    }
+
+    /// Compute the view at the provided time since the script started
+    ///
+    /// Returns `None`, if the script has no keyframes.
+    pub fn view_at(&self, time: Duration) -> Option<CameraView> {
+        let time = if self.repeat && !self.duration().is_zero() {
+            Duration::from_secs_f64(
+                time.as_secs_f64() % self.duration().as_secs_f64(),
+            )
+        } else {
+            time
+        };
+
+        let next = self
+            .keyframes
+            .iter()
+            .position(|keyframe| keyframe.time > time);
+
+        let view = match next {
+            Some(0) => self.keyframes[0].view,
+            Some(next) => {
+                let [a, b] = [&self.keyframes[next - 1], &self.keyframes[next]];
+                let t = (time - a.time).as_secs_f64()
+                    / (b.time - a.time).as_secs_f64();
+
+                // Ease in and out of each keyframe.
+                let t = t * t * (3. - 2. * t);
+
+                a.view.interpolate(&b.view, t)
+            }
+            None => self.keyframes.last()?.view,
+        };
+
+        Some(view)
+    }
+
+    /// Indicate whether the script has finished at the provided time
+    pub fn is_finished(&self, time: Duration) -> bool {
+        !self.repeat && time >= self.duration()
+    }
+
+    fn duration(&self) -> Duration {
+        self.keyframes
+            .last()
+            .map(|keyframe| keyframe.time)
+            .unwrap_or_default()
+    }
+}
+
+/// A view that the camera reaches at a specific time
+///
+/// See [`CameraScript`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CameraKeyframe {
+    /// The time since the script started
+    pub time: Duration,
+
+    /// The view
+    pub view: CameraView,
+}
+
+/// Interpolate between two directions, and normalize the result
+fn nlerp(a: Vector<3>, b: Vector<3>, t: f64) -> Vector<3> {
+    let [a, b] = [a, b].map(|vector| vector.normalize());
+    (a + (b - a) * t).normalize()
+}
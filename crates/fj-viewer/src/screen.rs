@@ -30,7 +30,7 @@ pub struct NormalizedScreenPosition {
 }
 
 /// The size of the screen
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct ScreenSize {
     /// The width of the screen
     pub width: u32,
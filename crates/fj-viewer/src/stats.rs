@@ -0,0 +1,116 @@
+//! Statistics about the viewer's performance
+
+use std::{
+    collections::VecDeque,
+    fmt,
+    time::{Duration, Instant},
+};
+
+use crate::input::now;
+
+/// Statistics about the viewer's performance
+///
+/// Returned by [`Viewer::stats`](crate::Viewer::stats).
+#[derive(Clone, Copy, Debug)]
+pub struct ViewerStats {
+    /// The number of frames drawn per second, averaged over the last second
+    pub fps: Option<f64>,
+
+    /// The average time between two frames
+    pub frame_time: Option<Duration>,
+
+    /// The number of triangles in the model
+    pub num_triangles: u32,
+
+    /// The number of draw calls made during the last frame
+    pub num_draw_calls: u32,
+
+    /// An estimate of the GPU memory used by the viewer, in bytes
+    ///
+    /// This covers the buffers and textures that the viewer allocates itself.
+    /// It doesn't include anything the graphics driver allocates on top of
+    /// that.
+    pub gpu_memory: u64,
+}
+
+impl fmt::Display for ViewerStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.fps, self.frame_time) {
+            (Some(fps), Some(frame_time)) => writeln!(
+                f,
+                "FPS:        {fps:.1} ({:.2} ms)",
+                frame_time.as_secs_f64() * 1000.
+            )?,
+            _ => writeln!(f, "FPS:        n/a")?,
+        }
+
+        writeln!(f, "Triangles:  {}", self.num_triangles)?;
+        writeln!(f, "Draw calls: {}", self.num_draw_calls)?;
+        write!(
+            f,
+            "GPU memory: ~{:.1} MiB",
+            self.gpu_memory as f64 / (1024. * 1024.)
+        )
+    }
+}
+
+/// Measures the frame rate
+///
+/// The measurement is only updated twice per second, to keep the displayed
+/// numbers readable.
+#[derive(Debug, Default)]
+pub struct FrameTimer {
+    frames: VecDeque<Instant>,
+    last_update: Option<Instant>,
+    frame_time: Option<Duration>,
+}
+
+impl FrameTimer {
+    const WINDOW: Duration = Duration::from_secs(1);
+    const UPDATE_INTERVAL: Duration = Duration::from_millis(500);
+
+    /// Record that a frame is being drawn
+    pub fn frame(&mut self) {
+        let Some(now) = now() else {
+            return;
+        };
+
+        self.frames.push_back(now);
+        while let Some(&oldest) = self.frames.front() {
+            if now.duration_since(oldest) <= Self::WINDOW {
+                break;
+            }
+            self.frames.pop_front();
+        }
+
+        let update_due = self.last_update.map_or(true, |last_update| {
+            now.duration_since(last_update) >= Self::UPDATE_INTERVAL
+        });
+        if !update_due {
+            return;
+        }
+        self.last_update = Some(now);
+
+        if let (Some(first), Some(last)) =
+            (self.frames.front(), self.frames.back())
+        {
+            let num_intervals = self.frames.len() as u32 - 1;
+            if num_intervals > 0 {
+                self.frame_time =
+                    Some(last.duration_since(*first) / num_intervals);
+            }
+        }
+    }
+
+    /// The average time between two frames
+    pub fn frame_time(&self) -> Option<Duration> {
+        self.frame_time
+    }
+
+    /// The number of frames per second
+    pub fn fps(&self) -> Option<f64> {
+        self.frame_time
+            .filter(|frame_time| !frame_time.is_zero())
+            .map(|frame_time| 1. / frame_time.as_secs_f64())
+    }
+}
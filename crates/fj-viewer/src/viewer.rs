@@ -1,12 +1,20 @@
+use std::time::Instant;
+
 use fj_interop::Model;
+use fj_math::{Point, Scalar};
 use tracing::warn;
 
 use crate::{
     camera::{Camera, FocusPoint},
     graphics::{DrawConfig, Renderer},
-    input::InputHandler,
-    InputEvent, NormalizedScreenPosition, RendererInitError, Screen,
-    ScreenSize,
+    input::{now, Action, ActionQueue, InputHandler, NavigationConfig},
+    pick::{pick, CursorPosition},
+    reference::ReferenceMesh,
+    scalar_field::ScalarField,
+    scene::{CameraView, SceneConfig},
+    stats::FrameTimer,
+    NormalizedScreenPosition, RendererInitError, Screen, ScreenSize,
+    ViewerStats,
 };
 
 /// The Fornjot model viewer
@@ -15,8 +23,21 @@ pub struct Viewer {
     cursor: Option<NormalizedScreenPosition>,
     draw_config: DrawConfig,
     focus_point: Option<FocusPoint>,
+    frame_timer: FrameTimer,
+    input_handler: InputHandler,
+    navigation_config: NavigationConfig,
     renderer: Renderer,
+    snapping: bool,
+    rotation_center: Option<FocusPoint>,
     model: Option<Model>,
+    scalar_field: Option<ScalarField>,
+    reference: Option<ReferenceMesh>,
+    aligning_reference: bool,
+    scene_config: SceneConfig,
+    camera_script_start: Option<Instant>,
+
+    #[cfg(feature = "hot-reload")]
+    hot_reload: crate::hot_reload::HotReload,
 }
 
 impl Viewer {
@@ -29,14 +50,38 @@ impl Viewer {
             cursor: None,
             draw_config: DrawConfig::default(),
             focus_point: None,
+            frame_timer: FrameTimer::default(),
+            input_handler: InputHandler::default(),
+            navigation_config: NavigationConfig::default(),
             renderer,
+            snapping: false,
+            rotation_center: None,
             model: None,
+            scalar_field: None,
+            reference: None,
+            aligning_reference: false,
+            scene_config: SceneConfig::default(),
+            camera_script_start: None,
+
+            #[cfg(feature = "hot-reload")]
+            hot_reload: crate::hot_reload::HotReload::new(),
         })
     }
 
-    /// Access the cursor
-    pub fn cursor(&mut self) -> &mut Option<NormalizedScreenPosition> {
-        &mut self.cursor
+    /// Replace the configuration of how the camera responds to input
+    pub fn set_navigation_config(&mut self, config: NavigationConfig) {
+        self.navigation_config = config;
+    }
+
+    /// Replace the configuration of how the model presents itself
+    ///
+    /// Only takes effect, once the first model is displayed. The exception is
+    /// the reference mesh, which is displayed right away.
+    pub fn set_scene_config(&mut self, mut config: SceneConfig) {
+        if let Some(reference) = config.reference_mesh.take() {
+            self.set_reference_mesh(Some(reference));
+        }
+        self.scene_config = config;
     }
 
     /// Toggle the "draw model" setting
@@ -49,30 +94,254 @@ impl Viewer {
         self.draw_config.draw_mesh = !self.draw_config.draw_mesh;
     }
 
+    /// Toggle the "draw face orientation" setting
+    ///
+    /// While enabled, the model is colored by whether the front or the back
+    /// of its triangles faces the viewer, to make orientation bugs visible.
+    pub fn toggle_draw_face_orientation(&mut self) {
+        self.draw_config.draw_face_orientation =
+            !self.draw_config.draw_face_orientation;
+    }
+
+    /// Toggle the "draw reference" setting
+    pub fn toggle_draw_reference(&mut self) {
+        self.draw_config.draw_reference = !self.draw_config.draw_reference;
+    }
+
+    /// Toggle alignment of the reference mesh
+    ///
+    /// See [`Action::ToggleAlignReference`].
+    pub fn toggle_align_reference(&mut self) {
+        self.aligning_reference = !self.aligning_reference;
+    }
+
+    /// Make the reference mesh more opaque, or almost transparent again
+    ///
+    /// See [`Action::CycleReferenceOpacity`].
+    pub fn cycle_reference_opacity(&mut self) {
+        if let Some(reference) = &mut self.reference {
+            reference.cycle_opacity();
+            self.renderer.update_reference_geometry(Some(reference));
+        }
+    }
+
+    /// Toggle the "draw stats" setting
+    pub fn toggle_draw_stats(&mut self) {
+        self.draw_config.draw_stats = !self.draw_config.draw_stats;
+    }
+
+    /// Toggle the "draw cursor position" setting
+    pub fn toggle_draw_cursor_position(&mut self) {
+        self.draw_config.draw_cursor_position =
+            !self.draw_config.draw_cursor_position;
+    }
+
+    /// Toggle snapping of the cursor position to features of the model
+    pub fn toggle_snapping(&mut self) {
+        self.snapping = !self.snapping;
+    }
+
+    /// Compute the point that the cursor points to
+    ///
+    /// This is the point on the model under the cursor, or the point on the
+    /// ground plane, if the cursor doesn't point at the model. If snapping is
+    /// enabled, points on the model snap to nearby vertices, edge midpoints,
+    /// and face centers.
+    pub fn cursor_position(&self) -> Option<CursorPosition> {
+        let model = self.model.as_ref()?;
+        pick(&self.camera, self.cursor?, model, self.snapping)
+    }
+
+    /// Access statistics about the viewer's performance
+    ///
+    /// The frame rate and frame time are only available after a few frames
+    /// have been drawn, and never on WASM.
+    pub fn stats(&self) -> ViewerStats {
+        let render = self.renderer.stats();
+
+        ViewerStats {
+            fps: self.frame_timer.fps(),
+            frame_time: self.frame_timer.frame_time(),
+            num_triangles: render.num_triangles,
+            num_draw_calls: render.num_draw_calls,
+            gpu_memory: render.gpu_memory,
+        }
+    }
+
+    /// Display a scalar field on the model, or stop displaying one
+    ///
+    /// The model is colored according to the field, and a legend explains the
+    /// colors. The field refers to the mesh of the current model, and keeps
+    /// being displayed when the model is updated. Replace or remove it then,
+    /// if it doesn't apply to the new model.
+    pub fn set_scalar_field(&mut self, scalar_field: Option<ScalarField>) {
+        self.scalar_field = scalar_field;
+        self.renderer.set_overlay_legend(
+            self.scalar_field.as_ref().map(ScalarField::legend),
+        );
+
+        if let Some(model) = &self.model {
+            self.renderer.update_geometry(
+                &model.mesh,
+                model.aabb,
+                self.scalar_field.as_ref(),
+            );
+        }
+    }
+
+    /// Display a reference mesh alongside the model, or stop displaying one
+    ///
+    /// The reference mesh keeps being displayed when the model is updated.
+    pub fn set_reference_mesh(&mut self, reference: Option<ReferenceMesh>) {
+        self.reference = reference;
+        self.renderer
+            .update_reference_geometry(self.reference.as_ref());
+        if let Some(reference) = &self.reference {
+            self.renderer.set_reference_placement(reference.placement());
+        }
+    }
+
+    /// Access the reference mesh, if one is displayed
+    ///
+    /// Its placement reflects any interactive alignment that has been done.
+    pub fn reference_mesh(&self) -> Option<&ReferenceMesh> {
+        self.reference.as_ref()
+    }
+
     /// Handle the model being updated
     pub fn handle_model_update(&mut self, model: Model) {
-        self.renderer.update_geometry((&model.mesh).into());
+        self.renderer.update_geometry(
+            &model.mesh,
+            model.aabb,
+            self.scalar_field.as_ref(),
+        );
 
         let aabb = model.aabb;
         if self.model.replace(model).is_none() {
-            self.camera.init_planes(&aabb);
+            match self.scene_config.initial_view(&aabb) {
+                Some(view) => self.camera.set_view(&view),
+                None => self.camera.init_planes(&aabb),
+            }
+
+            if self.scene_config.camera_script.is_some() {
+                self.camera_script_start = now();
+            }
+        }
+    }
+
+    /// Handle an action
+    ///
+    /// [`Action::Exit`] is ignored, as it is up to the application to handle
+    /// that.
+    pub fn handle_action(&mut self, action: Action) {
+        match action {
+            Action::RotateStart | Action::Motion { .. } => {
+                self.camera_script_start = None;
+                self.add_focus_point(true);
+            }
+            Action::PanStart | Action::ZoomDelta(_) => {
+                self.camera_script_start = None;
+                self.add_focus_point(false);
+            }
+            Action::SetRotationCenter => self.set_rotation_center(),
+            Action::ToggleDrawModel => self.toggle_draw_model(),
+            Action::ToggleDrawMesh => self.toggle_draw_mesh(),
+            Action::ToggleDrawFaceOrientation => {
+                self.toggle_draw_face_orientation();
+            }
+            Action::ToggleDrawStats => self.toggle_draw_stats(),
+            Action::ToggleDrawCursorPosition => {
+                self.toggle_draw_cursor_position();
+            }
+            Action::ToggleDrawReference => self.toggle_draw_reference(),
+            Action::ToggleAlignReference => self.toggle_align_reference(),
+            Action::CycleReferenceOpacity => self.cycle_reference_opacity(),
+            Action::ToggleSnapping => self.toggle_snapping(),
+            _ => {}
+        }
+
+        let event = self.input_handler.handle_action(
+            action,
+            &mut self.cursor,
+            &self.navigation_config,
+        );
+        if let (Some(event), Some(focus_point)) = (event, self.focus_point) {
+            let aligning = self.aligning_reference;
+            match self.reference.as_mut().filter(|_| aligning) {
+                Some(reference) => {
+                    reference.align(event, focus_point, &self.camera);
+                    self.renderer
+                        .set_reference_placement(reference.placement());
+                }
+                None => InputHandler::handle_event(
+                    event,
+                    focus_point,
+                    &mut self.camera,
+                ),
+            }
+        }
+
+        // The reference mesh doesn't keep moving after it has been released.
+        if let (Action::RotateEnd, false) =
+            (action, self.is_aligning_reference())
+        {
+            self.input_handler
+                .start_fling(self.focus_point, &self.navigation_config);
+        }
+        if let Action::RotateEnd | Action::PanEnd | Action::MotionEnd = action {
+            self.remove_focus_point();
         }
     }
 
-    /// Handle an input event
-    pub fn handle_input_event(&mut self, event: InputEvent) {
-        if let Some(focus_point) = self.focus_point {
-            InputHandler::handle_event(event, focus_point, &mut self.camera);
+    /// Handle all actions in the queue, in order
+    ///
+    /// See [`Viewer::handle_action`].
+    pub fn handle_actions(&mut self, actions: &mut ActionQueue) {
+        while let Some(action) = actions.pop() {
+            self.handle_action(action);
         }
     }
 
+    /// Access the view that the camera currently has
+    ///
+    /// Pass this as [`SceneConfig::initial_view`], to restore it later.
+    pub fn camera_view(&self) -> CameraView {
+        self.camera.view()
+    }
+
     /// Handle the screen being resized
     pub fn handle_screen_resize(&mut self, screen_size: ScreenSize) {
         self.renderer.handle_resize(screen_size);
     }
 
+    /// Rotate around the face under the cursor, from now on
+    ///
+    /// See [`Action::SetRotationCenter`].
+    pub fn set_rotation_center(&mut self) {
+        self.rotation_center = self
+            .model
+            .as_ref()
+            .and_then(|model| self.camera.rotation_center(self.cursor, model));
+    }
+
     /// Compute and store a focus point, unless one is already stored
-    pub fn add_focus_point(&mut self) {
+    ///
+    /// If the focus point is needed for a rotation, and a rotation center has
+    /// been set, that is used instead.
+    fn add_focus_point(&mut self, is_rotation: bool) {
+        if let Some(reference) =
+            self.reference.as_ref().filter(|_| self.aligning_reference)
+        {
+            let focus_point = reference.focus_point(&self.camera, self.cursor);
+            self.focus_point.get_or_insert(focus_point);
+            return;
+        }
+
+        if is_rotation && self.rotation_center.is_some() {
+            self.focus_point = self.rotation_center;
+            return;
+        }
+
         if let Some(model) = &self.model {
             if self.focus_point.is_none() {
                 self.focus_point =
@@ -81,21 +350,79 @@ impl Viewer {
         }
     }
 
+    /// Indicate whether the reference mesh is currently being aligned
+    fn is_aligning_reference(&self) -> bool {
+        self.aligning_reference && self.reference.is_some()
+    }
+
+    /// Move the camera according to the camera script, if it's playing
+    fn play_camera_script(&mut self) {
+        let (Some(start), Some(script)) =
+            (self.camera_script_start, &self.scene_config.camera_script)
+        else {
+            return;
+        };
+
+        let time = start.elapsed();
+        if let Some(view) = script.view_at(time) {
+            self.camera.set_view(&view);
+        }
+        if script.is_finished(time) {
+            self.camera_script_start = None;
+        }
+    }
+
     /// Remove the stored focus point
-    pub fn remove_focus_point(&mut self) {
+    fn remove_focus_point(&mut self) {
         self.focus_point = None;
     }
 
     /// Draw the graphics
     pub fn draw(&mut self) {
+        #[cfg(feature = "hot-reload")]
+        self.hot_reload
+            .update(&mut self.renderer, &mut self.draw_config);
+
         let aabb = self
             .model
             .as_ref()
             .map(|shape| shape.aabb)
             .unwrap_or_default();
+        let aabb = match &self.reference {
+            Some(reference) if self.draw_config.draw_reference => {
+                aabb.merged(&reference.aabb())
+            }
+            _ => aabb,
+        };
 
+        self.input_handler
+            .update(&self.navigation_config, &mut self.camera);
+        self.play_camera_script();
         self.camera.update_planes(&aabb);
 
+        self.frame_timer.frame();
+
+        let mut overlay = Vec::new();
+        if self.draw_config.draw_stats {
+            overlay.push(self.stats().to_string());
+        }
+        if self.draw_config.draw_cursor_position {
+            if let Some(position) = self.cursor_position() {
+                overlay.push(position.to_string());
+            }
+        }
+        if let Some(reference) =
+            self.reference.as_ref().filter(|_| self.aligning_reference)
+        {
+            let offset =
+                reference.placement().transform_point(&Point::origin());
+            let [x, y, z] = offset.coords.components.map(Scalar::into_f64);
+            overlay.push(format!(
+                "Aligning reference  (offset X: {x:.3}  Y: {y:.3}  Z: {z:.3})"
+            ));
+        }
+        self.renderer.set_overlay_text(&overlay.join("\n"));
+
         if let Err(err) = self.renderer.draw(&self.camera, &self.draw_config) {
             warn!("Draw error: {}", err);
         }
@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+
+use fj_viewer::Action;
+use winit::{
+    event::{ElementState, MouseButton},
+    keyboard::{Key, NamedKey},
+};
+
+/// A physical button that can be bound to an [`Action`]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Button {
+    /// A key on the keyboard
+    Key(Key),
+
+    /// A mouse button
+    Mouse(MouseButton),
+}
+
+/// Maps physical buttons to the actions they trigger
+///
+/// Continuous inputs, like cursor movement and the mouse wheel, are not
+/// covered by this. They map to actions directly.
+///
+/// Start from the default bindings and override some of them, or from
+/// [`Bindings::empty`] to define all of them.
+#[derive(Clone, Debug)]
+pub struct Bindings {
+    actions: HashMap<(Button, ElementState), Action>,
+}
+
+impl Bindings {
+    /// Construct an instance of `Bindings` that doesn't bind anything
+    pub fn empty() -> Self {
+        Self {
+            actions: HashMap::new(),
+        }
+    }
+
+    /// Bind an action to a change in the state of a button
+    ///
+    /// Replaces any action that was previously bound to the same change.
+    pub fn bind(
+        mut self,
+        button: Button,
+        state: ElementState,
+        action: Action,
+    ) -> Self {
+        self.actions.insert((button, state), action);
+        self
+    }
+
+    /// Bind an action to a button being pressed
+    pub fn bind_press(self, button: Button, action: Action) -> Self {
+        self.bind(button, ElementState::Pressed, action)
+    }
+
+    /// Access the action that a change in the state of a button triggers
+    pub fn action(
+        &self,
+        button: Button,
+        state: ElementState,
+    ) -> Option<Action> {
+        self.actions.get(&(button, state)).copied()
+    }
+}
+
+impl Default for Bindings {
+    fn default() -> Self {
+        Self::empty()
+            .bind_press(Button::Key(Key::Named(NamedKey::Escape)), Action::Exit)
+            .bind_press(
+                Button::Key(Key::Character("1".into())),
+                Action::ToggleDrawModel,
+            )
+            .bind_press(
+                Button::Key(Key::Character("2".into())),
+                Action::ToggleDrawMesh,
+            )
+            .bind_press(
+                Button::Key(Key::Character("3".into())),
+                Action::ToggleDrawStats,
+            )
+            .bind_press(
+                Button::Key(Key::Character("4".into())),
+                Action::ToggleDrawCursorPosition,
+            )
+            .bind_press(
+                Button::Key(Key::Character("5".into())),
+                Action::ToggleDrawFaceOrientation,
+            )
+            .bind_press(
+                Button::Key(Key::Character("6".into())),
+                Action::ToggleDrawReference,
+            )
+            .bind_press(
+                Button::Key(Key::Character("a".into())),
+                Action::ToggleAlignReference,
+            )
+            .bind_press(
+                Button::Key(Key::Character("t".into())),
+                Action::CycleReferenceOpacity,
+            )
+            .bind_press(
+                Button::Key(Key::Character("s".into())),
+                Action::ToggleSnapping,
+            )
+            .bind_press(
+                Button::Key(Key::Character("c".into())),
+                Action::SetRotationCenter,
+            )
+            .bind_press(Button::Mouse(MouseButton::Left), Action::RotateStart)
+            .bind(
+                Button::Mouse(MouseButton::Left),
+                ElementState::Released,
+                Action::RotateEnd,
+            )
+            .bind_press(Button::Mouse(MouseButton::Right), Action::PanStart)
+            .bind(
+                Button::Mouse(MouseButton::Right),
+                ElementState::Released,
+                Action::PanEnd,
+            )
+    }
+}
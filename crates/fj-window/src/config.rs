@@ -0,0 +1,32 @@
+use std::path::PathBuf;
+
+use fj_viewer::NavigationConfig;
+
+use crate::{bindings::Bindings, space_mouse::SpaceMouseConfig};
+
+/// Configuration of how the window handles input
+#[derive(Clone, Debug, Default)]
+pub struct InputConfig {
+    /// The actions that buttons on the keyboard and mouse trigger
+    pub bindings: Bindings,
+
+    /// Invert the direction of zooming with the mouse wheel
+    pub invert_zoom: bool,
+
+    /// Configuration of how the camera responds to input
+    pub navigation: NavigationConfig,
+
+    /// Configuration of the space mouse, if one is connected
+    pub space_mouse: SpaceMouseConfig,
+
+    /// Remember the view of the model in this file
+    ///
+    /// If set, the view is stored when the window is closed, and restored
+    /// from there when it's opened again. This takes precedence over
+    /// [`SceneConfig::initial_view`], once the file exists.
+    ///
+    /// This doesn't work on WebAssembly, where no file system is available.
+    ///
+    /// [`SceneConfig::initial_view`]: fj_viewer::SceneConfig::initial_view
+    pub view_file: Option<PathBuf>,
+}
@@ -1,44 +1,151 @@
+use std::{
+    sync::mpsc::{self, Receiver},
+    time::{Duration, Instant},
+};
+
 use fj_interop::Model;
 use fj_viewer::{
-    InputEvent, NormalizedScreenPosition, RendererInitError, Screen,
-    ScreenSize, Viewer,
+    Action, ActionQueue, NormalizedScreenPosition, RendererInitError,
+    SceneConfig, Screen, ScreenSize, Viewer,
 };
-use futures::executor::block_on;
 use winit::{
     dpi::PhysicalPosition,
     error::EventLoopError,
-    event::{
-        ElementState, Event, KeyEvent, MouseButton, MouseScrollDelta,
-        WindowEvent,
-    },
-    event_loop::EventLoop,
-    keyboard::{Key, NamedKey},
+    event::{Event, KeyEvent, MouseScrollDelta, WindowEvent},
+    event_loop::{EventLoop, EventLoopWindowTarget},
 };
 
-use crate::window::{self, Window};
+use crate::{
+    bindings::{Bindings, Button},
+    config::InputConfig,
+    space_mouse::SpaceMouse,
+    view_file,
+    window::{self, Window},
+};
 
 /// Display the provided mesh in a window that processes input
-pub fn display(model: Model, invert_zoom: bool) -> Result<(), Error> {
+pub fn display(
+    model: Model,
+    config: InputConfig,
+    scene: SceneConfig,
+) -> Result<(), Error> {
+    let (sender, models) = mpsc::channel();
+    sender
+        .send(model)
+        .expect("Receiver is still in scope, so sending can't fail");
+
+    display_updates(models, config, scene)
+}
+
+/// Display models as they arrive, in a window that processes input
+///
+/// Opens the window right away, without waiting for the first model. Every
+/// model that is received replaces the one currently displayed. This allows
+/// computing models in the background, without blocking the window.
+///
+/// The window stays open, if the sender is dropped.
+///
+/// The scene configuration applies to the first model that is received.
+pub fn display_updates(
+    models: Receiver<Model>,
+    config: InputConfig,
+    scene: SceneConfig,
+) -> Result<(), Error> {
     let event_loop = EventLoop::new()?;
     let window = Window::new(&event_loop)?;
-    let mut viewer = block_on(Viewer::new(&window))?;
 
-    viewer.handle_model_update(model);
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let viewer = futures::executor::block_on(Viewer::new(&window))?;
+        event_loop.run(event_handler(window, viewer, models, config, scene))?;
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        use winit::platform::web::EventLoopExtWebSys;
+
+        // The browser doesn't allow us to block while waiting for the graphics
+        // device. Initialize the viewer asynchronously instead, and only start
+        // the event loop once that's done.
+        wasm_bindgen_futures::spawn_local(async move {
+            let viewer = match Viewer::new(&window).await {
+                Ok(viewer) => viewer,
+                Err(err) => {
+                    tracing::error!("{}", Error::Graphics(err));
+                    return;
+                }
+            };
+
+            event_loop
+                .spawn(event_handler(window, viewer, models, config, scene));
+        });
+    }
+
+    Ok(())
+}
+
+fn event_handler(
+    window: Window,
+    mut viewer: Viewer,
+    models: Receiver<Model>,
+    config: InputConfig,
+    mut scene: SceneConfig,
+) -> impl FnMut(Event<()>, &EventLoopWindowTarget<()>) {
+    if let Some(view) = config.view_file.as_deref().and_then(view_file::read) {
+        scene.initial_view = Some(view);
+    }
+
+    viewer.set_navigation_config(config.navigation.clone());
+    viewer.set_scene_config(scene);
+
+    let mut actions = ActionQueue::default();
+    let space_mouse = SpaceMouse::connect();
+    let mut last_motion = None;
 
-    let mut held_mouse_button = None;
     let mut new_size = None;
     let mut stop_drawing = false;
 
-    event_loop.run(move |event, event_loop_window_target| {
-        let input_event = input_event(
+    move |event, event_loop_window_target| {
+        push_actions(
             &event,
             &window,
-            &held_mouse_button,
-            viewer.cursor(),
-            invert_zoom,
+            &config.bindings,
+            config.invert_zoom,
+            &mut actions,
         );
-        if let Some(input_event) = input_event {
-            viewer.handle_input_event(input_event);
+
+        if let (Event::AboutToWait, Some(space_mouse)) = (&event, &space_mouse)
+        {
+            for motion in space_mouse.poll() {
+                if motion.is_at_rest() {
+                    if last_motion.take().is_some() {
+                        actions.push(Action::MotionEnd);
+                    }
+                    continue;
+                }
+
+                last_motion = Some(Instant::now());
+                actions.push(config.space_mouse.action(motion));
+            }
+
+            // Not every device reports when it's been released. Consider the
+            // motion over, if nothing has been reported for a while.
+            if last_motion
+                .is_some_and(|last| last.elapsed() > MOTION_END_TIMEOUT)
+            {
+                last_motion = None;
+                actions.push(Action::MotionEnd);
+            }
+        }
+
+        while let Some(action) = actions.pop() {
+            match action {
+                Action::Exit => {
+                    save_view(&config, &viewer);
+                    event_loop_window_target.exit();
+                }
+                action => viewer.handle_action(action),
+            }
         }
 
         match event {
@@ -46,32 +153,9 @@ pub fn display(model: Model, invert_zoom: bool) -> Result<(), Error> {
                 event: WindowEvent::CloseRequested,
                 ..
             } => {
+                save_view(&config, &viewer);
                 event_loop_window_target.exit();
             }
-            Event::WindowEvent {
-                event:
-                    WindowEvent::KeyboardInput {
-                        event:
-                            KeyEvent {
-                                logical_key,
-                                state: ElementState::Pressed,
-                                ..
-                            },
-                        ..
-                    },
-                ..
-            } => match logical_key.as_ref() {
-                Key::Named(NamedKey::Escape) => {
-                    event_loop_window_target.exit();
-                }
-                Key::Character("1") => {
-                    viewer.toggle_draw_model();
-                }
-                Key::Character("2") => {
-                    viewer.toggle_draw_mesh();
-                }
-                _ => {}
-            },
             Event::WindowEvent {
                 event: WindowEvent::Resized(size),
                 ..
@@ -81,24 +165,13 @@ pub fn display(model: Model, invert_zoom: bool) -> Result<(), Error> {
                     height: size.height,
                 });
             }
-            Event::WindowEvent {
-                event: WindowEvent::MouseInput { state, button, .. },
-                ..
-            } => match state {
-                ElementState::Pressed => {
-                    held_mouse_button = Some(button);
-                    viewer.add_focus_point();
-                }
-                ElementState::Released => {
-                    held_mouse_button = None;
-                    viewer.remove_focus_point();
-                }
-            },
-            Event::WindowEvent {
-                event: WindowEvent::MouseWheel { .. },
-                ..
-            } => viewer.add_focus_point(),
             Event::AboutToWait => {
+                // If multiple models have arrived since the last frame, only
+                // the latest one is of interest.
+                if let Some(model) = models.try_iter().last() {
+                    viewer.handle_model_update(model);
+                }
+
                 window.window().request_redraw();
             }
             Event::WindowEvent {
@@ -120,9 +193,7 @@ pub fn display(model: Model, invert_zoom: bool) -> Result<(), Error> {
             }
             _ => {}
         }
-    })?;
-
-    Ok(())
+    }
 }
 
 /// Main loop initialization error
@@ -141,51 +212,56 @@ pub enum Error {
     Graphics(#[from] RendererInitError),
 }
 
-fn input_event<T>(
+/// Store the current view in the view file, if one is configured
+fn save_view(config: &InputConfig, viewer: &Viewer) {
+    if let Some(path) = &config.view_file {
+        if let Err(err) = view_file::write(path, &viewer.camera_view()) {
+            tracing::warn!(
+                "Failed to store view in `{}`: {err}",
+                path.display()
+            );
+        }
+    }
+}
+
+/// Translate a window event into actions, and push them into the queue
+fn push_actions<T>(
     event: &Event<T>,
     window: &Window,
-    held_mouse_button: &Option<MouseButton>,
-    previous_cursor: &mut Option<NormalizedScreenPosition>,
+    bindings: &Bindings,
     invert_zoom: bool,
-) -> Option<InputEvent> {
-    match event {
-        Event::WindowEvent {
-            event: WindowEvent::CursorMoved { position, .. },
+    actions: &mut ActionQueue,
+) {
+    let Event::WindowEvent { event, .. } = event else {
+        return;
+    };
+
+    let action = match event {
+        WindowEvent::KeyboardInput {
+            event:
+                KeyEvent {
+                    logical_key,
+                    state,
+                    repeat: false,
+                    ..
+                },
             ..
-        } => {
+        } => bindings.action(Button::Key(logical_key.clone()), *state),
+        WindowEvent::MouseInput { state, button, .. } => {
+            bindings.action(Button::Mouse(*button), *state)
+        }
+        WindowEvent::CursorMoved { position, .. } => {
             let [width, height] = window.size().as_f64();
             let aspect_ratio = width / height;
 
             // Cursor position in normalized coordinates (-1 to +1) with
             // aspect ratio taken into account.
-            let current = NormalizedScreenPosition {
+            Some(Action::CursorMoved(NormalizedScreenPosition {
                 x: position.x / width * 2. - 1.,
                 y: -(position.y / height * 2. - 1.) / aspect_ratio,
-            };
-            let event = match (*previous_cursor, held_mouse_button) {
-                (Some(previous), Some(button)) => match button {
-                    MouseButton::Left => {
-                        let diff_x = current.x - previous.x;
-                        let diff_y = current.y - previous.y;
-                        let angle_x = -diff_y * ROTATION_SENSITIVITY;
-                        let angle_y = diff_x * ROTATION_SENSITIVITY;
-
-                        Some(InputEvent::Rotation { angle_x, angle_y })
-                    }
-                    MouseButton::Right => {
-                        Some(InputEvent::Translation { previous, current })
-                    }
-                    _ => None,
-                },
-                _ => None,
-            };
-            *previous_cursor = Some(current);
-            event
+            }))
         }
-        Event::WindowEvent {
-            event: WindowEvent::MouseWheel { delta, .. },
-            ..
-        } => {
+        WindowEvent::MouseWheel { delta, .. } => {
             let delta = match delta {
                 MouseScrollDelta::LineDelta(_, y) => {
                     f64::from(*y) * ZOOM_FACTOR_LINE
@@ -197,9 +273,13 @@ fn input_event<T>(
 
             let delta = if invert_zoom { -delta } else { delta };
 
-            Some(InputEvent::Zoom(delta))
+            Some(Action::ZoomDelta(delta))
         }
         _ => None,
+    };
+
+    if let Some(action) = action {
+        actions.push(action);
     }
 }
 
@@ -215,8 +295,6 @@ const ZOOM_FACTOR_LINE: f64 = 0.075;
 /// Larger values will move the camera more with the same input.
 const ZOOM_FACTOR_PIXEL: f64 = 0.005;
 
-/// Affects the speed of rotation given a change in normalized screen position [-1, 1]
-///
-/// Smaller values will move the camera less with the same input.
-/// Larger values will move the camera more with the same input.
-const ROTATION_SENSITIVITY: f64 = 5.;
+/// How long a space mouse may go without reporting motion, before it's
+/// considered to be at rest
+const MOTION_END_TIMEOUT: Duration = Duration::from_millis(500);
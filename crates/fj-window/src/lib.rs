@@ -8,10 +8,20 @@
 //!
 //! [Fornjot]: https://www.fornjot.app/
 
+mod bindings;
+mod config;
 mod display;
+mod space_mouse;
+mod view_file;
 mod window;
 
+#[cfg(target_arch = "wasm32")]
+pub mod web;
+
 pub use self::{
-    display::{display, Error},
+    bindings::{Bindings, Button},
+    config::InputConfig,
+    display::{display, display_updates, Error},
+    space_mouse::{SpaceMouseConfig, SpaceMouseMotion},
     window::WindowError,
 };
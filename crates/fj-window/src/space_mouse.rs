@@ -0,0 +1,214 @@
+//! Support for 6-DoF input devices, like the ones made by 3Dconnexion
+//!
+//! These devices are accessed through [spacenavd], which needs to be running
+//! for them to be available. Where it isn't (or can't be) running, no space
+//! mouse is available, and the window works just the same without one.
+//!
+//! [spacenavd]: https://spacenav.sourceforge.net/
+
+use fj_viewer::Action;
+
+/// Configuration of the space mouse
+#[derive(Clone, Debug)]
+pub struct SpaceMouseConfig {
+    /// How fast the model moves at full deflection of the device
+    ///
+    /// Specified relative to the distance between camera and focus point, per
+    /// second.
+    pub translation_sensitivity: f64,
+
+    /// How fast the model rotates at full deflection of the device
+    ///
+    /// Specified in radians per second.
+    pub rotation_sensitivity: f64,
+
+    /// Invert movement along the x, y, and z axes
+    pub invert_translation: [bool; 3],
+
+    /// Invert rotation around the x, y, and z axes
+    pub invert_rotation: [bool; 3],
+}
+
+impl SpaceMouseConfig {
+    /// Convert motion of the space mouse into an action
+    pub fn action(&self, motion: SpaceMouseMotion) -> Action {
+        let seconds = motion.period.as_secs_f64();
+
+        let apply = |values: [f64; 3], invert: [bool; 3], sensitivity: f64| {
+            let mut values = values.map(|value| value * sensitivity * seconds);
+            for (value, invert) in values.iter_mut().zip(invert) {
+                if invert {
+                    *value = -*value;
+                }
+            }
+            values
+        };
+
+        Action::Motion {
+            translation: apply(
+                motion.translation,
+                self.invert_translation,
+                self.translation_sensitivity,
+            ),
+            rotation: apply(
+                motion.rotation,
+                self.invert_rotation,
+                self.rotation_sensitivity,
+            ),
+        }
+    }
+}
+
+impl Default for SpaceMouseConfig {
+    fn default() -> Self {
+        Self {
+            translation_sensitivity: 1.,
+            rotation_sensitivity: 2.,
+            invert_translation: [false; 3],
+            invert_rotation: [false; 3],
+        }
+    }
+}
+
+/// Motion of a space mouse
+///
+/// Values are relative to the screen, with x pointing right, y pointing up, and
+/// z pointing towards the user. They are normalized, so full deflection of the
+/// device results in a value of about `1`.
+#[derive(Clone, Copy, Debug)]
+pub struct SpaceMouseMotion {
+    /// The deflection of the device along the x, y, and z axes
+    pub translation: [f64; 3],
+
+    /// The deflection of the device around the x, y, and z axes
+    pub rotation: [f64; 3],
+
+    /// The time since the previous motion
+    pub period: std::time::Duration,
+}
+
+impl SpaceMouseMotion {
+    /// Determine whether the device is at rest
+    ///
+    /// spacenavd reports this once the device has been released.
+    pub fn is_at_rest(&self) -> bool {
+        self.translation
+            .into_iter()
+            .chain(self.rotation)
+            .all(|value| value == 0.)
+    }
+}
+
+pub use self::platform::SpaceMouse;
+
+#[cfg(unix)]
+mod platform {
+    use std::{
+        io::{self, Read},
+        os::unix::net::UnixStream,
+        sync::mpsc,
+        thread,
+        time::Duration,
+    };
+
+    use super::SpaceMouseMotion;
+
+    /// A connection to a space mouse
+    pub struct SpaceMouse {
+        motions: mpsc::Receiver<SpaceMouseMotion>,
+    }
+
+    impl SpaceMouse {
+        /// Connect to a space mouse, if one is available
+        ///
+        /// The connection is handled on a separate thread, which makes the
+        /// motions of the device available through [`SpaceMouse::poll`].
+        pub fn connect() -> Option<Self> {
+            let socket = UnixStream::connect(SOCKET).ok()?;
+
+            let (sender, motions) = mpsc::channel();
+            thread::spawn(move || {
+                if let Err(err) = read_motions(socket, sender) {
+                    tracing::warn!("Lost connection to space mouse: {err}");
+                }
+            });
+
+            Some(Self { motions })
+        }
+
+        /// Return the motions that happened since the last poll
+        pub fn poll(&self) -> impl Iterator<Item = SpaceMouseMotion> + '_ {
+            self.motions.try_iter()
+        }
+    }
+
+    fn read_motions(
+        mut socket: UnixStream,
+        sender: mpsc::Sender<SpaceMouseMotion>,
+    ) -> io::Result<()> {
+        loop {
+            // Each event is made up of 8 integers in native byte order. The
+            // first one determines the type of event.
+            let mut buffer = [0; 32];
+            socket.read_exact(&mut buffer)?;
+
+            let mut event = [0; 8];
+            for (value, bytes) in event.iter_mut().zip(buffer.chunks_exact(4)) {
+                *value = i32::from_ne_bytes(
+                    bytes.try_into().expect("Chunk has length 4"),
+                );
+            }
+
+            let [EVENT_MOTION, x, y, z, rx, ry, rz, period] = event else {
+                // We ignore button presses for now.
+                continue;
+            };
+
+            // spacenavd uses a left-handed coordinate system, with z pointing
+            // into the screen.
+            let normalize =
+                |values: [i32; 3]| values.map(|v| f64::from(v) / MAX_VALUE);
+            let motion = SpaceMouseMotion {
+                translation: normalize([x, y, -z]),
+                rotation: normalize([-rx, -ry, rz]),
+                period: Duration::from_millis(
+                    period.max(0).unsigned_abs().into(),
+                ),
+            };
+
+            if sender.send(motion).is_err() {
+                // The receiving side has been dropped. Nobody is interested in
+                // motions anymore.
+                return Ok(());
+            }
+        }
+    }
+
+    const SOCKET: &str = "/var/run/spnav.sock";
+    const EVENT_MOTION: i32 = 0;
+
+    /// The approximate value that spacenavd reports at full deflection
+    const MAX_VALUE: f64 = 350.;
+}
+
+#[cfg(not(unix))]
+mod platform {
+    use super::SpaceMouseMotion;
+
+    /// A connection to a space mouse
+    ///
+    /// Space mice are not supported on this platform, so this never connects.
+    pub struct SpaceMouse;
+
+    impl SpaceMouse {
+        /// Connect to a space mouse, if one is available
+        pub fn connect() -> Option<Self> {
+            None
+        }
+
+        /// Return the motions that happened since the last poll
+        pub fn poll(&self) -> impl Iterator<Item = SpaceMouseMotion> + '_ {
+            std::iter::empty()
+        }
+    }
+}
@@ -0,0 +1,45 @@
+//! Storing the view of the model in a file
+//!
+//! See [`InputConfig::view_file`]. The file contains the eye, target, and up
+//! vector of the view, as nine numbers separated by whitespace.
+//!
+//! [`InputConfig::view_file`]: crate::InputConfig::view_file
+
+use std::{fs, io, path::Path};
+
+use fj_viewer::CameraView;
+
+/// Read a view from the file
+///
+/// Returns `None`, if the file doesn't exist or doesn't contain a view.
+pub fn read(path: &Path) -> Option<CameraView> {
+    let contents = fs::read_to_string(path).ok()?;
+
+    let values = contents
+        .split_whitespace()
+        .map(|value| value.parse::<f64>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    let &[eye_x, eye_y, eye_z, target_x, target_y, target_z, up_x, up_y, up_z] =
+        values.as_slice()
+    else {
+        return None;
+    };
+
+    Some(CameraView {
+        eye: [eye_x, eye_y, eye_z].into(),
+        target: [target_x, target_y, target_z].into(),
+        up: [up_x, up_y, up_z].into(),
+    })
+}
+
+/// Write a view to the file, replacing any view it contained before
+pub fn write(path: &Path, view: &CameraView) -> io::Result<()> {
+    let CameraView { eye, target, up } = view;
+    let values = [eye.coords, target.coords, *up]
+        .into_iter()
+        .flat_map(|vector| vector.components)
+        .map(|value| value.to_string())
+        .collect::<Vec<_>>();
+
+    fs::write(path, values.join(" ") + "\n")
+}
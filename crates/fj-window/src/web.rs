@@ -0,0 +1,144 @@
+//! JavaScript API, for embedding models in web pages
+//!
+//! Only available when compiling to WebAssembly.
+
+use std::{
+    collections::BTreeMap,
+    sync::mpsc::{self, Sender},
+};
+
+use fj_interop::Model;
+use fj_viewer::SceneConfig;
+use wasm_bindgen::prelude::*;
+
+use crate::{config::InputConfig, display::display_updates};
+
+/// The parameters of a model, as set from JavaScript
+///
+/// See [`WebModel`].
+#[derive(Clone, Debug, Default)]
+pub struct Parameters {
+    values: BTreeMap<String, f64>,
+}
+
+impl Parameters {
+    /// Access the value of a parameter
+    ///
+    /// Returns the provided default, if the parameter hasn't been set.
+    pub fn get(&self, name: &str, default: f64) -> f64 {
+        self.values.get(name).copied().unwrap_or(default)
+    }
+
+    /// Set the value of a parameter
+    pub fn set(&mut self, name: impl Into<String>, value: f64) {
+        self.values.insert(name.into(), value);
+    }
+}
+
+/// A model that is embedded in a web page
+///
+/// Model crates that are compiled to WebAssembly export a function that
+/// creates this, passing the code that builds the model from its parameters.
+/// JavaScript then uses that function to get hold of the model:
+///
+/// ``` ignore
+/// #[wasm_bindgen]
+/// pub fn model() -> WebModel {
+///     WebModel::new(|parameters| {
+///         let size = parameters.get("size", 1.);
+///         build_model(size)
+///     })
+/// }
+/// ```
+///
+/// From JavaScript, the model can then be displayed, its parameters changed,
+/// and its mesh fetched.
+#[wasm_bindgen]
+pub struct WebModel {
+    build: Box<dyn FnMut(&Parameters) -> Model>,
+    parameters: Parameters,
+    model: Model,
+    input: InputConfig,
+    viewer: Option<Sender<Model>>,
+}
+
+impl WebModel {
+    /// Create a model, by building it with the default parameters
+    pub fn new(mut build: impl FnMut(&Parameters) -> Model + 'static) -> Self {
+        let parameters = Parameters::default();
+        let model = build(&parameters);
+
+        Self {
+            build: Box::new(build),
+            parameters,
+            model,
+            input: InputConfig::default(),
+            viewer: None,
+        }
+    }
+
+    /// Use the provided configuration, to handle input once displayed
+    pub fn with_input_config(mut self, input: InputConfig) -> Self {
+        self.input = input;
+        self
+    }
+}
+
+#[wasm_bindgen]
+impl WebModel {
+    /// Display the model in a canvas, that is appended to the document body
+    ///
+    /// From then on, the displayed model is updated whenever a parameter
+    /// changes.
+    pub fn display(&mut self) -> Result<(), JsError> {
+        let (sender, models) = mpsc::channel();
+        sender
+            .send(self.model.clone())
+            .expect("Receiver is still in scope, so sending can't fail");
+
+        display_updates(models, self.input.clone(), SceneConfig::default())
+            .map_err(|err| JsError::new(&err.to_string()))?;
+
+        self.viewer = Some(sender);
+        Ok(())
+    }
+
+    /// Set the value of a parameter, and rebuild the model
+    pub fn set_parameter(&mut self, name: &str, value: f64) {
+        self.parameters.set(name, value);
+        self.model = (self.build)(&self.parameters);
+
+        if let Some(viewer) = &self.viewer {
+            // The viewer might not be running anymore. Nothing to update then.
+            let _ = viewer.send(self.model.clone());
+        }
+    }
+
+    /// Access the value of a parameter, if it has been set
+    pub fn parameter(&self, name: &str) -> Option<f64> {
+        self.parameters.values.get(name).copied()
+    }
+
+    /// Access the positions of the mesh's vertices
+    ///
+    /// Contains three coordinates per vertex, and three vertices per triangle.
+    pub fn positions(&self) -> Vec<f32> {
+        self.model
+            .mesh
+            .triangles_f32()
+            .flatten()
+            .flatten()
+            .collect()
+    }
+
+    /// Access the colors of the mesh's triangles
+    ///
+    /// Contains four components (red, green, blue, and alpha) per triangle.
+    pub fn colors(&self) -> Vec<u8> {
+        self.model
+            .mesh
+            .triangles()
+            .flat_map(|triangle| triangle.color.0)
+            .collect()
+    }
+}
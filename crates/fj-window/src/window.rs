@@ -11,7 +11,7 @@ pub struct Window {
 impl Window {
     /// Create an instance of `Window` from the given `EventLoop`
     pub fn new<T>(event_loop: &EventLoop<T>) -> Result<Self, WindowError> {
-        let window = WindowBuilder::new()
+        let builder = WindowBuilder::new()
             .with_title("Fornjot")
             .with_maximized(true)
             // When the window decorations are enabled, I'm seeing the following
@@ -34,8 +34,18 @@ impl Window {
             // being tracked in this issue:
             // https://github.com/hannobraun/fornjot/issues/1848
             .with_decorations(false)
-            .with_transparent(false)
-            .build(event_loop)?;
+            .with_transparent(false);
+
+        #[cfg(target_arch = "wasm32")]
+        let builder = {
+            use winit::platform::web::WindowBuilderExtWebSys;
+
+            // Append the canvas to the document body, so the viewer shows up
+            // without requiring any setup on the JavaScript side.
+            builder.with_append(true)
+        };
+
+        let window = builder.build(event_loop)?;
 
         Ok(Self {
             inner: Arc::new(window),
@@ -1,8 +1,14 @@
-use std::{num::ParseFloatError, path::PathBuf, str::FromStr};
+use std::{net::SocketAddr, num::ParseFloatError, path::PathBuf, str::FromStr};
 
-use fj_core::algorithms::approx::{InvalidTolerance, Tolerance};
+use fj_core::algorithms::{
+    approx::{InvalidTolerance, Tolerance},
+    triangulate::{InvalidMaxEdgeLength, MaxEdgeLength},
+};
+use fj_export::{AxisConvention, StlColorFormat};
 use fj_math::Scalar;
 
+use crate::Profile;
+
 /// Standardized CLI for Fornjot models
 ///
 /// This is completely optional, as models are just Rust code and don't need any
@@ -17,16 +23,99 @@ use fj_math::Scalar;
 #[derive(clap::Parser)]
 pub struct Args {
     /// Export model to this path
+    ///
+    /// The file format is inferred from the extension. Can be passed multiple
+    /// times, to export to multiple files. The model is only triangulated
+    /// once, regardless.
     #[arg(short, long, value_name = "PATH")]
-    pub export: Option<PathBuf>,
+    pub export: Vec<PathBuf>,
+
+    /// Export each part of the model to its own file
+    ///
+    /// Parts are pieces of the model that don't touch each other. The files
+    /// are named after the export path, with the name of the part (if all of
+    /// its faces share one) or its number appended.
+    #[arg(long)]
+    pub split_parts: bool,
+
+    /// Convert the exported model to this axis convention
+    ///
+    /// Possible values are `z-up` (the default), `y-up`, and
+    /// `y-up-left-handed`.
+    #[arg(long, value_name = "CONVENTION", default_value = "z-up")]
+    pub axes: AxisConvention,
+
+    /// Store the colors of the model in exported STL files
+    ///
+    /// The STL format has no standard way of storing colors. Possible values
+    /// are `viscam` and `magics`, which select the convention used by the
+    /// respective software. Overrides the configuration of the instance.
+    #[arg(long, value_name = "FORMAT")]
+    pub stl_color: Option<StlColorFormat>,
+
+    /// Repair the exported mesh
+    ///
+    /// Welds duplicate vertices, removes degenerate and duplicate triangles,
+    /// unifies their winding, and fills small holes. The distances below
+    /// which vertices and triangles are considered duplicate or degenerate
+    /// are scaled to the size of the model.
+    #[arg(long)]
+    pub repair: bool,
+
+    /// The profile to process the model with
+    ///
+    /// Possible values are `draft`, `standard` (the default), and `final`.
+    /// Selects the default tolerance, and how validation errors are handled.
+    #[arg(long, value_enum, default_value_t)]
+    pub profile: Profile,
 
     /// How much the export can deviate from the original model
+    ///
+    /// Overrides the tolerance of the selected profile.
     #[arg(short, long, value_parser = parse_tolerance)]
     pub tolerance: Option<Tolerance>,
 
+    /// Limit the angle between adjacent segments of approximated curves
+    ///
+    /// The angle is given in degrees, and is respected in addition to the
+    /// tolerance.
+    #[arg(long, value_name = "DEGREES", value_parser = parse_angle)]
+    pub angular_deflection: Option<Scalar>,
+
+    /// Subdivide triangles, until none of their edges is longer than this
+    #[arg(long, value_name = "LENGTH", value_parser = parse_max_edge_length)]
+    pub max_edge_length: Option<MaxEdgeLength>,
+
     /// Ignore validation errors
+    ///
+    /// Has no effect with the `final` profile.
     #[arg(short, long)]
     pub ignore_validation: bool,
+
+    /// Print statistics about the model
+    #[arg(long)]
+    pub stats: bool,
+
+    /// Display this mesh alongside the model, for reference
+    ///
+    /// Supports STL and OBJ files. The reference mesh can be aligned with the
+    /// model interactively. Has no effect, unless the model is displayed.
+    #[arg(long, value_name = "PATH")]
+    pub reference: Option<PathBuf>,
+
+    /// Serve the model over HTTP at this address, instead of displaying it
+    #[arg(long, value_name = "ADDRESS")]
+    pub serve: Option<SocketAddr>,
+
+    /// Re-evaluate the served model, whenever its source changes
+    ///
+    /// Requires the model to be started with `cargo run`.
+    #[arg(long, requires = "serve")]
+    pub watch: bool,
+
+    /// Write the evaluated model into this directory, for a watching server
+    #[arg(long, value_name = "DIR", hide = true, conflicts_with = "serve")]
+    pub evaluate_into: Option<PathBuf>,
 }
 
 impl Args {
@@ -47,11 +136,29 @@ fn parse_tolerance(input: &str) -> Result<Tolerance, ArgsError> {
     Ok(tolerance)
 }
 
+fn parse_angle(input: &str) -> Result<Scalar, ArgsError> {
+    let degrees = f64::from_str(input)?;
+    let radians = Scalar::from_f64(degrees.to_radians());
+
+    Ok(radians)
+}
+
+fn parse_max_edge_length(input: &str) -> Result<MaxEdgeLength, ArgsError> {
+    let max_edge_length = f64::from_str(input)?;
+    let max_edge_length = Scalar::from_f64(max_edge_length);
+    let max_edge_length = MaxEdgeLength::from_scalar(max_edge_length)?;
+
+    Ok(max_edge_length)
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum ArgsError {
-    #[error("Error parsing tolerance")]
-    ParseTolerance(#[from] ParseFloatError),
+    #[error("Error parsing number")]
+    ParseFloat(#[from] ParseFloatError),
 
     #[error(transparent)]
     InvalidTolerance(#[from] InvalidTolerance),
+
+    #[error(transparent)]
+    InvalidMaxEdgeLength(#[from] InvalidMaxEdgeLength),
 }
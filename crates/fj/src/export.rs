@@ -0,0 +1,13 @@
+//! # Export triangle meshes to files
+//!
+//! Re-exports the functionality of [`fj_export`]. See the [crate-level
+//! documentation] for the stability guarantees that apply.
+//!
+//! [crate-level documentation]: crate#api-stability
+
+pub use fj_export::{
+    convert_axes, export, export_3mf, export_3mf_with_faces, export_glb,
+    export_obj, export_parts, export_parts_with_config, export_scene_glb,
+    export_stl, export_stl_with_color, export_with_config, AxisConvention,
+    Error, ExportConfig, Format, Scene, SceneMesh, SceneNode, StlColorFormat,
+};
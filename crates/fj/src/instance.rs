@@ -2,18 +2,34 @@ use std::{error::Error as _, fmt};
 
 use fj_core::{
     algorithms::{
-        approx::{InvalidTolerance, Tolerance},
+        approx::{InvalidAngularDeflection, InvalidTolerance, Tolerance},
         bounding_volume::BoundingVolume,
-        triangulate::Triangulate,
+        triangulate::{MaxEdgeLength, Triangulate},
     },
+    queries::Stats,
     validation::{ValidationConfig, ValidationErrors},
     Core,
 };
-use fj_interop::Model;
+use fj_interop::{Mesh, RepairConfig};
 use fj_math::{Aabb, Point, Scalar};
+use tracing::warn;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-use crate::Args;
+use crate::{export::ExportConfig, serve::ServeError, Args, Watch};
+
+#[cfg(feature = "display")]
+use {
+    crate::{
+        viewer::{ReferenceMesh, SceneConfig},
+        window::InputConfig,
+    },
+    fj_interop::Model,
+    std::{
+        sync::mpsc,
+        thread,
+        time::{Duration, Instant},
+    },
+};
 
 /// An instance of Fornjot
 ///
@@ -22,6 +38,23 @@ use crate::Args;
 pub struct Instance {
     /// The instance of the Fornjot core
     pub core: Core,
+
+    /// How the model is exported
+    ///
+    /// Can be overridden using command-line arguments.
+    pub export: ExportConfig,
+
+    /// How the model presents itself, when it is displayed
+    ///
+    /// Has no effect when exporting the model.
+    #[cfg(feature = "display")]
+    pub scene: SceneConfig,
+
+    /// How the window handles input, when the model is displayed
+    ///
+    /// This includes which keys and mouse buttons trigger which actions.
+    #[cfg(feature = "display")]
+    pub input: InputConfig,
 }
 
 impl Instance {
@@ -33,7 +66,14 @@ impl Instance {
     /// Construct an instance of `Instance`, using the provided configuration
     pub fn with_validation_config(config: ValidationConfig) -> Self {
         let core = fj_core::Core::with_validation_config(config);
-        Self { core }
+        Self {
+            core,
+            export: ExportConfig::default(),
+            #[cfg(feature = "display")]
+            scene: SceneConfig::default(),
+            #[cfg(feature = "display")]
+            input: InputConfig::default(),
+        }
     }
 
     /// Export or display a model, according to CLI arguments
@@ -44,10 +84,17 @@ impl Instance {
     ///
     /// This function is used by Fornjot's own testing infrastructure, but is
     /// useful beyond that, when using Fornjot directly to define a model.
+    ///
+    /// When displaying the model, it is triangulated on a background thread,
+    /// while the window is already open. The faces that have been
+    /// triangulated so far are displayed periodically, so large models appear
+    /// progressively. Displaying the model requires the `display` feature. If
+    /// that is disabled, the model must be exported or served instead.
     pub fn process_model<M>(&mut self, model: &M) -> Result
     where
         for<'r> (&'r M, Tolerance): Triangulate,
-        M: BoundingVolume<3>,
+        for<'r> (&'r M, Tolerance, MaxEdgeLength): Triangulate,
+        M: BoundingVolume<3> + Stats + Sync,
     {
         tracing_subscriber::registry()
             .with(tracing_subscriber::fmt::layer())
@@ -56,8 +103,27 @@ impl Instance {
 
         let args = Args::parse();
 
-        if !args.ignore_validation {
-            self.core.layers.validation.take_errors()?;
+        let validation = self.core.layers.validation.take_errors();
+
+        // In server mode, validation errors are reported to clients, instead
+        // of aborting.
+        let report_validation =
+            args.serve.is_none() && args.evaluate_into.is_none();
+        let validation = match validation {
+            Err(err)
+                if report_validation
+                    && args.profile.validation_errors_are_fatal(
+                        args.ignore_validation,
+                    ) =>
+            {
+                return Err(err.into());
+            }
+            validation => validation,
+        };
+        if let Err(err) = &validation {
+            if report_validation && !args.ignore_validation {
+                warn!("{err}");
+            }
         }
 
         let aabb = model.aabb().unwrap_or(Aabb {
@@ -69,7 +135,7 @@ impl Instance {
             None => {
                 // Compute a reasonable default for the tolerance value. To do
                 // this, we just look at the smallest non-zero extent of the
-                // bounding box and divide that by some value.
+                // bounding box and scale that according to the profile.
 
                 let mut min_extent = Scalar::MAX;
                 for extent in aabb.size().components {
@@ -78,27 +144,145 @@ impl Instance {
                     }
                 }
 
-                let tolerance = min_extent / Scalar::from_f64(1000.);
+                let tolerance = min_extent
+                    * Scalar::from_f64(args.profile.relative_tolerance());
                 Tolerance::from_scalar(tolerance)?
             }
             Some(user_defined_tolerance) => user_defined_tolerance,
         };
+        let tolerance = match args.angular_deflection {
+            Some(angle) => tolerance.with_angular_deflection(angle)?,
+            None => tolerance,
+        };
+
+        // Check the export paths early, so a typo doesn't only show up after
+        // the model has been triangulated.
+        for path in &args.export {
+            crate::export::Format::from_path(path)?;
+        }
+
+        let watch = match (args.serve, args.watch) {
+            (Some(_), true) => Some(
+                Watch::current_process().ok_or(ServeError::WatchUnavailable)?,
+            ),
+            _ => None,
+        };
+        let headless = !args.export.is_empty()
+            || args.serve.is_some()
+            || args.evaluate_into.is_some();
+
+        #[cfg(not(feature = "display"))]
+        if !headless {
+            return Err(Error::DisplayNotSupported);
+        }
+
+        #[cfg(feature = "display")]
+        if !headless {
+            let (sender, models) = mpsc::channel();
+            let core = &mut self.core;
+            let mut scene = self.scene.clone();
+            let input = self.input.clone();
+            if let Some(path) = &args.reference {
+                scene.reference_mesh = Some(ReferenceMesh::load(path)?);
+            }
+            let args = &args;
 
-        let mesh = (model, tolerance).triangulate(&mut self.core);
+            return thread::scope(|scope| {
+                scope.spawn(move || {
+                    // Every update contains all faces triangulated so far, and
+                    // replaces the previous one completely.
+                    let mut last_update = Instant::now();
+                    let mesh =
+                        triangulate(model, tolerance, args, core, |mesh| {
+                            if last_update.elapsed() < PARTIAL_UPDATE_INTERVAL {
+                                return;
+                            }
+                            last_update = Instant::now();
 
-        if let Some(path) = args.export {
-            crate::export::export(&mesh, &path)?;
+                            let mesh = mesh.clone();
+                            let _ = sender.send(Model { mesh, aabb });
+                        });
+
+                    // Nobody is listening anymore, if the window has been
+                    // closed in the meantime. That's fine.
+                    let _ = sender.send(Model { mesh, aabb });
+                });
+
+                crate::window::display_updates(models, input, scene)?;
+
+                Ok(())
+            });
+        }
+
+        let mesh = triangulate(model, tolerance, &args, &mut self.core, |_| {});
+
+        if let Some(dir) = &args.evaluate_into {
+            crate::serve::write_evaluation(&mesh, &validation, dir)?;
             return Ok(());
         }
 
-        let model = Model { mesh, aabb };
+        if !args.export.is_empty() {
+            let mesh = if args.repair {
+                mesh.repair(&RepairConfig::for_mesh(&mesh))
+            } else {
+                mesh
+            };
+            let mesh = crate::export::convert_axes(&mesh, args.axes);
 
-        crate::window::display(model, false)?;
+            let mut config = self.export;
+            if let Some(stl_color) = args.stl_color {
+                config.stl_color = Some(stl_color);
+            }
+
+            for path in &args.export {
+                if args.split_parts {
+                    crate::export::export_parts_with_config(
+                        &mesh, path, &config,
+                    )?;
+                } else {
+                    crate::export::export_with_config(&mesh, path, &config)?;
+                }
+            }
+            return Ok(());
+        }
+
+        if let Some(addr) = args.serve {
+            crate::serve::serve(&mesh, &validation, addr, watch)?;
+        }
 
         Ok(())
     }
 }
 
+/// How often to display partial results, while triangulating a model
+#[cfg(feature = "display")]
+const PARTIAL_UPDATE_INTERVAL: Duration = Duration::from_millis(200);
+
+fn triangulate<M>(
+    model: &M,
+    tolerance: Tolerance,
+    args: &Args,
+    core: &mut Core,
+    progress: impl FnMut(&Mesh<Point<3>>),
+) -> Mesh<Point<3>>
+where
+    for<'r> (&'r M, Tolerance): Triangulate,
+    for<'r> (&'r M, Tolerance, MaxEdgeLength): Triangulate,
+    M: Stats,
+{
+    let mesh = match args.max_edge_length {
+        Some(max_edge_length) => (model, tolerance, max_edge_length)
+            .triangulate_with_progress(core, progress),
+        None => (model, tolerance).triangulate_with_progress(core, progress),
+    };
+
+    if args.stats {
+        println!("{}, {} triangles", model.stats(), mesh.triangles().count());
+    }
+
+    mesh
+}
+
 /// Return value of [`Instance::process_model`]
 pub type Result = std::result::Result<(), Error>;
 
@@ -110,17 +294,39 @@ pub enum Error {
     Tracing(#[from] tracing::subscriber::SetGlobalDefaultError),
 
     /// Error displaying model
+    #[cfg(feature = "display")]
     #[error("Error displaying model")]
     Display(#[from] crate::window::Error),
 
+    /// Error loading the reference mesh to display alongside the model
+    #[cfg(feature = "display")]
+    #[error("Error loading reference mesh")]
+    Reference(#[from] crate::viewer::LoadReferenceError),
+
+    /// Model was to be displayed, but support for that is not available
+    #[cfg(not(feature = "display"))]
+    #[error(
+        "Can't display model, as the `display` feature is disabled. Export or \
+        serve the model instead."
+    )]
+    DisplayNotSupported,
+
     /// Error exporting model
     #[error("Error exporting model")]
     Export(#[from] crate::export::Error),
 
+    /// Error serving model
+    #[error("Error serving model")]
+    Serve(#[from] ServeError),
+
     /// Invalid tolerance
     #[error(transparent)]
     Tolerance(#[from] InvalidTolerance),
 
+    /// Invalid angular deflection
+    #[error(transparent)]
+    AngularDeflection(#[from] InvalidAngularDeflection),
+
     /// Unhandled validation errors
     #[error(transparent)]
     Validation(#[from] ValidationErrors),
@@ -4,22 +4,189 @@
 //! split into multiple libraries that can be used semi-independently, and this
 //! is one of those.
 //!
-//! This crate serves as a convenient entryway to Fornjot, re-exporting all
-//! crates that make up Fornjot.
+//! This crate serves as a convenient entryway to Fornjot, providing a stable
+//! API for defining models on top of the other crates that make up Fornjot.
+//!
+//! ## API Stability
+//!
+//! The internals of Fornjot change quickly, and their module layout gets
+//! reorganized frequently. To keep models working across those changes, the
+//! following parts of this crate make up a curated API that is kept stable:
+//!
+//! - [`prelude`]: The most commonly used items, for glob imports
+//! - [`math`]: Math primitives
+//! - [`operations`]: Operations that create and modify shapes
+//! - [`export`]: Export of triangle meshes to files
+//! - The items defined at the root of this crate, like [`Instance`]
+//!
+//! Changes to these follow [semantic versioning], meaning that (while Fornjot
+//! is still at `0.x`) breaking changes only happen in releases that increase
+//! the minor version. Items that are renamed or removed stay available under
+//! their old name for at least one such release, marked as deprecated, with a
+//! note that points to their replacement.
+//!
+//! All other crates that make up Fornjot are re-exported too ([`core`],
+//! [`interop`], `viewer`, `window`), for anything not covered by the curated
+//! API. These re-exports come without any stability guarantees.
+//!
+//! ## Features
+//!
+//! - `display` (enabled by default): Display models in a window. This pulls in
+//!   the viewer and window crates, and with them, the whole GUI stack. Disable
+//!   it to build headless applications, that only export or serve models.
+//! - `hot-reload`: Reload the viewer's shaders and configuration, when they
+//!   change on disk.
+//! - `image`: Support creating sketches from image files.
 //!
 //! [Fornjot]: https://www.fornjot.app/
+//! [semantic versioning]: https://semver.org/
+
+pub mod export;
+pub mod math;
+pub mod operations;
+pub mod prelude;
 
 mod args;
 mod instance;
+mod profile;
+mod serve;
 
 pub use self::{
     args::Args,
     instance::{Error, Instance, Result},
+    profile::Profile,
+    serve::{serve, ServeError, Watch},
 };
 
+pub use fj_core::Core;
+
 pub use fj_core as core;
-pub use fj_export as export;
 pub use fj_interop as interop;
-pub use fj_math as math;
+#[cfg(feature = "display")]
 pub use fj_viewer as viewer;
+#[cfg(feature = "display")]
 pub use fj_window as window;
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::BTreeSet,
+        fs,
+        path::{Path, PathBuf},
+    };
+
+    /// Traits that are only used in trait bounds, not called by models
+    const NOT_IN_PRELUDE: &[&str] = &["IsInserted"];
+
+    #[test]
+    fn preludes_contain_all_operation_traits() {
+        let mut traits = BTreeSet::new();
+        collect_traits(&crates().join("fj-core/src/operations"), &mut traits);
+        assert!(!traits.is_empty());
+
+        for prelude in ["fj/src/prelude.rs", "fj-core/src/prelude.rs"] {
+            let source = read(prelude);
+            let exported = identifiers(&source);
+
+            let missing = traits
+                .iter()
+                .filter(|name| !NOT_IN_PRELUDE.contains(&name.as_str()))
+                .filter(|name| !exported.contains(&name.as_str()))
+                .collect::<Vec<_>>();
+
+            assert!(missing.is_empty(), "`{prelude}` is missing {missing:?}");
+        }
+    }
+
+    #[test]
+    fn curated_modules_keep_previous_paths() {
+        // Before the curated API existed, `fj::math` and `fj::export` were
+        // plain re-exports of these crates.
+        for (module, lib) in [
+            ("fj/src/math.rs", "fj-math/src/lib.rs"),
+            ("fj/src/export.rs", "fj-export/src/lib.rs"),
+        ] {
+            let module_source = read(module);
+            let lib_source = read(lib);
+            let exported = identifiers(&module_source);
+
+            let missing = public_items(&lib_source)
+                .into_iter()
+                .filter(|name| !exported.contains(name))
+                .collect::<Vec<_>>();
+
+            assert!(missing.is_empty(), "`{module}` is missing {missing:?}");
+        }
+    }
+
+    fn crates() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("..")
+    }
+
+    fn read(path: impl AsRef<Path>) -> String {
+        fs::read_to_string(crates().join(path)).unwrap()
+    }
+
+    fn collect_traits(dir: &Path, traits: &mut BTreeSet<String>) {
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+
+            if path.is_dir() {
+                collect_traits(&path, traits);
+                continue;
+            }
+
+            let source = fs::read_to_string(&path).unwrap();
+            for line in source.lines() {
+                let Some(rest) = line.strip_prefix("pub trait ") else {
+                    continue;
+                };
+                if let Some(name) = identifiers(rest).into_iter().next() {
+                    traits.insert(name.to_string());
+                }
+            }
+        }
+    }
+
+    /// Names of the items a crate root defines or re-exports
+    fn public_items(source: &str) -> BTreeSet<&str> {
+        let mut items = BTreeSet::new();
+        let mut in_use = false;
+
+        // Only consider top-level items, not methods in `impl` blocks.
+        for line in source.lines() {
+            if let Some(rest) = line.strip_prefix("pub use ") {
+                in_use = true;
+                items.extend(reexported_names(rest));
+            } else if in_use {
+                items.extend(reexported_names(line));
+            } else if let Some(rest) = ["pub fn ", "pub struct ", "pub enum "]
+                .into_iter()
+                .find_map(|prefix| line.strip_prefix(prefix))
+            {
+                items.extend(identifiers(rest).into_iter().next());
+            }
+
+            if line.ends_with(';') {
+                in_use = false;
+            }
+        }
+
+        items
+    }
+
+    /// The last segments of the paths in (part of) a `use` declaration
+    fn reexported_names(source: &str) -> impl Iterator<Item = &str> {
+        source
+            .split(|c: char| c.is_whitespace() || ",{};".contains(c))
+            .filter(|path| !path.is_empty() && !path.ends_with("::"))
+            .filter_map(|path| path.rsplit("::").next())
+    }
+
+    fn identifiers(source: &str) -> Vec<&str> {
+        source
+            .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .filter(|s| !s.is_empty())
+            .collect()
+    }
+}
@@ -0,0 +1,12 @@
+//! # Math primitives
+//!
+//! Re-exports the types from [`fj_math`] that are used to define models. See
+//! the [crate-level documentation] for the stability guarantees that apply.
+//!
+//! [crate-level documentation]: crate#api-stability
+
+pub use fj_math::{
+    Aabb, Arc, Circle, CubicBezier, Ellipse, Line, NurbsSurface, Plane, Point,
+    PolyChain, Ray, Scalar, Segment, Sign, Transform, Triangle, Uv, Vector,
+    Winding, Xyz, T,
+};
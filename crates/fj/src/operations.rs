@@ -0,0 +1,78 @@
+//! # Create and modify shapes
+//!
+//! Re-exports the operations from [`fj_core::operations`], which are spread
+//! over many modules there, in a single flat namespace. This way, models keep
+//! working when that module layout changes. See the [crate-level
+//! documentation] for the stability guarantees that apply.
+//!
+//! All traits in this module are also available in the [`prelude`].
+//!
+//! [crate-level documentation]: crate#api-stability
+//! [`prelude`]: crate::prelude
+
+// Building and inserting objects
+pub use fj_core::operations::{
+    build::{
+        BuildCycle, BuildFace, BuildHalfEdge, BuildRegion, BuildShell,
+        BuildSketch, BuildSolid, BuildSolidError, BuildSurface, Polygon,
+        Tetrahedron, TetrahedronShell,
+    },
+    derive::DeriveFrom,
+    insert::Insert,
+    presentation::{GetColor, GetName, SetColor, SetName},
+};
+
+// Updating objects
+pub use fj_core::operations::{
+    cleanup::RemoveInternalFaces,
+    join::JoinCycle,
+    merge::Merge,
+    move_vertex::{MoveVertex, MoveVertexError},
+    replace::{ReplaceCurve, ReplaceHalfEdge, ReplaceOutput, ReplaceVertex},
+    reverse::{Reverse, ReverseCurveCoordinateSystems},
+    split::{SplitEdge, SplitFace, SplitHalfEdge, SplitShell},
+    update::{
+        UpdateCycle, UpdateFace, UpdateHalfEdge, UpdateRegion, UpdateShell,
+        UpdateSketch, UpdateSolid,
+    },
+};
+
+// Creating and modifying sketches
+pub use fj_core::operations::{
+    bitmap::{Bitmap, TraceBitmap},
+    boolean::{Boolean, BooleanError},
+    offset::{OffsetRegion, OffsetRegionError},
+};
+
+// Creating solids from sketches
+pub use fj_core::operations::{
+    emboss::{Emboss, EmbossError},
+    pipe::{Pipe, PipeError, PipePath},
+    revolve::{Revolve, RevolveError},
+    sweep::{
+        SweepCache, SweepCycle, SweepDirection, SweepFace, SweepFaceOfShell,
+        SweepHalfEdge, SweepOptions, SweepRegion, SweepSketch,
+        SweepSketchError, SweepSurfacePath, SweepVertex, SweptCycle,
+        SweptRegion, SweptShell,
+    },
+};
+
+// Modifying solids
+pub use fj_core::operations::{
+    chamfer::{ChamferDistances, ChamferEdge, ChamferEdgeError},
+    extend::{ExtendFace, ExtendFaceError},
+    holes::{AddHole, HoleLocation},
+    pad::{Pad, PadError},
+    pocket::{Pocket, PocketDepth, PocketError},
+};
+
+// Querying solids
+pub use fj_core::operations::section::{
+    SectionWithPlane, SectionWithPlaneError,
+};
+
+// Positioning objects
+pub use fj_core::operations::{
+    place::{Place, Placed},
+    transform::{Mirror, TransformCache, TransformObject},
+};
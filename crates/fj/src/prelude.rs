@@ -0,0 +1,43 @@
+//! # Convenient access to the most commonly used items
+//!
+//! Re-exports the object types, all operation traits from [`operations`], as
+//! well as the most commonly used math types. Intended to be used with a glob
+//! import, by code that defines models:
+//!
+//! ```
+//! use fj::prelude::*;
+//! ```
+//!
+//! Unlike [`fj_core::prelude`], the contents of this module are covered by the
+//! stability guarantees described in the [crate-level documentation].
+//!
+//! [`operations`]: crate::operations
+//! [crate-level documentation]: crate#api-stability
+
+pub use fj_core::{
+    objects::{
+        Curve, Cycle, Face, HalfEdge, Region, Shell, Sketch, Solid, Surface,
+        Vertex,
+    },
+    storage::Handle,
+    Core,
+};
+
+pub use crate::{
+    math::{Point, Scalar, Vector},
+    operations::{
+        AddHole, Boolean, BuildCycle, BuildFace, BuildHalfEdge, BuildRegion,
+        BuildShell, BuildSketch, BuildSolid, BuildSurface, ChamferEdge,
+        DeriveFrom, Emboss, ExtendFace, GetColor, GetName, Insert, JoinCycle,
+        Merge, Mirror, MoveVertex, OffsetRegion, Pad, Pipe, PipePath, Place,
+        Pocket, PocketDepth, RemoveInternalFaces, ReplaceCurve,
+        ReplaceHalfEdge, ReplaceVertex, Reverse, ReverseCurveCoordinateSystems,
+        Revolve, SectionWithPlane, SetColor, SetName, SplitEdge, SplitFace,
+        SplitHalfEdge, SplitShell, SweepCycle, SweepDirection, SweepFace,
+        SweepFaceOfShell, SweepHalfEdge, SweepRegion, SweepSketch,
+        SweepSurfacePath, SweepVertex, TraceBitmap, TransformObject,
+        UpdateCycle, UpdateFace, UpdateHalfEdge, UpdateRegion, UpdateShell,
+        UpdateSketch, UpdateSolid,
+    },
+    Instance,
+};
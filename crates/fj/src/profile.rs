@@ -0,0 +1,49 @@
+/// A named set of configuration values for processing a model
+///
+/// Selects the tolerance and the handling of validation errors in one place.
+/// Can be selected on the command line, using `--profile`. See [`Args`].
+///
+/// [`Args`]: crate::Args
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, clap::ValueEnum)]
+pub enum Profile {
+    /// For fast feedback, while working on a model
+    ///
+    /// Uses a coarse tolerance, and prints validation errors as warnings,
+    /// instead of aborting.
+    Draft,
+
+    /// The configuration that is used, unless another profile is selected
+    ///
+    /// Aborts on validation errors, unless they are explicitly ignored.
+    #[default]
+    Standard,
+
+    /// For the final export of a model
+    ///
+    /// Uses a fine tolerance, and always aborts on validation errors, even if
+    /// they are supposed to be ignored.
+    Final,
+}
+
+impl Profile {
+    /// The tolerance, relative to the size of the model
+    ///
+    /// This is multiplied with the smallest non-zero extent of the model's
+    /// bounding box, unless a tolerance is provided explicitly.
+    pub fn relative_tolerance(&self) -> f64 {
+        match self {
+            Self::Draft => 1e-2,
+            Self::Standard => 1e-3,
+            Self::Final => 1e-4,
+        }
+    }
+
+    /// Whether validation errors abort the processing of a model
+    pub fn validation_errors_are_fatal(&self, ignore_validation: bool) -> bool {
+        match self {
+            Self::Draft => false,
+            Self::Standard => !ignore_validation,
+            Self::Final => true,
+        }
+    }
+}
@@ -0,0 +1,864 @@
+use std::{
+    collections::BTreeMap,
+    env,
+    ffi::OsString,
+    fs,
+    io::{self, BufRead, BufReader, Cursor, Read, Write},
+    net::{Shutdown, SocketAddr, TcpListener, TcpStream},
+    path::{Path, PathBuf},
+    process::Command,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Condvar, Mutex,
+    },
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use base64::Engine as _;
+use fj_core::validation::ValidationErrors;
+use fj_interop::Mesh;
+use fj_math::Point;
+use sha1::{Digest as _, Sha1};
+
+/// Serve a mesh and its validation status over HTTP and WebSocket
+///
+/// This is a minimal server that is meant to make models available to remote
+/// viewers or other tooling, without opening a window. It handles every
+/// connection on its own thread, until the process is stopped.
+///
+/// The following resources are available:
+///
+/// - `/mesh.3mf`, `/mesh.stl`, `/mesh.obj`, `/mesh.glb`: The mesh in the
+///   respective format.
+/// - `/validation`: `ok`, if the model is valid; the validation errors as plain
+///   text otherwise.
+/// - `/updates`: A WebSocket that streams the model. Right after connecting,
+///   and whenever the model has been re-evaluated, the server sends a text
+///   message with the status of the model, followed by a binary message with
+///   the mesh as binary glTF.
+///
+/// The status is a JSON object like this one:
+///
+/// ``` json
+/// { "revision": 2, "valid": true, "validation": "ok", "error": null }
+/// ```
+///
+/// `error` is set, if re-evaluating the model failed. The mesh and validation
+/// status of the last successful evaluation are still served then.
+///
+/// If a [`Watch`] is provided, the model is re-evaluated whenever its source
+/// changes. Otherwise, the process needs to be restarted to serve an updated
+/// model.
+pub fn serve(
+    mesh: &Mesh<Point<3>>,
+    validation: &Result<(), ValidationErrors>,
+    addr: SocketAddr,
+    watch: Option<Watch>,
+) -> Result<(), ServeError> {
+    let evaluation = Evaluation::new(mesh, validation)?;
+    let shared = Arc::new(Shared::new(evaluation));
+
+    let listener = TcpListener::bind(addr)
+        .map_err(|source| ServeError::Bind { addr, source })?;
+
+    if let Some(watch) = watch {
+        let shared = shared.clone();
+        thread::spawn(move || watch.run(&shared));
+    }
+
+    for stream in listener.incoming() {
+        let stream = stream.map_err(ServeError::Accept)?;
+        let shared = shared.clone();
+
+        thread::spawn(move || {
+            if let Err(err) = handle_connection(stream, &shared) {
+                tracing::warn!("Error handling connection: {err}");
+            }
+        });
+    }
+
+    Ok(())
+}
+
+/// Write the mesh and validation status into a directory
+///
+/// This is how a model reports the results of its evaluation to the server
+/// that re-evaluates it. See [`Watch`].
+pub(crate) fn write_evaluation(
+    mesh: &Mesh<Point<3>>,
+    validation: &Result<(), ValidationErrors>,
+    dir: &Path,
+) -> Result<(), ServeError> {
+    let write = |path: PathBuf, contents: &[u8]| {
+        fs::write(&path, contents)
+            .map_err(|source| ServeError::WriteEvaluation { path, source })
+    };
+
+    fs::create_dir_all(dir).map_err(|source| ServeError::WriteEvaluation {
+        path: dir.to_path_buf(),
+        source,
+    })?;
+
+    for extension in FORMATS {
+        write(
+            dir.join(format!("mesh.{extension}")),
+            &export(mesh, extension)?,
+        )?;
+    }
+
+    let validation = match validation {
+        Ok(()) => String::new(),
+        Err(err) => err.to_string(),
+    };
+    write(dir.join(VALIDATION_FILE), validation.as_bytes())
+}
+
+/// Re-evaluation of a served model, whenever its source changes
+///
+/// See [`serve`].
+#[derive(Clone, Debug)]
+pub struct Watch {
+    /// The directory that contains the source of the model
+    ///
+    /// Changes to any file within it trigger a re-evaluation, except for
+    /// files in `target` and hidden directories.
+    pub path: PathBuf,
+
+    /// The program that re-evaluates the model
+    pub program: OsString,
+
+    /// The arguments that the program is invoked with
+    ///
+    /// `--evaluate-into` and a directory are appended to them. The program is
+    /// expected to evaluate the model, and to write the results into that
+    /// directory, like [`Instance::process_model`] does with that argument.
+    ///
+    /// [`Instance::process_model`]: crate::Instance::process_model
+    pub args: Vec<OsString>,
+}
+
+impl Watch {
+    /// How often the source of the model is checked for changes
+    const INTERVAL: Duration = Duration::from_millis(500);
+
+    /// Watch the model that the current process evaluates
+    ///
+    /// Re-evaluates the model by building and running the current executable
+    /// with `cargo run`, passing the same arguments, except for those related
+    /// to serving. Returns `None`, if the current process hasn't been started
+    /// by `cargo run`, as the source of the model is unknown then.
+    pub fn current_process() -> Option<Self> {
+        let path = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR")?);
+        let bin = env::current_exe().ok()?.file_stem()?.to_os_string();
+
+        let mut args: Vec<OsString> = vec![
+            "run".into(),
+            "--quiet".into(),
+            "--manifest-path".into(),
+            path.join("Cargo.toml").into(),
+            "--bin".into(),
+            bin,
+            "--".into(),
+        ];
+
+        let mut current_args = env::args_os().skip(1);
+        while let Some(arg) = current_args.next() {
+            let arg_str = arg.to_string_lossy();
+            if arg_str == "--serve" {
+                current_args.next();
+                continue;
+            }
+            if arg_str.starts_with("--serve=") || arg_str == "--watch" {
+                continue;
+            }
+            args.push(arg);
+        }
+
+        Some(Self {
+            path,
+            program: env::var_os("CARGO").unwrap_or_else(|| "cargo".into()),
+            args,
+        })
+    }
+
+    fn run(self, shared: &Shared) {
+        let mut last_modified = latest_modification(&self.path);
+
+        loop {
+            thread::sleep(Self::INTERVAL);
+
+            let modified = latest_modification(&self.path);
+            if modified <= last_modified {
+                continue;
+            }
+            last_modified = modified;
+
+            tracing::info!("Source of model changed. Re-evaluating...");
+            let result = self.evaluate();
+            if let Err(err) = &result {
+                tracing::warn!("Failed to re-evaluate model: {err}");
+            }
+            shared.update(result);
+        }
+    }
+
+    fn evaluate(&self) -> Result<Evaluation, String> {
+        // The evaluation is read into memory right away. The directory is
+        // removed again, once it goes out of scope.
+        let dir = tempfile::Builder::new()
+            .prefix("fj-serve-")
+            .tempdir()
+            .map_err(|err| format!("Failed to create directory: {err}"))?;
+        let dir = dir.path();
+
+        let output = Command::new(&self.program)
+            .args(&self.args)
+            .arg("--evaluate-into")
+            .arg(dir)
+            .output()
+            .map_err(|err| format!("Failed to run model: {err}"))?;
+
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).into_owned());
+        }
+
+        Evaluation::read(dir).map_err(|err| {
+            format!("Failed to read evaluation from `{}`: {err}", dir.display())
+        })
+    }
+}
+
+/// Error serving a model
+#[derive(Debug, thiserror::Error)]
+pub enum ServeError {
+    /// Failed to bind to the address
+    #[error("Failed to bind to `{addr}`")]
+    Bind {
+        /// The address
+        addr: SocketAddr,
+
+        /// The error that occurred
+        source: io::Error,
+    },
+
+    /// Failed to accept a connection
+    #[error("Failed to accept connection")]
+    Accept(#[source] io::Error),
+
+    /// Failed to export the mesh, to prepare it for serving
+    #[error("Failed to export mesh")]
+    Export(#[from] crate::export::Error),
+
+    /// Watching the model was requested, but its source is unknown
+    #[error(
+        "Can't watch model, as its source is unknown. Start it with \
+        `cargo run`."
+    )]
+    WatchUnavailable,
+
+    /// Failed to write the results of an evaluation
+    #[error("Failed to write evaluation to `{path}`")]
+    WriteEvaluation {
+        /// The path that couldn't be written
+        path: PathBuf,
+
+        /// The error that occurred
+        source: io::Error,
+    },
+}
+
+/// The formats that the mesh is served in, by their file extension
+const FORMATS: [&str; 4] = ["3mf", "stl", "obj", "glb"];
+
+/// The file that contains the validation errors, when writing an evaluation
+const VALIDATION_FILE: &str = "validation.txt";
+
+/// The result of evaluating the model, ready for serving
+struct Evaluation {
+    meshes: BTreeMap<&'static str, Vec<u8>>,
+
+    /// The validation errors, if any
+    validation: Option<String>,
+}
+
+impl Evaluation {
+    fn new(
+        mesh: &Mesh<Point<3>>,
+        validation: &Result<(), ValidationErrors>,
+    ) -> Result<Self, ServeError> {
+        let meshes = FORMATS
+            .into_iter()
+            .map(|extension| Ok((extension, export(mesh, extension)?)))
+            .collect::<Result<_, ServeError>>()?;
+        let validation = validation.as_ref().err().map(ToString::to_string);
+
+        Ok(Self { meshes, validation })
+    }
+
+    fn read(dir: &Path) -> io::Result<Self> {
+        let meshes = FORMATS
+            .into_iter()
+            .map(|extension| {
+                Ok((
+                    extension,
+                    fs::read(dir.join(format!("mesh.{extension}")))?,
+                ))
+            })
+            .collect::<io::Result<_>>()?;
+        let validation = fs::read_to_string(dir.join(VALIDATION_FILE))?;
+        let validation = (!validation.is_empty()).then_some(validation);
+
+        Ok(Self { meshes, validation })
+    }
+}
+
+/// The state that is shared between the threads of the server
+struct Shared {
+    state: Mutex<State>,
+    updated: Condvar,
+}
+
+impl Shared {
+    fn new(evaluation: Evaluation) -> Self {
+        Self {
+            state: Mutex::new(State {
+                revision: 1,
+                evaluation: Arc::new(evaluation),
+                error: None,
+            }),
+            updated: Condvar::new(),
+        }
+    }
+
+    /// Replace the evaluation, or record that re-evaluating failed
+    fn update(&self, result: Result<Evaluation, String>) {
+        let mut state = self.state.lock().expect("Server thread panicked");
+
+        state.revision += 1;
+        match result {
+            Ok(evaluation) => {
+                state.evaluation = Arc::new(evaluation);
+                state.error = None;
+            }
+            Err(err) => {
+                state.error = Some(err);
+            }
+        }
+
+        self.updated.notify_all();
+    }
+
+    fn current(&self) -> State {
+        self.state.lock().expect("Server thread panicked").clone()
+    }
+
+    /// Wait until the state is newer than the provided revision
+    ///
+    /// Returns `None`, if `closed` is set while waiting. Call [`Shared::wake`]
+    /// after setting it, to make that happen.
+    fn wait_for_update(
+        &self,
+        revision: u64,
+        closed: &AtomicBool,
+    ) -> Option<State> {
+        let state = self.state.lock().expect("Server thread panicked");
+        let state = self
+            .updated
+            .wait_while(state, |state| {
+                state.revision <= revision && !closed.load(Ordering::SeqCst)
+            })
+            .expect("Server thread panicked");
+
+        if closed.load(Ordering::SeqCst) {
+            return None;
+        }
+
+        Some(state.clone())
+    }
+
+    /// Wake all threads that are waiting for an update
+    fn wake(&self) {
+        // Holding the lock makes sure that no thread is between checking its
+        // condition and starting to wait, which would miss the notification.
+        let _state = self.state.lock().expect("Server thread panicked");
+        self.updated.notify_all();
+    }
+}
+
+#[derive(Clone)]
+struct State {
+    revision: u64,
+    evaluation: Arc<Evaluation>,
+
+    /// The error that occurred during the last re-evaluation, if it failed
+    error: Option<String>,
+}
+
+impl State {
+    fn status(&self) -> String {
+        let validation = self.evaluation.validation.as_deref();
+
+        serde_json::json!({
+            "revision": self.revision,
+            "valid": validation.is_none(),
+            "validation": validation.unwrap_or("ok"),
+            "error": self.error,
+        })
+        .to_string()
+    }
+}
+
+fn handle_connection(mut stream: TcpStream, shared: &Shared) -> io::Result<()> {
+    let mut request_line = String::new();
+    let mut headers = BTreeMap::new();
+    {
+        let mut reader = BufReader::new(&mut stream);
+        reader.read_line(&mut request_line)?;
+
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line)?;
+
+            let Some((name, value)) = line.split_once(':') else {
+                break;
+            };
+            headers.insert(
+                name.trim().to_ascii_lowercase(),
+                value.trim().to_string(),
+            );
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next();
+    let path = parts.next();
+
+    if let (Some("GET"), Some("/updates"), Some(key)) =
+        (method, path, headers.get("sec-websocket-key"))
+    {
+        return stream_updates(stream, key, shared);
+    }
+
+    let evaluation = shared.current().evaluation;
+
+    let (status, content_type, body) = match (method, path) {
+        (Some("GET"), Some("/validation")) => {
+            let body = match &evaluation.validation {
+                None => "ok\n".to_string(),
+                Some(err) => err.clone(),
+            };
+            ("200 OK", "text/plain", body.into_bytes())
+        }
+        (Some("GET"), Some(path)) if path.starts_with("/mesh.") => {
+            let extension = path.trim_start_matches("/mesh.");
+            match evaluation
+                .meshes
+                .get(extension.to_ascii_lowercase().as_str())
+            {
+                Some(mesh) => {
+                    ("200 OK", "application/octet-stream", mesh.clone())
+                }
+                None => (
+                    "404 Not Found",
+                    "text/plain",
+                    format!("Unsupported format: `{extension}`\n").into_bytes(),
+                ),
+            }
+        }
+        (Some("GET"), _) => {
+            ("404 Not Found", "text/plain", b"Not found\n".to_vec())
+        }
+        _ => (
+            "405 Method Not Allowed",
+            "text/plain",
+            b"Method not allowed\n".to_vec(),
+        ),
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\n\
+        Content-Type: {content_type}\r\n\
+        Content-Length: {}\r\n\
+        Access-Control-Allow-Origin: *\r\n\
+        Connection: close\r\n\r\n",
+        body.len(),
+    )?;
+    stream.write_all(&body)?;
+    stream.flush()?;
+
+    Ok(())
+}
+
+/// Accept a WebSocket connection, and stream the model to it
+///
+/// Frames from the client are read on a separate thread, which answers pings
+/// and notices when the client goes away. Returns, once it has.
+fn stream_updates(
+    mut stream: TcpStream,
+    key: &str,
+    shared: &Shared,
+) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 101 Switching Protocols\r\n\
+        Upgrade: websocket\r\n\
+        Connection: Upgrade\r\n\
+        Sec-WebSocket-Accept: {}\r\n\r\n",
+        accept_key(key),
+    )?;
+
+    let reader = stream.try_clone()?;
+    let writer = Mutex::new(stream);
+    let closed = AtomicBool::new(false);
+
+    thread::scope(|scope| {
+        scope.spawn(|| {
+            if let Err(err) = read_client_frames(reader, &writer) {
+                tracing::debug!("WebSocket client went away: {err}");
+            }
+
+            closed.store(true, Ordering::SeqCst);
+            shared.wake();
+        });
+
+        let result = write_updates(&writer, &closed, shared);
+
+        // If writing failed, the reader might still be waiting for frames.
+        // Shutting down the connection ends that.
+        let _ = writer
+            .lock()
+            .expect("Reader thread panicked")
+            .shutdown(Shutdown::Both);
+
+        result
+    })
+}
+
+/// Send the model to the client, and again whenever it changes
+///
+/// Returns, once `closed` is set.
+fn write_updates(
+    writer: &Mutex<TcpStream>,
+    closed: &AtomicBool,
+    shared: &Shared,
+) -> io::Result<()> {
+    let mut state = shared.current();
+
+    loop {
+        {
+            let mut stream = writer.lock().expect("Reader thread panicked");
+            write_frame(&mut *stream, Opcode::Text, state.status().as_bytes())?;
+            write_frame(
+                &mut *stream,
+                Opcode::Binary,
+                &state.evaluation.meshes["glb"],
+            )?;
+            stream.flush()?;
+        }
+
+        match shared.wait_for_update(state.revision, closed) {
+            Some(next) => state = next,
+            None => return Ok(()),
+        }
+    }
+}
+
+/// Compute the `Sec-WebSocket-Accept` header from the client's key
+///
+/// See RFC 6455, section 4.2.2.
+fn accept_key(key: &str) -> String {
+    const GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+    base64::engine::general_purpose::STANDARD
+        .encode(Sha1::digest(format!("{key}{GUID}")))
+}
+
+/// Read frames from the client, until it closes the connection
+///
+/// Answers pings with pongs, and a close frame with a close frame. All other
+/// frames are ignored, as the client has nothing to say to the server.
+fn read_client_frames(
+    mut reader: TcpStream,
+    writer: &Mutex<TcpStream>,
+) -> io::Result<()> {
+    loop {
+        let (opcode, payload) = read_frame(&mut reader)?;
+
+        let reply = match opcode {
+            opcode if opcode == Opcode::Close as u8 => Opcode::Close,
+            opcode if opcode == Opcode::Ping as u8 => Opcode::Pong,
+            _ => continue,
+        };
+
+        let mut stream = writer.lock().expect("Writer thread panicked");
+        write_frame(&mut *stream, reply, &payload)?;
+        stream.flush()?;
+
+        if opcode == Opcode::Close as u8 {
+            return Ok(());
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Opcode {
+    Text = 0x1,
+    Binary = 0x2,
+    Close = 0x8,
+    Ping = 0x9,
+    Pong = 0xa,
+}
+
+/// Write an unfragmented WebSocket frame
+///
+/// See RFC 6455, section 5.2. Frames sent by the server are not masked.
+fn write_frame(
+    stream: &mut impl Write,
+    opcode: Opcode,
+    payload: &[u8],
+) -> io::Result<()> {
+    const FIN: u8 = 0x80;
+    stream.write_all(&[FIN | opcode as u8])?;
+
+    match payload.len() {
+        len @ 0..=125 => stream.write_all(&[len as u8])?,
+        len @ 126..=0xffff => {
+            stream.write_all(&[126])?;
+            stream.write_all(&(len as u16).to_be_bytes())?;
+        }
+        len => {
+            stream.write_all(&[127])?;
+            stream.write_all(&(len as u64).to_be_bytes())?;
+        }
+    }
+
+    stream.write_all(payload)
+}
+
+/// Read a WebSocket frame, returning its opcode and unmasked payload
+///
+/// See RFC 6455, section 5.2. Frames sent by the client are masked.
+/// Fragmented messages are not reassembled, as the server only acts on
+/// control frames, which can't be fragmented.
+fn read_frame(stream: &mut impl Read) -> io::Result<(u8, Vec<u8>)> {
+    let mut header = [0; 2];
+    stream.read_exact(&mut header)?;
+
+    let opcode = header[0] & 0x0f;
+    let masked = header[1] & 0x80 != 0;
+
+    let len = match header[1] & 0x7f {
+        126 => {
+            let mut len = [0; 2];
+            stream.read_exact(&mut len)?;
+            u64::from(u16::from_be_bytes(len))
+        }
+        127 => {
+            let mut len = [0; 8];
+            stream.read_exact(&mut len)?;
+            u64::from_be_bytes(len)
+        }
+        len => u64::from(len),
+    };
+
+    let mut mask = [0; 4];
+    if masked {
+        stream.read_exact(&mut mask)?;
+    }
+
+    let mut payload = Vec::new();
+    stream.take(len).read_to_end(&mut payload)?;
+    if payload.len() as u64 != len {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+
+    for (i, byte) in payload.iter_mut().enumerate() {
+        *byte ^= mask[i % 4];
+    }
+
+    Ok((opcode, payload))
+}
+
+/// Find the time of the latest modification within a directory
+///
+/// Skips `target` and hidden directories, as the former changes whenever the
+/// model is built, and the latter usually contain version control data.
+fn latest_modification(path: &Path) -> Option<SystemTime> {
+    let metadata = fs::metadata(path).ok()?;
+    if !metadata.is_dir() {
+        return metadata.modified().ok();
+    }
+
+    fs::read_dir(path)
+        .ok()?
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            name != "target" && !name.starts_with('.')
+        })
+        .filter_map(|entry| latest_modification(&entry.path()))
+        .max()
+}
+
+fn export(
+    mesh: &Mesh<Point<3>>,
+    extension: &str,
+) -> Result<Vec<u8>, crate::export::Error> {
+    let path = PathBuf::from(format!("mesh.{extension}"));
+
+    let mut buf = Cursor::new(Vec::new());
+    crate::export::Format::from_path(&path)?.export(mesh, &mut buf)?;
+
+    Ok(buf.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::{TcpListener, TcpStream},
+        sync::Arc,
+        thread,
+    };
+
+    use fj_interop::{Color, Mesh};
+    use fj_math::Point;
+
+    use super::{
+        accept_key, handle_connection, read_frame, write_frame, Evaluation,
+        Opcode, Shared,
+    };
+
+    #[test]
+    fn websocket_accept_key() {
+        // The example from RFC 6455, section 1.3.
+        assert_eq!(
+            accept_key("dGhlIHNhbXBsZSBub25jZQ=="),
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+    }
+
+    #[test]
+    fn websocket_updates() -> anyhow::Result<()> {
+        let shared = Arc::new(Shared::new(evaluation()?));
+
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let server = thread::spawn({
+            let shared = shared.clone();
+            move || -> std::io::Result<()> {
+                let (stream, _) = listener.accept()?;
+                handle_connection(stream, &shared)
+            }
+        });
+
+        let mut client = TcpStream::connect(addr)?;
+        write!(
+            client,
+            "GET /updates HTTP/1.1\r\n\
+            Upgrade: websocket\r\n\
+            Connection: Upgrade\r\n\
+            Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\
+            Sec-WebSocket-Version: 13\r\n\r\n"
+        )?;
+
+        let mut reader = BufReader::new(client.try_clone()?);
+        let mut response = Vec::new();
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line)?;
+            if line == "\r\n" {
+                break;
+            }
+            response.push(line.trim_end().to_string());
+        }
+        assert_eq!(response[0], "HTTP/1.1 101 Switching Protocols");
+        assert!(response.contains(
+            &"Sec-WebSocket-Accept: s3pPLMBiTxaQ9kYGzzhZRbK+xOo=".into()
+        ));
+
+        // The current model is sent right away.
+        assert_eq!(read_update(&mut reader)?, 1);
+
+        // A changed model is sent, once it's available.
+        shared.update(Ok(evaluation()?));
+        assert_eq!(read_update(&mut reader)?, 2);
+
+        client.write_all(&client_frame(Opcode::Ping, b"ping"))?;
+        assert_eq!(
+            read_frame(&mut reader)?,
+            (Opcode::Pong as u8, b"ping".to_vec())
+        );
+
+        // Once the client closes the connection, the server stops waiting for
+        // updates to send.
+        client.write_all(&client_frame(Opcode::Close, &[]))?;
+        assert_eq!(read_frame(&mut reader)?, (Opcode::Close as u8, vec![]));
+        server.join().expect("Server thread panicked")?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn websocket_frames() -> std::io::Result<()> {
+        for (len, header) in [
+            (5, vec![0x81, 5]),
+            (300, vec![0x81, 126, 0x01, 0x2c]),
+            (70_000, vec![0x81, 127, 0, 0, 0, 0, 0, 0x01, 0x11, 0x70]),
+        ] {
+            let payload = vec![b'a'; len];
+
+            let mut frame = Vec::new();
+            write_frame(&mut frame, Opcode::Text, &payload)?;
+
+            let mut expected = header;
+            expected.write_all(&payload)?;
+            assert_eq!(frame, expected);
+
+            let mut payload_read = Vec::new();
+            (&frame[frame.len() - len..]).read_to_end(&mut payload_read)?;
+            assert_eq!(payload_read, payload);
+        }
+
+        Ok(())
+    }
+
+    fn evaluation() -> anyhow::Result<Evaluation> {
+        let mut mesh = Mesh::new();
+        mesh.push_triangle(
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]].map(Point::from),
+            Color::default(),
+        );
+
+        Ok(Evaluation::new(&mesh, &Ok(()))?)
+    }
+
+    /// Read the status and mesh that the server sends, returning the revision
+    fn read_update(stream: &mut impl Read) -> anyhow::Result<u64> {
+        let (opcode, status) = read_frame(stream)?;
+        assert_eq!(opcode, Opcode::Text as u8);
+        let (opcode, _) = read_frame(stream)?;
+        assert_eq!(opcode, Opcode::Binary as u8);
+
+        let status: serde_json::Value = serde_json::from_slice(&status)?;
+        Ok(status["revision"].as_u64().expect("Expected revision"))
+    }
+
+    /// Build a frame, masked like frames sent by a client
+    fn client_frame(opcode: Opcode, payload: &[u8]) -> Vec<u8> {
+        let mask = [0x12, 0x34, 0x56, 0x78];
+
+        let mut frame = vec![0x80 | opcode as u8, 0x80 | payload.len() as u8];
+        frame.extend(mask);
+        frame.extend(
+            payload
+                .iter()
+                .enumerate()
+                .map(|(i, byte)| byte ^ mask[i % 4]),
+        );
+
+        frame
+    }
+}
@@ -1,14 +1,6 @@
-use fj::{
-    core::{
-        objects::Solid,
-        operations::{
-            build::BuildSolid, merge::Merge, transform::TransformObject,
-        },
-    },
-    math::{Scalar, Vector},
-};
-
-pub fn model(core: &mut fj::core::Core) -> Solid {
+use fj::prelude::*;
+
+pub fn model(core: &mut fj::Core) -> Solid {
     // Just combine all the other models using offsets/rotations that won't
     // result in neat vertex positions or axis-aligned edges/faces. This is
     // useful for testing.
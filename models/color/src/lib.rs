@@ -1,11 +1,6 @@
-use fj::core::{
-    objects::Solid,
-    operations::{
-        presentation::SetColor, split::SplitFace, update::UpdateSolid,
-    },
-};
+use fj::prelude::*;
 
-pub fn model(core: &mut fj::core::Core) -> Solid {
+pub fn model(core: &mut fj::Core) -> Solid {
     let size = 1.;
     let cuboid = cuboid::model([size, size, size], core);
 
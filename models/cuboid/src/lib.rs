@@ -1,16 +1,6 @@
-use fj::{
-    core::{
-        objects::{Region, Sketch, Solid},
-        operations::{
-            build::{BuildRegion, BuildSketch},
-            sweep::SweepSketch,
-            update::UpdateSketch,
-        },
-    },
-    math::{Scalar, Vector},
-};
+use fj::prelude::*;
 
-pub fn model(size: impl Into<Vector<3>>, core: &mut fj::core::Core) -> Solid {
+pub fn model(size: impl Into<Vector<3>>, core: &mut fj::Core) -> Solid {
     let [x, y, z] = size.into().components;
 
     let bottom_surface = core.layers.objects.surfaces.xy_plane();
@@ -0,0 +1,59 @@
+use fj::{core::operations::holes::HoleLocation, prelude::*};
+
+/// A round enclosure, made up of a body and a lid
+///
+/// The lid is a cap that is pushed over the rim of the body. Its inner radius
+/// is smaller than the body's outer radius by `interference`, so it stays on by
+/// friction. Both parts are placed next to each other, ready for printing.
+pub fn model(
+    radius: f64,
+    height: f64,
+    wall: f64,
+    lid_height: f64,
+    interference: f64,
+    core: &mut fj::Core,
+) -> Solid {
+    let body = cup(radius, height, wall, core);
+
+    // Holes can only be started from faces that face away from their surface's
+    // normal, which means the cup opens downward. Flip the body, so it opens
+    // upward.
+    let body = body
+        .rotate([Scalar::PI, Scalar::ZERO, Scalar::ZERO], core)
+        .translate([0., 0., height], core);
+
+    let lid_inner_radius = radius - interference;
+    let lid_radius = lid_inner_radius + wall;
+    let lid = cup(lid_radius, lid_height, wall, core)
+        .translate([radius + lid_radius + wall * 2., 0., 0.], core);
+
+    body.merge(&lid, core)
+}
+
+/// A cylinder with a blind hole that starts at its bottom
+fn cup(radius: f64, height: f64, wall: f64, core: &mut fj::Core) -> Solid {
+    let bottom_surface = core.layers.objects.surfaces.xy_plane();
+    let sweep_path = Vector::from([0., 0., height]);
+
+    let cylinder = Sketch::empty()
+        .add_regions([Region::circle(Point::origin(), radius, core)], core)
+        .sweep_sketch(bottom_surface, sweep_path, core);
+
+    cylinder.update_shell(
+        cylinder.shells().only(),
+        |shell, core| {
+            let bottom_face = shell.faces().first();
+
+            [shell.add_blind_hole(
+                HoleLocation {
+                    face: bottom_face,
+                    position: Point::origin(),
+                },
+                radius - wall,
+                [0., 0., height - wall],
+                core,
+            )]
+        },
+        core,
+    )
+}
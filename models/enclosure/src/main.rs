@@ -0,0 +1,6 @@
+fn main() -> fj::Result {
+    let mut fj = fj::Instance::new();
+    let model = enclosure::model(30., 40., 2., 10., 0.1, &mut fj.core);
+    fj.process_model(&model)?;
+    Ok(())
+}
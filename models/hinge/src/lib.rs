@@ -0,0 +1,87 @@
+use fj::prelude::*;
+
+/// A butt hinge, made up of two leaves
+///
+/// Each leaf is a plate with a knuckle on one side, which has a hole for the
+/// pin. The leaves are arranged end to end, with their knuckles on a common
+/// axis (the y-axis), separated by a small gap.
+pub fn model(
+    leaf_width: f64,
+    leaf_length: f64,
+    thickness: f64,
+    knuckle_radius: f64,
+    pin_radius: f64,
+    gap: f64,
+    core: &mut fj::Core,
+) -> Solid {
+    let leaf = |core: &mut fj::Core| {
+        leaf(
+            leaf_width,
+            leaf_length,
+            thickness,
+            knuckle_radius,
+            pin_radius,
+            core,
+        )
+    };
+
+    let a = leaf(core);
+    let b = leaf(core)
+        .rotate([Scalar::ZERO, Scalar::PI, Scalar::ZERO], core)
+        .translate([0., leaf_length + gap, 0.], core);
+
+    a.merge(&b, core)
+}
+
+fn leaf(
+    width: f64,
+    length: f64,
+    thickness: f64,
+    knuckle_radius: f64,
+    pin_radius: f64,
+    core: &mut fj::Core,
+) -> Solid {
+    // The profile of the leaf is defined in the xz-plane. The plate is
+    // centered on the x-axis and ends where it meets the knuckle, which is
+    // centered on the origin.
+    let half_thickness = thickness / 2.;
+    let knuckle_angle = (half_thickness / knuckle_radius).asin();
+    let plate_start = knuckle_radius * knuckle_angle.cos();
+
+    let [a, b, c, d] = [
+        [plate_start, -half_thickness],
+        [width, -half_thickness],
+        [width, half_thickness],
+        [plate_start, half_thickness],
+    ];
+
+    let profile = Region::empty(core)
+        .update_exterior(
+            |_, core| {
+                let edges = [[a, b], [b, c], [c, d]]
+                    .map(|points| HalfEdge::line_segment(points, None, core))
+                    .into_iter()
+                    .chain([HalfEdge::arc(
+                        d,
+                        a,
+                        Scalar::TAU - knuckle_angle * 2.,
+                        core,
+                    )])
+                    .map(|half_edge| half_edge.insert(core))
+                    .collect::<Vec<_>>();
+
+                Cycle::empty().add_half_edges(edges, core)
+            },
+            core,
+        )
+        .add_interiors(
+            [Cycle::circle(Point::origin(), pin_radius, core).reverse(core)],
+            core,
+        );
+
+    Sketch::empty().add_regions([profile], core).sweep_sketch(
+        core.layers.objects.surfaces.xz_plane(),
+        [0., length, 0.],
+        core,
+    )
+}
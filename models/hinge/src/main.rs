@@ -0,0 +1,6 @@
+fn main() -> fj::Result {
+    let mut fj = fj::Instance::new();
+    let model = hinge::model(20., 30., 2., 3., 1.5, 0.5, &mut fj.core);
+    fj.process_model(&model)?;
+    Ok(())
+}
@@ -1,15 +1,6 @@
-use fj::{
-    core::{
-        objects::Solid,
-        operations::{
-            holes::{AddHole, HoleLocation},
-            update::UpdateSolid,
-        },
-    },
-    math::Scalar,
-};
+use fj::{operations::HoleLocation, prelude::*};
 
-pub fn model(radius: impl Into<Scalar>, core: &mut fj::core::Core) -> Solid {
+pub fn model(radius: impl Into<Scalar>, core: &mut fj::Core) -> Solid {
     let radius = radius.into();
 
     let size = radius * 4.;
@@ -0,0 +1,73 @@
+use fj::{core::operations::holes::HoleLocation, prelude::*};
+
+/// A reducing coupling that connects hoses of two different sizes
+///
+/// The larger hose is pushed into a socket at the bottom. The smaller one goes
+/// into the bore that continues from the socket's floor to the top.
+pub fn model(
+    outer_radius: f64,
+    socket_radius: f64,
+    bore_radius: f64,
+    socket_depth: f64,
+    length: f64,
+    core: &mut fj::Core,
+) -> Solid {
+    let bottom_surface = core.layers.objects.surfaces.xy_plane();
+    let sweep_path = Vector::from([0., 0., length]);
+
+    let cylinder = Sketch::empty()
+        .add_regions(
+            [Region::circle(Point::origin(), outer_radius, core)],
+            core,
+        )
+        .sweep_sketch(bottom_surface, sweep_path, core);
+
+    cylinder.update_shell(
+        cylinder.shells().only(),
+        |shell, core| {
+            let bottom_face = shell.faces().first().clone();
+            let top_face = shell
+                .faces()
+                .nth(2)
+                .expect("Expected cylinder to have top face")
+                .clone();
+
+            // Holes can only be started from faces that face away from their
+            // surface's normal, like the bottom face here. That's why both
+            // holes are started from the bottom.
+            let shell = shell.add_blind_hole(
+                HoleLocation {
+                    face: &bottom_face,
+                    position: Point::origin(),
+                },
+                socket_radius,
+                [0., 0., socket_depth],
+                core,
+            );
+
+            // The blind hole adds its floor as the last face.
+            let socket_floor = shell
+                .faces()
+                .iter()
+                .last()
+                .expect("Expected socket to have floor")
+                .clone();
+
+            [shell.add_through_hole(
+                [
+                    HoleLocation {
+                        face: &socket_floor,
+                        position: Point::origin(),
+                    },
+                    HoleLocation {
+                        face: &top_face,
+                        position: Point::origin(),
+                    },
+                ],
+                bore_radius,
+                core,
+            )]
+        },
+        core,
+    )
+}
@@ -0,0 +1,6 @@
+fn main() -> fj::Result {
+    let mut fj = fj::Instance::new();
+    let model = hose_adapter::model(12., 10., 6., 20., 40., &mut fj.core);
+    fj.process_model(&model)?;
+    Ok(())
+}
@@ -0,0 +1,155 @@
+use fj::{core::operations::holes::HoleLocation, prelude::*};
+
+/// The side length of a NEMA 17 stepper motor's faceplate
+pub const MOTOR_SIZE: f64 = 42.3;
+
+/// The distance between the centers of the motor's mounting holes
+const HOLE_SPACING: f64 = 31.;
+
+/// The radius of the hole for the motor's centering boss
+const PILOT_RADIUS: f64 = 11.5;
+
+/// The radius of clearance holes for M3 screws
+const SCREW_RADIUS: f64 = 1.6;
+
+/// An L-shaped bracket for mounting a NEMA 17 stepper motor
+///
+/// The motor is mounted to a plate with a hole for its centering boss, and
+/// holes for the four screws. A foot extends from one edge of the plate, at a
+/// right angle, for mounting the bracket.
+///
+/// The plate lies on the xy-plane, and the foot points upward, which is how
+/// the bracket would be printed.
+pub fn model(thickness: f64, foot_length: f64, core: &mut fj::Core) -> Solid {
+    let width = MOTOR_SIZE;
+    let length = MOTOR_SIZE + thickness;
+
+    let bottom_surface = core.layers.objects.surfaces.xy_plane();
+    let sweep_path = Vector::from([0., 0., thickness]);
+
+    let plate = Sketch::empty()
+        .add_regions(
+            [Region::polygon(
+                [[0., 0.], [width, 0.], [width, length], [0., length]],
+                core,
+            )],
+            core,
+        )
+        .sweep_sketch(bottom_surface, sweep_path, core);
+
+    plate.update_shell(
+        plate.shells().only(),
+        |shell, core| {
+            let top_face = shell
+                .faces()
+                .nth(5)
+                .expect("Expected plate to have top face")
+                .clone();
+
+            // Split a strip off the top face, along its edge at `y == 0`, and
+            // sweep that upward to form the foot.
+            let line = [0., width].map(|x| {
+                let (half_edge, point) = point_on_edge(&top_face, x, thickness);
+                (half_edge.clone(), point)
+            });
+            let (shell, faces) = shell.split_face(
+                &top_face,
+                [(&line[0].0, line[0].1), (&line[1].0, line[1].1)],
+                core,
+            );
+            let [strip, top_face] = if is_below(&faces[0], thickness) {
+                faces
+            } else {
+                let [a, b] = faces;
+                [b, a]
+            };
+
+            let mut shell =
+                shell.sweep_face_of_shell(strip, [0., 0., foot_length], core);
+
+            // Holes can only be added between faces whose surface coordinates
+            // are not distorted, like the top and bottom faces of the plate.
+            // The top face has been replaced by the split, so we need to look
+            // it up by its position in the shell.
+            let index = shell
+                .faces()
+                .index_of(&top_face)
+                .expect("Expected top face to be part of shell");
+
+            let center = [width / 2., thickness + MOTOR_SIZE / 2.];
+            let offset = HOLE_SPACING / 2.;
+            let holes = [
+                (center, PILOT_RADIUS),
+                ([center[0] - offset, center[1] - offset], SCREW_RADIUS),
+                ([center[0] + offset, center[1] - offset], SCREW_RADIUS),
+                ([center[0] + offset, center[1] + offset], SCREW_RADIUS),
+                ([center[0] - offset, center[1] + offset], SCREW_RADIUS),
+            ];
+
+            for (position, radius) in holes {
+                let bottom_face = shell.faces().first().clone();
+                let top_face = shell
+                    .faces()
+                    .nth(index)
+                    .expect("Expected top face to stay in place")
+                    .clone();
+
+                // Both surfaces are parallel to the xy-plane, with their
+                // origin above or on the global origin.
+                shell = shell.add_through_hole(
+                    [
+                        HoleLocation {
+                            face: &bottom_face,
+                            position: position.into(),
+                        },
+                        HoleLocation {
+                            face: &top_face,
+                            position: position.into(),
+                        },
+                    ],
+                    radius,
+                    core,
+                );
+            }
+
+            [shell]
+        },
+        core,
+    )
+}
+
+/// Find the point on the face's exterior, where it crosses `y` at `x`
+///
+/// Only considers the edges of the exterior that are parallel to the y-axis.
+/// Returns the half-edge, and the point in its curve coordinates.
+fn point_on_edge(face: &Face, x: f64, y: f64) -> (&Handle<HalfEdge>, Point<1>) {
+    let [x, y] = [x, y].map(Scalar::from);
+
+    face.region()
+        .exterior()
+        .half_edges()
+        .pairs()
+        .find_map(|(half_edge, next)| {
+            let [start, end] =
+                [half_edge, next].map(|edge| edge.start_position());
+
+            if start.u != x || end.u != x {
+                return None;
+            }
+
+            let t = (y - start.v) / (end.v - start.v);
+            let [a, b] = half_edge.boundary().inner;
+
+            Some((half_edge, a + (b - a) * t))
+        })
+        .expect("Expected face to have edge at `x`")
+}
+
+/// Determine whether all of the face's exterior is below `y`
+fn is_below(face: &Face, y: f64) -> bool {
+    face.region()
+        .exterior()
+        .half_edges()
+        .iter()
+        .all(|half_edge| half_edge.start_position().v <= Scalar::from(y))
+}
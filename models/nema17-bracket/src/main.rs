@@ -0,0 +1,6 @@
+fn main() -> fj::Result {
+    let mut fj = fj::Instance::new();
+    let model = nema17_bracket::model(4., 30., &mut fj.core);
+    fj.process_model(&model)?;
+    Ok(())
+}
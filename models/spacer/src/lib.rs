@@ -1,21 +1,10 @@
-use fj::{
-    core::{
-        objects::{Cycle, Region, Sketch, Solid},
-        operations::{
-            build::{BuildCycle, BuildRegion, BuildSketch},
-            reverse::Reverse,
-            sweep::SweepSketch,
-            update::{UpdateRegion, UpdateSketch},
-        },
-    },
-    math::{Point, Vector},
-};
+use fj::prelude::*;
 
 pub fn model(
     outer: f64,
     inner: f64,
     height: f64,
-    core: &mut fj::core::Core,
+    core: &mut fj::Core,
 ) -> Solid {
     let bottom_surface = core.layers.objects.surfaces.xy_plane();
     let sweep_path = Vector::from([0., 0., height]);
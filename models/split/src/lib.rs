@@ -1,11 +1,6 @@
-use fj::core::{
-    objects::Solid,
-    operations::{
-        split::SplitFace, sweep::SweepFaceOfShell, update::UpdateSolid,
-    },
-};
+use fj::prelude::*;
 
-pub fn model(size: f64, split_pos: f64, core: &mut fj::core::Core) -> Solid {
+pub fn model(size: f64, split_pos: f64, core: &mut fj::Core) -> Solid {
     let cuboid = cuboid::model([size, size, size], core);
 
     cuboid.update_shell(
@@ -1,24 +1,13 @@
 use std::f64::consts::PI;
 
-use fj::{
-    core::{
-        objects::{Cycle, Region, Sketch, Solid},
-        operations::{
-            build::{BuildCycle, BuildRegion, BuildSketch},
-            reverse::Reverse,
-            sweep::SweepSketch,
-            update::{UpdateRegion, UpdateSketch},
-        },
-    },
-    math::Vector,
-};
+use fj::prelude::*;
 
 pub fn model(
     num_points: u64,
     r1: f64,
     r2: f64,
     h: f64,
-    core: &mut fj::core::Core,
+    core: &mut fj::Core,
 ) -> Solid {
     let num_vertices = num_points * 2;
     let vertex_iter = (0..num_vertices).map(|i| {
@@ -1,12 +1,6 @@
-use fj::core::{
-    objects::{Shell, Solid},
-    operations::{
-        build::{BuildShell, BuildSolid},
-        update::UpdateSolid,
-    },
-};
+use fj::prelude::*;
 
-pub fn model(core: &mut fj::core::Core) -> Solid {
+pub fn model(core: &mut fj::Core) -> Solid {
     Solid::empty().add_shells(
         [Shell::from_vertices_and_indices(
             [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]],
@@ -27,6 +27,7 @@ fn main() -> anyhow::Result<()> {
                 "fj-interop",
                 "fj-math",
                 "fj-viewer",
+                "fj-window",
             ],
         },
     ];
@@ -0,0 +1,134 @@
+//! # Model Tester
+//!
+//! Builds the example models and checks that they result in valid, watertight
+//! shapes. See [`check_model`].
+
+use anyhow::{anyhow, bail};
+use fj::{
+    core::{
+        algorithms::{
+            approx::Tolerance, bounding_volume::BoundingVolume,
+            triangulate::Triangulate,
+        },
+        objects::Solid,
+        operations::insert::Insert,
+        Core,
+    },
+    math::Scalar,
+};
+
+/// The tolerances that each model is triangulated with
+///
+/// These are relative to the smallest extent of the model's bounding box.
+pub const RELATIVE_TOLERANCES: [f64; 2] = [100., 1000.];
+
+/// Build a model and check the result
+///
+/// The model is built using a fresh instance of [`Core`] and must not trigger
+/// any validation errors. Afterwards, it is triangulated with each of the
+/// [`RELATIVE_TOLERANCES`]. Each of the resulting meshes must be watertight and
+/// enclose a positive volume.
+pub fn check_model(
+    model: impl FnOnce(&mut Core) -> Solid,
+) -> anyhow::Result<()> {
+    let mut core = Core::new();
+
+    let solid = model(&mut core).insert(&mut core);
+    core.layers.validation.take_errors()?;
+
+    let aabb = solid
+        .aabb()
+        .ok_or_else(|| anyhow!("Model has no bounding box"))?;
+
+    let mut min_extent = Scalar::MAX;
+    for extent in aabb.size().components {
+        if extent > Scalar::ZERO && extent < min_extent {
+            min_extent = extent;
+        }
+    }
+
+    for relative_tolerance in RELATIVE_TOLERANCES {
+        let tolerance =
+            Tolerance::from_scalar(min_extent / relative_tolerance)?;
+        let mesh = (&*solid, tolerance).triangulate(&mut core);
+
+        if let Err(mismatch) = fj_test_utils::check_watertight(&mesh) {
+            bail!("Mesh for tolerance {tolerance:?}: {mismatch}");
+        }
+
+        let volume = mesh.volume();
+        if volume <= Scalar::ZERO {
+            bail!(
+                "Mesh for tolerance {tolerance:?} doesn't enclose a positive \
+                volume: {volume}"
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::check_model;
+
+    #[test]
+    fn all() -> anyhow::Result<()> {
+        check_model(all::model)
+    }
+
+    #[test]
+    fn color() -> anyhow::Result<()> {
+        check_model(color::model)
+    }
+
+    #[test]
+    fn cuboid() -> anyhow::Result<()> {
+        check_model(|core| cuboid::model([3., 2., 1.], core))
+    }
+
+    #[test]
+    fn enclosure() -> anyhow::Result<()> {
+        check_model(|core| enclosure::model(30., 40., 2., 10., 0.1, core))
+    }
+
+    #[test]
+    fn hinge() -> anyhow::Result<()> {
+        check_model(|core| hinge::model(20., 30., 2., 3., 1.5, 0.5, core))
+    }
+
+    #[test]
+    fn holes() -> anyhow::Result<()> {
+        check_model(|core| holes::model(0.25, core))
+    }
+
+    #[test]
+    fn hose_adapter() -> anyhow::Result<()> {
+        check_model(|core| hose_adapter::model(12., 10., 6., 20., 40., core))
+    }
+
+    #[test]
+    fn nema17_bracket() -> anyhow::Result<()> {
+        check_model(|core| nema17_bracket::model(4., 30., core))
+    }
+
+    #[test]
+    fn spacer() -> anyhow::Result<()> {
+        check_model(|core| spacer::model(1., 0.5, 1., core))
+    }
+
+    #[test]
+    fn split() -> anyhow::Result<()> {
+        check_model(|core| split::model(1.0, 0.2, core))
+    }
+
+    #[test]
+    fn star() -> anyhow::Result<()> {
+        check_model(|core| star::model(5, 1., 2., 1., core))
+    }
+
+    #[test]
+    fn vertices_indices() -> anyhow::Result<()> {
+        check_model(vertices_indices::model)
+    }
+}